@@ -8,9 +8,9 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::domain::{
-    ContentGenerationType, ContentReference, DifficultyLevel,
-    EmbeddingContentType, GenerationStatus, MessageRole,
-    QuestionOption, QuestionType, TutorSessionStatus,
+    AIFeature, ContentGenerationType, ContentReference, DifficultyLevel,
+    EmbeddingContentType, GenerationStatus, MessageRole, PlanTier,
+    QuestionOption, QuestionType, TutorSessionStatus, UsageQuota,
 };
 
 // =============================================================================
@@ -197,6 +197,10 @@ pub struct IndexCourseRequest {
 pub struct IndexingResponse {
     pub course_id: Uuid,
     pub chunks_indexed: i32,
+    pub sources_indexed: i32,
+    pub sources_unchanged: i32,
+    pub sources_removed: i32,
+    pub sources_failed: i32,
     pub status: String,
 }
 
@@ -365,13 +369,39 @@ impl From<QuestionOption> for QuestionOptionResponse {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageQuotaResponse {
+    pub plan: PlanTier,
     pub tutor_chat: FeatureQuotaResponse,
     pub semantic_search: FeatureQuotaResponse,
     pub summary_generation: FeatureQuotaResponse,
     pub quiz_generation: FeatureQuotaResponse,
 }
 
-#[derive(Debug, Serialize)]
+impl UsageQuotaResponse {
+    pub fn new(plan: PlanTier, quotas: Vec<UsageQuota>) -> Self {
+        let mut response = Self {
+            plan,
+            tutor_chat: FeatureQuotaResponse::default(),
+            semantic_search: FeatureQuotaResponse::default(),
+            summary_generation: FeatureQuotaResponse::default(),
+            quiz_generation: FeatureQuotaResponse::default(),
+        };
+
+        for quota in quotas {
+            let slot = match quota.feature {
+                AIFeature::TutorChat => &mut response.tutor_chat,
+                AIFeature::SemanticSearch => &mut response.semantic_search,
+                AIFeature::SummaryGeneration => &mut response.summary_generation,
+                AIFeature::QuizGeneration => &mut response.quiz_generation,
+                AIFeature::TranscriptionGeneration => continue,
+            };
+            *slot = quota.into();
+        }
+
+        response
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FeatureQuotaResponse {
     pub daily_limit: i32,
@@ -380,10 +410,29 @@ pub struct FeatureQuotaResponse {
     pub monthly_limit: i32,
     pub monthly_used: i32,
     pub monthly_remaining: i32,
+    pub tokens_today: i64,
+    pub tokens_this_month: i64,
     pub reset_daily_at: DateTime<Utc>,
     pub reset_monthly_at: DateTime<Utc>,
 }
 
+impl From<UsageQuota> for FeatureQuotaResponse {
+    fn from(quota: UsageQuota) -> Self {
+        Self {
+            daily_limit: quota.daily_limit,
+            daily_used: quota.used_today,
+            daily_remaining: (quota.daily_limit - quota.used_today).max(0),
+            monthly_limit: quota.monthly_limit,
+            monthly_used: quota.used_this_month,
+            monthly_remaining: (quota.monthly_limit - quota.used_this_month).max(0),
+            tokens_today: quota.tokens_today,
+            tokens_this_month: quota.tokens_this_month,
+            reset_daily_at: quota.reset_daily_at,
+            reset_monthly_at: quota.reset_monthly_at,
+        }
+    }
+}
+
 // =============================================================================
 // HEALTH CHECK
 // =============================================================================
@@ -12,7 +12,7 @@ use crate::api::{
     IndexCourseRequest, HealthResponse, TutorSessionResponse, TutorMessageResponse,
     SemanticSearchResultResponse, ContentGenerationResponse, QuizGenerationResponse,
     GeneratedQuestionsResponse, SummaryResponse, KeyPointsResponse,
-    GlossaryResponse, IndexingResponse,
+    GlossaryResponse, IndexingResponse, UsageQuotaResponse,
 };
 use crate::domain::AIError;
 
@@ -54,7 +54,12 @@ fn error_response(error: AIError) -> HttpResponse {
         _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
     };
 
-    HttpResponse::build(status).json(ApiResponse::<()>::error(
+    let mut response = HttpResponse::build(status);
+    if let Some(seconds) = error.retry_after_secs() {
+        response.insert_header((actix_web::http::header::RETRY_AFTER, seconds.to_string()));
+    }
+
+    response.json(ApiResponse::<()>::error(
         error.error_code(),
         &error.to_string(),
     ))
@@ -278,11 +283,15 @@ pub async fn index_course_content(
     let force_reindex = body.force_reindex.unwrap_or(false);
 
     match state.search_service.index_course(course_id, force_reindex).await {
-        Ok(chunks_indexed) => {
+        Ok(report) => {
             HttpResponse::Ok().json(ApiResponse::success(IndexingResponse {
                 course_id,
-                chunks_indexed,
-                status: "completed".to_string(),
+                chunks_indexed: report.chunks_indexed,
+                sources_indexed: report.sources_indexed,
+                sources_unchanged: report.sources_unchanged,
+                sources_removed: report.sources_removed,
+                sources_failed: report.sources_failed,
+                status: if report.sources_failed > 0 { "partial" } else { "completed" }.to_string(),
             }))
         }
         Err(e) => error_response(e),
@@ -299,13 +308,13 @@ pub async fn generate_summary(
     state: web::Data<AppState>,
     body: web::Json<GenerateContentRequest>,
 ) -> HttpResponse {
-    let _user_id = match extract_user_id(&req) {
+    let user_id = match extract_user_id(&req) {
         Ok(id) => id,
         Err(e) => return error_response(e),
     };
     let language = body.language.clone().unwrap_or_else(|| "es".to_string());
 
-    match state.summary_service.generate_summary(body.course_id, body.lesson_id, &language).await {
+    match state.summary_service.generate_summary(user_id, body.course_id, body.lesson_id, &language).await {
         Ok(summary) => {
             HttpResponse::Ok().json(ApiResponse::success(SummaryResponse {
                 summary_id: summary.summary_id,
@@ -325,13 +334,13 @@ pub async fn generate_key_points(
     state: web::Data<AppState>,
     body: web::Json<GenerateContentRequest>,
 ) -> HttpResponse {
-    let _user_id = match extract_user_id(&req) {
+    let user_id = match extract_user_id(&req) {
         Ok(id) => id,
         Err(e) => return error_response(e),
     };
     let language = body.language.clone().unwrap_or_else(|| "es".to_string());
 
-    match state.summary_service.generate_key_points(body.course_id, body.lesson_id, &language).await {
+    match state.summary_service.generate_key_points(user_id, body.course_id, body.lesson_id, &language).await {
         Ok(key_points) => {
             HttpResponse::Ok().json(ApiResponse::success(KeyPointsResponse {
                 lesson_id: body.lesson_id,
@@ -354,13 +363,13 @@ pub async fn generate_glossary(
     state: web::Data<AppState>,
     body: web::Json<GenerateContentRequest>,
 ) -> HttpResponse {
-    let _user_id = match extract_user_id(&req) {
+    let user_id = match extract_user_id(&req) {
         Ok(id) => id,
         Err(e) => return error_response(e),
     };
     let language = body.language.clone().unwrap_or_else(|| "es".to_string());
 
-    match state.summary_service.generate_glossary(body.course_id, body.lesson_id, &language).await {
+    match state.summary_service.generate_glossary(user_id, body.course_id, body.lesson_id, &language).await {
         Ok(terms) => {
             HttpResponse::Ok().json(ApiResponse::success(GlossaryResponse {
                 lesson_id: body.lesson_id,
@@ -539,8 +548,10 @@ pub async fn get_usage_quota(
         Err(e) => return error_response(e),
     };
 
-    match state.tutor_service.get_usage_quota(user_id).await {
-        Ok(quota) => HttpResponse::Ok().json(ApiResponse::success(quota)),
+    match state.usage_meter.get_quotas(user_id).await {
+        Ok((plan, quotas)) => {
+            HttpResponse::Ok().json(ApiResponse::success(UsageQuotaResponse::new(plan, quotas)))
+        }
         Err(e) => error_response(e),
     }
 }
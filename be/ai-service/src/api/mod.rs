@@ -9,7 +9,9 @@ pub use dto::*;
 pub use handlers::*;
 
 use std::sync::Arc;
-use crate::service::{TutorService, SemanticSearchService, SummaryService, QuizGeneratorService};
+use crate::service::{
+    TutorService, SemanticSearchService, SummaryService, QuizGeneratorService, UsageMeter,
+};
 
 /// Application state shared across handlers.
 pub struct AppState {
@@ -17,6 +19,7 @@ pub struct AppState {
     pub search_service: Arc<SemanticSearchService>,
     pub summary_service: Arc<SummaryService>,
     pub quiz_generator_service: Arc<QuizGeneratorService>,
+    pub usage_meter: Arc<UsageMeter>,
 }
 
 /// Configures all routes for the AI service.
@@ -189,6 +189,21 @@ pub struct SemanticSearchResult {
     pub has_access: bool,
 }
 
+/// Outcome of indexing a course.
+///
+/// Sources are the course lessons plus one course-level source
+/// (description and KB articles).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexingReport {
+    pub course_id: Uuid,
+    pub chunks_indexed: i32,
+    pub sources_indexed: i32,
+    pub sources_unchanged: i32,
+    pub sources_removed: i32,
+    /// Sources kept as previously indexed because some content could not be fetched.
+    pub sources_failed: i32,
+}
+
 // =============================================================================
 // CONTENT GENERATION ENTITIES
 // =============================================================================
@@ -305,7 +320,7 @@ pub struct AIUsage {
 }
 
 /// AI features for usage tracking.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AIFeature {
     TutorChat,
@@ -315,6 +330,101 @@ pub enum AIFeature {
     TranscriptionGeneration,
 }
 
+impl AIFeature {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TutorChat => "tutor_chat",
+            Self::SemanticSearch => "semantic_search",
+            Self::SummaryGeneration => "summary_generation",
+            Self::QuizGeneration => "quiz_generation",
+            Self::TranscriptionGeneration => "transcription_generation",
+        }
+    }
+}
+
+impl std::fmt::Display for AIFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Subscription plan tier (`subscriptions.plan_tier`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanTier {
+    /// Users without an active subscription.
+    #[default]
+    Free,
+    Basic,
+    Professional,
+    Enterprise,
+    Custom,
+}
+
+impl std::str::FromStr for PlanTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "free" => Ok(Self::Free),
+            "basic" => Ok(Self::Basic),
+            "professional" => Ok(Self::Professional),
+            "enterprise" => Ok(Self::Enterprise),
+            "custom" => Ok(Self::Custom),
+            other => Err(format!("Unknown plan tier: {}", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for PlanTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Free => write!(f, "free"),
+            Self::Basic => write!(f, "basic"),
+            Self::Professional => write!(f, "professional"),
+            Self::Enterprise => write!(f, "enterprise"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+/// Request limits of a feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureLimits {
+    pub daily: i32,
+    pub monthly: i32,
+}
+
+/// A user's plan with the AI quotas it overrides (`limits.ai_quotas`).
+#[derive(Debug, Clone, Default)]
+pub struct UserPlan {
+    pub tier: PlanTier,
+    pub ai_quotas: std::collections::HashMap<AIFeature, FeatureLimits>,
+}
+
+/// Tokens consumed by an LLM call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenUsage {
+    pub input: i32,
+    pub output: i32,
+}
+
+impl TokenUsage {
+    /// Splits a provider-reported total using the estimated prompt size
+    /// (providers only report totals through `LLMClient`).
+    pub fn from_total(estimated_input: i32, total: i32) -> Self {
+        let input = estimated_input.clamp(0, total.max(0));
+        Self {
+            input,
+            output: total.max(0) - input,
+        }
+    }
+
+    pub fn total(&self) -> i32 {
+        self.input + self.output
+    }
+}
+
 /// User's AI usage quota.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageQuota {
@@ -324,6 +434,8 @@ pub struct UsageQuota {
     pub monthly_limit: i32,
     pub used_today: i32,
     pub used_this_month: i32,
+    pub tokens_today: i64,
+    pub tokens_this_month: i64,
     pub reset_daily_at: DateTime<Utc>,
     pub reset_monthly_at: DateTime<Utc>,
 }
@@ -2,8 +2,11 @@
 //!
 //! Error types for the AI service domain.
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::domain::AIFeature;
+
 /// Errors that can occur in AI operations.
 #[derive(Debug, thiserror::Error)]
 pub enum AIError {
@@ -51,11 +54,19 @@ pub enum AIError {
     QuizGenerationFailed(String),
 
     // Usage/Quota Errors
-    #[error("Daily usage limit exceeded for {feature}")]
-    DailyLimitExceeded { feature: String },
-
-    #[error("Monthly usage limit exceeded for {feature}")]
-    MonthlyLimitExceeded { feature: String },
+    #[error("Daily usage limit of {limit} requests exceeded for {feature}")]
+    DailyLimitExceeded {
+        feature: AIFeature,
+        limit: i32,
+        resets_at: DateTime<Utc>,
+    },
+
+    #[error("Monthly usage limit of {limit} requests exceeded for {feature}")]
+    MonthlyLimitExceeded {
+        feature: AIFeature,
+        limit: i32,
+        resets_at: DateTime<Utc>,
+    },
 
     // Access Errors
     #[error("User not enrolled in course: {0}")]
@@ -116,6 +127,17 @@ impl AIError {
         }
     }
 
+    /// Seconds until a quota-exceeded request can be retried.
+    pub fn retry_after_secs(&self) -> Option<i64> {
+        match self {
+            Self::DailyLimitExceeded { resets_at, .. }
+            | Self::MonthlyLimitExceeded { resets_at, .. } => {
+                Some((*resets_at - Utc::now()).num_seconds().max(1))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the error code for API responses.
     pub fn error_code(&self) -> &'static str {
         match self {
@@ -0,0 +1,177 @@
+//! # LLM Configuration
+//!
+//! Provider selection from environment variables:
+//!
+//! | Variable | Default | Description |
+//! |----------|---------|-------------|
+//! | `LLM_PROVIDER` | `openai` | `openai`, `openai_compatible` or `mock` |
+//! | `LLM_BASE_URL` | - | API root for `openai_compatible` (e.g. `http://ollama:11434/v1`) |
+//! | `LLM_API_KEY` | `OPENAI_API_KEY` | API key (optional for `openai_compatible`) |
+//! | `LLM_CHAT_MODEL` | provider default | Chat model |
+//! | `LLM_EMBEDDING_MODEL` | provider default | Embedding model |
+//! | `LLM_EMBEDDING_DIMENSIONS` | `1536` | Vector size produced by `mock` |
+//! | `LLM_TIMEOUT_SECS` | `120` | Request timeout for `openai_compatible` |
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::llm::{
+    LLMClient, MockLLMClient, OpenAIClient, OpenAICompatibleClient,
+    DEFAULT_EMBEDDING_DIMENSIONS,
+};
+
+const DEFAULT_COMPATIBLE_CHAT_MODEL: &str = "llama3.1";
+const DEFAULT_COMPATIBLE_EMBEDDING_MODEL: &str = "nomic-embed-text";
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// Available LLM providers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LLMProvider {
+    /// OpenAI API.
+    OpenAI,
+    /// Self-hosted OpenAI-compatible endpoint (vLLM, Ollama, ...).
+    OpenAICompatible,
+    /// Deterministic in-process provider (CI, air-gapped installs).
+    Mock,
+}
+
+impl FromStr for LLMProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAI),
+            "openai_compatible" | "vllm" | "ollama" => Ok(Self::OpenAICompatible),
+            "mock" => Ok(Self::Mock),
+            other => Err(format!("Unknown LLM provider: {}", other)),
+        }
+    }
+}
+
+/// LLM provider configuration.
+#[derive(Debug, Clone)]
+pub struct LLMConfig {
+    pub provider: LLMProvider,
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    pub chat_model: Option<String>,
+    pub embedding_model: Option<String>,
+    pub embedding_dimensions: usize,
+    pub timeout: Duration,
+}
+
+impl Default for LLMConfig {
+    fn default() -> Self {
+        Self {
+            provider: LLMProvider::OpenAI,
+            base_url: None,
+            api_key: None,
+            chat_model: None,
+            embedding_model: None,
+            embedding_dimensions: DEFAULT_EMBEDDING_DIMENSIONS,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl LLMConfig {
+    /// Loads the configuration from environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let provider = match var("LLM_PROVIDER") {
+            Some(value) => value.parse()?,
+            None => LLMProvider::OpenAI,
+        };
+
+        let embedding_dimensions = match var("LLM_EMBEDDING_DIMENSIONS") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| "LLM_EMBEDDING_DIMENSIONS must be a valid number".to_string())?,
+            None => DEFAULT_EMBEDDING_DIMENSIONS,
+        };
+
+        let timeout = match var("LLM_TIMEOUT_SECS") {
+            Some(value) => Duration::from_secs(
+                value
+                    .parse()
+                    .map_err(|_| "LLM_TIMEOUT_SECS must be a valid number".to_string())?,
+            ),
+            None => Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        };
+
+        Ok(Self {
+            provider,
+            base_url: var("LLM_BASE_URL"),
+            api_key: var("LLM_API_KEY").or_else(|| var("OPENAI_API_KEY")),
+            chat_model: var("LLM_CHAT_MODEL"),
+            embedding_model: var("LLM_EMBEDDING_MODEL"),
+            embedding_dimensions,
+            timeout,
+        })
+    }
+
+    /// Creates the client for the configured provider.
+    pub fn build_client(&self) -> Result<Arc<dyn LLMClient>, String> {
+        match self.provider {
+            LLMProvider::OpenAI => {
+                let api_key = self.api_key.as_deref()
+                    .ok_or("OPENAI_API_KEY (or LLM_API_KEY) must be set for the openai provider")?;
+
+                let mut client = OpenAIClient::new(api_key);
+                if let (Some(chat), Some(embedding)) = (&self.chat_model, &self.embedding_model) {
+                    client = client.with_models(chat, embedding);
+                }
+                Ok(Arc::new(client))
+            }
+            LLMProvider::OpenAICompatible => {
+                let base_url = self.base_url.as_deref()
+                    .ok_or("LLM_BASE_URL must be set for the openai_compatible provider")?;
+
+                let mut client = OpenAICompatibleClient::new(
+                    base_url,
+                    self.chat_model.as_deref().unwrap_or(DEFAULT_COMPATIBLE_CHAT_MODEL),
+                    self.embedding_model.as_deref().unwrap_or(DEFAULT_COMPATIBLE_EMBEDDING_MODEL),
+                    self.timeout,
+                );
+                if let Some(api_key) = &self.api_key {
+                    client = client.with_api_key(api_key);
+                }
+                Ok(Arc::new(client))
+            }
+            LLMProvider::Mock => Ok(Arc::new(MockLLMClient::new(self.embedding_dimensions))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!("openai".parse::<LLMProvider>(), Ok(LLMProvider::OpenAI));
+        assert_eq!("Ollama".parse::<LLMProvider>(), Ok(LLMProvider::OpenAICompatible));
+        assert_eq!(" mock ".parse::<LLMProvider>(), Ok(LLMProvider::Mock));
+        assert!("anthropic".parse::<LLMProvider>().is_err());
+    }
+
+    #[test]
+    fn test_build_client_requires_provider_settings() {
+        let openai = LLMConfig::default();
+        assert!(openai.build_client().is_err());
+
+        let compatible = LLMConfig {
+            provider: LLMProvider::OpenAICompatible,
+            ..Default::default()
+        };
+        assert!(compatible.build_client().is_err());
+
+        let mock = LLMConfig {
+            provider: LLMProvider::Mock,
+            ..Default::default()
+        };
+        assert!(mock.build_client().is_ok());
+    }
+}
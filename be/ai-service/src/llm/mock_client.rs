@@ -0,0 +1,378 @@
+//! # Mock Client
+//!
+//! Deterministic in-process provider for CI and air-gapped installs.
+//! Builds answers from the given content without any model: the same input
+//! always yields the same output.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    GeneratedQuestion, GlossaryTerm, KeyPoint, QuestionOption, QuestionType,
+    QuizGenerationConfig, TutorMessage,
+};
+use crate::llm::LLMClient;
+
+/// Dimension of `ai.embeddings.embedding` (vector(1536)).
+pub const DEFAULT_EMBEDDING_DIMENSIONS: usize = 1536;
+
+const SUMMARY_SENTENCES: usize = 3;
+const MAX_KEY_POINTS: usize = 5;
+const MAX_GLOSSARY_TERMS: usize = 10;
+const OPTION_IDS: [&str; 4] = ["a", "b", "c", "d"];
+
+/// Deterministic LLM provider.
+pub struct MockLLMClient {
+    embedding_dimensions: usize,
+}
+
+impl MockLLMClient {
+    pub fn new(embedding_dimensions: usize) -> Self {
+        Self {
+            embedding_dimensions: embedding_dimensions.max(1),
+        }
+    }
+}
+
+impl Default for MockLLMClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_EMBEDDING_DIMENSIONS)
+    }
+}
+
+#[async_trait]
+impl LLMClient for MockLLMClient {
+    /// Hashed bag-of-words, L2-normalized: texts sharing words have a high
+    /// cosine similarity, so semantic search behaves sensibly.
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, String> {
+        let mut embedding = vec![0.0f32; self.embedding_dimensions];
+
+        for word in words(text) {
+            let hash = fnv1a(word.as_bytes());
+            let index = (hash % self.embedding_dimensions as u64) as usize;
+            let sign = if hash & (1 << 63) == 0 { 1.0 } else { -1.0 };
+            embedding[index] += sign;
+        }
+
+        let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            embedding.iter_mut().for_each(|v| *v /= norm);
+        }
+
+        Ok(embedding)
+    }
+
+    async fn generate_tutor_response(
+        &self,
+        query: &str,
+        _history: &[TutorMessage],
+        context: &str,
+        _course_id: Uuid,
+    ) -> Result<(String, i32), String> {
+        let query_words: Vec<String> = words(query).collect();
+
+        // Context sentence sharing the most words with the question
+        let best = sentences(context)
+            .into_iter()
+            .map(|sentence| {
+                let overlap = words(sentence).filter(|w| query_words.contains(w)).count();
+                (overlap, sentence)
+            })
+            .filter(|(overlap, _)| *overlap > 0)
+            .max_by_key(|(overlap, _)| *overlap);
+
+        let answer = match best {
+            Some((_, sentence)) => format!("Según el contenido del curso: {}", sentence),
+            None => "No tengo suficiente información en el contenido del curso para responder esa pregunta.".to_string(),
+        };
+
+        let tokens = estimate_tokens(context) + estimate_tokens(query) + estimate_tokens(&answer);
+        Ok((answer, tokens))
+    }
+
+    async fn generate_summary(&self, content: &str, _language: &str) -> Result<(String, i32), String> {
+        let summary = sentences(content)
+            .into_iter()
+            .take(SUMMARY_SENTENCES)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let tokens = estimate_tokens(content) + estimate_tokens(&summary);
+        Ok((summary, tokens))
+    }
+
+    async fn generate_key_points(&self, content: &str, _language: &str) -> Result<(Vec<KeyPoint>, i32), String> {
+        let key_points: Vec<KeyPoint> = sentences(content)
+            .into_iter()
+            .take(MAX_KEY_POINTS)
+            .enumerate()
+            .map(|(i, sentence)| KeyPoint {
+                order_index: i as i32 + 1,
+                title: sentence.split_whitespace().take(6).collect::<Vec<_>>().join(" "),
+                description: sentence.to_string(),
+                timestamp_seconds: None,
+            })
+            .collect();
+
+        Ok((key_points, estimate_tokens(content)))
+    }
+
+    /// Definitions of the form "<término> es <definición>" / "<term> is <definition>".
+    async fn generate_glossary(&self, content: &str, _language: &str) -> Result<(Vec<GlossaryTerm>, i32), String> {
+        let mut terms: Vec<GlossaryTerm> = Vec::new();
+
+        for sentence in sentences(content) {
+            let Some((term, _)) = sentence.split_once(" es ").or_else(|| sentence.split_once(" is ")) else {
+                continue;
+            };
+            let term = term.trim();
+            if term.is_empty() || term.split_whitespace().count() > 4 || terms.iter().any(|t| t.term == term) {
+                continue;
+            }
+
+            terms.push(GlossaryTerm {
+                term: term.to_string(),
+                definition: sentence.to_string(),
+                related_terms: Vec::new(),
+            });
+            if terms.len() == MAX_GLOSSARY_TERMS {
+                break;
+            }
+        }
+
+        Ok((terms, estimate_tokens(content)))
+    }
+
+    /// Fill-in-the-blank questions over the content sentences, cycling
+    /// through the requested question types.
+    async fn generate_quiz(&self, content: &str, config: &QuizGenerationConfig) -> Result<Vec<GeneratedQuestion>, String> {
+        let candidates: Vec<(&str, String)> = sentences(content)
+            .into_iter()
+            .filter_map(|sentence| keyword(sentence).map(|k| (sentence, k)))
+            .collect();
+
+        if candidates.is_empty() {
+            return Err("Content has no sentences to generate questions from".to_string());
+        }
+
+        let keywords: Vec<&str> = candidates.iter().map(|(_, k)| k.as_str()).collect();
+        let types = if config.question_types.is_empty() {
+            vec![QuestionType::SingleChoice]
+        } else {
+            config.question_types.clone()
+        };
+
+        let questions = (0..config.question_count.max(0) as usize)
+            .map(|i| {
+                let (sentence, answer) = &candidates[i % candidates.len()];
+                let question_type = types[i % types.len()];
+                let blanked = sentence.replacen(answer.as_str(), "_____", 1);
+
+                let (question_text, options, correct_answer) = match question_type {
+                    QuestionType::TrueFalse => (
+                        format!("Verdadero o falso: {}", sentence),
+                        Some(vec![
+                            option("true", "Verdadero", true),
+                            option("false", "Falso", false),
+                        ]),
+                        "true".to_string(),
+                    ),
+                    QuestionType::SingleChoice | QuestionType::MultipleChoice => {
+                        let (options, correct) = choice_options(answer, &keywords, i);
+                        (format!("Completa la frase: {}", blanked), Some(options), correct)
+                    }
+                    QuestionType::ShortAnswer => {
+                        (format!("Completa la frase: {}", blanked), None, answer.clone())
+                    }
+                    QuestionType::Code => (
+                        format!("Escribe un ejemplo de código que ilustre: {}", sentence),
+                        None,
+                        String::new(),
+                    ),
+                };
+
+                GeneratedQuestion {
+                    question_id: Uuid::now_v7(),
+                    request_id: Uuid::nil(), // Will be set by caller
+                    question_type,
+                    difficulty: config.difficulty,
+                    question_text,
+                    options,
+                    correct_answer,
+                    explanation: config
+                        .include_explanations
+                        .then(|| format!("Según el contenido: {}", sentence)),
+                    points: 10,
+                    source_reference: None,
+                }
+            })
+            .collect();
+
+        Ok(questions)
+    }
+}
+
+fn option(option_id: &str, text: &str, is_correct: bool) -> QuestionOption {
+    QuestionOption {
+        option_id: option_id.to_string(),
+        text: text.to_string(),
+        is_correct,
+    }
+}
+
+/// Correct keyword plus up to three distractors taken from other sentences;
+/// the position of the correct option rotates with the question index.
+fn choice_options(answer: &str, keywords: &[&str], index: usize) -> (Vec<QuestionOption>, String) {
+    let mut texts: Vec<&str> = keywords
+        .iter()
+        .copied()
+        .filter(|k| *k != answer)
+        .fold(Vec::new(), |mut acc, k| {
+            if !acc.contains(&k) {
+                acc.push(k);
+            }
+            acc
+        });
+    texts.truncate(OPTION_IDS.len() - 1);
+
+    let position = index % (texts.len() + 1);
+    texts.insert(position, answer);
+
+    let options = texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| option(OPTION_IDS[i], text, i == position))
+        .collect();
+
+    (options, OPTION_IDS[position].to_string())
+}
+
+/// Longest word of a sentence (first one on ties), used as the blank.
+fn keyword(sentence: &str) -> Option<String> {
+    sentence
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.chars().count() > 3)
+        .fold(None, |best: Option<&str>, w| match best {
+            Some(b) if b.chars().count() >= w.chars().count() => Some(b),
+            _ => Some(w),
+        })
+        .map(str::to_string)
+}
+
+fn sentences(text: &str) -> Vec<&str> {
+    text.split_inclusive(['.', '?', '!', '\n'])
+        .map(str::trim)
+        .filter(|s| s.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Rough token count (~4 characters per token).
+fn estimate_tokens(text: &str) -> i32 {
+    (text.chars().count() as i32 + 3) / 4
+}
+
+/// FNV-1a: stable across runs and Rust versions (unlike `DefaultHasher`).
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DifficultyLevel;
+
+    const CONTENT: &str = "Rust es un lenguaje de sistemas. El ownership garantiza seguridad de memoria. \
+        Un trait es una interfaz compartida. Los lifetimes evitan referencias colgantes.";
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[tokio::test]
+    async fn test_embedding_is_deterministic_and_normalized() {
+        let client = MockLLMClient::new(64);
+
+        let a = client.generate_embedding("ownership y memoria").await.unwrap();
+        let b = client.generate_embedding("ownership y memoria").await.unwrap();
+        let related = client.generate_embedding("memoria y ownership en Rust").await.unwrap();
+        let unrelated = client.generate_embedding("receta de pan casero").await.unwrap();
+
+        assert_eq!(a.len(), 64);
+        assert_eq!(a, b);
+        assert!((cosine(&a, &a) - 1.0).abs() < 1e-5);
+        assert!(cosine(&a, &related) > cosine(&a, &unrelated));
+    }
+
+    #[tokio::test]
+    async fn test_tutor_response_uses_matching_context() {
+        let client = MockLLMClient::default();
+
+        let (answer, tokens) = client
+            .generate_tutor_response("¿Qué es un trait?", &[], CONTENT, Uuid::nil())
+            .await
+            .unwrap();
+
+        assert!(answer.contains("Un trait es una interfaz compartida."));
+        assert!(tokens > 0);
+    }
+
+    #[tokio::test]
+    async fn test_summary_key_points_and_glossary() {
+        let client = MockLLMClient::default();
+
+        let (summary, _) = client.generate_summary(CONTENT, "es").await.unwrap();
+        let (points, _) = client.generate_key_points(CONTENT, "es").await.unwrap();
+        let (glossary, _) = client.generate_glossary(CONTENT, "es").await.unwrap();
+
+        assert!(summary.starts_with("Rust es un lenguaje de sistemas."));
+        assert!(!summary.contains("lifetimes"));
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1].order_index, 2);
+        let terms: Vec<&str> = glossary.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(terms, vec!["Rust", "Un trait"]);
+    }
+
+    #[tokio::test]
+    async fn test_quiz_follows_config() {
+        let client = MockLLMClient::default();
+        let config = QuizGenerationConfig {
+            question_count: 6,
+            difficulty: DifficultyLevel::Hard,
+            question_types: vec![QuestionType::SingleChoice, QuestionType::TrueFalse, QuestionType::ShortAnswer],
+            language: "es".to_string(),
+            include_explanations: false,
+        };
+
+        let questions = client.generate_quiz(CONTENT, &config).await.unwrap();
+        let again = client.generate_quiz(CONTENT, &config).await.unwrap();
+
+        assert_eq!(questions.len(), 6);
+        assert_eq!(questions[1].question_type, QuestionType::TrueFalse);
+        assert_eq!(questions[2].question_type, QuestionType::ShortAnswer);
+        assert!(questions.iter().all(|q| q.difficulty == DifficultyLevel::Hard && q.explanation.is_none()));
+
+        let first = &questions[0];
+        let options = first.options.as_ref().unwrap();
+        let correct: Vec<&QuestionOption> = options.iter().filter(|o| o.is_correct).collect();
+        assert_eq!(correct.len(), 1);
+        assert_eq!(correct[0].option_id, first.correct_answer);
+        assert!(first.question_text.contains("_____"));
+        assert_eq!(questions[0].question_text, again[0].question_text);
+        assert_eq!(questions[3].correct_answer, again[3].correct_answer);
+    }
+
+    #[tokio::test]
+    async fn test_quiz_without_content_fails() {
+        let client = MockLLMClient::default();
+        assert!(client.generate_quiz("  ", &QuizGenerationConfig::default()).await.is_err());
+    }
+}
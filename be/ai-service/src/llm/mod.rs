@@ -2,9 +2,16 @@
 //!
 //! LLM client implementations for AI operations
 
+pub mod config;
+pub mod mock_client;
 pub mod openai_client;
+pub mod openai_compatible_client;
+pub mod prompts;
 
+pub use config::{LLMConfig, LLMProvider};
+pub use mock_client::{MockLLMClient, DEFAULT_EMBEDDING_DIMENSIONS};
 pub use openai_client::OpenAIClient;
+pub use openai_compatible_client::OpenAICompatibleClient;
 
 use async_trait::async_trait;
 use uuid::Uuid;
@@ -19,6 +26,16 @@ pub trait LLMClient: Send + Sync {
     /// Generates an embedding vector for text.
     async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, String>;
 
+    /// Generates embeddings for several texts, in input order.
+    /// Providers with a batch API override this to use a single request.
+    async fn generate_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.generate_embedding(text).await?);
+        }
+        Ok(embeddings)
+    }
+
     /// Generates a tutor response.
     async fn generate_tutor_response(
         &self,
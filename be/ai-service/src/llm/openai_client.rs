@@ -15,10 +15,9 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::domain::{
-    DifficultyLevel, GeneratedQuestion, GlossaryTerm, KeyPoint,
-    QuestionOption, QuestionType, QuizGenerationConfig, TutorMessage,
+    GeneratedQuestion, GlossaryTerm, KeyPoint, QuizGenerationConfig, TutorMessage,
 };
-use crate::llm::LLMClient;
+use crate::llm::{prompts, LLMClient};
 
 /// OpenAI API client.
 pub struct OpenAIClient {
@@ -45,25 +44,6 @@ impl OpenAIClient {
         self
     }
 
-    /// Builds system prompt for tutor.
-    fn build_tutor_system_prompt(&self, context: &str) -> String {
-        format!(
-            r#"Eres un tutor educativo inteligente que ayuda a estudiantes a comprender el contenido de sus cursos.
-
-REGLAS:
-1. Solo responde preguntas relacionadas con el contenido del curso proporcionado.
-2. Si la pregunta está fuera del tema del curso, responde cortésmente que solo puedes ayudar con el contenido del curso.
-3. Usa el contexto proporcionado para dar respuestas precisas y relevantes.
-4. Cita las lecciones específicas cuando sea relevante.
-5. Mantén un tono amigable y educativo.
-6. Si no tienes suficiente información, admítelo honestamente.
-
-CONTEXTO DEL CURSO:
-{}"#,
-            context
-        )
-    }
-
     /// Builds messages for chat completion.
     fn build_chat_messages(
         &self,
@@ -139,6 +119,28 @@ impl LLMClient for OpenAIClient {
         Ok(embedding)
     }
 
+    async fn generate_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let request = CreateEmbeddingRequestArgs::default()
+            .model(&self.embedding_model)
+            .input(texts.to_vec())
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let response = self.client
+            .embeddings()
+            .create(request)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut data = response.data;
+        if data.len() != texts.len() {
+            return Err(format!("Expected {} embeddings, got {}", texts.len(), data.len()));
+        }
+        data.sort_by_key(|d| d.index);
+
+        Ok(data.into_iter().map(|d| d.embedding).collect())
+    }
+
     async fn generate_tutor_response(
         &self,
         query: &str,
@@ -146,7 +148,7 @@ impl LLMClient for OpenAIClient {
         context: &str,
         _course_id: Uuid,
     ) -> Result<(String, i32), String> {
-        let system_prompt = self.build_tutor_system_prompt(context);
+        let system_prompt = prompts::tutor_system_prompt(context);
         let messages = self.build_chat_messages(&system_prompt, history, query);
 
         let request = CreateChatCompletionRequestArgs::default()
@@ -176,24 +178,7 @@ impl LLMClient for OpenAIClient {
     }
 
     async fn generate_summary(&self, content: &str, language: &str) -> Result<(String, i32), String> {
-        let lang_name = match language {
-            "es" => "español",
-            "en" => "English",
-            "pt" => "português",
-            _ => "español",
-        };
-
-        let prompt = format!(
-            r#"Genera un resumen conciso del siguiente contenido educativo en {}.
-El resumen debe:
-1. Capturar los puntos principales
-2. Ser claro y fácil de entender
-3. Tener entre 150-300 palabras
-
-CONTENIDO:
-{}"#,
-            lang_name, content
-        );
+        let prompt = prompts::summary_prompt(content, language);
 
         let messages = vec![
             ChatCompletionRequestMessage::User(
@@ -231,22 +216,7 @@ CONTENIDO:
     }
 
     async fn generate_key_points(&self, content: &str, language: &str) -> Result<(Vec<KeyPoint>, i32), String> {
-        let lang_name = match language {
-            "es" => "español",
-            "en" => "English",
-            "pt" => "português",
-            _ => "español",
-        };
-
-        let prompt = format!(
-            r#"Extrae los puntos clave del siguiente contenido educativo en {}.
-Responde SOLO con un JSON array con el siguiente formato:
-[{{"order_index": 1, "title": "Título del punto", "description": "Descripción breve"}}]
-
-CONTENIDO:
-{}"#,
-            lang_name, content
-        );
+        let prompt = prompts::key_points_prompt(content, language);
 
         let messages = vec![
             ChatCompletionRequestMessage::User(
@@ -276,8 +246,7 @@ CONTENIDO:
             .and_then(|c| c.message.content.clone())
             .unwrap_or_default();
 
-        let key_points: Vec<KeyPoint> = serde_json::from_str(&content_str)
-            .unwrap_or_default();
+        let key_points: Vec<KeyPoint> = prompts::parse_json_list(&content_str);
 
         let tokens = response.usage
             .map(|u| u.total_tokens as i32)
@@ -287,22 +256,7 @@ CONTENIDO:
     }
 
     async fn generate_glossary(&self, content: &str, language: &str) -> Result<(Vec<GlossaryTerm>, i32), String> {
-        let lang_name = match language {
-            "es" => "español",
-            "en" => "English",
-            "pt" => "português",
-            _ => "español",
-        };
-
-        let prompt = format!(
-            r#"Extrae un glosario de términos técnicos del siguiente contenido educativo en {}.
-Responde SOLO con un JSON array con el siguiente formato:
-[{{"term": "Término", "definition": "Definición clara", "related_terms": ["término1", "término2"]}}]
-
-CONTENIDO:
-{}"#,
-            lang_name, content
-        );
+        let prompt = prompts::glossary_prompt(content, language);
 
         let messages = vec![
             ChatCompletionRequestMessage::User(
@@ -332,8 +286,7 @@ CONTENIDO:
             .and_then(|c| c.message.content.clone())
             .unwrap_or_default();
 
-        let terms: Vec<GlossaryTerm> = serde_json::from_str(&content_str)
-            .unwrap_or_default();
+        let terms: Vec<GlossaryTerm> = prompts::parse_json_list(&content_str);
 
         let tokens = response.usage
             .map(|u| u.total_tokens as i32)
@@ -343,56 +296,7 @@ CONTENIDO:
     }
 
     async fn generate_quiz(&self, content: &str, config: &QuizGenerationConfig) -> Result<Vec<GeneratedQuestion>, String> {
-        let lang_name = match config.language.as_str() {
-            "es" => "español",
-            "en" => "English",
-            "pt" => "português",
-            _ => "español",
-        };
-
-        let difficulty_str = match config.difficulty {
-            DifficultyLevel::Easy => "fácil",
-            DifficultyLevel::Medium => "intermedia",
-            DifficultyLevel::Hard => "difícil",
-        };
-
-        let types_str: Vec<&str> = config.question_types.iter().map(|t| match t {
-            QuestionType::SingleChoice => "opción única",
-            QuestionType::MultipleChoice => "opción múltiple",
-            QuestionType::TrueFalse => "verdadero/falso",
-            QuestionType::ShortAnswer => "respuesta corta",
-            QuestionType::Code => "código",
-        }).collect();
-
-        let prompt = format!(
-            r#"Genera {} preguntas de quiz basadas en el siguiente contenido educativo.
-
-REQUISITOS:
-- Idioma: {}
-- Dificultad: {}
-- Tipos de pregunta: {}
-- Incluir explicaciones: {}
-
-Responde SOLO con un JSON array con el siguiente formato:
-[{{
-    "question_type": "single_choice|multiple_choice|true_false|short_answer|code",
-    "difficulty": "easy|medium|hard",
-    "question_text": "Texto de la pregunta",
-    "options": [{{"option_id": "a", "text": "Opción A", "is_correct": false}}],
-    "correct_answer": "a",
-    "explanation": "Explicación de la respuesta correcta",
-    "points": 10
-}}]
-
-CONTENIDO:
-{}"#,
-            config.question_count,
-            lang_name,
-            difficulty_str,
-            types_str.join(", "),
-            if config.include_explanations { "sí" } else { "no" },
-            content
-        );
+        let prompt = prompts::quiz_prompt(content, config);
 
         let messages = vec![
             ChatCompletionRequestMessage::User(
@@ -422,70 +326,6 @@ CONTENIDO:
             .and_then(|c| c.message.content.clone())
             .unwrap_or_default();
 
-        // Parse the JSON response
-        let raw_questions: Vec<RawQuestion> = serde_json::from_str(&content_str)
-            .map_err(|e| format!("Failed to parse quiz response: {}", e))?;
-
-        // Convert to domain objects
-        let questions: Vec<GeneratedQuestion> = raw_questions
-            .into_iter()
-            .map(|q| GeneratedQuestion {
-                question_id: Uuid::now_v7(),
-                request_id: Uuid::nil(), // Will be set by caller
-                question_type: parse_question_type(&q.question_type),
-                difficulty: parse_difficulty(&q.difficulty),
-                question_text: q.question_text,
-                options: q.options.map(|opts| opts.into_iter().map(|o| QuestionOption {
-                    option_id: o.option_id,
-                    text: o.text,
-                    is_correct: o.is_correct,
-                }).collect()),
-                correct_answer: q.correct_answer,
-                explanation: q.explanation,
-                points: q.points.unwrap_or(10),
-                source_reference: None,
-            })
-            .collect();
-
-        Ok(questions)
-    }
-}
-
-// Helper structs for JSON parsing
-#[derive(serde::Deserialize)]
-struct RawQuestion {
-    question_type: String,
-    difficulty: String,
-    question_text: String,
-    options: Option<Vec<RawOption>>,
-    correct_answer: String,
-    explanation: Option<String>,
-    points: Option<i32>,
-}
-
-#[derive(serde::Deserialize)]
-struct RawOption {
-    option_id: String,
-    text: String,
-    is_correct: bool,
-}
-
-fn parse_question_type(s: &str) -> QuestionType {
-    match s {
-        "single_choice" => QuestionType::SingleChoice,
-        "multiple_choice" => QuestionType::MultipleChoice,
-        "true_false" => QuestionType::TrueFalse,
-        "short_answer" => QuestionType::ShortAnswer,
-        "code" => QuestionType::Code,
-        _ => QuestionType::SingleChoice,
-    }
-}
-
-fn parse_difficulty(s: &str) -> DifficultyLevel {
-    match s {
-        "easy" => DifficultyLevel::Easy,
-        "medium" => DifficultyLevel::Medium,
-        "hard" => DifficultyLevel::Hard,
-        _ => DifficultyLevel::Medium,
+        prompts::parse_quiz_response(&content_str)
     }
 }
@@ -0,0 +1,371 @@
+//! # OpenAI-Compatible Client
+//!
+//! Generic HTTP client for self-hosted endpoints that implement the OpenAI
+//! `/chat/completions` and `/embeddings` API (vLLM, Ollama, LocalAI, ...).
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    GeneratedQuestion, GlossaryTerm, KeyPoint, MessageRole, QuizGenerationConfig, TutorMessage,
+};
+use crate::llm::{prompts, LLMClient};
+
+/// Maximum number of characters of an error body included in error messages.
+const ERROR_BODY_LIMIT: usize = 500;
+
+/// Client for OpenAI-compatible HTTP endpoints.
+pub struct OpenAICompatibleClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    chat_model: String,
+    embedding_model: String,
+}
+
+impl OpenAICompatibleClient {
+    /// `base_url` is the API root, e.g. `http://localhost:11434/v1` (Ollama)
+    /// or `http://vllm:8000/v1`.
+    pub fn new(base_url: &str, chat_model: &str, embedding_model: &str, timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            chat_model: chat_model.to_string(),
+            embedding_model: embedding_model.to_string(),
+        }
+    }
+
+    /// Sends `Authorization: Bearer <key>` (vLLM `--api-key`, gateways).
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    async fn post<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<R, String> {
+        let mut request = self.http.post(format!("{}{}", self.base_url, path)).json(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let body: String = body.chars().take(ERROR_BODY_LIMIT).collect();
+            return Err(format!("LLM endpoint returned {}: {}", status, body));
+        }
+
+        response.json::<R>().await.map_err(|e| e.to_string())
+    }
+
+    /// Runs a chat completion and returns the content and total tokens.
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        max_tokens: u32,
+        temperature: f32,
+    ) -> Result<(String, i32), String> {
+        let request = ChatCompletionRequest {
+            model: &self.chat_model,
+            messages,
+            max_tokens,
+            temperature,
+            stream: false,
+        };
+
+        let response: ChatCompletionResponse = self.post("/chat/completions", &request).await?;
+
+        let content = response.choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .unwrap_or_default();
+
+        let tokens = response.usage
+            .map(|u| u.total_tokens)
+            .unwrap_or(0);
+
+        Ok((content, tokens))
+    }
+
+    async fn prompt(&self, prompt: String, max_tokens: u32, temperature: f32) -> Result<(String, i32), String> {
+        self.chat(vec![ChatMessage::new("user", prompt)], max_tokens, temperature).await
+    }
+}
+
+#[async_trait]
+impl LLMClient for OpenAICompatibleClient {
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, String> {
+        let request = EmbeddingRequest {
+            model: &self.embedding_model,
+            input: vec![text],
+        };
+
+        let response: EmbeddingResponse = self.post("/embeddings", &request).await?;
+
+        response.data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .ok_or_else(|| "No embedding returned".to_string())
+    }
+
+    async fn generate_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let request = EmbeddingRequest {
+            model: &self.embedding_model,
+            input: texts.iter().map(String::as_str).collect(),
+        };
+
+        let response: EmbeddingResponse = self.post("/embeddings", &request).await?;
+
+        let mut data = response.data;
+        if data.len() != texts.len() {
+            return Err(format!("Expected {} embeddings, got {}", texts.len(), data.len()));
+        }
+        data.sort_by_key(|d| d.index);
+
+        Ok(data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    async fn generate_tutor_response(
+        &self,
+        query: &str,
+        history: &[TutorMessage],
+        context: &str,
+        _course_id: Uuid,
+    ) -> Result<(String, i32), String> {
+        let mut messages = vec![ChatMessage::new("system", prompts::tutor_system_prompt(context))];
+
+        // Add history (last few messages)
+        for msg in history.iter().take(10) {
+            match msg.role {
+                MessageRole::User => messages.push(ChatMessage::new("user", msg.content.clone())),
+                MessageRole::Assistant => {
+                    messages.push(ChatMessage::new("assistant", msg.content.clone()))
+                }
+                MessageRole::System => {}
+            }
+        }
+
+        messages.push(ChatMessage::new("user", query.to_string()));
+
+        self.chat(messages, 1000, 0.7).await
+    }
+
+    async fn generate_summary(&self, content: &str, language: &str) -> Result<(String, i32), String> {
+        self.prompt(prompts::summary_prompt(content, language), 500, 0.3).await
+    }
+
+    async fn generate_key_points(&self, content: &str, language: &str) -> Result<(Vec<KeyPoint>, i32), String> {
+        let (answer, tokens) = self.prompt(prompts::key_points_prompt(content, language), 1000, 0.3).await?;
+        Ok((prompts::parse_json_list(&answer), tokens))
+    }
+
+    async fn generate_glossary(&self, content: &str, language: &str) -> Result<(Vec<GlossaryTerm>, i32), String> {
+        let (answer, tokens) = self.prompt(prompts::glossary_prompt(content, language), 1000, 0.3).await?;
+        Ok((prompts::parse_json_list(&answer), tokens))
+    }
+
+    async fn generate_quiz(&self, content: &str, config: &QuizGenerationConfig) -> Result<Vec<GeneratedQuestion>, String> {
+        let (answer, _) = self.prompt(prompts::quiz_prompt(content, config), 2000, 0.5).await?;
+        prompts::parse_quiz_response(&answer)
+    }
+}
+
+// Wire types (subset of the OpenAI API)
+#[derive(Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    content: String,
+}
+
+impl ChatMessage {
+    fn new(role: &'static str, content: String) -> Self {
+        Self { role, content }
+    }
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    max_tokens: u32,
+    temperature: f32,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    total_tokens: i32,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: Vec<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> OpenAICompatibleClient {
+        OpenAICompatibleClient::new(
+            &format!("{}/v1/", server.uri()),
+            "llama3.1",
+            "nomic-embed-text",
+            Duration::from_secs(5),
+        )
+    }
+
+    fn completion(content: &str) -> serde_json::Value {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": content}, "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 30, "completion_tokens": 12, "total_tokens": 42}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_tutor_response_sends_history_and_auth() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer local-key"))
+            .and(body_partial_json(json!({
+                "model": "llama3.1",
+                "stream": false,
+                "messages": [{"role": "system"}, {"role": "user", "content": "¿Qué es un trait?"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Un trait define comportamiento.")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).with_api_key("local-key");
+        let (answer, tokens) = client
+            .generate_tutor_response("¿Qué es un trait?", &[], "Lección 1: traits", Uuid::nil())
+            .await
+            .unwrap();
+
+        assert_eq!(answer, "Un trait define comportamiento.");
+        assert_eq!(tokens, 42);
+    }
+
+    #[tokio::test]
+    async fn test_key_points_accepts_fenced_json() {
+        let server = MockServer::start().await;
+        let answer = "```json\n[{\"order_index\": 1, \"title\": \"Ownership\", \"description\": \"Cada valor tiene un dueño\"}]\n```";
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion(answer)))
+            .mount(&server)
+            .await;
+
+        let (points, _) = client(&server).generate_key_points("...", "es").await.unwrap();
+
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].title, "Ownership");
+    }
+
+    #[tokio::test]
+    async fn test_embedding() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(json!({"model": "nomic-embed-text", "input": ["hola"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"object": "embedding", "index": 0, "embedding": [0.1, 0.2, 0.3]}],
+                "model": "nomic-embed-text"
+            })))
+            .mount(&server)
+            .await;
+
+        let embedding = client(&server).generate_embedding("hola").await.unwrap();
+
+        assert_eq!(embedding, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_embeddings_keep_input_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(json!({"input": ["uno", "dos"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [
+                    {"index": 1, "embedding": [2.0]},
+                    {"index": 0, "embedding": [1.0]}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let texts = vec!["uno".to_string(), "dos".to_string()];
+        let embeddings = client(&server).generate_embeddings(&texts).await.unwrap();
+
+        assert_eq!(embeddings, vec![vec![1.0], vec![2.0]]);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("model is loading"))
+            .mount(&server)
+            .await;
+
+        let err = client(&server).generate_summary("...", "es").await.unwrap_err();
+
+        assert!(err.contains("503"));
+        assert!(err.contains("model is loading"));
+    }
+}
@@ -0,0 +1,239 @@
+//! # Prompts
+//!
+//! Prompt templates and response parsing shared by the chat-based providers
+//! (OpenAI and OpenAI-compatible endpoints).
+
+use uuid::Uuid;
+
+use crate::domain::{
+    DifficultyLevel, GeneratedQuestion, QuestionOption, QuestionType, QuizGenerationConfig,
+};
+
+/// Maps a language code to the name used inside prompts.
+pub fn language_name(language: &str) -> &'static str {
+    match language {
+        "es" => "español",
+        "en" => "English",
+        "pt" => "português",
+        _ => "español",
+    }
+}
+
+/// Builds system prompt for tutor.
+pub fn tutor_system_prompt(context: &str) -> String {
+    format!(
+        r#"Eres un tutor educativo inteligente que ayuda a estudiantes a comprender el contenido de sus cursos.
+
+REGLAS:
+1. Solo responde preguntas relacionadas con el contenido del curso proporcionado.
+2. Si la pregunta está fuera del tema del curso, responde cortésmente que solo puedes ayudar con el contenido del curso.
+3. Usa el contexto proporcionado para dar respuestas precisas y relevantes.
+4. Cita las lecciones específicas cuando sea relevante.
+5. Mantén un tono amigable y educativo.
+6. Si no tienes suficiente información, admítelo honestamente.
+
+CONTEXTO DEL CURSO:
+{}"#,
+        context
+    )
+}
+
+pub fn summary_prompt(content: &str, language: &str) -> String {
+    format!(
+        r#"Genera un resumen conciso del siguiente contenido educativo en {}.
+El resumen debe:
+1. Capturar los puntos principales
+2. Ser claro y fácil de entender
+3. Tener entre 150-300 palabras
+
+CONTENIDO:
+{}"#,
+        language_name(language), content
+    )
+}
+
+pub fn key_points_prompt(content: &str, language: &str) -> String {
+    format!(
+        r#"Extrae los puntos clave del siguiente contenido educativo en {}.
+Responde SOLO con un JSON array con el siguiente formato:
+[{{"order_index": 1, "title": "Título del punto", "description": "Descripción breve"}}]
+
+CONTENIDO:
+{}"#,
+        language_name(language), content
+    )
+}
+
+pub fn glossary_prompt(content: &str, language: &str) -> String {
+    format!(
+        r#"Extrae un glosario de términos técnicos del siguiente contenido educativo en {}.
+Responde SOLO con un JSON array con el siguiente formato:
+[{{"term": "Término", "definition": "Definición clara", "related_terms": ["término1", "término2"]}}]
+
+CONTENIDO:
+{}"#,
+        language_name(language), content
+    )
+}
+
+pub fn quiz_prompt(content: &str, config: &QuizGenerationConfig) -> String {
+    let difficulty_str = match config.difficulty {
+        DifficultyLevel::Easy => "fácil",
+        DifficultyLevel::Medium => "intermedia",
+        DifficultyLevel::Hard => "difícil",
+    };
+
+    let types_str: Vec<&str> = config.question_types.iter().map(|t| match t {
+        QuestionType::SingleChoice => "opción única",
+        QuestionType::MultipleChoice => "opción múltiple",
+        QuestionType::TrueFalse => "verdadero/falso",
+        QuestionType::ShortAnswer => "respuesta corta",
+        QuestionType::Code => "código",
+    }).collect();
+
+    format!(
+        r#"Genera {} preguntas de quiz basadas en el siguiente contenido educativo.
+
+REQUISITOS:
+- Idioma: {}
+- Dificultad: {}
+- Tipos de pregunta: {}
+- Incluir explicaciones: {}
+
+Responde SOLO con un JSON array con el siguiente formato:
+[{{
+    "question_type": "single_choice|multiple_choice|true_false|short_answer|code",
+    "difficulty": "easy|medium|hard",
+    "question_text": "Texto de la pregunta",
+    "options": [{{"option_id": "a", "text": "Opción A", "is_correct": false}}],
+    "correct_answer": "a",
+    "explanation": "Explicación de la respuesta correcta",
+    "points": 10
+}}]
+
+CONTENIDO:
+{}"#,
+        config.question_count,
+        language_name(&config.language),
+        difficulty_str,
+        types_str.join(", "),
+        if config.include_explanations { "sí" } else { "no" },
+        content
+    )
+}
+
+/// Strips the markdown code fence that self-hosted models often wrap
+/// JSON answers in (```json ... ```).
+pub fn extract_json(content: &str) -> &str {
+    let trimmed = content.trim();
+
+    match trimmed.strip_prefix("```") {
+        Some(rest) => {
+            let body = rest.split_once('\n').map(|(_, body)| body).unwrap_or_default();
+            body.trim_end().trim_end_matches("```").trim()
+        }
+        None => trimmed,
+    }
+}
+
+/// Parses a JSON array answer, returning an empty list if it is malformed.
+pub fn parse_json_list<T: serde::de::DeserializeOwned>(content: &str) -> Vec<T> {
+    serde_json::from_str(extract_json(content)).unwrap_or_default()
+}
+
+/// Parses the quiz JSON answer into domain questions.
+pub fn parse_quiz_response(content: &str) -> Result<Vec<GeneratedQuestion>, String> {
+    let raw_questions: Vec<RawQuestion> = serde_json::from_str(extract_json(content))
+        .map_err(|e| format!("Failed to parse quiz response: {}", e))?;
+
+    // Convert to domain objects
+    let questions = raw_questions
+        .into_iter()
+        .map(|q| GeneratedQuestion {
+            question_id: Uuid::now_v7(),
+            request_id: Uuid::nil(), // Will be set by caller
+            question_type: parse_question_type(&q.question_type),
+            difficulty: parse_difficulty(&q.difficulty),
+            question_text: q.question_text,
+            options: q.options.map(|opts| opts.into_iter().map(|o| QuestionOption {
+                option_id: o.option_id,
+                text: o.text,
+                is_correct: o.is_correct,
+            }).collect()),
+            correct_answer: q.correct_answer,
+            explanation: q.explanation,
+            points: q.points.unwrap_or(10),
+            source_reference: None,
+        })
+        .collect();
+
+    Ok(questions)
+}
+
+// Helper structs for JSON parsing
+#[derive(serde::Deserialize)]
+struct RawQuestion {
+    question_type: String,
+    difficulty: String,
+    question_text: String,
+    options: Option<Vec<RawOption>>,
+    correct_answer: String,
+    explanation: Option<String>,
+    points: Option<i32>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawOption {
+    option_id: String,
+    text: String,
+    is_correct: bool,
+}
+
+fn parse_question_type(s: &str) -> QuestionType {
+    match s {
+        "single_choice" => QuestionType::SingleChoice,
+        "multiple_choice" => QuestionType::MultipleChoice,
+        "true_false" => QuestionType::TrueFalse,
+        "short_answer" => QuestionType::ShortAnswer,
+        "code" => QuestionType::Code,
+        _ => QuestionType::SingleChoice,
+    }
+}
+
+fn parse_difficulty(s: &str) -> DifficultyLevel {
+    match s {
+        "easy" => DifficultyLevel::Easy,
+        "medium" => DifficultyLevel::Medium,
+        "hard" => DifficultyLevel::Hard,
+        _ => DifficultyLevel::Medium,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_json_strips_code_fence() {
+        assert_eq!(extract_json("```json\n[1, 2]\n```"), "[1, 2]");
+        assert_eq!(extract_json("```\n[]\n```\n"), "[]");
+        assert_eq!(extract_json("  [3]  "), "[3]");
+    }
+
+    #[test]
+    fn test_parse_quiz_response() {
+        let content = r#"```json
+[{"question_type": "true_false", "difficulty": "hard", "question_text": "¿Rust tiene GC?",
+  "options": [{"option_id": "a", "text": "Sí", "is_correct": false}],
+  "correct_answer": "b", "explanation": null}]
+```"#;
+
+        let questions = parse_quiz_response(content).unwrap();
+
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].question_type, QuestionType::TrueFalse);
+        assert_eq!(questions[0].difficulty, DifficultyLevel::Hard);
+        assert_eq!(questions[0].points, 10);
+        assert!(parse_quiz_response("not json").is_err());
+    }
+}
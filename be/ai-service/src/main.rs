@@ -10,14 +10,20 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use ai_service::{
     api::{configure_routes, AppState},
     repository::{
-        ConversationRepository, EmbeddingRepository,
-        SummaryRepository, QuizGenerationRepository,
+        ConversationRepository, CourseContentRepository, EmbeddingRepository,
+        SummaryRepository, QuizGenerationRepository, UsageRepository,
     },
     service::{
-        TutorService, SemanticSearchService,
-        SummaryService, QuizGeneratorService,
+        TutorService, SemanticSearchService, ContentServiceTranscripts,
+        SummaryService, QuizGeneratorService, RedisUsageCounters, UsageMeter,
     },
-    llm::OpenAIClient,
+    llm::LLMConfig,
+};
+use shared::{
+    auth::ServiceTokens,
+    config::RedisConfig,
+    redis_client::RedisClient,
+    service_client::{ServiceClient, ServiceClientConfig},
 };
 
 #[tokio::main]
@@ -35,8 +41,14 @@ async fn main() -> std::io::Result<()> {
     // Load configuration
     let database_url = std::env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set");
-    let openai_api_key = std::env::var("OPENAI_API_KEY")
-        .expect("OPENAI_API_KEY must be set");
+    let llm_config = LLMConfig::from_env()
+        .expect("Invalid LLM configuration");
+    let content_service_url = std::env::var("CONTENT_SERVICE_URL")
+        .unwrap_or_else(|_| "http://localhost:8083".to_string());
+    let redis_url = std::env::var("REDIS_URL")
+        .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let internal_secret = std::env::var("INTERNAL_SERVICE_SECRET")
+        .expect("INTERNAL_SERVICE_SECRET must be set");
     let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port: u16 = std::env::var("PORT")
         .unwrap_or_else(|_| "8093".to_string())
@@ -52,32 +64,68 @@ async fn main() -> std::io::Result<()> {
 
     tracing::info!("Database connection established");
 
-    // Create LLM client
-    let llm_client = Arc::new(OpenAIClient::new(&openai_api_key));
+    // Redis holds the AI usage counters and caches access lookups
+    let redis = RedisClient::new(&RedisConfig { url: redis_url, pool_size: 10 })
+        .await
+        .expect("Failed to connect to Redis");
+
+    // Create LLM client (LLM_PROVIDER: openai | openai_compatible | mock)
+    let llm_client = llm_config.build_client()
+        .expect("Failed to create LLM client");
+
+    tracing::info!("Using LLM provider: {:?}", llm_config.provider);
 
     // Create repositories
     let conversation_repo = ConversationRepository::new(pool.clone());
     let embedding_repo = EmbeddingRepository::new(pool.clone());
     let summary_repo = SummaryRepository::new(pool.clone());
     let quiz_gen_repo = QuizGenerationRepository::new(pool.clone());
+    let course_content_repo = CourseContentRepository::new(pool.clone());
+    let usage_repo = UsageRepository::new(pool.clone());
+
+    // Per-user quotas by subscription plan, checked before every LLM call
+    let usage_meter = Arc::new(UsageMeter::new(
+        Arc::new(RedisUsageCounters::new(redis.clone())),
+        Arc::new(usage_repo),
+    ));
+
+    // Transcriptions are read through content-service (files live in its storage)
+    let transcripts = Arc::new(ContentServiceTranscripts::new(
+        &content_service_url,
+        std::time::Duration::from_secs(30),
+    ));
+
+    // Enrollment and course lookups in other services
+    let service_tokens = Arc::new(
+        ServiceTokens::new("ai-service", &internal_secret)
+            .expect("Invalid INTERNAL_SERVICE_SECRET"),
+    );
+    let service_client = ServiceClient::new(ServiceClientConfig::from_env(), service_tokens, Some(redis));
 
     // Create services
     let tutor_service = Arc::new(TutorService::new(
         conversation_repo,
         embedding_repo.clone(),
         llm_client.clone(),
+        usage_meter.clone(),
     ));
     let search_service = Arc::new(SemanticSearchService::new(
         embedding_repo.clone(),
+        course_content_repo,
+        transcripts,
         llm_client.clone(),
+        usage_meter.clone(),
+        service_client,
     ));
     let summary_service = Arc::new(SummaryService::new(
         summary_repo,
         llm_client.clone(),
+        usage_meter.clone(),
     ));
     let quiz_generator_service = Arc::new(QuizGeneratorService::new(
         quiz_gen_repo,
         llm_client.clone(),
+        usage_meter.clone(),
     ));
 
     // Create app state
@@ -86,6 +134,7 @@ async fn main() -> std::io::Result<()> {
         search_service,
         summary_service,
         quiz_generator_service,
+        usage_meter,
     });
 
     tracing::info!("Starting HTTP server on {}:{}", host, port);
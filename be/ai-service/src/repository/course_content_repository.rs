@@ -0,0 +1,263 @@
+//! # Course Content Repository
+//!
+//! Read access to the course material indexed for semantic search
+//! (courses, lessons, KB articles, asset transcriptions) and the per-lesson
+//! content hashes used for incremental re-indexing.
+
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::{AIError, AIResult};
+
+/// Course-level text (title, descriptions, objectives).
+#[derive(Debug, Clone)]
+pub struct CourseText {
+    pub course_id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub short_description: String,
+    pub full_description: Option<String>,
+    pub learning_objectives: Vec<String>,
+}
+
+/// A lesson with its content reference (article lessons keep their text there).
+#[derive(Debug, Clone)]
+pub struct LessonText {
+    pub lesson_id: Uuid,
+    pub title: String,
+    pub content_type: String,
+    pub content_ref: Option<String>,
+}
+
+/// A published KB article linked to the course.
+#[derive(Debug, Clone)]
+pub struct KbArticleText {
+    pub article_id: Uuid,
+    pub title: String,
+    pub content: String,
+}
+
+/// A transcription of a course asset (one format per asset and language).
+#[derive(Debug, Clone)]
+pub struct TranscriptionRef {
+    pub asset_id: Uuid,
+    pub lesson_id: Option<Uuid>,
+    pub language: String,
+    pub format: String,
+}
+
+/// Hash of the content last indexed for a lesson (or the course itself).
+#[derive(Debug, Clone)]
+pub struct IndexedSource {
+    pub source_key: String,
+    pub lesson_id: Option<Uuid>,
+    pub content_hash: String,
+}
+
+/// Repository for course content used by indexing.
+#[derive(Clone)]
+pub struct CourseContentRepository {
+    pool: PgPool,
+}
+
+impl CourseContentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Gets the course texts.
+    pub async fn get_course(&self, course_id: Uuid) -> AIResult<Option<CourseText>> {
+        let row = sqlx::query(
+            r#"
+            SELECT course_id, slug, title, short_description, full_description,
+                   learning_objectives
+            FROM courses.courses
+            WHERE course_id = $1 AND deleted_at IS NULL
+            "#
+        )
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| {
+            let objectives: Option<serde_json::Value> = row.get("learning_objectives");
+
+            CourseText {
+                course_id: row.get("course_id"),
+                slug: row.get("slug"),
+                title: row.get("title"),
+                short_description: row.get("short_description"),
+                full_description: row.get("full_description"),
+                learning_objectives: objectives
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default(),
+            }
+        }))
+    }
+
+    /// Lists the course lessons in syllabus order.
+    pub async fn list_lessons(&self, course_id: Uuid) -> AIResult<Vec<LessonText>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT l.lesson_id, l.title, l.content_type, l.content_ref
+            FROM courses.lessons l
+            JOIN courses.sections s ON s.section_id = l.section_id
+            WHERE l.course_id = $1
+            ORDER BY s.sort_order, l.sort_order
+            "#
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| LessonText {
+                lesson_id: row.get("lesson_id"),
+                title: row.get("title"),
+                content_type: row.get("content_type"),
+                content_ref: row.get("content_ref"),
+            })
+            .collect())
+    }
+
+    /// Lists published KB articles tagged with the course id or slug.
+    pub async fn list_kb_articles(&self, course_id: Uuid, slug: &str) -> AIResult<Vec<KbArticleText>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT article_id, title, content
+            FROM kb.articles
+            WHERE status = 'published'
+                AND (tags ? $1 OR tags ? $2)
+            ORDER BY published_at, article_id
+            "#
+        )
+        .bind(course_id.to_string())
+        .bind(slug)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| KbArticleText {
+                article_id: row.get("article_id"),
+                title: row.get("title"),
+                content: row.get("content"),
+            })
+            .collect())
+    }
+
+    /// Lists transcriptions of the course assets, preferring plain text
+    /// over subtitle formats for each asset and language.
+    pub async fn list_transcriptions(&self, course_id: Uuid) -> AIResult<Vec<TranscriptionRef>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT ON (t.asset_id, t.language)
+                t.asset_id, a.lesson_id, t.language, t.format
+            FROM content.transcriptions t
+            JOIN content.assets a ON a.asset_id = t.asset_id
+            WHERE a.course_id = $1 AND a.deleted_at IS NULL
+            ORDER BY t.asset_id, t.language,
+                CASE t.format WHEN 'txt' THEN 0 WHEN 'vtt' THEN 1 ELSE 2 END
+            "#
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TranscriptionRef {
+                asset_id: row.get("asset_id"),
+                lesson_id: row.get("lesson_id"),
+                language: row.get("language"),
+                format: row.get("format"),
+            })
+            .collect())
+    }
+
+    /// Gets the content hashes of the last indexing run.
+    pub async fn list_indexed_sources(&self, course_id: Uuid) -> AIResult<Vec<IndexedSource>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT source_key, lesson_id, content_hash
+            FROM ai.content_index_state
+            WHERE course_id = $1
+            "#
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| IndexedSource {
+                source_key: row.get("source_key"),
+                lesson_id: row.get("lesson_id"),
+                content_hash: row.get("content_hash"),
+            })
+            .collect())
+    }
+
+    /// Records the hash of indexed content.
+    pub async fn save_indexed_source(
+        &self,
+        course_id: Uuid,
+        source: &IndexedSource,
+        chunk_count: i32,
+    ) -> AIResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO ai.content_index_state (
+                course_id, source_key, lesson_id, content_hash, chunk_count, indexed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (course_id, source_key)
+            DO UPDATE SET
+                content_hash = EXCLUDED.content_hash,
+                chunk_count = EXCLUDED.chunk_count,
+                indexed_at = EXCLUDED.indexed_at
+            "#
+        )
+        .bind(course_id)
+        .bind(&source.source_key)
+        .bind(source.lesson_id)
+        .bind(&source.content_hash)
+        .bind(chunk_count)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Forgets an indexed source (lesson deleted or emptied).
+    pub async fn delete_indexed_source(&self, course_id: Uuid, source_key: &str) -> AIResult<()> {
+        sqlx::query(
+            "DELETE FROM ai.content_index_state WHERE course_id = $1 AND source_key = $2"
+        )
+        .bind(course_id)
+        .bind(source_key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Forgets all indexed sources of a course (forced re-index).
+    pub async fn clear_indexed_sources(&self, course_id: Uuid) -> AIResult<()> {
+        sqlx::query("DELETE FROM ai.content_index_state WHERE course_id = $1")
+            .bind(course_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -9,6 +9,19 @@ use crate::domain::{
     AIError, AIResult, ContentEmbedding, EmbeddingContentType, SemanticSearchResult,
 };
 
+const INSERT_EMBEDDING: &str = r#"
+    INSERT INTO ai.content_embeddings (
+        embedding_id, course_id, lesson_id, content_type,
+        chunk_index, content_text, embedding, metadata, created_at
+    )
+    VALUES ($1, $2, $3, $4, $5, $6, $7::vector, $8, $9)
+    ON CONFLICT (course_id, lesson_id, chunk_index)
+    DO UPDATE SET
+        content_text = EXCLUDED.content_text,
+        embedding = EXCLUDED.embedding,
+        metadata = EXCLUDED.metadata
+"#;
+
 /// Repository for embedding operations.
 #[derive(Clone)]
 pub struct EmbeddingRepository {
@@ -24,32 +37,19 @@ impl EmbeddingRepository {
     pub async fn save_embedding(&self, embedding: &ContentEmbedding) -> AIResult<()> {
         let embedding_vec: Vec<f32> = embedding.embedding.clone();
 
-        sqlx::query(
-            r#"
-            INSERT INTO ai.content_embeddings (
-                embedding_id, course_id, lesson_id, content_type,
-                chunk_index, content_text, embedding, metadata, created_at
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7::vector, $8, $9)
-            ON CONFLICT (course_id, lesson_id, chunk_index)
-            DO UPDATE SET
-                content_text = EXCLUDED.content_text,
-                embedding = EXCLUDED.embedding,
-                metadata = EXCLUDED.metadata
-            "#
-        )
-        .bind(embedding.embedding_id)
-        .bind(embedding.course_id)
-        .bind(embedding.lesson_id)
-        .bind(embedding.content_type.to_string())
-        .bind(embedding.chunk_index)
-        .bind(&embedding.content_text)
-        .bind(&embedding_vec)
-        .bind(&embedding.metadata)
-        .bind(embedding.created_at)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| AIError::Database(e.to_string()))?;
+        sqlx::query(INSERT_EMBEDDING)
+            .bind(embedding.embedding_id)
+            .bind(embedding.course_id)
+            .bind(embedding.lesson_id)
+            .bind(embedding.content_type.to_string())
+            .bind(embedding.chunk_index)
+            .bind(&embedding.content_text)
+            .bind(&embedding_vec)
+            .bind(&embedding.metadata)
+            .bind(embedding.created_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AIError::Database(e.to_string()))?;
 
         Ok(())
     }
@@ -64,6 +64,62 @@ impl EmbeddingRepository {
         Ok(count)
     }
 
+    /// Atomically replaces the embeddings of a lesson (`None`: course-level
+    /// content such as the description and KB articles).
+    pub async fn replace_lesson_embeddings(
+        &self,
+        course_id: Uuid,
+        lesson_id: Option<Uuid>,
+        embeddings: &[ContentEmbedding],
+    ) -> AIResult<i32> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AIError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "DELETE FROM ai.content_embeddings WHERE course_id = $1 AND lesson_id IS NOT DISTINCT FROM $2"
+        )
+        .bind(course_id)
+        .bind(lesson_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        for embedding in embeddings {
+            sqlx::query(INSERT_EMBEDDING)
+                .bind(embedding.embedding_id)
+                .bind(embedding.course_id)
+                .bind(embedding.lesson_id)
+                .bind(embedding.content_type.to_string())
+                .bind(embedding.chunk_index)
+                .bind(&embedding.content_text)
+                .bind(&embedding.embedding)
+                .bind(&embedding.metadata)
+                .bind(embedding.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AIError::Database(e.to_string()))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(embeddings.len() as i32)
+    }
+
+    /// Deletes the embeddings of a lesson (`None`: course-level content).
+    pub async fn delete_lesson_embeddings(&self, course_id: Uuid, lesson_id: Option<Uuid>) -> AIResult<i64> {
+        let result = sqlx::query(
+            "DELETE FROM ai.content_embeddings WHERE course_id = $1 AND lesson_id IS NOT DISTINCT FROM $2"
+        )
+        .bind(course_id)
+        .bind(lesson_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() as i64)
+    }
+
     /// Performs semantic search using cosine similarity.
     pub async fn search_similar(
         &self,
@@ -3,11 +3,15 @@
 //! Data access layer for AI Service
 
 pub mod conversation_repository;
+pub mod course_content_repository;
 pub mod embedding_repository;
 pub mod summary_repository;
 pub mod quiz_generation_repository;
+pub mod usage_repository;
 
 pub use conversation_repository::ConversationRepository;
+pub use course_content_repository::{CourseContentRepository, IndexedSource};
 pub use embedding_repository::EmbeddingRepository;
 pub use summary_repository::SummaryRepository;
 pub use quiz_generation_repository::QuizGenerationRepository;
+pub use usage_repository::UsageRepository;
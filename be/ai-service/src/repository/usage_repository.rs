@@ -0,0 +1,78 @@
+//! # Usage Repository
+//!
+//! Persists metered AI usage and reads the user's subscription plan,
+//! which decides the AI quotas.
+
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::{AIError, AIResult, AIUsage, PlanTier, UserPlan};
+
+/// Repository for AI usage records and plan lookups.
+#[derive(Clone)]
+pub struct UsageRepository {
+    pool: PgPool,
+}
+
+impl UsageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Gets the plan of the user's current subscription (free when none).
+    pub async fn get_user_plan(&self, user_id: Uuid) -> AIResult<UserPlan> {
+        let row = sqlx::query(
+            r#"
+            SELECT p.tier::text AS tier, p.limits -> 'ai_quotas' AS ai_quotas
+            FROM subscriptions.subscriptions s
+            JOIN subscriptions.plans p ON p.id = s.plan_id
+            WHERE s.user_id = $1 AND s.status IN ('active', 'trialing')
+            ORDER BY s.current_period_end DESC
+            LIMIT 1
+            "#
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(UserPlan::default());
+        };
+
+        let tier: String = row.get("tier");
+        let ai_quotas: Option<serde_json::Value> = row.get("ai_quotas");
+
+        Ok(UserPlan {
+            tier: tier.parse().unwrap_or(PlanTier::Free),
+            ai_quotas: ai_quotas
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Records the tokens consumed by an LLM call.
+    pub async fn record_usage(&self, usage: &AIUsage) -> AIResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO ai.usage_records (
+                usage_id, user_id, feature, tokens_input, tokens_output,
+                cost_cents, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(usage.usage_id)
+        .bind(usage.user_id)
+        .bind(usage.feature.as_str())
+        .bind(usage.tokens_input)
+        .bind(usage.tokens_output)
+        .bind(usage.cost_cents)
+        .bind(usage.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AIError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
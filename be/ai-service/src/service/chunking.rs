@@ -0,0 +1,183 @@
+//! # Chunking
+//!
+//! Splits text into overlapping, token-bounded chunks for embedding.
+//! Chunks end on sentence boundaries whenever a sentence fits; longer
+//! sentences are split by words.
+
+/// Chunk size limits, in estimated tokens.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkingConfig {
+    pub max_tokens: usize,
+    pub overlap_tokens: usize,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            max_tokens: 400,
+            overlap_tokens: 60,
+        }
+    }
+}
+
+/// Estimates BPE tokens: one per ~4 characters of each word, at least one
+/// per word. Close enough to cl100k for Spanish/English prose.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace().map(word_tokens).sum()
+}
+
+fn word_tokens(word: &str) -> usize {
+    word.chars().count().div_ceil(4).max(1)
+}
+
+/// Splits `text` into chunks of at most `max_tokens`, each starting with the
+/// last `overlap_tokens` of the previous one.
+pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<String> {
+    let max_tokens = config.max_tokens.max(1);
+    let overlap_tokens = config.overlap_tokens.min(max_tokens / 2);
+
+    let units: Vec<(String, usize)> = split_sentences(text)
+        .into_iter()
+        .flat_map(|sentence| split_long_sentence(sentence, max_tokens))
+        .collect();
+
+    let mut chunks = Vec::new();
+    let mut current: Vec<&(String, usize)> = Vec::new();
+    let mut current_tokens = 0;
+
+    for unit in &units {
+        if current_tokens + unit.1 > max_tokens && !current.is_empty() {
+            chunks.push(join(&current));
+
+            // Carry the trailing sentences over as overlap
+            let mut kept = Vec::new();
+            let mut kept_tokens = 0;
+            for previous in current.iter().rev() {
+                if kept_tokens + previous.1 > overlap_tokens
+                    || kept_tokens + previous.1 + unit.1 > max_tokens
+                {
+                    break;
+                }
+                kept_tokens += previous.1;
+                kept.push(*previous);
+            }
+            kept.reverse();
+
+            current = kept;
+            current_tokens = kept_tokens;
+        }
+
+        current.push(unit);
+        current_tokens += unit.1;
+    }
+
+    if !current.is_empty() {
+        chunks.push(join(&current));
+    }
+
+    chunks
+}
+
+fn join(units: &[&(String, usize)]) -> String {
+    units.iter().map(|(text, _)| text.as_str()).collect::<Vec<_>>().join(" ")
+}
+
+/// Sentences end at `.`, `!` or `?` followed by whitespace, or at a line break.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let next_is_space = chars.peek().map(|(_, n)| n.is_whitespace()).unwrap_or(true);
+        if c == '\n' || (matches!(c, '.' | '!' | '?') && next_is_space) {
+            let end = i + c.len_utf8();
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Splits a sentence that does not fit in a chunk into word groups.
+fn split_long_sentence(sentence: &str, max_tokens: usize) -> Vec<(String, usize)> {
+    let tokens = estimate_tokens(sentence);
+    if tokens <= max_tokens {
+        return vec![(sentence.split_whitespace().collect::<Vec<_>>().join(" "), tokens)];
+    }
+
+    let mut parts = Vec::new();
+    let mut words = Vec::new();
+    let mut part_tokens = 0;
+
+    for word in sentence.split_whitespace() {
+        let tokens = word_tokens(word);
+        if part_tokens + tokens > max_tokens && !words.is_empty() {
+            parts.push((words.join(" "), part_tokens));
+            words.clear();
+            part_tokens = 0;
+        }
+        words.push(word);
+        part_tokens += tokens;
+    }
+    if !words.is_empty() {
+        parts.push((words.join(" "), part_tokens));
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("a de la"), 3);
+        assert_eq!(estimate_tokens("ownership"), 3);
+    }
+
+    #[test]
+    fn test_short_text_is_single_chunk() {
+        let chunks = chunk_text("Hola mundo. Esto es Rust.", &ChunkingConfig::default());
+        assert_eq!(chunks, vec!["Hola mundo. Esto es Rust.".to_string()]);
+        assert!(chunk_text("  \n ", &ChunkingConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_chunks_respect_limit_and_overlap() {
+        let config = ChunkingConfig { max_tokens: 12, overlap_tokens: 4 };
+        // 4 tokens per sentence
+        let text = "a b c d. e f g h. i j k l. m n o p. q r s t.";
+
+        let chunks = chunk_text(text, &config);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], "a b c d. e f g h. i j k l.");
+        // The second chunk starts with the last sentence of the first
+        assert_eq!(chunks[1], "i j k l. m n o p. q r s t.");
+        assert!(chunks.iter().all(|c| estimate_tokens(c) <= 12));
+    }
+
+    #[test]
+    fn test_long_sentence_is_split_by_words() {
+        let config = ChunkingConfig { max_tokens: 5, overlap_tokens: 0 };
+        let text = "a b c d e f g h i j k";
+
+        let chunks = chunk_text(text, &config);
+
+        assert_eq!(chunks, vec!["a b c d e", "f g h i j", "k"]);
+    }
+
+    #[test]
+    fn test_decimal_point_does_not_split() {
+        assert_eq!(split_sentences("Pi vale 3.14 aprox. Fin"), vec!["Pi vale 3.14 aprox.", "Fin"]);
+    }
+}
@@ -0,0 +1,561 @@
+//! # Content Ingestion
+//!
+//! Builds the documents indexed for semantic search from course material,
+//! and embeds their chunks in batches with retry/backoff.
+//!
+//! Each lesson is one document (lesson text + transcriptions of its assets);
+//! course-level material (description, objectives, KB articles, transcriptions
+//! not tied to a lesson) forms one more document. A document is re-embedded
+//! only when its content hash changes.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::{AIError, AIResult, EmbeddingContentType};
+use crate::llm::LLMClient;
+use crate::repository::course_content_repository::{
+    CourseText, KbArticleText, LessonText, TranscriptionRef,
+};
+use crate::service::chunking::{chunk_text, ChunkingConfig};
+
+/// Source key of the course-level document.
+pub const COURSE_SOURCE_KEY: &str = "course";
+
+/// Indexing pipeline settings.
+#[derive(Debug, Clone)]
+pub struct IndexingConfig {
+    pub chunking: ChunkingConfig,
+    /// Texts per embedding request.
+    pub batch_size: usize,
+    /// Retries per batch before failing the indexing run.
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for IndexingConfig {
+    fn default() -> Self {
+        Self {
+            chunking: ChunkingConfig::default(),
+            batch_size: 32,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+// =============================================================================
+// TRANSCRIPTS
+// =============================================================================
+
+/// Source of transcription text.
+#[async_trait]
+pub trait TranscriptFetcher: Send + Sync {
+    /// Returns the raw transcription file (txt, vtt or srt).
+    async fn fetch_transcript(&self, asset_id: Uuid, language: &str) -> Result<String, String>;
+}
+
+/// Fetches transcriptions from content-service.
+pub struct ContentServiceTranscripts {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl ContentServiceTranscripts {
+    /// `base_url` is the content-service root, e.g. `http://content-service:8083`.
+    pub fn new(base_url: &str, timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptFetcher for ContentServiceTranscripts {
+    async fn fetch_transcript(&self, asset_id: Uuid, language: &str) -> Result<String, String> {
+        let url = format!(
+            "{}/api/v1/content/assets/{}/transcriptions/{}",
+            self.base_url, asset_id, language
+        );
+
+        // Internal call: indexing reads every course asset
+        let response = self.http
+            .get(&url)
+            .header("X-User-Id", Uuid::nil().to_string())
+            .header("X-User-Role", "admin")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("content-service returned {} for {}", status, url));
+        }
+
+        response.text().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Extracts the spoken text of a VTT/SRT file (drops headers, cue numbers,
+/// timings and markup). Plain text is returned unchanged.
+pub fn subtitle_to_text(content: &str, format: &str) -> String {
+    if format != "vtt" && format != "srt" {
+        return content.trim().to_string();
+    }
+
+    let mut lines = Vec::new();
+    let mut in_note = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            in_note = false;
+            continue;
+        }
+        if in_note || line.starts_with("WEBVTT") || line.contains("-->") {
+            continue;
+        }
+        if line.starts_with("NOTE") || line.starts_with("STYLE") || line.starts_with("REGION") {
+            in_note = true;
+            continue;
+        }
+        if line.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+
+        let text = strip_tags(line);
+        // Consecutive cues often repeat the previous line
+        if !text.is_empty() && lines.last() != Some(&text) {
+            lines.push(text);
+        }
+    }
+
+    lines.join(" ")
+}
+
+fn strip_tags(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut in_tag = false;
+
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.trim().to_string()
+}
+
+// =============================================================================
+// DOCUMENTS
+// =============================================================================
+
+/// A piece of text of a document.
+#[derive(Debug, Clone)]
+pub struct SourcePart {
+    pub content_type: EmbeddingContentType,
+    pub title: String,
+    pub text: String,
+}
+
+/// A chunk ready to embed.
+#[derive(Debug, Clone)]
+pub struct SourceChunk {
+    pub content_type: EmbeddingContentType,
+    pub title: String,
+    pub text: String,
+}
+
+/// Indexable content of a lesson (`lesson_id = None`: course-level).
+#[derive(Debug, Clone)]
+pub struct SourceDocument {
+    pub lesson_id: Option<Uuid>,
+    pub parts: Vec<SourcePart>,
+    /// False when some content could not be fetched; the document is then
+    /// left as previously indexed instead of being re-indexed without it.
+    pub complete: bool,
+}
+
+impl SourceDocument {
+    fn new(lesson_id: Option<Uuid>) -> Self {
+        Self {
+            lesson_id,
+            parts: Vec::new(),
+            complete: true,
+        }
+    }
+
+    pub fn source_key(&self) -> String {
+        self.lesson_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| COURSE_SOURCE_KEY.to_string())
+    }
+
+    /// SHA-256 over every part (type, title and text).
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in &self.parts {
+            hasher.update(format!("{:?}", part.content_type).as_bytes());
+            hasher.update([0]);
+            hasher.update(part.title.as_bytes());
+            hasher.update([0]);
+            hasher.update(part.text.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Splits every part into overlapping chunks.
+    pub fn chunks(&self, config: &ChunkingConfig) -> Vec<SourceChunk> {
+        self.parts
+            .iter()
+            .flat_map(|part| {
+                chunk_text(&part.text, config).into_iter().map(|text| SourceChunk {
+                    content_type: part.content_type,
+                    title: part.title.clone(),
+                    text,
+                })
+            })
+            .collect()
+    }
+
+    fn push(&mut self, content_type: EmbeddingContentType, title: &str, text: String) {
+        let text = text.trim().to_string();
+        if !text.is_empty() {
+            self.parts.push(SourcePart {
+                content_type,
+                title: title.to_string(),
+                text,
+            });
+        }
+    }
+}
+
+/// A transcription with its fetched text (or the fetch error).
+pub struct FetchedTranscript {
+    pub transcription: TranscriptionRef,
+    pub text: Result<String, String>,
+}
+
+/// Groups course material into one document per lesson plus the
+/// course-level document. Documents without any text are omitted.
+pub fn build_documents(
+    course: &CourseText,
+    lessons: &[LessonText],
+    articles: &[KbArticleText],
+    transcripts: Vec<FetchedTranscript>,
+) -> Vec<SourceDocument> {
+    let mut course_doc = SourceDocument::new(None);
+
+    let mut description = vec![course.short_description.clone()];
+    description.extend(course.full_description.clone());
+    description.extend(course.learning_objectives.iter().cloned());
+    course_doc.push(EmbeddingContentType::Description, &course.title, description.join("\n"));
+
+    for article in articles {
+        course_doc.push(EmbeddingContentType::Resource, &article.title, article.content.clone());
+    }
+
+    let mut lesson_docs: Vec<(Uuid, &str, SourceDocument)> = lessons
+        .iter()
+        .map(|lesson| {
+            let mut doc = SourceDocument::new(Some(lesson.lesson_id));
+            if let Some(text) = lesson_text(lesson) {
+                doc.push(EmbeddingContentType::LessonText, &lesson.title, text.to_string());
+            }
+            (lesson.lesson_id, lesson.title.as_str(), doc)
+        })
+        .collect();
+
+    for fetched in transcripts {
+        let transcription = fetched.transcription;
+        let target = transcription
+            .lesson_id
+            .and_then(|id| lesson_docs.iter_mut().find(|(lesson_id, _, _)| *lesson_id == id));
+
+        let (title, doc) = match target {
+            Some((_, title, doc)) => (title.to_string(), doc),
+            None => (course.title.clone(), &mut course_doc),
+        };
+
+        match fetched.text {
+            Ok(text) => {
+                let text = subtitle_to_text(&text, &transcription.format);
+                doc.push(EmbeddingContentType::Transcription, &title, text);
+            }
+            Err(_) => doc.complete = false,
+        }
+    }
+
+    std::iter::once(course_doc)
+        .chain(lesson_docs.into_iter().map(|(_, _, doc)| doc))
+        .filter(|doc| !doc.parts.is_empty() || !doc.complete)
+        .collect()
+}
+
+/// Article lessons keep their text in `content_ref`; for other lesson types
+/// it is a storage key or an id.
+fn lesson_text(lesson: &LessonText) -> Option<&str> {
+    let content = lesson.content_ref.as_deref()?.trim();
+    (lesson.content_type == "article" && content.contains(char::is_whitespace)).then_some(content)
+}
+
+// =============================================================================
+// EMBEDDING
+// =============================================================================
+
+/// Embeds texts in batches, retrying failed requests with exponential backoff.
+pub async fn embed_in_batches(
+    llm_client: &dyn LLMClient,
+    texts: &[String],
+    config: &IndexingConfig,
+) -> AIResult<Vec<Vec<f32>>> {
+    let mut embeddings = Vec::with_capacity(texts.len());
+
+    for batch in texts.chunks(config.batch_size.max(1)) {
+        let mut attempt = 0;
+        let mut backoff = config.initial_backoff;
+
+        loop {
+            let result = llm_client.generate_embeddings(batch).await.and_then(|vectors| {
+                if vectors.len() == batch.len() {
+                    Ok(vectors)
+                } else {
+                    Err(format!("Expected {} embeddings, got {}", batch.len(), vectors.len()))
+                }
+            });
+
+            match result {
+                Ok(vectors) => {
+                    embeddings.extend(vectors);
+                    break;
+                }
+                Err(e) if attempt < config.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "Embedding batch failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt, config.max_retries, backoff, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(config.max_backoff);
+                }
+                Err(e) => return Err(AIError::EmbeddingService(e)),
+            }
+        }
+    }
+
+    Ok(embeddings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{GeneratedQuestion, GlossaryTerm, KeyPoint, QuizGenerationConfig, TutorMessage};
+    use crate::llm::MockLLMClient;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    fn course() -> CourseText {
+        CourseText {
+            course_id: Uuid::nil(),
+            slug: "rust-101".to_string(),
+            title: "Rust 101".to_string(),
+            short_description: "Introducción a Rust.".to_string(),
+            full_description: None,
+            learning_objectives: vec!["Entender ownership".to_string()],
+        }
+    }
+
+    fn lesson(id: u128, content_type: &str, content_ref: Option<&str>) -> LessonText {
+        LessonText {
+            lesson_id: Uuid::from_u128(id),
+            title: format!("Lección {}", id),
+            content_type: content_type.to_string(),
+            content_ref: content_ref.map(str::to_string),
+        }
+    }
+
+    fn transcript(lesson_id: Option<u128>, text: Result<&str, &str>) -> FetchedTranscript {
+        FetchedTranscript {
+            transcription: TranscriptionRef {
+                asset_id: Uuid::now_v7(),
+                lesson_id: lesson_id.map(Uuid::from_u128),
+                language: "es".to_string(),
+                format: "vtt".to_string(),
+            },
+            text: text.map(str::to_string).map_err(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_subtitle_to_text() {
+        let vtt = "WEBVTT\n\nNOTE generado\nautomáticamente\n\n1\n00:00:00.000 --> 00:00:02.000\n<v Ana>Hola a todos</v>\n\n\
+            2\n00:00:02.000 --> 00:00:04.000\nHola a todos\n\n3\n00:00:04.000 --> 00:00:06.000\nhoy vemos traits\n";
+
+        assert_eq!(subtitle_to_text(vtt, "vtt"), "Hola a todos hoy vemos traits");
+        assert_eq!(subtitle_to_text("  texto plano \n", "txt"), "texto plano");
+    }
+
+    #[test]
+    fn test_build_documents_groups_by_lesson() {
+        let lessons = vec![
+            lesson(1, "article", Some("Los traits definen comportamiento compartido.")),
+            lesson(2, "video", Some("courses/rust/video.mp4")),
+            lesson(3, "quiz", Some("quiz-id")),
+        ];
+        let articles = vec![KbArticleText {
+            article_id: Uuid::nil(),
+            title: "Instalar Rust".to_string(),
+            content: "Usa rustup para instalar.".to_string(),
+        }];
+        let transcripts = vec![
+            transcript(Some(2), Ok("WEBVTT\n\n00:00.000 --> 00:01.000\nEl borrow checker")),
+            transcript(None, Ok("Bienvenida al curso")),
+        ];
+
+        let docs = build_documents(&course(), &lessons, &articles, transcripts);
+
+        // course + lesson 1 (text) + lesson 2 (transcript); lesson 3 has no text
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[0].source_key(), COURSE_SOURCE_KEY);
+        let course_types: Vec<_> = docs[0].parts.iter().map(|p| p.content_type).collect();
+        assert_eq!(course_types, vec![
+            EmbeddingContentType::Description,
+            EmbeddingContentType::Resource,
+            EmbeddingContentType::Transcription,
+        ]);
+        assert_eq!(docs[1].parts[0].content_type, EmbeddingContentType::LessonText);
+        assert_eq!(docs[2].lesson_id, Some(Uuid::from_u128(2)));
+        assert_eq!(docs[2].parts[0].text, "El borrow checker");
+        assert_eq!(docs[2].parts[0].title, "Lección 2");
+    }
+
+    #[test]
+    fn test_failed_transcript_marks_document_incomplete() {
+        let lessons = vec![lesson(1, "video", None)];
+
+        let docs = build_documents(&course(), &lessons, &[], vec![transcript(Some(1), Err("timeout"))]);
+
+        assert_eq!(docs.len(), 2);
+        assert!(docs[0].complete);
+        assert!(!docs[1].complete);
+    }
+
+    #[test]
+    fn test_content_hash_tracks_changes() {
+        let lessons = vec![lesson(1, "article", Some("Texto original de la lección."))];
+        let changed = vec![lesson(1, "article", Some("Texto editado de la lección."))];
+
+        let a = build_documents(&course(), &lessons, &[], vec![]);
+        let b = build_documents(&course(), &lessons, &[], vec![]);
+        let c = build_documents(&course(), &changed, &[], vec![]);
+
+        assert_eq!(a[1].content_hash(), b[1].content_hash());
+        assert_ne!(a[1].content_hash(), c[1].content_hash());
+        // The course document is not affected by the lesson edit
+        assert_eq!(a[0].content_hash(), c[0].content_hash());
+    }
+
+    /// Fails the first `failures` batch requests, records batch sizes.
+    struct FlakyEmbeddings {
+        inner: MockLLMClient,
+        failures: AtomicU32,
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl LLMClient for FlakyEmbeddings {
+        async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, String> {
+            self.inner.generate_embedding(text).await
+        }
+
+        async fn generate_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+            self.batches.lock().unwrap().push(texts.len());
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err("429 Too Many Requests".to_string());
+            }
+            self.inner.generate_embeddings(texts).await
+        }
+
+        async fn generate_tutor_response(
+            &self,
+            query: &str,
+            history: &[TutorMessage],
+            context: &str,
+            course_id: Uuid,
+        ) -> Result<(String, i32), String> {
+            self.inner.generate_tutor_response(query, history, context, course_id).await
+        }
+
+        async fn generate_summary(&self, content: &str, language: &str) -> Result<(String, i32), String> {
+            self.inner.generate_summary(content, language).await
+        }
+
+        async fn generate_key_points(&self, content: &str, language: &str) -> Result<(Vec<KeyPoint>, i32), String> {
+            self.inner.generate_key_points(content, language).await
+        }
+
+        async fn generate_glossary(&self, content: &str, language: &str) -> Result<(Vec<GlossaryTerm>, i32), String> {
+            self.inner.generate_glossary(content, language).await
+        }
+
+        async fn generate_quiz(&self, content: &str, config: &QuizGenerationConfig) -> Result<Vec<GeneratedQuestion>, String> {
+            self.inner.generate_quiz(content, config).await
+        }
+    }
+
+    fn fast_config(max_retries: u32) -> IndexingConfig {
+        IndexingConfig {
+            batch_size: 2,
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_in_batches_retries() {
+        let client = FlakyEmbeddings {
+            inner: MockLLMClient::new(8),
+            failures: AtomicU32::new(2),
+            batches: Mutex::new(Vec::new()),
+        };
+        let texts: Vec<String> = (0..5).map(|i| format!("chunk {}", i)).collect();
+
+        let embeddings = embed_in_batches(&client, &texts, &fast_config(3)).await.unwrap();
+
+        assert_eq!(embeddings.len(), 5);
+        assert_eq!(embeddings[4], client.inner.generate_embedding("chunk 4").await.unwrap());
+        // Two failed attempts, then three batches of 2, 2 and 1
+        assert_eq!(*client.batches.lock().unwrap(), vec![2, 2, 2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_embed_in_batches_gives_up() {
+        let client = FlakyEmbeddings {
+            inner: MockLLMClient::new(8),
+            failures: AtomicU32::new(10),
+            batches: Mutex::new(Vec::new()),
+        };
+        let texts = vec!["chunk".to_string()];
+
+        let err = embed_in_batches(&client, &texts, &fast_config(2)).await.unwrap_err();
+
+        assert!(matches!(err, AIError::EmbeddingService(_)));
+        assert_eq!(client.batches.lock().unwrap().len(), 3);
+    }
+}
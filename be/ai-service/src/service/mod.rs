@@ -2,12 +2,17 @@
 //!
 //! Business logic layer for AI Service
 
+pub mod chunking;
+pub mod content_ingestion;
 pub mod tutor_service;
 pub mod semantic_search_service;
 pub mod summary_service;
 pub mod quiz_generator_service;
+pub mod usage_meter;
 
 pub use tutor_service::TutorService;
 pub use semantic_search_service::SemanticSearchService;
+pub use content_ingestion::{ContentServiceTranscripts, IndexingConfig, TranscriptFetcher};
 pub use summary_service::SummaryService;
 pub use quiz_generator_service::QuizGeneratorService;
+pub use usage_meter::{RedisUsageCounters, UsageCounters, UsageMeter, UsageStore};
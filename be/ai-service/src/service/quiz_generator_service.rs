@@ -7,26 +7,31 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::domain::{
-    AIError, AIResult, DifficultyLevel, GeneratedQuestion, GenerationStatus,
-    QuestionType, QuizGenerationConfig, QuizGenerationRequest,
+    AIError, AIFeature, AIResult, DifficultyLevel, GeneratedQuestion, GenerationStatus,
+    QuestionType, QuizGenerationConfig, QuizGenerationRequest, TokenUsage,
 };
 use crate::llm::LLMClient;
 use crate::repository::QuizGenerationRepository;
+use crate::service::chunking::estimate_tokens;
+use crate::service::UsageMeter;
 
 /// Service for quiz generation.
 pub struct QuizGeneratorService {
     quiz_repo: QuizGenerationRepository,
     llm_client: Arc<dyn LLMClient>,
+    usage_meter: Arc<UsageMeter>,
 }
 
 impl QuizGeneratorService {
     pub fn new(
         quiz_repo: QuizGenerationRepository,
         llm_client: Arc<dyn LLMClient>,
+        usage_meter: Arc<UsageMeter>,
     ) -> Self {
         Self {
             quiz_repo,
             llm_client,
+            usage_meter,
         }
     }
 
@@ -68,40 +73,51 @@ impl QuizGeneratorService {
             completed_at: None,
         };
 
-        self.quiz_repo.create_request(&request).await?;
-
-        // Fetch lesson content
-        let content = self.fetch_lesson_content(lesson_id).await?;
-
-        // Generate quiz using LLM
-        match self.llm_client
-            .generate_quiz(&content, &config)
+        // Requests rejected by the quota leave no record behind
+        self.usage_meter
+            .track(instructor_id, AIFeature::QuizGeneration, async {
+                self.quiz_repo.create_request(&request).await?;
+
+                // Fetch lesson content
+                let content = self.fetch_lesson_content(lesson_id).await?;
+
+                // Generate quiz using LLM
+                match self.llm_client
+                    .generate_quiz(&content, &config)
+                    .await
+                {
+                    Ok(questions) => {
+                        // Save generated questions
+                        self.quiz_repo.save_questions_batch(&questions).await?;
+
+                        // Update status to completed
+                        self.quiz_repo.update_status(request_id, GenerationStatus::Completed).await?;
+
+                        // The provider does not report tokens for quizzes
+                        let usage = TokenUsage {
+                            input: estimate_tokens(&content) as i32,
+                            output: questions_tokens(&questions),
+                        };
+
+                        Ok((QuizGenerationRequest {
+                            request_id,
+                            course_id,
+                            lesson_id,
+                            instructor_id,
+                            config: config.clone(),
+                            status: GenerationStatus::Completed,
+                            created_at: now,
+                            completed_at: Some(Utc::now()),
+                        }, usage))
+                    }
+                    Err(e) => {
+                        // Update status to failed
+                        self.quiz_repo.update_status(request_id, GenerationStatus::Failed).await?;
+                        Err(AIError::QuizGenerationFailed(e))
+                    }
+                }
+            })
             .await
-        {
-            Ok(questions) => {
-                // Save generated questions
-                self.quiz_repo.save_questions_batch(&questions).await?;
-
-                // Update status to completed
-                self.quiz_repo.update_status(request_id, GenerationStatus::Completed).await?;
-
-                Ok(QuizGenerationRequest {
-                    request_id,
-                    course_id,
-                    lesson_id,
-                    instructor_id,
-                    config,
-                    status: GenerationStatus::Completed,
-                    created_at: now,
-                    completed_at: Some(Utc::now()),
-                })
-            }
-            Err(e) => {
-                // Update status to failed
-                self.quiz_repo.update_status(request_id, GenerationStatus::Failed).await?;
-                Err(AIError::QuizGenerationFailed(e))
-            }
-        }
     }
 
     /// Gets generation status.
@@ -130,3 +146,15 @@ impl QuizGeneratorService {
         Ok("Lesson content would be fetched from courses-service".to_string())
     }
 }
+
+/// Estimated tokens of the generated questions.
+fn questions_tokens(questions: &[GeneratedQuestion]) -> i32 {
+    questions
+        .iter()
+        .map(|q| {
+            estimate_tokens(&q.question_text)
+                + q.options.iter().flatten().map(|o| estimate_tokens(&o.text)).sum::<usize>()
+                + q.explanation.as_deref().map(estimate_tokens).unwrap_or(0)
+        })
+        .sum::<usize>() as i32
+}
@@ -2,33 +2,62 @@
 //!
 //! Business logic for semantic search across course content.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use chrono::Utc;
+use shared::service_client::ServiceClient;
 use uuid::Uuid;
 
 use crate::domain::{
-    AIError, AIResult, ContentEmbedding, EmbeddingContentType, SemanticSearchResult,
+    AIError, AIFeature, AIResult, ContentEmbedding, IndexingReport, SemanticSearchResult,
+    TokenUsage,
 };
 use crate::llm::LLMClient;
-use crate::repository::EmbeddingRepository;
+use crate::repository::{CourseContentRepository, EmbeddingRepository, IndexedSource};
+use crate::service::content_ingestion::{
+    build_documents, embed_in_batches, FetchedTranscript, IndexingConfig, SourceDocument,
+    TranscriptFetcher,
+};
+use crate::service::chunking::estimate_tokens;
+use crate::service::UsageMeter;
 
 /// Service for semantic search operations.
 pub struct SemanticSearchService {
     embedding_repo: EmbeddingRepository,
+    content_repo: CourseContentRepository,
+    transcripts: Arc<dyn TranscriptFetcher>,
     llm_client: Arc<dyn LLMClient>,
+    usage_meter: Arc<UsageMeter>,
+    /// Enrollment and instructor lookups for `has_access`
+    services: ServiceClient,
+    indexing: IndexingConfig,
 }
 
 impl SemanticSearchService {
     pub fn new(
         embedding_repo: EmbeddingRepository,
+        content_repo: CourseContentRepository,
+        transcripts: Arc<dyn TranscriptFetcher>,
         llm_client: Arc<dyn LLMClient>,
+        usage_meter: Arc<UsageMeter>,
+        services: ServiceClient,
     ) -> Self {
         Self {
             embedding_repo,
+            content_repo,
+            transcripts,
             llm_client,
+            usage_meter,
+            services,
+            indexing: IndexingConfig::default(),
         }
     }
 
+    pub fn with_indexing_config(mut self, indexing: IndexingConfig) -> Self {
+        self.indexing = indexing;
+        self
+    }
+
     /// Performs semantic search.
     pub async fn search(
         &self,
@@ -42,11 +71,19 @@ impl SemanticSearchService {
             return Err(AIError::EmptyQuery);
         }
 
-        // Generate embedding for query
-        let query_embedding = self.llm_client
-            .generate_embedding(query)
-            .await
-            .map_err(|e| AIError::EmbeddingService(e))?;
+        // Generate embedding for query (metered for signed-in users)
+        let embed = async {
+            let embedding = self.llm_client
+                .generate_embedding(query)
+                .await
+                .map_err(AIError::EmbeddingService)?;
+            let usage = TokenUsage { input: estimate_tokens(query) as i32, output: 0 };
+            Ok((embedding, usage))
+        };
+        let query_embedding = match user_id {
+            Some(uid) => self.usage_meter.track(uid, AIFeature::SemanticSearch, embed).await?,
+            None => embed.await?.0,
+        };
 
         // Search for similar content
         let course_ids_ref = course_ids.as_deref();
@@ -54,10 +91,19 @@ impl SemanticSearchService {
             .search_similar(&query_embedding, course_ids_ref, limit, min_score)
             .await?;
 
-        // Check access for each result
+        // Check access once per course in the results
         if let Some(uid) = user_id {
+            let mut access: HashMap<Uuid, bool> = HashMap::new();
             for result in &mut results {
-                result.has_access = self.check_user_access(uid, result.course_id).await;
+                let has_access = match access.get(&result.course_id) {
+                    Some(&has_access) => has_access,
+                    None => {
+                        let has_access = self.check_user_access(uid, result.course_id).await;
+                        access.insert(result.course_id, has_access);
+                        has_access
+                    }
+                };
+                result.has_access = has_access;
             }
         }
 
@@ -65,66 +111,151 @@ impl SemanticSearchService {
     }
 
     /// Indexes course content for semantic search.
-    pub async fn index_course(&self, course_id: Uuid, force_reindex: bool) -> AIResult<i32> {
-        // Check if already indexed
-        if !force_reindex && self.embedding_repo.has_embeddings(course_id).await? {
-            return Ok(0);
-        }
+    ///
+    /// Only lessons whose content hash changed since the last run are
+    /// re-embedded; `force_reindex` drops the whole index first.
+    pub async fn index_course(&self, course_id: Uuid, force_reindex: bool) -> AIResult<IndexingReport> {
+        let documents = self.fetch_course_content(course_id).await?;
 
-        // Delete existing embeddings if reindexing
         if force_reindex {
             self.embedding_repo.delete_course_embeddings(course_id).await?;
+            self.content_repo.clear_indexed_sources(course_id).await?;
         }
 
-        // Fetch course content (this would normally call courses-service)
-        let content_chunks = self.fetch_course_content(course_id).await?;
+        let indexed: HashMap<String, IndexedSource> = self.content_repo
+            .list_indexed_sources(course_id)
+            .await?
+            .into_iter()
+            .map(|source| (source.source_key.clone(), source))
+            .collect();
 
-        // Generate embeddings for each chunk
-        let mut embeddings = Vec::new();
-        for (i, chunk) in content_chunks.iter().enumerate() {
-            let embedding_vec = self.llm_client
-                .generate_embedding(&chunk.text)
-                .await
-                .map_err(|e| AIError::EmbeddingService(e))?;
+        let mut report = IndexingReport {
+            course_id,
+            ..Default::default()
+        };
 
-            let embedding = ContentEmbedding {
-                embedding_id: Uuid::now_v7(),
-                course_id,
-                lesson_id: chunk.lesson_id,
-                content_type: chunk.content_type,
-                chunk_index: i as i32,
-                content_text: chunk.text.clone(),
-                embedding: embedding_vec,
-                metadata: None,
-                created_at: Utc::now(),
+        for document in &documents {
+            if !document.complete {
+                report.sources_failed += 1;
+                continue;
+            }
+
+            let source = IndexedSource {
+                source_key: document.source_key(),
+                lesson_id: document.lesson_id,
+                content_hash: document.content_hash(),
             };
 
-            embeddings.push(embedding);
+            if indexed.get(&source.source_key).map(|s| &s.content_hash) == Some(&source.content_hash) {
+                report.sources_unchanged += 1;
+                continue;
+            }
+
+            let count = self.index_document(course_id, document, &source).await?;
+            self.content_repo.save_indexed_source(course_id, &source, count).await?;
+
+            report.chunks_indexed += count;
+            report.sources_indexed += 1;
+        }
+
+        // Lessons deleted or left without text
+        let current: HashSet<String> = documents.iter().map(|d| d.source_key()).collect();
+        for source in indexed.values().filter(|s| !current.contains(&s.source_key)) {
+            self.embedding_repo.delete_lesson_embeddings(course_id, source.lesson_id).await?;
+            self.content_repo.delete_indexed_source(course_id, &source.source_key).await?;
+            report.sources_removed += 1;
         }
 
-        // Save embeddings
-        let count = self.embedding_repo.save_embeddings_batch(&embeddings).await?;
+        tracing::info!(
+            course_id = %course_id,
+            chunks = report.chunks_indexed,
+            indexed = report.sources_indexed,
+            unchanged = report.sources_unchanged,
+            removed = report.sources_removed,
+            failed = report.sources_failed,
+            "Course content indexed"
+        );
 
-        Ok(count)
+        Ok(report)
     }
 
-    /// Checks if user has access to a course.
-    async fn check_user_access(&self, _user_id: Uuid, _course_id: Uuid) -> bool {
-        // TODO: Call enrollments-service to check access
-        true
+    /// Chunks, embeds and stores one document, replacing its previous chunks.
+    async fn index_document(
+        &self,
+        course_id: Uuid,
+        document: &SourceDocument,
+        source: &IndexedSource,
+    ) -> AIResult<i32> {
+        let chunks = document.chunks(&self.indexing.chunking);
+        let texts: Vec<String> = chunks.iter().map(|c| c.text.clone()).collect();
+        let vectors = embed_in_batches(self.llm_client.as_ref(), &texts, &self.indexing).await?;
+
+        let embeddings: Vec<ContentEmbedding> = chunks
+            .into_iter()
+            .zip(vectors)
+            .enumerate()
+            .map(|(i, (chunk, embedding))| ContentEmbedding {
+                embedding_id: Uuid::now_v7(),
+                course_id,
+                lesson_id: document.lesson_id,
+                content_type: chunk.content_type,
+                chunk_index: i as i32,
+                content_text: chunk.text,
+                embedding,
+                metadata: Some(serde_json::json!({
+                    "source": source.source_key,
+                    "title": chunk.title,
+                    "content_hash": source.content_hash,
+                })),
+                created_at: Utc::now(),
+            })
+            .collect();
+
+        self.embedding_repo
+            .replace_lesson_embeddings(course_id, document.lesson_id, &embeddings)
+            .await
     }
 
-    /// Fetches course content for indexing.
-    async fn fetch_course_content(&self, _course_id: Uuid) -> AIResult<Vec<ContentChunk>> {
-        // TODO: Call courses-service to fetch content
-        // For now, return empty - this would be populated by actual course content
-        Ok(vec![])
+    /// Checks if user has access to a course (enrolled or teaching it).
+    /// Lookup failures deny access: results are still returned, marked as
+    /// not accessible.
+    async fn check_user_access(&self, user_id: Uuid, course_id: Uuid) -> bool {
+        self.services
+            .can_access_course(user_id, course_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(%user_id, %course_id, error = %e, "Course access check failed");
+                false
+            })
     }
-}
 
-/// A chunk of content for embedding.
-struct ContentChunk {
-    text: String,
-    lesson_id: Option<Uuid>,
-    content_type: EmbeddingContentType,
+    /// Fetches course content for indexing: lesson text, KB articles
+    /// tagged with the course and asset transcriptions.
+    async fn fetch_course_content(&self, course_id: Uuid) -> AIResult<Vec<SourceDocument>> {
+        let course = self.content_repo
+            .get_course(course_id)
+            .await?
+            .ok_or(AIError::CourseNotFound(course_id))?;
+
+        let lessons = self.content_repo.list_lessons(course_id).await?;
+        let articles = self.content_repo.list_kb_articles(course_id, &course.slug).await?;
+
+        let mut transcripts = Vec::new();
+        for transcription in self.content_repo.list_transcriptions(course_id).await? {
+            let text = self.transcripts
+                .fetch_transcript(transcription.asset_id, &transcription.language)
+                .await;
+
+            if let Err(e) = &text {
+                tracing::warn!(
+                    asset_id = %transcription.asset_id,
+                    "Could not fetch transcription, keeping previous index: {}", e
+                );
+            }
+
+            transcripts.push(FetchedTranscript { transcription, text });
+        }
+
+        Ok(build_documents(&course, &lessons, &articles, transcripts))
+    }
 }
@@ -7,32 +7,38 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::domain::{
-    AIError, AIResult, ContentGenerationType, ContentSummary,
-    GenerationStatus, GlossaryTerm, KeyPoint,
+    AIError, AIFeature, AIResult, ContentGenerationType, ContentSummary,
+    GenerationStatus, GlossaryTerm, KeyPoint, TokenUsage,
 };
 use crate::llm::LLMClient;
 use crate::repository::SummaryRepository;
+use crate::service::chunking::estimate_tokens;
+use crate::service::UsageMeter;
 
 /// Service for content summary generation.
 pub struct SummaryService {
     summary_repo: SummaryRepository,
     llm_client: Arc<dyn LLMClient>,
+    usage_meter: Arc<UsageMeter>,
 }
 
 impl SummaryService {
     pub fn new(
         summary_repo: SummaryRepository,
         llm_client: Arc<dyn LLMClient>,
+        usage_meter: Arc<UsageMeter>,
     ) -> Self {
         Self {
             summary_repo,
             llm_client,
+            usage_meter,
         }
     }
 
     /// Generates a summary for a lesson.
     pub async fn generate_summary(
         &self,
+        user_id: Uuid,
         course_id: Uuid,
         lesson_id: Uuid,
         language: &str,
@@ -65,13 +71,20 @@ impl SummaryService {
             updated_at: now,
         };
 
-        self.summary_repo.create_summary(&summary).await?;
-
         // Generate summary using LLM
-        let (summary_text, tokens_used) = self.llm_client
-            .generate_summary(&content, language)
-            .await
-            .map_err(|e| AIError::GenerationFailed(e))?;
+        let (summary_text, tokens_used) = self.usage_meter
+            .track(user_id, AIFeature::SummaryGeneration, async {
+                self.summary_repo.create_summary(&summary).await?;
+
+                let (summary_text, tokens_used) = self.llm_client
+                    .generate_summary(&content, language)
+                    .await
+                    .map_err(AIError::GenerationFailed)?;
+
+                let usage = TokenUsage::from_total(estimate_tokens(&content) as i32, tokens_used);
+                Ok(((summary_text, tokens_used), usage))
+            })
+            .await?;
 
         // Update summary with generated content
         self.summary_repo
@@ -97,6 +110,7 @@ impl SummaryService {
     /// Generates key points for a lesson.
     pub async fn generate_key_points(
         &self,
+        user_id: Uuid,
         course_id: Uuid,
         lesson_id: Uuid,
         language: &str,
@@ -116,10 +130,17 @@ impl SummaryService {
         let content = self.fetch_lesson_content(lesson_id).await?;
 
         // Generate key points using LLM
-        let (key_points, tokens_used) = self.llm_client
-            .generate_key_points(&content, language)
-            .await
-            .map_err(|e| AIError::GenerationFailed(e))?;
+        let (key_points, tokens_used) = self.usage_meter
+            .track(user_id, AIFeature::SummaryGeneration, async {
+                let (key_points, tokens_used) = self.llm_client
+                    .generate_key_points(&content, language)
+                    .await
+                    .map_err(AIError::GenerationFailed)?;
+
+                let usage = TokenUsage::from_total(estimate_tokens(&content) as i32, tokens_used);
+                Ok(((key_points, tokens_used), usage))
+            })
+            .await?;
 
         // Store key points
         let summary_id = Uuid::now_v7();
@@ -149,6 +170,7 @@ impl SummaryService {
     /// Generates a glossary for a lesson.
     pub async fn generate_glossary(
         &self,
+        user_id: Uuid,
         course_id: Uuid,
         lesson_id: Uuid,
         language: &str,
@@ -168,10 +190,17 @@ impl SummaryService {
         let content = self.fetch_lesson_content(lesson_id).await?;
 
         // Generate glossary using LLM
-        let (terms, tokens_used) = self.llm_client
-            .generate_glossary(&content, language)
-            .await
-            .map_err(|e| AIError::GenerationFailed(e))?;
+        let (terms, tokens_used) = self.usage_meter
+            .track(user_id, AIFeature::SummaryGeneration, async {
+                let (terms, tokens_used) = self.llm_client
+                    .generate_glossary(&content, language)
+                    .await
+                    .map_err(AIError::GenerationFailed)?;
+
+                let usage = TokenUsage::from_total(estimate_tokens(&content) as i32, tokens_used);
+                Ok(((terms, tokens_used), usage))
+            })
+            .await?;
 
         // Store glossary
         let summary_id = Uuid::now_v7();
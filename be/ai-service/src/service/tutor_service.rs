@@ -6,19 +6,21 @@ use std::sync::Arc;
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::api::UsageQuotaResponse;
 use crate::domain::{
-    AIError, AIResult, ContentReference, CreateTutorSession, MessageRole,
-    TutorMessage, TutorSession,
+    AIError, AIFeature, AIResult, ContentReference, CreateTutorSession, MessageRole,
+    TokenUsage, TutorMessage, TutorSession,
 };
 use crate::llm::LLMClient;
 use crate::repository::{ConversationRepository, EmbeddingRepository};
+use crate::service::chunking::estimate_tokens;
+use crate::service::UsageMeter;
 
 /// Service for AI tutor operations.
 pub struct TutorService {
     conversation_repo: ConversationRepository,
     embedding_repo: EmbeddingRepository,
     llm_client: Arc<dyn LLMClient>,
+    usage_meter: Arc<UsageMeter>,
 }
 
 impl TutorService {
@@ -26,11 +28,13 @@ impl TutorService {
         conversation_repo: ConversationRepository,
         embedding_repo: EmbeddingRepository,
         llm_client: Arc<dyn LLMClient>,
+        usage_meter: Arc<UsageMeter>,
     ) -> Self {
         Self {
             conversation_repo,
             embedding_repo,
             llm_client,
+            usage_meter,
         }
     }
 
@@ -89,27 +93,39 @@ impl TutorService {
             created_at: Utc::now(),
         };
 
-        // Save user message
-        self.conversation_repo.save_message(&user_message).await?;
-
-        // Get conversation history for context
-        let history = self.conversation_repo.get_recent_messages(session_id, 10).await?;
-
-        // Search for relevant content
-        let references = self.find_relevant_content(
-            content,
-            session.course_id,
-            lesson_id,
-        ).await?;
-
-        // Build context from references
-        let context = self.build_context(&references);
-
-        // Generate response using LLM
-        let (response_text, tokens_used) = self.llm_client
-            .generate_tutor_response(content, &history, &context, session.course_id)
-            .await
-            .map_err(|e| AIError::LLMError(e))?;
+        // The message is only stored once the quota allows an answer
+        let (response_text, tokens_used, references) = self.usage_meter
+            .track(user_id, AIFeature::TutorChat, async {
+                // Save user message
+                self.conversation_repo.save_message(&user_message).await?;
+
+                // Get conversation history for context
+                let history = self.conversation_repo.get_recent_messages(session_id, 10).await?;
+
+                // Search for relevant content
+                let references = self.find_relevant_content(
+                    content,
+                    session.course_id,
+                    lesson_id,
+                ).await?;
+
+                // Build context from references
+                let context = self.build_context(&references);
+
+                // Generate response using LLM
+                let (response_text, tokens_used) = self.llm_client
+                    .generate_tutor_response(content, &history, &context, session.course_id)
+                    .await
+                    .map_err(AIError::LLMError)?;
+
+                let prompt_tokens = estimate_tokens(content)
+                    + estimate_tokens(&context)
+                    + history.iter().map(|m| estimate_tokens(&m.content)).sum::<usize>();
+                let usage = TokenUsage::from_total(prompt_tokens as i32, tokens_used);
+
+                Ok(((response_text, tokens_used, references), usage))
+            })
+            .await?;
 
         // Create assistant message
         let assistant_message = TutorMessage {
@@ -145,57 +161,6 @@ impl TutorService {
         self.conversation_repo.get_messages(session_id, limit, offset).await
     }
 
-    /// Gets user's AI usage quota.
-    pub async fn get_usage_quota(&self, _user_id: Uuid) -> AIResult<UsageQuotaResponse> {
-        // TODO: Implement actual quota tracking
-        let now = Utc::now();
-        let tomorrow = now + chrono::Duration::days(1);
-        let next_month = now + chrono::Duration::days(30);
-
-        Ok(UsageQuotaResponse {
-            tutor_chat: crate::api::FeatureQuotaResponse {
-                daily_limit: 50,
-                daily_used: 0,
-                daily_remaining: 50,
-                monthly_limit: 500,
-                monthly_used: 0,
-                monthly_remaining: 500,
-                reset_daily_at: tomorrow,
-                reset_monthly_at: next_month,
-            },
-            semantic_search: crate::api::FeatureQuotaResponse {
-                daily_limit: 100,
-                daily_used: 0,
-                daily_remaining: 100,
-                monthly_limit: 1000,
-                monthly_used: 0,
-                monthly_remaining: 1000,
-                reset_daily_at: tomorrow,
-                reset_monthly_at: next_month,
-            },
-            summary_generation: crate::api::FeatureQuotaResponse {
-                daily_limit: 20,
-                daily_used: 0,
-                daily_remaining: 20,
-                monthly_limit: 200,
-                monthly_used: 0,
-                monthly_remaining: 200,
-                reset_daily_at: tomorrow,
-                reset_monthly_at: next_month,
-            },
-            quiz_generation: crate::api::FeatureQuotaResponse {
-                daily_limit: 10,
-                daily_used: 0,
-                daily_remaining: 10,
-                monthly_limit: 100,
-                monthly_used: 0,
-                monthly_remaining: 100,
-                reset_daily_at: tomorrow,
-                reset_monthly_at: next_month,
-            },
-        })
-    }
-
     /// Finds relevant content for the query.
     async fn find_relevant_content(
         &self,
@@ -0,0 +1,530 @@
+//! # Usage Meter
+//!
+//! Per-user AI quotas. Every LLM call reserves one request against the
+//! daily and monthly counters of its feature *before* reaching the provider
+//! and records the tokens it consumed afterwards; failed calls release
+//! their reservation.
+//!
+//! Counters live in Redis (`ai_usage:{user_id}:{feature}:{metric}:{period}`)
+//! and expire with their period. Every call is also stored in
+//! `ai.usage_records` for billing. Limits depend on the plan tier of the
+//! user's subscription and can be overridden per plan through
+//! `limits.ai_quotas` in `subscriptions.plans`, e.g.
+//! `{"ai_quotas": {"tutor_chat": {"daily": 80, "monthly": 1200}}}`.
+//! A limit of 0 disables the feature for the plan.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use shared::redis_client::RedisClient;
+use uuid::Uuid;
+
+use crate::domain::{
+    AIError, AIFeature, AIResult, AIUsage, FeatureLimits, PlanTier, TokenUsage, UsageQuota,
+    UserPlan,
+};
+use crate::repository::UsageRepository;
+
+/// Features exposed through the quota endpoint.
+pub const METERED_FEATURES: [AIFeature; 4] = [
+    AIFeature::TutorChat,
+    AIFeature::SemanticSearch,
+    AIFeature::SummaryGeneration,
+    AIFeature::QuizGeneration,
+];
+
+/// Counters outlive their period a little so late reads still see them.
+const DAILY_TTL: Duration = Duration::from_secs(2 * 24 * 3600);
+const MONTHLY_TTL: Duration = Duration::from_secs(32 * 24 * 3600);
+
+/// Default limits per plan tier.
+pub fn default_limits(tier: PlanTier, feature: AIFeature) -> FeatureLimits {
+    let (daily, monthly) = match (tier, feature) {
+        (PlanTier::Free, AIFeature::TutorChat) => (20, 200),
+        (PlanTier::Free, AIFeature::SemanticSearch) => (50, 500),
+        (PlanTier::Free, AIFeature::SummaryGeneration) => (5, 50),
+        (PlanTier::Free, AIFeature::QuizGeneration) => (3, 30),
+        (PlanTier::Free, AIFeature::TranscriptionGeneration) => (2, 10),
+
+        (PlanTier::Basic, AIFeature::TutorChat) => (50, 500),
+        (PlanTier::Basic, AIFeature::SemanticSearch) => (100, 1000),
+        (PlanTier::Basic, AIFeature::SummaryGeneration) => (20, 200),
+        (PlanTier::Basic, AIFeature::QuizGeneration) => (10, 100),
+        (PlanTier::Basic, AIFeature::TranscriptionGeneration) => (10, 50),
+
+        (PlanTier::Professional, AIFeature::TutorChat) => (200, 3000),
+        (PlanTier::Professional, AIFeature::SemanticSearch) => (500, 10000),
+        (PlanTier::Professional, AIFeature::SummaryGeneration) => (100, 1500),
+        (PlanTier::Professional, AIFeature::QuizGeneration) => (50, 750),
+        (PlanTier::Professional, AIFeature::TranscriptionGeneration) => (50, 500),
+
+        // Custom plans start from enterprise limits and override them
+        (PlanTier::Enterprise | PlanTier::Custom, AIFeature::TutorChat) => (1000, 20000),
+        (PlanTier::Enterprise | PlanTier::Custom, AIFeature::SemanticSearch) => (2000, 50000),
+        (PlanTier::Enterprise | PlanTier::Custom, AIFeature::SummaryGeneration) => (500, 10000),
+        (PlanTier::Enterprise | PlanTier::Custom, AIFeature::QuizGeneration) => (250, 5000),
+        (PlanTier::Enterprise | PlanTier::Custom, AIFeature::TranscriptionGeneration) => (200, 4000),
+    };
+
+    FeatureLimits { daily, monthly }
+}
+
+impl UserPlan {
+    /// Limits of a feature: the plan override, or the tier default.
+    pub fn limits(&self, feature: AIFeature) -> FeatureLimits {
+        self.ai_quotas
+            .get(&feature)
+            .copied()
+            .unwrap_or_else(|| default_limits(self.tier, feature))
+    }
+}
+
+// =============================================================================
+// STORAGE
+// =============================================================================
+
+/// Expiring counters (Redis in production).
+#[async_trait]
+pub trait UsageCounters: Send + Sync {
+    /// Adds `delta` to a counter, which expires after `ttl` once created.
+    /// Returns the new value.
+    async fn increment(&self, key: &str, delta: i64, ttl: Duration) -> Result<i64, String>;
+
+    /// Current value (0 when missing).
+    async fn get(&self, key: &str) -> Result<i64, String>;
+}
+
+/// Usage counters in Redis.
+pub struct RedisUsageCounters {
+    redis: RedisClient,
+}
+
+impl RedisUsageCounters {
+    pub fn new(redis: RedisClient) -> Self {
+        Self { redis }
+    }
+}
+
+#[async_trait]
+impl UsageCounters for RedisUsageCounters {
+    async fn increment(&self, key: &str, delta: i64, ttl: Duration) -> Result<i64, String> {
+        let value = self.redis.incr_by(key, delta).await.map_err(|e| e.to_string())?;
+
+        // First write of the period
+        if value == delta {
+            self.redis.expire(key, ttl).await.map_err(|e| e.to_string())?;
+        }
+
+        Ok(value)
+    }
+
+    async fn get(&self, key: &str) -> Result<i64, String> {
+        let value: Option<i64> = self.redis.get(key).await.map_err(|e| e.to_string())?;
+        Ok(value.unwrap_or(0))
+    }
+}
+
+/// Plans and usage records (PostgreSQL in production).
+#[async_trait]
+pub trait UsageStore: Send + Sync {
+    async fn get_user_plan(&self, user_id: Uuid) -> AIResult<UserPlan>;
+
+    async fn record_usage(&self, usage: &AIUsage) -> AIResult<()>;
+}
+
+#[async_trait]
+impl UsageStore for UsageRepository {
+    async fn get_user_plan(&self, user_id: Uuid) -> AIResult<UserPlan> {
+        UsageRepository::get_user_plan(self, user_id).await
+    }
+
+    async fn record_usage(&self, usage: &AIUsage) -> AIResult<()> {
+        UsageRepository::record_usage(self, usage).await
+    }
+}
+
+// =============================================================================
+// METER
+// =============================================================================
+
+/// Quota periods containing an instant (UTC).
+struct Periods {
+    day: String,
+    month: String,
+    daily_reset: DateTime<Utc>,
+    monthly_reset: DateTime<Utc>,
+}
+
+impl Periods {
+    fn at(now: DateTime<Utc>) -> Self {
+        let today = now.date_naive();
+        let first_of_next_month = if today.month() == 12 {
+            NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1)
+        };
+
+        Self {
+            day: today.format("%Y-%m-%d").to_string(),
+            month: today.format("%Y-%m").to_string(),
+            daily_reset: midnight(today.succ_opt().unwrap_or(today)),
+            monthly_reset: midnight(first_of_next_month.unwrap_or(today)),
+        }
+    }
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn counter_key(user_id: Uuid, feature: AIFeature, metric: &str, period: &str) -> String {
+    format!("ai_usage:{}:{}:{}:{}", user_id, feature, metric, period)
+}
+
+/// Enforces quotas and meters LLM usage.
+pub struct UsageMeter {
+    counters: Arc<dyn UsageCounters>,
+    store: Arc<dyn UsageStore>,
+}
+
+impl UsageMeter {
+    pub fn new(counters: Arc<dyn UsageCounters>, store: Arc<dyn UsageStore>) -> Self {
+        Self { counters, store }
+    }
+
+    /// Runs an LLM call within the user's quota for `feature`.
+    ///
+    /// The request is counted before `call` runs, so concurrent calls
+    /// cannot exceed the limit; if `call` fails the request is released.
+    /// `call` returns its result with the tokens it consumed.
+    pub async fn track<T, F>(&self, user_id: Uuid, feature: AIFeature, call: F) -> AIResult<T>
+    where
+        F: Future<Output = AIResult<(T, TokenUsage)>>,
+    {
+        let limits = self.store.get_user_plan(user_id).await?.limits(feature);
+        let periods = Periods::at(Utc::now());
+
+        self.reserve(user_id, feature, limits, &periods).await?;
+
+        match call.await {
+            Ok((value, tokens)) => {
+                self.record(user_id, feature, tokens, &periods).await;
+                Ok(value)
+            }
+            Err(e) => {
+                self.release(user_id, feature, &periods).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Gets the user's plan and current usage of every metered feature.
+    pub async fn get_quotas(&self, user_id: Uuid) -> AIResult<(PlanTier, Vec<UsageQuota>)> {
+        let plan = self.store.get_user_plan(user_id).await?;
+        let periods = Periods::at(Utc::now());
+
+        let mut quotas = Vec::with_capacity(METERED_FEATURES.len());
+        for feature in METERED_FEATURES {
+            let limits = plan.limits(feature);
+            quotas.push(UsageQuota {
+                user_id,
+                feature,
+                daily_limit: limits.daily,
+                monthly_limit: limits.monthly,
+                used_today: self.read(user_id, feature, "requests", &periods.day).await? as i32,
+                used_this_month: self.read(user_id, feature, "requests", &periods.month).await? as i32,
+                tokens_today: self.read(user_id, feature, "tokens", &periods.day).await?,
+                tokens_this_month: self.read(user_id, feature, "tokens", &periods.month).await?,
+                reset_daily_at: periods.daily_reset,
+                reset_monthly_at: periods.monthly_reset,
+            });
+        }
+
+        Ok((plan.tier, quotas))
+    }
+
+    async fn read(&self, user_id: Uuid, feature: AIFeature, metric: &str, period: &str) -> AIResult<i64> {
+        self.counters
+            .get(&counter_key(user_id, feature, metric, period))
+            .await
+            .map_err(AIError::Cache)
+    }
+
+    /// Counts one request, undoing it when a limit is exceeded.
+    async fn reserve(
+        &self,
+        user_id: Uuid,
+        feature: AIFeature,
+        limits: FeatureLimits,
+        periods: &Periods,
+    ) -> AIResult<()> {
+        if limits.daily <= 0 || limits.monthly <= 0 {
+            return Err(AIError::FeatureNotAvailable);
+        }
+
+        let daily_key = counter_key(user_id, feature, "requests", &periods.day);
+        let monthly_key = counter_key(user_id, feature, "requests", &periods.month);
+
+        let used_today = self.counters
+            .increment(&daily_key, 1, DAILY_TTL)
+            .await
+            .map_err(AIError::Cache)?;
+
+        if used_today > i64::from(limits.daily) {
+            self.undo(&daily_key, DAILY_TTL).await;
+            return Err(AIError::DailyLimitExceeded {
+                feature,
+                limit: limits.daily,
+                resets_at: periods.daily_reset,
+            });
+        }
+
+        let used_this_month = match self.counters.increment(&monthly_key, 1, MONTHLY_TTL).await {
+            Ok(value) => value,
+            Err(e) => {
+                self.undo(&daily_key, DAILY_TTL).await;
+                return Err(AIError::Cache(e));
+            }
+        };
+
+        if used_this_month > i64::from(limits.monthly) {
+            self.undo(&daily_key, DAILY_TTL).await;
+            self.undo(&monthly_key, MONTHLY_TTL).await;
+            return Err(AIError::MonthlyLimitExceeded {
+                feature,
+                limit: limits.monthly,
+                resets_at: periods.monthly_reset,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Gives back the request of a failed call.
+    async fn release(&self, user_id: Uuid, feature: AIFeature, periods: &Periods) {
+        self.undo(&counter_key(user_id, feature, "requests", &periods.day), DAILY_TTL).await;
+        self.undo(&counter_key(user_id, feature, "requests", &periods.month), MONTHLY_TTL).await;
+    }
+
+    async fn undo(&self, key: &str, ttl: Duration) {
+        if let Err(e) = self.counters.increment(key, -1, ttl).await {
+            tracing::warn!("Failed to release AI usage counter {}: {}", key, e);
+        }
+    }
+
+    /// Records the tokens of a completed call. The user already got the
+    /// response, so failures are logged rather than returned.
+    async fn record(&self, user_id: Uuid, feature: AIFeature, tokens: TokenUsage, periods: &Periods) {
+        let total = i64::from(tokens.total());
+        if total > 0 {
+            for (period, ttl) in [(&periods.day, DAILY_TTL), (&periods.month, MONTHLY_TTL)] {
+                let key = counter_key(user_id, feature, "tokens", period);
+                if let Err(e) = self.counters.increment(&key, total, ttl).await {
+                    tracing::warn!("Failed to update AI token counter {}: {}", key, e);
+                }
+            }
+        }
+
+        let usage = AIUsage {
+            usage_id: Uuid::now_v7(),
+            user_id,
+            feature,
+            tokens_input: tokens.input,
+            tokens_output: tokens.output,
+            cost_cents: None,
+            created_at: Utc::now(),
+        };
+
+        if let Err(e) = self.store.record_usage(&usage).await {
+            tracing::warn!("Failed to record AI usage for user {}: {}", user_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryCounters {
+        values: Mutex<HashMap<String, i64>>,
+    }
+
+    #[async_trait]
+    impl UsageCounters for MemoryCounters {
+        async fn increment(&self, key: &str, delta: i64, _ttl: Duration) -> Result<i64, String> {
+            let mut values = self.values.lock().unwrap();
+            let value = values.entry(key.to_string()).or_insert(0);
+            *value += delta;
+            Ok(*value)
+        }
+
+        async fn get(&self, key: &str) -> Result<i64, String> {
+            Ok(self.values.lock().unwrap().get(key).copied().unwrap_or(0))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        plan: UserPlan,
+        records: Mutex<Vec<AIUsage>>,
+    }
+
+    #[async_trait]
+    impl UsageStore for MemoryStore {
+        async fn get_user_plan(&self, _user_id: Uuid) -> AIResult<UserPlan> {
+            Ok(self.plan.clone())
+        }
+
+        async fn record_usage(&self, usage: &AIUsage) -> AIResult<()> {
+            self.records.lock().unwrap().push(usage.clone());
+            Ok(())
+        }
+    }
+
+    fn meter(plan: UserPlan) -> (UsageMeter, Arc<MemoryStore>) {
+        let store = Arc::new(MemoryStore { plan, ..Default::default() });
+        (UsageMeter::new(Arc::new(MemoryCounters::default()), store.clone()), store)
+    }
+
+    fn plan_with(feature: AIFeature, daily: i32, monthly: i32) -> UserPlan {
+        UserPlan {
+            tier: PlanTier::Custom,
+            ai_quotas: HashMap::from([(feature, FeatureLimits { daily, monthly })]),
+        }
+    }
+
+    async fn call(meter: &UsageMeter, user_id: Uuid, calls: &AtomicU32) -> AIResult<&'static str> {
+        meter
+            .track(user_id, AIFeature::TutorChat, async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(("respuesta", TokenUsage { input: 30, output: 12 }))
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_track_records_requests_and_tokens() {
+        let (meter, store) = meter(UserPlan::default());
+        let user_id = Uuid::now_v7();
+        let calls = AtomicU32::new(0);
+
+        assert_eq!(call(&meter, user_id, &calls).await.unwrap(), "respuesta");
+        call(&meter, user_id, &calls).await.unwrap();
+
+        let (tier, quotas) = meter.get_quotas(user_id).await.unwrap();
+        let tutor = quotas.iter().find(|q| q.feature == AIFeature::TutorChat).unwrap();
+        assert_eq!(tier, PlanTier::Free);
+        assert_eq!(tutor.daily_limit, 20);
+        assert_eq!(tutor.used_today, 2);
+        assert_eq!(tutor.used_this_month, 2);
+        assert_eq!(tutor.tokens_today, 84);
+        assert!(quotas.iter().filter(|q| q.feature != AIFeature::TutorChat).all(|q| q.used_today == 0));
+
+        let records = store.records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].tokens_input, records[0].tokens_output), (30, 12));
+    }
+
+    #[tokio::test]
+    async fn test_daily_limit_blocks_before_provider_call() {
+        let (meter, _) = meter(plan_with(AIFeature::TutorChat, 2, 100));
+        let user_id = Uuid::now_v7();
+        let calls = AtomicU32::new(0);
+
+        call(&meter, user_id, &calls).await.unwrap();
+        call(&meter, user_id, &calls).await.unwrap();
+        let err = call(&meter, user_id, &calls).await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            AIError::DailyLimitExceeded { feature: AIFeature::TutorChat, limit: 2, .. }
+        ));
+        assert_eq!(err.status_code(), 429);
+        assert!(err.retry_after_secs().unwrap() > 0);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Rejected requests are not counted
+        let (_, quotas) = meter.get_quotas(user_id).await.unwrap();
+        assert_eq!(quotas[0].used_today, 2);
+    }
+
+    #[tokio::test]
+    async fn test_monthly_limit() {
+        let (meter, _) = meter(plan_with(AIFeature::TutorChat, 10, 1));
+        let user_id = Uuid::now_v7();
+        let calls = AtomicU32::new(0);
+
+        call(&meter, user_id, &calls).await.unwrap();
+        let err = call(&meter, user_id, &calls).await.unwrap_err();
+
+        assert!(matches!(err, AIError::MonthlyLimitExceeded { limit: 1, .. }));
+        let (_, quotas) = meter.get_quotas(user_id).await.unwrap();
+        assert_eq!((quotas[0].used_today, quotas[0].used_this_month), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_failed_call_releases_request() {
+        let (meter, store) = meter(UserPlan::default());
+        let user_id = Uuid::now_v7();
+
+        let result: AIResult<()> = meter
+            .track(user_id, AIFeature::SummaryGeneration, async {
+                Err(AIError::LLMError("timeout".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(AIError::LLMError(_))));
+        let (_, quotas) = meter.get_quotas(user_id).await.unwrap();
+        assert!(quotas.iter().all(|q| q.used_today == 0 && q.used_this_month == 0));
+        assert!(store.records.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zero_limit_disables_feature() {
+        let (meter, _) = meter(plan_with(AIFeature::TutorChat, 0, 0));
+        let calls = AtomicU32::new(0);
+
+        let err = call(&meter, Uuid::now_v7(), &calls).await.unwrap_err();
+
+        assert!(matches!(err, AIError::FeatureNotAvailable));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_limits_vary_by_plan() {
+        let free = UserPlan::default();
+        let professional = UserPlan { tier: PlanTier::Professional, ..Default::default() };
+        let custom = plan_with(AIFeature::QuizGeneration, 7, 70);
+
+        assert!(professional.limits(AIFeature::TutorChat).daily > free.limits(AIFeature::TutorChat).daily);
+        assert_eq!(custom.limits(AIFeature::QuizGeneration), FeatureLimits { daily: 7, monthly: 70 });
+        // Features without override keep the tier default
+        assert_eq!(custom.limits(AIFeature::TutorChat), default_limits(PlanTier::Custom, AIFeature::TutorChat));
+    }
+
+    #[test]
+    fn test_periods() {
+        let now = "2026-12-31T18:30:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let periods = Periods::at(now);
+
+        assert_eq!(periods.day, "2026-12-31");
+        assert_eq!(periods.month, "2026-12");
+        assert_eq!(periods.daily_reset.to_rfc3339(), "2027-01-01T00:00:00+00:00");
+        assert_eq!(periods.monthly_reset, periods.daily_reset);
+    }
+
+    #[test]
+    fn test_token_usage_from_total() {
+        assert_eq!(TokenUsage::from_total(30, 100), TokenUsage { input: 30, output: 70 });
+        // Estimates above the reported total are capped
+        assert_eq!(TokenUsage::from_total(120, 100), TokenUsage { input: 100, output: 0 });
+    }
+}
@@ -7,10 +7,13 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::domain::{
-    Quiz, QuizQuestion, QuizSubmission, QuestionType,
-    QuizWithQuestions, SubmissionWithResponses,
+    AssignedQuestion, BankQuestion, Difficulty, QuestionBank, QuizDrawRule,
+    Quiz, QuizAccommodation, QuizQuestion, QuizSubmission, QuestionType,
+    QuizWithQuestions, RubricSelection, SubmissionWithResponses,
 };
-use crate::repository::QuizStats;
+use crate::interchange::InterchangeIssue;
+use crate::repository::{QuestionStats, QuizStats};
+use crate::service::{ImportSummary, QuestionRubricStats};
 
 // =============================================================================
 // QUIZ DTOs
@@ -36,6 +39,7 @@ pub struct CreateQuizRequest {
     pub passing_score_percentage: Option<f64>,
 
     pub shuffle_questions: Option<bool>,
+    pub shuffle_options: Option<bool>,
     pub show_correct_answers: Option<bool>,
 }
 
@@ -55,6 +59,7 @@ pub struct UpdateQuizRequest {
     pub passing_score_percentage: Option<f64>,
 
     pub shuffle_questions: Option<bool>,
+    pub shuffle_options: Option<bool>,
     pub show_correct_answers: Option<bool>,
     pub is_published: Option<bool>,
 }
@@ -73,6 +78,7 @@ pub struct QuizResponseDto {
     pub max_attempts: Option<i32>,
     pub passing_score_percentage: f64,
     pub shuffle_questions: bool,
+    pub shuffle_options: bool,
     pub show_correct_answers: bool,
     pub is_published: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -93,6 +99,7 @@ impl From<Quiz> for QuizResponseDto {
             max_attempts: q.max_attempts,
             passing_score_percentage: q.passing_score_percentage,
             shuffle_questions: q.shuffle_questions,
+            shuffle_options: q.shuffle_options,
             show_correct_answers: q.show_correct_answers,
             is_published: q.is_published,
             created_at: q.created_at,
@@ -137,6 +144,12 @@ pub struct CreateQuestionRequest {
     pub sort_order: i32,
     pub explanation: Option<String>,
     pub code_language: Option<String>,
+    /// Hidden test cases for code questions
+    pub test_cases: Option<serde_json::Value>,
+    /// Partial credit, penalties and answer matching
+    pub scoring_policy: Option<serde_json::Value>,
+    /// Grading rubric for essay questions
+    pub rubric: Option<serde_json::Value>,
 }
 
 /// Request to update a question.
@@ -153,6 +166,12 @@ pub struct UpdateQuestionRequest {
     pub sort_order: Option<i32>,
     pub explanation: Option<String>,
     pub code_language: Option<String>,
+    pub test_cases: Option<serde_json::Value>,
+    pub scoring_policy: Option<serde_json::Value>,
+    pub rubric: Option<serde_json::Value>,
+    /// Removes the rubric (takes precedence over `rubric`)
+    #[serde(default)]
+    pub remove_rubric: bool,
 }
 
 /// Question response.
@@ -169,6 +188,12 @@ pub struct QuestionResponseDto {
     pub sort_order: i32,
     pub explanation: Option<String>,
     pub code_language: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_array")]
+    pub test_cases: serde_json::Value,
+    #[serde(skip_serializing_if = "is_empty_object")]
+    pub scoring_policy: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rubric: Option<serde_json::Value>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -176,6 +201,10 @@ fn is_empty_array(v: &serde_json::Value) -> bool {
     v.as_array().map(|a| a.is_empty()).unwrap_or(false)
 }
 
+fn is_empty_object(v: &serde_json::Value) -> bool {
+    v.as_object().map(|o| o.is_empty()).unwrap_or(false)
+}
+
 impl From<QuizQuestion> for QuestionResponseDto {
     fn from(q: QuizQuestion) -> Self {
         Self {
@@ -189,11 +218,211 @@ impl From<QuizQuestion> for QuestionResponseDto {
             sort_order: q.sort_order,
             explanation: q.explanation,
             code_language: q.code_language,
+            test_cases: q.test_cases,
+            scoring_policy: q.scoring_policy,
+            rubric: q.rubric,
             created_at: q.created_at,
         }
     }
 }
 
+// =============================================================================
+// QUESTION BANK DTOs
+// =============================================================================
+
+/// Request to create a question bank.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateBankRequest {
+    pub course_id: Uuid,
+
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+
+    pub description: Option<String>,
+}
+
+/// Request to update a question bank.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateBankRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+
+    pub description: Option<String>,
+}
+
+/// Question bank response.
+#[derive(Debug, Serialize)]
+pub struct BankResponseDto {
+    pub bank_id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<QuestionBank> for BankResponseDto {
+    fn from(b: QuestionBank) -> Self {
+        Self {
+            bank_id: b.bank_id,
+            course_id: b.course_id,
+            title: b.title,
+            description: b.description,
+            created_at: b.created_at,
+            updated_at: b.updated_at,
+        }
+    }
+}
+
+/// Request to add a question to a bank.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateBankQuestionRequest {
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub question_type: QuestionType,
+
+    #[validate(length(min = 1))]
+    pub question_text: String,
+
+    pub options: serde_json::Value,
+    pub correct_answers: serde_json::Value,
+    pub points: Option<i32>,
+    pub explanation: Option<String>,
+    pub code_language: Option<String>,
+    pub test_cases: Option<serde_json::Value>,
+    pub scoring_policy: Option<serde_json::Value>,
+    pub rubric: Option<serde_json::Value>,
+}
+
+/// Request to update a bank question (content changes create a version).
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateBankQuestionRequest {
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub question_type: Option<QuestionType>,
+
+    #[validate(length(min = 1))]
+    pub question_text: Option<String>,
+
+    pub options: Option<serde_json::Value>,
+    pub correct_answers: Option<serde_json::Value>,
+    pub points: Option<i32>,
+    pub explanation: Option<String>,
+    pub code_language: Option<String>,
+    pub test_cases: Option<serde_json::Value>,
+    pub scoring_policy: Option<serde_json::Value>,
+    pub rubric: Option<serde_json::Value>,
+    /// Removes the rubric (takes precedence over `rubric`)
+    #[serde(default)]
+    pub remove_rubric: bool,
+}
+
+/// Filters for listing bank questions.
+#[derive(Debug, Deserialize)]
+pub struct BankQuestionQuery {
+    pub difficulty: Option<Difficulty>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Bank question response.
+#[derive(Debug, Serialize)]
+pub struct BankQuestionResponseDto {
+    pub bank_question_id: Uuid,
+    pub bank_id: Uuid,
+    pub tags: Vec<String>,
+    pub difficulty: Difficulty,
+    pub version: i32,
+    pub version_id: Uuid,
+    pub question_type: QuestionType,
+    pub question_text: String,
+    pub options: serde_json::Value,
+    pub correct_answers: serde_json::Value,
+    pub points: i32,
+    pub explanation: Option<String>,
+    pub code_language: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_array")]
+    pub test_cases: serde_json::Value,
+    #[serde(skip_serializing_if = "is_empty_object")]
+    pub scoring_policy: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rubric: Option<serde_json::Value>,
+    pub is_archived: bool,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<BankQuestion> for BankQuestionResponseDto {
+    fn from(q: BankQuestion) -> Self {
+        Self {
+            bank_question_id: q.bank_question_id,
+            bank_id: q.bank_id,
+            tags: q.tags,
+            difficulty: q.difficulty,
+            version: q.version,
+            version_id: q.version_id,
+            question_type: q.question_type,
+            question_text: q.question_text,
+            options: q.options,
+            correct_answers: q.correct_answers,
+            points: q.points,
+            explanation: q.explanation,
+            code_language: q.code_language,
+            test_cases: q.test_cases,
+            scoring_policy: q.scoring_policy,
+            rubric: q.rubric,
+            is_archived: q.is_archived,
+            updated_at: q.updated_at,
+        }
+    }
+}
+
+/// Request to add a draw rule to a quiz.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateDrawRuleRequest {
+    pub bank_id: Uuid,
+
+    #[validate(range(min = 1))]
+    pub question_count: i32,
+
+    pub difficulty: Option<Difficulty>,
+    /// Draw questions with any of these tags
+    pub tags: Option<Vec<String>>,
+
+    #[validate(range(min = 1))]
+    pub points_per_question: i32,
+
+    pub sort_order: Option<i32>,
+}
+
+/// Draw rule response.
+#[derive(Debug, Serialize)]
+pub struct DrawRuleResponseDto {
+    pub rule_id: Uuid,
+    pub quiz_id: Uuid,
+    pub bank_id: Uuid,
+    pub question_count: i32,
+    pub difficulty: Option<Difficulty>,
+    pub tags: Vec<String>,
+    pub points_per_question: i32,
+    pub sort_order: i32,
+}
+
+impl From<QuizDrawRule> for DrawRuleResponseDto {
+    fn from(r: QuizDrawRule) -> Self {
+        Self {
+            rule_id: r.rule_id,
+            quiz_id: r.quiz_id,
+            bank_id: r.bank_id,
+            question_count: r.question_count,
+            difficulty: r.difficulty,
+            tags: r.tags,
+            points_per_question: r.points_per_question,
+            sort_order: r.sort_order,
+        }
+    }
+}
+
 // =============================================================================
 // SUBMISSION DTOs
 // =============================================================================
@@ -211,12 +440,6 @@ pub struct SaveAnswerRequest {
     pub answer_data: serde_json::Value,
 }
 
-/// Request to submit a quiz.
-#[derive(Debug, Deserialize)]
-pub struct SubmitQuizRequest {
-    pub time_spent_seconds: i32,
-}
-
 /// Submission response.
 #[derive(Debug, Serialize)]
 pub struct SubmissionResponseDto {
@@ -234,6 +457,9 @@ pub struct SubmissionResponseDto {
     pub submitted_at: Option<chrono::DateTime<chrono::Utc>>,
     pub graded_at: Option<chrono::DateTime<chrono::Utc>>,
     pub instructor_feedback: Option<String>,
+    /// Server-side deadline for in-progress attempts
+    pub deadline_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub auto_submitted: bool,
 }
 
 impl From<QuizSubmission> for SubmissionResponseDto {
@@ -253,6 +479,51 @@ impl From<QuizSubmission> for SubmissionResponseDto {
             submitted_at: s.submitted_at,
             graded_at: s.graded_at,
             instructor_feedback: s.instructor_feedback,
+            deadline_at: s.deadline_at,
+            auto_submitted: s.auto_submitted,
+        }
+    }
+}
+
+/// A question as shown in one attempt.
+#[derive(Debug, Serialize)]
+pub struct AttemptQuestionDto {
+    pub question_id: Uuid,
+    pub position: i32,
+    pub question_type: QuestionType,
+    pub question_text: String,
+    /// Options in this attempt's display order
+    pub options: serde_json::Value,
+    pub points: i32,
+    pub code_language: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_array")]
+    pub correct_answers: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Rubric the essay will be graded with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rubric: Option<serde_json::Value>,
+    /// Bank question version, for drawn questions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<Uuid>,
+}
+
+impl From<AssignedQuestion> for AttemptQuestionDto {
+    fn from(a: AssignedQuestion) -> Self {
+        let options = a.presented_options();
+        let q = a.question;
+        Self {
+            question_id: q.question_id,
+            position: q.sort_order,
+            question_type: q.question_type,
+            question_text: q.question_text,
+            options,
+            points: q.points,
+            code_language: q.code_language,
+            correct_answers: q.correct_answers,
+            explanation: q.explanation,
+            rubric: q.rubric,
+            version_id: a.version_id,
         }
     }
 }
@@ -284,6 +555,11 @@ pub struct AnswerResponseDto {
     pub points_earned: f64,
     pub instructor_feedback: Option<String>,
     pub auto_graded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_results: Option<serde_json::Value>,
+    /// Per-criterion breakdown of rubric grading
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rubric_scores: Option<serde_json::Value>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -298,6 +574,8 @@ impl From<crate::domain::QuizResponse> for AnswerResponseDto {
             points_earned: r.points_earned,
             instructor_feedback: r.instructor_feedback,
             auto_graded: r.auto_graded,
+            test_results: r.test_results,
+            rubric_scores: r.rubric_scores,
             created_at: r.created_at,
         }
     }
@@ -316,15 +594,147 @@ pub struct GradeSubmissionRequest {
     pub feedback: Option<String>,
 }
 
-/// Request to grade a response.
+/// Request to grade a response, either with points or with a level for
+/// every criterion of the question's rubric.
 #[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_grade_response"))]
 pub struct GradeResponseRequest {
     #[validate(range(min = 0.0))]
-    pub points_earned: f64,
+    pub points_earned: Option<f64>,
+
+    pub rubric: Option<Vec<RubricSelectionDto>>,
 
     pub feedback: Option<String>,
 }
 
+fn validate_grade_response(req: &GradeResponseRequest) -> Result<(), validator::ValidationError> {
+    if req.points_earned.is_some() == req.rubric.is_some() {
+        return Err(validator::ValidationError::new("points_earned_or_rubric"));
+    }
+    Ok(())
+}
+
+/// Level picked for one rubric criterion.
+#[derive(Debug, Deserialize)]
+pub struct RubricSelectionDto {
+    pub criterion_id: String,
+    pub level_id: String,
+    pub comment: Option<String>,
+}
+
+impl From<RubricSelectionDto> for RubricSelection {
+    fn from(s: RubricSelectionDto) -> Self {
+        Self { criterion_id: s.criterion_id, level_id: s.level_id, comment: s.comment }
+    }
+}
+
+/// Result of regrading a quiz.
+#[derive(Debug, Serialize)]
+pub struct RegradeResponse {
+    pub quiz_id: Uuid,
+    pub responses_changed: usize,
+    pub submissions_changed: usize,
+}
+
+// =============================================================================
+// IMPORT / EXPORT DTOs
+// =============================================================================
+
+/// Query for importing questions.
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// `qti` or `gift`
+    pub format: String,
+    /// Title of the new quiz (defaults to the title in the file)
+    pub title: Option<String>,
+}
+
+/// Result of an import, with the items that could not be mapped.
+#[derive(Debug, Serialize)]
+pub struct ImportResponse {
+    pub quiz: QuizResponseDto,
+    pub imported: Vec<QuestionResponseDto>,
+    pub skipped: Vec<InterchangeIssue>,
+    pub warnings: Vec<InterchangeIssue>,
+}
+
+impl From<ImportSummary> for ImportResponse {
+    fn from(s: ImportSummary) -> Self {
+        Self {
+            quiz: s.quiz.into(),
+            imported: s.questions.into_iter().map(Into::into).collect(),
+            skipped: s.skipped,
+            warnings: s.warnings,
+        }
+    }
+}
+
+/// Items left out of or changed in an export.
+#[derive(Debug, Serialize)]
+pub struct ExportReport {
+    pub skipped: Vec<InterchangeIssue>,
+    pub warnings: Vec<InterchangeIssue>,
+}
+
+// =============================================================================
+// ACCOMMODATION DTOs
+// =============================================================================
+
+/// Request to grant a student extra time or attempts.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetAccommodationRequest {
+    pub user_id: Uuid,
+    /// Limit to one quiz (omit for every quiz in the course)
+    pub quiz_id: Option<Uuid>,
+
+    #[validate(range(min = 1.0, max = 10.0))]
+    pub time_multiplier: Option<f64>,
+
+    #[validate(range(min = 0))]
+    pub extra_time_minutes: Option<i32>,
+
+    #[validate(range(min = 0))]
+    pub extra_attempts: Option<i32>,
+
+    pub notes: Option<String>,
+}
+
+/// Query for listing accommodations.
+#[derive(Debug, Deserialize)]
+pub struct AccommodationQuery {
+    pub user_id: Option<Uuid>,
+}
+
+/// Accommodation response.
+#[derive(Debug, Serialize)]
+pub struct AccommodationResponseDto {
+    pub accommodation_id: Uuid,
+    pub course_id: Uuid,
+    pub quiz_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub time_multiplier: f64,
+    pub extra_time_minutes: i32,
+    pub extra_attempts: i32,
+    pub notes: Option<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<QuizAccommodation> for AccommodationResponseDto {
+    fn from(a: QuizAccommodation) -> Self {
+        Self {
+            accommodation_id: a.accommodation_id,
+            course_id: a.course_id,
+            quiz_id: a.quiz_id,
+            user_id: a.user_id,
+            time_multiplier: a.time_multiplier,
+            extra_time_minutes: a.extra_time_minutes,
+            extra_attempts: a.extra_attempts,
+            notes: a.notes,
+            updated_at: a.updated_at,
+        }
+    }
+}
+
 // =============================================================================
 // COMMON DTOs
 // =============================================================================
@@ -363,6 +773,9 @@ pub struct QuizStatsResponse {
     pub average_score: f64,
     pub average_time_seconds: f64,
     pub pass_rate: Option<f64>,
+    /// Criterion breakdowns of rubric-graded questions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rubrics: Vec<QuestionRubricStatsDto>,
 }
 
 impl From<QuizStats> for QuizStatsResponse {
@@ -380,6 +793,65 @@ impl From<QuizStats> for QuizStatsResponse {
             average_score: s.avg_score,
             average_time_seconds: s.avg_time_seconds,
             pass_rate,
+            rubrics: Vec::new(),
+        }
+    }
+}
+
+impl QuizStatsResponse {
+    pub fn with_rubrics(mut self, rubrics: Vec<QuestionRubricStats>) -> Self {
+        self.rubrics = rubrics.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Rubric statistics of one question.
+#[derive(Debug, Serialize)]
+pub struct QuestionRubricStatsDto {
+    pub question_id: Uuid,
+    pub graded_count: i64,
+    pub criteria: Vec<CriterionStatsDto>,
+}
+
+/// Statistics of one rubric criterion.
+#[derive(Debug, Serialize)]
+pub struct CriterionStatsDto {
+    pub criterion_id: String,
+    pub title: String,
+    pub max_points: f64,
+    pub average_points: f64,
+    pub levels: Vec<LevelCountDto>,
+}
+
+/// How often a rubric level was picked.
+#[derive(Debug, Serialize)]
+pub struct LevelCountDto {
+    pub level_id: String,
+    pub title: String,
+    pub points: f64,
+    pub count: i64,
+}
+
+impl From<QuestionRubricStats> for QuestionRubricStatsDto {
+    fn from(s: QuestionRubricStats) -> Self {
+        Self {
+            question_id: s.question_id,
+            graded_count: s.graded_count,
+            criteria: s
+                .criteria
+                .into_iter()
+                .map(|c| CriterionStatsDto {
+                    criterion_id: c.criterion_id,
+                    title: c.criterion,
+                    max_points: c.max_points,
+                    average_points: c.average_points,
+                    levels: c
+                        .levels
+                        .into_iter()
+                        .map(|l| LevelCountDto { level_id: l.level_id, title: l.level, points: l.points, count: l.count })
+                        .collect(),
+                })
+                .collect(),
         }
     }
 }
@@ -397,6 +869,40 @@ pub struct PaginationQuery {
 fn default_page() -> i64 { 1 }
 fn default_page_size() -> i64 { 20 }
 
+/// Per-question statistics.
+#[derive(Debug, Serialize)]
+pub struct QuestionStatsResponse {
+    pub question_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_question_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    pub times_presented: i64,
+    pub times_answered: i64,
+    pub correct_rate: f64,
+    pub avg_points: f64,
+    pub max_points: i32,
+}
+
+impl From<QuestionStats> for QuestionStatsResponse {
+    fn from(s: QuestionStats) -> Self {
+        Self {
+            question_id: s.question_id,
+            bank_question_id: s.bank_question_id,
+            version: s.version,
+            times_presented: s.times_presented,
+            times_answered: s.times_answered,
+            correct_rate: if s.times_answered > 0 {
+                (s.correct_count as f64 / s.times_answered as f64) * 100.0
+            } else {
+                0.0
+            },
+            avg_points: s.avg_points,
+            max_points: s.max_points,
+        }
+    }
+}
+
 /// Error response.
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
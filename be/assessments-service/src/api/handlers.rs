@@ -7,12 +7,18 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::api::dto::*;
-use crate::domain::{NewQuiz, NewQuizQuestion, UpdateQuiz, UpdateQuizQuestion};
-use crate::service::{AssessmentError, AssessmentService};
+use crate::domain::{
+    BankQuestionContent, NewBankQuestion, NewQuestionBank, NewQuiz, NewQuizAccommodation,
+    NewQuizDrawRule, NewQuizQuestion, RubricSelection, UpdateBankQuestion, UpdateQuestionBank,
+    UpdateQuiz, UpdateQuizQuestion,
+};
+use crate::interchange::InterchangeFormat;
+use crate::service::{AssessmentError, AssessmentService, QuestionBankService};
 
 /// Application state shared across handlers.
 pub struct AppState {
     pub service: AssessmentService,
+    pub banks: QuestionBankService,
 }
 
 // =============================================================================
@@ -25,12 +31,18 @@ impl From<AssessmentError> for HttpResponse {
             AssessmentError::QuizNotFound => (actix_web::http::StatusCode::NOT_FOUND, "quiz_not_found"),
             AssessmentError::QuestionNotFound => (actix_web::http::StatusCode::NOT_FOUND, "question_not_found"),
             AssessmentError::SubmissionNotFound => (actix_web::http::StatusCode::NOT_FOUND, "submission_not_found"),
+            AssessmentError::ResponseNotFound => (actix_web::http::StatusCode::NOT_FOUND, "response_not_found"),
+            AssessmentError::BankNotFound => (actix_web::http::StatusCode::NOT_FOUND, "bank_not_found"),
+            AssessmentError::BankQuestionNotFound => (actix_web::http::StatusCode::NOT_FOUND, "bank_question_not_found"),
+            AssessmentError::DrawRuleNotFound => (actix_web::http::StatusCode::NOT_FOUND, "draw_rule_not_found"),
             AssessmentError::QuizNotPublished => (actix_web::http::StatusCode::BAD_REQUEST, "quiz_not_published"),
             AssessmentError::MaxAttemptsExceeded => (actix_web::http::StatusCode::BAD_REQUEST, "max_attempts_exceeded"),
             AssessmentError::SubmissionAlreadyCompleted => (actix_web::http::StatusCode::BAD_REQUEST, "submission_completed"),
             AssessmentError::QuizHasSubmissions => (actix_web::http::StatusCode::CONFLICT, "quiz_has_submissions"),
             AssessmentError::Unauthorized => (actix_web::http::StatusCode::FORBIDDEN, "unauthorized"),
             AssessmentError::TimeLimitExceeded => (actix_web::http::StatusCode::BAD_REQUEST, "time_limit_exceeded"),
+            AssessmentError::NotEnrolled => (actix_web::http::StatusCode::FORBIDDEN, "not_enrolled"),
+            AssessmentError::ServiceUnavailable(_) => (actix_web::http::StatusCode::SERVICE_UNAVAILABLE, "service_unavailable"),
             AssessmentError::Database(_) => (actix_web::http::StatusCode::INTERNAL_SERVER_ERROR, "database_error"),
             AssessmentError::Validation(_) => (actix_web::http::StatusCode::BAD_REQUEST, "validation_error"),
         };
@@ -143,6 +155,7 @@ pub async fn create_quiz(
         max_attempts: data.max_attempts,
         passing_score_percentage: data.passing_score_percentage,
         shuffle_questions: data.shuffle_questions,
+        shuffle_options: data.shuffle_options,
         show_correct_answers: data.show_correct_answers,
     };
 
@@ -175,6 +188,7 @@ pub async fn update_quiz(
         time_limit_minutes: data.time_limit_minutes.map(Some),
         max_attempts: data.max_attempts.map(Some),
         shuffle_questions: data.shuffle_questions,
+        shuffle_options: data.shuffle_options,
         show_correct_answers: data.show_correct_answers,
         is_published: data.is_published,
     };
@@ -221,8 +235,33 @@ pub async fn get_quiz_stats(
 ) -> impl Responder {
     let quiz_id = path.into_inner();
 
-    match state.service.get_quiz_stats(quiz_id).await {
-        Ok(stats) => HttpResponse::Ok().json(QuizStatsResponse::from(stats)),
+    let stats = match state.service.get_quiz_stats(quiz_id).await {
+        Ok(stats) => stats,
+        Err(e) => return e.into(),
+    };
+
+    match state.service.get_rubric_stats(quiz_id).await {
+        Ok(rubrics) => HttpResponse::Ok().json(QuizStatsResponse::from(stats).with_rubrics(rubrics)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Get per-question statistics (instructor).
+/// GET /quizzes/{quiz_id}/question-stats
+pub async fn get_question_stats(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.service.get_question_stats(path.into_inner()).await {
+        Ok(stats) => {
+            let response: Vec<QuestionStatsResponse> = stats.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
         Err(e) => e.into(),
     }
 }
@@ -270,6 +309,9 @@ pub async fn create_question(
         sort_order: data.sort_order,
         explanation: data.explanation,
         code_language: data.code_language,
+        test_cases: data.test_cases.unwrap_or_else(|| serde_json::json!([])),
+        scoring_policy: data.scoring_policy.unwrap_or_else(|| serde_json::json!({})),
+        rubric: data.rubric,
     };
 
     match state.service.add_question(new_question).await {
@@ -301,6 +343,9 @@ pub async fn update_question(
         sort_order: data.sort_order,
         explanation: data.explanation.map(Some),
         code_language: data.code_language.map(Some),
+        test_cases: data.test_cases,
+        scoring_policy: data.scoring_policy,
+        rubric: if data.remove_rubric { Some(None) } else { data.rubric.map(Some) },
     };
 
     match state.service.update_question(question_id, update).await {
@@ -381,6 +426,27 @@ pub async fn get_submission_with_responses(
     }
 }
 
+/// Get the questions of an attempt in display order.
+/// GET /submissions/{submission_id}/questions
+pub async fn get_attempt_questions(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    user_id: web::ReqData<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    let submission_id = path.into_inner();
+    let user_id = user_id.into_inner();
+    let is_instructor = is_instructor.into_inner();
+
+    match state.service.get_attempt_questions(submission_id, user_id, is_instructor).await {
+        Ok(questions) => {
+            let response: Vec<AttemptQuestionDto> = questions.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => e.into(),
+    }
+}
+
 /// Get user's submissions for a quiz.
 /// GET /quizzes/{quiz_id}/my-submissions
 pub async fn get_my_submissions(
@@ -424,13 +490,11 @@ pub async fn submit_quiz(
     state: web::Data<AppState>,
     path: web::Path<Uuid>,
     user_id: web::ReqData<Uuid>,
-    body: web::Json<SubmitQuizRequest>,
 ) -> impl Responder {
     let submission_id = path.into_inner();
     let user_id = user_id.into_inner();
-    let data = body.into_inner();
 
-    match state.service.submit_quiz(submission_id, user_id, data.time_spent_seconds).await {
+    match state.service.submit_quiz(submission_id, user_id).await {
         Ok(submission) => HttpResponse::Ok().json(SubmissionResponseDto::from(submission)),
         Err(e) => e.into(),
     }
@@ -471,12 +535,561 @@ pub async fn grade_response(
     let response_id = path.into_inner();
     let data = body.into_inner();
 
-    match state.service.grade_single_response(response_id, data.points_earned, data.feedback).await {
+    let result = match (data.points_earned, data.rubric) {
+        (_, Some(rubric)) => {
+            let selections: Vec<RubricSelection> = rubric.into_iter().map(Into::into).collect();
+            state.service.grade_response_with_rubric(response_id, &selections, data.feedback).await
+        }
+        (points_earned, None) => {
+            state.service.grade_single_response(response_id, points_earned.unwrap_or_default(), data.feedback).await
+        }
+    };
+
+    match result {
         Ok(response) => HttpResponse::Ok().json(AnswerResponseDto::from(response)),
         Err(e) => e.into(),
     }
 }
 
+/// Regrade a quiz's submissions with the current answer key (instructor).
+/// POST /quizzes/{quiz_id}/regrade
+pub async fn regrade_quiz(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    let quiz_id = path.into_inner();
+
+    match state.service.regrade_quiz(quiz_id).await {
+        Ok(summary) => HttpResponse::Ok().json(RegradeResponse {
+            quiz_id,
+            responses_changed: summary.responses_changed,
+            submissions_changed: summary.submissions_changed,
+        }),
+        Err(e) => e.into(),
+    }
+}
+
+// =============================================================================
+// IMPORT / EXPORT HANDLERS
+// =============================================================================
+
+/// Create a quiz from a QTI 2.1 package or GIFT file (instructor).
+/// POST /courses/{course_id}/quizzes/import?format=qti|gift
+pub async fn import_quiz(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<ImportQuery>,
+    body: web::Bytes,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    let format: InterchangeFormat = match query.format.parse() {
+        Ok(format) => format,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse::new("validation_error", e.to_string())),
+    };
+    let query = query.into_inner();
+
+    match state.service.import_quiz(path.into_inner(), format, &body, query.title).await {
+        Ok(summary) => HttpResponse::Created().json(ImportResponse::from(summary)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Append questions from a QTI 2.1 package or GIFT file (instructor).
+/// POST /quizzes/{quiz_id}/import?format=qti|gift
+pub async fn import_questions(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<ImportQuery>,
+    body: web::Bytes,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    let format: InterchangeFormat = match query.format.parse() {
+        Ok(format) => format,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse::new("validation_error", e.to_string())),
+    };
+
+    match state.service.import_questions(path.into_inner(), format, &body).await {
+        Ok(summary) => HttpResponse::Ok().json(ImportResponse::from(summary)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Export a quiz as a QTI 2.1 package (instructor).
+/// GET /quizzes/{quiz_id}/export/qti
+///
+/// Items that could not be exported are listed in the `X-Export-Report`
+/// header as JSON.
+pub async fn export_quiz_qti(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.service.export_quiz_qti(path.into_inner()).await {
+        Ok((quiz, package)) => {
+            let report = serde_json::to_string(&ExportReport {
+                skipped: package.skipped,
+                warnings: package.warnings,
+            })
+            .unwrap_or_default();
+
+            HttpResponse::Ok()
+                .content_type("application/zip")
+                .insert_header((
+                    "Content-Disposition",
+                    format!("attachment; filename=\"quiz-{}-qti.zip\"", quiz.quiz_id),
+                ))
+                .insert_header(("X-Export-Report", report))
+                .body(package.data)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+// =============================================================================
+// ACCOMMODATION HANDLERS
+// =============================================================================
+
+/// List accommodations for a course (instructor).
+/// GET /courses/{course_id}/accommodations
+pub async fn list_accommodations(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<AccommodationQuery>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    let course_id = path.into_inner();
+
+    match state.service.list_accommodations(course_id, query.user_id).await {
+        Ok(accommodations) => {
+            let response: Vec<AccommodationResponseDto> = accommodations.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Grant a student extra time or attempts (instructor).
+/// PUT /courses/{course_id}/accommodations
+pub async fn set_accommodation(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetAccommodationRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let accommodation = NewQuizAccommodation {
+        course_id: path.into_inner(),
+        quiz_id: data.quiz_id,
+        user_id: data.user_id,
+        time_multiplier: data.time_multiplier.unwrap_or(1.0),
+        extra_time_minutes: data.extra_time_minutes.unwrap_or(0),
+        extra_attempts: data.extra_attempts.unwrap_or(0),
+        notes: data.notes,
+    };
+
+    match state.service.set_accommodation(accommodation).await {
+        Ok(accommodation) => HttpResponse::Ok().json(AccommodationResponseDto::from(accommodation)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Remove an accommodation (instructor).
+/// DELETE /accommodations/{accommodation_id}
+pub async fn delete_accommodation(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.service.remove_accommodation(path.into_inner()).await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Accommodation not found")),
+        Err(e) => e.into(),
+    }
+}
+
+// =============================================================================
+// QUESTION BANK HANDLERS
+// =============================================================================
+
+/// List question banks for a course (instructor).
+/// GET /courses/{course_id}/question-banks
+pub async fn list_course_banks(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.list_banks(path.into_inner()).await {
+        Ok(banks) => {
+            let response: Vec<BankResponseDto> = banks.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Create a question bank (instructor).
+/// POST /question-banks
+pub async fn create_bank(
+    state: web::Data<AppState>,
+    body: web::Json<CreateBankRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let new_bank = NewQuestionBank {
+        course_id: data.course_id,
+        title: data.title,
+        description: data.description,
+    };
+
+    match state.banks.create_bank(new_bank).await {
+        Ok(bank) => HttpResponse::Created().json(BankResponseDto::from(bank)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Get a question bank (instructor).
+/// GET /question-banks/{bank_id}
+pub async fn get_bank(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.get_bank(path.into_inner()).await {
+        Ok(bank) => HttpResponse::Ok().json(BankResponseDto::from(bank)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Update a question bank (instructor).
+/// PATCH /question-banks/{bank_id}
+pub async fn update_bank(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateBankRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let update = UpdateQuestionBank {
+        title: data.title,
+        description: data.description.map(Some),
+    };
+
+    match state.banks.update_bank(path.into_inner(), update).await {
+        Ok(bank) => HttpResponse::Ok().json(BankResponseDto::from(bank)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Delete an unused question bank (instructor).
+/// DELETE /question-banks/{bank_id}
+pub async fn delete_bank(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.delete_bank(path.into_inner()).await {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(e) => e.into(),
+    }
+}
+
+/// List the questions of a bank (instructor).
+/// GET /question-banks/{bank_id}/questions
+pub async fn list_bank_questions(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<BankQuestionQuery>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    let bank_id = path.into_inner();
+
+    match state.banks
+        .list_questions(bank_id, query.difficulty, query.tag.as_deref(), query.include_archived)
+        .await
+    {
+        Ok(questions) => {
+            let response: Vec<BankQuestionResponseDto> = questions.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Add a question to a bank (instructor).
+/// POST /question-banks/{bank_id}/questions
+pub async fn create_bank_question(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<CreateBankQuestionRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let new_question = NewBankQuestion {
+        bank_id: path.into_inner(),
+        tags: data.tags.unwrap_or_default(),
+        difficulty: data.difficulty.unwrap_or_default(),
+        content: BankQuestionContent {
+            question_type: data.question_type,
+            question_text: data.question_text,
+            options: data.options,
+            correct_answers: data.correct_answers,
+            points: data.points.unwrap_or(1),
+            explanation: data.explanation,
+            code_language: data.code_language,
+            test_cases: data.test_cases.unwrap_or_else(|| serde_json::json!([])),
+            scoring_policy: data.scoring_policy.unwrap_or_else(|| serde_json::json!({})),
+            rubric: data.rubric,
+        },
+    };
+
+    match state.banks.add_question(new_question).await {
+        Ok(question) => HttpResponse::Created().json(BankQuestionResponseDto::from(question)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Get a bank question (instructor).
+/// GET /bank-questions/{bank_question_id}
+pub async fn get_bank_question(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.get_question(path.into_inner()).await {
+        Ok(question) => HttpResponse::Ok().json(BankQuestionResponseDto::from(question)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Update a bank question; content changes create a new version (instructor).
+/// PATCH /bank-questions/{bank_question_id}
+pub async fn update_bank_question(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateBankQuestionRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let update = UpdateBankQuestion {
+        tags: data.tags,
+        difficulty: data.difficulty,
+        question_type: data.question_type,
+        question_text: data.question_text,
+        options: data.options,
+        correct_answers: data.correct_answers,
+        points: data.points,
+        explanation: data.explanation.map(Some),
+        code_language: data.code_language.map(Some),
+        test_cases: data.test_cases,
+        scoring_policy: data.scoring_policy,
+        rubric: if data.remove_rubric { Some(None) } else { data.rubric.map(Some) },
+    };
+
+    match state.banks.update_question(path.into_inner(), update).await {
+        Ok(question) => HttpResponse::Ok().json(BankQuestionResponseDto::from(question)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Archive a bank question so it is no longer drawn (instructor).
+/// DELETE /bank-questions/{bank_question_id}
+pub async fn archive_bank_question(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.set_archived(path.into_inner(), true).await {
+        Ok(question) => HttpResponse::Ok().json(BankQuestionResponseDto::from(question)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Restore an archived bank question (instructor).
+/// POST /bank-questions/{bank_question_id}/restore
+pub async fn restore_bank_question(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.banks.set_archived(path.into_inner(), false).await {
+        Ok(question) => HttpResponse::Ok().json(BankQuestionResponseDto::from(question)),
+        Err(e) => e.into(),
+    }
+}
+
+// =============================================================================
+// DRAW RULE HANDLERS
+// =============================================================================
+
+/// List the draw rules of a quiz (instructor).
+/// GET /quizzes/{quiz_id}/draw-rules
+pub async fn list_draw_rules(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.service.list_draw_rules(path.into_inner()).await {
+        Ok(rules) => {
+            let response: Vec<DrawRuleResponseDto> = rules.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Add a draw rule to a quiz (instructor).
+/// POST /quizzes/{quiz_id}/draw-rules
+pub async fn create_draw_rule(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<CreateDrawRuleRequest>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    if let Err(e) = body.validate() {
+        return validation_error(e);
+    }
+
+    let data = body.into_inner();
+
+    let rule = NewQuizDrawRule {
+        quiz_id: path.into_inner(),
+        bank_id: data.bank_id,
+        question_count: data.question_count,
+        difficulty: data.difficulty,
+        tags: data.tags.unwrap_or_default(),
+        points_per_question: data.points_per_question,
+        sort_order: data.sort_order.unwrap_or(0),
+    };
+
+    match state.service.add_draw_rule(rule).await {
+        Ok(rule) => HttpResponse::Created().json(DrawRuleResponseDto::from(rule)),
+        Err(e) => e.into(),
+    }
+}
+
+/// Remove a draw rule (instructor).
+/// DELETE /draw-rules/{rule_id}
+pub async fn delete_draw_rule(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    is_instructor: web::ReqData<bool>,
+) -> impl Responder {
+    if !is_instructor.into_inner() {
+        return AssessmentError::Unauthorized.into();
+    }
+
+    match state.service.remove_draw_rule(path.into_inner()).await {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(e) => e.into(),
+    }
+}
+
 // =============================================================================
 // HEALTH CHECK
 // =============================================================================
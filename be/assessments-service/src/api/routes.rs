@@ -6,6 +6,9 @@ use actix_web::web;
 
 use crate::api::handlers;
 
+/// Largest accepted import file.
+const IMPORT_PAYLOAD_LIMIT: usize = 10 * 1024 * 1024;
+
 /// Configures all assessment routes.
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg
@@ -23,12 +26,52 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                 .route("/{quiz_id}/questions", web::get().to(handlers::list_quiz_questions))
                 .route("/{quiz_id}/publish", web::post().to(handlers::publish_quiz))
                 .route("/{quiz_id}/stats", web::get().to(handlers::get_quiz_stats))
+                .route("/{quiz_id}/question-stats", web::get().to(handlers::get_question_stats))
+                .route("/{quiz_id}/draw-rules", web::get().to(handlers::list_draw_rules))
+                .route("/{quiz_id}/draw-rules", web::post().to(handlers::create_draw_rule))
+                .route("/{quiz_id}/regrade", web::post().to(handlers::regrade_quiz))
                 .route("/{quiz_id}/start", web::post().to(handlers::start_quiz))
                 .route("/{quiz_id}/my-submissions", web::get().to(handlers::get_my_submissions))
+                .route("/{quiz_id}/export/qti", web::get().to(handlers::export_quiz_qti))
+                .service(
+                    web::resource("/{quiz_id}/import")
+                        .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
+                        .route(web::post().to(handlers::import_questions))
+                )
         )
 
         // Course quizzes
         .route("/courses/{course_id}/quizzes", web::get().to(handlers::list_course_quizzes))
+        .service(
+            web::resource("/courses/{course_id}/quizzes/import")
+                .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
+                .route(web::post().to(handlers::import_quiz))
+        )
+
+        // Accommodation routes
+        .route("/courses/{course_id}/accommodations", web::get().to(handlers::list_accommodations))
+        .route("/courses/{course_id}/accommodations", web::put().to(handlers::set_accommodation))
+        .route("/accommodations/{accommodation_id}", web::delete().to(handlers::delete_accommodation))
+
+        // Question bank routes
+        .route("/courses/{course_id}/question-banks", web::get().to(handlers::list_course_banks))
+        .service(
+            web::scope("/question-banks")
+                .route("", web::post().to(handlers::create_bank))
+                .route("/{bank_id}", web::get().to(handlers::get_bank))
+                .route("/{bank_id}", web::patch().to(handlers::update_bank))
+                .route("/{bank_id}", web::delete().to(handlers::delete_bank))
+                .route("/{bank_id}/questions", web::get().to(handlers::list_bank_questions))
+                .route("/{bank_id}/questions", web::post().to(handlers::create_bank_question))
+        )
+        .service(
+            web::scope("/bank-questions")
+                .route("/{bank_question_id}", web::get().to(handlers::get_bank_question))
+                .route("/{bank_question_id}", web::patch().to(handlers::update_bank_question))
+                .route("/{bank_question_id}", web::delete().to(handlers::archive_bank_question))
+                .route("/{bank_question_id}/restore", web::post().to(handlers::restore_bank_question))
+        )
+        .route("/draw-rules/{rule_id}", web::delete().to(handlers::delete_draw_rule))
 
         // Lesson quizzes
         .route("/lessons/{lesson_id}/quizzes", web::get().to(handlers::get_lesson_quizzes))
@@ -46,6 +89,7 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             web::scope("/submissions")
                 .route("/{submission_id}", web::get().to(handlers::get_submission))
                 .route("/{submission_id}/full", web::get().to(handlers::get_submission_with_responses))
+                .route("/{submission_id}/questions", web::get().to(handlers::get_attempt_questions))
                 .route("/{submission_id}/answers", web::post().to(handlers::save_answer))
                 .route("/{submission_id}/submit", web::post().to(handlers::submit_quiz))
                 .route("/{submission_id}/grade", web::post().to(handlers::grade_submission))
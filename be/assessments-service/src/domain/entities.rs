@@ -165,6 +165,8 @@ pub struct Quiz {
     pub max_attempts: Option<i32>,
     /// Whether to randomize question order
     pub shuffle_questions: bool,
+    /// Whether to randomize choice order per attempt
+    pub shuffle_options: bool,
     /// Whether to show correct answers after submission
     pub show_correct_answers: bool,
     /// Whether the quiz is published and available
@@ -190,6 +192,24 @@ impl Quiz {
     pub fn passing_score(&self) -> f64 {
         (self.total_points as f64) * (self.passing_score_percentage / 100.0)
     }
+
+    /// Time allowed per attempt in seconds, including any accommodation
+    /// (None = unlimited).
+    pub fn time_allowed_seconds(&self, accommodation: Option<&QuizAccommodation>) -> Option<i64> {
+        let minutes = self.time_limit_minutes? as f64;
+        let seconds = match accommodation {
+            Some(a) => minutes * a.time_multiplier.max(1.0) * 60.0 + a.extra_time_minutes.max(0) as f64 * 60.0,
+            None => minutes * 60.0,
+        };
+        Some(seconds.round() as i64)
+    }
+
+    /// Maximum attempts for a student, including any accommodation
+    /// (None = unlimited).
+    pub fn attempt_limit(&self, accommodation: Option<&QuizAccommodation>) -> Option<i32> {
+        let extra = accommodation.map(|a| a.extra_attempts.max(0)).unwrap_or(0);
+        self.max_attempts.map(|max| max + extra)
+    }
 }
 
 /// Data required to create a new quiz.
@@ -205,6 +225,7 @@ pub struct NewQuiz {
     pub time_limit_minutes: Option<i32>,
     pub max_attempts: Option<i32>,
     pub shuffle_questions: Option<bool>,
+    pub shuffle_options: Option<bool>,
     pub show_correct_answers: Option<bool>,
 }
 
@@ -219,6 +240,7 @@ pub struct UpdateQuiz {
     pub time_limit_minutes: Option<Option<i32>>,
     pub max_attempts: Option<Option<i32>>,
     pub shuffle_questions: Option<bool>,
+    pub shuffle_options: Option<bool>,
     pub show_correct_answers: Option<bool>,
     pub is_published: Option<bool>,
 }
@@ -258,14 +280,42 @@ pub struct QuizQuestion {
     pub correct_answers: serde_json::Value,
     /// Programming language for code questions
     pub code_language: Option<String>,
+    /// Hidden test cases for code questions as JSON array
+    /// Format: [{"name": "...", "input": "...", "expected_output": "...", "weight": 1.0}, ...]
+    pub test_cases: serde_json::Value,
+    /// Scoring policy as JSON object (see [`ScoringPolicy`])
+    pub scoring_policy: serde_json::Value,
+    /// Grading rubric for essay questions (see [`Rubric`])
+    pub rubric: Option<serde_json::Value>,
     /// Record creation timestamp
     pub created_at: DateTime<Utc>,
 }
 
 impl QuizQuestion {
     /// Returns true if this question requires manual grading.
+    ///
+    /// Code questions are auto-graded only when they have test cases.
     pub fn needs_manual_grading(&self) -> bool {
-        matches!(self.question_type, QuestionType::Essay | QuestionType::Code)
+        match self.question_type {
+            QuestionType::Essay => true,
+            QuestionType::Code => self.code_test_cases().is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Returns the hidden test cases of a code question.
+    pub fn code_test_cases(&self) -> Vec<CodeTestCase> {
+        serde_json::from_value(self.test_cases.clone()).unwrap_or_default()
+    }
+
+    /// Returns the scoring policy (all-or-nothing if unset or invalid).
+    pub fn scoring(&self) -> ScoringPolicy {
+        serde_json::from_value(self.scoring_policy.clone()).unwrap_or_default()
+    }
+
+    /// Returns the grading rubric, if the question has a valid one.
+    pub fn grading_rubric(&self) -> Option<Rubric> {
+        self.rubric.clone().and_then(|r| serde_json::from_value(r).ok())
     }
 
     /// Returns the number of correct answers expected.
@@ -286,6 +336,9 @@ pub struct NewQuizQuestion {
     pub options: serde_json::Value,
     pub correct_answers: serde_json::Value,
     pub code_language: Option<String>,
+    pub test_cases: serde_json::Value,
+    pub scoring_policy: serde_json::Value,
+    pub rubric: Option<serde_json::Value>,
 }
 
 /// Data for updating a question.
@@ -299,6 +352,238 @@ pub struct UpdateQuizQuestion {
     pub options: Option<serde_json::Value>,
     pub correct_answers: Option<serde_json::Value>,
     pub code_language: Option<Option<String>>,
+    pub test_cases: Option<serde_json::Value>,
+    pub scoring_policy: Option<serde_json::Value>,
+    pub rubric: Option<Option<serde_json::Value>>,
+}
+
+impl UpdateQuizQuestion {
+    /// Returns true if the update only touches the answer key, scoring
+    /// policy, rubric or explanation, which may change after students
+    /// submitted (existing submissions are then regraded).
+    pub fn only_changes_answer_key(&self) -> bool {
+        self.question_text.is_none()
+            && self.question_type.is_none()
+            && self.points.is_none()
+            && self.sort_order.is_none()
+            && self.options.is_none()
+            && self.code_language.is_none()
+            && self.test_cases.is_none()
+    }
+}
+
+// =============================================================================
+// SCORING POLICY
+// =============================================================================
+
+/// Per-question scoring rules for auto-graded questions.
+///
+/// The default is all-or-nothing with case-insensitive exact matching of
+/// short answers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringPolicy {
+    /// Multiple choice: award the share of correct options picked instead
+    /// of all-or-nothing
+    pub partial_credit: bool,
+    /// Fraction of the question points deducted per wrong pick (multiple
+    /// choice) or for a wrong answer (other types). Unanswered questions
+    /// are never penalized.
+    pub wrong_penalty: f64,
+    /// Allow the question score to go below zero (negative marking)
+    pub allow_negative: bool,
+    /// How short answers are compared with the accepted answers
+    pub text_match: TextMatch,
+    /// Grade short answers as numbers with a tolerance
+    pub numeric: Option<NumericTolerance>,
+}
+
+/// Short answer matching modes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum TextMatch {
+    /// Equal after trimming (and lowercasing unless case sensitive)
+    Exact {
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    /// Accepted answers are regular expressions matching the whole answer
+    Regex {
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    /// Normalized edit-distance similarity of at least `min_similarity`
+    Fuzzy {
+        #[serde(default = "default_min_similarity")]
+        min_similarity: f64,
+    },
+}
+
+impl Default for TextMatch {
+    fn default() -> Self {
+        TextMatch::Exact { case_sensitive: false }
+    }
+}
+
+fn default_min_similarity() -> f64 {
+    0.85
+}
+
+/// Numeric answer grading.
+///
+/// Accepted answers are numbers; student answers may carry a unit
+/// (e.g. `"9.81 m/s^2"`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumericTolerance {
+    /// Maximum difference from an accepted answer
+    pub tolerance: f64,
+    /// Interpret `tolerance` as a fraction of the accepted answer
+    pub relative: bool,
+    /// Accepted units (empty = no unit allowed)
+    pub units: Vec<String>,
+    /// Reject answers without a unit
+    pub require_unit: bool,
+}
+
+// =============================================================================
+// RUBRICS
+// =============================================================================
+
+/// Grading rubric of an essay question.
+///
+/// Each criterion is graded by picking one of its performance levels. The
+/// rubric total is scaled to the question's points, so a rubric worth 12
+/// on a 6-point question awards half a point per rubric point.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rubric {
+    pub criteria: Vec<RubricCriterion>,
+}
+
+impl Rubric {
+    /// Highest rubric total: the best level of every criterion.
+    pub fn max_points(&self) -> f64 {
+        self.criteria.iter().map(RubricCriterion::max_points).sum()
+    }
+}
+
+/// A rubric criterion (e.g. "Thesis", "Evidence").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RubricCriterion {
+    /// Identifier, unique within the rubric
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Performance levels, usually from lowest to highest
+    pub levels: Vec<RubricLevel>,
+}
+
+impl RubricCriterion {
+    /// Points of the best level.
+    pub fn max_points(&self) -> f64 {
+        self.levels.iter().map(|l| l.points).fold(0.0, f64::max)
+    }
+}
+
+/// A performance level of a criterion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RubricLevel {
+    /// Identifier, unique within the criterion
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub points: f64,
+}
+
+/// Level picked by the grader for one criterion.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RubricSelection {
+    pub criterion_id: String,
+    pub level_id: String,
+    pub comment: Option<String>,
+}
+
+/// Graded criterion stored with the response.
+///
+/// Titles and points are copied from the rubric so later rubric edits do
+/// not change grades already returned to students.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RubricScore {
+    pub criterion_id: String,
+    pub criterion: String,
+    pub level_id: String,
+    pub level: String,
+    /// Rubric points of the selected level
+    pub points: f64,
+    /// Rubric points of the criterion's best level
+    pub max_points: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+// =============================================================================
+// CODE TEST CASES
+// =============================================================================
+
+/// A hidden test case of a code question.
+///
+/// The submitted program receives `input` on stdin and passes when its stdout
+/// matches `expected_output` (ignoring trailing whitespace).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeTestCase {
+    /// Label shown to the student in the results
+    pub name: String,
+    /// Data written to stdin
+    #[serde(default)]
+    pub input: String,
+    /// Expected stdout
+    pub expected_output: String,
+    /// Relative weight for partial credit
+    #[serde(default = "default_test_weight")]
+    pub weight: f64,
+}
+
+fn default_test_weight() -> f64 {
+    1.0
+}
+
+/// Outcome of a single test case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestCaseStatus {
+    /// Output matched
+    Passed,
+    /// Program ran but the output did not match
+    WrongAnswer,
+    /// Program exited with an error or was killed by a signal
+    RuntimeError,
+    /// Program exceeded the time limit
+    TimeLimitExceeded,
+    /// Program wrote more output than allowed
+    OutputLimitExceeded,
+    /// Program did not compile
+    CompileError,
+}
+
+/// Result of running a submission against one test case.
+///
+/// Inputs and expected outputs are never included, so results can be shown
+/// to students without revealing the hidden tests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseResult {
+    /// Test case label
+    pub name: String,
+    /// Outcome
+    pub status: TestCaseStatus,
+    /// Test case weight
+    pub weight: f64,
+    /// Wall-clock run time in milliseconds
+    pub time_ms: u64,
+    /// Compiler output for compile errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 // =============================================================================
@@ -347,6 +632,10 @@ pub struct QuizSubmission {
     pub graded_at: Option<DateTime<Utc>>,
     /// Instructor feedback on submission
     pub instructor_feedback: Option<String>,
+    /// Server-side deadline (None = no time limit)
+    pub deadline_at: Option<DateTime<Utc>>,
+    /// Whether the attempt was submitted by the expiry sweeper
+    pub auto_submitted: bool,
 }
 
 impl QuizSubmission {
@@ -376,6 +665,18 @@ impl QuizSubmission {
         let secs = seconds % 60;
         format!("{}:{:02}", minutes, secs)
     }
+
+    /// Returns true if `now` is past the deadline plus `grace_seconds`.
+    pub fn is_expired(&self, now: DateTime<Utc>, grace_seconds: i64) -> bool {
+        self.deadline_at
+            .is_some_and(|deadline| now > deadline + chrono::Duration::seconds(grace_seconds))
+    }
+
+    /// Seconds spent on the attempt as of `now`, capped at the deadline.
+    pub fn elapsed_seconds(&self, now: DateTime<Utc>) -> i32 {
+        let end = self.deadline_at.map_or(now, |deadline| now.min(deadline));
+        (end - self.started_at).num_seconds().clamp(0, i32::MAX as i64) as i32
+    }
 }
 
 /// Data required to start a new submission.
@@ -385,6 +686,8 @@ pub struct NewQuizSubmission {
     pub user_id: Uuid,
     pub enrollment_id: Uuid,
     pub max_score: f64,
+    /// Seconds until the deadline, counted from the start (None = unlimited)
+    pub time_allowed_seconds: Option<i64>,
 }
 
 /// Data for updating a submission.
@@ -399,6 +702,336 @@ pub struct UpdateQuizSubmission {
     pub instructor_feedback: Option<Option<String>>,
 }
 
+// =============================================================================
+// ACCOMMODATIONS
+// =============================================================================
+
+/// Extra time or attempts granted to a student.
+///
+/// Applies to every quiz of the course, or to a single quiz when `quiz_id`
+/// is set (quiz-specific accommodations take precedence).
+///
+/// # Database Mapping
+///
+/// Maps to `assessments.quiz_accommodations` table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizAccommodation {
+    /// Unique identifier
+    pub accommodation_id: Uuid,
+    /// Course the accommodation applies to
+    pub course_id: Uuid,
+    /// Single quiz it applies to (None = every quiz in the course)
+    pub quiz_id: Option<Uuid>,
+    /// Student receiving the accommodation
+    pub user_id: Uuid,
+    /// Multiplier on the time limit (e.g., 1.5 = time and a half)
+    pub time_multiplier: f64,
+    /// Minutes added after the multiplier
+    pub extra_time_minutes: i32,
+    /// Attempts added to `max_attempts`
+    pub extra_attempts: i32,
+    /// Instructor notes
+    pub notes: Option<String>,
+    /// Record creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Data for creating or replacing an accommodation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewQuizAccommodation {
+    pub course_id: Uuid,
+    pub quiz_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub time_multiplier: f64,
+    pub extra_time_minutes: i32,
+    pub extra_attempts: i32,
+    pub notes: Option<String>,
+}
+
+// =============================================================================
+// QUESTION BANKS
+// =============================================================================
+
+/// Difficulty of a bank question.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+/// A reusable pool of questions for a course.
+///
+/// # Database Mapping
+///
+/// Maps to `assessments.question_banks` table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuestionBank {
+    /// Unique identifier
+    pub bank_id: Uuid,
+    /// Course owning the bank
+    pub course_id: Uuid,
+    /// Bank title
+    pub title: String,
+    /// Description of the bank
+    pub description: Option<String>,
+    /// Record creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Data required to create a question bank.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewQuestionBank {
+    pub course_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// Data for updating a question bank.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateQuestionBank {
+    pub title: Option<String>,
+    pub description: Option<Option<String>>,
+}
+
+/// A bank question with the content of its current version.
+///
+/// Content is versioned: editing it creates a new immutable row in
+/// `assessments.bank_question_versions`, so attempts keep the version they
+/// received. Tags and difficulty are plain metadata.
+///
+/// # Database Mapping
+///
+/// `assessments.bank_questions` joined with its current version.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BankQuestion {
+    /// Unique identifier
+    pub bank_question_id: Uuid,
+    /// Parent bank
+    pub bank_id: Uuid,
+    /// Topic tags
+    pub tags: Vec<String>,
+    /// Difficulty level
+    pub difficulty: Difficulty,
+    /// Current version number
+    pub version: i32,
+    /// Current version ID (used as the question ID in attempts)
+    pub version_id: Uuid,
+    /// Type of question
+    pub question_type: QuestionType,
+    /// Question text (supports markdown)
+    pub question_text: String,
+    /// Answer options as JSON array
+    pub options: serde_json::Value,
+    /// Correct answers as JSON array
+    pub correct_answers: serde_json::Value,
+    /// Default points
+    pub points: i32,
+    /// Explanation shown after answering
+    pub explanation: Option<String>,
+    /// Programming language for code questions
+    pub code_language: Option<String>,
+    /// Hidden test cases for code questions
+    pub test_cases: serde_json::Value,
+    /// Scoring policy (see [`ScoringPolicy`])
+    pub scoring_policy: serde_json::Value,
+    /// Grading rubric for essay questions (see [`Rubric`])
+    pub rubric: Option<serde_json::Value>,
+    /// Whether the question is excluded from new draws
+    pub is_archived: bool,
+    /// Record creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Content of a bank question version.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BankQuestionContent {
+    pub question_type: QuestionType,
+    pub question_text: String,
+    pub options: serde_json::Value,
+    pub correct_answers: serde_json::Value,
+    pub points: i32,
+    pub explanation: Option<String>,
+    pub code_language: Option<String>,
+    pub test_cases: serde_json::Value,
+    pub scoring_policy: serde_json::Value,
+    pub rubric: Option<serde_json::Value>,
+}
+
+/// Data required to add a question to a bank.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewBankQuestion {
+    pub bank_id: Uuid,
+    pub tags: Vec<String>,
+    pub difficulty: Difficulty,
+    pub content: BankQuestionContent,
+}
+
+/// Data for updating a bank question.
+///
+/// Any content change creates a new version.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateBankQuestion {
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub question_type: Option<QuestionType>,
+    pub question_text: Option<String>,
+    pub options: Option<serde_json::Value>,
+    pub correct_answers: Option<serde_json::Value>,
+    pub points: Option<i32>,
+    pub explanation: Option<Option<String>>,
+    pub code_language: Option<Option<String>>,
+    pub test_cases: Option<serde_json::Value>,
+    pub scoring_policy: Option<serde_json::Value>,
+    pub rubric: Option<Option<serde_json::Value>>,
+}
+
+impl UpdateBankQuestion {
+    /// Returns true if the update touches versioned content.
+    pub fn changes_content(&self) -> bool {
+        self.question_type.is_some()
+            || self.question_text.is_some()
+            || self.options.is_some()
+            || self.correct_answers.is_some()
+            || self.points.is_some()
+            || self.explanation.is_some()
+            || self.code_language.is_some()
+            || self.test_cases.is_some()
+            || self.scoring_policy.is_some()
+            || self.rubric.is_some()
+    }
+
+    /// Applies the content changes on top of `question`.
+    pub fn merged_content(&self, question: &BankQuestion) -> BankQuestionContent {
+        BankQuestionContent {
+            question_type: self.question_type.unwrap_or(question.question_type),
+            question_text: self.question_text.clone().unwrap_or_else(|| question.question_text.clone()),
+            options: self.options.clone().unwrap_or_else(|| question.options.clone()),
+            correct_answers: self.correct_answers.clone().unwrap_or_else(|| question.correct_answers.clone()),
+            points: self.points.unwrap_or(question.points),
+            explanation: self.explanation.clone().unwrap_or_else(|| question.explanation.clone()),
+            code_language: self.code_language.clone().unwrap_or_else(|| question.code_language.clone()),
+            test_cases: self.test_cases.clone().unwrap_or_else(|| question.test_cases.clone()),
+            scoring_policy: self.scoring_policy.clone().unwrap_or_else(|| question.scoring_policy.clone()),
+            rubric: self.rubric.clone().unwrap_or_else(|| question.rubric.clone()),
+        }
+    }
+}
+
+/// Rule drawing random questions from a bank into each attempt.
+///
+/// # Database Mapping
+///
+/// Maps to `assessments.quiz_draw_rules` table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizDrawRule {
+    /// Unique identifier
+    pub rule_id: Uuid,
+    /// Quiz the rule belongs to
+    pub quiz_id: Uuid,
+    /// Bank to draw from
+    pub bank_id: Uuid,
+    /// Questions drawn per attempt
+    pub question_count: i32,
+    /// Only draw questions of this difficulty
+    pub difficulty: Option<Difficulty>,
+    /// Only draw questions with any of these tags (empty = any)
+    pub tags: Vec<String>,
+    /// Points of each drawn question (keeps totals equal across attempts)
+    pub points_per_question: i32,
+    /// Position among the quiz's fixed questions and other rules
+    pub sort_order: i32,
+    /// Record creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data required to add a draw rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewQuizDrawRule {
+    pub quiz_id: Uuid,
+    pub bank_id: Uuid,
+    pub question_count: i32,
+    pub difficulty: Option<Difficulty>,
+    pub tags: Vec<String>,
+    pub points_per_question: i32,
+    pub sort_order: i32,
+}
+
+// =============================================================================
+// SUBMISSION QUESTIONS
+// =============================================================================
+
+/// A question as delivered in one attempt.
+///
+/// `question.question_id` is the fixed quiz question ID or the bank
+/// question version ID, and is what responses refer to. Points and
+/// `sort_order` are those of the attempt.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AssignedQuestion {
+    /// Question content
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub question: QuizQuestion,
+    /// Bank question version (None = fixed quiz question)
+    pub version_id: Option<Uuid>,
+    /// Indices into `question.options` in display order
+    pub option_order: Option<serde_json::Value>,
+}
+
+impl AssignedQuestion {
+    /// Returns the options in the order shown to the student.
+    pub fn presented_options(&self) -> serde_json::Value {
+        let (Some(order), Some(options)) = (
+            self.option_order.as_ref().and_then(|o| o.as_array()),
+            self.question.options.as_array(),
+        ) else {
+            return self.question.options.clone();
+        };
+
+        let presented: Vec<serde_json::Value> = order
+            .iter()
+            .filter_map(|i| i.as_u64().and_then(|i| options.get(i as usize)).cloned())
+            .collect();
+
+        if presented.len() == options.len() {
+            serde_json::Value::Array(presented)
+        } else {
+            self.question.options.clone()
+        }
+    }
+}
+
+/// A question chosen for a new attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewSubmissionQuestion {
+    /// Fixed quiz question
+    pub quiz_question_id: Option<Uuid>,
+    /// Bank question version
+    pub version_id: Option<Uuid>,
+    /// Draw rule that picked it
+    pub rule_id: Option<Uuid>,
+    /// Points in this attempt
+    pub points: i32,
+    /// Display order of the options
+    pub option_order: Option<Vec<usize>>,
+}
+
+impl NewSubmissionQuestion {
+    /// ID stored on responses to this question.
+    pub fn question_id(&self) -> Uuid {
+        self.quiz_question_id.or(self.version_id).unwrap_or_default()
+    }
+}
+
 // =============================================================================
 // QUIZ RESPONSE
 // =============================================================================
@@ -427,6 +1060,10 @@ pub struct QuizResponse {
     pub instructor_feedback: Option<String>,
     /// Whether this was auto-graded
     pub auto_graded: bool,
+    /// Per-test results for auto-graded code questions
+    pub test_results: Option<serde_json::Value>,
+    /// Per-criterion breakdown of rubric grading (see [`RubricScore`])
+    pub rubric_scores: Option<serde_json::Value>,
     /// Record creation timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -504,3 +1141,97 @@ impl SubmissionWithResponses {
             .count()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn quiz(time_limit_minutes: Option<i32>, max_attempts: Option<i32>) -> Quiz {
+        Quiz {
+            quiz_id: Uuid::new_v4(),
+            course_id: Uuid::new_v4(),
+            lesson_id: None,
+            title: "Quiz".into(),
+            description: None,
+            instructions: None,
+            total_points: 100,
+            passing_score_percentage: 70.0,
+            time_limit_minutes,
+            max_attempts,
+            shuffle_questions: false,
+            shuffle_options: false,
+            show_correct_answers: true,
+            is_published: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn accommodation(time_multiplier: f64, extra_time_minutes: i32, extra_attempts: i32) -> QuizAccommodation {
+        QuizAccommodation {
+            accommodation_id: Uuid::new_v4(),
+            course_id: Uuid::new_v4(),
+            quiz_id: None,
+            user_id: Uuid::new_v4(),
+            time_multiplier,
+            extra_time_minutes,
+            extra_attempts,
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn submission(started_at: DateTime<Utc>, deadline_at: Option<DateTime<Utc>>) -> QuizSubmission {
+        QuizSubmission {
+            submission_id: Uuid::new_v4(),
+            quiz_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            enrollment_id: Uuid::new_v4(),
+            attempt_number: 1,
+            status: SubmissionStatus::InProgress,
+            score: 0.0,
+            max_score: 100.0,
+            passed: None,
+            time_spent_seconds: None,
+            started_at,
+            submitted_at: None,
+            graded_at: None,
+            instructor_feedback: None,
+            deadline_at,
+            auto_submitted: false,
+        }
+    }
+
+    #[test]
+    fn test_time_allowed_with_accommodation() {
+        let timed = quiz(Some(30), None);
+        assert_eq!(timed.time_allowed_seconds(None), Some(1800));
+        assert_eq!(timed.time_allowed_seconds(Some(&accommodation(1.5, 0, 0))), Some(2700));
+        assert_eq!(timed.time_allowed_seconds(Some(&accommodation(1.5, 10, 0))), Some(3300));
+        assert_eq!(quiz(None, None).time_allowed_seconds(Some(&accommodation(2.0, 10, 0))), None);
+    }
+
+    #[test]
+    fn test_attempt_limit_with_accommodation() {
+        assert_eq!(quiz(None, Some(1)).attempt_limit(None), Some(1));
+        assert_eq!(quiz(None, Some(1)).attempt_limit(Some(&accommodation(1.0, 0, 2))), Some(3));
+        assert_eq!(quiz(None, None).attempt_limit(Some(&accommodation(1.0, 0, 2))), None);
+    }
+
+    #[test]
+    fn test_deadline_and_elapsed_time() {
+        let started = Utc::now() - Duration::minutes(40);
+        let timed = submission(started, Some(started + Duration::minutes(30)));
+        let now = Utc::now();
+
+        assert!(timed.is_expired(now, 30));
+        assert!(!timed.is_expired(started + Duration::minutes(30) + Duration::seconds(20), 30));
+        assert_eq!(timed.elapsed_seconds(now), 1800);
+
+        let untimed = submission(started, None);
+        assert!(!untimed.is_expired(now, 0));
+        assert_eq!(untimed.elapsed_seconds(started + Duration::seconds(95)), 95);
+    }
+}
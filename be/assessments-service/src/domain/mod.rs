@@ -8,6 +8,11 @@
 //! - [`QuizQuestion`]: Questions within a quiz
 //! - [`QuizSubmission`]: Student quiz attempt
 //! - [`QuizResponse`]: Individual question response
+//! - [`QuizAccommodation`]: Per-student extra time and attempts
+//! - [`QuestionBank`] / [`BankQuestion`]: Reusable, versioned questions
+//! - [`QuizDrawRule`]: Random draws from a bank into each attempt
+//! - [`AssignedQuestion`]: The exact questions an attempt received
+//! - [`Rubric`]: Criteria and performance levels for grading essays
 //!
 //! ## Events
 //!
@@ -20,8 +25,13 @@ pub mod events;
 pub mod value_objects;
 
 pub use entities::{
-    NewQuiz, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
-    Quiz, QuizQuestion, QuestionType, QuizResponse, QuizSubmission,
+    AssignedQuestion, BankQuestion, BankQuestionContent, Difficulty, NewBankQuestion,
+    NewQuestionBank, NewQuizDrawRule, NewSubmissionQuestion, QuestionBank, QuizDrawRule,
+    UpdateBankQuestion, UpdateQuestionBank,
+    CodeTestCase, NewQuiz, NewQuizAccommodation, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
+    NumericTolerance, Quiz, QuizAccommodation, QuizQuestion, QuestionType, QuizResponse, QuizSubmission,
+    Rubric, RubricScore, RubricSelection,
+    ScoringPolicy, TestCaseResult, TestCaseStatus, TextMatch,
     SubmissionStatus, UpdateQuiz, UpdateQuizQuestion, UpdateQuizSubmission,
     QuizWithQuestions, SubmissionWithResponses,
 };
@@ -0,0 +1,242 @@
+//! # Code Grader
+//!
+//! Grades code responses by running them against the question's hidden
+//! test cases. Each passed test earns its share of the question points
+//! (weighted by `CodeTestCase::weight`).
+
+use std::sync::Arc;
+
+use crate::domain::{CodeTestCase, QuizQuestion, TestCaseResult, TestCaseStatus};
+
+use super::runner::{CodeRunner, ExitStatus, RunReport, RunnerError};
+use super::scoring::round_points;
+
+/// Result of grading one code response.
+#[derive(Debug, Clone)]
+pub struct CodeGrade {
+    /// True if every test passed
+    pub is_correct: bool,
+    /// Points earned (partial credit)
+    pub points: f64,
+    /// One result per test case
+    pub results: Vec<TestCaseResult>,
+}
+
+/// Grades code responses with a [`CodeRunner`].
+#[derive(Clone)]
+pub struct CodeGrader {
+    runner: Arc<dyn CodeRunner>,
+}
+
+impl std::fmt::Debug for CodeGrader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodeGrader").finish_non_exhaustive()
+    }
+}
+
+impl CodeGrader {
+    /// Creates a grader backed by `runner`.
+    pub fn new(runner: Arc<dyn CodeRunner>) -> Self {
+        Self { runner }
+    }
+
+    /// Returns true if code in `language` can be auto-graded.
+    pub fn supports(&self, language: &str) -> bool {
+        self.runner.supports(language)
+    }
+
+    /// Runs a response against the question's test cases.
+    pub async fn grade(
+        &self,
+        question: &QuizQuestion,
+        answer: &serde_json::Value,
+    ) -> Result<CodeGrade, RunnerError> {
+        let cases = question.code_test_cases();
+        let language = question.code_language.as_deref().unwrap_or_default();
+
+        let report = match submitted_source(answer) {
+            Some(source) => {
+                let inputs: Vec<String> = cases.iter().map(|c| c.input.clone()).collect();
+                self.runner.run(language, source, &inputs).await?
+            }
+            None => RunReport::CompileError("No code submitted".into()),
+        };
+
+        Ok(score(question.points, evaluate(&cases, &report)))
+    }
+}
+
+/// Extracts the source from an answer: either a plain string or
+/// `{"code": "..."}`.
+pub fn submitted_source(answer: &serde_json::Value) -> Option<&str> {
+    answer
+        .as_str()
+        .or_else(|| answer.get("code").and_then(|c| c.as_str()))
+        .filter(|source| !source.trim().is_empty())
+}
+
+/// Turns a run report into per-test results.
+pub fn evaluate(cases: &[CodeTestCase], report: &RunReport) -> Vec<TestCaseResult> {
+    cases
+        .iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let (status, time_ms, message) = match report {
+                RunReport::CompileError(message) => (TestCaseStatus::CompileError, 0, Some(message.clone())),
+                RunReport::Completed(outcomes) => match outcomes.get(i) {
+                    Some(outcome) => {
+                        let status = match outcome.status {
+                            ExitStatus::Exited(0) if outputs_match(&outcome.stdout, &case.expected_output) => {
+                                TestCaseStatus::Passed
+                            }
+                            ExitStatus::Exited(0) => TestCaseStatus::WrongAnswer,
+                            ExitStatus::Exited(_) | ExitStatus::Signaled(_) => TestCaseStatus::RuntimeError,
+                            ExitStatus::TimedOut => TestCaseStatus::TimeLimitExceeded,
+                            ExitStatus::OutputLimitExceeded => TestCaseStatus::OutputLimitExceeded,
+                        };
+                        (status, outcome.duration.as_millis() as u64, None)
+                    }
+                    None => (TestCaseStatus::RuntimeError, 0, None),
+                },
+            };
+
+            TestCaseResult {
+                name: case.name.clone(),
+                status,
+                weight: case.weight,
+                time_ms,
+                message,
+            }
+        })
+        .collect()
+}
+
+/// Compares outputs ignoring line endings and trailing whitespace.
+pub fn outputs_match(actual: &str, expected: &str) -> bool {
+    fn normalize(text: &str) -> Vec<&str> {
+        let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+
+    normalize(actual) == normalize(expected)
+}
+
+/// Awards the weighted share of `points` for the passed tests.
+pub fn score(points: i32, results: Vec<TestCaseResult>) -> CodeGrade {
+    let passed = |r: &&TestCaseResult| r.status == TestCaseStatus::Passed;
+
+    let total_weight: f64 = results.iter().map(|r| r.weight.max(0.0)).sum();
+    let fraction = if results.is_empty() {
+        0.0
+    } else if total_weight > 0.0 {
+        results.iter().filter(passed).map(|r| r.weight.max(0.0)).sum::<f64>() / total_weight
+    } else {
+        results.iter().filter(passed).count() as f64 / results.len() as f64
+    };
+
+    CodeGrade {
+        is_correct: !results.is_empty() && results.iter().all(|r| r.status == TestCaseStatus::Passed),
+        points: round_points(points as f64 * fraction),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::grading::runner::RunOutcome;
+
+    fn case(name: &str, expected: &str, weight: f64) -> CodeTestCase {
+        CodeTestCase {
+            name: name.to_string(),
+            input: String::new(),
+            expected_output: expected.to_string(),
+            weight,
+        }
+    }
+
+    fn outcome(status: ExitStatus, stdout: &str) -> RunOutcome {
+        RunOutcome {
+            status,
+            stdout: stdout.to_string(),
+            duration: Duration::from_millis(12),
+        }
+    }
+
+    #[test]
+    fn test_outputs_match() {
+        assert!(outputs_match("42\n", "42"));
+        assert!(outputs_match("a  \r\nb\n\n", "a\nb"));
+        assert!(!outputs_match("4 2", "42"));
+        assert!(!outputs_match("\n42", "42"));
+    }
+
+    #[test]
+    fn test_submitted_source() {
+        assert_eq!(submitted_source(&serde_json::json!("print(1)")), Some("print(1)"));
+        assert_eq!(submitted_source(&serde_json::json!({"code": "print(1)"})), Some("print(1)"));
+        assert_eq!(submitted_source(&serde_json::json!("   ")), None);
+        assert_eq!(submitted_source(&serde_json::json!(["a"])), None);
+    }
+
+    #[test]
+    fn test_weighted_partial_credit() {
+        let cases = [case("small", "1", 1.0), case("large", "2", 3.0), case("edge", "3", 1.0)];
+        let report = RunReport::Completed(vec![
+            outcome(ExitStatus::Exited(0), "1\n"),
+            outcome(ExitStatus::Exited(0), "2\n"),
+            outcome(ExitStatus::TimedOut, ""),
+        ]);
+
+        let grade = score(10, evaluate(&cases, &report));
+
+        assert!(!grade.is_correct);
+        assert_eq!(grade.points, 8.0);
+        assert_eq!(grade.results[2].status, TestCaseStatus::TimeLimitExceeded);
+        assert_eq!(grade.results[0].time_ms, 12);
+    }
+
+    #[test]
+    fn test_statuses() {
+        let cases = [case("a", "1", 1.0), case("b", "1", 1.0), case("c", "1", 1.0)];
+        let report = RunReport::Completed(vec![
+            outcome(ExitStatus::Exited(0), "2"),
+            outcome(ExitStatus::Exited(1), "1"),
+            outcome(ExitStatus::OutputLimitExceeded, "1"),
+        ]);
+
+        let statuses: Vec<_> = evaluate(&cases, &report).into_iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [TestCaseStatus::WrongAnswer, TestCaseStatus::RuntimeError, TestCaseStatus::OutputLimitExceeded]
+        );
+    }
+
+    #[test]
+    fn test_compile_error_fails_every_test() {
+        let cases = [case("a", "1", 1.0), case("b", "2", 1.0)];
+        let grade = score(5, evaluate(&cases, &RunReport::CompileError("syntax error".into())));
+
+        assert_eq!(grade.points, 0.0);
+        assert!(grade.results.iter().all(|r| r.status == TestCaseStatus::CompileError));
+        assert_eq!(grade.results[0].message.as_deref(), Some("syntax error"));
+    }
+
+    #[test]
+    fn test_all_passed() {
+        let cases = [case("a", "ok", 0.0), case("b", "ok", 0.0)];
+        let report = RunReport::Completed(vec![
+            outcome(ExitStatus::Exited(0), "ok"),
+            outcome(ExitStatus::Exited(0), "ok"),
+        ]);
+
+        let grade = score(7, evaluate(&cases, &report));
+        assert!(grade.is_correct);
+        assert_eq!(grade.points, 7.0);
+    }
+}
@@ -0,0 +1,18 @@
+//! # Grading Module
+//!
+//! Automatic and rubric-assisted grading.
+//!
+//! - [`scoring`]: objective questions scored by their scoring policy
+//! - [`rubric`]: essay grading by rubric criteria and levels
+//! - [`runner::CodeRunner`]: compiles and runs submissions (pluggable per backend)
+//! - [`LocalSandboxRunner`]: resource-limited local process sandbox
+//! - [`CodeGrader`]: runs hidden test cases and computes partial credit
+
+pub mod code_grader;
+pub mod rubric;
+pub mod runner;
+pub mod sandbox;
+pub mod scoring;
+
+pub use code_grader::CodeGrader;
+pub use sandbox::{LocalSandboxRunner, SandboxConfig};
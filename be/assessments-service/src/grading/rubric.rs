@@ -0,0 +1,280 @@
+//! # Rubric Grading
+//!
+//! Grades essay responses by the level picked for each criterion of the
+//! question's [`Rubric`], and aggregates graded breakdowns into
+//! per-criterion statistics.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use super::scoring::round_points;
+use crate::domain::{QuestionType, Rubric, RubricScore, RubricSelection};
+
+/// Result of grading a response with a rubric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RubricGrade {
+    /// Breakdown stored with the response
+    pub scores: Vec<RubricScore>,
+    /// Question points earned (rubric total scaled to the question)
+    pub points: f64,
+    /// True if every criterion got its best level
+    pub is_correct: bool,
+}
+
+/// Checks that a rubric is well formed and allowed on the question type.
+pub fn validate_rubric(rubric: &Value, question_type: QuestionType) -> Result<Rubric, String> {
+    let rubric: Rubric = serde_json::from_value(rubric.clone())
+        .map_err(|e| format!("Invalid rubric: {}", e))?;
+
+    if question_type != QuestionType::Essay {
+        return Err("Rubrics are only allowed on essay questions".into());
+    }
+    if rubric.criteria.is_empty() {
+        return Err("Rubric needs at least one criterion".into());
+    }
+
+    let mut criterion_ids = HashSet::new();
+    for criterion in &rubric.criteria {
+        if criterion.id.trim().is_empty() || criterion.title.trim().is_empty() {
+            return Err("Rubric criteria need an id and a title".into());
+        }
+        if !criterion_ids.insert(criterion.id.as_str()) {
+            return Err(format!("Duplicate rubric criterion '{}'", criterion.id));
+        }
+        if criterion.levels.is_empty() {
+            return Err(format!("Criterion '{}' needs at least one level", criterion.id));
+        }
+
+        let mut level_ids = HashSet::new();
+        for level in &criterion.levels {
+            if level.id.trim().is_empty() || level.title.trim().is_empty() {
+                return Err(format!("Levels of criterion '{}' need an id and a title", criterion.id));
+            }
+            if !level_ids.insert(level.id.as_str()) {
+                return Err(format!("Duplicate level '{}' in criterion '{}'", level.id, criterion.id));
+            }
+            if !level.points.is_finite() || level.points < 0.0 {
+                return Err(format!("Level '{}' of criterion '{}' has invalid points", level.id, criterion.id));
+            }
+        }
+    }
+
+    if rubric.max_points() <= 0.0 {
+        return Err("Rubric must be worth more than zero points".into());
+    }
+
+    Ok(rubric)
+}
+
+/// Grades a response from the grader's level selections.
+///
+/// Every criterion needs exactly one selection. The rubric total is scaled
+/// to `question_points`.
+pub fn grade(rubric: &Rubric, question_points: i32, selections: &[RubricSelection]) -> Result<RubricGrade, String> {
+    if let Some(unknown) = selections
+        .iter()
+        .find(|s| !rubric.criteria.iter().any(|c| c.id == s.criterion_id))
+    {
+        return Err(format!("Unknown rubric criterion '{}'", unknown.criterion_id));
+    }
+
+    let mut scores = Vec::with_capacity(rubric.criteria.len());
+    for criterion in &rubric.criteria {
+        let mut picked = selections.iter().filter(|s| s.criterion_id == criterion.id);
+        let selection = picked
+            .next()
+            .ok_or_else(|| format!("No level selected for criterion '{}'", criterion.id))?;
+        if picked.next().is_some() {
+            return Err(format!("Criterion '{}' was selected more than once", criterion.id));
+        }
+
+        let level = criterion
+            .levels
+            .iter()
+            .find(|l| l.id == selection.level_id)
+            .ok_or_else(|| format!("Unknown level '{}' for criterion '{}'", selection.level_id, criterion.id))?;
+
+        scores.push(RubricScore {
+            criterion_id: criterion.id.clone(),
+            criterion: criterion.title.clone(),
+            level_id: level.id.clone(),
+            level: level.title.clone(),
+            points: level.points,
+            max_points: criterion.max_points(),
+            comment: selection.comment.clone().filter(|c| !c.trim().is_empty()),
+        });
+    }
+
+    let total: f64 = scores.iter().map(|s| s.points).sum();
+    let max = rubric.max_points();
+    let points = if max > 0.0 { question_points as f64 * total / max } else { 0.0 };
+
+    Ok(RubricGrade {
+        scores,
+        points: round_points(points),
+        is_correct: max > 0.0 && total >= max,
+    })
+}
+
+/// Statistics of one criterion over graded responses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriterionStats {
+    pub criterion_id: String,
+    pub criterion: String,
+    pub max_points: f64,
+    /// Responses graded on this criterion
+    pub graded_count: i64,
+    /// Average rubric points on this criterion
+    pub average_points: f64,
+    /// How often each level was picked, lowest points first
+    pub levels: Vec<LevelCount>,
+}
+
+/// How often a level was picked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelCount {
+    pub level_id: String,
+    pub level: String,
+    pub points: f64,
+    pub count: i64,
+}
+
+/// Aggregates the stored breakdowns of one question's responses.
+///
+/// Works from the breakdowns alone, so grades given with an older version
+/// of the rubric are still counted under the titles they were given with.
+/// Criteria keep the order in which they first appear.
+pub fn criterion_stats(breakdowns: &[Vec<RubricScore>]) -> Vec<CriterionStats> {
+    let mut stats: Vec<CriterionStats> = Vec::new();
+
+    for score in breakdowns.iter().flatten() {
+        let index = match stats.iter().position(|s| s.criterion_id == score.criterion_id) {
+            Some(index) => index,
+            None => {
+                stats.push(CriterionStats {
+                    criterion_id: score.criterion_id.clone(),
+                    criterion: score.criterion.clone(),
+                    max_points: score.max_points,
+                    graded_count: 0,
+                    average_points: 0.0,
+                    levels: Vec::new(),
+                });
+                stats.len() - 1
+            }
+        };
+
+        let entry = &mut stats[index];
+        entry.graded_count += 1;
+        // Running sum, turned into the average below
+        entry.average_points += score.points;
+        entry.max_points = entry.max_points.max(score.max_points);
+
+        match entry.levels.iter_mut().find(|l| l.level_id == score.level_id) {
+            Some(level) => level.count += 1,
+            None => entry.levels.push(LevelCount {
+                level_id: score.level_id.clone(),
+                level: score.level.clone(),
+                points: score.points,
+                count: 1,
+            }),
+        }
+    }
+
+    for entry in &mut stats {
+        entry.average_points = round_points(entry.average_points / entry.graded_count as f64);
+        entry.levels.sort_by(|a, b| a.points.total_cmp(&b.points));
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rubric() -> Rubric {
+        validate_rubric(
+            &json!({"criteria": [
+                {"id": "thesis", "title": "Thesis", "levels": [
+                    {"id": "weak", "title": "Weak", "points": 1},
+                    {"id": "strong", "title": "Strong", "points": 4}
+                ]},
+                {"id": "style", "title": "Style", "levels": [
+                    {"id": "poor", "title": "Poor", "points": 0},
+                    {"id": "good", "title": "Good", "points": 2}
+                ]}
+            ]}),
+            QuestionType::Essay,
+        )
+        .unwrap()
+    }
+
+    fn select(criterion_id: &str, level_id: &str) -> RubricSelection {
+        RubricSelection { criterion_id: criterion_id.into(), level_id: level_id.into(), comment: None }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_rubrics() {
+        let rubric = json!({"criteria": [{"id": "a", "title": "A", "levels": [{"id": "x", "title": "X", "points": 1}]}]});
+        assert!(validate_rubric(&rubric, QuestionType::Essay).is_ok());
+        assert!(validate_rubric(&rubric, QuestionType::ShortAnswer).is_err());
+        assert!(validate_rubric(&json!({"criteria": []}), QuestionType::Essay).is_err());
+
+        let duplicate = json!({"criteria": [
+            {"id": "a", "title": "A", "levels": [{"id": "x", "title": "X", "points": 1}]},
+            {"id": "a", "title": "B", "levels": [{"id": "y", "title": "Y", "points": 1}]}
+        ]});
+        assert!(validate_rubric(&duplicate, QuestionType::Essay).is_err());
+
+        let negative = json!({"criteria": [{"id": "a", "title": "A", "levels": [{"id": "x", "title": "X", "points": -1}]}]});
+        assert!(validate_rubric(&negative, QuestionType::Essay).is_err());
+    }
+
+    #[test]
+    fn test_grade_scales_to_question_points() {
+        let grade = grade(&rubric(), 12, &[select("thesis", "strong"), select("style", "poor")]).unwrap();
+        // 4 of 6 rubric points on a 12-point question
+        assert_eq!(grade.points, 8.0);
+        assert!(!grade.is_correct);
+        assert_eq!(grade.scores[0].level, "Strong");
+        assert_eq!(grade.scores[1].max_points, 2.0);
+
+        let full = super::grade(&rubric(), 12, &[select("style", "good"), select("thesis", "strong")]).unwrap();
+        assert_eq!(full.points, 12.0);
+        assert!(full.is_correct);
+    }
+
+    #[test]
+    fn test_grade_requires_one_level_per_criterion() {
+        let rubric = rubric();
+        assert!(grade(&rubric, 10, &[select("thesis", "weak")]).is_err());
+        assert!(grade(&rubric, 10, &[select("thesis", "weak"), select("thesis", "strong"), select("style", "good")]).is_err());
+        assert!(grade(&rubric, 10, &[select("thesis", "great"), select("style", "good")]).is_err());
+        assert!(grade(&rubric, 10, &[select("thesis", "weak"), select("style", "good"), select("tone", "x")]).is_err());
+    }
+
+    #[test]
+    fn test_criterion_stats() {
+        let rubric = rubric();
+        let breakdowns: Vec<Vec<RubricScore>> = [
+            [select("thesis", "strong"), select("style", "good")],
+            [select("thesis", "weak"), select("style", "good")],
+            [select("thesis", "strong"), select("style", "poor")],
+        ]
+        .iter()
+        .map(|s| grade(&rubric, 6, s).unwrap().scores)
+        .collect();
+
+        let stats = criterion_stats(&breakdowns);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].criterion_id, "thesis");
+        assert_eq!(stats[0].graded_count, 3);
+        assert_eq!(stats[0].average_points, 3.0);
+        assert_eq!(stats[0].levels[0].level_id, "weak");
+        assert_eq!(stats[0].levels[1].count, 2);
+        assert_eq!(stats[1].average_points, 1.33);
+    }
+}
@@ -0,0 +1,161 @@
+//! # Code Runner
+//!
+//! Abstraction over the environment that compiles and runs submitted code.
+//! The grader only sees [`CodeRunner`], so new languages or execution
+//! backends (containers, remote judges) can be added without touching the
+//! grading logic.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+/// Errors raised by a runner itself (not by the submitted program).
+#[derive(Debug, thiserror::Error)]
+pub enum RunnerError {
+    #[error("Unsupported language: {0}")]
+    UnsupportedLanguage(String),
+
+    #[error("Sandbox error: {0}")]
+    Sandbox(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// How a program run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Exited on its own with this code
+    Exited(i32),
+    /// Killed by this signal (includes CPU and memory limits)
+    Signaled(i32),
+    /// Killed after exceeding the wall-clock limit
+    TimedOut,
+    /// Killed after writing more than the output limit
+    OutputLimitExceeded,
+}
+
+/// A single run of the program against one input.
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub duration: Duration,
+}
+
+/// Result of running a submission against a list of inputs.
+#[derive(Debug, Clone)]
+pub enum RunReport {
+    /// Compilation failed; no input was run
+    CompileError(String),
+    /// One outcome per input, in order
+    Completed(Vec<RunOutcome>),
+}
+
+/// Compiles and runs submitted programs.
+#[async_trait]
+pub trait CodeRunner: Send + Sync {
+    /// Returns true if the runner can execute `language`.
+    fn supports(&self, language: &str) -> bool;
+
+    /// Compiles `source` if the language needs it, then runs it once per
+    /// input with the input on stdin.
+    async fn run(&self, language: &str, source: &str, inputs: &[String]) -> Result<RunReport, RunnerError>;
+}
+
+/// How to build and run one language.
+///
+/// Commands are argument vectors run inside the submission's working
+/// directory; `{source}` is replaced by the source file name and
+/// `{memory_mb}` by the memory limit.
+#[derive(Debug, Clone)]
+pub struct LanguageSpec {
+    /// Canonical name stored in `QuizQuestion::code_language`
+    pub name: String,
+    /// Other accepted names
+    pub aliases: Vec<String>,
+    /// File the source is written to
+    pub source_file: String,
+    /// Compile command, if any
+    pub compile: Option<Vec<String>>,
+    /// Run command
+    pub run: Vec<String>,
+    /// Enforce the memory limit on the address space. Runtimes that reserve
+    /// large virtual ranges up front (V8) limit their heap via `{memory_mb}`
+    /// instead.
+    pub limit_address_space: bool,
+}
+
+impl LanguageSpec {
+    /// Creates an interpreted language.
+    pub fn interpreted(name: &str, source_file: &str, run: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            source_file: source_file.to_string(),
+            compile: None,
+            run: run.iter().map(|s| s.to_string()).collect(),
+            limit_address_space: true,
+        }
+    }
+
+    /// Creates a compiled language.
+    pub fn compiled(name: &str, source_file: &str, compile: &[&str], run: &[&str]) -> Self {
+        Self {
+            compile: Some(compile.iter().map(|s| s.to_string()).collect()),
+            ..Self::interpreted(name, source_file, run)
+        }
+    }
+
+    /// Adds alternative names.
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases = aliases.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Leaves the address space unlimited (see `limit_address_space`).
+    pub fn without_address_space_limit(mut self) -> Self {
+        self.limit_address_space = false;
+        self
+    }
+
+    /// Returns true if `language` names this spec (case-insensitive).
+    pub fn matches(&self, language: &str) -> bool {
+        let language = language.trim();
+        self.name.eq_ignore_ascii_case(language)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(language))
+    }
+
+    /// Built-in languages.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::interpreted("python", "main.py", &["python3", "-I", "{source}"])
+                .with_aliases(&["python3", "py"]),
+            Self::interpreted("javascript", "main.js", &["node", "--max-old-space-size={memory_mb}", "{source}"])
+                .with_aliases(&["js", "node"])
+                .without_address_space_limit(),
+            Self::compiled("c", "main.c", &["gcc", "-O2", "-std=c11", "-o", "main", "{source}", "-lm"], &["./main"]),
+            Self::compiled("cpp", "main.cpp", &["g++", "-O2", "-std=c++17", "-o", "main", "{source}"], &["./main"])
+                .with_aliases(&["c++"]),
+            Self::compiled("rust", "main.rs", &["rustc", "-O", "-o", "main", "{source}"], &["./main"])
+                .with_aliases(&["rs"]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_matching() {
+        let specs = LanguageSpec::defaults();
+        let find = |lang: &str| specs.iter().find(|s| s.matches(lang)).map(|s| s.name.as_str());
+
+        assert_eq!(find("Python"), Some("python"));
+        assert_eq!(find("py"), Some("python"));
+        assert_eq!(find("c++"), Some("cpp"));
+        assert_eq!(find(" js "), Some("javascript"));
+        assert_eq!(find("cobol"), None);
+    }
+}
@@ -0,0 +1,831 @@
+//! # Local Sandbox Runner
+//!
+//! [`CodeRunner`] that compiles and runs submissions as child processes on
+//! the grading host. Every process is confined with:
+//!
+//! - a private temporary working directory, removed afterwards
+//! - an empty environment (only `PATH` and `HOME`)
+//! - `RLIMIT_AS` (memory), `RLIMIT_CPU`, `RLIMIT_FSIZE` and no core dumps
+//! - new network, mount, PID, IPC and UTS namespaces: no network access, and
+//!   a private `/proc` that only shows the submission's own processes (not
+//!   the service and its environment)
+//! - its own root filesystem: the system directories bind-mounted read-only,
+//!   the submission's directory at `/box` and a private tmpfs at `/tmp`; the
+//!   host filesystem (other submissions, the service) is not visible
+//! - a uid/gid of its own, taken from a range with one id per concurrent
+//!   submission, with no supplementary groups and `no_new_privs`
+//! - `RLIMIT_NPROC`, counted against that uid, so fork bombs stay contained
+//! - a wall-clock limit; its whole process group is killed on expiry
+//!
+//! Isolation needs the service to run as root, or with `CAP_SYS_ADMIN`,
+//! `CAP_SETUID` and `CAP_SETGID`. It fails closed: if the namespaces cannot
+//! be created or the uid cannot be dropped the process is not started, and
+//! [`LocalSandboxRunner::check`] lets the service refuse to start instead.
+//!
+//! The number of submissions running at once is bounded by a semaphore.
+
+use std::ffi::{CStr, CString};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{chown, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tempfile::TempDir;
+use tracing::debug;
+
+use super::runner::{CodeRunner, ExitStatus, LanguageSpec, RunOutcome, RunReport, RunnerError};
+
+/// Compiler output kept for compile errors.
+const MAX_COMPILE_MESSAGE: usize = 2000;
+
+/// Stderr kept per process (only used for compile errors).
+const MAX_STDERR_BYTES: usize = 64 * 1024;
+
+/// Default first uid/gid of sandboxed processes; not used by any system account.
+const SANDBOX_ID: u32 = 60000;
+
+/// Device nodes bind-mounted into the sandbox.
+const DEVICES: &[&str] = &["null", "zero", "random", "urandom"];
+
+/// Sandbox limits and settings.
+#[derive(Debug, Clone)]
+pub struct SandboxConfig {
+    /// Wall-clock limit per test case
+    pub time_limit: Duration,
+    /// Wall-clock limit for compilation
+    pub compile_time_limit: Duration,
+    /// Address space limit per test case
+    pub memory_limit_mb: u64,
+    /// Address space limit for compilation
+    pub compile_memory_limit_mb: u64,
+    /// Maximum stdout per test case
+    pub output_limit_bytes: usize,
+    /// Submissions graded at the same time
+    pub max_concurrent: usize,
+    /// Run each process in new namespaces and its own root as an
+    /// unprivileged uid; turn off only for local development, where the
+    /// service is not privileged
+    pub isolate: bool,
+    /// First uid (and gid) of the range submissions run as; one id per
+    /// concurrent submission (`uid_base..uid_base + max_concurrent`)
+    pub uid_base: u32,
+    /// Processes and threads per submission (`RLIMIT_NPROC`)
+    pub max_processes: u64,
+    /// Host directories mounted read-only in the sandbox root (compilers,
+    /// runtimes and their libraries); missing ones are skipped
+    pub read_only_paths: Vec<PathBuf>,
+    /// Parent directory of the per-submission working directories
+    pub work_dir: PathBuf,
+    /// `PATH` seen by compilers and programs
+    pub path: String,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            time_limit: Duration::from_secs(2),
+            compile_time_limit: Duration::from_secs(30),
+            memory_limit_mb: 256,
+            compile_memory_limit_mb: 2048,
+            output_limit_bytes: 64 * 1024,
+            max_concurrent: 4,
+            isolate: true,
+            uid_base: SANDBOX_ID,
+            max_processes: 128,
+            read_only_paths: ["/usr", "/bin", "/sbin", "/lib", "/lib32", "/lib64", "/etc", "/opt"]
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            work_dir: std::env::temp_dir(),
+            path: "/usr/local/bin:/usr/bin:/bin".to_string(),
+        }
+    }
+}
+
+/// Resource limits of one process.
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    time: Duration,
+    memory_bytes: u64,
+    output_bytes: usize,
+}
+
+/// What `confine` applies to a process. Built before `fork`, so nothing is
+/// allocated between `fork` and `exec`.
+#[derive(Debug)]
+struct Confinement {
+    memory_bytes: u64,
+    cpu_seconds: u64,
+    file_bytes: u64,
+    /// `None` runs unisolated
+    jail: Option<Jail>,
+}
+
+/// Namespaces, root filesystem and identity of an isolated process.
+#[derive(Debug)]
+struct Jail {
+    uid: libc::uid_t,
+    gid: libc::gid_t,
+    max_processes: u64,
+    /// Empty directory the new root is built on
+    root: CString,
+    /// `(host path, mount point)` of the read-only directories
+    read_only: Vec<(CString, CString)>,
+    /// `(host path, mount point)` of the device nodes
+    devices: Vec<(CString, CString)>,
+    /// Submission directory and its mount point (`/box`)
+    work: (CString, CString),
+    dev: CString,
+    tmp: CString,
+    proc: CString,
+}
+
+/// Per-submission directory: `box/` holds the source and build output (owned
+/// by the submission's uid), `root/` is where its sandbox root is mounted.
+/// Both live in a root-only directory, so other submissions cannot reach them
+/// even from the host.
+struct Workspace {
+    dir: TempDir,
+}
+
+impl Workspace {
+    fn work_dir(&self) -> PathBuf {
+        self.dir.path().join("box")
+    }
+
+    fn root_dir(&self) -> PathBuf {
+        self.dir.path().join("root")
+    }
+}
+
+/// A concurrency permit and the uid/gid a submission runs as; the id goes
+/// back to the pool on drop.
+struct Slot<'a> {
+    _permit: SemaphorePermit<'a>,
+    ids: &'a Mutex<Vec<u32>>,
+    id: u32,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut ids) = self.ids.lock() {
+            ids.push(self.id);
+        }
+    }
+}
+
+/// Raw result of one process.
+#[derive(Debug)]
+struct ProcessOutput {
+    status: ExitStatus,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    duration: Duration,
+}
+
+/// Runs submissions in confined local processes.
+pub struct LocalSandboxRunner {
+    config: SandboxConfig,
+    languages: Vec<LanguageSpec>,
+    permits: Semaphore,
+    /// Free ids of the uid range; one per permit
+    ids: Mutex<Vec<u32>>,
+}
+
+impl LocalSandboxRunner {
+    /// Creates a runner for the built-in languages.
+    pub fn new(config: SandboxConfig) -> Self {
+        Self::with_languages(config, LanguageSpec::defaults())
+    }
+
+    /// Creates a runner for the given languages.
+    pub fn with_languages(config: SandboxConfig, languages: Vec<LanguageSpec>) -> Self {
+        let slots = config.max_concurrent.max(1);
+        let permits = Semaphore::new(slots);
+        let ids = Mutex::new((0..slots as u32).rev().map(|i| config.uid_base + i).collect());
+        Self { config, languages, permits, ids }
+    }
+
+    /// Runs a trivial program in the sandbox, so a host that cannot isolate
+    /// submissions is detected at startup rather than on the first one.
+    pub async fn check(&self) -> Result<(), RunnerError> {
+        let slot = self.slot().await?;
+        let workspace = self.workspace(slot.id)?;
+        let limits = Limits {
+            time: Duration::from_secs(5),
+            memory_bytes: libc::RLIM_INFINITY,
+            output_bytes: 1024,
+        };
+
+        let output = self
+            .execute(&workspace, slot.id, &["true".to_string()], b"", limits)
+            .await?;
+        match output.status {
+            ExitStatus::Exited(0) => Ok(()),
+            status => Err(RunnerError::Sandbox(format!("Sandbox check ended with {:?}", status))),
+        }
+    }
+
+    fn language(&self, language: &str) -> Option<&LanguageSpec> {
+        self.languages.iter().find(|spec| spec.matches(language))
+    }
+
+    fn run_limits(&self, spec: &LanguageSpec) -> Limits {
+        Limits {
+            time: self.config.time_limit,
+            memory_bytes: address_space(spec, self.config.memory_limit_mb),
+            output_bytes: self.config.output_limit_bytes,
+        }
+    }
+
+    fn compile_limits(&self, spec: &LanguageSpec) -> Limits {
+        Limits {
+            time: self.config.compile_time_limit,
+            memory_bytes: address_space(spec, self.config.compile_memory_limit_mb),
+            output_bytes: self.config.output_limit_bytes,
+        }
+    }
+
+    /// Waits for a free permit and takes an id of the uid range.
+    async fn slot(&self) -> Result<Slot<'_>, RunnerError> {
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| RunnerError::Sandbox(e.to_string()))?;
+        let id = self
+            .ids
+            .lock()
+            .map_err(|e| RunnerError::Sandbox(e.to_string()))?
+            .pop()
+            .ok_or_else(|| RunnerError::Sandbox("No free sandbox uid".into()))?;
+
+        Ok(Slot { _permit: permit, ids: &self.ids, id })
+    }
+
+    /// Creates the workspace of a submission running as `id`.
+    fn workspace(&self, id: u32) -> Result<Workspace, RunnerError> {
+        // tempfile creates it 0700 and owned by the service
+        let workspace = Workspace {
+            dir: tempfile::Builder::new()
+                .prefix("submission-")
+                .tempdir_in(&self.config.work_dir)?,
+        };
+        std::fs::create_dir(workspace.work_dir())?;
+        if self.config.isolate {
+            std::fs::create_dir(workspace.root_dir())?;
+            chown(workspace.work_dir(), Some(id), Some(id))?;
+            std::fs::set_permissions(workspace.work_dir(), std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(workspace)
+    }
+
+    /// Mounts and identity of a process running as `id` in `workspace`.
+    fn jail(&self, workspace: &Workspace, id: u32) -> Result<Jail, RunnerError> {
+        let root = workspace.root_dir();
+        let under_root = |target: &str| c_path(&root.join(target));
+
+        let mut read_only = Vec::new();
+        for path in self.config.read_only_paths.iter().filter(|p| p.exists()) {
+            let relative = path.strip_prefix("/").unwrap_or(path);
+            read_only.push((c_path(path)?, c_path(&root.join(relative))?));
+        }
+        let devices = DEVICES
+            .iter()
+            .map(|name| Ok((c_path(&Path::new("/dev").join(name))?, under_root(&format!("dev/{}", name))?)))
+            .collect::<Result<_, RunnerError>>()?;
+
+        Ok(Jail {
+            uid: id,
+            gid: id,
+            max_processes: self.config.max_processes,
+            root: c_path(&root)?,
+            read_only,
+            devices,
+            work: (c_path(&workspace.work_dir())?, under_root("box")?),
+            dev: under_root("dev")?,
+            tmp: under_root("tmp")?,
+            proc: under_root("proc")?,
+        })
+    }
+
+    /// Replaces the placeholders of a command.
+    fn expand(&self, command: &[String], spec: &LanguageSpec) -> Vec<String> {
+        command
+            .iter()
+            .map(|arg| {
+                arg.replace("{source}", &spec.source_file)
+                    .replace("{memory_mb}", &self.config.memory_limit_mb.to_string())
+            })
+            .collect()
+    }
+
+    /// Runs one confined process to completion.
+    async fn execute(
+        &self,
+        workspace: &Workspace,
+        id: u32,
+        argv: &[String],
+        input: &[u8],
+        limits: Limits,
+    ) -> Result<ProcessOutput, RunnerError> {
+        let (program, args) = argv
+            .split_first()
+            .ok_or_else(|| RunnerError::Sandbox("Empty command".into()))?;
+
+        let jail = match self.config.isolate {
+            true => Some(self.jail(workspace, id)?),
+            false => None,
+        };
+        let home = match jail {
+            Some(_) => PathBuf::from("/box"),
+            None => workspace.work_dir(),
+        };
+
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(workspace.work_dir())
+            .env_clear()
+            .env("PATH", &self.config.path)
+            .env("HOME", home)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let confinement = Confinement {
+            memory_bytes: limits.memory_bytes,
+            // CPU limit rounded up; the wall-clock limit is the precise one
+            cpu_seconds: limits.time.as_secs() + 1,
+            file_bytes: limits.output_bytes as u64,
+            jail,
+        };
+        // SAFETY: `confine` only calls async-signal-safe libc functions.
+        unsafe {
+            command.pre_exec(move || confine(&confinement));
+        }
+
+        debug!("Sandbox running {}", argv.join(" "));
+        let started = Instant::now();
+        let mut child = command.spawn()?;
+        let pgid = child.id().map(|pid| pid as i32);
+
+        let stdin = child.stdin.take();
+        let input = input.to_vec();
+        tokio::spawn(async move {
+            if let Some(mut stdin) = stdin {
+                // The program may exit without reading its input
+                let _ = stdin.write_all(&input).await;
+            }
+        });
+        let stdout = tokio::spawn(read_capped(child.stdout.take(), limits.output_bytes, pgid));
+        let stderr = tokio::spawn(read_capped(child.stderr.take(), MAX_STDERR_BYTES, None));
+
+        let status = match tokio::time::timeout(limits.time, child.wait()).await {
+            Ok(status) => Some(status?),
+            Err(_) => None,
+        };
+        // Also stops anything the program left running in the background
+        if let Some(pgid) = pgid {
+            kill_group(pgid);
+        }
+        if status.is_none() {
+            let _ = child.wait().await;
+        }
+        let duration = started.elapsed();
+
+        let (stdout, overflowed) = stdout.await.map_err(|e| RunnerError::Sandbox(e.to_string()))?;
+        let (stderr, _) = stderr.await.map_err(|e| RunnerError::Sandbox(e.to_string()))?;
+
+        let status = match status {
+            _ if overflowed => ExitStatus::OutputLimitExceeded,
+            None => ExitStatus::TimedOut,
+            Some(status) => exit_status(status),
+        };
+
+        Ok(ProcessOutput { status, stdout, stderr, duration })
+    }
+}
+
+#[async_trait]
+impl CodeRunner for LocalSandboxRunner {
+    fn supports(&self, language: &str) -> bool {
+        self.language(language).is_some()
+    }
+
+    async fn run(&self, language: &str, source: &str, inputs: &[String]) -> Result<RunReport, RunnerError> {
+        let spec = self
+            .language(language)
+            .ok_or_else(|| RunnerError::UnsupportedLanguage(language.to_string()))?;
+        let slot = self.slot().await?;
+
+        let workspace = self.workspace(slot.id)?;
+        let source_path = workspace.work_dir().join(&spec.source_file);
+        tokio::fs::write(&source_path, source).await?;
+        if self.config.isolate {
+            chown(&source_path, Some(slot.id), Some(slot.id))?;
+        }
+
+        if let Some(compile) = &spec.compile {
+            let output = self
+                .execute(&workspace, slot.id, &self.expand(compile, spec), b"", self.compile_limits(spec))
+                .await?;
+            if output.status != ExitStatus::Exited(0) {
+                return Ok(RunReport::CompileError(compile_message(&output)));
+            }
+        }
+
+        let run = self.expand(&spec.run, spec);
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let output = self
+                .execute(&workspace, slot.id, &run, input.as_bytes(), self.run_limits(spec))
+                .await?;
+            outcomes.push(RunOutcome {
+                status: output.status,
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                duration: output.duration,
+            });
+        }
+
+        Ok(RunReport::Completed(outcomes))
+    }
+}
+
+/// `RLIMIT_AS` value for a language.
+fn address_space(spec: &LanguageSpec, limit_mb: u64) -> u64 {
+    if spec.limit_address_space {
+        limit_mb * 1024 * 1024
+    } else {
+        libc::RLIM_INFINITY
+    }
+}
+
+/// Compiler diagnostics shown to the student.
+fn compile_message(output: &ProcessOutput) -> String {
+    let message = match output.status {
+        ExitStatus::TimedOut => "Compilation timed out".to_string(),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if stderr.trim().is_empty() { stdout.into_owned() } else { stderr.into_owned() }
+        }
+    };
+
+    message.chars().take(MAX_COMPILE_MESSAGE).collect()
+}
+
+/// Reads at most `limit` bytes. On overflow the process group is killed and
+/// the second value is true.
+async fn read_capped<R>(reader: Option<R>, limit: usize, pgid: Option<i32>) -> (Vec<u8>, bool)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = Vec::new();
+    if let Some(reader) = reader {
+        let _ = reader.take(limit as u64 + 1).read_to_end(&mut buffer).await;
+    }
+
+    let overflowed = buffer.len() > limit;
+    if overflowed {
+        buffer.truncate(limit);
+        if let Some(pgid) = pgid {
+            kill_group(pgid);
+        }
+    }
+
+    (buffer, overflowed)
+}
+
+fn exit_status(status: std::process::ExitStatus) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+
+    match (status.code(), status.signal()) {
+        (Some(code), _) => ExitStatus::Exited(code),
+        // RLIMIT_CPU is a time limit too
+        (None, Some(libc::SIGXCPU)) => ExitStatus::TimedOut,
+        (None, Some(signal)) => ExitStatus::Signaled(signal),
+        (None, None) => ExitStatus::Signaled(0),
+    }
+}
+
+fn kill_group(pgid: i32) {
+    // SAFETY: plain syscall; ESRCH (group already gone) is ignored.
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+}
+
+fn c_path(path: &Path) -> Result<CString, RunnerError> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| RunnerError::Sandbox(format!("Invalid path: {}", path.display())))
+}
+
+/// Runs in the child between `fork` and `exec`.
+fn confine(confinement: &Confinement) -> std::io::Result<()> {
+    // Own process group, so timeouts kill every descendant
+    check(unsafe { libc::setpgid(0, 0) })?;
+
+    if let Some(jail) = &confinement.jail {
+        enter_namespaces()?;
+        enter_root(jail)?;
+    }
+
+    set_limit(libc::RLIMIT_AS, confinement.memory_bytes)?;
+    set_limit(libc::RLIMIT_CPU, confinement.cpu_seconds)?;
+    set_limit(libc::RLIMIT_FSIZE, confinement.file_bytes)?;
+    set_limit(libc::RLIMIT_CORE, 0)?;
+
+    if let Some(jail) = &confinement.jail {
+        // Counted per uid, which is why every submission has its own; an
+        // unisolated process would share it with the service's threads
+        set_limit(libc::RLIMIT_NPROC, jail.max_processes)?;
+
+        check(unsafe { libc::setgroups(0, std::ptr::null()) })?;
+        check(unsafe { libc::setgid(jail.gid) })?;
+        check(unsafe { libc::setuid(jail.uid) })?;
+        check(unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) })?;
+    }
+
+    Ok(())
+}
+
+/// Builds the sandbox root on `jail.root` and makes it `/`: read-only system
+/// directories, `/box` (the submission directory, the only writable path that
+/// outlives the process, so the compiled program reaches the run step), a
+/// private `/tmp` and `/proc`. The old root is detached.
+fn enter_root(jail: &Jail) -> std::io::Result<()> {
+    // Mounts stay in this namespace
+    mount(None, c"/", None, libc::MS_REC | libc::MS_PRIVATE, None)?;
+    mount(Some(c"tmpfs"), &jail.root, Some(c"tmpfs"), libc::MS_NOSUID | libc::MS_NODEV, Some(c"size=1m,mode=0755"))?;
+
+    for (source, target) in &jail.read_only {
+        make_dir(target)?;
+        bind(source, target, libc::MS_RDONLY | libc::MS_NOSUID)?;
+    }
+
+    make_dir(&jail.dev)?;
+    for (source, target) in &jail.devices {
+        let fd = unsafe { libc::open(target.as_ptr(), libc::O_CREAT | libc::O_WRONLY | libc::O_CLOEXEC, 0o644) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        unsafe { libc::close(fd) };
+        bind(source, target, libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NOEXEC)?;
+    }
+
+    make_dir(&jail.work.1)?;
+    bind(&jail.work.0, &jail.work.1, libc::MS_NOSUID | libc::MS_NODEV)?;
+
+    make_dir(&jail.tmp)?;
+    mount(Some(c"tmpfs"), &jail.tmp, Some(c"tmpfs"), libc::MS_NOSUID | libc::MS_NODEV, Some(c"size=64m,mode=1777"))?;
+
+    // A fresh /proc only shows this PID namespace
+    make_dir(&jail.proc)?;
+    mount(Some(c"proc"), &jail.proc, Some(c"proc"), libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC, None)?;
+
+    check(unsafe { libc::chdir(jail.root.as_ptr()) })?;
+    let dot = c".".as_ptr();
+    check(unsafe { libc::syscall(libc::SYS_pivot_root, dot, dot) } as libc::c_int)?;
+    check(unsafe { libc::umount2(dot, libc::MNT_DETACH) })?;
+
+    mount(None, c"/", None, libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV, None)?;
+    check(unsafe { libc::chdir(c"/box".as_ptr()) })
+}
+
+fn mount(
+    source: Option<&CStr>,
+    target: &CStr,
+    fstype: Option<&CStr>,
+    flags: libc::c_ulong,
+    data: Option<&CStr>,
+) -> std::io::Result<()> {
+    let ptr = |value: Option<&CStr>| value.map_or(std::ptr::null(), |v| v.as_ptr());
+    check(unsafe {
+        libc::mount(ptr(source), target.as_ptr(), ptr(fstype), flags, ptr(data).cast())
+    })
+}
+
+/// Bind-mounts `source` on `target`; the flags need a remount to apply.
+fn bind(source: &CStr, target: &CStr, flags: libc::c_ulong) -> std::io::Result<()> {
+    mount(Some(source), target, None, libc::MS_BIND | libc::MS_REC, None)?;
+    mount(None, target, None, libc::MS_BIND | libc::MS_REMOUNT | flags, None)
+}
+
+fn make_dir(path: &CStr) -> std::io::Result<()> {
+    check(unsafe { libc::mkdir(path.as_ptr(), 0o755) })
+}
+
+/// Moves into new namespaces. A new PID namespace only applies to children,
+/// so this forks: the child (PID 1 of the namespace) returns and goes on to
+/// `exec`, the parent waits for it and exits the same way.
+fn enter_namespaces() -> std::io::Result<()> {
+    let namespaces = libc::CLONE_NEWNS
+        | libc::CLONE_NEWPID
+        | libc::CLONE_NEWNET
+        | libc::CLONE_NEWIPC
+        | libc::CLONE_NEWUTS;
+    check(unsafe { libc::unshare(namespaces) })?;
+
+    match unsafe { libc::fork() } {
+        -1 => Err(std::io::Error::last_os_error()),
+        // Killed with the parent, which is what the service signals
+        0 => check(unsafe { libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) }),
+        pid => relay_exit(pid),
+    }
+}
+
+/// Waits for `pid` and exits with its status, so the service sees how the
+/// sandboxed program ended.
+fn relay_exit(pid: libc::pid_t) -> ! {
+    unsafe {
+        // std's exec-error pipe is among these; holding it open would block
+        // `spawn` until the program ends
+        if libc::syscall(libc::SYS_close_range, 3, libc::c_uint::MAX, 0) != 0 {
+            for fd in 3..libc::sysconf(libc::_SC_OPEN_MAX).max(1024) as libc::c_int {
+                libc::close(fd);
+            }
+        }
+
+        let mut status = 0;
+        while libc::waitpid(pid, &mut status, 0) != pid {
+            if std::io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                libc::_exit(127);
+            }
+        }
+
+        if libc::WIFSIGNALED(status) {
+            let signal = libc::WTERMSIG(status);
+            libc::signal(signal, libc::SIG_DFL);
+            libc::kill(libc::getpid(), signal);
+            libc::_exit(128 + signal);
+        }
+        libc::_exit(libc::WEXITSTATUS(status))
+    }
+}
+
+fn set_limit(resource: libc::__rlimit_resource_t, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    check(unsafe { libc::setrlimit(resource, &limit) })
+}
+
+fn check(result: libc::c_int) -> std::io::Result<()> {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Isolation is on by default, so these need root (or the capabilities
+    // listed in the module docs)
+    fn runner() -> LocalSandboxRunner {
+        LocalSandboxRunner::new(SandboxConfig {
+            time_limit: Duration::from_millis(1500),
+            ..SandboxConfig::default()
+        })
+    }
+
+    fn completed(report: RunReport) -> Vec<RunOutcome> {
+        match report {
+            RunReport::Completed(outcomes) => outcomes,
+            RunReport::CompileError(message) => panic!("unexpected compile error: {}", message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_each_input() {
+        let source = "import sys\nprint(sum(int(x) for x in sys.stdin.read().split()))\n";
+        let inputs = vec!["1 2".to_string(), "10 20 30".to_string()];
+
+        let outcomes = completed(runner().run("python", source, &inputs).await.unwrap());
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| o.status == ExitStatus::Exited(0)));
+        assert_eq!(outcomes[0].stdout.trim(), "3");
+        assert_eq!(outcomes[1].stdout.trim(), "60");
+    }
+
+    #[tokio::test]
+    async fn test_enforces_limits() {
+        let runner = runner();
+
+        let outcomes = completed(runner.run("python", "while True:\n    pass\n", &[String::new()]).await.unwrap());
+        assert_eq!(outcomes[0].status, ExitStatus::TimedOut);
+
+        let outcomes = completed(runner.run("python", "while True:\n    print('x' * 1000)\n", &[String::new()]).await.unwrap());
+        assert_eq!(outcomes[0].status, ExitStatus::OutputLimitExceeded);
+
+        let outcomes = completed(runner.run("python", "x = bytearray(512 * 1024 * 1024)\n", &[String::new()]).await.unwrap());
+        assert_ne!(outcomes[0].status, ExitStatus::Exited(0));
+    }
+
+    #[tokio::test]
+    async fn test_javascript_heap_limit() {
+        let source = "console.log(require('fs').readFileSync(0, 'utf8').trim().toUpperCase())";
+        let outcomes = completed(runner().run("javascript", source, &["abc".to_string()]).await.unwrap());
+        assert_eq!(outcomes[0].stdout.trim(), "ABC");
+    }
+
+    #[tokio::test]
+    async fn test_compile_error() {
+        let report = runner().run("c", "int main( {", &[String::new()]).await.unwrap();
+        assert!(matches!(report, RunReport::CompileError(message) if message.contains("error")));
+    }
+
+    #[tokio::test]
+    async fn test_default_config_refuses_network() {
+        let runner = LocalSandboxRunner::new(SandboxConfig::default());
+        runner.check().await.unwrap();
+
+        let source = "import socket\n\
+            try:\n    socket.create_connection(('1.1.1.1', 53), timeout=1)\n    print('connected')\n\
+            except OSError as e:\n    print('refused', e.errno)\n";
+        let outcomes = completed(runner.run("python", source, &[String::new()]).await.unwrap());
+
+        assert_eq!(outcomes[0].status, ExitStatus::Exited(0));
+        assert!(outcomes[0].stdout.starts_with("refused"), "{}", outcomes[0].stdout);
+    }
+
+    #[tokio::test]
+    async fn test_runs_as_sandbox_user_without_service_processes() {
+        let source = "import os\n\
+            print(os.getuid(), os.getgid(), os.getpid())\n\
+            print(sorted(p for p in os.listdir('/proc') if p.isdigit()))\n";
+        let outcomes = completed(runner().run("python", source, &[String::new()]).await.unwrap());
+
+        let lines: Vec<&str> = outcomes[0].stdout.lines().collect();
+        assert_eq!(lines[0], format!("{} {} 1", SANDBOX_ID, SANDBOX_ID));
+        assert_eq!(lines[1], "['1']");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submissions_get_distinct_uids() {
+        let runner = runner();
+        let source = "import os, time\ntime.sleep(0.3)\nprint(os.getuid())\n";
+        let input = [String::new()];
+
+        let (a, b) = tokio::join!(runner.run("python", source, &input), runner.run("python", source, &input));
+        let a = completed(a.unwrap())[0].stdout.clone();
+        let b = completed(b.unwrap())[0].stdout.clone();
+
+        assert_ne!(a, b);
+    }
+
+    #[tokio::test]
+    async fn test_submission_cannot_reach_other_submissions() {
+        let runner = runner();
+        // Another submission's workspace, as `run` leaves it while grading
+        let other = runner.workspace(SANDBOX_ID + 1).unwrap();
+        std::fs::write(other.work_dir().join("main.py"), "secret").unwrap();
+        let other_source = other.work_dir().join("main.py");
+
+        let source = format!(
+            "import os\n\
+            def attempt(f):\n    try:\n        f()\n        return 'ok'\n    except OSError:\n        return 'denied'\n\
+            print(attempt(lambda: open('{}').read()))\n\
+            print(attempt(lambda: open('/etc/passwd', 'a')))\n\
+            print(attempt(lambda: open('/tmp/x', 'w').write('x')))\n\
+            print(os.listdir('/'))\n",
+            other_source.display()
+        );
+        let outcomes = completed(runner.run("python", &source, &[String::new()]).await.unwrap());
+
+        let lines: Vec<&str> = outcomes[0].stdout.lines().collect();
+        assert_eq!(lines[0], "denied");
+        assert_eq!(lines[1], "denied");
+        assert_eq!(lines[2], "ok");
+        assert!(!lines[3].contains("'root'") && !lines[3].contains("'app'"), "{}", lines[3]);
+    }
+
+    #[tokio::test]
+    async fn test_fork_bomb_is_contained() {
+        let source = "import os\n\
+            n = 0\n\
+            try:\n    while True:\n        if os.fork() == 0:\n            import time; time.sleep(5); os._exit(0)\n        n += 1\n\
+            except OSError:\n    print('limited', n)\n";
+        let outcomes = completed(runner().run("python", source, &[String::new()]).await.unwrap());
+
+        assert!(outcomes[0].stdout.starts_with("limited"), "{}", outcomes[0].stdout);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_language() {
+        let result = runner().run("cobol", "", &[]).await;
+        assert!(matches!(result, Err(RunnerError::UnsupportedLanguage(_))));
+    }
+}
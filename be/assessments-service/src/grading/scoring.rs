@@ -0,0 +1,400 @@
+//! # Response Scoring
+//!
+//! Scores objective questions (choice, true/false, short answer) according
+//! to the question's [`ScoringPolicy`]: partial credit, wrong-answer
+//! penalties, numeric tolerance with units, and regex or fuzzy matching of
+//! short answers.
+
+use std::collections::HashSet;
+
+use regex::RegexBuilder;
+use serde_json::Value;
+
+use crate::domain::{NumericTolerance, QuestionType, QuizQuestion, ScoringPolicy, TextMatch};
+
+/// Score of a single response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseScore {
+    /// True if the response earned full credit
+    pub is_correct: bool,
+    /// Points earned (negative only with `allow_negative`)
+    pub points: f64,
+}
+
+/// Scores a response to an objective question.
+///
+/// Essay and code questions are graded elsewhere and score zero here.
+pub fn score_response(question: &QuizQuestion, answer: &Value) -> ResponseScore {
+    let policy = question.scoring();
+    let points = question.points as f64;
+
+    if is_unanswered(answer) {
+        return ResponseScore { is_correct: false, points: 0.0 };
+    }
+
+    // Share of the question points earned, before clamping
+    let fraction = match question.question_type {
+        QuestionType::SingleChoice | QuestionType::TrueFalse => {
+            let correct = question.correct_answers.get(0).is_some_and(|c| c == answer);
+            if correct { 1.0 } else { -policy.wrong_penalty }
+        }
+        QuestionType::MultipleChoice => choice_fraction(&policy, answer, &question.correct_answers),
+        QuestionType::ShortAnswer => {
+            let correct = short_answer_matches(&policy, answer, &question.correct_answers);
+            if correct { 1.0 } else { -policy.wrong_penalty }
+        }
+        QuestionType::Essay | QuestionType::Code => 0.0,
+    };
+
+    let fraction = if policy.allow_negative { fraction.max(-1.0) } else { fraction.max(0.0) };
+
+    ResponseScore {
+        is_correct: fraction >= 1.0,
+        points: round_points(points * fraction.min(1.0)),
+    }
+}
+
+/// Checks that a policy can be applied to a question type.
+pub fn validate_policy(policy: &ScoringPolicy, question_type: QuestionType) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&policy.wrong_penalty) {
+        return Err("wrong_penalty must be between 0 and 1".into());
+    }
+    if policy.partial_credit && question_type != QuestionType::MultipleChoice {
+        return Err("partial_credit only applies to multiple choice questions".into());
+    }
+
+    if question_type != QuestionType::ShortAnswer
+        && (policy.numeric.is_some() || policy.text_match != TextMatch::default())
+    {
+        return Err("text_match and numeric only apply to short answer questions".into());
+    }
+    if let TextMatch::Fuzzy { min_similarity } = policy.text_match {
+        if !(0.0..=1.0).contains(&min_similarity) {
+            return Err("min_similarity must be between 0 and 1".into());
+        }
+    }
+    if let Some(numeric) = &policy.numeric {
+        if numeric.tolerance < 0.0 {
+            return Err("tolerance cannot be negative".into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the accepted answers are usable with the policy (valid
+/// patterns for regex matching, numbers for numeric answers).
+pub fn validate_answer_key(policy: &ScoringPolicy, correct_answers: &Value) -> Result<(), String> {
+    let answers = correct_answers.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    if policy.numeric.is_some() {
+        if let Some(bad) = answers.iter().find(|a| answer_number(a).is_none()) {
+            return Err(format!("Numeric answer key must contain numbers, got {}", bad));
+        }
+    } else if let TextMatch::Regex { case_sensitive } = policy.text_match {
+        for pattern in answers.iter().filter_map(Value::as_str) {
+            anchored_regex(pattern, case_sensitive).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rounds to the precision stored in the database.
+pub fn round_points(points: f64) -> f64 {
+    // `+ 0.0` turns -0.0 into 0.0
+    (points * 100.0).round() / 100.0 + 0.0
+}
+
+fn is_unanswered(answer: &Value) -> bool {
+    match answer {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+/// Multiple choice: all-or-nothing, or the share of correct options picked
+/// minus the penalty for each wrong pick.
+fn choice_fraction(policy: &ScoringPolicy, answer: &Value, correct_answers: &Value) -> f64 {
+    let picked = string_set(answer);
+    let correct = string_set(correct_answers);
+    if correct.is_empty() {
+        return 0.0;
+    }
+
+    let right_picks = picked.intersection(&correct).count() as f64;
+    let wrong_picks = picked.difference(&correct).count() as f64;
+
+    if policy.partial_credit {
+        right_picks / correct.len() as f64 - policy.wrong_penalty * wrong_picks
+    } else if picked == correct {
+        1.0
+    } else {
+        -policy.wrong_penalty
+    }
+}
+
+fn string_set(value: &Value) -> HashSet<&str> {
+    value
+        .as_array()
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn short_answer_matches(policy: &ScoringPolicy, answer: &Value, correct_answers: &Value) -> bool {
+    let accepted = correct_answers.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    if let Some(numeric) = &policy.numeric {
+        return numeric_matches(numeric, answer, accepted);
+    }
+
+    let Some(answer) = answer.as_str().map(str::trim) else {
+        return false;
+    };
+    let mut accepted = accepted.iter().filter_map(Value::as_str);
+
+    match policy.text_match {
+        TextMatch::Exact { case_sensitive: true } => accepted.map(str::trim).any(|a| a == answer),
+        TextMatch::Exact { case_sensitive: false } => {
+            let answer = answer.to_lowercase();
+            accepted.any(|a| a.trim().to_lowercase() == answer)
+        }
+        TextMatch::Regex { case_sensitive } => accepted
+            .filter_map(|pattern| anchored_regex(pattern, case_sensitive).ok())
+            .any(|re| re.is_match(answer)),
+        TextMatch::Fuzzy { min_similarity } => {
+            let answer = normalize_text(answer);
+            accepted.any(|a| similarity(&answer, &normalize_text(a)) >= min_similarity)
+        }
+    }
+}
+
+fn anchored_regex(pattern: &str, case_sensitive: bool) -> Result<regex::Regex, regex::Error> {
+    RegexBuilder::new(&format!("^(?:{})$", pattern))
+        .case_insensitive(!case_sensitive)
+        .size_limit(1 << 20)
+        .build()
+}
+
+/// Lowercases and collapses whitespace.
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Levenshtein similarity in [0, 1].
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+fn numeric_matches(numeric: &NumericTolerance, answer: &Value, accepted: &[Value]) -> bool {
+    let (value, unit) = match answer {
+        Value::Number(n) => match n.as_f64() {
+            Some(value) => (value, None),
+            None => return false,
+        },
+        Value::String(s) => match parse_quantity(s) {
+            Some(quantity) => quantity,
+            None => return false,
+        },
+        _ => return false,
+    };
+
+    let unit_ok = match unit {
+        Some(unit) => numeric.units.iter().any(|u| u.trim() == unit),
+        None => !numeric.require_unit,
+    };
+    if !unit_ok {
+        return false;
+    }
+
+    accepted.iter().filter_map(answer_number).any(|expected| {
+        let allowed = if numeric.relative {
+            numeric.tolerance * expected.abs()
+        } else {
+            numeric.tolerance
+        };
+        // Guard against floating point noise on exact answers
+        (value - expected).abs() <= allowed + 1e-9
+    })
+}
+
+fn answer_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Splits `"9.81 m/s^2"` into the number and the unit.
+fn parse_quantity(text: &str) -> Option<(f64, Option<&str>)> {
+    let text = text.trim();
+
+    // Longest prefix that parses as a number
+    let split = text
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .rfind(|&end| text[..end].parse::<f64>().is_ok())?;
+
+    let value: f64 = text[..split].parse().ok()?;
+    if !value.is_finite() {
+        return None;
+    }
+
+    let unit = text[split..].trim();
+    Some((value, (!unit.is_empty()).then_some(unit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn question(question_type: QuestionType, correct: Value, policy: Value) -> QuizQuestion {
+        QuizQuestion {
+            question_id: Uuid::new_v4(),
+            quiz_id: Uuid::new_v4(),
+            question_text: "Question".into(),
+            question_type,
+            points: 4,
+            sort_order: 1,
+            explanation: None,
+            options: json!([]),
+            correct_answers: correct,
+            code_language: None,
+            test_cases: json!([]),
+            scoring_policy: policy,
+            rubric: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn points(q: &QuizQuestion, answer: Value) -> f64 {
+        score_response(q, &answer).points
+    }
+
+    #[test]
+    fn test_default_is_all_or_nothing() {
+        let q = question(QuestionType::MultipleChoice, json!(["a", "b", "c", "d"]), json!({}));
+        assert_eq!(points(&q, json!(["a", "b", "c"])), 0.0);
+        assert!(score_response(&q, &json!(["d", "c", "b", "a"])).is_correct);
+    }
+
+    #[test]
+    fn test_multiple_choice_partial_credit() {
+        let q = question(QuestionType::MultipleChoice, json!(["a", "b", "c", "d"]), json!({"partial_credit": true}));
+        assert_eq!(points(&q, json!(["a", "b", "c"])), 3.0);
+
+        let q = question(
+            QuestionType::MultipleChoice,
+            json!(["a", "b", "c", "d"]),
+            json!({"partial_credit": true, "wrong_penalty": 0.25}),
+        );
+        let score = score_response(&q, &json!(["a", "b", "c", "e"]));
+        assert_eq!(score.points, 2.0);
+        assert!(!score.is_correct);
+        // Never below zero without allow_negative
+        assert_eq!(points(&q, json!(["e", "f"])), 0.0);
+    }
+
+    #[test]
+    fn test_negative_marking() {
+        let q = question(
+            QuestionType::SingleChoice,
+            json!(["b"]),
+            json!({"wrong_penalty": 0.25, "allow_negative": true}),
+        );
+        assert_eq!(points(&q, json!("b")), 4.0);
+        assert_eq!(points(&q, json!("a")), -1.0);
+        assert_eq!(points(&q, Value::Null), 0.0);
+    }
+
+    #[test]
+    fn test_short_answer_modes() {
+        let q = question(QuestionType::ShortAnswer, json!(["Paris"]), json!({}));
+        assert_eq!(points(&q, json!("  paris ")), 4.0);
+
+        let q = question(
+            QuestionType::ShortAnswer,
+            json!(["colou?r"]),
+            json!({"text_match": {"mode": "regex"}}),
+        );
+        assert_eq!(points(&q, json!("Color")), 4.0);
+        assert_eq!(points(&q, json!("colors")), 0.0);
+
+        let q = question(
+            QuestionType::ShortAnswer,
+            json!(["photosynthesis"]),
+            json!({"text_match": {"mode": "fuzzy", "min_similarity": 0.85}}),
+        );
+        assert_eq!(points(&q, json!("Photosynthesys")), 4.0);
+        assert_eq!(points(&q, json!("respiration")), 0.0);
+    }
+
+    #[test]
+    fn test_numeric_tolerance_and_units() {
+        let q = question(
+            QuestionType::ShortAnswer,
+            json!([9.81]),
+            json!({"numeric": {"tolerance": 0.01, "relative": true, "units": ["m/s^2"]}}),
+        );
+        assert_eq!(points(&q, json!("9.8 m/s^2")), 4.0);
+        assert_eq!(points(&q, json!(9.75)), 4.0);
+        assert_eq!(points(&q, json!("9.5")), 0.0);
+        assert_eq!(points(&q, json!("9.81 km")), 0.0);
+
+        let q = question(
+            QuestionType::ShortAnswer,
+            json!(["42"]),
+            json!({"numeric": {"tolerance": 0.5, "units": ["kg"], "require_unit": true}}),
+        );
+        assert_eq!(points(&q, json!("42.4kg")), 4.0);
+        assert_eq!(points(&q, json!("42")), 0.0);
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("9.81 m/s^2"), Some((9.81, Some("m/s^2"))));
+        assert_eq!(parse_quantity("-3e2kg"), Some((-300.0, Some("kg"))));
+        assert_eq!(parse_quantity("12"), Some((12.0, None)));
+        assert_eq!(parse_quantity("kg 12"), None);
+    }
+
+    #[test]
+    fn test_validation() {
+        let policy = |v: Value| serde_json::from_value::<ScoringPolicy>(v).unwrap();
+
+        assert!(validate_policy(&policy(json!({"wrong_penalty": 1.5})), QuestionType::SingleChoice).is_err());
+        assert!(validate_policy(&policy(json!({"partial_credit": true})), QuestionType::TrueFalse).is_err());
+        assert!(validate_policy(&policy(json!({"numeric": {}})), QuestionType::ShortAnswer).is_ok());
+
+        let regex = policy(json!({"text_match": {"mode": "regex"}}));
+        assert!(validate_answer_key(&regex, &json!(["(unclosed"])).is_err());
+        let numeric = policy(json!({"numeric": {"tolerance": 0.1}}));
+        assert!(validate_answer_key(&numeric, &json!(["abc"])).is_err());
+        assert!(validate_answer_key(&numeric, &json!([1, "2.5"])).is_ok());
+    }
+}
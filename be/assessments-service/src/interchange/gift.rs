@@ -0,0 +1,549 @@
+//! # GIFT Import
+//!
+//! Parses Moodle GIFT text into portable questions.
+//!
+//! | GIFT                          | Imported as                          |
+//! |-------------------------------|--------------------------------------|
+//! | `{=right ~wrong}`             | single choice                        |
+//! | `{~%50%a ~%50%b ~%-100%c}`    | multiple choice with partial credit  |
+//! | `{T}` / `{FALSE}`             | true/false                           |
+//! | `{=answer =other}`            | short answer                         |
+//! | `{#3.14:0.01}` / `{#1..5}`    | short answer with numeric tolerance  |
+//! | text `{...}` text             | missing word (blank shown as `_____`) |
+//! | `{}`                          | essay                                |
+//!
+//! Matching questions and description items are skipped. GIFT carries no
+//! points, so every question is worth one point.
+
+use serde_json::{json, Value};
+
+use super::{true_false_options, InterchangeIssue, ParsedImport, PortableQuestion};
+use crate::domain::QuestionType;
+
+/// Blank shown in place of the answer of missing word questions.
+const BLANK: &str = "_____";
+
+/// Parses GIFT text.
+pub fn parse(text: &str) -> ParsedImport {
+    let mut result = ParsedImport::default();
+
+    for (index, item) in split_items(text).iter().enumerate() {
+        let fallback = format!("question {}", index + 1);
+        let (title, body) = split_title(item);
+        let identifier = title.clone().unwrap_or(fallback);
+
+        match parse_item(&identifier, body) {
+            Ok((question, warnings)) => {
+                result.questions.push(question);
+                result.warnings.extend(warnings.into_iter().map(|w| InterchangeIssue::new(&identifier, w)));
+            }
+            Err(reason) => result.skipped.push(InterchangeIssue::new(identifier, reason)),
+        }
+    }
+
+    result
+}
+
+/// Splits the text into items separated by blank lines, dropping comments
+/// and category lines.
+fn split_items(text: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") || trimmed.starts_with("$CATEGORY:") {
+            continue;
+        }
+
+        // Blank lines end an item unless they are inside an answer block
+        if trimmed.is_empty() && depth <= 0 {
+            if !current.trim().is_empty() {
+                items.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            depth = 0;
+            continue;
+        }
+
+        depth += brace_delta(line);
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !current.trim().is_empty() {
+        items.push(current);
+    }
+    items
+}
+
+/// Net count of unescaped opening braces on a line.
+fn brace_delta(line: &str) -> i32 {
+    let mut delta = 0;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => delta += 1,
+            '}' => delta -= 1,
+            _ => {}
+        }
+    }
+    delta
+}
+
+/// Splits an optional `::title::` off an item.
+fn split_title(item: &str) -> (Option<String>, &str) {
+    let item = item.trim();
+    if let Some(rest) = item.strip_prefix("::") {
+        if let Some(end) = find_unescaped(rest, "::") {
+            let title = unescape(rest[..end].trim());
+            let title = (!title.is_empty()).then_some(title);
+            return (title, &rest[end + 2..]);
+        }
+    }
+    (None, item)
+}
+
+/// Parses one item into a question and its fidelity warnings.
+fn parse_item(identifier: &str, body: &str) -> Result<(PortableQuestion, Vec<String>), String> {
+    let body = strip_format(body.trim_start());
+
+    let open = find_unescaped(body, "{").ok_or("Description items have no answers")?;
+    let close = find_unescaped(&body[open..], "}")
+        .map(|i| open + i)
+        .ok_or("Unterminated answer block")?;
+
+    let before = unescape(body[..open].trim());
+    let after = unescape(strip_format(body[close + 1..].trim()));
+    let question_text = if after.is_empty() {
+        before
+    } else {
+        format!("{} {} {}", before, BLANK, after).trim().to_string()
+    };
+    if question_text.is_empty() {
+        return Err("Question text is empty".into());
+    }
+
+    let (answers, explanation) = match find_unescaped(&body[open + 1..close], "####") {
+        Some(i) => {
+            let block = &body[open + 1..close];
+            (block[..i].trim(), Some(unescape(block[i + 4..].trim())).filter(|e| !e.is_empty()))
+        }
+        None => (body[open + 1..close].trim(), None),
+    };
+
+    let mut warnings = Vec::new();
+    let mapped = parse_answers(answers, &mut warnings)?;
+
+    Ok((
+        PortableQuestion {
+            source_id: identifier.to_string(),
+            question_type: mapped.question_type,
+            question_text,
+            options: mapped.options,
+            correct_answers: mapped.correct_answers,
+            points: 1,
+            explanation,
+            scoring_policy: mapped.scoring_policy,
+        },
+        warnings,
+    ))
+}
+
+/// Answer block mapped onto the service's model.
+struct MappedAnswers {
+    question_type: QuestionType,
+    options: Value,
+    correct_answers: Value,
+    scoring_policy: Value,
+}
+
+fn parse_answers(block: &str, warnings: &mut Vec<String>) -> Result<MappedAnswers, String> {
+    if block.is_empty() {
+        return Ok(MappedAnswers {
+            question_type: QuestionType::Essay,
+            options: json!([]),
+            correct_answers: json!([]),
+            scoring_policy: json!({}),
+        });
+    }
+
+    if let Some(numeric) = block.strip_prefix('#') {
+        return parse_numeric(numeric, warnings);
+    }
+
+    let head = split_feedback(block).0.trim().to_ascii_uppercase();
+    if matches!(head.as_str(), "T" | "TRUE" | "F" | "FALSE") {
+        let answer = if head.starts_with('T') { "true" } else { "false" };
+        return Ok(MappedAnswers {
+            question_type: QuestionType::TrueFalse,
+            options: true_false_options(),
+            correct_answers: json!([answer]),
+            scoring_policy: json!({}),
+        });
+    }
+
+    if find_unescaped(block, "->").is_some() {
+        return Err("Matching questions are not supported".into());
+    }
+
+    let answers = split_answers(block);
+    if answers.is_empty() {
+        return Err("Answer block has no answers".into());
+    }
+
+    if answers.iter().all(|a| a.marker == '=') {
+        parse_short_answer(&answers, warnings)
+    } else {
+        parse_choice(&answers, warnings)
+    }
+}
+
+/// One `=` or `~` answer.
+#[derive(Debug)]
+struct GiftAnswer {
+    marker: char,
+    weight: Option<f64>,
+    text: String,
+}
+
+impl GiftAnswer {
+    /// Percentage of the points this answer earns.
+    fn credit(&self) -> f64 {
+        match (self.marker, self.weight) {
+            (_, Some(weight)) => weight,
+            ('=', None) => 100.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Splits an answer block on unescaped `=` and `~` markers.
+fn split_answers(block: &str) -> Vec<GiftAnswer> {
+    let mut raw: Vec<(char, String)> = Vec::new();
+    let mut escaped = false;
+
+    for c in block.chars() {
+        if !escaped && (c == '=' || c == '~') {
+            raw.push((c, String::new()));
+            continue;
+        }
+        escaped = !escaped && c == '\\';
+        if let Some((_, text)) = raw.last_mut() {
+            text.push(c);
+        }
+    }
+
+    raw.into_iter()
+        .map(|(marker, text)| {
+            let (text, weight) = split_weight(split_feedback(text.trim()).0.trim());
+            GiftAnswer { marker, weight, text: unescape(text.trim()) }
+        })
+        .collect()
+}
+
+fn parse_short_answer(answers: &[GiftAnswer], warnings: &mut Vec<String>) -> Result<MappedAnswers, String> {
+    let accepted: Vec<&str> = answers
+        .iter()
+        .filter(|a| a.credit() >= 100.0)
+        .map(|a| a.text.as_str())
+        .collect();
+
+    if accepted.len() < answers.len() {
+        warnings.push("Partial-credit answers were dropped".into());
+    }
+    if accepted.is_empty() {
+        return Err("Short answer has no full-credit answer".into());
+    }
+
+    Ok(MappedAnswers {
+        question_type: QuestionType::ShortAnswer,
+        options: json!([]),
+        correct_answers: json!(accepted),
+        scoring_policy: json!({}),
+    })
+}
+
+fn parse_choice(answers: &[GiftAnswer], warnings: &mut Vec<String>) -> Result<MappedAnswers, String> {
+    let options: Vec<Value> = answers
+        .iter()
+        .enumerate()
+        .map(|(i, a)| json!({"id": option_id(i), "text": a.text}))
+        .collect();
+
+    let positive: Vec<usize> = (0..answers.len()).filter(|&i| answers[i].credit() > 0.0).collect();
+    if positive.is_empty() {
+        return Err("Choice question has no correct answer".into());
+    }
+
+    let penalties: Vec<f64> = answers
+        .iter()
+        .map(GiftAnswer::credit)
+        .filter(|c| *c < 0.0)
+        .map(|c| (-c / 100.0).min(1.0))
+        .collect();
+    let wrong_penalty = penalties.iter().cloned().fold(0.0, f64::max);
+    if penalties.iter().any(|p| (p - wrong_penalty).abs() > f64::EPSILON) {
+        warnings.push("Different wrong-answer penalties were merged into the largest".into());
+    }
+
+    let correct: Vec<String> = positive.iter().map(|&i| option_id(i)).collect();
+
+    let (question_type, scoring_policy) = if positive.len() == 1 {
+        if answers[positive[0]].credit() < 100.0 {
+            warnings.push("Partial credit on the only correct answer was dropped".into());
+        }
+        (QuestionType::SingleChoice, json!({"wrong_penalty": wrong_penalty}))
+    } else {
+        let first = answers[positive[0]].credit();
+        if positive.iter().any(|&i| (answers[i].credit() - first).abs() > 0.01) {
+            warnings.push("Unequal answer weights were imported as equal shares".into());
+        }
+        (
+            QuestionType::MultipleChoice,
+            json!({"partial_credit": true, "wrong_penalty": wrong_penalty}),
+        )
+    };
+
+    Ok(MappedAnswers {
+        question_type,
+        options: json!(options),
+        correct_answers: json!(correct),
+        scoring_policy,
+    })
+}
+
+/// Numerical answers: `#value`, `#value:tolerance`, `#min..max` or
+/// several `=` answers of those forms.
+fn parse_numeric(block: &str, warnings: &mut Vec<String>) -> Result<MappedAnswers, String> {
+    let specs: Vec<(Option<f64>, String)> = if find_unescaped(block, "=").is_some() {
+        split_answers(block).into_iter().map(|a| (Some(a.credit()), a.text)).collect()
+    } else {
+        vec![(None, split_feedback(block).0.trim().to_string())]
+    };
+
+    let mut values = Vec::new();
+    let mut tolerances = Vec::new();
+    for (credit, spec) in &specs {
+        if credit.is_some_and(|c| c < 100.0) {
+            warnings.push("Partial-credit answers were dropped".into());
+            continue;
+        }
+        let (value, tolerance) = parse_numeric_spec(spec).ok_or_else(|| format!("Invalid numerical answer '{}'", spec))?;
+        values.push(value);
+        tolerances.push(tolerance);
+    }
+
+    if values.is_empty() {
+        return Err("Numerical question has no full-credit answer".into());
+    }
+
+    let tolerance = tolerances.iter().cloned().fold(0.0, f64::max);
+    if tolerances.iter().any(|t| (t - tolerance).abs() > f64::EPSILON) {
+        warnings.push("Different tolerances were merged into the largest".into());
+    }
+
+    Ok(MappedAnswers {
+        question_type: QuestionType::ShortAnswer,
+        options: json!([]),
+        correct_answers: json!(values),
+        scoring_policy: json!({"numeric": {"tolerance": tolerance}}),
+    })
+}
+
+/// Parses `value`, `value:tolerance` or `min..max`.
+fn parse_numeric_spec(spec: &str) -> Option<(f64, f64)> {
+    let spec = spec.trim();
+    if let Some((min, max)) = spec.split_once("..") {
+        let (min, max): (f64, f64) = (min.trim().parse().ok()?, max.trim().parse().ok()?);
+        return (min <= max).then(|| ((min + max) / 2.0, (max - min) / 2.0));
+    }
+    match spec.split_once(':') {
+        Some((value, tolerance)) => Some((value.trim().parse().ok()?, tolerance.trim().parse::<f64>().ok()?.abs())),
+        None => Some((spec.parse().ok()?, 0.0)),
+    }
+}
+
+/// Splits `text#feedback`.
+fn split_feedback(text: &str) -> (&str, Option<&str>) {
+    match find_unescaped(text, "#") {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    }
+}
+
+/// Splits a leading `%weight%` off an answer.
+fn split_weight(text: &str) -> (&str, Option<f64>) {
+    if let Some(rest) = text.strip_prefix('%') {
+        if let Some((weight, text)) = rest.split_once('%') {
+            if let Ok(weight) = weight.trim().parse() {
+                return (text, Some(weight));
+            }
+        }
+    }
+    (text, None)
+}
+
+/// Strips a leading `[html]`, `[moodle]`, `[plain]` or `[markdown]` marker.
+fn strip_format(text: &str) -> &str {
+    for marker in ["[html]", "[moodle]", "[plain]", "[markdown]"] {
+        if let Some(rest) = text.strip_prefix(marker) {
+            return rest;
+        }
+    }
+    text
+}
+
+/// Byte offset of the first unescaped occurrence of `pattern`.
+fn find_unescaped(text: &str, pattern: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if c == '\\' {
+            escaped = true;
+            continue;
+        }
+        if text[i..].starts_with(pattern) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Resolves GIFT escapes (`\~ \= \# \{ \} \: \n`).
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some(next @ ('~' | '=' | '#' | '{' | '}' | ':' | '\\')) => out.push(next),
+            Some(next) => {
+                out.push('\\');
+                out.push(next);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Option ID for the option at `index` (`a`, `b`, ... then `o27`, ...).
+fn option_id(index: usize) -> String {
+    if index < 26 {
+        ((b'a' + index as u8) as char).to_string()
+    } else {
+        format!("o{}", index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_common_question_types() {
+        let text = r#"
+// Sample quiz
+$CATEGORY: geography
+
+::Capital:: What is the capital of France? {
+    =Paris #Right
+    ~London
+    ~Berlin
+####Paris has been the capital since 987.
+}
+
+Grant is buried in Grant's tomb. {FALSE}
+
+Who's buried in Grant's tomb? {=Grant =Ulysses S. Grant =%50%Ulysses}
+
+When was Ulysses S. Grant born? {#1822:1}
+
+Which are primary colors? {~%50%Red ~%50%Blue ~%-100%Green}
+
+Describe the water cycle. {}
+"#;
+        let result = parse(text);
+
+        assert!(result.skipped.is_empty(), "{:?}", result.skipped);
+        assert_eq!(result.questions.len(), 6);
+
+        let capital = &result.questions[0];
+        assert_eq!(capital.source_id, "Capital");
+        assert_eq!(capital.question_type, QuestionType::SingleChoice);
+        assert_eq!(capital.question_text, "What is the capital of France?");
+        assert_eq!(capital.options[0], json!({"id": "a", "text": "Paris"}));
+        assert_eq!(capital.correct_answers, json!(["a"]));
+        assert_eq!(capital.explanation.as_deref(), Some("Paris has been the capital since 987."));
+
+        assert_eq!(result.questions[1].question_type, QuestionType::TrueFalse);
+        assert_eq!(result.questions[1].correct_answers, json!(["false"]));
+
+        let short = &result.questions[2];
+        assert_eq!(short.question_type, QuestionType::ShortAnswer);
+        assert_eq!(short.correct_answers, json!(["Grant", "Ulysses S. Grant"]));
+        assert_eq!(result.warnings[0].identifier, "question 3");
+
+        let numeric = &result.questions[3];
+        assert_eq!(numeric.correct_answers, json!([1822.0]));
+        assert_eq!(numeric.scoring_policy, json!({"numeric": {"tolerance": 1.0}}));
+
+        let multi = &result.questions[4];
+        assert_eq!(multi.question_type, QuestionType::MultipleChoice);
+        assert_eq!(multi.correct_answers, json!(["a", "b"]));
+        assert_eq!(multi.scoring_policy, json!({"partial_credit": true, "wrong_penalty": 1.0}));
+
+        assert_eq!(result.questions[5].question_type, QuestionType::Essay);
+    }
+
+    #[test]
+    fn test_missing_word_and_escapes() {
+        let result = parse(r"Mahatma Gandhi's birthday is an Indian holiday on {~15th =2nd ~3rd} of October\: a \{fixed\} date.");
+
+        let question = &result.questions[0];
+        assert_eq!(
+            question.question_text,
+            "Mahatma Gandhi's birthday is an Indian holiday on _____ of October: a {fixed} date."
+        );
+        assert_eq!(question.correct_answers, json!(["b"]));
+    }
+
+    #[test]
+    fn test_numeric_range() {
+        let result = parse("What is a number from 1 to 5? {#1..5}");
+        assert_eq!(result.questions[0].correct_answers, json!([3.0]));
+        assert_eq!(result.questions[0].scoring_policy, json!({"numeric": {"tolerance": 2.0}}));
+    }
+
+    #[test]
+    fn test_reports_unsupported_items() {
+        let text = "::Match:: Match the capitals. {=Canada -> Ottawa =Italy -> Rome}\n\nJust a description.\n\n::Broken:: Unclosed {=a ~b";
+        let result = parse(text);
+
+        assert!(result.questions.is_empty());
+        let reasons: Vec<(&str, &str)> = result
+            .skipped
+            .iter()
+            .map(|s| (s.identifier.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("Match", "Matching questions are not supported"),
+                ("question 2", "Description items have no answers"),
+                ("Broken", "Unterminated answer block"),
+            ]
+        );
+    }
+}
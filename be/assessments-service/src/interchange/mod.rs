@@ -0,0 +1,189 @@
+//! # Question Interchange
+//!
+//! Import and export of quiz content in formats used by other LMSes.
+//!
+//! - [`gift`]: Moodle GIFT text import
+//! - [`qti`]: IMS QTI 2.1 item and package import, and package export
+//!
+//! Imported choice options use the shape `[{"id": "...", "text": "..."}]`
+//! with the correct option IDs in `correct_answers`; true/false questions
+//! use the option IDs `"true"` and `"false"`. Content that has no
+//! equivalent on the other side is left out and listed in the report.
+
+pub mod gift;
+pub mod qti;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::domain::QuestionType;
+
+/// Supported interchange formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterchangeFormat {
+    /// IMS QTI 2.1 (content package or single item XML)
+    Qti,
+    /// Moodle GIFT text
+    Gift,
+}
+
+impl std::str::FromStr for InterchangeFormat {
+    type Err = InterchangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "qti" | "qti21" | "qti2.1" => Ok(InterchangeFormat::Qti),
+            "gift" => Ok(InterchangeFormat::Gift),
+            other => Err(InterchangeError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+/// Interchange errors that reject the whole file.
+#[derive(Debug, Error)]
+pub enum InterchangeError {
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid package: {0}")]
+    InvalidPackage(String),
+
+    #[error("Invalid XML: {0}")]
+    Xml(String),
+
+    #[error("Invalid text encoding: {0}")]
+    Encoding(String),
+}
+
+/// A question in the service's model, detached from any quiz.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortableQuestion {
+    /// Identifier in the source file (title, item identifier or position)
+    pub source_id: String,
+    pub question_type: QuestionType,
+    pub question_text: String,
+    pub options: Value,
+    pub correct_answers: Value,
+    pub points: i32,
+    pub explanation: Option<String>,
+    pub scoring_policy: Value,
+}
+
+/// An item that was left out or changed on the way in or out.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InterchangeIssue {
+    /// Identifier of the item in the source
+    pub identifier: String,
+    /// Why the item was skipped or what was lost
+    pub reason: String,
+}
+
+impl InterchangeIssue {
+    pub fn new(identifier: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self { identifier: identifier.into(), reason: reason.into() }
+    }
+}
+
+/// Result of parsing an import file.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedImport {
+    /// Quiz title found in the file, if any
+    pub title: Option<String>,
+    /// Questions that could be mapped, in file order
+    pub questions: Vec<PortableQuestion>,
+    /// Items that could not be mapped
+    pub skipped: Vec<InterchangeIssue>,
+    /// Items imported with some loss of fidelity
+    pub warnings: Vec<InterchangeIssue>,
+}
+
+/// Parses an import file in the given format.
+pub fn parse(format: InterchangeFormat, data: &[u8]) -> Result<ParsedImport, InterchangeError> {
+    match format {
+        InterchangeFormat::Qti => qti::import(data),
+        InterchangeFormat::Gift => {
+            let text = std::str::from_utf8(data).map_err(|e| InterchangeError::Encoding(e.to_string()))?;
+            Ok(gift::parse(text))
+        }
+    }
+}
+
+/// Options for a true/false question.
+pub(crate) fn true_false_options() -> Value {
+    json!([
+        {"id": "true", "text": "True"},
+        {"id": "false", "text": "False"}
+    ])
+}
+
+/// Reads stored options as `(id, text)` pairs.
+///
+/// Accepts `{"id", "text"}` objects (with `label` as a fallback for the
+/// text) and plain strings, which serve as both ID and text.
+pub(crate) fn option_entries(options: &Value) -> Vec<(String, String)> {
+    options
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|option| match option {
+            Value::String(s) => Some((s.clone(), s.clone())),
+            Value::Object(map) => {
+                let id = match map.get("id")? {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let text = map
+                    .get("text")
+                    .or_else(|| map.get("label"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| id.clone());
+                Some((id, text))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reads `correct_answers` as strings (numbers are formatted).
+pub(crate) fn answer_strings(correct_answers: &Value) -> Vec<String> {
+    correct_answers
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|answer| match answer {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_option_entries_accepts_objects_and_strings() {
+        let options = json!([{"id": "a", "text": "Alpha"}, {"id": 2, "label": "Two"}, "Plain", 7]);
+        assert_eq!(
+            option_entries(&options),
+            vec![
+                ("a".to_string(), "Alpha".to_string()),
+                ("2".to_string(), "Two".to_string()),
+                ("Plain".to_string(), "Plain".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("QTI".parse::<InterchangeFormat>().unwrap(), InterchangeFormat::Qti);
+        assert_eq!("gift".parse::<InterchangeFormat>().unwrap(), InterchangeFormat::Gift);
+        assert!("csv".parse::<InterchangeFormat>().is_err());
+    }
+}
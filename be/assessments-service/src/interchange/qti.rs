@@ -0,0 +1,1121 @@
+//! # QTI 2.1 Import / Export
+//!
+//! Reads IMS QTI 2.1 content packages (zip with `imsmanifest.xml`) or a
+//! single `assessmentItem` document, and writes a quiz as a package with
+//! one item per question and an `assessmentTest` keeping their order.
+//!
+//! | QTI interaction                      | Question type                   |
+//! |--------------------------------------|---------------------------------|
+//! | `choiceInteraction` (single)         | single choice, or true/false when the choices are `true`/`false` |
+//! | `choiceInteraction` (multiple)       | multiple choice                 |
+//! | `inlineChoiceInteraction`            | single choice                   |
+//! | `textEntryInteraction`               | short answer                    |
+//! | `extendedTextInteraction`            | essay                           |
+//!
+//! Items with other interactions, or with more than one interaction, are
+//! reported as skipped. Code questions cannot be exported.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Cursor, Read, Write};
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::{json, Value};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{
+    answer_strings, option_entries, true_false_options, InterchangeError, InterchangeIssue, ParsedImport,
+    PortableQuestion,
+};
+use crate::domain::{QuestionType, Quiz, QuizQuestion, ScoringPolicy, TextMatch};
+
+/// Blank marking where a text entry sits in the question text.
+const BLANK: &str = "_____";
+
+/// Largest file read from a package.
+const MAX_ENTRY_BYTES: u64 = 5 * 1024 * 1024;
+
+const QTI_NAMESPACE: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1";
+const QTI_SCHEMA: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1 http://www.imsglobal.org/xsd/qti/qtiv2p1/imsqti_v2p1.xsd";
+const MATCH_CORRECT: &str = "http://www.imsglobal.org/question/qti_v2p1/rptemplates/match_correct";
+const MAP_RESPONSE: &str = "http://www.imsglobal.org/question/qti_v2p1/rptemplates/map_response";
+
+// =============================================================================
+// IMPORT
+// =============================================================================
+
+/// Imports a QTI 2.1 package or a single item document.
+pub fn import(data: &[u8]) -> Result<ParsedImport, InterchangeError> {
+    if data.starts_with(b"PK") {
+        return import_package(data);
+    }
+
+    let root = parse_xml(data)?;
+    if root.name != "assessmentItem" {
+        return Err(unexpected_root(&root.name));
+    }
+
+    let mut result = ParsedImport::default();
+    let identifier = root.attr("identifier").unwrap_or("item").to_string();
+    push_item(&mut result, &identifier, import_item(&root));
+    Ok(result)
+}
+
+fn import_package(data: &[u8]) -> Result<ParsedImport, InterchangeError> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|e| InterchangeError::InvalidPackage(e.to_string()))?;
+
+    let manifest = read_entry(&mut archive, "imsmanifest.xml")?
+        .ok_or_else(|| InterchangeError::InvalidPackage("imsmanifest.xml not found".into()))?;
+    let manifest = parse_xml(&manifest)?;
+
+    let mut result = ParsedImport::default();
+    let mut item_hrefs = Vec::new();
+    let mut test_href = None;
+
+    for resource in manifest.descendants("resource") {
+        let kind = resource.attr("type").unwrap_or_default();
+        let href = resource.attr("href").unwrap_or_default();
+        if kind.starts_with("imsqti_item_xmlv2p") {
+            item_hrefs.push(href.to_string());
+        } else if kind.starts_with("imsqti_test_xmlv2p") {
+            test_href.get_or_insert_with(|| href.to_string());
+        } else if kind.starts_with("imsqti") {
+            let identifier = resource.attr("identifier").unwrap_or(href);
+            result.skipped.push(InterchangeIssue::new(identifier, format!("Resource type '{}' is not supported", kind)));
+        }
+    }
+
+    // The test fixes the title and the item order
+    if let Some(test_href) = test_href {
+        if let Some(test) = read_entry(&mut archive, &test_href)? {
+            let test = parse_xml(&test)?;
+            result.title = test.attr("title").map(str::to_string).filter(|t| !t.is_empty());
+
+            let ordered: Vec<String> = test
+                .descendants("assessmentItemRef")
+                .into_iter()
+                .filter_map(|r| r.attr("href"))
+                .map(|href| resolve_href(&test_href, href))
+                .collect();
+            if !ordered.is_empty() {
+                let mut rest: Vec<String> = item_hrefs.into_iter().filter(|h| !ordered.contains(h)).collect();
+                item_hrefs = ordered;
+                item_hrefs.append(&mut rest);
+            }
+        }
+    }
+
+    if item_hrefs.is_empty() && result.skipped.is_empty() {
+        return Err(InterchangeError::InvalidPackage("Package contains no QTI 2.1 items".into()));
+    }
+
+    for href in item_hrefs {
+        let item = match read_entry(&mut archive, &href)? {
+            Some(item) => item,
+            None => {
+                result.skipped.push(InterchangeIssue::new(&href, "File missing from package"));
+                continue;
+            }
+        };
+
+        match parse_xml(&item) {
+            Ok(root) if root.name == "assessmentItem" => {
+                let identifier = root.attr("identifier").unwrap_or(&href).to_string();
+                push_item(&mut result, &identifier, import_item(&root));
+            }
+            Ok(root) => result.skipped.push(InterchangeIssue::new(&href, unexpected_root(&root.name).to_string())),
+            Err(e) => result.skipped.push(InterchangeIssue::new(&href, e.to_string())),
+        }
+    }
+
+    Ok(result)
+}
+
+fn push_item(
+    result: &mut ParsedImport,
+    identifier: &str,
+    item: Result<(PortableQuestion, Vec<String>), String>,
+) {
+    match item {
+        Ok((question, warnings)) => {
+            result.questions.push(question);
+            result.warnings.extend(warnings.into_iter().map(|w| InterchangeIssue::new(identifier, w)));
+        }
+        Err(reason) => result.skipped.push(InterchangeIssue::new(identifier, reason)),
+    }
+}
+
+fn unexpected_root(name: &str) -> InterchangeError {
+    if name == "questestinterop" {
+        InterchangeError::InvalidPackage("QTI 1.x is not supported; export as QTI 2.1".into())
+    } else {
+        InterchangeError::InvalidPackage(format!("Expected an assessmentItem, found '{}'", name))
+    }
+}
+
+/// Maps one `assessmentItem` onto a question.
+fn import_item(item: &Element) -> Result<(PortableQuestion, Vec<String>), String> {
+    let identifier = item.attr("identifier").unwrap_or_default().to_string();
+    let body = item.child("itemBody").ok_or("Item has no itemBody")?;
+
+    let interactions: Vec<&Element> = body
+        .descendants_where(|e| e.name.ends_with("Interaction"))
+        .into_iter()
+        .collect();
+    let interaction = match interactions.as_slice() {
+        [] => return Err("Item has no interactions".into()),
+        [interaction] => *interaction,
+        _ => return Err("Items with several interactions are not supported".into()),
+    };
+    if !matches!(
+        interaction.name.as_str(),
+        "choiceInteraction" | "inlineChoiceInteraction" | "textEntryInteraction" | "extendedTextInteraction"
+    ) {
+        return Err(format!("{} is not supported", interaction.name));
+    }
+
+    let response_id = interaction.attr("responseIdentifier").unwrap_or("RESPONSE");
+    let declaration = item
+        .children_named("responseDeclaration")
+        .find(|d| d.attr("identifier") == Some(response_id));
+    let correct: Vec<String> = declaration
+        .and_then(|d| d.child("correctResponse"))
+        .map(|c| c.children_named("value").map(Element::text).collect())
+        .unwrap_or_default();
+    let mapping: Vec<MapEntry> = declaration
+        .and_then(|d| d.child("mapping"))
+        .map(|m| m.children_named("mapEntry").filter_map(MapEntry::from_element).collect())
+        .unwrap_or_default();
+
+    let mut warnings = Vec::new();
+    let points = item_points(item, declaration, &mapping, &mut warnings);
+
+    let mut question_text = body_text(body);
+    if question_text.is_empty() {
+        question_text = item.attr("title").unwrap_or_default().to_string();
+    }
+    if question_text.is_empty() {
+        return Err("Item has no question text".into());
+    }
+
+    let explanation = item
+        .children_named("modalFeedback")
+        .map(|f| normalize_text(&f.flat_text()))
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (question_type, options, correct_answers, scoring_policy) = match interaction.name.as_str() {
+        "choiceInteraction" | "inlineChoiceInteraction" => {
+            let multiple = declaration.and_then(|d| d.attr("cardinality")) == Some("multiple")
+                || interaction.attr("maxChoices").is_some_and(|m| m != "1");
+            map_choice(interaction, multiple, correct, &mapping, points, &mut warnings)?
+        }
+        "textEntryInteraction" => {
+            let base_type = declaration.and_then(|d| d.attr("baseType")).unwrap_or("string");
+            map_text_entry(base_type, correct, &mapping, &mut warnings)?
+        }
+        _ => (QuestionType::Essay, json!([]), json!([]), json!({})),
+    };
+
+    Ok((
+        PortableQuestion {
+            source_id: identifier,
+            question_type,
+            question_text,
+            options,
+            correct_answers,
+            points,
+            explanation: (!explanation.is_empty()).then_some(explanation),
+            scoring_policy,
+        },
+        warnings,
+    ))
+}
+
+/// An entry of a response `mapping`.
+struct MapEntry {
+    key: String,
+    value: f64,
+    case_sensitive: bool,
+}
+
+impl MapEntry {
+    fn from_element(entry: &Element) -> Option<Self> {
+        Some(Self {
+            key: entry.attr("mapKey")?.to_string(),
+            value: entry.attr("mappedValue")?.trim().parse().ok()?,
+            case_sensitive: entry.attr("caseSensitive") == Some("true"),
+        })
+    }
+}
+
+/// Points of an item: `MAXSCORE`, else the `SCORE` maximum, else the
+/// mapping bound, else one.
+fn item_points(item: &Element, declaration: Option<&Element>, mapping: &[MapEntry], warnings: &mut Vec<String>) -> i32 {
+    let outcome = |id: &str| item.children_named("outcomeDeclaration").find(|o| o.attr("identifier") == Some(id));
+
+    let max_score = outcome("MAXSCORE")
+        .and_then(|o| o.child("defaultValue"))
+        .and_then(|d| d.child("value"))
+        .and_then(|v| v.text().trim().parse::<f64>().ok())
+        .or_else(|| outcome("SCORE").and_then(|o| o.attr("normalMaximum")).and_then(|m| m.parse().ok()))
+        .or_else(|| {
+            declaration
+                .and_then(|d| d.child("mapping"))
+                .and_then(|m| m.attr("upperBound"))
+                .and_then(|b| b.parse().ok())
+        })
+        .or_else(|| {
+            let sum: f64 = mapping.iter().map(|e| e.value).filter(|v| *v > 0.0).sum();
+            (sum > 0.0).then_some(sum)
+        });
+
+    match max_score {
+        Some(score) if score > 0.0 => {
+            if score.fract() != 0.0 {
+                warnings.push(format!("Score {} was rounded to whole points", score));
+            }
+            (score.round() as i32).max(1)
+        }
+        _ => 1,
+    }
+}
+
+type MappedQuestion = (QuestionType, Value, Value, Value);
+
+fn map_choice(
+    interaction: &Element,
+    multiple: bool,
+    mut correct: Vec<String>,
+    mapping: &[MapEntry],
+    points: i32,
+    warnings: &mut Vec<String>,
+) -> Result<MappedQuestion, String> {
+    let choices: Vec<(String, String)> = interaction
+        .descendants_where(|e| e.name == "simpleChoice" || e.name == "inlineChoice")
+        .into_iter()
+        .filter_map(|c| Some((c.attr("identifier")?.to_string(), normalize_text(&c.flat_text()))))
+        .collect();
+    if choices.len() < 2 {
+        return Err("Choice interaction needs at least two choices".into());
+    }
+
+    if correct.is_empty() {
+        correct = mapping.iter().filter(|e| e.value > 0.0).map(|e| e.key.clone()).collect();
+    }
+    if correct.is_empty() {
+        return Err("Item has no correct response".into());
+    }
+
+    let ids: BTreeSet<String> = choices.iter().map(|(id, _)| id.to_ascii_lowercase()).collect();
+    if !multiple && ids == BTreeSet::from(["false".to_string(), "true".to_string()]) {
+        let answer = correct[0].to_ascii_lowercase();
+        return Ok((QuestionType::TrueFalse, true_false_options(), json!([answer]), json!({})));
+    }
+
+    let options: Vec<Value> = choices.iter().map(|(id, text)| json!({"id": id, "text": text})).collect();
+
+    if !multiple {
+        if correct.len() > 1 {
+            warnings.push("Only the first correct response was kept".into());
+        }
+        return Ok((QuestionType::SingleChoice, json!(options), json!([correct[0]]), json!({})));
+    }
+
+    // A mapping gives credit per choice
+    let policy = if mapping.is_empty() {
+        json!({})
+    } else {
+        let positive: Vec<f64> = mapping.iter().map(|e| e.value).filter(|v| *v > 0.0).collect();
+        if positive.iter().any(|v| (v - positive[0]).abs() > 1e-6) {
+            warnings.push("Unequal choice weights were imported as equal shares".into());
+        }
+        let penalty = mapping
+            .iter()
+            .map(|e| -e.value / points as f64)
+            .fold(0.0, f64::max)
+            .min(1.0);
+        json!({"partial_credit": true, "wrong_penalty": penalty})
+    };
+
+    Ok((QuestionType::MultipleChoice, json!(options), json!(correct), policy))
+}
+
+fn map_text_entry(
+    base_type: &str,
+    correct: Vec<String>,
+    mapping: &[MapEntry],
+    warnings: &mut Vec<String>,
+) -> Result<MappedQuestion, String> {
+    let best = mapping.iter().map(|e| e.value).fold(0.0, f64::max);
+    let mut accepted = correct;
+    for entry in mapping.iter().filter(|e| e.value > 0.0) {
+        if !accepted.contains(&entry.key) {
+            accepted.push(entry.key.clone());
+        }
+    }
+    if mapping.iter().any(|e| e.value > 0.0 && e.value < best) {
+        warnings.push("Partial-credit answers were imported as full credit".into());
+    }
+    if accepted.is_empty() {
+        return Err("Item has no correct response".into());
+    }
+
+    let policy = match base_type {
+        "float" | "integer" => json!({"numeric": {"tolerance": 0.0}}),
+        _ if mapping.iter().any(|e| e.case_sensitive) => {
+            json!({"text_match": {"mode": "exact", "case_sensitive": true}})
+        }
+        _ => json!({}),
+    };
+
+    Ok((QuestionType::ShortAnswer, json!([]), json!(accepted), policy))
+}
+
+/// Text of an item body: prompts of block interactions are kept, inline
+/// interactions become a blank, feedback and rubrics are dropped.
+fn body_text(body: &Element) -> String {
+    fn walk(element: &Element, out: &mut String) {
+        for node in &element.children {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Element(child) => match child.name.as_str() {
+                    "textEntryInteraction" | "inlineChoiceInteraction" => out.push_str(BLANK),
+                    name if name.ends_with("Interaction") => {
+                        if let Some(prompt) = child.child("prompt") {
+                            out.push('\n');
+                            out.push_str(&prompt.flat_text());
+                            out.push('\n');
+                        }
+                    }
+                    "rubricBlock" | "feedbackBlock" | "feedbackInline" | "modalFeedback" => {}
+                    name if is_block(name) => {
+                        out.push('\n');
+                        walk(child, out);
+                        out.push('\n');
+                    }
+                    _ => walk(child, out),
+                },
+            }
+        }
+    }
+
+    let mut out = String::new();
+    walk(body, &mut out);
+    normalize_text(&out)
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div" | "br" | "li" | "ul" | "ol" | "pre" | "blockquote" | "table" | "tr"
+            | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+    )
+}
+
+/// Collapses whitespace within lines and drops empty lines.
+fn normalize_text(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resolves an `href` relative to the file that contains it.
+fn resolve_href(base: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = base.split('/').collect();
+    parts.pop();
+    for segment in href.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                parts.pop();
+            }
+            segment => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Option<Vec<u8>>, InterchangeError> {
+    let name = name.trim_start_matches("./");
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(InterchangeError::InvalidPackage(e.to_string())),
+    };
+
+    let mut data = Vec::new();
+    (&mut file)
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| InterchangeError::InvalidPackage(e.to_string()))?;
+    if data.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(InterchangeError::InvalidPackage(format!("{} is too large", name)));
+    }
+    Ok(Some(data))
+}
+
+// =============================================================================
+// EXPORT
+// =============================================================================
+
+/// A QTI package built from a quiz.
+#[derive(Debug)]
+pub struct ExportedPackage {
+    /// Zip content
+    pub data: Vec<u8>,
+    /// Questions left out of the package
+    pub skipped: Vec<InterchangeIssue>,
+    /// Questions exported with some loss of fidelity
+    pub warnings: Vec<InterchangeIssue>,
+}
+
+/// Exports a quiz and its questions as a QTI 2.1 content package.
+pub fn export(quiz: &Quiz, questions: &[QuizQuestion]) -> Result<ExportedPackage, InterchangeError> {
+    let mut skipped = Vec::new();
+    let mut warnings = Vec::new();
+    let mut items = Vec::new();
+
+    for question in questions {
+        let identifier = format!("item-{}", question.question_id);
+        let mut item_warnings = Vec::new();
+        match export_item(&identifier, question, &mut item_warnings) {
+            Ok(xml) => {
+                items.push((identifier.clone(), format!("items/{}.xml", identifier), xml));
+                warnings.extend(item_warnings.into_iter().map(|w| InterchangeIssue::new(&identifier, w)));
+            }
+            Err(reason) => skipped.push(InterchangeIssue::new(identifier, reason)),
+        }
+    }
+
+    let test_identifier = format!("test-{}", quiz.quiz_id);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut write = |name: &str, content: &str| -> Result<(), InterchangeError> {
+        zip.start_file(name, options)
+            .map_err(|e| InterchangeError::InvalidPackage(e.to_string()))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| InterchangeError::InvalidPackage(e.to_string()))
+    };
+
+    write("imsmanifest.xml", &manifest_xml(quiz, &test_identifier, &items))?;
+    write("test.xml", &test_xml(quiz, &test_identifier, &items))?;
+    for (_, href, xml) in &items {
+        write(href, xml)?;
+    }
+
+    let data = zip
+        .finish()
+        .map_err(|e| InterchangeError::InvalidPackage(e.to_string()))?
+        .into_inner();
+
+    Ok(ExportedPackage { data, skipped, warnings })
+}
+
+fn export_item(identifier: &str, question: &QuizQuestion, warnings: &mut Vec<String>) -> Result<String, String> {
+    let policy = question.scoring();
+    let points = question.points.max(0);
+    let prompt = escape(question.question_text.as_str());
+
+    let (declaration, body, processing) = match question.question_type {
+        QuestionType::SingleChoice | QuestionType::TrueFalse | QuestionType::MultipleChoice => {
+            let mut entries = option_entries(&question.options);
+            if entries.is_empty() && question.question_type == QuestionType::TrueFalse {
+                entries = option_entries(&true_false_options());
+            }
+            if entries.len() < 2 {
+                return Err("Choice question has fewer than two options".into());
+            }
+
+            let ids = choice_identifiers(&entries);
+            let correct: Vec<&str> = answer_strings(&question.correct_answers)
+                .iter()
+                .filter_map(|a| entries.iter().position(|(id, _)| id == a))
+                .map(|i| ids[i].as_str())
+                .collect();
+            if correct.is_empty() {
+                return Err("Question has no correct option".into());
+            }
+
+            let multiple = question.question_type == QuestionType::MultipleChoice;
+            let values: String = correct.iter().map(|c| format!("<value>{}</value>", c)).collect();
+
+            let (mapping, processing) = if multiple && policy.partial_credit {
+                let share = points as f64 / correct.len() as f64;
+                let penalty = -(policy.wrong_penalty * points as f64);
+                let entries: String = ids
+                    .iter()
+                    .map(|id| {
+                        let value = if correct.contains(&id.as_str()) { share } else { penalty };
+                        format!(r#"<mapEntry mapKey="{}" mappedValue="{}"/>"#, id, value)
+                    })
+                    .collect();
+                let lower = if policy.allow_negative { -(points as f64) } else { 0.0 };
+                (
+                    format!(r#"<mapping lowerBound="{}" upperBound="{}" defaultValue="0">{}</mapping>"#, lower, points, entries),
+                    MAP_RESPONSE,
+                )
+            } else {
+                export_penalty_warning(&policy, warnings);
+                (String::new(), MATCH_CORRECT)
+            };
+
+            let cardinality = if multiple { "multiple" } else { "single" };
+            let max_choices = if multiple { 0 } else { 1 };
+            let choices: String = ids
+                .iter()
+                .zip(&entries)
+                .map(|(id, (_, text))| format!(r#"<simpleChoice identifier="{}">{}</simpleChoice>"#, id, escape(text.as_str())))
+                .collect();
+
+            (
+                format!(
+                    r#"<responseDeclaration identifier="RESPONSE" cardinality="{}" baseType="identifier"><correctResponse>{}</correctResponse>{}</responseDeclaration>"#,
+                    cardinality, values, mapping
+                ),
+                format!(
+                    r#"<choiceInteraction responseIdentifier="RESPONSE" shuffle="false" maxChoices="{}"><prompt>{}</prompt>{}</choiceInteraction>"#,
+                    max_choices, prompt, choices
+                ),
+                Some(processing),
+            )
+        }
+        QuestionType::ShortAnswer => {
+            let answers = answer_strings(&question.correct_answers);
+            if answers.is_empty() {
+                return Err("Question has no accepted answers".into());
+            }
+
+            let (base_type, case_sensitive) = match (&policy.numeric, &policy.text_match) {
+                (Some(numeric), _) => {
+                    if numeric.tolerance > 0.0 || !numeric.units.is_empty() {
+                        warnings.push("Numeric tolerance and units were exported as exact values".into());
+                    }
+                    ("float", false)
+                }
+                (None, TextMatch::Exact { case_sensitive }) => ("string", *case_sensitive),
+                (None, TextMatch::Regex { .. }) => {
+                    warnings.push("Regular expression answers were exported as exact text".into());
+                    ("string", false)
+                }
+                (None, TextMatch::Fuzzy { .. }) => {
+                    warnings.push("Fuzzy matching was exported as exact matching".into());
+                    ("string", false)
+                }
+            };
+            export_penalty_warning(&policy, warnings);
+
+            let entries: String = answers
+                .iter()
+                .map(|a| {
+                    format!(
+                        r#"<mapEntry mapKey="{}" mappedValue="{}" caseSensitive="{}"/>"#,
+                        escape(a.as_str()), points, case_sensitive
+                    )
+                })
+                .collect();
+            let expected_length = answers.iter().map(|a| a.chars().count()).max().unwrap_or(10).max(10);
+            let entry = format!(r#"<textEntryInteraction responseIdentifier="RESPONSE" expectedLength="{}"/>"#, expected_length);
+
+            // Missing word questions keep the entry where the blank was
+            let text = question.question_text.as_str();
+            let body = match text.split_once(BLANK) {
+                Some((before, after)) => format!("<p>{}{}{}</p>", escape(before), entry, escape(after)),
+                None => format!("<p>{}</p><p>{}</p>", prompt, entry),
+            };
+
+            (
+                format!(
+                    r#"<responseDeclaration identifier="RESPONSE" cardinality="single" baseType="{}"><correctResponse><value>{}</value></correctResponse><mapping lowerBound="0" upperBound="{}" defaultValue="0">{}</mapping></responseDeclaration>"#,
+                    base_type, escape(answers[0].as_str()), points, entries
+                ),
+                body,
+                Some(MAP_RESPONSE),
+            )
+        }
+        QuestionType::Essay => (
+            r#"<responseDeclaration identifier="RESPONSE" cardinality="single" baseType="string"/>"#.to_string(),
+            format!(r#"<extendedTextInteraction responseIdentifier="RESPONSE"><prompt>{}</prompt></extendedTextInteraction>"#, prompt),
+            None,
+        ),
+        QuestionType::Code => return Err("Code questions have no QTI 2.1 equivalent".into()),
+    };
+
+    let processing = processing
+        .map(|template| format!(r#"<responseProcessing template="{}"/>"#, template))
+        .unwrap_or_default();
+    let feedback = question
+        .explanation
+        .as_deref()
+        .filter(|e| !e.trim().is_empty())
+        .map(|e| {
+            format!(
+                r#"<modalFeedback outcomeIdentifier="FEEDBACK" identifier="EXPLANATION" showHide="hide">{}</modalFeedback>"#,
+                escape(e)
+            )
+        })
+        .unwrap_or_default();
+    let feedback_outcome = if feedback.is_empty() {
+        ""
+    } else {
+        r#"<outcomeDeclaration identifier="FEEDBACK" cardinality="single" baseType="identifier"/>"#
+    };
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<assessmentItem xmlns="{ns}" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="{schema}" identifier="{identifier}" title="{title}" adaptive="false" timeDependent="false">
+{declaration}
+<outcomeDeclaration identifier="SCORE" cardinality="single" baseType="float"><defaultValue><value>0</value></defaultValue></outcomeDeclaration>
+<outcomeDeclaration identifier="MAXSCORE" cardinality="single" baseType="float"><defaultValue><value>{points}</value></defaultValue></outcomeDeclaration>
+{feedback_outcome}
+<itemBody>{body}</itemBody>
+{processing}
+{feedback}
+</assessmentItem>
+"#,
+        ns = QTI_NAMESPACE,
+        schema = QTI_SCHEMA,
+        identifier = identifier,
+        title = escape(item_title(&question.question_text).as_str()),
+        declaration = declaration,
+        points = points,
+        feedback_outcome = feedback_outcome,
+        body = body,
+        processing = processing,
+        feedback = feedback,
+    ))
+}
+
+fn export_penalty_warning(policy: &ScoringPolicy, warnings: &mut Vec<String>) {
+    if policy.wrong_penalty > 0.0 {
+        warnings.push("Wrong-answer penalty was not exported".into());
+    }
+}
+
+/// Choice identifiers for export: the stored IDs when they are valid and
+/// distinct QTI identifiers, otherwise `choice_1`, `choice_2`, ...
+fn choice_identifiers(entries: &[(String, String)]) -> Vec<String> {
+    let ids: Vec<String> = entries.iter().map(|(id, _)| id.clone()).collect();
+    let distinct: BTreeSet<&String> = ids.iter().collect();
+    if distinct.len() == ids.len() && ids.iter().all(|id| is_identifier(id)) {
+        ids
+    } else {
+        (1..=ids.len()).map(|i| format!("choice_{}", i)).collect()
+    }
+}
+
+/// QTI identifiers are XML NCNames.
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Short item title from the question text.
+fn item_title(text: &str) -> String {
+    let first_line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().trim();
+    if first_line.chars().count() > 80 {
+        format!("{}...", first_line.chars().take(77).collect::<String>())
+    } else {
+        first_line.to_string()
+    }
+}
+
+fn manifest_xml(quiz: &Quiz, test_identifier: &str, items: &[(String, String, String)]) -> String {
+    let dependencies: String = items
+        .iter()
+        .map(|(id, _, _)| format!(r#"<dependency identifierref="{}"/>"#, id))
+        .collect();
+    let resources: String = items
+        .iter()
+        .map(|(id, href, _)| {
+            format!(
+                r#"<resource identifier="{id}" type="imsqti_item_xmlv2p1" href="{href}"><file href="{href}"/></resource>"#,
+                id = id,
+                href = href
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest xmlns="http://www.imsglobal.org/xsd/imscp_v1p1" identifier="manifest-{quiz_id}">
+<metadata><schema>QTIv2.1 Package</schema><schemaversion>1.0.0</schemaversion></metadata>
+<organizations/>
+<resources>
+<resource identifier="{test}" type="imsqti_test_xmlv2p1" href="test.xml"><file href="test.xml"/>{dependencies}</resource>
+{resources}
+</resources>
+</manifest>
+"#,
+        quiz_id = quiz.quiz_id,
+        test = test_identifier,
+        dependencies = dependencies,
+        resources = resources,
+    )
+}
+
+fn test_xml(quiz: &Quiz, test_identifier: &str, items: &[(String, String, String)]) -> String {
+    let title = escape(quiz.title.as_str());
+    let time_limits = quiz
+        .time_limit_minutes
+        .map(|minutes| format!(r#"<timeLimits maxTime="{}"/>"#, minutes * 60))
+        .unwrap_or_default();
+    let ordering = if quiz.shuffle_questions { r#"<ordering shuffle="true"/>"# } else { "" };
+    let refs: String = items
+        .iter()
+        .map(|(id, href, _)| format!(r#"<assessmentItemRef identifier="{}" href="{}"/>"#, id, href))
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<assessmentTest xmlns="{ns}" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="{schema}" identifier="{test}" title="{title}">
+<testPart identifier="part-1" navigationMode="nonlinear" submissionMode="simultaneous">
+{time_limits}
+<assessmentSection identifier="section-1" title="{title}" visible="true">{ordering}{refs}</assessmentSection>
+</testPart>
+</assessmentTest>
+"#,
+        ns = QTI_NAMESPACE,
+        schema = QTI_SCHEMA,
+        test = test_identifier,
+        title = title,
+        time_limits = time_limits,
+        ordering = ordering,
+        refs = refs,
+    )
+}
+
+// =============================================================================
+// XML TREE
+// =============================================================================
+
+/// Minimal element tree; names are stored without namespace prefixes.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: HashMap<String, String>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn from_start(start: &BytesStart<'_>) -> Result<Self, InterchangeError> {
+        let mut attrs = HashMap::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(|e| InterchangeError::Xml(e.to_string()))?;
+            let value = attr.unescape_value().map_err(|e| InterchangeError::Xml(e.to_string()))?;
+            attrs.insert(String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned(), value.into_owned());
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attrs,
+            children: Vec::new(),
+        })
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(String::as_str)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter_map(move |node| match node {
+            Node::Element(e) if e.name == name => Some(e),
+            _ => None,
+        })
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find_map(|node| match node {
+            Node::Element(e) if e.name == name => Some(e),
+            _ => None,
+        })
+    }
+
+    fn descendants(&self, name: &str) -> Vec<&Element> {
+        self.descendants_where(|e| e.name == name)
+    }
+
+    fn descendants_where(&self, predicate: impl Fn(&Element) -> bool + Copy) -> Vec<&Element> {
+        let mut found = Vec::new();
+        for node in &self.children {
+            if let Node::Element(e) = node {
+                if predicate(e) {
+                    found.push(e);
+                }
+                found.extend(e.descendants_where(predicate));
+            }
+        }
+        found
+    }
+
+    /// Direct text content.
+    fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    /// All text below this element, with line breaks around blocks.
+    fn flat_text(&self) -> String {
+        let mut out = String::new();
+        for node in &self.children {
+            match node {
+                Node::Text(t) => out.push_str(t),
+                Node::Element(e) if e.name == "feedbackInline" => {}
+                Node::Element(e) if is_block(&e.name) => {
+                    out.push('\n');
+                    out.push_str(&e.flat_text());
+                    out.push('\n');
+                }
+                Node::Element(e) => out.push_str(&e.flat_text()),
+            }
+        }
+        out
+    }
+}
+
+fn parse_xml(data: &[u8]) -> Result<Element, InterchangeError> {
+    let mut reader = Reader::from_reader(data);
+    let mut buf = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    let xml_error = |e: quick_xml::Error| InterchangeError::Xml(e.to_string());
+
+    loop {
+        match reader.read_event_into(&mut buf).map_err(xml_error)? {
+            Event::Start(start) => stack.push(Element::from_start(&start)?),
+            Event::Empty(start) => {
+                let element = Element::from_start(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(Node::Element(element)),
+                    None => root = Some(element),
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().ok_or_else(|| InterchangeError::Xml("Unbalanced end tag".into()))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(Node::Element(element)),
+                    None => root = Some(element),
+                }
+            }
+            Event::Text(text) => {
+                if let Some(parent) = stack.last_mut() {
+                    let text = text.unescape().map_err(xml_error)?;
+                    parent.children.push(Node::Text(text.into_owned()));
+                }
+            }
+            Event::CData(data) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Node::Text(String::from_utf8_lossy(&data).into_owned()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if !stack.is_empty() {
+        return Err(InterchangeError::Xml("Unexpected end of document".into()));
+    }
+    root.ok_or_else(|| InterchangeError::Xml("Document has no root element".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn quiz() -> Quiz {
+        Quiz {
+            quiz_id: Uuid::new_v4(),
+            course_id: Uuid::new_v4(),
+            lesson_id: None,
+            title: "Geography & History".into(),
+            description: None,
+            instructions: None,
+            total_points: 100,
+            passing_score_percentage: 70.0,
+            time_limit_minutes: Some(30),
+            max_attempts: None,
+            shuffle_questions: false,
+            shuffle_options: false,
+            show_correct_answers: true,
+            is_published: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn question(question_type: QuestionType, text: &str, options: Value, correct: Value, policy: Value) -> QuizQuestion {
+        QuizQuestion {
+            question_id: Uuid::new_v4(),
+            quiz_id: Uuid::new_v4(),
+            question_text: text.into(),
+            question_type,
+            points: 4,
+            sort_order: 0,
+            explanation: None,
+            options,
+            correct_answers: correct,
+            code_language: None,
+            test_cases: json!([]),
+            scoring_policy: policy,
+            rubric: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_imports_choice_item() {
+        let xml = br#"<?xml version="1.0"?>
+<assessmentItem xmlns="http://www.imsglobal.org/xsd/imsqti_v2p1" identifier="capital" title="Capital">
+  <responseDeclaration identifier="RESPONSE" cardinality="single" baseType="identifier">
+    <correctResponse><value>ChoiceB</value></correctResponse>
+  </responseDeclaration>
+  <outcomeDeclaration identifier="SCORE" cardinality="single" baseType="float"/>
+  <itemBody>
+    <p>Look at the map.</p>
+    <choiceInteraction responseIdentifier="RESPONSE" shuffle="true" maxChoices="1">
+      <prompt>What is the capital of <b>France</b>?</prompt>
+      <simpleChoice identifier="ChoiceA">London</simpleChoice>
+      <simpleChoice identifier="ChoiceB">Paris</simpleChoice>
+    </choiceInteraction>
+  </itemBody>
+  <responseProcessing template="http://www.imsglobal.org/question/qti_v2p1/rptemplates/match_correct"/>
+</assessmentItem>"#;
+
+        let result = import(xml).unwrap();
+        let question = &result.questions[0];
+        assert_eq!(question.source_id, "capital");
+        assert_eq!(question.question_type, QuestionType::SingleChoice);
+        assert_eq!(question.question_text, "Look at the map.\nWhat is the capital of France?");
+        assert_eq!(question.options[1], json!({"id": "ChoiceB", "text": "Paris"}));
+        assert_eq!(question.correct_answers, json!(["ChoiceB"]));
+        assert_eq!(question.points, 1);
+    }
+
+    #[test]
+    fn test_imports_text_entry_with_mapping() {
+        let xml = br#"<assessmentItem identifier="blank">
+  <responseDeclaration identifier="RESPONSE" cardinality="single" baseType="string">
+    <correctResponse><value>York</value></correctResponse>
+    <mapping defaultValue="0">
+      <mapEntry mapKey="York" mappedValue="2" caseSensitive="true"/>
+      <mapEntry mapKey="york" mappedValue="1"/>
+    </mapping>
+  </responseDeclaration>
+  <outcomeDeclaration identifier="MAXSCORE" cardinality="single" baseType="float"><defaultValue><value>2</value></defaultValue></outcomeDeclaration>
+  <itemBody><p>Richard of <textEntryInteraction responseIdentifier="RESPONSE"/> gave battle in vain.</p></itemBody>
+</assessmentItem>"#;
+
+        let result = import(xml).unwrap();
+        let question = &result.questions[0];
+        assert_eq!(question.question_type, QuestionType::ShortAnswer);
+        assert_eq!(question.question_text, "Richard of _____ gave battle in vain.");
+        assert_eq!(question.correct_answers, json!(["York", "york"]));
+        assert_eq!(question.points, 2);
+        assert_eq!(question.scoring_policy["text_match"]["case_sensitive"], json!(true));
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_skips_unsupported_interactions() {
+        let xml = br#"<assessmentItem identifier="order">
+  <itemBody><orderInteraction responseIdentifier="RESPONSE"><simpleChoice identifier="a">A</simpleChoice></orderInteraction></itemBody>
+</assessmentItem>"#;
+
+        let result = import(xml).unwrap();
+        assert!(result.questions.is_empty());
+        assert_eq!(result.skipped, vec![InterchangeIssue::new("order", "orderInteraction is not supported")]);
+        assert!(import(b"<questestinterop/>").is_err());
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let questions = [
+            question(
+                QuestionType::SingleChoice,
+                "Capital of France?",
+                json!([{"id": "a", "text": "Paris"}, {"id": "b", "text": "Rome & Co"}]),
+                json!(["a"]),
+                json!({}),
+            ),
+            question(
+                QuestionType::MultipleChoice,
+                "Primary colors?",
+                json!([{"id": "1", "text": "Red"}, {"id": "2", "text": "Blue"}, {"id": "3", "text": "Green"}]),
+                json!(["1", "2"]),
+                json!({"partial_credit": true, "wrong_penalty": 0.5}),
+            ),
+            question(QuestionType::TrueFalse, "The earth is flat.", json!([]), json!([false]), json!({})),
+            question(QuestionType::ShortAnswer, "Richard of _____ gave battle.", json!([]), json!(["York"]), json!({})),
+            question(QuestionType::Essay, "Discuss.", json!([]), json!([]), json!({})),
+            question(QuestionType::Code, "Write a program.", json!([]), json!([]), json!({})),
+        ];
+        let mut with_explanation = questions[0].clone();
+        with_explanation.explanation = Some("Since 987.".into());
+        let questions = [vec![with_explanation], questions[1..].to_vec()].concat();
+
+        let package = export(&quiz(), &questions).unwrap();
+        assert_eq!(package.skipped.len(), 1);
+        assert_eq!(package.skipped[0].identifier, format!("item-{}", questions[5].question_id));
+
+        let result = import(&package.data).unwrap();
+        assert_eq!(result.title.as_deref(), Some("Geography & History"));
+        assert!(result.skipped.is_empty(), "{:?}", result.skipped);
+
+        let imported = &result.questions;
+        assert_eq!(imported.len(), 5);
+
+        assert_eq!(imported[0].question_type, QuestionType::SingleChoice);
+        assert_eq!(imported[0].options[1], json!({"id": "b", "text": "Rome & Co"}));
+        assert_eq!(imported[0].correct_answers, json!(["a"]));
+        assert_eq!(imported[0].points, 4);
+        assert_eq!(imported[0].explanation.as_deref(), Some("Since 987."));
+
+        // Numeric IDs are not valid QTI identifiers
+        assert_eq!(imported[1].question_type, QuestionType::MultipleChoice);
+        assert_eq!(imported[1].correct_answers, json!(["choice_1", "choice_2"]));
+        assert_eq!(imported[1].scoring_policy, json!({"partial_credit": true, "wrong_penalty": 0.5}));
+
+        assert_eq!(imported[2].question_type, QuestionType::TrueFalse);
+        assert_eq!(imported[2].correct_answers, json!(["false"]));
+
+        assert_eq!(imported[3].question_type, QuestionType::ShortAnswer);
+        assert_eq!(imported[3].question_text, "Richard of _____ gave battle.");
+        assert_eq!(imported[3].correct_answers, json!(["York"]));
+
+        assert_eq!(imported[4].question_type, QuestionType::Essay);
+        assert_eq!(imported[4].question_text, "Discuss.");
+    }
+
+    #[test]
+    fn test_resolve_href() {
+        assert_eq!(resolve_href("tests/test.xml", "../items/q1.xml"), "items/q1.xml");
+        assert_eq!(resolve_href("test.xml", "items/q1.xml"), "items/q1.xml");
+    }
+}
@@ -9,17 +9,21 @@
 //! - `assessments.quiz_questions`: Questions for quizzes
 //! - `assessments.quiz_submissions`: Student quiz attempts
 //! - `assessments.quiz_responses`: Individual question responses
+//! - `assessments.quiz_accommodations`: Per-student extra time and attempts
+//! - `assessments.submission_questions`: Questions each attempt received
 //!
 //! ## Cross-Schema Access
 //!
 //! Has SELECT permission on `courses` and `enrollments` schemas for validation.
 
 use sqlx::postgres::PgPool;
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
 use crate::domain::{
-    NewQuiz, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
-    Quiz, QuizQuestion, QuizResponse, QuizSubmission,
+    AssignedQuestion, NewSubmissionQuestion,
+    NewQuiz, NewQuizAccommodation, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
+    Quiz, QuizAccommodation, QuizQuestion, QuizResponse, QuizSubmission,
     SubmissionStatus, UpdateQuiz, UpdateQuizQuestion, UpdateQuizSubmission,
 };
 
@@ -52,7 +56,7 @@ impl AssessmentRepository {
             SELECT
                 quiz_id, course_id, lesson_id, title, description, instructions,
                 total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
+                max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
                 is_published, created_at, updated_at
             FROM assessments.quizzes
             WHERE course_id = $1
@@ -76,7 +80,7 @@ impl AssessmentRepository {
             SELECT
                 quiz_id, course_id, lesson_id, title, description, instructions,
                 total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
+                max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
                 is_published, created_at, updated_at
             FROM assessments.quizzes
             WHERE quiz_id = $1
@@ -94,7 +98,7 @@ impl AssessmentRepository {
             SELECT
                 quiz_id, course_id, lesson_id, title, description, instructions,
                 total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
+                max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
                 is_published, created_at, updated_at
             FROM assessments.quizzes
             WHERE lesson_id = $1 AND is_published = TRUE
@@ -108,34 +112,25 @@ impl AssessmentRepository {
 
     /// Creates a new quiz.
     pub async fn create_quiz(&self, data: NewQuiz) -> Result<Quiz, sqlx::Error> {
-        sqlx::query_as::<_, Quiz>(
-            r#"
-            INSERT INTO assessments.quizzes (
-                course_id, lesson_id, title, description, instructions,
-                total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-            RETURNING
-                quiz_id, course_id, lesson_id, title, description, instructions,
-                total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
-                is_published, created_at, updated_at
-            "#,
-        )
-        .bind(data.course_id)
-        .bind(data.lesson_id)
-        .bind(&data.title)
-        .bind(&data.description)
-        .bind(&data.instructions)
-        .bind(data.total_points.unwrap_or(100))
-        .bind(data.passing_score_percentage.unwrap_or(70.0))
-        .bind(data.time_limit_minutes)
-        .bind(data.max_attempts)
-        .bind(data.shuffle_questions.unwrap_or(false))
-        .bind(data.show_correct_answers.unwrap_or(true))
-        .fetch_one(&self.pool)
-        .await
+        insert_quiz(&self.pool, &data).await
+    }
+
+    /// Creates a quiz and its questions in one transaction.
+    pub async fn create_quiz_with_questions(
+        &self,
+        data: NewQuiz,
+        questions: &[NewQuizQuestion],
+    ) -> Result<(Quiz, Vec<QuizQuestion>), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let quiz = insert_quiz(&mut *tx, &data).await?;
+        let mut created = Vec::with_capacity(questions.len());
+        for question in questions {
+            created.push(insert_question(&mut *tx, quiz.quiz_id, question).await?);
+        }
+
+        tx.commit().await?;
+        Ok((quiz, created))
     }
 
     /// Updates a quiz.
@@ -154,12 +149,13 @@ impl AssessmentRepository {
                 shuffle_questions = COALESCE($13, shuffle_questions),
                 show_correct_answers = COALESCE($14, show_correct_answers),
                 is_published = COALESCE($15, is_published),
+                shuffle_options = COALESCE($16, shuffle_options),
                 updated_at = NOW()
             WHERE quiz_id = $1
             RETURNING
                 quiz_id, course_id, lesson_id, title, description, instructions,
                 total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
+                max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
                 is_published, created_at, updated_at
             "#,
         )
@@ -178,6 +174,7 @@ impl AssessmentRepository {
         .bind(data.shuffle_questions)
         .bind(data.show_correct_answers)
         .bind(data.is_published)
+        .bind(data.shuffle_options)
         .fetch_one(&self.pool)
         .await
     }
@@ -204,7 +201,7 @@ impl AssessmentRepository {
             RETURNING
                 quiz_id, course_id, lesson_id, title, description, instructions,
                 total_points, passing_score_percentage, time_limit_minutes,
-                max_attempts, shuffle_questions, show_correct_answers,
+                max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
                 is_published, created_at, updated_at
             "#,
         )
@@ -224,7 +221,7 @@ impl AssessmentRepository {
             SELECT
                 question_id, quiz_id, question_text, question_type,
                 points, sort_order, explanation, options, correct_answers,
-                code_language, created_at
+                code_language, test_cases, scoring_policy, rubric, created_at
             FROM assessments.quiz_questions
             WHERE quiz_id = $1
             ORDER BY sort_order ASC
@@ -242,7 +239,7 @@ impl AssessmentRepository {
             SELECT
                 question_id, quiz_id, question_text, question_type,
                 points, sort_order, explanation, options, correct_answers,
-                code_language, created_at
+                code_language, test_cases, scoring_policy, rubric, created_at
             FROM assessments.quiz_questions
             WHERE question_id = $1
             "#,
@@ -254,30 +251,24 @@ impl AssessmentRepository {
 
     /// Creates a new question.
     pub async fn create_question(&self, data: NewQuizQuestion) -> Result<QuizQuestion, sqlx::Error> {
-        sqlx::query_as::<_, QuizQuestion>(
-            r#"
-            INSERT INTO assessments.quiz_questions (
-                quiz_id, question_text, question_type, points, sort_order,
-                explanation, options, correct_answers, code_language
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING
-                question_id, quiz_id, question_text, question_type,
-                points, sort_order, explanation, options, correct_answers,
-                code_language, created_at
-            "#,
-        )
-        .bind(data.quiz_id)
-        .bind(&data.question_text)
-        .bind(data.question_type.to_string())
-        .bind(data.points.unwrap_or(5))
-        .bind(data.sort_order)
-        .bind(&data.explanation)
-        .bind(&data.options)
-        .bind(&data.correct_answers)
-        .bind(&data.code_language)
-        .fetch_one(&self.pool)
-        .await
+        insert_question(&self.pool, data.quiz_id, &data).await
+    }
+
+    /// Appends questions to a quiz in one transaction.
+    pub async fn create_questions(
+        &self,
+        quiz_id: Uuid,
+        questions: &[NewQuizQuestion],
+    ) -> Result<Vec<QuizQuestion>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut created = Vec::with_capacity(questions.len());
+        for question in questions {
+            created.push(insert_question(&mut *tx, quiz_id, question).await?);
+        }
+
+        tx.commit().await?;
+        Ok(created)
     }
 
     /// Updates a question.
@@ -293,12 +284,15 @@ impl AssessmentRepository {
                 explanation = CASE WHEN $6 THEN $7 ELSE explanation END,
                 options = COALESCE($8, options),
                 correct_answers = COALESCE($9, correct_answers),
-                code_language = CASE WHEN $10 THEN $11 ELSE code_language END
+                code_language = CASE WHEN $10 THEN $11 ELSE code_language END,
+                test_cases = COALESCE($12, test_cases),
+                scoring_policy = COALESCE($13, scoring_policy),
+                rubric = CASE WHEN $14 THEN $15 ELSE rubric END
             WHERE question_id = $1
             RETURNING
                 question_id, quiz_id, question_text, question_type,
                 points, sort_order, explanation, options, correct_answers,
-                code_language, created_at
+                code_language, test_cases, scoring_policy, rubric, created_at
             "#,
         )
         .bind(question_id)
@@ -312,6 +306,10 @@ impl AssessmentRepository {
         .bind(&data.correct_answers)
         .bind(data.code_language.is_some())
         .bind(data.code_language.flatten())
+        .bind(&data.test_cases)
+        .bind(&data.scoring_policy)
+        .bind(data.rubric.is_some())
+        .bind(data.rubric.flatten())
         .fetch_one(&self.pool)
         .await
     }
@@ -332,50 +330,44 @@ impl AssessmentRepository {
     // SUBMISSION QUERIES
     // =========================================================================
 
-    /// Lists submissions for a quiz.
-    pub async fn list_submissions_by_quiz(
+    /// Lists submissions by user for a quiz.
+    pub async fn list_user_submissions(
         &self,
+        user_id: Uuid,
         quiz_id: Uuid,
-        limit: i64,
-        offset: i64,
     ) -> Result<Vec<QuizSubmission>, sqlx::Error> {
         sqlx::query_as::<_, QuizSubmission>(
             r#"
             SELECT
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             FROM assessments.quiz_submissions
-            WHERE quiz_id = $1
-            ORDER BY started_at DESC
-            LIMIT $2 OFFSET $3
+            WHERE user_id = $1 AND quiz_id = $2
+            ORDER BY attempt_number ASC
             "#,
         )
+        .bind(user_id)
         .bind(quiz_id)
-        .bind(limit)
-        .bind(offset)
         .fetch_all(&self.pool)
         .await
     }
 
-    /// Lists submissions by user for a quiz.
-    pub async fn list_user_submissions(
-        &self,
-        user_id: Uuid,
-        quiz_id: Uuid,
-    ) -> Result<Vec<QuizSubmission>, sqlx::Error> {
+    /// Lists submitted and graded submissions for a quiz.
+    pub async fn list_completed_submissions(&self, quiz_id: Uuid) -> Result<Vec<QuizSubmission>, sqlx::Error> {
         sqlx::query_as::<_, QuizSubmission>(
             r#"
             SELECT
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             FROM assessments.quiz_submissions
-            WHERE user_id = $1 AND quiz_id = $2
-            ORDER BY attempt_number ASC
+            WHERE quiz_id = $1 AND status != 'in_progress'
+            ORDER BY started_at ASC
             "#,
         )
-        .bind(user_id)
         .bind(quiz_id)
         .fetch_all(&self.pool)
         .await
@@ -388,7 +380,8 @@ impl AssessmentRepository {
             SELECT
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             FROM assessments.quiz_submissions
             WHERE submission_id = $1
             "#,
@@ -398,47 +391,199 @@ impl AssessmentRepository {
         .await
     }
 
-    /// Gets the current attempt number for a user on a quiz.
-    pub async fn get_attempt_count(&self, user_id: Uuid, quiz_id: Uuid) -> Result<i32, sqlx::Error> {
-        let result: (i64,) = sqlx::query_as(
+    /// Finds the user's in-progress attempt on a quiz, if any.
+    pub async fn find_in_progress_submission(
+        &self,
+        user_id: Uuid,
+        quiz_id: Uuid,
+    ) -> Result<Option<QuizSubmission>, sqlx::Error> {
+        sqlx::query_as::<_, QuizSubmission>(
             r#"
-            SELECT COUNT(*) as count
+            SELECT
+                submission_id, quiz_id, user_id, enrollment_id, attempt_number,
+                status, score, max_score, passed, time_spent_seconds,
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             FROM assessments.quiz_submissions
-            WHERE user_id = $1 AND quiz_id = $2
+            WHERE user_id = $1 AND quiz_id = $2 AND status = 'in_progress'
+            ORDER BY started_at DESC
+            LIMIT 1
             "#,
         )
         .bind(user_id)
         .bind(quiz_id)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(result.0 as i32)
+        .fetch_optional(&self.pool)
+        .await
     }
 
     /// Creates a new submission (starts a quiz attempt).
-    pub async fn create_submission(&self, data: NewQuizSubmission) -> Result<QuizSubmission, sqlx::Error> {
-        // Get next attempt number
-        let attempt = self.get_attempt_count(data.user_id, data.quiz_id).await? + 1;
+    ///
+    /// Attempts are counted under a per-user/quiz advisory lock, so
+    /// concurrent starts cannot exceed `max_attempts`; a start that loses
+    /// the race returns the attempt the other one created instead of
+    /// opening a second. Returns `None` when the limit is reached. The deadline is computed from the database
+    /// clock, like `started_at`. The assembled questions are stored in the
+    /// same transaction.
+    pub async fn create_submission(
+        &self,
+        data: NewQuizSubmission,
+        max_attempts: Option<i32>,
+        questions: &[NewSubmissionQuestion],
+    ) -> Result<Option<QuizSubmission>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
 
-        sqlx::query_as::<_, QuizSubmission>(
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text || ':' || $2::text, 0))")
+            .bind(data.user_id)
+            .bind(data.quiz_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let current = sqlx::query_as::<_, QuizSubmission>(
+            r#"
+            SELECT
+                submission_id, quiz_id, user_id, enrollment_id, attempt_number,
+                status, score, max_score, passed, time_spent_seconds,
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
+            FROM assessments.quiz_submissions
+            WHERE user_id = $1 AND quiz_id = $2 AND status = 'in_progress'
+            LIMIT 1
+            "#,
+        )
+        .bind(data.user_id)
+        .bind(data.quiz_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if current.is_some() {
+            return Ok(current);
+        }
+
+        let (attempts,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM assessments.quiz_submissions
+            WHERE user_id = $1 AND quiz_id = $2
+            "#,
+        )
+        .bind(data.user_id)
+        .bind(data.quiz_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if max_attempts.is_some_and(|max| attempts >= max as i64) {
+            return Ok(None);
+        }
+
+        let submission = sqlx::query_as::<_, QuizSubmission>(
             r#"
             INSERT INTO assessments.quiz_submissions (
                 quiz_id, user_id, enrollment_id, attempt_number,
-                status, score, max_score
+                status, score, max_score, deadline_at
             )
-            VALUES ($1, $2, $3, $4, 'in_progress', 0, $5)
+            VALUES ($1, $2, $3, $4, 'in_progress', 0, $5, NOW() + $6 * INTERVAL '1 second')
             RETURNING
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             "#,
         )
         .bind(data.quiz_id)
         .bind(data.user_id)
         .bind(data.enrollment_id)
-        .bind(attempt)
+        .bind(attempts as i32 + 1)
         .bind(data.max_score)
-        .fetch_one(&self.pool)
+        .bind(data.time_allowed_seconds.map(|s| s as f64))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (position, question) in questions.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO assessments.submission_questions (
+                    submission_id, position, question_id, quiz_question_id,
+                    version_id, rule_id, points, option_order
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(submission.submission_id)
+            .bind(position as i32)
+            .bind(question.question_id())
+            .bind(question.quiz_question_id)
+            .bind(question.version_id)
+            .bind(question.rule_id)
+            .bind(question.points)
+            .bind(question.option_order.as_ref().map(|order| serde_json::json!(order)))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(submission))
+    }
+
+    /// Lists the questions an attempt received, in display order.
+    ///
+    /// Empty for attempts started before questions were recorded per
+    /// attempt.
+    pub async fn list_submission_questions(&self, submission_id: Uuid) -> Result<Vec<AssignedQuestion>, sqlx::Error> {
+        sqlx::query_as::<_, AssignedQuestion>(
+            r#"
+            SELECT
+                sq.question_id, s.quiz_id,
+                COALESCE(q.question_text, v.question_text) AS question_text,
+                COALESCE(q.question_type, v.question_type) AS question_type,
+                sq.points, sq.position AS sort_order,
+                COALESCE(q.explanation, v.explanation) AS explanation,
+                COALESCE(q.options, v.options) AS options,
+                COALESCE(q.correct_answers, v.correct_answers) AS correct_answers,
+                COALESCE(q.code_language, v.code_language) AS code_language,
+                COALESCE(q.test_cases, v.test_cases) AS test_cases,
+                COALESCE(q.scoring_policy, v.scoring_policy) AS scoring_policy,
+                COALESCE(q.rubric, v.rubric) AS rubric,
+                COALESCE(q.created_at, v.created_at) AS created_at,
+                sq.version_id, sq.option_order
+            FROM assessments.submission_questions sq
+            JOIN assessments.quiz_submissions s ON s.submission_id = sq.submission_id
+            LEFT JOIN assessments.quiz_questions q ON q.question_id = sq.quiz_question_id
+            LEFT JOIN assessments.bank_question_versions v ON v.version_id = sq.version_id
+            WHERE sq.submission_id = $1
+            ORDER BY sq.position ASC
+            "#,
+        )
+        .bind(submission_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Lists in-progress submissions whose deadline passed more than
+    /// `grace_seconds` ago, oldest first.
+    pub async fn list_expired_submissions(
+        &self,
+        grace_seconds: i64,
+        limit: i64,
+    ) -> Result<Vec<QuizSubmission>, sqlx::Error> {
+        sqlx::query_as::<_, QuizSubmission>(
+            r#"
+            SELECT
+                submission_id, quiz_id, user_id, enrollment_id, attempt_number,
+                status, score, max_score, passed, time_spent_seconds,
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
+            FROM assessments.quiz_submissions
+            WHERE status = 'in_progress'
+              AND deadline_at IS NOT NULL
+              AND deadline_at < NOW() - make_interval(secs => $1)
+            ORDER BY deadline_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(grace_seconds as f64)
+        .bind(limit)
+        .fetch_all(&self.pool)
         .await
     }
 
@@ -463,7 +608,8 @@ impl AssessmentRepository {
             RETURNING
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             "#,
         )
         .bind(submission_id)
@@ -482,29 +628,36 @@ impl AssessmentRepository {
         .await
     }
 
-    /// Submits a quiz attempt.
+    /// Submits an in-progress quiz attempt.
+    ///
+    /// Returns `None` if the attempt was already submitted (e.g., by the
+    /// expiry sweeper racing the student).
     pub async fn submit_quiz(
         &self,
         submission_id: Uuid,
         time_spent_seconds: i32,
-    ) -> Result<QuizSubmission, sqlx::Error> {
+        auto_submitted: bool,
+    ) -> Result<Option<QuizSubmission>, sqlx::Error> {
         sqlx::query_as::<_, QuizSubmission>(
             r#"
             UPDATE assessments.quiz_submissions
             SET
                 status = 'submitted',
                 time_spent_seconds = $2,
+                auto_submitted = $3,
                 submitted_at = NOW()
-            WHERE submission_id = $1
+            WHERE submission_id = $1 AND status = 'in_progress'
             RETURNING
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             "#,
         )
         .bind(submission_id)
         .bind(time_spent_seconds)
-        .fetch_one(&self.pool)
+        .bind(auto_submitted)
+        .fetch_optional(&self.pool)
         .await
     }
 
@@ -529,7 +682,8 @@ impl AssessmentRepository {
             RETURNING
                 submission_id, quiz_id, user_id, enrollment_id, attempt_number,
                 status, score, max_score, passed, time_spent_seconds,
-                started_at, submitted_at, graded_at, instructor_feedback
+                started_at, submitted_at, graded_at, instructor_feedback,
+                deadline_at, auto_submitted
             "#,
         )
         .bind(submission_id)
@@ -553,7 +707,8 @@ impl AssessmentRepository {
             r#"
             SELECT
                 response_id, submission_id, question_id, answer_data,
-                is_correct, points_earned, instructor_feedback, auto_graded, created_at
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
             FROM assessments.quiz_responses
             WHERE submission_id = $1
             ORDER BY created_at ASC
@@ -576,7 +731,8 @@ impl AssessmentRepository {
             SET answer_data = $3
             RETURNING
                 response_id, submission_id, question_id, answer_data,
-                is_correct, points_earned, instructor_feedback, auto_graded, created_at
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
             "#,
         )
         .bind(data.submission_id)
@@ -602,11 +758,13 @@ impl AssessmentRepository {
                 is_correct = $2,
                 points_earned = $3,
                 instructor_feedback = $4,
-                auto_graded = $5
+                auto_graded = $5,
+                rubric_scores = NULL
             WHERE response_id = $1
             RETURNING
                 response_id, submission_id, question_id, answer_data,
-                is_correct, points_earned, instructor_feedback, auto_graded, created_at
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
             "#,
         )
         .bind(response_id)
@@ -618,6 +776,209 @@ impl AssessmentRepository {
         .await
     }
 
+    /// Grades a code response with its per-test results.
+    pub async fn grade_code_response(
+        &self,
+        response_id: Uuid,
+        is_correct: bool,
+        points_earned: f64,
+        test_results: serde_json::Value,
+    ) -> Result<QuizResponse, sqlx::Error> {
+        sqlx::query_as::<_, QuizResponse>(
+            r#"
+            UPDATE assessments.quiz_responses
+            SET
+                is_correct = $2,
+                points_earned = $3,
+                test_results = $4,
+                auto_graded = TRUE
+            WHERE response_id = $1
+            RETURNING
+                response_id, submission_id, question_id, answer_data,
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
+            "#,
+        )
+        .bind(response_id)
+        .bind(is_correct)
+        .bind(points_earned)
+        .bind(test_results)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Finds a response by ID.
+    pub async fn find_response_by_id(&self, response_id: Uuid) -> Result<Option<QuizResponse>, sqlx::Error> {
+        sqlx::query_as::<_, QuizResponse>(
+            r#"
+            SELECT
+                response_id, submission_id, question_id, answer_data,
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
+            FROM assessments.quiz_responses
+            WHERE response_id = $1
+            "#,
+        )
+        .bind(response_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Grades a response with its rubric breakdown.
+    pub async fn grade_rubric_response(
+        &self,
+        response_id: Uuid,
+        is_correct: bool,
+        points_earned: f64,
+        feedback: Option<String>,
+        rubric_scores: serde_json::Value,
+    ) -> Result<QuizResponse, sqlx::Error> {
+        sqlx::query_as::<_, QuizResponse>(
+            r#"
+            UPDATE assessments.quiz_responses
+            SET
+                is_correct = $2,
+                points_earned = $3,
+                instructor_feedback = $4,
+                rubric_scores = $5,
+                auto_graded = FALSE
+            WHERE response_id = $1
+            RETURNING
+                response_id, submission_id, question_id, answer_data,
+                is_correct, points_earned, instructor_feedback, auto_graded, test_results,
+                rubric_scores, created_at
+            "#,
+        )
+        .bind(response_id)
+        .bind(is_correct)
+        .bind(points_earned)
+        .bind(feedback)
+        .bind(rubric_scores)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Lists the rubric breakdowns of a quiz's completed submissions as
+    /// `(question_id, rubric_scores)` pairs.
+    pub async fn list_rubric_scores(&self, quiz_id: Uuid) -> Result<Vec<(Uuid, serde_json::Value)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT r.question_id, r.rubric_scores
+            FROM assessments.quiz_responses r
+            JOIN assessments.quiz_submissions s ON s.submission_id = r.submission_id
+            WHERE s.quiz_id = $1
+              AND s.status != 'in_progress'
+              AND r.rubric_scores IS NOT NULL
+            ORDER BY r.created_at ASC
+            "#,
+        )
+        .bind(quiz_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // =========================================================================
+    // ACCOMMODATION QUERIES
+    // =========================================================================
+
+    /// Finds the accommodation that applies to a user on a quiz, preferring
+    /// a quiz-specific one over a course-wide one.
+    pub async fn find_accommodation(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+        quiz_id: Uuid,
+    ) -> Result<Option<QuizAccommodation>, sqlx::Error> {
+        sqlx::query_as::<_, QuizAccommodation>(
+            r#"
+            SELECT
+                accommodation_id, course_id, quiz_id, user_id,
+                time_multiplier::FLOAT8 AS time_multiplier, extra_time_minutes,
+                extra_attempts, notes, created_at, updated_at
+            FROM assessments.quiz_accommodations
+            WHERE user_id = $1 AND course_id = $2 AND (quiz_id = $3 OR quiz_id IS NULL)
+            ORDER BY quiz_id NULLS LAST
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .bind(quiz_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Lists accommodations for a course, optionally for one user.
+    pub async fn list_accommodations(
+        &self,
+        course_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<QuizAccommodation>, sqlx::Error> {
+        sqlx::query_as::<_, QuizAccommodation>(
+            r#"
+            SELECT
+                accommodation_id, course_id, quiz_id, user_id,
+                time_multiplier::FLOAT8 AS time_multiplier, extra_time_minutes,
+                extra_attempts, notes, created_at, updated_at
+            FROM assessments.quiz_accommodations
+            WHERE course_id = $1 AND ($2::UUID IS NULL OR user_id = $2)
+            ORDER BY user_id, quiz_id NULLS FIRST
+            "#,
+        )
+        .bind(course_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Creates or replaces the accommodation for a user and scope.
+    pub async fn upsert_accommodation(
+        &self,
+        data: NewQuizAccommodation,
+    ) -> Result<QuizAccommodation, sqlx::Error> {
+        sqlx::query_as::<_, QuizAccommodation>(
+            r#"
+            INSERT INTO assessments.quiz_accommodations (
+                course_id, quiz_id, user_id, time_multiplier,
+                extra_time_minutes, extra_attempts, notes
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (user_id, course_id, COALESCE(quiz_id, '00000000-0000-0000-0000-000000000000'::uuid))
+            DO UPDATE SET
+                time_multiplier = EXCLUDED.time_multiplier,
+                extra_time_minutes = EXCLUDED.extra_time_minutes,
+                extra_attempts = EXCLUDED.extra_attempts,
+                notes = EXCLUDED.notes,
+                updated_at = NOW()
+            RETURNING
+                accommodation_id, course_id, quiz_id, user_id,
+                time_multiplier::FLOAT8 AS time_multiplier, extra_time_minutes,
+                extra_attempts, notes, created_at, updated_at
+            "#,
+        )
+        .bind(data.course_id)
+        .bind(data.quiz_id)
+        .bind(data.user_id)
+        .bind(data.time_multiplier)
+        .bind(data.extra_time_minutes)
+        .bind(data.extra_attempts)
+        .bind(data.notes)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Deletes an accommodation.
+    pub async fn delete_accommodation(&self, accommodation_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM assessments.quiz_accommodations WHERE accommodation_id = $1"
+        )
+        .bind(accommodation_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     // =========================================================================
     // STATISTICS
     // =========================================================================
@@ -641,21 +1002,35 @@ impl AssessmentRepository {
         .await
     }
 
-    /// Gets submission statistics for grading queue.
-    pub async fn get_pending_grading_count(&self, course_id: Uuid) -> Result<i64, sqlx::Error> {
-        let result: (i64,) = sqlx::query_as(
+    /// Gets per-question statistics over completed attempts.
+    ///
+    /// Bank questions are reported per version, so edits never mix results
+    /// of different content.
+    pub async fn get_question_stats(&self, quiz_id: Uuid) -> Result<Vec<QuestionStats>, sqlx::Error> {
+        sqlx::query_as::<_, QuestionStats>(
             r#"
-            SELECT COUNT(*) as count
-            FROM assessments.quiz_submissions s
-            JOIN assessments.quizzes q ON s.quiz_id = q.quiz_id
-            WHERE q.course_id = $1 AND s.status = 'submitted'
+            SELECT
+                sq.question_id,
+                v.bank_question_id,
+                v.version,
+                COUNT(*) as times_presented,
+                COUNT(r.response_id) as times_answered,
+                COUNT(*) FILTER (WHERE r.is_correct = TRUE) as correct_count,
+                COALESCE(AVG(r.points_earned::FLOAT8), 0) as avg_points,
+                MAX(sq.points) as max_points
+            FROM assessments.submission_questions sq
+            JOIN assessments.quiz_submissions s ON s.submission_id = sq.submission_id
+            LEFT JOIN assessments.bank_question_versions v ON v.version_id = sq.version_id
+            LEFT JOIN assessments.quiz_responses r
+                ON r.submission_id = sq.submission_id AND r.question_id = sq.question_id
+            WHERE s.quiz_id = $1 AND s.status != 'in_progress'
+            GROUP BY sq.question_id, v.bank_question_id, v.version
+            ORDER BY times_presented DESC
             "#,
         )
-        .bind(course_id)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(result.0)
+        .bind(quiz_id)
+        .fetch_all(&self.pool)
+        .await
     }
 }
 
@@ -669,6 +1044,19 @@ pub struct QuizStats {
     pub avg_time_seconds: f64,
 }
 
+/// Statistics for one question (or bank question version) of a quiz.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct QuestionStats {
+    pub question_id: Uuid,
+    pub bank_question_id: Option<Uuid>,
+    pub version: Option<i32>,
+    pub times_presented: i64,
+    pub times_answered: i64,
+    pub correct_count: i64,
+    pub avg_points: f64,
+    pub max_points: i32,
+}
+
 /// Statistics for submissions.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct SubmissionStats {
@@ -676,3 +1064,72 @@ pub struct SubmissionStats {
     pub correct_count: i64,
     pub pending_review: i64,
 }
+
+/// Inserts a quiz on a pool or inside a transaction.
+async fn insert_quiz<'e, E: PgExecutor<'e>>(executor: E, data: &NewQuiz) -> Result<Quiz, sqlx::Error> {
+    sqlx::query_as::<_, Quiz>(
+        r#"
+        INSERT INTO assessments.quizzes (
+            course_id, lesson_id, title, description, instructions,
+            total_points, passing_score_percentage, time_limit_minutes,
+            max_attempts, shuffle_questions, show_correct_answers, shuffle_options
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING
+            quiz_id, course_id, lesson_id, title, description, instructions,
+            total_points, passing_score_percentage, time_limit_minutes,
+            max_attempts, shuffle_questions, shuffle_options, show_correct_answers,
+            is_published, created_at, updated_at
+        "#,
+    )
+    .bind(data.course_id)
+    .bind(data.lesson_id)
+    .bind(&data.title)
+    .bind(&data.description)
+    .bind(&data.instructions)
+    .bind(data.total_points.unwrap_or(100))
+    .bind(data.passing_score_percentage.unwrap_or(70.0))
+    .bind(data.time_limit_minutes)
+    .bind(data.max_attempts)
+    .bind(data.shuffle_questions.unwrap_or(false))
+    .bind(data.show_correct_answers.unwrap_or(true))
+    .bind(data.shuffle_options.unwrap_or(false))
+    .fetch_one(executor)
+    .await
+}
+
+/// Inserts a question on a pool or inside a transaction.
+async fn insert_question<'e, E: PgExecutor<'e>>(
+    executor: E,
+    quiz_id: Uuid,
+    data: &NewQuizQuestion,
+) -> Result<QuizQuestion, sqlx::Error> {
+    sqlx::query_as::<_, QuizQuestion>(
+        r#"
+        INSERT INTO assessments.quiz_questions (
+            quiz_id, question_text, question_type, points, sort_order,
+            explanation, options, correct_answers, code_language, test_cases,
+            scoring_policy, rubric
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING
+            question_id, quiz_id, question_text, question_type,
+            points, sort_order, explanation, options, correct_answers,
+            code_language, test_cases, scoring_policy, rubric, created_at
+        "#,
+    )
+    .bind(quiz_id)
+    .bind(&data.question_text)
+    .bind(data.question_type.to_string())
+    .bind(data.points.unwrap_or(5))
+    .bind(data.sort_order)
+    .bind(&data.explanation)
+    .bind(&data.options)
+    .bind(&data.correct_answers)
+    .bind(&data.code_language)
+    .bind(&data.test_cases)
+    .bind(&data.scoring_policy)
+    .bind(&data.rubric)
+    .fetch_one(executor)
+    .await
+}
@@ -0,0 +1,427 @@
+//! # Question Bank Repository
+//!
+//! PostgreSQL data access for question banks, versioned bank questions and
+//! quiz draw rules.
+//!
+//! ## Schema
+//!
+//! - `assessments.question_banks`: Banks per course
+//! - `assessments.bank_questions`: Tags, difficulty and current version
+//! - `assessments.bank_question_versions`: Immutable question content
+//! - `assessments.quiz_draw_rules`: Random draws from a bank into a quiz
+
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    BankQuestion, BankQuestionContent, Difficulty, NewBankQuestion, NewQuestionBank,
+    NewQuizDrawRule, QuestionBank, QuizDrawRule, UpdateBankQuestion, UpdateQuestionBank,
+};
+
+/// Repository for question bank data access.
+#[derive(Debug, Clone)]
+pub struct QuestionBankRepository {
+    pool: PgPool,
+}
+
+impl QuestionBankRepository {
+    /// Creates a new question bank repository.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // =========================================================================
+    // BANK QUERIES
+    // =========================================================================
+
+    /// Lists the banks of a course.
+    pub async fn list_banks(&self, course_id: Uuid) -> Result<Vec<QuestionBank>, sqlx::Error> {
+        sqlx::query_as::<_, QuestionBank>(
+            r#"
+            SELECT bank_id, course_id, title, description, created_at, updated_at
+            FROM assessments.question_banks
+            WHERE course_id = $1
+            ORDER BY title ASC
+            "#,
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Finds a bank by ID.
+    pub async fn find_bank(&self, bank_id: Uuid) -> Result<Option<QuestionBank>, sqlx::Error> {
+        sqlx::query_as::<_, QuestionBank>(
+            r#"
+            SELECT bank_id, course_id, title, description, created_at, updated_at
+            FROM assessments.question_banks
+            WHERE bank_id = $1
+            "#,
+        )
+        .bind(bank_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Creates a bank.
+    pub async fn create_bank(&self, data: NewQuestionBank) -> Result<QuestionBank, sqlx::Error> {
+        sqlx::query_as::<_, QuestionBank>(
+            r#"
+            INSERT INTO assessments.question_banks (course_id, title, description)
+            VALUES ($1, $2, $3)
+            RETURNING bank_id, course_id, title, description, created_at, updated_at
+            "#,
+        )
+        .bind(data.course_id)
+        .bind(&data.title)
+        .bind(&data.description)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Updates a bank.
+    pub async fn update_bank(&self, bank_id: Uuid, data: UpdateQuestionBank) -> Result<QuestionBank, sqlx::Error> {
+        sqlx::query_as::<_, QuestionBank>(
+            r#"
+            UPDATE assessments.question_banks
+            SET
+                title = COALESCE($2, title),
+                description = CASE WHEN $3 THEN $4 ELSE description END,
+                updated_at = NOW()
+            WHERE bank_id = $1
+            RETURNING bank_id, course_id, title, description, created_at, updated_at
+            "#,
+        )
+        .bind(bank_id)
+        .bind(&data.title)
+        .bind(data.description.is_some())
+        .bind(data.description.flatten())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Deletes a bank and its questions.
+    pub async fn delete_bank(&self, bank_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM assessments.question_banks WHERE bank_id = $1"
+        )
+        .bind(bank_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns true if a draw rule or any attempt references the bank.
+    pub async fn bank_in_use(&self, bank_id: Uuid) -> Result<bool, sqlx::Error> {
+        let (in_use,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT
+                EXISTS (SELECT 1 FROM assessments.quiz_draw_rules WHERE bank_id = $1)
+                OR EXISTS (
+                    SELECT 1
+                    FROM assessments.submission_questions sq
+                    JOIN assessments.bank_question_versions v ON v.version_id = sq.version_id
+                    JOIN assessments.bank_questions bq ON bq.bank_question_id = v.bank_question_id
+                    WHERE bq.bank_id = $1
+                )
+            "#,
+        )
+        .bind(bank_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(in_use)
+    }
+
+    // =========================================================================
+    // BANK QUESTION QUERIES
+    // =========================================================================
+
+    /// Finds a bank question with its current version.
+    pub async fn find_question(&self, bank_question_id: Uuid) -> Result<Option<BankQuestion>, sqlx::Error> {
+        sqlx::query_as::<_, BankQuestion>(
+            r#"
+            SELECT
+                bq.bank_question_id, bq.bank_id, bq.tags, bq.difficulty,
+                v.version, v.version_id, v.question_type, v.question_text,
+                v.options, v.correct_answers, v.points, v.explanation,
+                v.code_language, v.test_cases, v.scoring_policy, v.rubric,
+                bq.is_archived, bq.created_at, bq.updated_at
+            FROM assessments.bank_questions bq
+            JOIN assessments.bank_question_versions v
+                ON v.bank_question_id = bq.bank_question_id AND v.version = bq.current_version
+            WHERE bq.bank_question_id = $1
+            "#,
+        )
+        .bind(bank_question_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Lists questions of a bank, optionally filtered by difficulty and tag.
+    pub async fn list_questions(
+        &self,
+        bank_id: Uuid,
+        difficulty: Option<Difficulty>,
+        tag: Option<&str>,
+        include_archived: bool,
+    ) -> Result<Vec<BankQuestion>, sqlx::Error> {
+        sqlx::query_as::<_, BankQuestion>(
+            r#"
+            SELECT
+                bq.bank_question_id, bq.bank_id, bq.tags, bq.difficulty,
+                v.version, v.version_id, v.question_type, v.question_text,
+                v.options, v.correct_answers, v.points, v.explanation,
+                v.code_language, v.test_cases, v.scoring_policy, v.rubric,
+                bq.is_archived, bq.created_at, bq.updated_at
+            FROM assessments.bank_questions bq
+            JOIN assessments.bank_question_versions v
+                ON v.bank_question_id = bq.bank_question_id AND v.version = bq.current_version
+            WHERE bq.bank_id = $1
+            AND ($2::TEXT IS NULL OR bq.difficulty = $2)
+            AND ($3::TEXT IS NULL OR $3 = ANY(bq.tags))
+            AND ($4 OR NOT bq.is_archived)
+            ORDER BY bq.created_at ASC
+            "#,
+        )
+        .bind(bank_id)
+        .bind(difficulty)
+        .bind(tag)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Lists the active questions a draw rule can pick from.
+    pub async fn list_candidates(&self, rule: &QuizDrawRule) -> Result<Vec<BankQuestion>, sqlx::Error> {
+        sqlx::query_as::<_, BankQuestion>(
+            r#"
+            SELECT
+                bq.bank_question_id, bq.bank_id, bq.tags, bq.difficulty,
+                v.version, v.version_id, v.question_type, v.question_text,
+                v.options, v.correct_answers, v.points, v.explanation,
+                v.code_language, v.test_cases, v.scoring_policy, v.rubric,
+                bq.is_archived, bq.created_at, bq.updated_at
+            FROM assessments.bank_questions bq
+            JOIN assessments.bank_question_versions v
+                ON v.bank_question_id = bq.bank_question_id AND v.version = bq.current_version
+            WHERE bq.bank_id = $1
+            AND NOT bq.is_archived
+            AND ($2::TEXT IS NULL OR bq.difficulty = $2)
+            AND (cardinality($3::TEXT[]) = 0 OR bq.tags && $3)
+            "#,
+        )
+        .bind(rule.bank_id)
+        .bind(rule.difficulty)
+        .bind(&rule.tags)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Adds a question to a bank as version 1.
+    pub async fn create_question(&self, data: NewBankQuestion) -> Result<BankQuestion, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let (bank_question_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO assessments.bank_questions (bank_id, tags, difficulty)
+            VALUES ($1, $2, $3)
+            RETURNING bank_question_id
+            "#,
+        )
+        .bind(data.bank_id)
+        .bind(&data.tags)
+        .bind(data.difficulty)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_version(&mut tx, bank_question_id, 1, &data.content).await?;
+
+        tx.commit().await?;
+
+        self.find_question(bank_question_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Updates a bank question. `content` (if any) becomes a new version.
+    pub async fn update_question(
+        &self,
+        bank_question_id: Uuid,
+        data: &UpdateBankQuestion,
+        content: Option<BankQuestionContent>,
+    ) -> Result<BankQuestion, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let (current_version,): (i32,) = sqlx::query_as(
+            r#"
+            SELECT current_version
+            FROM assessments.bank_questions
+            WHERE bank_question_id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(bank_question_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let version = match &content {
+            Some(content) => {
+                Self::insert_version(&mut tx, bank_question_id, current_version + 1, content).await?;
+                current_version + 1
+            }
+            None => current_version,
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE assessments.bank_questions
+            SET
+                tags = COALESCE($2, tags),
+                difficulty = COALESCE($3, difficulty),
+                current_version = $4,
+                updated_at = NOW()
+            WHERE bank_question_id = $1
+            "#,
+        )
+        .bind(bank_question_id)
+        .bind(&data.tags)
+        .bind(data.difficulty)
+        .bind(version)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.find_question(bank_question_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Archives or restores a bank question. Archived questions are no
+    /// longer drawn; past attempts keep their versions.
+    pub async fn set_archived(&self, bank_question_id: Uuid, archived: bool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE assessments.bank_questions
+            SET is_archived = $2, updated_at = NOW()
+            WHERE bank_question_id = $1
+            "#,
+        )
+        .bind(bank_question_id)
+        .bind(archived)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_version(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        bank_question_id: Uuid,
+        version: i32,
+        content: &BankQuestionContent,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO assessments.bank_question_versions (
+                bank_question_id, version, question_type, question_text,
+                options, correct_answers, points, explanation,
+                code_language, test_cases, scoring_policy, rubric
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+        )
+        .bind(bank_question_id)
+        .bind(version)
+        .bind(content.question_type)
+        .bind(&content.question_text)
+        .bind(&content.options)
+        .bind(&content.correct_answers)
+        .bind(content.points)
+        .bind(&content.explanation)
+        .bind(&content.code_language)
+        .bind(&content.test_cases)
+        .bind(&content.scoring_policy)
+        .bind(&content.rubric)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // DRAW RULE QUERIES
+    // =========================================================================
+
+    /// Lists the draw rules of a quiz.
+    pub async fn list_rules(&self, quiz_id: Uuid) -> Result<Vec<QuizDrawRule>, sqlx::Error> {
+        sqlx::query_as::<_, QuizDrawRule>(
+            r#"
+            SELECT
+                rule_id, quiz_id, bank_id, question_count, difficulty, tags,
+                points_per_question, sort_order, created_at
+            FROM assessments.quiz_draw_rules
+            WHERE quiz_id = $1
+            ORDER BY sort_order ASC, created_at ASC
+            "#,
+        )
+        .bind(quiz_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Finds a draw rule by ID.
+    pub async fn find_rule(&self, rule_id: Uuid) -> Result<Option<QuizDrawRule>, sqlx::Error> {
+        sqlx::query_as::<_, QuizDrawRule>(
+            r#"
+            SELECT
+                rule_id, quiz_id, bank_id, question_count, difficulty, tags,
+                points_per_question, sort_order, created_at
+            FROM assessments.quiz_draw_rules
+            WHERE rule_id = $1
+            "#,
+        )
+        .bind(rule_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Adds a draw rule to a quiz.
+    pub async fn create_rule(&self, data: NewQuizDrawRule) -> Result<QuizDrawRule, sqlx::Error> {
+        sqlx::query_as::<_, QuizDrawRule>(
+            r#"
+            INSERT INTO assessments.quiz_draw_rules (
+                quiz_id, bank_id, question_count, difficulty, tags,
+                points_per_question, sort_order
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                rule_id, quiz_id, bank_id, question_count, difficulty, tags,
+                points_per_question, sort_order, created_at
+            "#,
+        )
+        .bind(data.quiz_id)
+        .bind(data.bank_id)
+        .bind(data.question_count)
+        .bind(data.difficulty)
+        .bind(&data.tags)
+        .bind(data.points_per_question)
+        .bind(data.sort_order)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Deletes a draw rule.
+    pub async fn delete_rule(&self, rule_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM assessments.quiz_draw_rules WHERE rule_id = $1"
+        )
+        .bind(rule_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
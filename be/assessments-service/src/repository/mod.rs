@@ -3,9 +3,12 @@
 //! PostgreSQL data access layer for assessments.
 
 pub mod assessment_repository;
+pub mod bank_repository;
 
 pub use assessment_repository::{
     AssessmentRepository,
+    QuestionStats,
     QuizStats,
     SubmissionStats,
 };
+pub use bank_repository::QuestionBankRepository;
@@ -0,0 +1,255 @@
+//! # Quiz Assembly
+//!
+//! Builds the question list of a new attempt from the quiz's fixed
+//! questions and its draw rules, shuffling questions and options when the
+//! quiz asks for it.
+
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::domain::{BankQuestion, NewSubmissionQuestion, QuestionType, Quiz, QuizDrawRule, QuizQuestion};
+
+/// Assembles the questions of one attempt.
+///
+/// Fixed questions and draw rules are laid out by `sort_order` (fixed
+/// questions first on ties), then shuffled if `shuffle_questions` is set.
+/// Rules with fewer candidates draw first so overlapping rules do not steal
+/// the only questions a narrower rule can use.
+pub fn assemble<R: Rng + ?Sized>(
+    quiz: &Quiz,
+    fixed: &[QuizQuestion],
+    draws: &[(QuizDrawRule, Vec<BankQuestion>)],
+    rng: &mut R,
+) -> Result<Vec<NewSubmissionQuestion>, String> {
+    // (sort_order, fixed before rules, questions)
+    let mut slots: Vec<(i32, u8, Vec<NewSubmissionQuestion>)> = fixed
+        .iter()
+        .map(|q| {
+            let question = NewSubmissionQuestion {
+                quiz_question_id: Some(q.question_id),
+                version_id: None,
+                rule_id: None,
+                points: q.points,
+                option_order: option_order(quiz, q.question_type, &q.options, rng),
+            };
+            (q.sort_order, 0, vec![question])
+        })
+        .collect();
+
+    let mut draw_order: Vec<&(QuizDrawRule, Vec<BankQuestion>)> = draws.iter().collect();
+    draw_order.sort_by_key(|(_, candidates)| candidates.len());
+
+    let mut picked: HashSet<Uuid> = HashSet::new();
+    for (rule, candidates) in draw_order {
+        let count = rule.question_count.max(0) as usize;
+        let available: Vec<&BankQuestion> = candidates
+            .iter()
+            .filter(|c| !picked.contains(&c.bank_question_id))
+            .collect();
+
+        if available.len() < count {
+            return Err(format!(
+                "Draw rule {} needs {} questions but only {} are available",
+                rule.rule_id,
+                count,
+                available.len()
+            ));
+        }
+
+        let questions = available
+            .choose_multiple(rng, count)
+            .map(|c| {
+                picked.insert(c.bank_question_id);
+                NewSubmissionQuestion {
+                    quiz_question_id: None,
+                    version_id: Some(c.version_id),
+                    rule_id: Some(rule.rule_id),
+                    points: rule.points_per_question,
+                    option_order: option_order(quiz, c.question_type, &c.options, rng),
+                }
+            })
+            .collect();
+
+        slots.push((rule.sort_order, 1, questions));
+    }
+
+    slots.sort_by_key(|(sort_order, rank, _)| (*sort_order, *rank));
+    let mut questions: Vec<NewSubmissionQuestion> = slots.into_iter().flat_map(|(_, _, q)| q).collect();
+
+    if quiz.shuffle_questions {
+        questions.shuffle(rng);
+    }
+
+    Ok(questions)
+}
+
+/// Random display order for the options of a choice question.
+fn option_order<R: Rng + ?Sized>(
+    quiz: &Quiz,
+    question_type: QuestionType,
+    options: &serde_json::Value,
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    if !quiz.shuffle_options
+        || !matches!(question_type, QuestionType::SingleChoice | QuestionType::MultipleChoice)
+    {
+        return None;
+    }
+
+    let count = options.as_array().map(Vec::len).unwrap_or(0);
+    if count < 2 {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..count).collect();
+    order.shuffle(rng);
+    Some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use serde_json::json;
+
+    use super::*;
+    use crate::domain::Difficulty;
+
+    fn quiz(shuffle_questions: bool, shuffle_options: bool) -> Quiz {
+        Quiz {
+            quiz_id: Uuid::new_v4(),
+            course_id: Uuid::new_v4(),
+            lesson_id: None,
+            title: "Quiz".into(),
+            description: None,
+            instructions: None,
+            total_points: 100,
+            passing_score_percentage: 70.0,
+            time_limit_minutes: None,
+            max_attempts: None,
+            shuffle_questions,
+            shuffle_options,
+            show_correct_answers: true,
+            is_published: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn fixed(sort_order: i32) -> QuizQuestion {
+        QuizQuestion {
+            question_id: Uuid::new_v4(),
+            quiz_id: Uuid::new_v4(),
+            question_text: "Fixed".into(),
+            question_type: QuestionType::SingleChoice,
+            points: 10,
+            sort_order,
+            explanation: None,
+            options: json!([{"id": "a"}, {"id": "b"}, {"id": "c"}]),
+            correct_answers: json!(["a"]),
+            code_language: None,
+            test_cases: json!([]),
+            scoring_policy: json!({}),
+            rubric: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn bank_question(difficulty: Difficulty) -> BankQuestion {
+        BankQuestion {
+            bank_question_id: Uuid::new_v4(),
+            bank_id: Uuid::new_v4(),
+            tags: vec!["algebra".into()],
+            difficulty,
+            version: 1,
+            version_id: Uuid::new_v4(),
+            question_type: QuestionType::MultipleChoice,
+            question_text: "Drawn".into(),
+            options: json!([{"id": "a"}, {"id": "b"}, {"id": "c"}, {"id": "d"}]),
+            correct_answers: json!(["a", "b"]),
+            points: 1,
+            explanation: None,
+            code_language: None,
+            test_cases: json!([]),
+            scoring_policy: json!({}),
+            rubric: None,
+            is_archived: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn rule(question_count: i32, sort_order: i32) -> QuizDrawRule {
+        QuizDrawRule {
+            rule_id: Uuid::new_v4(),
+            quiz_id: Uuid::new_v4(),
+            bank_id: Uuid::new_v4(),
+            question_count,
+            difficulty: None,
+            tags: Vec::new(),
+            points_per_question: 5,
+            sort_order,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_layout_follows_sort_order() {
+        let fixed = [fixed(0), fixed(2)];
+        let pool: Vec<BankQuestion> = (0..5).map(|_| bank_question(Difficulty::Medium)).collect();
+        let draws = [(rule(3, 1), pool.clone())];
+
+        let questions = assemble(&quiz(false, false), &fixed, &draws, &mut StdRng::seed_from_u64(7)).unwrap();
+
+        assert_eq!(questions.len(), 5);
+        assert_eq!(questions[0].quiz_question_id, Some(fixed[0].question_id));
+        assert!(questions[1..4].iter().all(|q| q.version_id.is_some() && q.points == 5));
+        assert_eq!(questions[4].quiz_question_id, Some(fixed[1].question_id));
+        assert!(questions.iter().all(|q| q.option_order.is_none()));
+
+        let drawn: HashSet<Uuid> = questions.iter().filter_map(|q| q.version_id).collect();
+        assert_eq!(drawn.len(), 3);
+    }
+
+    #[test]
+    fn test_overlapping_rules_never_repeat_questions() {
+        let hard = bank_question(Difficulty::Hard);
+        let mut any: Vec<BankQuestion> = (0..3).map(|_| bank_question(Difficulty::Easy)).collect();
+        any.push(hard.clone());
+
+        // The broad rule must leave the only hard question to the narrow one
+        let draws = [(rule(3, 0), any), (rule(1, 1), vec![hard.clone()])];
+
+        for seed in 0..20 {
+            let questions = assemble(&quiz(false, false), &[], &draws, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let ids: HashSet<Uuid> = questions.iter().filter_map(|q| q.version_id).collect();
+            assert_eq!(ids.len(), 4);
+            assert_eq!(questions[3].version_id, Some(hard.version_id));
+        }
+    }
+
+    #[test]
+    fn test_not_enough_candidates() {
+        let draws = [(rule(3, 0), vec![bank_question(Difficulty::Easy)])];
+        let err = assemble(&quiz(false, false), &[], &draws, &mut StdRng::seed_from_u64(1)).unwrap_err();
+        assert!(err.contains("only 1"));
+    }
+
+    #[test]
+    fn test_shuffled_options_are_permutations() {
+        let fixed = [fixed(0)];
+        let draws = [(rule(1, 1), vec![bank_question(Difficulty::Easy)])];
+
+        let questions = assemble(&quiz(true, true), &fixed, &draws, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        for question in &questions {
+            let mut order = question.option_order.clone().unwrap();
+            order.sort_unstable();
+            assert_eq!(order, (0..order.len()).collect::<Vec<_>>());
+        }
+    }
+}
@@ -7,19 +7,34 @@
 //! - Quiz CRUD with authorization
 //! - Question management
 //! - Submission lifecycle (start, answer, submit)
+//! - Server-enforced time and attempt limits with accommodations
+//! - Randomized quizzes drawn from question banks
 //! - Auto-grading for objective questions
+//! - Code questions graded against hidden test cases
+//! - Per-question scoring policies and regrading
+//! - Rubric grading of essay questions
+//! - QTI 2.1 / GIFT import and QTI 2.1 export
 //! - Manual grading workflow
 
 use std::sync::Arc;
+use chrono::Utc;
+use shared::service_client::ServiceClient;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::domain::{
-    NewQuiz, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
-    Quiz, QuizQuestion, QuestionType, QuizResponse, QuizSubmission,
+    AssignedQuestion, BankQuestion, CodeTestCase, NewQuiz, NewQuizAccommodation, NewQuizDrawRule, QuizDrawRule, NewQuizQuestion, NewQuizResponse, NewQuizSubmission,
+    Quiz, QuizAccommodation, QuizQuestion, QuestionType, QuizResponse, QuizSubmission, RubricScore, RubricSelection, ScoringPolicy,
     QuizWithQuestions, SubmissionStatus, SubmissionWithResponses,
     UpdateQuiz, UpdateQuizQuestion, UpdateQuizSubmission,
 };
-use crate::repository::{AssessmentRepository, QuizStats};
+use crate::grading::rubric::{self, CriterionStats};
+use crate::grading::scoring::{self, round_points};
+use crate::grading::CodeGrader;
+use crate::interchange::{self, qti, InterchangeFormat, InterchangeIssue, PortableQuestion};
+use crate::repository::{AssessmentRepository, QuestionBankRepository, QuestionStats, QuizStats};
+
+use super::assembly;
 
 /// Assessment service errors.
 #[derive(Debug, thiserror::Error)]
@@ -30,9 +45,21 @@ pub enum AssessmentError {
     #[error("Question not found")]
     QuestionNotFound,
 
+    #[error("Question bank not found")]
+    BankNotFound,
+
+    #[error("Bank question not found")]
+    BankQuestionNotFound,
+
+    #[error("Draw rule not found")]
+    DrawRuleNotFound,
+
     #[error("Submission not found")]
     SubmissionNotFound,
 
+    #[error("Response not found")]
+    ResponseNotFound,
+
     #[error("Quiz is not published")]
     QuizNotPublished,
 
@@ -51,6 +78,12 @@ pub enum AssessmentError {
     #[error("Time limit exceeded")]
     TimeLimitExceeded,
 
+    #[error("No active enrollment in this course")]
+    NotEnrolled,
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
@@ -61,16 +94,59 @@ pub enum AssessmentError {
 /// Result type for assessment operations.
 pub type AssessmentResult<T> = Result<T, AssessmentError>;
 
+/// Outcome of regrading a quiz.
+#[derive(Debug, Clone, Default)]
+pub struct RegradeSummary {
+    /// Responses whose points changed
+    pub responses_changed: usize,
+    /// Graded submissions whose score changed
+    pub submissions_changed: usize,
+}
+
+/// Rubric statistics of one question of a quiz.
+#[derive(Debug, Clone)]
+pub struct QuestionRubricStats {
+    pub question_id: Uuid,
+    /// Responses graded with the rubric
+    pub graded_count: i64,
+    pub criteria: Vec<CriterionStats>,
+}
+
+/// Outcome of importing questions from another LMS.
+#[derive(Debug, Clone)]
+pub struct ImportSummary {
+    /// Quiz the questions were added to
+    pub quiz: Quiz,
+    /// Questions created
+    pub questions: Vec<QuizQuestion>,
+    /// Items that could not be mapped or failed validation
+    pub skipped: Vec<InterchangeIssue>,
+    /// Items imported with some loss of fidelity
+    pub warnings: Vec<InterchangeIssue>,
+}
+
 /// Service for assessment business logic.
 #[derive(Debug, Clone)]
 pub struct AssessmentService {
     repository: Arc<AssessmentRepository>,
+    banks: Arc<QuestionBankRepository>,
+    code_grader: CodeGrader,
+    /// Enrollment lookups in enrollments-service
+    services: ServiceClient,
+    /// Seconds accepted after a deadline to absorb network latency
+    grace_period_seconds: i64,
 }
 
 impl AssessmentService {
     /// Creates a new assessment service.
-    pub fn new(repository: Arc<AssessmentRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<AssessmentRepository>,
+        banks: Arc<QuestionBankRepository>,
+        code_grader: CodeGrader,
+        services: ServiceClient,
+        grace_period_seconds: i64,
+    ) -> Self {
+        Self { repository, banks, code_grader, services, grace_period_seconds }
     }
 
     // =========================================================================
@@ -110,10 +186,11 @@ impl AssessmentService {
         let quiz = self.get_quiz(quiz_id).await?;
         let mut questions = self.repository.list_questions_by_quiz(quiz_id).await?;
 
-        // Hide correct answers for students
+        // Hide correct answers and test cases for students
         if !include_answers {
             for q in &mut questions {
                 q.correct_answers = serde_json::json!([]);
+                q.test_cases = serde_json::json!([]);
             }
         }
 
@@ -159,14 +236,20 @@ impl AssessmentService {
 
         // Validate quiz has questions
         let questions = self.repository.list_questions_by_quiz(quiz_id).await?;
-        if questions.is_empty() {
+        let draws = self.load_draws(quiz_id).await?;
+        if questions.is_empty() && draws.is_empty() {
             return Err(AssessmentError::Validation(
                 "Cannot publish quiz without questions".into()
             ));
         }
 
+        // Validate every draw rule can be satisfied
+        assembly::assemble(&quiz, &questions, &draws, &mut rand::thread_rng())
+            .map_err(AssessmentError::Validation)?;
+
         // Validate total points match
-        let question_points: i32 = questions.iter().map(|q| q.points).sum();
+        let question_points: i32 = questions.iter().map(|q| q.points).sum::<i32>()
+            + draws.iter().map(|(r, _)| r.question_count * r.points_per_question).sum::<i32>();
         if question_points != quiz.total_points {
             return Err(AssessmentError::Validation(
                 format!(
@@ -221,6 +304,10 @@ impl AssessmentService {
             }
         }
 
+        validate_code_question(&self.code_grader, data.question_type, data.code_language.as_deref(), &data.test_cases)?;
+        validate_scoring(data.question_type, &data.scoring_policy, &data.correct_answers)?;
+        validate_rubric(data.question_type, data.rubric.as_ref())?;
+
         self.repository.create_question(data).await.map_err(Into::into)
     }
 
@@ -232,19 +319,50 @@ impl AssessmentService {
     ) -> AssessmentResult<QuizQuestion> {
         let question = self.get_question(question_id).await?;
 
-        // Check if parent quiz has submissions
+        // Only the answer key and scoring may change once students submitted
         let quiz = self.get_quiz(question.quiz_id).await?;
+        let mut has_submissions = false;
         if quiz.is_published {
             let stats = self.repository.get_quiz_stats(quiz.quiz_id).await?;
-            if stats.total_submissions > 0 {
+            has_submissions = stats.total_submissions > 0;
+            if has_submissions && !data.only_changes_answer_key() {
                 return Err(AssessmentError::QuizHasSubmissions);
             }
         }
 
-        self.repository
-            .update_question(question_id, data)
-            .await
-            .map_err(Into::into)
+        let language = match &data.code_language {
+            Some(language) => language.as_deref(),
+            None => question.code_language.as_deref(),
+        };
+        validate_code_question(
+            &self.code_grader,
+            data.question_type.unwrap_or(question.question_type),
+            language,
+            data.test_cases.as_ref().unwrap_or(&question.test_cases),
+        )?;
+        validate_scoring(
+            data.question_type.unwrap_or(question.question_type),
+            data.scoring_policy.as_ref().unwrap_or(&question.scoring_policy),
+            data.correct_answers.as_ref().unwrap_or(&question.correct_answers),
+        )?;
+        validate_rubric(
+            data.question_type.unwrap_or(question.question_type),
+            match &data.rubric {
+                Some(rubric) => rubric.as_ref(),
+                None => question.rubric.as_ref(),
+            },
+        )?;
+
+        let regrade = has_submissions
+            && (data.correct_answers.is_some() || data.scoring_policy.is_some());
+
+        let updated = self.repository.update_question(question_id, data).await?;
+
+        if regrade {
+            self.regrade_quiz(quiz.quiz_id).await?;
+        }
+
+        Ok(updated)
     }
 
     /// Removes a question from a quiz.
@@ -263,11 +381,83 @@ impl AssessmentService {
         self.repository.delete_question(question_id).await.map_err(Into::into)
     }
 
+    // =========================================================================
+    // DRAW RULE OPERATIONS
+    // =========================================================================
+
+    /// Lists the draw rules of a quiz.
+    pub async fn list_draw_rules(&self, quiz_id: Uuid) -> AssessmentResult<Vec<QuizDrawRule>> {
+        self.get_quiz(quiz_id).await?;
+        self.banks.list_rules(quiz_id).await.map_err(Into::into)
+    }
+
+    /// Adds a rule drawing random bank questions into each attempt.
+    pub async fn add_draw_rule(&self, data: NewQuizDrawRule) -> AssessmentResult<QuizDrawRule> {
+        let quiz = self.get_quiz(data.quiz_id).await?;
+        self.ensure_no_submissions(&quiz).await?;
+
+        let bank = self.banks
+            .find_bank(data.bank_id)
+            .await?
+            .ok_or(AssessmentError::BankNotFound)?;
+        if bank.course_id != quiz.course_id {
+            return Err(AssessmentError::Validation("Question bank belongs to another course".into()));
+        }
+        if data.question_count < 1 || data.points_per_question < 1 {
+            return Err(AssessmentError::Validation(
+                "question_count and points_per_question must be positive".into()
+            ));
+        }
+
+        self.banks.create_rule(data).await.map_err(Into::into)
+    }
+
+    /// Removes a draw rule.
+    pub async fn remove_draw_rule(&self, rule_id: Uuid) -> AssessmentResult<bool> {
+        let rule = self.banks
+            .find_rule(rule_id)
+            .await?
+            .ok_or(AssessmentError::DrawRuleNotFound)?;
+        let quiz = self.get_quiz(rule.quiz_id).await?;
+        self.ensure_no_submissions(&quiz).await?;
+
+        self.banks.delete_rule(rule_id).await.map_err(Into::into)
+    }
+
+    /// Loads the draw rules of a quiz with their candidate questions.
+    async fn load_draws(&self, quiz_id: Uuid) -> AssessmentResult<Vec<(QuizDrawRule, Vec<BankQuestion>)>> {
+        let rules = self.banks.list_rules(quiz_id).await?;
+
+        let mut draws = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let candidates = self.banks.list_candidates(&rule).await?;
+            draws.push((rule, candidates));
+        }
+
+        Ok(draws)
+    }
+
+    /// Rejects structural changes to a published quiz with submissions.
+    async fn ensure_no_submissions(&self, quiz: &Quiz) -> AssessmentResult<()> {
+        if quiz.is_published {
+            let stats = self.repository.get_quiz_stats(quiz.quiz_id).await?;
+            if stats.total_submissions > 0 {
+                return Err(AssessmentError::QuizHasSubmissions);
+            }
+        }
+        Ok(())
+    }
+
     // =========================================================================
     // SUBMISSION OPERATIONS
     // =========================================================================
 
     /// Starts a quiz attempt.
+    ///
+    /// Resumes the student's in-progress attempt if it has not expired. New
+    /// attempts require `enrollment_id` to be the student's active enrollment
+    /// in the quiz's course. The deadline and attempt limit include the
+    /// student's accommodation.
     pub async fn start_quiz(
         &self,
         quiz_id: Uuid,
@@ -281,24 +471,59 @@ impl AssessmentService {
             return Err(AssessmentError::QuizNotPublished);
         }
 
-        // Check max attempts
-        if let Some(max) = quiz.max_attempts {
-            let attempts = self.repository.get_attempt_count(user_id, quiz_id).await?;
-            if attempts >= max {
-                return Err(AssessmentError::MaxAttemptsExceeded);
+        if let Some(current) = self.repository.find_in_progress_submission(user_id, quiz_id).await? {
+            if !current.is_expired(Utc::now(), self.grace_period_seconds) {
+                return Ok(current);
             }
+            self.finalize_submission(&current, true).await?;
         }
 
-        // TODO: Validate enrollment is active
+        self.ensure_enrolled(user_id, quiz.course_id, enrollment_id).await?;
+
+        let accommodation = self.repository
+            .find_accommodation(user_id, quiz.course_id, quiz_id)
+            .await?;
+
+        // Pick this attempt's questions and option order
+        let fixed = self.repository.list_questions_by_quiz(quiz_id).await?;
+        let draws = self.load_draws(quiz_id).await?;
+        let questions = assembly::assemble(&quiz, &fixed, &draws, &mut rand::thread_rng())
+            .map_err(AssessmentError::Validation)?;
 
         let data = NewQuizSubmission {
             quiz_id,
             user_id,
             enrollment_id,
-            max_score: quiz.total_points as f64,
+            max_score: questions.iter().map(|q| q.points).sum::<i32>() as f64,
+            time_allowed_seconds: quiz.time_allowed_seconds(accommodation.as_ref()),
         };
 
-        self.repository.create_submission(data).await.map_err(Into::into)
+        self.repository
+            .create_submission(data, quiz.attempt_limit(accommodation.as_ref()), &questions)
+            .await?
+            .ok_or(AssessmentError::MaxAttemptsExceeded)
+    }
+
+    /// Checks that `enrollment_id` is the user's enrollment in the course and
+    /// grants access. Fails closed when enrollments-service cannot answer.
+    async fn ensure_enrolled(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+        enrollment_id: Uuid,
+    ) -> AssessmentResult<()> {
+        let access = self.services
+            .enrollment_access(user_id, course_id)
+            .await
+            .map_err(|e| {
+                error!(%user_id, %course_id, error = %e, "Enrollment check failed");
+                AssessmentError::ServiceUnavailable(e.service().to_string())
+            })?;
+
+        if !access.has_access || access.enrollment_id != Some(enrollment_id) {
+            return Err(AssessmentError::NotEnrolled);
+        }
+        Ok(())
     }
 
     /// Gets a submission by ID.
@@ -346,6 +571,59 @@ impl AssessmentService {
             .map_err(Into::into)
     }
 
+    /// Gets the questions of an attempt in the order shown to the student.
+    ///
+    /// Students only see answer keys and explanations of completed attempts
+    /// when the quiz shows correct answers; test cases stay hidden.
+    pub async fn get_attempt_questions(
+        &self,
+        submission_id: Uuid,
+        user_id: Uuid,
+        is_instructor: bool,
+    ) -> AssessmentResult<Vec<AssignedQuestion>> {
+        let submission = self.get_submission(submission_id, user_id, is_instructor).await?;
+
+        let mut questions = self.repository.list_submission_questions(submission_id).await?;
+        if questions.is_empty() {
+            // Attempts started before questions were recorded per attempt
+            questions = self.repository
+                .list_questions_by_quiz(submission.quiz_id)
+                .await?
+                .into_iter()
+                .map(|question| AssignedQuestion { question, version_id: None, option_order: None })
+                .collect();
+        }
+
+        if !is_instructor {
+            let quiz = self.get_quiz(submission.quiz_id).await?;
+            let reveal = submission.status != SubmissionStatus::InProgress && quiz.show_correct_answers;
+            for q in &mut questions {
+                if !reveal {
+                    q.question.correct_answers = serde_json::json!([]);
+                    q.question.scoring_policy = serde_json::json!({});
+                    q.question.explanation = None;
+                }
+                q.question.test_cases = serde_json::json!([]);
+            }
+        }
+
+        Ok(questions)
+    }
+
+    /// Questions to grade an attempt against: those it received, or the
+    /// quiz's current questions for attempts without a recorded list.
+    async fn questions_for_submission(&self, submission: &QuizSubmission) -> AssessmentResult<Vec<QuizQuestion>> {
+        let assigned = self.repository.list_submission_questions(submission.submission_id).await?;
+        if assigned.is_empty() {
+            return self.repository
+                .list_questions_by_quiz(submission.quiz_id)
+                .await
+                .map_err(Into::into);
+        }
+
+        Ok(assigned.into_iter().map(|a| a.question).collect())
+    }
+
     /// Saves an answer for a question.
     pub async fn save_answer(
         &self,
@@ -368,7 +646,14 @@ impl AssessmentService {
             return Err(AssessmentError::SubmissionAlreadyCompleted);
         }
 
-        // TODO: Check time limit
+        if submission.is_expired(Utc::now(), self.grace_period_seconds) {
+            return Err(AssessmentError::TimeLimitExceeded);
+        }
+
+        let questions = self.questions_for_submission(&submission).await?;
+        if !questions.iter().any(|q| q.question_id == question_id) {
+            return Err(AssessmentError::QuestionNotFound);
+        }
 
         let data = NewQuizResponse {
             submission_id,
@@ -380,11 +665,13 @@ impl AssessmentService {
     }
 
     /// Submits a quiz attempt for grading.
+    ///
+    /// Time spent is measured by the server and capped at the deadline, so a
+    /// late submission only keeps the answers saved in time.
     pub async fn submit_quiz(
         &self,
         submission_id: Uuid,
         user_id: Uuid,
-        time_spent_seconds: i32,
     ) -> AssessmentResult<QuizSubmission> {
         // Verify submission
         let submission = self.repository
@@ -400,13 +687,50 @@ impl AssessmentService {
             return Err(AssessmentError::SubmissionAlreadyCompleted);
         }
 
-        // Submit the attempt
-        let submitted = self.repository
-            .submit_quiz(submission_id, time_spent_seconds)
+        let expired = submission.is_expired(Utc::now(), self.grace_period_seconds);
+        self.finalize_submission(&submission, expired).await
+    }
+
+    /// Submits every expired in-progress attempt (up to `limit`).
+    ///
+    /// Called periodically by the expiry sweeper. Returns how many attempts
+    /// were submitted.
+    pub async fn submit_expired_submissions(&self, limit: i64) -> AssessmentResult<usize> {
+        let expired = self.repository
+            .list_expired_submissions(self.grace_period_seconds, limit)
             .await?;
 
+        let mut submitted = 0;
+        for submission in &expired {
+            match self.finalize_submission(submission, true).await {
+                Ok(_) => submitted += 1,
+                Err(AssessmentError::SubmissionAlreadyCompleted) => {}
+                Err(e) => warn!("Failed to auto-submit submission {}: {}", submission.submission_id, e),
+            }
+        }
+
+        if submitted > 0 {
+            info!("Auto-submitted {} expired quiz attempt(s)", submitted);
+        }
+
+        Ok(submitted)
+    }
+
+    /// Marks an in-progress attempt as submitted and auto-grades it.
+    async fn finalize_submission(
+        &self,
+        submission: &QuizSubmission,
+        auto_submitted: bool,
+    ) -> AssessmentResult<QuizSubmission> {
+        let time_spent = submission.elapsed_seconds(Utc::now());
+
+        let submitted = self.repository
+            .submit_quiz(submission.submission_id, time_spent, auto_submitted)
+            .await?
+            .ok_or(AssessmentError::SubmissionAlreadyCompleted)?;
+
         // Auto-grade if possible
-        let graded = self.auto_grade_submission(submission_id).await?;
+        let graded = self.auto_grade_submission(submission.submission_id).await?;
 
         Ok(graded.unwrap_or(submitted))
     }
@@ -422,7 +746,7 @@ impl AssessmentService {
             .ok_or(AssessmentError::SubmissionNotFound)?;
 
         let quiz = self.get_quiz(submission.quiz_id).await?;
-        let questions = self.repository.list_questions_by_quiz(quiz.quiz_id).await?;
+        let questions = self.questions_for_submission(&submission).await?;
         let responses = self.repository.list_responses_by_submission(submission_id).await?;
 
         let mut total_score = 0.0;
@@ -438,21 +762,45 @@ impl AssessmentService {
                     continue;
                 }
 
-                // Auto-grade based on question type
-                let (is_correct, points) = self.auto_grade_response(q, &response.answer_data);
+                if q.question_type == QuestionType::Code {
+                    match self.code_grader.grade(q, &response.answer_data).await {
+                        Ok(grade) => {
+                            self.repository.grade_code_response(
+                                response.response_id,
+                                grade.is_correct,
+                                grade.points,
+                                serde_json::to_value(&grade.results).unwrap_or_default(),
+                            ).await?;
+
+                            total_score += grade.points;
+                        }
+                        Err(e) => {
+                            // Sandbox failures fall back to manual grading
+                            warn!("Code grading failed for response {}: {}", response.response_id, e);
+                            needs_manual_grading = true;
+                        }
+                    }
+                    continue;
+                }
+
+                // Auto-grade with the question's scoring policy
+                let score = scoring::score_response(q, &response.answer_data);
 
                 self.repository.grade_response(
                     response.response_id,
-                    is_correct,
-                    points,
+                    score.is_correct,
+                    score.points,
                     None,
                     true, // auto_graded
                 ).await?;
 
-                total_score += points;
+                total_score += score.points;
             }
         }
 
+        // Penalties never make the whole submission negative
+        let total_score = round_points(total_score.max(0.0));
+
         // If all questions auto-graded, mark submission as graded
         if !needs_manual_grading {
             let passed = (total_score / submission.max_score) * 100.0 >= quiz.passing_score_percentage;
@@ -467,49 +815,67 @@ impl AssessmentService {
         Ok(None)
     }
 
-    /// Auto-grades a single response based on question type (internal helper).
-    fn auto_grade_response(&self, question: &QuizQuestion, answer: &serde_json::Value) -> (bool, f64) {
-        let correct_answers = &question.correct_answers;
+    /// Regrades the auto-graded responses of a quiz's completed submissions
+    /// with the current answer key and scoring policies.
+    ///
+    /// Code and manually graded responses keep their points. The score of a
+    /// graded submission moves by the difference, so instructor adjustments
+    /// are preserved.
+    pub async fn regrade_quiz(&self, quiz_id: Uuid) -> AssessmentResult<RegradeSummary> {
+        let quiz = self.get_quiz(quiz_id).await?;
+        let submissions = self.repository.list_completed_submissions(quiz_id).await?;
 
-        match question.question_type {
-            QuestionType::SingleChoice | QuestionType::TrueFalse => {
-                // Compare single answer
-                let is_correct = answer == correct_answers.get(0).unwrap_or(&serde_json::json!(null));
-                let points = if is_correct { question.points as f64 } else { 0.0 };
-                (is_correct, points)
-            }
-            QuestionType::MultipleChoice => {
-                // Compare all selected answers
-                let user_answers: Vec<String> = answer.as_array()
-                    .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                    .unwrap_or_default();
-                let correct: Vec<String> = correct_answers.as_array()
-                    .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                    .unwrap_or_default();
-
-                let mut user_set: std::collections::HashSet<_> = user_answers.iter().collect();
-                let correct_set: std::collections::HashSet<_> = correct.iter().collect();
-
-                let is_correct = user_set == correct_set;
-                let points = if is_correct { question.points as f64 } else { 0.0 };
-                (is_correct, points)
-            }
-            QuestionType::ShortAnswer => {
-                // Case-insensitive comparison
-                let user_answer = answer.as_str().unwrap_or("").to_lowercase().trim().to_string();
-                let is_correct = correct_answers.as_array()
-                    .map(|arr| arr.iter().any(|a| {
-                        a.as_str().map(|s| s.to_lowercase().trim() == user_answer).unwrap_or(false)
-                    }))
-                    .unwrap_or(false);
-                let points = if is_correct { question.points as f64 } else { 0.0 };
-                (is_correct, points)
+        let mut summary = RegradeSummary::default();
+
+        for submission in submissions {
+            let questions = self.questions_for_submission(&submission).await?;
+            let responses = self.repository.list_responses_by_submission(submission.submission_id).await?;
+            let mut delta = 0.0;
+
+            for response in &responses {
+                let Some(q) = questions.iter().find(|q| q.question_id == response.question_id) else {
+                    continue;
+                };
+                if q.needs_manual_grading() || q.question_type == QuestionType::Code || !response.auto_graded {
+                    continue;
+                }
+
+                let score = scoring::score_response(q, &response.answer_data);
+                if score.points == response.points_earned && Some(score.is_correct) == response.is_correct {
+                    continue;
+                }
+
+                self.repository.grade_response(
+                    response.response_id,
+                    score.is_correct,
+                    score.points,
+                    response.instructor_feedback.clone(),
+                    true,
+                ).await?;
+
+                delta += score.points - response.points_earned;
+                summary.responses_changed += 1;
             }
-            QuestionType::Essay | QuestionType::Code => {
-                // Requires manual grading
-                (false, 0.0)
+
+            if submission.status == SubmissionStatus::Graded && delta != 0.0 {
+                let score = round_points((submission.score + delta).clamp(0.0, submission.max_score));
+                let passed = submission.max_score > 0.0
+                    && (score / submission.max_score) * 100.0 >= quiz.passing_score_percentage;
+
+                self.repository.update_submission(
+                    submission.submission_id,
+                    UpdateQuizSubmission {
+                        score: Some(score),
+                        passed: Some(Some(passed)),
+                        ..Default::default()
+                    },
+                ).await?;
+
+                summary.submissions_changed += 1;
             }
         }
+
+        Ok(summary)
     }
 
     /// Manually grades a submission (instructor).
@@ -548,6 +914,241 @@ impl AssessmentService {
             .map_err(Into::into)
     }
 
+    /// Grades an essay response by picking a level for every criterion of
+    /// the question's rubric (instructor).
+    ///
+    /// The breakdown is stored with the response and shown to the student.
+    /// The submission score is not recomputed, as with other manual grades.
+    pub async fn grade_response_with_rubric(
+        &self,
+        response_id: Uuid,
+        selections: &[RubricSelection],
+        feedback: Option<String>,
+    ) -> AssessmentResult<QuizResponse> {
+        let response = self.repository
+            .find_response_by_id(response_id)
+            .await?
+            .ok_or(AssessmentError::ResponseNotFound)?;
+        let submission = self.repository
+            .find_submission_by_id(response.submission_id)
+            .await?
+            .ok_or(AssessmentError::SubmissionNotFound)?;
+        if submission.status == SubmissionStatus::InProgress {
+            return Err(AssessmentError::Validation("Cannot grade an attempt that is still in progress".into()));
+        }
+
+        // The question as the attempt received it (bank draws included)
+        let questions = self.questions_for_submission(&submission).await?;
+        let question = questions
+            .iter()
+            .find(|q| q.question_id == response.question_id)
+            .ok_or(AssessmentError::QuestionNotFound)?;
+        let rubric = question
+            .grading_rubric()
+            .ok_or_else(|| AssessmentError::Validation("Question has no rubric".into()))?;
+
+        let grade = rubric::grade(&rubric, question.points, selections).map_err(AssessmentError::Validation)?;
+
+        self.repository
+            .grade_rubric_response(
+                response_id,
+                grade.is_correct,
+                grade.points,
+                feedback,
+                serde_json::to_value(&grade.scores).unwrap_or_default(),
+            )
+            .await
+            .map_err(Into::into)
+    }
+
+    // =========================================================================
+    // IMPORT / EXPORT
+    // =========================================================================
+
+    /// Creates an unpublished quiz from a QTI or GIFT file.
+    ///
+    /// The quiz total is the sum of the imported question points.
+    pub async fn import_quiz(
+        &self,
+        course_id: Uuid,
+        format: InterchangeFormat,
+        data: &[u8],
+        title: Option<String>,
+    ) -> AssessmentResult<ImportSummary> {
+        let parsed = interchange::parse(format, data).map_err(|e| AssessmentError::Validation(e.to_string()))?;
+        let title = title
+            .or(parsed.title)
+            .unwrap_or_else(|| "Imported quiz".to_string());
+
+        let (questions, skipped) = self.prepare_import(parsed.questions, 0, parsed.skipped)?;
+
+        let new_quiz = NewQuiz {
+            course_id,
+            lesson_id: None,
+            title,
+            description: None,
+            instructions: None,
+            total_points: Some(questions.iter().filter_map(|q| q.points).sum()),
+            passing_score_percentage: None,
+            time_limit_minutes: None,
+            max_attempts: None,
+            shuffle_questions: None,
+            shuffle_options: None,
+            show_correct_answers: None,
+        };
+
+        let (quiz, questions) = self.repository.create_quiz_with_questions(new_quiz, &questions).await?;
+
+        info!(quiz_id = %quiz.quiz_id, imported = questions.len(), skipped = skipped.len(), "Quiz imported");
+
+        Ok(ImportSummary { quiz, questions, skipped, warnings: parsed.warnings })
+    }
+
+    /// Appends questions from a QTI or GIFT file to an existing quiz.
+    ///
+    /// The quiz total is left unchanged; publishing checks it again.
+    pub async fn import_questions(
+        &self,
+        quiz_id: Uuid,
+        format: InterchangeFormat,
+        data: &[u8],
+    ) -> AssessmentResult<ImportSummary> {
+        let quiz = self.get_quiz(quiz_id).await?;
+        self.ensure_no_submissions(&quiz).await?;
+
+        let parsed = interchange::parse(format, data).map_err(|e| AssessmentError::Validation(e.to_string()))?;
+
+        let existing = self.repository.list_questions_by_quiz(quiz_id).await?;
+        let next_order = existing.iter().map(|q| q.sort_order + 1).max().unwrap_or(0);
+
+        let (questions, skipped) = self.prepare_import(parsed.questions, next_order, parsed.skipped)?;
+        let questions = self.repository.create_questions(quiz_id, &questions).await?;
+
+        info!(quiz_id = %quiz_id, imported = questions.len(), skipped = skipped.len(), "Questions imported");
+
+        Ok(ImportSummary { quiz, questions, skipped, warnings: parsed.warnings })
+    }
+
+    /// Exports a quiz's fixed questions as a QTI 2.1 package.
+    ///
+    /// Draw rules are reported as skipped: bank questions are drawn per
+    /// attempt and have no fixed place in the package.
+    pub async fn export_quiz_qti(&self, quiz_id: Uuid) -> AssessmentResult<(Quiz, qti::ExportedPackage)> {
+        let quiz = self.get_quiz(quiz_id).await?;
+        let questions = self.repository.list_questions_by_quiz(quiz_id).await?;
+        let rules = self.banks.list_rules(quiz_id).await?;
+
+        let mut package = qti::export(&quiz, &questions).map_err(|e| AssessmentError::Validation(e.to_string()))?;
+        package.skipped.extend(rules.iter().map(|rule| {
+            InterchangeIssue::new(
+                format!("rule-{}", rule.rule_id),
+                "Random draws from question banks are not exported",
+            )
+        }));
+
+        Ok((quiz, package))
+    }
+
+    /// Validates imported questions, moving invalid ones to `skipped`.
+    fn prepare_import(
+        &self,
+        imported: Vec<PortableQuestion>,
+        first_sort_order: i32,
+        mut skipped: Vec<InterchangeIssue>,
+    ) -> AssessmentResult<(Vec<NewQuizQuestion>, Vec<InterchangeIssue>)> {
+        let mut questions = Vec::with_capacity(imported.len());
+
+        for question in imported {
+            if let Err(e) = validate_scoring(question.question_type, &question.scoring_policy, &question.correct_answers) {
+                let reason = match e {
+                    AssessmentError::Validation(reason) => reason,
+                    other => other.to_string(),
+                };
+                skipped.push(InterchangeIssue::new(question.source_id, reason));
+                continue;
+            }
+
+            questions.push(NewQuizQuestion {
+                // Set by the repository on insert
+                quiz_id: Uuid::nil(),
+                question_text: question.question_text,
+                question_type: question.question_type,
+                points: Some(question.points),
+                sort_order: first_sort_order + questions.len() as i32,
+                explanation: question.explanation,
+                options: question.options,
+                correct_answers: question.correct_answers,
+                code_language: None,
+                test_cases: serde_json::json!([]),
+                scoring_policy: question.scoring_policy,
+                rubric: None,
+            });
+        }
+
+        if questions.is_empty() {
+            let reasons: Vec<String> = skipped
+                .iter()
+                .take(5)
+                .map(|s| format!("{}: {}", s.identifier, s.reason))
+                .collect();
+            return Err(AssessmentError::Validation(format!(
+                "No questions could be imported ({})",
+                if reasons.is_empty() { "file is empty".to_string() } else { reasons.join("; ") }
+            )));
+        }
+
+        Ok((questions, skipped))
+    }
+
+    // =========================================================================
+    // ACCOMMODATIONS
+    // =========================================================================
+
+    /// Lists accommodations for a course, optionally for one student.
+    pub async fn list_accommodations(
+        &self,
+        course_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> AssessmentResult<Vec<QuizAccommodation>> {
+        self.repository
+            .list_accommodations(course_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Grants extra time or attempts to a student, replacing any existing
+    /// accommodation with the same scope.
+    ///
+    /// Takes effect on the student's next attempt.
+    pub async fn set_accommodation(&self, data: NewQuizAccommodation) -> AssessmentResult<QuizAccommodation> {
+        if !(1.0..=10.0).contains(&data.time_multiplier) {
+            return Err(AssessmentError::Validation("time_multiplier must be between 1 and 10".into()));
+        }
+        if data.extra_time_minutes < 0 || data.extra_attempts < 0 {
+            return Err(AssessmentError::Validation("Extra time and attempts cannot be negative".into()));
+        }
+
+        if let Some(quiz_id) = data.quiz_id {
+            let quiz = self.get_quiz(quiz_id).await?;
+            if quiz.course_id != data.course_id {
+                return Err(AssessmentError::Validation("Quiz does not belong to this course".into()));
+            }
+        }
+
+        self.repository
+            .upsert_accommodation(data)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Removes an accommodation.
+    pub async fn remove_accommodation(&self, accommodation_id: Uuid) -> AssessmentResult<bool> {
+        self.repository
+            .delete_accommodation(accommodation_id)
+            .await
+            .map_err(Into::into)
+    }
+
     // =========================================================================
     // STATISTICS
     // =========================================================================
@@ -557,8 +1158,107 @@ impl AssessmentService {
         self.repository.get_quiz_stats(quiz_id).await.map_err(Into::into)
     }
 
-    /// Gets pending grading count for a course.
-    pub async fn get_pending_grading_count(&self, course_id: Uuid) -> AssessmentResult<i64> {
-        self.repository.get_pending_grading_count(course_id).await.map_err(Into::into)
+    /// Gets per-question statistics of a quiz.
+    pub async fn get_question_stats(&self, quiz_id: Uuid) -> AssessmentResult<Vec<QuestionStats>> {
+        self.get_quiz(quiz_id).await?;
+        self.repository.get_question_stats(quiz_id).await.map_err(Into::into)
+    }
+
+    /// Gets per-criterion statistics of the rubric-graded questions of a quiz.
+    pub async fn get_rubric_stats(&self, quiz_id: Uuid) -> AssessmentResult<Vec<QuestionRubricStats>> {
+        let rows = self.repository.list_rubric_scores(quiz_id).await?;
+
+        let mut grouped: Vec<(Uuid, Vec<Vec<RubricScore>>)> = Vec::new();
+        for (question_id, scores) in rows {
+            let Ok(scores) = serde_json::from_value::<Vec<RubricScore>>(scores) else {
+                continue;
+            };
+            match grouped.iter_mut().find(|(id, _)| *id == question_id) {
+                Some((_, breakdowns)) => breakdowns.push(scores),
+                None => grouped.push((question_id, vec![scores])),
+            }
+        }
+
+        Ok(grouped
+            .into_iter()
+            .map(|(question_id, breakdowns)| QuestionRubricStats {
+                question_id,
+                graded_count: breakdowns.len() as i64,
+                criteria: rubric::criterion_stats(&breakdowns),
+            })
+            .collect())
+    }
+}
+
+// =============================================================================
+// VALIDATION
+// =============================================================================
+
+/// Validates a scoring policy against the question type and answer key.
+pub(crate) fn validate_scoring(
+    question_type: QuestionType,
+    scoring_policy: &serde_json::Value,
+    correct_answers: &serde_json::Value,
+) -> AssessmentResult<()> {
+    let policy: ScoringPolicy = serde_json::from_value(scoring_policy.clone())
+        .map_err(|e| AssessmentError::Validation(format!("Invalid scoring policy: {}", e)))?;
+
+    scoring::validate_policy(&policy, question_type)
+        .and_then(|_| scoring::validate_answer_key(&policy, correct_answers))
+        .map_err(AssessmentError::Validation)
+}
+
+/// Validates the rubric of a question, if it has one.
+pub(crate) fn validate_rubric(
+    question_type: QuestionType,
+    rubric: Option<&serde_json::Value>,
+) -> AssessmentResult<()> {
+    match rubric {
+        Some(rubric) => rubric::validate_rubric(rubric, question_type)
+            .map(|_| ())
+            .map_err(AssessmentError::Validation),
+        None => Ok(()),
+    }
+}
+
+/// Validates the test cases of a code question.
+pub(crate) fn validate_code_question(
+    code_grader: &CodeGrader,
+    question_type: QuestionType,
+    language: Option<&str>,
+    test_cases: &serde_json::Value,
+) -> AssessmentResult<()> {
+    let cases: Vec<CodeTestCase> = serde_json::from_value(test_cases.clone())
+        .map_err(|e| AssessmentError::Validation(format!("Invalid test cases: {}", e)))?;
+    if cases.is_empty() {
+        return Ok(());
+    }
+
+    if question_type != QuestionType::Code {
+        return Err(AssessmentError::Validation(
+            "Test cases are only allowed on code questions".into()
+        ));
     }
+
+    match language {
+        Some(language) if code_grader.supports(language) => {}
+        Some(language) => {
+            return Err(AssessmentError::Validation(
+                format!("Code language '{}' cannot be auto-graded", language)
+            ));
+        }
+        None => {
+            return Err(AssessmentError::Validation(
+                "Code questions with test cases need a code language".into()
+            ));
+        }
+    }
+
+    if cases.iter().any(|c| c.weight < 0.0) {
+        return Err(AssessmentError::Validation(
+            "Test case weights cannot be negative".into()
+        ));
+    }
+
+    Ok(())
 }
@@ -0,0 +1,157 @@
+//! # Question Bank Service
+//!
+//! Business logic for reusable question banks. Bank questions are versioned:
+//! editing their content creates a new version, and attempts keep the
+//! version they were given.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    BankQuestion, BankQuestionContent, Difficulty, NewBankQuestion, NewQuestionBank,
+    QuestionBank, UpdateBankQuestion, UpdateQuestionBank,
+};
+use crate::grading::CodeGrader;
+use crate::repository::QuestionBankRepository;
+
+use super::assessment_service::{validate_code_question, validate_rubric, validate_scoring};
+use super::{AssessmentError, AssessmentResult};
+
+/// Service for question bank management.
+#[derive(Debug, Clone)]
+pub struct QuestionBankService {
+    banks: Arc<QuestionBankRepository>,
+    code_grader: CodeGrader,
+}
+
+impl QuestionBankService {
+    /// Creates a new question bank service.
+    pub fn new(banks: Arc<QuestionBankRepository>, code_grader: CodeGrader) -> Self {
+        Self { banks, code_grader }
+    }
+
+    // =========================================================================
+    // BANK OPERATIONS
+    // =========================================================================
+
+    /// Lists the banks of a course.
+    pub async fn list_banks(&self, course_id: Uuid) -> AssessmentResult<Vec<QuestionBank>> {
+        self.banks.list_banks(course_id).await.map_err(Into::into)
+    }
+
+    /// Gets a bank by ID.
+    pub async fn get_bank(&self, bank_id: Uuid) -> AssessmentResult<QuestionBank> {
+        self.banks
+            .find_bank(bank_id)
+            .await?
+            .ok_or(AssessmentError::BankNotFound)
+    }
+
+    /// Creates a bank.
+    pub async fn create_bank(&self, data: NewQuestionBank) -> AssessmentResult<QuestionBank> {
+        self.banks.create_bank(data).await.map_err(Into::into)
+    }
+
+    /// Updates a bank.
+    pub async fn update_bank(&self, bank_id: Uuid, data: UpdateQuestionBank) -> AssessmentResult<QuestionBank> {
+        self.get_bank(bank_id).await?;
+        self.banks.update_bank(bank_id, data).await.map_err(Into::into)
+    }
+
+    /// Deletes a bank that no quiz or attempt uses.
+    pub async fn delete_bank(&self, bank_id: Uuid) -> AssessmentResult<bool> {
+        self.get_bank(bank_id).await?;
+
+        if self.banks.bank_in_use(bank_id).await? {
+            return Err(AssessmentError::Validation(
+                "Bank is used by quizzes or attempts; archive its questions instead".into()
+            ));
+        }
+
+        self.banks.delete_bank(bank_id).await.map_err(Into::into)
+    }
+
+    // =========================================================================
+    // BANK QUESTION OPERATIONS
+    // =========================================================================
+
+    /// Lists the questions of a bank.
+    pub async fn list_questions(
+        &self,
+        bank_id: Uuid,
+        difficulty: Option<Difficulty>,
+        tag: Option<&str>,
+        include_archived: bool,
+    ) -> AssessmentResult<Vec<BankQuestion>> {
+        self.get_bank(bank_id).await?;
+        self.banks
+            .list_questions(bank_id, difficulty, tag, include_archived)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Gets a bank question with its current content.
+    pub async fn get_question(&self, bank_question_id: Uuid) -> AssessmentResult<BankQuestion> {
+        self.banks
+            .find_question(bank_question_id)
+            .await?
+            .ok_or(AssessmentError::BankQuestionNotFound)
+    }
+
+    /// Adds a question to a bank.
+    pub async fn add_question(&self, data: NewBankQuestion) -> AssessmentResult<BankQuestion> {
+        self.get_bank(data.bank_id).await?;
+        self.validate_content(&data.content)?;
+
+        self.banks.create_question(data).await.map_err(Into::into)
+    }
+
+    /// Updates a bank question; content changes create a new version.
+    pub async fn update_question(
+        &self,
+        bank_question_id: Uuid,
+        data: UpdateBankQuestion,
+    ) -> AssessmentResult<BankQuestion> {
+        let question = self.get_question(bank_question_id).await?;
+
+        let content = if data.changes_content() {
+            let content = data.merged_content(&question);
+            self.validate_content(&content)?;
+            Some(content)
+        } else {
+            None
+        };
+
+        self.banks
+            .update_question(bank_question_id, &data, content)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Archives or restores a bank question.
+    pub async fn set_archived(&self, bank_question_id: Uuid, archived: bool) -> AssessmentResult<BankQuestion> {
+        if !self.banks.set_archived(bank_question_id, archived).await? {
+            return Err(AssessmentError::BankQuestionNotFound);
+        }
+        self.get_question(bank_question_id).await
+    }
+
+    fn validate_content(&self, content: &BankQuestionContent) -> AssessmentResult<()> {
+        if content.question_text.trim().is_empty() {
+            return Err(AssessmentError::Validation("Question text cannot be empty".into()));
+        }
+        if content.points < 0 {
+            return Err(AssessmentError::Validation("Points cannot be negative".into()));
+        }
+
+        validate_code_question(
+            &self.code_grader,
+            content.question_type,
+            content.code_language.as_deref(),
+            &content.test_cases,
+        )?;
+        validate_scoring(content.question_type, &content.scoring_policy, &content.correct_answers)?;
+        validate_rubric(content.question_type, content.rubric.as_ref())
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! Business logic for assessments, quizzes, and grading.
 
+pub mod assembly;
 pub mod assessment_service;
+pub mod bank_service;
+pub mod sweeper;
 
-pub use assessment_service::{AssessmentError, AssessmentResult, AssessmentService};
+pub use assessment_service::{AssessmentError, AssessmentResult, AssessmentService, ImportSummary, QuestionRubricStats};
+pub use bank_service::QuestionBankService;
+pub use sweeper::{SubmissionSweeper, SweeperConfig};
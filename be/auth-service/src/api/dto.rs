@@ -21,7 +21,12 @@
 //! │  - RefreshTokenRequest             - ProfileResponse                    │
 //! │  - VerifyEmailRequest              - MessageResponse                    │
 //! │  - ForgotPasswordRequest           - HealthResponse                     │
-//! │  - ResetPasswordRequest                                                 │
+//! │  - ResetPasswordRequest            - MfaChallengeDto                    │
+//! │  - MfaVerifyRequest                - MfaEnrolledResponse                │
+//! │  - WebAuthnLoginRequest            - MfaStatusDto                       │
+//! │  - TotpConfirmRequest              - IdentityProviderDto                │
+//! │  - WebAuthnRegisterRequest         - SsoAuthorizeResponse               │
+//! │  - SsoCallbackRequest              - SessionDto                         │
 //! │                                                                         │
 //! └─────────────────────────────────────────────────────────────────────────┘
 //! ```
@@ -225,6 +230,115 @@ pub struct ResetPasswordRequest {
     pub new_password: String,
 }
 
+// =============================================================================
+// MULTI-FACTOR AUTHENTICATION
+// =============================================================================
+
+/// Request body for completing a login with a TOTP or recovery code.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///   "mfaToken": "9f86d081884c7d659a2feaa0c55ad015...",
+///   "code": "123456"
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaVerifyRequest {
+    /// Token returned by login
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+
+    /// 6-digit TOTP code or `XXXXX-XXXXX` recovery code
+    #[validate(length(min = 6, max = 16, message = "Code must be 6-16 characters"))]
+    pub code: String,
+}
+
+/// Request body for WebAuthn login options.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaTokenRequest {
+    /// Token returned by login
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+}
+
+/// Request body for completing a login with a WebAuthn assertion.
+///
+/// `credential` is the result of `navigator.credentials.get()` serialized
+/// with `PublicKeyCredential.toJSON()`.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct WebAuthnLoginRequest {
+    /// Token returned by login
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+
+    /// Assertion from the authenticator
+    pub credential: AuthenticationCredential,
+}
+
+/// Request body for enabling TOTP with the first code.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpConfirmRequest {
+    /// Code currently shown by the authenticator app
+    #[validate(length(equal = 6, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+/// Request body for registering a WebAuthn credential.
+///
+/// `credential` is the result of `navigator.credentials.create()`
+/// serialized with `PublicKeyCredential.toJSON()`.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct WebAuthnRegisterRequest {
+    /// Label for the credential ("YubiKey", "MacBook")
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    /// Attestation from the authenticator
+    pub credential: RegistrationCredential,
+}
+
+// =============================================================================
+// SINGLE SIGN-ON
+// =============================================================================
+
+/// Query of the SSO provider list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SsoProvidersQuery {
+    /// Tenant whose providers are listed, besides the global ones
+    pub tenant: Option<uuid::Uuid>,
+}
+
+/// Request body for completing an SSO sign-in.
+///
+/// Both values come from the query string of the IdP redirect.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///   "code": "4/0AX4XfWh...",
+///   "state": "kq2QnXy3W1Z..."
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoCallbackRequest {
+    /// Authorization code issued by the IdP
+    #[validate(length(min = 1, max = 2048, message = "Code is required"))]
+    pub code: String,
+
+    /// State returned by authorize
+    #[validate(length(min = 1, max = 128, message = "State is required"))]
+    pub state: String,
+}
+
 // =============================================================================
 // RESPONSE TYPES
 // =============================================================================
@@ -343,6 +457,166 @@ pub struct UserProfileDto {
     pub created_at: String,
 }
 
+/// Login response when a second factor is needed.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///   "mfaRequired": true,
+///   "mfaToken": "9f86d081884c7d659a2feaa0c55ad015...",
+///   "methods": ["totp", "webauthn", "recovery_code"],
+///   "enrollmentRequired": false,
+///   "expiresIn": 300
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaChallengeDto {
+    /// Always `true`; distinguishes this from [`AuthResponseDto`]
+    pub mfa_required: bool,
+    /// Pending login details
+    #[serde(flatten)]
+    pub challenge: MfaChallenge,
+}
+
+/// Secret of a TOTP authenticator being set up.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpSetupResponse {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub otpauth_uri: String,
+}
+
+/// Response after enrolling a factor.
+///
+/// `recoveryCodes` is only present for the user's first factor and is
+/// shown once. `session` is present when the factor was enrolled with an
+/// `X-MFA-Token` and completes that login.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaEnrolledResponse {
+    /// Registered WebAuthn credential
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential: Option<WebAuthnCredentialDto>,
+    /// Single-use recovery codes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
+    /// Tokens of the completed login
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<AuthResponseDto>,
+}
+
+/// Newly generated recovery codes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesResponse {
+    /// Single-use codes, shown once
+    pub recovery_codes: Vec<String>,
+}
+
+/// WebAuthn credential in API responses (no key material).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebAuthnCredentialDto {
+    pub credential_id: String,
+    pub name: String,
+    pub transports: Vec<String>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<String>,
+}
+
+/// Second factors of the authenticated user.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///   "totpEnabled": true,
+///   "webauthnCredentials": [],
+///   "recoveryCodesRemaining": 9,
+///   "required": true
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaStatusDto {
+    pub totp_enabled: bool,
+    pub webauthn_credentials: Vec<WebAuthnCredentialDto>,
+    pub recovery_codes_remaining: i64,
+    /// The user's role cannot log in without MFA
+    pub required: bool,
+}
+
+/// Identity provider shown on the login page.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityProviderDto {
+    pub slug: String,
+    pub display_name: String,
+}
+
+/// URL the browser is sent to for an SSO sign-in.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoAuthorizeResponse {
+    pub authorization_url: String,
+}
+
+/// Active session (signed-in device) of the user.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///   "sessionId": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+///   "ipAddress": "190.24.10.8",
+///   "userAgent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_5) ...",
+///   "location": "Bogotá, CO",
+///   "createdAt": "2024-01-10T08:00:00+00:00",
+///   "lastUsedAt": "2024-01-15T10:30:00+00:00",
+///   "expiresAt": "2024-01-22T10:30:00+00:00",
+///   "current": true
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDto {
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    pub created_at: String,
+    pub last_used_at: String,
+    pub expires_at: String,
+    /// Session of the device making the request (same fingerprint)
+    pub current: bool,
+}
+
+impl SessionDto {
+    /// Converts a session, marking it current if it was opened with
+    /// `device_fingerprint`.
+    pub fn new(session: UserSession, device_fingerprint: Option<&str>) -> Self {
+        Self {
+            session_id: session.session_id.to_string(),
+            current: device_fingerprint.is_some()
+                && session.device_fingerprint.as_deref() == device_fingerprint,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            location: session.location,
+            created_at: session.created_at.to_rfc3339(),
+            last_used_at: session.last_used_at.to_rfc3339(),
+            expires_at: session.expires_at.to_rfc3339(),
+        }
+    }
+}
+
 /// Generic message response for simple confirmations.
 ///
 /// # Example JSON
@@ -394,7 +668,54 @@ pub struct HealthResponse {
 // CONVERSIONS
 // =============================================================================
 
-use crate::domain::UserProfile;
+use crate::domain::{IdentityProvider, UserProfile, UserSession, WebAuthnCredential};
+use crate::service::auth_service::AuthResponse;
+use crate::service::mfa_service::{MfaChallenge, MfaStatus};
+use crate::service::webauthn::{AuthenticationCredential, RegistrationCredential};
+
+impl From<AuthResponse> for AuthResponseDto {
+    fn from(response: AuthResponse) -> Self {
+        Self {
+            access_token: response.tokens.access_token,
+            refresh_token: response.tokens.refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: response.tokens.expires_in,
+            user: response.user.into(),
+        }
+    }
+}
+
+impl From<WebAuthnCredential> for WebAuthnCredentialDto {
+    fn from(credential: WebAuthnCredential) -> Self {
+        Self {
+            credential_id: credential.credential_id.to_string(),
+            name: credential.name,
+            transports: credential.transports,
+            created_at: credential.created_at.to_rfc3339(),
+            last_used_at: credential.last_used_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+impl From<IdentityProvider> for IdentityProviderDto {
+    fn from(provider: IdentityProvider) -> Self {
+        Self {
+            slug: provider.slug,
+            display_name: provider.display_name,
+        }
+    }
+}
+
+impl From<MfaStatus> for MfaStatusDto {
+    fn from(status: MfaStatus) -> Self {
+        Self {
+            totp_enabled: status.totp_enabled,
+            webauthn_credentials: status.webauthn_credentials.into_iter().map(Into::into).collect(),
+            recovery_codes_remaining: status.recovery_codes_remaining,
+            required: status.required,
+        }
+    }
+}
 
 impl From<UserProfile> for UserProfileDto {
     /// Converts domain `UserProfile` to API DTO.
@@ -425,6 +746,7 @@ impl From<UserProfile> for UserProfileDto {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::service::mfa_service::MfaMethod;
     use validator::Validate;
 
     #[test]
@@ -472,6 +794,25 @@ mod tests {
         assert!(request.validate().is_ok());
     }
 
+    #[test]
+    fn test_mfa_challenge_dto_flattens_challenge() {
+        let dto = MfaChallengeDto {
+            mfa_required: true,
+            challenge: MfaChallenge {
+                mfa_token: "abc".to_string(),
+                methods: vec![MfaMethod::Totp, MfaMethod::RecoveryCode],
+                enrollment_required: false,
+                expires_in: 300,
+            },
+        };
+
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json["mfaRequired"], true);
+        assert_eq!(json["mfaToken"], "abc");
+        assert_eq!(json["methods"], serde_json::json!(["totp", "recovery_code"]));
+        assert_eq!(json["enrollmentRequired"], false);
+    }
+
     #[test]
     fn test_message_response_new() {
         let response = MessageResponse::new("Test message");
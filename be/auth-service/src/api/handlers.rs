@@ -42,17 +42,19 @@
 //! - Service: [`crate::service::AuthService`]
 //! - Errors: [`shared::errors::ApiError`]
 
-use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
 use chrono::Utc;
 use shared::{errors::ApiError, validation};
 use tracing::{info, warn};
 
+use crate::domain::ClientInfo;
+use crate::service::LoginOutcome;
 use crate::AppState;
 
 use super::dto::{
     AuthResponseDto, ForgotPasswordRequest, HealthResponse, LoginRequest, LogoutRequest,
-    MessageResponse, RefreshTokenRequest, RegisterRequest, ResetPasswordRequest,
-    TokenResponseDto, UserProfileDto, VerifyEmailRequest,
+    MessageResponse, MfaChallengeDto, RefreshTokenRequest, RegisterRequest, ResetPasswordRequest,
+    SessionDto, TokenResponseDto, UserProfileDto, VerifyEmailRequest,
 };
 
 // =============================================================================
@@ -92,6 +94,28 @@ pub async fn health_check() -> HttpResponse {
     HttpResponse::Ok().json(response)
 }
 
+// =============================================================================
+// JSON WEB KEY SET
+// =============================================================================
+
+/// Public keys other services validate access tokens with.
+///
+/// # Route
+///
+/// `GET /.well-known/jwks.json`
+///
+/// # Response
+///
+/// - **200 OK**: `{ "keys": [...] }` with every published key (`kid`),
+///   empty when tokens are signed with the HS256 secret
+///
+/// Cacheable for 5 minutes, matching the verifiers' refresh interval.
+pub async fn jwks(state: web::Data<AppState>) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "public, max-age=300"))
+        .json(state.jwt_service.jwks())
+}
+
 // =============================================================================
 // REGISTRATION
 // =============================================================================
@@ -173,7 +197,9 @@ pub async fn register(
 ///
 /// # Responses
 ///
-/// - **200 OK**: Login successful
+/// - **200 OK**: Login successful, or a second factor is needed
+///   (`MfaChallengeDto` with `mfaRequired: true`; continue with
+///   `/mfa/verify` or `/mfa/webauthn/verify`)
 /// - **400 Bad Request**: Validation failed
 /// - **401 Unauthorized**: Invalid credentials
 ///
@@ -200,45 +226,16 @@ pub async fn login(
     validation::validate_request(&body)?;
 
     // Extract device info from request
-    let device_fingerprint = req
-        .headers()
-        .get("X-Device-Fingerprint")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from);
-
-    let ip_address = req
-        .connection_info()
-        .peer_addr()
-        .map(String::from);
-
-    let user_agent = req
-        .headers()
-        .get("User-Agent")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from);
+    let client = client_info(&req);
 
     // Call service layer
-    let response = state
+    let outcome = state
         .auth_service
-        .login(
-            &body.email,
-            &body.password,
-            device_fingerprint,
-            ip_address,
-            user_agent,
-        )
+        .login(&body.email, &body.password, client)
         .await?;
 
     // Convert to DTO
-    let dto = AuthResponseDto {
-        access_token: response.tokens.access_token,
-        refresh_token: response.tokens.refresh_token,
-        token_type: "Bearer".to_string(),
-        expires_in: response.tokens.expires_in,
-        user: response.user.into(),
-    };
-
-    Ok(HttpResponse::Ok().json(dto))
+    Ok(login_response(outcome))
 }
 
 // =============================================================================
@@ -267,7 +264,8 @@ pub async fn login(
 /// # Security
 ///
 /// Implements token rotation - the old refresh token is invalidated
-/// when a new pair is issued.
+/// when a new pair is issued. Reusing an invalidated token revokes the
+/// whole session.
 ///
 /// # Example
 ///
@@ -277,6 +275,7 @@ pub async fn login(
 ///   -d '{"refreshToken":"<your_refresh_token>"}'
 /// ```
 pub async fn refresh_token(
+    req: HttpRequest,
     state: web::Data<AppState>,
     body: web::Json<RefreshTokenRequest>,
 ) -> Result<HttpResponse, ApiError> {
@@ -285,7 +284,10 @@ pub async fn refresh_token(
     validation::validate_request(&body)?;
 
     // Call service layer
-    let tokens = state.auth_service.refresh_token(&body.refresh_token).await?;
+    let tokens = state
+        .auth_service
+        .refresh_token(&body.refresh_token, client_info(&req))
+        .await?;
 
     // Convert to DTO
     let dto = TokenResponseDto {
@@ -418,6 +420,74 @@ pub async fn logout_all(
     ))))
 }
 
+// =============================================================================
+// SESSIONS
+// =============================================================================
+
+/// Lists the user's active sessions (signed-in devices).
+///
+/// # Route
+///
+/// `GET /api/v1/auth/sessions`
+///
+/// # Headers
+///
+/// - `Authorization: Bearer <access_token>` (required)
+/// - `X-Device-Fingerprint` (optional): marks this device's session as `current`
+///
+/// # Responses
+///
+/// - **200 OK**: `[SessionDto]`, most recently used first
+/// - **401 Unauthorized**: Invalid or missing access token
+pub async fn list_sessions(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state
+        .jwt_service
+        .validate_access_token(&access_token)?;
+
+    let sessions = state.auth_service.list_sessions(claims.sub).await?;
+
+    let device_fingerprint = client_info(&req).device_fingerprint;
+    let dtos: Vec<SessionDto> = sessions
+        .into_iter()
+        .map(|session| SessionDto::new(session, device_fingerprint.as_deref()))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(dtos))
+}
+
+/// Signs out one device remotely.
+///
+/// # Route
+///
+/// `DELETE /api/v1/auth/sessions/{session_id}`
+///
+/// # Responses
+///
+/// - **200 OK**: Session revoked
+/// - **401 Unauthorized**: Invalid or missing access token
+/// - **404 Not Found**: No active session with that ID
+pub async fn revoke_session(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<uuid::Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state
+        .jwt_service
+        .validate_access_token(&access_token)?;
+
+    state
+        .auth_service
+        .revoke_session(claims.sub, path.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse::new("Session revoked")))
+}
+
 // =============================================================================
 // USER PROFILE
 // =============================================================================
@@ -631,6 +701,61 @@ pub async fn reset_password(
 // HELPER FUNCTIONS
 // =============================================================================
 
+/// Device fingerprint, client IP, user agent and location of a request.
+///
+/// The location comes from `X-Geo-City` / `X-Geo-Country`, set by the
+/// edge proxy from its GeoIP database; it is shown to users only.
+pub(super) fn client_info(req: &HttpRequest) -> ClientInfo {
+    let device_fingerprint = req
+        .headers()
+        .get("X-Device-Fingerprint")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let ip_address = req
+        .connection_info()
+        .peer_addr()
+        .map(String::from);
+
+    let user_agent = req
+        .headers()
+        .get("User-Agent")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let location = match (header("X-Geo-City"), header("X-Geo-Country")) {
+        (Some(city), Some(country)) => Some(format!("{}, {}", city, country)),
+        (city, country) => city.or(country).map(String::from),
+    };
+
+    ClientInfo {
+        device_fingerprint,
+        ip_address,
+        user_agent,
+        location,
+    }
+}
+
+/// Tokens, or the MFA challenge when a second factor is needed.
+pub(super) fn login_response(outcome: LoginOutcome) -> HttpResponse {
+    match outcome {
+        LoginOutcome::Authenticated(response) => {
+            HttpResponse::Ok().json(AuthResponseDto::from(*response))
+        }
+        LoginOutcome::MfaRequired(challenge) => HttpResponse::Ok().json(MfaChallengeDto {
+            mfa_required: true,
+            challenge,
+        }),
+    }
+}
+
 /// Extracts Bearer token from Authorization header.
 ///
 /// # Format
@@ -643,7 +768,7 @@ pub async fn reset_password(
 ///
 /// Returns `ApiError::MissingAuth` if header is missing.
 /// Returns `ApiError::InvalidToken` if format is invalid.
-fn extract_bearer_token(req: &HttpRequest) -> Result<String, ApiError> {
+pub(super) fn extract_bearer_token(req: &HttpRequest) -> Result<String, ApiError> {
     let auth_header = req
         .headers()
         .get("Authorization")
@@ -702,6 +827,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_client_info_location() {
+        let req = TestRequest::default()
+            .insert_header(("X-Geo-City", "Medellin"))
+            .insert_header(("X-Geo-Country", "CO"))
+            .insert_header(("X-Device-Fingerprint", "fp-1"))
+            .to_http_request();
+
+        let client = client_info(&req);
+        assert_eq!(client.location.as_deref(), Some("Medellin, CO"));
+        assert_eq!(client.device_fingerprint.as_deref(), Some("fp-1"));
+
+        let req = TestRequest::default()
+            .insert_header(("X-Geo-Country", "CO"))
+            .to_http_request();
+        assert_eq!(client_info(&req).location.as_deref(), Some("CO"));
+        assert!(client_info(&TestRequest::default().to_http_request()).location.is_none());
+    }
+
     #[test]
     fn test_extract_bearer_token_empty() {
         let req = TestRequest::default()
@@ -0,0 +1,411 @@
+//! # MFA Handlers
+//!
+//! Second step of the login and enrollment of second factors.
+//!
+//! ## Authentication
+//!
+//! | Endpoints | Accepted credentials |
+//! |-----------|----------------------|
+//! | `/mfa/verify`, `/mfa/webauthn/*` (login) | `mfaToken` in the body |
+//! | TOTP setup/confirm, WebAuthn registration | `Authorization: Bearer` or `X-MFA-Token` |
+//! | Removal, recovery codes, status | `Authorization: Bearer` |
+//!
+//! `X-MFA-Token` is accepted only while the user has no factor: it lets
+//! users whose role requires MFA enroll during their first login, and the
+//! enrollment response then carries the session tokens.
+//!
+//! ## Related Documentation
+//!
+//! - Service: [`crate::service::mfa_service`]
+//! - Login: [`super::handlers::login`]
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use shared::{errors::ApiError, validation};
+use uuid::Uuid;
+
+use crate::AppState;
+
+use super::dto::{
+    AuthResponseDto, MessageResponse, MfaEnrolledResponse, MfaStatusDto, MfaTokenRequest,
+    MfaVerifyRequest, RecoveryCodesResponse, TotpConfirmRequest, TotpSetupResponse,
+    WebAuthnLoginRequest, WebAuthnRegisterRequest,
+};
+use super::handlers::extract_bearer_token;
+
+/// Header carrying the `mfaToken` on enrollment endpoints.
+const MFA_TOKEN_HEADER: &str = "X-MFA-Token";
+
+// =============================================================================
+// LOGIN (SECOND STEP)
+// =============================================================================
+
+/// Completes a login with a TOTP or recovery code.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/verify`
+///
+/// # Responses
+///
+/// - **200 OK**: `AuthResponseDto`
+/// - **401 Unauthorized**: Wrong code, or `mfaToken` unknown/expired
+///   (discarded after 5 wrong codes)
+pub async fn verify_code(
+    state: web::Data<AppState>,
+    body: web::Json<MfaVerifyRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let response = state
+        .auth_service
+        .verify_mfa_code(&body.mfa_token, &body.code)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AuthResponseDto::from(response)))
+}
+
+/// Options for `navigator.credentials.get()`.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/webauthn/challenge`
+pub async fn webauthn_challenge(
+    state: web::Data<AppState>,
+    body: web::Json<MfaTokenRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let options = state
+        .auth_service
+        .webauthn_login_options(&body.mfa_token)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(options))
+}
+
+/// Completes a login with a WebAuthn assertion.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/webauthn/verify`
+///
+/// # Responses
+///
+/// - **200 OK**: `AuthResponseDto`
+/// - **400 Bad Request**: No challenge requested for this `mfaToken`
+/// - **401 Unauthorized**: Assertion rejected, or `mfaToken` unknown/expired
+pub async fn webauthn_verify(
+    state: web::Data<AppState>,
+    body: web::Json<WebAuthnLoginRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let response = state
+        .auth_service
+        .verify_mfa_webauthn(&body.mfa_token, &body.credential)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AuthResponseDto::from(response)))
+}
+
+// =============================================================================
+// ENROLLMENT
+// =============================================================================
+
+/// Starts TOTP setup and returns the secret to show as a QR code.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/totp/setup`
+///
+/// # Responses
+///
+/// - **200 OK**: `TotpSetupResponse`
+/// - **409 Conflict**: TOTP already enabled
+pub async fn totp_setup(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let subject = enrollment_subject(&req, &state).await?;
+
+    let setup = state
+        .mfa_service
+        .setup_totp(subject.user_id, &subject.email)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(TotpSetupResponse {
+        secret: setup.secret,
+        otpauth_uri: setup.otpauth_uri,
+    }))
+}
+
+/// Enables TOTP with the first code from the authenticator app.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/totp/confirm`
+///
+/// # Responses
+///
+/// - **200 OK**: `MfaEnrolledResponse`
+/// - **400 Bad Request**: Wrong code or setup not started
+pub async fn totp_confirm(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<TotpConfirmRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let subject = enrollment_subject(&req, &state).await?;
+
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let recovery_codes = state
+        .mfa_service
+        .confirm_totp(subject.user_id, &body.code)
+        .await?;
+
+    let session = complete_login(&state, &subject).await?;
+
+    Ok(HttpResponse::Ok().json(MfaEnrolledResponse {
+        credential: None,
+        recovery_codes,
+        session,
+    }))
+}
+
+/// Disables TOTP.
+///
+/// # Route
+///
+/// `DELETE /api/v1/auth/mfa/totp`
+///
+/// # Responses
+///
+/// - **200 OK**: TOTP disabled
+/// - **400 Bad Request**: Last factor of a role that requires MFA
+/// - **404 Not Found**: TOTP not set up
+pub async fn totp_disable(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state.jwt_service.validate_access_token(&access_token)?;
+
+    state
+        .mfa_service
+        .disable_totp(claims.sub, &claims.role)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse::new("TOTP disabled")))
+}
+
+/// Options for `navigator.credentials.create()`.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/webauthn/register/options`
+pub async fn webauthn_register_options(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let subject = enrollment_subject(&req, &state).await?;
+
+    let options = state
+        .mfa_service
+        .webauthn_register_options(subject.user_id, &subject.email)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(options))
+}
+
+/// Registers the credential created by the browser.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/webauthn/register`
+///
+/// # Responses
+///
+/// - **201 Created**: `MfaEnrolledResponse`
+/// - **400 Bad Request**: Attestation rejected or no options requested
+/// - **409 Conflict**: Credential already registered
+pub async fn webauthn_register(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    body: web::Json<WebAuthnRegisterRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let subject = enrollment_subject(&req, &state).await?;
+
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let (credential, recovery_codes) = state
+        .mfa_service
+        .register_webauthn(subject.user_id, &body.name, &body.credential)
+        .await?;
+
+    let session = complete_login(&state, &subject).await?;
+
+    Ok(HttpResponse::Created().json(MfaEnrolledResponse {
+        credential: Some(credential.into()),
+        recovery_codes,
+        session,
+    }))
+}
+
+/// Removes a WebAuthn credential.
+///
+/// # Route
+///
+/// `DELETE /api/v1/auth/mfa/webauthn/{credential_id}`
+pub async fn webauthn_delete(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state.jwt_service.validate_access_token(&access_token)?;
+
+    state
+        .mfa_service
+        .delete_webauthn(claims.sub, &claims.role, path.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse::new("WebAuthn credential removed")))
+}
+
+/// Replaces the user's recovery codes.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/mfa/recovery-codes`
+pub async fn regenerate_recovery_codes(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state.jwt_service.validate_access_token(&access_token)?;
+
+    let recovery_codes = state
+        .mfa_service
+        .regenerate_recovery_codes(claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// Second factors of the authenticated user.
+///
+/// # Route
+///
+/// `GET /api/v1/auth/mfa`
+pub async fn status(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = extract_bearer_token(&req)?;
+    let claims = state.jwt_service.validate_access_token(&access_token)?;
+
+    let status = state.mfa_service.status(claims.sub, &claims.role).await?;
+
+    Ok(HttpResponse::Ok().json(MfaStatusDto::from(status)))
+}
+
+// =============================================================================
+// HELPER FUNCTIONS
+// =============================================================================
+
+/// User enrolling a factor.
+struct EnrollmentSubject {
+    user_id: Uuid,
+    email: String,
+    /// Set when enrolling during login with `X-MFA-Token`
+    mfa_token: Option<String>,
+}
+
+/// Resolves the user from `X-MFA-Token` or the access token.
+///
+/// # Errors
+///
+/// - `ApiError::InsufficientPermissions` if `X-MFA-Token` is used by a
+///   user who already has a factor (they must verify it instead)
+async fn enrollment_subject(req: &HttpRequest, state: &AppState) -> Result<EnrollmentSubject, ApiError> {
+    if let Some(mfa_token) = mfa_token_header(req)? {
+        let pending = state.mfa_service.pending_login(&mfa_token).await?;
+
+        if state.mfa_service.has_factors(pending.user_id).await? {
+            return Err(ApiError::InsufficientPermissions);
+        }
+
+        return Ok(EnrollmentSubject {
+            user_id: pending.user_id,
+            email: pending.email,
+            mfa_token: Some(mfa_token),
+        });
+    }
+
+    let access_token = extract_bearer_token(req)?;
+    let claims = state.jwt_service.validate_access_token(&access_token)?;
+
+    Ok(EnrollmentSubject {
+        user_id: claims.sub,
+        email: claims.email,
+        mfa_token: None,
+    })
+}
+
+/// Finishes the login of a user who enrolled with `X-MFA-Token`.
+async fn complete_login(
+    state: &AppState,
+    subject: &EnrollmentSubject,
+) -> Result<Option<AuthResponseDto>, ApiError> {
+    match &subject.mfa_token {
+        Some(mfa_token) => {
+            let response = state.auth_service.complete_mfa_enrollment(mfa_token).await?;
+            Ok(Some(response.into()))
+        }
+        None => Ok(None),
+    }
+}
+
+fn mfa_token_header(req: &HttpRequest) -> Result<Option<String>, ApiError> {
+    match req.headers().get(MFA_TOKEN_HEADER) {
+        Some(value) => {
+            let token = value.to_str().map_err(|_| ApiError::InvalidToken)?.trim();
+            if token.is_empty() {
+                return Err(ApiError::InvalidToken);
+            }
+            Ok(Some(token.to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_mfa_token_header() {
+        let req = TestRequest::default()
+            .insert_header((MFA_TOKEN_HEADER, "abc123"))
+            .to_http_request();
+        assert_eq!(mfa_token_header(&req).unwrap(), Some("abc123".to_string()));
+
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(mfa_token_header(&req).unwrap(), None);
+
+        let req = TestRequest::default()
+            .insert_header((MFA_TOKEN_HEADER, " "))
+            .to_http_request();
+        assert!(mfa_token_header(&req).is_err());
+    }
+}
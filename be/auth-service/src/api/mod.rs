@@ -40,6 +40,8 @@
 //! |--------------|-----------------------------------------------------|
 //! | `routes`     | Route definitions with actix-web's `configure()`    |
 //! | `handlers`   | Request handlers (extractors → service → response)  |
+//! | `mfa_handlers` | Second login step and factor enrollment           |
+//! | `sso_handlers` | Sign-in through OpenID Connect providers          |
 //! | `dto`        | Data Transfer Objects for request/response          |
 //!
 //! ## Endpoint Summary
@@ -51,11 +53,16 @@
 //! | POST   | `/api/v1/auth/refresh`     | `refresh_token`      | No   |
 //! | POST   | `/api/v1/auth/logout`      | `logout`             | Yes  |
 //! | POST   | `/api/v1/auth/logout-all`  | `logout_all`         | Yes  |
+//! | GET    | `/api/v1/auth/sessions`    | `list_sessions`      | Yes  |
+//! | DELETE | `/api/v1/auth/sessions/{id}` | `revoke_session`   | Yes  |
 //! | GET    | `/api/v1/auth/me`          | `get_profile`        | Yes  |
 //! | POST   | `/api/v1/auth/verify-email`| `verify_email`       | No   |
 //! | POST   | `/api/v1/auth/forgot-password` | `forgot_password`| No   |
 //! | POST   | `/api/v1/auth/reset-password`  | `reset_password` | No   |
+//! | *      | `/api/v1/auth/mfa/*`       | `mfa_handlers::*`    | Varies |
+//! | *      | `/api/v1/auth/sso/*`       | `sso_handlers::*`    | No     |
 //! | GET    | `/health`                  | `health_check`       | No   |
+//! | GET    | `/.well-known/jwks.json`   | `jwks`               | No   |
 //!
 //! ## Related Documentation
 //!
@@ -65,4 +72,6 @@
 
 pub mod dto;
 pub mod handlers;
+pub mod mfa_handlers;
 pub mod routes;
+pub mod sso_handlers;
@@ -8,6 +8,7 @@
 //! ```text
 //! /
 //! ├── health                           GET  → health_check
+//! ├── .well-known/jwks.json            GET  → jwks
 //! │
 //! └── api/v1/auth/
 //!     ├── register                     POST → register
@@ -15,10 +16,27 @@
 //!     ├── refresh                      POST → refresh_token
 //!     ├── logout                       POST → logout
 //!     ├── logout-all                   POST → logout_all
+//!     ├── sessions                     GET  → list_sessions
+//!     │   └── {session_id}             DELETE → revoke_session
 //!     ├── me                           GET  → get_profile
 //!     ├── verify-email                 POST → verify_email
 //!     ├── forgot-password              POST → forgot_password
-//!     └── reset-password               POST → reset_password
+//!     ├── reset-password               POST → reset_password
+//!     ├── mfa                          GET  → mfa_handlers::status
+//!     │   ├── verify                   POST → mfa_handlers::verify_code
+//!     │   ├── totp/setup               POST → mfa_handlers::totp_setup
+//!     │   ├── totp/confirm             POST → mfa_handlers::totp_confirm
+//!     │   ├── totp                     DELETE → mfa_handlers::totp_disable
+//!     │   ├── webauthn/challenge       POST → mfa_handlers::webauthn_challenge
+//!     │   ├── webauthn/verify          POST → mfa_handlers::webauthn_verify
+//!     │   ├── webauthn/register/options POST → mfa_handlers::webauthn_register_options
+//!     │   ├── webauthn/register        POST → mfa_handlers::webauthn_register
+//!     │   ├── webauthn/{credential_id} DELETE → mfa_handlers::webauthn_delete
+//!     │   └── recovery-codes           POST → mfa_handlers::regenerate_recovery_codes
+//!     └── sso/
+//!         ├── providers                GET  → sso_handlers::providers
+//!         ├── {slug}/authorize         POST → sso_handlers::authorize
+//!         └── {slug}/callback          POST → sso_handlers::callback
 //! ```
 //!
 //! ## Versioning
@@ -41,8 +59,9 @@
 //! - Auth middleware: [`shared::auth::middleware`]
 
 use actix_web::web;
+use shared::auth::JWKS_PATH;
 
-use super::handlers;
+use super::{handlers, mfa_handlers, sso_handlers};
 
 /// Configures all routes for the auth service.
 ///
@@ -56,7 +75,8 @@ use super::handlers;
 /// # Route Groups
 ///
 /// 1. **Health check** (`/health`) - Service status
-/// 2. **Auth endpoints** (`/api/v1/auth/*`) - Authentication operations
+/// 2. **Key set** (`/.well-known/jwks.json`) - Token verification keys
+/// 3. **Auth endpoints** (`/api/v1/auth/*`) - Authentication operations
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg
         // ─────────────────────────────────────────────────────────────────
@@ -66,6 +86,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         // Returns 200 OK if service is running.
         .route("/health", web::get().to(handlers::health_check))
         // ─────────────────────────────────────────────────────────────────
+        // JSON Web Key Set
+        // ─────────────────────────────────────────────────────────────────
+        // Public signing keys, fetched by services running JwtService
+        // in verifier mode (JWT_JWKS_URL).
+        .route(JWKS_PATH, web::get().to(handlers::jwks))
+        // ─────────────────────────────────────────────────────────────────
         // Auth API v1
         // ─────────────────────────────────────────────────────────────────
         .service(
@@ -130,7 +156,108 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                 // Ends all sessions for the user
                 // Headers: Authorization: Bearer <access_token>
                 // Response: MessageResponse { message }
-                .route("/logout-all", web::post().to(handlers::logout_all)),
+                .route("/logout-all", web::post().to(handlers::logout_all))
+                //
+                // GET /api/v1/auth/sessions
+                // Lists active sessions (signed-in devices)
+                // Headers: Authorization: Bearer <access_token>
+                // Response: [SessionDto]
+                .route("/sessions", web::get().to(handlers::list_sessions))
+                //
+                // DELETE /api/v1/auth/sessions/{session_id}
+                // Signs out one device
+                // Headers: Authorization: Bearer <access_token>
+                // Response: MessageResponse { message }
+                .route("/sessions/{session_id}", web::delete().to(handlers::revoke_session))
+                // ─────────────────────────────────────────────────────────
+                // Multi-Factor Authentication
+                // ─────────────────────────────────────────────────────────
+                //
+                // POST /api/v1/auth/mfa/verify
+                // Completes a login with a TOTP or recovery code
+                // Request: MfaVerifyRequest { mfaToken, code }
+                // Response: AuthResponseDto
+                .route("/mfa/verify", web::post().to(mfa_handlers::verify_code))
+                //
+                // POST /api/v1/auth/mfa/webauthn/challenge
+                // WebAuthn options for a pending login
+                // Request: MfaTokenRequest { mfaToken }
+                // Response: PublicKeyCredentialRequestOptions (JSON)
+                .route("/mfa/webauthn/challenge", web::post().to(mfa_handlers::webauthn_challenge))
+                //
+                // POST /api/v1/auth/mfa/webauthn/verify
+                // Completes a login with a WebAuthn assertion
+                // Request: WebAuthnLoginRequest { mfaToken, credential }
+                // Response: AuthResponseDto
+                .route("/mfa/webauthn/verify", web::post().to(mfa_handlers::webauthn_verify))
+                //
+                // GET /api/v1/auth/mfa
+                // Enrolled factors
+                // Headers: Authorization: Bearer <access_token>
+                // Response: MfaStatusDto
+                .route("/mfa", web::get().to(mfa_handlers::status))
+                //
+                // POST /api/v1/auth/mfa/totp/setup
+                // Headers: Authorization: Bearer <access_token> or X-MFA-Token
+                // Response: TotpSetupResponse { secret, otpauthUri }
+                .route("/mfa/totp/setup", web::post().to(mfa_handlers::totp_setup))
+                //
+                // POST /api/v1/auth/mfa/totp/confirm
+                // Headers: Authorization: Bearer <access_token> or X-MFA-Token
+                // Request: TotpConfirmRequest { code }
+                // Response: MfaEnrolledResponse { recoveryCodes?, session? }
+                .route("/mfa/totp/confirm", web::post().to(mfa_handlers::totp_confirm))
+                //
+                // DELETE /api/v1/auth/mfa/totp
+                // Headers: Authorization: Bearer <access_token>
+                .route("/mfa/totp", web::delete().to(mfa_handlers::totp_disable))
+                //
+                // POST /api/v1/auth/mfa/webauthn/register/options
+                // Headers: Authorization: Bearer <access_token> or X-MFA-Token
+                // Response: PublicKeyCredentialCreationOptions (JSON)
+                .route(
+                    "/mfa/webauthn/register/options",
+                    web::post().to(mfa_handlers::webauthn_register_options),
+                )
+                //
+                // POST /api/v1/auth/mfa/webauthn/register
+                // Headers: Authorization: Bearer <access_token> or X-MFA-Token
+                // Request: WebAuthnRegisterRequest { name, credential }
+                // Response: MfaEnrolledResponse { credential, recoveryCodes?, session? }
+                .route("/mfa/webauthn/register", web::post().to(mfa_handlers::webauthn_register))
+                //
+                // DELETE /api/v1/auth/mfa/webauthn/{credential_id}
+                // Headers: Authorization: Bearer <access_token>
+                .route(
+                    "/mfa/webauthn/{credential_id}",
+                    web::delete().to(mfa_handlers::webauthn_delete),
+                )
+                //
+                // POST /api/v1/auth/mfa/recovery-codes
+                // Replaces recovery codes
+                // Headers: Authorization: Bearer <access_token>
+                // Response: RecoveryCodesResponse { recoveryCodes }
+                .route(
+                    "/mfa/recovery-codes",
+                    web::post().to(mfa_handlers::regenerate_recovery_codes),
+                )
+                // ─────────────────────────────────────────────────────────
+                // Single Sign-On (OpenID Connect)
+                // ─────────────────────────────────────────────────────────
+                //
+                // GET /api/v1/auth/sso/providers?tenant={tenant_id}
+                // Response: [IdentityProviderDto { slug, displayName }]
+                .route("/sso/providers", web::get().to(sso_handlers::providers))
+                //
+                // POST /api/v1/auth/sso/{slug}/authorize
+                // Headers: Authorization: Bearer <access_token> (optional, links the account)
+                // Response: SsoAuthorizeResponse { authorizationUrl }
+                .route("/sso/{slug}/authorize", web::post().to(sso_handlers::authorize))
+                //
+                // POST /api/v1/auth/sso/{slug}/callback
+                // Request: SsoCallbackRequest { code, state }
+                // Response: AuthResponseDto or MfaChallengeDto
+                .route("/sso/{slug}/callback", web::post().to(sso_handlers::callback)),
         );
 }
 
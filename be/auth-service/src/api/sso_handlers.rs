@@ -0,0 +1,110 @@
+//! # SSO Handlers
+//!
+//! Single sign-on through OpenID Connect providers. The SPA drives the
+//! flow: it asks for the authorization URL, sends the browser there and
+//! posts the `code` and `state` it gets back on `SSO_REDIRECT_URI`.
+//!
+//! Calling authorize with `Authorization: Bearer` links the provider to
+//! the signed-in account instead of resolving it by email.
+//!
+//! ## Related Documentation
+//!
+//! - Service: [`crate::service::sso_service`]
+//! - Login responses: [`super::handlers::login`]
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use shared::{errors::ApiError, validation};
+
+use crate::AppState;
+
+use super::dto::{IdentityProviderDto, SsoAuthorizeResponse, SsoCallbackRequest, SsoProvidersQuery};
+use super::handlers::{client_info, extract_bearer_token, login_response};
+
+/// Lists the identity providers of a tenant.
+///
+/// # Route
+///
+/// `GET /api/v1/auth/sso/providers?tenant={tenant_id}`
+///
+/// # Responses
+///
+/// - **200 OK**: `[IdentityProviderDto]` (global providers included)
+pub async fn providers(
+    state: web::Data<AppState>,
+    query: web::Query<SsoProvidersQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let providers = state.sso_service.providers(query.tenant).await?;
+
+    let dtos: Vec<IdentityProviderDto> = providers.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(dtos))
+}
+
+/// Starts a sign-in with an identity provider.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/sso/{slug}/authorize`
+///
+/// # Responses
+///
+/// - **200 OK**: `SsoAuthorizeResponse`
+/// - **401 Unauthorized**: Invalid access token (link flow)
+/// - **404 Not Found**: Unknown or disabled provider
+/// - **503 Service Unavailable**: Provider discovery failed
+pub async fn authorize(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let link_user_id = match req.headers().contains_key("Authorization") {
+        true => {
+            let access_token = extract_bearer_token(&req)?;
+            Some(state.jwt_service.validate_access_token(&access_token)?.sub)
+        }
+        false => None,
+    };
+
+    let authorization_url = state
+        .sso_service
+        .authorize(&path.into_inner(), link_user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SsoAuthorizeResponse { authorization_url }))
+}
+
+/// Completes a sign-in with the code returned by the provider.
+///
+/// # Route
+///
+/// `POST /api/v1/auth/sso/{slug}/callback`
+///
+/// # Responses
+///
+/// - **200 OK**: `AuthResponseDto`, or `MfaChallengeDto` when a second
+///   factor is needed
+/// - **401 Unauthorized**: Unknown/used `state`, or code rejected
+/// - **403 Forbidden**: Email domain not allowed, or no account and
+///   provisioning disabled
+/// - **409 Conflict**: Identity linked to another account
+pub async fn callback(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<SsoCallbackRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    validation::validate_request(&body)?;
+
+    let user = state
+        .sso_service
+        .callback(&path.into_inner(), &body.code, &body.state)
+        .await?;
+
+    let outcome = state
+        .auth_service
+        .login_federated(user, client_info(&req))
+        .await?;
+
+    Ok(login_response(outcome))
+}
@@ -46,6 +46,10 @@
 //! | [`UserProfile`]   | -                  | Public user data (no secrets)   |
 //! | [`UserPreferences`]| `user_preferences`| User notification settings      |
 //! | [`RefreshToken`]  | `refresh_tokens`   | Session tracking for tokens     |
+//! | [`UserSession`]   | `refresh_tokens`   | Active session (token family)   |
+//! | [`MfaTotp`]       | `mfa_totp`         | TOTP authenticator of a user    |
+//! | [`WebAuthnCredential`] | `webauthn_credentials` | Passkeys / security keys |
+//! | [`IdentityProvider`] | `identity_providers` | OIDC IdP configured for SSO |
 //!
 //! ## Data Transfer Objects
 //!
@@ -53,6 +57,7 @@
 //! |-------------------|--------------------------------------------------|
 //! | [`NewUser`]       | Data required to create a new user               |
 //! | [`NewRefreshToken`]| Data required to create a refresh token         |
+//! | [`ClientInfo`]    | Device, IP and location of a login               |
 //!
 //! ## Security Considerations
 //!
@@ -347,6 +352,15 @@ pub struct UserPreferences {
 /// | `expires_at`        | DateTime<Utc>         | Token expiration           |
 /// | `created_at`        | DateTime<Utc>         | Token creation time        |
 /// | `revoked_at`        | Option<DateTime<Utc>> | Revocation timestamp       |
+/// | `family_id`         | UUID                  | Session the token belongs to |
+/// | `location`          | Option<String>        | Approximate client location |
+/// | `revoked_reason`    | Option<String>        | Why the token was revoked  |
+///
+/// # Rotation and Reuse Detection
+///
+/// Every refresh revokes the presented token (`revoked_reason = "rotated"`)
+/// and issues a new one in the same family. A rotated token presented
+/// again means it was copied, so the whole family is revoked.
 #[derive(Debug, Clone, FromRow)]
 pub struct RefreshToken {
     /// Unique token identifier (UUID v4)
@@ -367,6 +381,121 @@ pub struct RefreshToken {
     pub created_at: DateTime<Utc>,
     /// If set, token has been revoked (logout)
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Session (login) the token belongs to; shared by its rotations
+    pub family_id: Uuid,
+    /// Approximate location of the client ("Bogotá, CO")
+    pub location: Option<String>,
+    /// `rotated`, `logout`, `revoked` or `reuse_detected`
+    pub revoked_reason: Option<String>,
+}
+
+impl RefreshToken {
+    /// Whether the token was replaced by a refresh (as opposed to logout).
+    pub fn was_rotated(&self) -> bool {
+        self.revoked_reason.as_deref() == Some("rotated")
+    }
+}
+
+/// Active session of a user: the live token of a refresh token family.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSession {
+    /// Family ID of the session's refresh tokens
+    pub session_id: Uuid,
+    /// Device fingerprint sent at login
+    pub device_fingerprint: Option<String>,
+    /// Client IP at the last refresh
+    pub ip_address: Option<String>,
+    /// Browser/client identifier
+    pub user_agent: Option<String>,
+    /// Approximate location at the last refresh
+    pub location: Option<String>,
+    /// Login time
+    pub created_at: DateTime<Utc>,
+    /// Last login or token refresh
+    pub last_used_at: DateTime<Utc>,
+    /// When the session ends unless refreshed
+    pub expires_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// MULTI-FACTOR AUTHENTICATION
+// =============================================================================
+
+/// TOTP authenticator enrolled by a user (`auth.mfa_totp`).
+///
+/// The row exists from the moment setup starts; the factor only counts
+/// once `confirmed_at` is set by a first valid code.
+#[derive(Debug, Clone, FromRow)]
+pub struct MfaTotp {
+    /// Owner of the authenticator
+    pub user_id: Uuid,
+    /// AES-256-GCM encrypted secret (nonce prepended)
+    pub secret_encrypted: Vec<u8>,
+    /// When the user proved possession of the authenticator
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Last accepted time step, to reject replayed codes
+    pub last_used_step: Option<i64>,
+    /// When setup started
+    pub created_at: DateTime<Utc>,
+}
+
+/// WebAuthn credential (passkey or security key) registered by a user.
+#[derive(Debug, Clone, FromRow)]
+pub struct WebAuthnCredential {
+    /// Our identifier of the credential
+    pub credential_id: Uuid,
+    /// Owner of the credential
+    pub user_id: Uuid,
+    /// Credential ID chosen by the authenticator
+    pub raw_id: Vec<u8>,
+    /// COSE_Key encoded public key
+    pub public_key: Vec<u8>,
+    /// Signature counter reported by the last assertion
+    pub sign_count: i64,
+    /// Label chosen by the user ("YubiKey", "MacBook")
+    pub name: String,
+    /// Transports hinted by the browser (usb, nfc, internal, ...)
+    pub transports: Vec<String>,
+    /// When the credential was registered
+    pub created_at: DateTime<Utc>,
+    /// Last successful authentication
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// OpenID Connect identity provider available for single sign-on.
+///
+/// The client secret itself never reaches the database: `client_secret_env`
+/// names the environment variable holding it.
+#[derive(Debug, Clone, FromRow)]
+pub struct IdentityProvider {
+    /// Primary key
+    pub provider_id: Uuid,
+    /// URL identifier (`/sso/{slug}/authorize`)
+    pub slug: String,
+    /// Tenant the IdP belongs to; `None` for global providers (Google, ...)
+    pub tenant_id: Option<Uuid>,
+    /// Button label ("Microsoft", "Universidad de ...")
+    pub display_name: String,
+    /// Issuer identifier; discovery is fetched from it
+    pub issuer: String,
+    /// OAuth client ID registered at the IdP
+    pub client_id: String,
+    /// Environment variable with the client secret; `None` for public clients
+    pub client_secret_env: Option<String>,
+    /// Space-separated scopes requested
+    pub scopes: String,
+    /// Accepted email domains (empty = any)
+    pub allowed_domains: Vec<String>,
+    /// Create unknown users on first sign-in
+    pub jit_provisioning: bool,
+    /// Role of provisioned users
+    pub default_role: String,
+    /// Disabled providers are hidden and rejected
+    pub enabled: bool,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp
+    pub updated_at: DateTime<Utc>,
 }
 
 // =============================================================================
@@ -406,6 +535,20 @@ pub struct NewUser {
     pub role: String,
 }
 
+/// Client a login or refresh request comes from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientInfo {
+    /// `X-Device-Fingerprint` header
+    pub device_fingerprint: Option<String>,
+    /// Peer IP address
+    pub ip_address: Option<String>,
+    /// `User-Agent` header
+    pub user_agent: Option<String>,
+    /// Approximate location from the edge proxy's geo headers
+    #[serde(default)]
+    pub location: Option<String>,
+}
+
 /// Data required to create a new refresh token.
 ///
 /// Used when generating tokens after login or token refresh.
@@ -427,6 +570,8 @@ pub struct NewUser {
 ///     device_fingerprint: request.headers().get("X-Device-Fingerprint").map(|h| h.to_string()),
 ///     ip_address: request.peer_addr().map(|addr| addr.ip().to_string()),
 ///     user_agent: request.headers().get("User-Agent").map(|h| h.to_string()),
+///     location: None,
+///     family_id: Uuid::new_v4(),
 ///     expires_at: Utc::now() + Duration::days(7),
 /// };
 ///
@@ -445,6 +590,10 @@ pub struct NewRefreshToken {
     pub ip_address: Option<String>,
     /// Client User-Agent
     pub user_agent: Option<String>,
+    /// Approximate client location
+    pub location: Option<String>,
+    /// Session the token belongs to (new UUID at login, kept on refresh)
+    pub family_id: Uuid,
     /// Token expiration time
     pub expires_at: DateTime<Utc>,
 }
@@ -35,6 +35,10 @@
 //! | `EmailVerified`    | User confirms email address       | Update profile, analytics    |
 //! | `PasswordResetRequested` | User requests reset         | Send reset email             |
 //! | `LoginFailed`      | Failed authentication attempt     | Security monitoring          |
+//! | `NewDeviceLogin`   | Login from a device not seen before | "New sign-in" email        |
+//!
+//! `LoginFailed` and `NewDeviceLogin` are written to the `auth.outbox_events`
+//! outbox and relayed to the `events:user` stream.
 //!
 //! ## Future Integration
 //!
@@ -50,6 +54,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::events::PublishableEvent;
 use uuid::Uuid;
 
 // =============================================================================
@@ -206,6 +211,9 @@ impl DomainEvent for UserLoggedOut {
 pub struct LoginFailed {
     /// Email that was attempted (may not exist)
     pub attempted_email: String,
+    /// Account the attempt targeted, when the email matched a user
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
     /// Reason for failure
     pub reason: LoginFailureReason,
     /// IP address of the attempt
@@ -230,6 +238,11 @@ pub enum LoginFailureReason {
     AccountLocked,
     /// Email not yet verified (if required)
     EmailNotVerified,
+    /// Password was correct but the TOTP, recovery code or WebAuthn
+    /// assertion was not
+    InvalidSecondFactor,
+    /// Pending MFA login abandoned after too many wrong second factors
+    TooManySecondFactorAttempts,
 }
 
 impl DomainEvent for LoginFailed {
@@ -241,9 +254,81 @@ impl DomainEvent for LoginFailed {
         self.occurred_at
     }
 
-    /// Returns a zero UUID since there's no valid user
+    /// The targeted user, or a zero UUID when the email matched no user
+    fn aggregate_id(&self) -> Uuid {
+        self.user_id.unwrap_or_else(Uuid::nil)
+    }
+}
+
+impl PublishableEvent for LoginFailed {
+    fn aggregate_type(&self) -> &'static str {
+        "user"
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        DomainEvent::aggregate_id(self)
+    }
+
+    fn event_type(&self) -> &'static str {
+        DomainEvent::event_type(self)
+    }
+}
+
+/// Emitted when a user signs in from a device none of their sessions
+/// used before (matched by device fingerprint, else by user agent).
+///
+/// Not emitted for a user's first session.
+///
+/// # Handlers
+///
+/// - **Notification Service**: "New sign-in to your account" email, with a
+///   link to the sessions page to revoke it
+/// - **Audit Log**: Record the device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewDeviceLogin {
+    /// User who signed in
+    pub user_id: Uuid,
+    /// Address the notification goes to
+    pub email: String,
+    /// Session opened by the login (revocable through the sessions API)
+    pub session_id: Uuid,
+    /// Device fingerprint if available
+    pub device_fingerprint: Option<String>,
+    /// IP address during login
+    pub ip_address: Option<String>,
+    /// Browser/client identifier
+    pub user_agent: Option<String>,
+    /// Approximate location ("Bogotá, CO")
+    pub location: Option<String>,
+    /// When login occurred
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl DomainEvent for NewDeviceLogin {
+    fn event_type(&self) -> &'static str {
+        "user.new_device_login"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
     fn aggregate_id(&self) -> Uuid {
-        Uuid::nil()
+        self.user_id
+    }
+}
+
+impl PublishableEvent for NewDeviceLogin {
+    fn aggregate_type(&self) -> &'static str {
+        "user"
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    fn event_type(&self) -> &'static str {
+        DomainEvent::event_type(self)
     }
 }
 
@@ -437,6 +522,7 @@ mod tests {
     fn test_login_failed_event_nil_aggregate() {
         let event = LoginFailed {
             attempted_email: "unknown@example.com".to_string(),
+            user_id: None,
             reason: LoginFailureReason::UserNotFound,
             ip_address: None,
             user_agent: None,
@@ -444,7 +530,42 @@ mod tests {
         };
 
         // Failed logins have no valid user, so aggregate_id is nil
-        assert_eq!(event.aggregate_id(), Uuid::nil());
+        assert_eq!(DomainEvent::aggregate_id(&event), Uuid::nil());
+    }
+
+    #[test]
+    fn test_second_factor_failure_targets_user() {
+        let user_id = Uuid::new_v4();
+        let event = LoginFailed {
+            attempted_email: "admin@example.com".to_string(),
+            user_id: Some(user_id),
+            reason: LoginFailureReason::InvalidSecondFactor,
+            ip_address: None,
+            user_agent: None,
+            occurred_at: Utc::now(),
+        };
+
+        assert_eq!(PublishableEvent::aggregate_id(&event), user_id);
+        assert_eq!(event.aggregate_type(), "user");
+        assert!(serde_json::to_string(&event).unwrap().contains("invalid_second_factor"));
+    }
+
+    #[test]
+    fn test_new_device_login_publishes_to_user_stream() {
+        let event = NewDeviceLogin {
+            user_id: Uuid::new_v4(),
+            email: "ana@example.com".to_string(),
+            session_id: Uuid::new_v4(),
+            device_fingerprint: None,
+            ip_address: Some("10.0.0.1".to_string()),
+            user_agent: Some("Mozilla/5.0".to_string()),
+            location: Some("Bogotá, CO".to_string()),
+            occurred_at: Utc::now(),
+        };
+
+        assert_eq!(PublishableEvent::event_type(&event), "user.new_device_login");
+        assert_eq!(PublishableEvent::aggregate_id(&event), event.user_id);
+        assert_eq!(event.aggregate_type(), "user");
     }
 
     #[test]
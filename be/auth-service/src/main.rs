@@ -56,6 +56,7 @@
 //! | POST   | `/verify-email` | Verify email address       | No            |
 //! | POST   | `/forgot-password` | Request password reset  | No            |
 //! | POST   | `/reset-password`  | Complete password reset | No            |
+//! | *      | `/mfa/*`           | Second factor (TOTP, WebAuthn) | Varies |
 //!
 //! *Requires valid refresh token in request body
 //!
@@ -97,7 +98,7 @@
 //! - **Password Hashing**: Argon2id with OWASP-recommended parameters
 //! - **JWT Tokens**: Short-lived access tokens (15 min), longer refresh tokens (7 days)
 //! - **Token Blacklisting**: Redis-based invalidation for logout
-//! - **Rate Limiting**: Protection against brute-force attacks
+//! - **Rate Limiting**: Per-role limits, stricter on login/MFA/password reset ([`shared::rate_limit`])
 //! - **Device Tracking**: Fingerprint and IP logging for sessions
 //!
 //! ## Configuration
@@ -106,9 +107,14 @@
 //! Key settings for auth-service:
 //!
 //! - `SERVICE_NAME=auth-service`
-//! - `JWT_SECRET` - Secret key for token signing
+//! - `JWT_SECRET` - Secret key for HS256 signing (and legacy tokens)
+//! - `JWT_SIGNING_KEYS_DIR` - PKCS#8 RS256/EdDSA keys (`<kid>.pem`), published on `/.well-known/jwks.json`
+//! - `JWT_ACTIVE_KID` - Key that signs new tokens (default: greatest `kid`)
 //! - `JWT_ACCESS_EXPIRY_SECS` - Access token lifetime (default: 900)
 //! - `JWT_REFRESH_EXPIRY_SECS` - Refresh token lifetime (default: 604800)
+//! - `MFA_ENCRYPTION_KEY` - Base64 AES-256 key for TOTP secrets (required)
+//! - `MFA_REQUIRED_ROLES` - Roles that must use MFA (default: `admin,instructor`)
+//! - `WEBAUTHN_RP_ID`, `WEBAUTHN_RP_NAME`, `WEBAUTHN_ORIGINS` - WebAuthn relying party
 //!
 //! ## Related Documentation
 //!
@@ -119,11 +125,17 @@
 //! - Security requirements: `_docs/business/non-functional-requirements.md`
 
 use actix_cors::Cors;
-use actix_web::{middleware, web, App, HttpServer};
+use actix_web::{http::Method, middleware, web, App, HttpServer};
 use shared::{
     auth::{jwt::JwtService, password::PasswordHasher},
     config::AppConfig,
-    database, redis_client::RedisClient,
+    database,
+    events::{OutboxRelay, RelayConfig},
+    rate_limit::{
+        RateLimit, RateLimitConfig, RateLimiter, RouteLimit, RATE_LIMIT_LIMIT,
+        RATE_LIMIT_REMAINING, RATE_LIMIT_RESET,
+    },
+    redis_client::RedisClient,
     tracing_config,
 };
 use std::sync::Arc;
@@ -135,8 +147,8 @@ mod repository;
 mod service;
 
 use api::routes;
-use repository::UserRepository;
-use service::AuthService;
+use repository::{MfaRepository, SsoRepository, UserRepository};
+use service::{AuthService, MfaConfig, MfaService, SsoConfig, SsoService};
 
 /// Shared application state injected into all request handlers.
 ///
@@ -150,6 +162,8 @@ use service::AuthService;
 /// |----------------|-----------------------|-----------------------------------|
 /// | `auth_service` | [`AuthService`]       | Business logic for auth operations|
 /// | `jwt_service`  | `Arc<JwtService>`     | JWT token generation/validation   |
+/// | `mfa_service`  | [`MfaService`]        | Second factor enrollment          |
+/// | `sso_service`  | [`SsoService`]        | Sign-in through OIDC providers    |
 ///
 /// # Thread Safety
 ///
@@ -172,6 +186,10 @@ pub struct AppState {
     pub auth_service: AuthService,
     /// JWT service for token operations, shared via Arc for efficiency
     pub jwt_service: Arc<JwtService>,
+    /// Second factor enrollment and verification
+    pub mfa_service: MfaService,
+    /// Single sign-on through OpenID Connect providers
+    pub sso_service: SsoService,
 }
 
 /// Application entry point and server initialization.
@@ -242,21 +260,62 @@ async fn main() -> std::io::Result<()> {
     // ─────────────────────────────────────────────────────────────────────
     // Services are created with dependency injection pattern.
     // JwtService and PasswordHasher are wrapped in Arc for shared ownership.
-    let jwt_service = Arc::new(JwtService::new(config.jwt.clone()));
+    // Signs with JWT_SIGNING_KEYS_DIR keys when set, else the HS256 secret.
+    let jwt_service = Arc::new(
+        JwtService::from_config(config.jwt.clone())
+            .await
+            .expect("Failed to load JWT signing keys"),
+    );
     let password_hasher = Arc::new(PasswordHasher::new());
     let user_repository = UserRepository::new(db_pool.clone());
+    let mfa_repository = MfaRepository::new(db_pool.clone());
+
+    // LoginFailed events are written to the outbox and relayed to Redis Streams
+    let relay = OutboxRelay::new(
+        db_pool.clone(),
+        &redis_client,
+        mfa_repository.outbox(),
+        RelayConfig::default(),
+    );
+    tokio::spawn(relay.run());
+
+    let mfa_config = MfaConfig::from_env().expect("Invalid MFA configuration");
+    let mfa_service = MfaService::new(mfa_repository, redis_client.clone(), mfa_config);
+    let sso_service = SsoService::new(
+        SsoRepository::new(db_pool.clone()),
+        user_repository.clone(),
+        redis_client.clone(),
+        password_hasher.clone(),
+        SsoConfig::from_env(),
+    );
+
+    // Per-role limits (RF-GLOBAL-004), stricter on credential endpoints.
+    // Anonymous clients are keyed by peer address; forwarded headers count
+    // only from RATE_LIMIT_TRUSTED_PROXIES.
+    let rate_limit_config = RateLimitConfig::default()
+        .with_trusted_proxies(&std::env::var("RATE_LIMIT_TRUSTED_PROXIES").unwrap_or_default())
+        .expect("Invalid RATE_LIMIT_TRUSTED_PROXIES");
+    let rate_limiter = RateLimiter::new(redis_client.clone(), rate_limit_config)
+        .with_jwt(jwt_service.clone())
+        .with_route(RouteLimit::new("/api/v1/auth/login", RateLimit::new(1, 5)).method(Method::POST))
+        .with_route(RouteLimit::new("/api/v1/auth/mfa/verify", RateLimit::new(1, 5)))
+        .with_route(RouteLimit::new("/api/v1/auth/mfa/webauthn/verify", RateLimit::new(1, 5)))
+        .with_route(RouteLimit::new("/api/v1/auth/forgot-password", RateLimit::new(1, 3)));
     let auth_service = AuthService::new(
         user_repository,
         jwt_service.clone(),
         password_hasher,
         redis_client,
         config.jwt.clone(),
+        mfa_service.clone(),
     );
 
     // Wrap state in web::Data for thread-safe sharing across workers
     let app_state = web::Data::new(AppState {
         auth_service,
         jwt_service,
+        mfa_service,
+        sso_service,
     });
 
     // Database pool is also shared for health checks and direct queries
@@ -285,12 +344,14 @@ async fn main() -> std::io::Result<()> {
             .allow_any_origin() // TODO: Configure specific origins for production
             .allow_any_method()
             .allow_any_header()
+            .expose_headers([RATE_LIMIT_LIMIT, RATE_LIMIT_REMAINING, RATE_LIMIT_RESET])
             .max_age(3600); // Cache preflight for 1 hour
 
         App::new()
             // ─────────────────────────────────────────────────────────
             // Middleware Stack (order matters: first added = last executed)
             // ─────────────────────────────────────────────────────────
+            .wrap(rate_limiter.clone())                      // Rate limiting (Redis)
             .wrap(cors)                                      // CORS headers
             .wrap(middleware::Logger::default())             // Request logging
             .wrap(middleware::Compress::default())           // Response compression
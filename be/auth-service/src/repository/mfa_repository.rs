@@ -0,0 +1,327 @@
+//! # MFA Repository
+//!
+//! Data access for second factors:
+//!
+//! - `auth.mfa_totp`: TOTP authenticator per user (encrypted secret)
+//! - `auth.webauthn_credentials`: passkeys and security keys
+//! - `auth.mfa_recovery_codes`: single-use recovery codes (SHA-256)
+//! - `auth.outbox_events`: `LoginFailed` events awaiting relay
+//!
+//! Consuming a factor (TOTP step, recovery code, WebAuthn counter) is a
+//! conditional `UPDATE`, so two concurrent logins cannot both use it.
+
+use shared::errors::ApiError;
+use shared::events::OutboxWriter;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{events::LoginFailed, MfaTotp, WebAuthnCredential};
+
+/// Outbox table of this service.
+pub const OUTBOX_TABLE: &str = "auth.outbox_events";
+
+/// Repository for MFA factors.
+#[derive(Debug, Clone)]
+pub struct MfaRepository {
+    pool: PgPool,
+    outbox: OutboxWriter,
+}
+
+impl MfaRepository {
+    /// Creates a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            outbox: OutboxWriter::new(OUTBOX_TABLE).expect("valid outbox table name"),
+        }
+    }
+
+    /// Outbox the repository writes events to.
+    pub fn outbox(&self) -> &OutboxWriter {
+        &self.outbox
+    }
+
+    // =========================================================================
+    // TOTP
+    // =========================================================================
+
+    /// Gets the user's TOTP authenticator, confirmed or not.
+    pub async fn find_totp(&self, user_id: Uuid) -> Result<Option<MfaTotp>, ApiError> {
+        sqlx::query_as::<_, MfaTotp>("SELECT * FROM auth.mfa_totp WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Starts (or restarts) TOTP setup with a new secret.
+    ///
+    /// Returns `false` if the user already has a confirmed authenticator,
+    /// which must be removed first.
+    pub async fn upsert_pending_totp(&self, user_id: Uuid, secret_encrypted: &[u8]) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO auth.mfa_totp (user_id, secret_encrypted)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret_encrypted = EXCLUDED.secret_encrypted,
+                last_used_step = NULL,
+                created_at = NOW()
+            WHERE auth.mfa_totp.confirmed_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret_encrypted)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Marks the authenticator as confirmed by the code of `step`.
+    pub async fn confirm_totp(&self, user_id: Uuid, step: i64) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE auth.mfa_totp
+            SET confirmed_at = NOW(), last_used_step = $2
+            WHERE user_id = $1 AND confirmed_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records `step` as used. Returns `false` if it (or a later step) was
+    /// already used, i.e. the code is a replay.
+    pub async fn consume_totp_step(&self, user_id: Uuid, step: i64) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE auth.mfa_totp
+            SET last_used_step = $2
+            WHERE user_id = $1
+              AND confirmed_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes the user's TOTP authenticator.
+    pub async fn delete_totp(&self, user_id: Uuid) -> Result<bool, ApiError> {
+        let result = sqlx::query("DELETE FROM auth.mfa_totp WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // =========================================================================
+    // WEBAUTHN
+    // =========================================================================
+
+    /// Lists the user's WebAuthn credentials, oldest first.
+    pub async fn list_webauthn_credentials(&self, user_id: Uuid) -> Result<Vec<WebAuthnCredential>, ApiError> {
+        sqlx::query_as::<_, WebAuthnCredential>(
+            "SELECT * FROM auth.webauthn_credentials WHERE user_id = $1 ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Finds a credential of the user by its authenticator-assigned ID.
+    pub async fn find_webauthn_credential(
+        &self,
+        user_id: Uuid,
+        raw_id: &[u8],
+    ) -> Result<Option<WebAuthnCredential>, ApiError> {
+        sqlx::query_as::<_, WebAuthnCredential>(
+            "SELECT * FROM auth.webauthn_credentials WHERE user_id = $1 AND raw_id = $2",
+        )
+        .bind(user_id)
+        .bind(raw_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Stores a newly registered credential.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::Conflict` if the credential is already registered
+    pub async fn create_webauthn_credential(
+        &self,
+        user_id: Uuid,
+        raw_id: &[u8],
+        public_key: &[u8],
+        sign_count: i64,
+        name: &str,
+        transports: &[String],
+    ) -> Result<WebAuthnCredential, ApiError> {
+        sqlx::query_as::<_, WebAuthnCredential>(
+            r#"
+            INSERT INTO auth.webauthn_credentials (
+                user_id, raw_id, public_key, sign_count, name, transports
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(raw_id)
+        .bind(public_key)
+        .bind(sign_count)
+        .bind(name)
+        .bind(transports)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => ApiError::Conflict {
+                resource: "WebAuthn credential already registered".to_string(),
+            },
+            _ => db_error(e),
+        })
+    }
+
+    /// Stores the counter of a verified assertion.
+    ///
+    /// Returns `false` if a concurrent login already stored a counter at
+    /// least as high (the assertion is then treated as a replay).
+    pub async fn record_webauthn_use(
+        &self,
+        credential_id: Uuid,
+        previous_count: i64,
+        sign_count: i64,
+    ) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE auth.webauthn_credentials
+            SET sign_count = $3, last_used_at = NOW()
+            WHERE credential_id = $1 AND sign_count = $2
+            "#,
+        )
+        .bind(credential_id)
+        .bind(previous_count)
+        .bind(sign_count)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes a credential of the user.
+    pub async fn delete_webauthn_credential(&self, user_id: Uuid, credential_id: Uuid) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            "DELETE FROM auth.webauthn_credentials WHERE user_id = $1 AND credential_id = $2",
+        )
+        .bind(user_id)
+        .bind(credential_id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // =========================================================================
+    // RECOVERY CODES
+    // =========================================================================
+
+    /// Replaces all recovery codes of the user.
+    pub async fn replace_recovery_codes(&self, user_id: Uuid, code_hashes: &[String]) -> Result<(), ApiError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM auth.mfa_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO auth.mfa_recovery_codes (user_id, code_hash)
+            SELECT $1, UNNEST($2::TEXT[])
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hashes)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)
+    }
+
+    /// Marks a recovery code as used. Returns `false` if it does not exist
+    /// or was already used.
+    pub async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE auth.mfa_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Number of recovery codes the user has left.
+    pub async fn count_unused_recovery_codes(&self, user_id: Uuid) -> Result<i64, ApiError> {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM auth.mfa_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Removes all recovery codes of the user (last factor removed).
+    pub async fn delete_recovery_codes(&self, user_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("DELETE FROM auth.mfa_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // EVENTS
+    // =========================================================================
+
+    /// Writes a `LoginFailed` event to the outbox.
+    pub async fn record_login_failure(&self, event: &LoginFailed) -> Result<(), ApiError> {
+        self.outbox.publish(&self.pool, event).await.map_err(db_error)
+    }
+}
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::InternalError {
+        message: format!("Database error: {}", e),
+    }
+}
@@ -53,9 +53,15 @@
 //! ## Related Documentation
 //!
 //! - Database schema: `db/migrations/postgresql/001_initial_schema.sql`
+//! - MFA schema: `db/migrations/postgresql/025_auth_mfa.sql`
+//! - SSO schema: `db/migrations/postgresql/026_auth_sso.sql`
 //! - Error handling: [`shared::errors`]
 //! - Connection pool: [`shared::database`]
 
+pub mod mfa_repository;
+pub mod sso_repository;
 pub mod user_repository;
 
+pub use mfa_repository::MfaRepository;
+pub use sso_repository::SsoRepository;
 pub use user_repository::UserRepository;
@@ -0,0 +1,203 @@
+//! # SSO Repository
+//!
+//! Data access for federated sign-in:
+//!
+//! - `auth.identity_providers`: OIDC providers, global or per tenant
+//! - `auth.user_identities`: users linked to an IdP subject (`sub`)
+//!
+//! JIT provisioning creates the user and its identity in one transaction,
+//! so a failed link never leaves an orphan account behind.
+
+use shared::errors::ApiError;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{IdentityProvider, NewUser, User};
+
+/// Repository for identity providers and linked identities.
+#[derive(Debug, Clone)]
+pub struct SsoRepository {
+    pool: PgPool,
+}
+
+impl SsoRepository {
+    /// Creates a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // =========================================================================
+    // PROVIDERS
+    // =========================================================================
+
+    /// Enabled providers offered to a tenant: its own plus the global ones.
+    pub async fn list_providers(&self, tenant_id: Option<Uuid>) -> Result<Vec<IdentityProvider>, ApiError> {
+        sqlx::query_as::<_, IdentityProvider>(
+            r#"
+            SELECT * FROM auth.identity_providers
+            WHERE enabled AND (tenant_id IS NULL OR tenant_id = $1)
+            ORDER BY tenant_id NULLS LAST, display_name
+            "#,
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Finds an enabled provider by slug.
+    pub async fn find_provider(&self, slug: &str) -> Result<Option<IdentityProvider>, ApiError> {
+        sqlx::query_as::<_, IdentityProvider>(
+            "SELECT * FROM auth.identity_providers WHERE slug = $1 AND enabled",
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    // =========================================================================
+    // IDENTITIES
+    // =========================================================================
+
+    /// Active user linked to `subject` at the provider.
+    pub async fn find_user_by_identity(&self, provider_id: Uuid, subject: &str) -> Result<Option<User>, ApiError> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM auth.users u
+            JOIN auth.user_identities i ON i.user_id = u.user_id
+            WHERE i.provider_id = $1 AND i.subject = $2 AND u.deleted_at IS NULL
+            "#,
+        )
+        .bind(provider_id)
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Links a user to `subject` at the provider.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::Conflict` if the subject is linked to another user, or
+    ///   the user already has an identity at this provider
+    pub async fn link_identity(
+        &self,
+        user_id: Uuid,
+        provider_id: Uuid,
+        subject: &str,
+        email: Option<&str>,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO auth.user_identities (user_id, provider_id, subject, email, last_login_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider_id)
+        .bind(subject)
+        .bind(email)
+        .execute(&self.pool)
+        .await
+        .map_err(link_error)?;
+
+        Ok(())
+    }
+
+    /// Stores the sign-in time and latest email of an identity.
+    pub async fn record_identity_login(
+        &self,
+        provider_id: Uuid,
+        subject: &str,
+        email: Option<&str>,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE auth.user_identities
+            SET last_login_at = NOW(), email = COALESCE($3, email)
+            WHERE provider_id = $1 AND subject = $2
+            "#,
+        )
+        .bind(provider_id)
+        .bind(subject)
+        .bind(email)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    /// Creates a user with a verified email and links the identity.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::Conflict` if the email is already registered (a
+    ///   concurrent sign-in provisioned it first)
+    pub async fn provision_user(
+        &self,
+        new_user: NewUser,
+        provider_id: Uuid,
+        subject: &str,
+    ) -> Result<User, ApiError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            INSERT INTO auth.users (
+                email, hashed_password, first_name, last_name, role, email_verified
+            )
+            VALUES ($1, $2, $3, $4, $5, TRUE)
+            RETURNING *
+            "#,
+        )
+        .bind(&new_user.email)
+        .bind(&new_user.hashed_password)
+        .bind(&new_user.first_name)
+        .bind(&new_user.last_name)
+        .bind(&new_user.role)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => ApiError::Conflict {
+                resource: "email".to_string(),
+            },
+            _ => db_error(e),
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO auth.user_identities (user_id, provider_id, subject, email, last_login_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            "#,
+        )
+        .bind(user.user_id)
+        .bind(provider_id)
+        .bind(subject)
+        .bind(&user.email)
+        .execute(&mut *tx)
+        .await
+        .map_err(link_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(user)
+    }
+}
+
+fn link_error(e: sqlx::Error) -> ApiError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => ApiError::Conflict {
+            resource: "Identity already linked to another account".to_string(),
+        },
+        _ => db_error(e),
+    }
+}
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::InternalError {
+        message: format!("Database error: {}", e),
+    }
+}
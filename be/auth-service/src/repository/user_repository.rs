@@ -2,8 +2,9 @@
 //!
 //! Data access layer for user-related database operations including:
 //! - User CRUD operations
-//! - Refresh token management
+//! - Refresh token management (sessions, rotation, reuse detection)
 //! - User preferences
+//! - `NewDeviceLogin` events (`auth.outbox_events`)
 //!
 //! ## Query Patterns
 //!
@@ -36,14 +37,18 @@
 //!
 //! - Entity definitions: [`crate::domain::entities`]
 //! - Database schema: `db/migrations/postgresql/001_initial_schema.sql`
+//! - Sessions schema: `db/migrations/postgresql/027_auth_sessions.sql`
 //! - Error types: [`shared::errors::ApiError`]
 
 use chrono::{DateTime, Utc};
 use shared::errors::ApiError;
+use shared::events::OutboxWriter;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::{NewRefreshToken, NewUser, RefreshToken, User, UserPreferences};
+use super::mfa_repository::OUTBOX_TABLE;
+use crate::domain::events::NewDeviceLogin;
+use crate::domain::{NewRefreshToken, NewUser, RefreshToken, User, UserPreferences, UserSession};
 
 /// Repository for user-related database operations.
 ///
@@ -69,6 +74,8 @@ use crate::domain::{NewRefreshToken, NewUser, RefreshToken, User, UserPreference
 pub struct UserRepository {
     /// PostgreSQL connection pool
     pool: PgPool,
+    /// Outbox for `NewDeviceLogin` events
+    outbox: OutboxWriter,
 }
 
 impl UserRepository {
@@ -85,7 +92,10 @@ impl UserRepository {
     /// let repo = UserRepository::new(pool);
     /// ```
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            outbox: OutboxWriter::new(OUTBOX_TABLE).expect("valid outbox table name"),
+        }
     }
 
     // =========================================================================
@@ -463,9 +473,9 @@ impl UserRepository {
             r#"
             INSERT INTO refresh_tokens (
                 user_id, token_hash, device_fingerprint, ip_address,
-                user_agent, expires_at
+                user_agent, location, family_id, expires_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#,
         )
@@ -474,6 +484,8 @@ impl UserRepository {
         .bind(&new_token.device_fingerprint)
         .bind(&new_token.ip_address)
         .bind(&new_token.user_agent)
+        .bind(&new_token.location)
+        .bind(new_token.family_id)
         .bind(&new_token.expires_at)
         .fetch_one(&self.pool)
         .await
@@ -515,9 +527,44 @@ impl UserRepository {
         Ok(token)
     }
 
+    /// Finds a refresh token by its hash, whatever its state.
+    ///
+    /// Token refresh needs revoked tokens too: a rotated token presented
+    /// again is a replay (see [`RefreshToken::was_rotated`]).
+    pub async fn find_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, ApiError> {
+        sqlx::query_as::<_, RefreshToken>("SELECT * FROM refresh_tokens WHERE token_hash = $1")
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })
+    }
+
+    /// Revokes a token being replaced by a refresh.
+    ///
+    /// Returns `false` if the token was already revoked, e.g. by a
+    /// concurrent refresh with the same token.
+    pub async fn rotate_refresh_token(&self, token_id: Uuid) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW(), revoked_reason = 'rotated'
+            WHERE token_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(token_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Revokes a specific refresh token.
     ///
-    /// Used when user logs out or token is rotated.
+    /// Used when user logs out.
     ///
     /// # Arguments
     ///
@@ -526,7 +573,7 @@ impl UserRepository {
         sqlx::query(
             r#"
             UPDATE refresh_tokens
-            SET revoked_at = NOW()
+            SET revoked_at = NOW(), revoked_reason = 'logout'
             WHERE token_id = $1 AND revoked_at IS NULL
             "#,
         )
@@ -554,7 +601,7 @@ impl UserRepository {
         let result = sqlx::query(
             r#"
             UPDATE refresh_tokens
-            SET revoked_at = NOW()
+            SET revoked_at = NOW(), revoked_reason = 'revoked'
             WHERE user_id = $1 AND revoked_at IS NULL
             "#,
         )
@@ -566,6 +613,120 @@ impl UserRepository {
         Ok(result.rows_affected())
     }
 
+    /// Revokes every token of a family after a rotated token was replayed.
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens that were revoked.
+    pub async fn revoke_token_family(&self, family_id: Uuid) -> Result<u64, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW(), revoked_reason = 'reuse_detected'
+            WHERE family_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(family_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })?;
+
+        Ok(result.rows_affected())
+    }
+
+    // =========================================================================
+    // SESSION OPERATIONS
+    // =========================================================================
+
+    /// Lists the user's active sessions, most recently used first.
+    ///
+    /// A session is a refresh token family; its live token carries the
+    /// latest IP and location, and its creation is the last use.
+    pub async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<UserSession>, ApiError> {
+        sqlx::query_as::<_, UserSession>(
+            r#"
+            SELECT
+                t.family_id AS session_id,
+                t.device_fingerprint,
+                t.ip_address,
+                t.user_agent,
+                t.location,
+                (SELECT MIN(f.created_at) FROM refresh_tokens f WHERE f.family_id = t.family_id) AS created_at,
+                t.created_at AS last_used_at,
+                t.expires_at
+            FROM refresh_tokens t
+            WHERE t.user_id = $1 AND t.revoked_at IS NULL AND t.expires_at > NOW()
+            ORDER BY t.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })
+    }
+
+    /// Revokes one session of the user.
+    ///
+    /// Returns `false` if the user has no active session with that ID.
+    pub async fn revoke_session(&self, user_id: Uuid, session_id: Uuid) -> Result<bool, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW(), revoked_reason = 'revoked'
+            WHERE user_id = $1 AND family_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether a login comes from a device none of the user's sessions
+    /// used, matched by fingerprint when present, else by user agent.
+    ///
+    /// Always `false` for a user without any session yet.
+    pub async fn is_new_device(
+        &self,
+        user_id: Uuid,
+        device_fingerprint: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<bool, ApiError> {
+        let (total, matching): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COUNT(*) FILTER (
+                    WHERE CASE
+                        WHEN $2::TEXT IS NOT NULL THEN device_fingerprint = $2
+                        ELSE user_agent IS NOT DISTINCT FROM $3
+                    END
+                )
+            FROM refresh_tokens
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(device_fingerprint)
+        .bind(user_agent)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })?;
+
+        Ok(total > 0 && matching == 0)
+    }
+
+    /// Writes a `NewDeviceLogin` event to the outbox.
+    pub async fn record_new_device_login(&self, event: &NewDeviceLogin) -> Result<(), ApiError> {
+        self.outbox
+            .publish(&self.pool, event)
+            .await
+            .map_err(|e| ApiError::InternalError { message: format!("Database error: {}", e) })
+    }
+
     /// Deletes expired refresh tokens for cleanup.
     ///
     /// Should be called periodically (e.g., daily) to remove tokens
@@ -742,6 +903,8 @@ mod tests {
             device_fingerprint: Some("device123".to_string()),
             ip_address: Some("192.168.1.1".to_string()),
             user_agent: Some("Mozilla/5.0".to_string()),
+            location: None,
+            family_id: Uuid::new_v4(),
             expires_at: Utc::now(),
         };
 
@@ -6,6 +6,9 @@
 //! - Token management
 //! - Password reset
 //! - Email verification
+//! - Second step of MFA logins (see [`super::mfa_service`])
+//! - Sessions for single sign-on users (see [`super::sso_service`])
+//! - Session (device) listing and remote revocation
 //!
 //! ## Security Model
 //!
@@ -34,6 +37,16 @@
 //! └─────────────────────────────────────────────────────────────────────────┘
 //! ```
 //!
+//! ## Sessions
+//!
+//! Each login starts a session: a family of refresh tokens sharing a
+//! `family_id`. Refreshing rotates the token within its family. Presenting
+//! an already-rotated token again means it was copied, so the whole family
+//! is revoked and both holders must log in again.
+//!
+//! Logins from a device none of the user's sessions used publish a
+//! `NewDeviceLogin` event.
+//!
 //! ## Error Handling
 //!
 //! All methods return `Result<T, ApiError>` for consistent error responses.
@@ -67,10 +80,13 @@ use tracing::{info, warn};
 use uuid::Uuid;
 
 use crate::{
-    domain::{NewRefreshToken, NewUser, User, UserProfile},
+    domain::{events::NewDeviceLogin, ClientInfo, NewRefreshToken, NewUser, User, UserProfile, UserSession},
     repository::UserRepository,
 };
 
+use super::mfa_service::{MfaChallenge, MfaService};
+use super::webauthn::AuthenticationCredential;
+
 // =============================================================================
 // SERVICE STRUCT
 // =============================================================================
@@ -98,6 +114,8 @@ pub struct AuthService {
     redis_client: RedisClient,
     /// JWT configuration (token lifetimes)
     jwt_config: JwtConfig,
+    /// Second factors
+    mfa_service: MfaService,
 }
 
 /// Response returned after successful authentication.
@@ -113,6 +131,15 @@ pub struct AuthResponse {
     pub user: UserProfile,
 }
 
+/// Result of the password step of a login.
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    /// No second factor needed; the session is started
+    Authenticated(Box<AuthResponse>),
+    /// A second factor must be presented with the returned `mfa_token`
+    MfaRequired(MfaChallenge),
+}
+
 impl AuthService {
     /// Creates a new authentication service instance.
     ///
@@ -123,12 +150,14 @@ impl AuthService {
     /// * `password_hasher` - Service for password hashing
     /// * `redis_client` - Client for Redis caching
     /// * `jwt_config` - Configuration for token lifetimes
+    /// * `mfa_service` - Second factor enrollment and verification
     pub fn new(
         repository: UserRepository,
         jwt_service: Arc<JwtService>,
         password_hasher: Arc<PasswordHasher>,
         redis_client: RedisClient,
         jwt_config: JwtConfig,
+        mfa_service: MfaService,
     ) -> Self {
         Self {
             repository,
@@ -136,6 +165,7 @@ impl AuthService {
             password_hasher,
             redis_client,
             jwt_config,
+            mfa_service,
         }
     }
 
@@ -188,7 +218,9 @@ impl AuthService {
         );
 
         // Generate tokens and store refresh token
-        let tokens = self.generate_and_store_tokens(&user, None, None, None).await?;
+        let tokens = self
+            .generate_and_store_tokens(&user, Uuid::new_v4(), &ClientInfo::default())
+            .await?;
 
         Ok(AuthResponse {
             tokens,
@@ -202,6 +234,9 @@ impl AuthService {
 
     /// Authenticates a user with email and password.
     ///
+    /// Users with an enrolled second factor, or whose role requires one,
+    /// get [`LoginOutcome::MfaRequired`] instead of tokens.
+    ///
     /// # Security
     ///
     /// Uses constant-time comparison for password verification.
@@ -210,10 +245,8 @@ impl AuthService {
         &self,
         email: &str,
         password: &str,
-        device_fingerprint: Option<String>,
-        ip_address: Option<String>,
-        user_agent: Option<String>,
-    ) -> Result<AuthResponse, ApiError> {
+        client: ClientInfo,
+    ) -> Result<LoginOutcome, ApiError> {
         // Find user by email
         let user = self
             .repository
@@ -238,20 +271,142 @@ impl AuthService {
             return Err(ApiError::InvalidCredentials);
         }
 
+        self.login_federated(user, client).await
+    }
+
+    /// Signs in a user whose identity was already established, by password
+    /// or by an identity provider (see [`super::sso_service`]).
+    ///
+    /// Second factors apply exactly as for password logins.
+    pub async fn login_federated(&self, user: User, client: ClientInfo) -> Result<LoginOutcome, ApiError> {
+        // Second factor enrolled or required by role: stop here
+        if let Some(challenge) = self.mfa_service.begin_login(&user, client.clone()).await? {
+            return Ok(LoginOutcome::MfaRequired(challenge));
+        }
+
+        let response = self.start_session(user, client).await?;
+
+        Ok(LoginOutcome::Authenticated(Box::new(response)))
+    }
+
+    // =========================================================================
+    // SECOND FACTOR
+    // =========================================================================
+
+    /// Completes a pending login with a TOTP or recovery code.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::InvalidToken` if `mfa_token` is unknown, expired or
+    ///   discarded after too many attempts
+    /// - `ApiError::AccountLocked` while the user has too many recent wrong
+    ///   second factors
+    /// - `ApiError::InvalidCredentials` if the code is wrong
+    pub async fn verify_mfa_code(&self, mfa_token: &str, code: &str) -> Result<AuthResponse, ApiError> {
+        let pending = self.mfa_service.pending_login(mfa_token).await?;
+
+        if !self.mfa_service.verify_code(pending.user_id, code).await? {
+            self.mfa_service.record_failure(mfa_token, &pending).await?;
+            return Err(ApiError::InvalidCredentials);
+        }
+
+        self.complete_mfa_login(mfa_token).await
+    }
+
+    /// WebAuthn options for a pending login.
+    pub async fn webauthn_login_options(&self, mfa_token: &str) -> Result<serde_json::Value, ApiError> {
+        let pending = self.mfa_service.pending_login(mfa_token).await?;
+
+        self.mfa_service.webauthn_login_options(mfa_token, &pending).await
+    }
+
+    /// Completes a pending login with a WebAuthn assertion.
+    pub async fn verify_mfa_webauthn(
+        &self,
+        mfa_token: &str,
+        credential: &AuthenticationCredential,
+    ) -> Result<AuthResponse, ApiError> {
+        let pending = self.mfa_service.pending_login(mfa_token).await?;
+
+        if !self.mfa_service.verify_webauthn(mfa_token, &pending, credential).await? {
+            self.mfa_service.record_failure(mfa_token, &pending).await?;
+            return Err(ApiError::InvalidCredentials);
+        }
+
+        self.complete_mfa_login(mfa_token).await
+    }
+
+    /// Completes a pending login once the user has enrolled a factor
+    /// through the `mfa_token` (role requires MFA, none enrolled yet).
+    pub async fn complete_mfa_enrollment(&self, mfa_token: &str) -> Result<AuthResponse, ApiError> {
+        let pending = self.mfa_service.pending_login(mfa_token).await?;
+
+        if !self.mfa_service.has_factors(pending.user_id).await? {
+            return Err(ApiError::BadRequest {
+                message: "Enroll a second factor first".to_string(),
+            });
+        }
+
+        self.complete_mfa_login(mfa_token).await
+    }
+
+    /// Issues tokens for a pending login whose second factor was accepted.
+    async fn complete_mfa_login(&self, mfa_token: &str) -> Result<AuthResponse, ApiError> {
+        let pending = self.mfa_service.finish_login(mfa_token).await?;
+
+        let user = self
+            .repository
+            .find_by_id(pending.user_id)
+            .await?
+            .ok_or(ApiError::InvalidToken)?;
+
+        self.start_session(user, pending.client).await
+    }
+
+    /// Records the login, issues tokens for a new session and caches it.
+    async fn start_session(&self, user: User, client: ClientInfo) -> Result<AuthResponse, ApiError> {
         // Update last login
         self.repository.update_last_login(user.user_id).await?;
 
         info!(
             user_id = %user.user_id,
-            email = %email,
+            email = %user.email,
             "User logged in successfully"
         );
 
+        // Checked before the new session's token exists
+        let new_device = self
+            .repository
+            .is_new_device(
+                user.user_id,
+                client.device_fingerprint.as_deref(),
+                client.user_agent.as_deref(),
+            )
+            .await?;
+
         // Generate tokens
+        let session_id = Uuid::new_v4();
         let tokens = self
-            .generate_and_store_tokens(&user, device_fingerprint, ip_address, user_agent)
+            .generate_and_store_tokens(&user, session_id, &client)
             .await?;
 
+        if new_device {
+            let event = NewDeviceLogin {
+                user_id: user.user_id,
+                email: user.email.clone(),
+                session_id,
+                device_fingerprint: client.device_fingerprint,
+                ip_address: client.ip_address,
+                user_agent: client.user_agent,
+                location: client.location,
+                occurred_at: Utc::now(),
+            };
+            if let Err(e) = self.repository.record_new_device_login(&event).await {
+                warn!(error = %e, "Failed to record new device login");
+                // Non-fatal: the notification is lost, the login is not
+            }
+        }
+
         // Cache session in Redis for quick validation
         let session_key = format!("session:{}", user.user_id);
         if let Err(e) = self
@@ -276,18 +431,34 @@ impl AuthService {
     /// Refreshes tokens using a valid refresh token.
     ///
     /// Implements **token rotation**: the old refresh token is revoked and
-    /// a new pair is issued.
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, ApiError> {
+    /// a new pair is issued in the same session. `client` updates the
+    /// session's IP and location.
+    ///
+    /// # Reuse Detection
+    ///
+    /// A token that was already rotated (or is being rotated by a
+    /// concurrent request) revokes the whole session.
+    pub async fn refresh_token(&self, refresh_token: &str, client: ClientInfo) -> Result<TokenPair, ApiError> {
         // Hash the provided token
         let token_hash = Self::hash_token(refresh_token);
 
-        // Find the token in database
+        // Find the token in database, revoked ones included
         let stored_token = self
             .repository
-            .find_refresh_token(&token_hash)
+            .find_refresh_token_by_hash(&token_hash)
             .await?
             .ok_or(ApiError::InvalidToken)?;
 
+        if stored_token.was_rotated() {
+            self.revoke_reused_family(stored_token.user_id, stored_token.family_id)
+                .await?;
+            return Err(ApiError::InvalidToken);
+        }
+
+        if stored_token.revoked_at.is_some() || stored_token.expires_at <= Utc::now() {
+            return Err(ApiError::InvalidToken);
+        }
+
         // Load user
         let user = self
             .repository
@@ -295,10 +466,13 @@ impl AuthService {
             .await?
             .ok_or(ApiError::InvalidToken)?;
 
-        // Revoke the old token (token rotation)
-        self.repository
-            .revoke_refresh_token(stored_token.token_id)
-            .await?;
+        // Revoke the old token (token rotation); losing the race to a
+        // concurrent refresh with the same token is a replay too
+        if !self.repository.rotate_refresh_token(stored_token.token_id).await? {
+            self.revoke_reused_family(stored_token.user_id, stored_token.family_id)
+                .await?;
+            return Err(ApiError::InvalidToken);
+        }
 
         info!(
             user_id = %user.user_id,
@@ -306,18 +480,62 @@ impl AuthService {
         );
 
         // Generate new tokens
+        let client = ClientInfo {
+            device_fingerprint: client.device_fingerprint.or(stored_token.device_fingerprint),
+            ip_address: client.ip_address.or(stored_token.ip_address),
+            user_agent: client.user_agent.or(stored_token.user_agent),
+            location: client.location.or(stored_token.location),
+        };
         let tokens = self
-            .generate_and_store_tokens(
-                &user,
-                stored_token.device_fingerprint,
-                stored_token.ip_address,
-                stored_token.user_agent,
-            )
+            .generate_and_store_tokens(&user, stored_token.family_id, &client)
             .await?;
 
         Ok(tokens)
     }
 
+    /// Ends a session whose rotated refresh token was presented again.
+    async fn revoke_reused_family(&self, user_id: Uuid, family_id: Uuid) -> Result<(), ApiError> {
+        let revoked = self.repository.revoke_token_family(family_id).await?;
+
+        warn!(
+            user_id = %user_id,
+            session_id = %family_id,
+            tokens_revoked = revoked,
+            "Refresh token reuse detected, session revoked"
+        );
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // SESSIONS
+    // =========================================================================
+
+    /// Lists the user's active sessions.
+    pub async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<UserSession>, ApiError> {
+        self.repository.list_sessions(user_id).await
+    }
+
+    /// Revokes one of the user's sessions (e.g. a lost device).
+    ///
+    /// Its refresh token stops working immediately; access tokens already
+    /// issued to it expire on their own (`access_token_ttl_seconds`).
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::NotFound` if the user has no active session with that ID
+    pub async fn revoke_session(&self, user_id: Uuid, session_id: Uuid) -> Result<(), ApiError> {
+        if !self.repository.revoke_session(user_id, session_id).await? {
+            return Err(ApiError::NotFound {
+                resource: format!("session:{}", session_id),
+            });
+        }
+
+        info!(user_id = %user_id, session_id = %session_id, "Session revoked");
+
+        Ok(())
+    }
+
     // =========================================================================
     // LOGOUT
     // =========================================================================
@@ -500,9 +718,8 @@ impl AuthService {
     async fn generate_and_store_tokens(
         &self,
         user: &User,
-        device_fingerprint: Option<String>,
-        ip_address: Option<String>,
-        user_agent: Option<String>,
+        family_id: Uuid,
+        client: &ClientInfo,
     ) -> Result<TokenPair, ApiError> {
         // Generate token pair (requires email for claims)
         let tokens = self
@@ -520,9 +737,11 @@ impl AuthService {
         let new_token = NewRefreshToken {
             user_id: user.user_id,
             token_hash,
-            device_fingerprint,
-            ip_address,
-            user_agent,
+            device_fingerprint: client.device_fingerprint.clone(),
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+            location: client.location.clone(),
+            family_id,
             expires_at,
         };
 
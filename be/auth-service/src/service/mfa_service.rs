@@ -0,0 +1,914 @@
+//! # Multi-Factor Authentication
+//!
+//! Second factors for the login flow and their enrollment:
+//!
+//! | Method | Enrollment | Verification |
+//! |--------|------------|--------------|
+//! | TOTP | `setup_totp` + `confirm_totp` (first code) | 6-digit code, ±30 s, no replay |
+//! | WebAuthn | `webauthn_register_options` + `register_webauthn` | Assertion signed by the key |
+//! | Recovery code | Issued with the first factor, `regenerate_recovery_codes` | Single use |
+//!
+//! ## Two-Step Login
+//!
+//! ```text
+//! POST /login ──> password OK ──> factors enrolled or role requires MFA?
+//!                                   │ no                     │ yes
+//!                                   ▼                        ▼
+//!                                 tokens          mfa_token (opaque, 5 min, Redis)
+//!                                                            │
+//!                     POST /mfa/verify or /mfa/webauthn/verify (max 5 failures)
+//!                                                            │
+//!                                                            ▼
+//!                                                          tokens
+//! ```
+//!
+//! Users whose role requires MFA (`MFA_REQUIRED_ROLES`) but who have no
+//! factor yet get an `mfa_token` with `enrollmentRequired`; it authorizes
+//! the enrollment endpoints only, and confirming a factor completes the
+//! login. Such users cannot remove their last factor.
+//!
+//! Wrong second factors are counted per user over a sliding 15-minute
+//! window, not per `mfa_token`, so entering the password again does not buy
+//! more guesses: once the window holds 5 failures, new logins and
+//! verifications are refused with `AccountLocked` until the oldest one ages
+//! out. Failed second factors are published as `LoginFailed` events.
+
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::{errors::ApiError, redis_client::RedisClient};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::totp;
+use super::webauthn::{self, AuthenticationCredential, RegistrationCredential, WebAuthnConfig};
+use crate::domain::events::{LoginFailed, LoginFailureReason};
+use crate::domain::{ClientInfo, MfaTotp, User, WebAuthnCredential};
+use crate::repository::MfaRepository;
+
+/// Lifetime of an `mfa_token` and of WebAuthn challenges.
+pub const MFA_PENDING_TTL_SECS: u64 = 300;
+
+/// Wrong second factors accepted per user within [`FAILURE_WINDOW_SECS`].
+const MAX_ATTEMPTS: i64 = 5;
+
+/// Sliding window over which wrong second factors are counted.
+const FAILURE_WINDOW_SECS: u64 = 900;
+
+/// Recovery codes issued at a time.
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Recovery code alphabet (no 0/O, 1/I/L).
+const RECOVERY_ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+
+/// Characters per recovery code, shown as two dash-separated halves.
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// MFA settings, read from the environment.
+#[derive(Clone)]
+pub struct MfaConfig {
+    /// AES-256 key encrypting TOTP secrets at rest
+    pub encryption_key: [u8; 32],
+    /// Roles that cannot log in without a second factor
+    pub required_roles: Vec<String>,
+    /// Relying party for WebAuthn; its name is also the TOTP issuer
+    pub webauthn: WebAuthnConfig,
+}
+
+impl MfaConfig {
+    /// Reads `MFA_ENCRYPTION_KEY` (base64, 32 bytes, required),
+    /// `MFA_REQUIRED_ROLES`, `WEBAUTHN_RP_ID`, `WEBAUTHN_RP_NAME` and
+    /// `WEBAUTHN_ORIGINS`.
+    pub fn from_env() -> Result<Self, String> {
+        let key = std::env::var("MFA_ENCRYPTION_KEY").map_err(|_| "MFA_ENCRYPTION_KEY is not set".to_string())?;
+        let encryption_key = STANDARD
+            .decode(key.trim())
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| "MFA_ENCRYPTION_KEY must be 32 bytes, base64 encoded".to_string())?;
+
+        let env_or = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+
+        Ok(Self {
+            encryption_key,
+            required_roles: parse_list(&env_or("MFA_REQUIRED_ROLES", "admin,instructor")),
+            webauthn: WebAuthnConfig {
+                rp_id: env_or("WEBAUTHN_RP_ID", "localhost"),
+                rp_name: env_or("WEBAUTHN_RP_NAME", "ACC LMS"),
+                origins: parse_list(&env_or("WEBAUTHN_ORIGINS", "http://localhost:5173")),
+            },
+        })
+    }
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// =============================================================================
+// TYPES
+// =============================================================================
+
+/// Second factor a user can present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MfaMethod {
+    Totp,
+    Webauthn,
+    RecoveryCode,
+}
+
+/// Returned by login instead of tokens when a second factor is needed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaChallenge {
+    /// Opaque token identifying the half-finished login
+    pub mfa_token: String,
+    /// Factors the user can present (empty when enrolling)
+    pub methods: Vec<MfaMethod>,
+    /// The role requires MFA and the user has no factor yet
+    pub enrollment_required: bool,
+    /// Seconds until `mfa_token` expires
+    pub expires_in: u64,
+}
+
+/// Login waiting for its second factor (stored in Redis).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMfaLogin {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: String,
+    #[serde(flatten)]
+    pub client: ClientInfo,
+}
+
+/// Secret shown once while setting up TOTP.
+#[derive(Debug, Clone)]
+pub struct TotpSetup {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI for the QR code
+    pub otpauth_uri: String,
+}
+
+/// Factors enrolled by a user.
+#[derive(Debug, Clone)]
+pub struct MfaStatus {
+    pub totp_enabled: bool,
+    pub webauthn_credentials: Vec<WebAuthnCredential>,
+    pub recovery_codes_remaining: i64,
+    /// The user's role cannot log in without MFA
+    pub required: bool,
+}
+
+// =============================================================================
+// SERVICE
+// =============================================================================
+
+/// Enrollment and verification of second factors.
+#[derive(Clone)]
+pub struct MfaService {
+    repository: MfaRepository,
+    redis_client: RedisClient,
+    cipher: SecretCipher,
+    config: MfaConfig,
+}
+
+impl MfaService {
+    pub fn new(repository: MfaRepository, redis_client: RedisClient, config: MfaConfig) -> Self {
+        Self {
+            repository,
+            redis_client,
+            cipher: SecretCipher::new(&config.encryption_key),
+            config,
+        }
+    }
+
+    /// Whether users with `role` must use a second factor.
+    pub fn role_requires_mfa(&self, role: &str) -> bool {
+        self.config.required_roles.iter().any(|r| r == role)
+    }
+
+    // =========================================================================
+    // LOGIN
+    // =========================================================================
+
+    /// Starts the second step of a login whose password was correct.
+    ///
+    /// Returns `None` when the user has no factor and their role does not
+    /// require one, so tokens can be issued right away.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::AccountLocked` while the user has too many recent wrong
+    ///   second factors
+    pub async fn begin_login(
+        &self,
+        user: &User,
+        client: ClientInfo,
+    ) -> Result<Option<MfaChallenge>, ApiError> {
+        self.ensure_attempts_left(user.user_id).await?;
+
+        let methods = self.enabled_methods(user.user_id).await?;
+        let required = self.role_requires_mfa(&user.role);
+
+        if methods.is_empty() && !required {
+            return Ok(None);
+        }
+
+        let mfa_token = random_token();
+        let pending = PendingMfaLogin {
+            user_id: user.user_id,
+            email: user.email.clone(),
+            role: user.role.clone(),
+            client,
+        };
+        self.redis_client
+            .set(&pending_key(&mfa_token), &pending, Some(Duration::from_secs(MFA_PENDING_TTL_SECS)))
+            .await?;
+
+        info!(user_id = %user.user_id, enrollment_required = methods.is_empty(), "Second factor required");
+
+        Ok(Some(MfaChallenge {
+            mfa_token,
+            enrollment_required: methods.is_empty(),
+            methods,
+            expires_in: MFA_PENDING_TTL_SECS,
+        }))
+    }
+
+    /// Looks up a pending login.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::InvalidToken` if the token is unknown or expired
+    pub async fn pending_login(&self, mfa_token: &str) -> Result<PendingMfaLogin, ApiError> {
+        self.redis_client
+            .get(&pending_key(mfa_token))
+            .await?
+            .ok_or(ApiError::InvalidToken)
+    }
+
+    /// Consumes a pending login once its second factor was accepted.
+    ///
+    /// Fails if a concurrent request already consumed it.
+    pub async fn finish_login(&self, mfa_token: &str) -> Result<PendingMfaLogin, ApiError> {
+        let pending = self.pending_login(mfa_token).await?;
+
+        if !self.redis_client.delete(&pending_key(mfa_token)).await? {
+            return Err(ApiError::InvalidToken);
+        }
+        let _ = self.redis_client.delete(&failures_key(pending.user_id)).await;
+
+        Ok(pending)
+    }
+
+    /// Checks a TOTP or recovery code for a pending login.
+    ///
+    /// Six digits are treated as a TOTP code, anything else as a recovery code.
+    /// Fails with `ApiError::AccountLocked` like [`Self::begin_login`].
+    pub async fn verify_code(&self, user_id: Uuid, code: &str) -> Result<bool, ApiError> {
+        self.ensure_attempts_left(user_id).await?;
+
+        let code = code.trim();
+        if code.len() == totp::TOTP_DIGITS as usize && code.bytes().all(|b| b.is_ascii_digit()) {
+            return self.verify_totp(user_id, code).await;
+        }
+
+        match normalize_recovery_code(code) {
+            Some(normalized) => self.repository.use_recovery_code(user_id, &hash(&normalized)).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Options for `navigator.credentials.get()` during a pending login.
+    pub async fn webauthn_login_options(
+        &self,
+        mfa_token: &str,
+        pending: &PendingMfaLogin,
+    ) -> Result<serde_json::Value, ApiError> {
+        let allow: Vec<Vec<u8>> = self
+            .repository
+            .list_webauthn_credentials(pending.user_id)
+            .await?
+            .into_iter()
+            .map(|credential| credential.raw_id)
+            .collect();
+
+        if allow.is_empty() {
+            return Err(ApiError::BadRequest {
+                message: "No WebAuthn credential registered".to_string(),
+            });
+        }
+
+        let challenge = self.store_challenge(&login_challenge_key(mfa_token)).await?;
+
+        Ok(webauthn::request_options(&self.config.webauthn, &challenge, &allow))
+    }
+
+    /// Checks a WebAuthn assertion for a pending login.
+    /// Fails with `ApiError::AccountLocked` like [`Self::begin_login`].
+    pub async fn verify_webauthn(
+        &self,
+        mfa_token: &str,
+        pending: &PendingMfaLogin,
+        credential: &AuthenticationCredential,
+    ) -> Result<bool, ApiError> {
+        self.ensure_attempts_left(pending.user_id).await?;
+
+        let Some(challenge) = self.take_challenge(&login_challenge_key(mfa_token)).await? else {
+            return Err(ApiError::BadRequest {
+                message: "Request a WebAuthn challenge first".to_string(),
+            });
+        };
+
+        let Ok(raw_id) = webauthn::decode(&credential.id) else {
+            return Ok(false);
+        };
+        let Some(stored) = self.repository.find_webauthn_credential(pending.user_id, &raw_id).await? else {
+            return Ok(false);
+        };
+
+        let sign_count = match webauthn::verify_assertion(
+            &self.config.webauthn,
+            credential,
+            &challenge,
+            &stored.public_key,
+            stored.sign_count as u32,
+        ) {
+            Ok(sign_count) => sign_count,
+            Err(e) => {
+                warn!(user_id = %pending.user_id, error = %e, "WebAuthn assertion rejected");
+                return Ok(false);
+            }
+        };
+
+        self.repository
+            .record_webauthn_use(stored.credential_id, stored.sign_count, i64::from(sign_count))
+            .await
+    }
+
+    /// Counts a wrong second factor and publishes `LoginFailed`.
+    ///
+    /// After [`MAX_ATTEMPTS`] within the window the pending login is
+    /// discarded and the user is locked out of second factors until the
+    /// oldest failure leaves the window.
+    pub async fn record_failure(&self, mfa_token: &str, pending: &PendingMfaLogin) -> Result<(), ApiError> {
+        let reason = self.count_failure(mfa_token, pending.user_id).await?;
+
+        self.repository
+            .record_login_failure(&LoginFailed {
+                attempted_email: pending.email.clone(),
+                user_id: Some(pending.user_id),
+                reason,
+                ip_address: pending.client.ip_address.clone(),
+                user_agent: pending.client.user_agent.clone(),
+                occurred_at: Utc::now(),
+            })
+            .await
+    }
+
+    /// Adds a failure to the user's window; discards the pending login once
+    /// the window is full.
+    async fn count_failure(&self, mfa_token: &str, user_id: Uuid) -> Result<LoginFailureReason, ApiError> {
+        let attempts = self
+            .redis_client
+            .record_in_window(&failures_key(user_id), Duration::from_secs(FAILURE_WINDOW_SECS))
+            .await?;
+
+        warn!(user_id = %user_id, attempts, "Failed second factor");
+
+        if attempts >= MAX_ATTEMPTS {
+            self.redis_client.delete(&pending_key(mfa_token)).await?;
+            return Ok(LoginFailureReason::TooManySecondFactorAttempts);
+        }
+        Ok(LoginFailureReason::InvalidSecondFactor)
+    }
+
+    /// Refuses second factors while the user's failure window is full.
+    async fn ensure_attempts_left(&self, user_id: Uuid) -> Result<(), ApiError> {
+        let window = Duration::from_secs(FAILURE_WINDOW_SECS);
+        let failures = self.redis_client.events_in_window(&failures_key(user_id), window).await?;
+
+        match locked_until(&failures, window) {
+            Some(until) => {
+                warn!(user_id = %user_id, %until, "Second factor locked");
+                Err(ApiError::AccountLocked { until })
+            }
+            None => Ok(()),
+        }
+    }
+
+    // =========================================================================
+    // ENROLLMENT
+    // =========================================================================
+
+    /// Factors enrolled by the user.
+    pub async fn status(&self, user_id: Uuid, role: &str) -> Result<MfaStatus, ApiError> {
+        Ok(MfaStatus {
+            totp_enabled: self.confirmed_totp(user_id).await?.is_some(),
+            webauthn_credentials: self.repository.list_webauthn_credentials(user_id).await?,
+            recovery_codes_remaining: self.repository.count_unused_recovery_codes(user_id).await?,
+            required: self.role_requires_mfa(role),
+        })
+    }
+
+    /// Whether the user has at least one factor besides recovery codes.
+    pub async fn has_factors(&self, user_id: Uuid) -> Result<bool, ApiError> {
+        Ok(self.confirmed_totp(user_id).await?.is_some()
+            || !self.repository.list_webauthn_credentials(user_id).await?.is_empty())
+    }
+
+    /// Generates a new TOTP secret, to be confirmed with a first code.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::Conflict` if TOTP is already enabled
+    pub async fn setup_totp(&self, user_id: Uuid, email: &str) -> Result<TotpSetup, ApiError> {
+        let secret = totp::generate_secret();
+
+        if !self.repository.upsert_pending_totp(user_id, &self.cipher.encrypt(&secret)).await? {
+            return Err(ApiError::Conflict {
+                resource: "TOTP is already enabled".to_string(),
+            });
+        }
+
+        Ok(TotpSetup {
+            secret: totp::encode_secret(&secret),
+            otpauth_uri: totp::otpauth_uri(&secret, &self.config.webauthn.rp_name, email),
+        })
+    }
+
+    /// Enables TOTP once the user proves they can generate codes.
+    ///
+    /// Returns recovery codes if this is the user's first factor.
+    pub async fn confirm_totp(&self, user_id: Uuid, code: &str) -> Result<Option<Vec<String>>, ApiError> {
+        let totp_row = self
+            .repository
+            .find_totp(user_id)
+            .await?
+            .filter(|row| row.confirmed_at.is_none())
+            .ok_or_else(|| ApiError::BadRequest {
+                message: "Start TOTP setup first".to_string(),
+            })?;
+
+        let secret = self.cipher.decrypt(&totp_row.secret_encrypted)?;
+        let step = totp::verify(&secret, code, now(), None).ok_or_else(|| ApiError::BadRequest {
+            message: "Invalid TOTP code".to_string(),
+        })?;
+
+        let first_factor = !self.has_factors(user_id).await?;
+        if !self.repository.confirm_totp(user_id, step as i64).await? {
+            return Err(ApiError::Conflict {
+                resource: "TOTP is already enabled".to_string(),
+            });
+        }
+
+        info!(user_id = %user_id, "TOTP enabled");
+
+        self.initial_recovery_codes(user_id, first_factor).await
+    }
+
+    /// Disables TOTP.
+    pub async fn disable_totp(&self, user_id: Uuid, role: &str) -> Result<(), ApiError> {
+        if self.confirmed_totp(user_id).await?.is_some() {
+            let webauthn = self.repository.list_webauthn_credentials(user_id).await?;
+            self.ensure_not_last_factor(role, webauthn.is_empty())?;
+        }
+
+        if !self.repository.delete_totp(user_id).await? {
+            return Err(ApiError::NotFound {
+                resource: "TOTP authenticator".to_string(),
+            });
+        }
+
+        info!(user_id = %user_id, "TOTP disabled");
+
+        self.cleanup_if_no_factors(user_id).await
+    }
+
+    /// Options for `navigator.credentials.create()`.
+    pub async fn webauthn_register_options(&self, user_id: Uuid, email: &str) -> Result<serde_json::Value, ApiError> {
+        let exclude: Vec<Vec<u8>> = self
+            .repository
+            .list_webauthn_credentials(user_id)
+            .await?
+            .into_iter()
+            .map(|credential| credential.raw_id)
+            .collect();
+
+        let challenge = self.store_challenge(&register_challenge_key(user_id)).await?;
+
+        Ok(webauthn::creation_options(
+            &self.config.webauthn,
+            user_id.as_bytes(),
+            email,
+            email,
+            &challenge,
+            &exclude,
+        ))
+    }
+
+    /// Stores the credential created by the browser.
+    ///
+    /// Returns recovery codes if this is the user's first factor.
+    pub async fn register_webauthn(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        credential: &RegistrationCredential,
+    ) -> Result<(WebAuthnCredential, Option<Vec<String>>), ApiError> {
+        let challenge = self
+            .take_challenge(&register_challenge_key(user_id))
+            .await?
+            .ok_or_else(|| ApiError::BadRequest {
+                message: "Request registration options first".to_string(),
+            })?;
+
+        let key = webauthn::verify_registration(&self.config.webauthn, credential, &challenge)
+            .map_err(|e| ApiError::BadRequest { message: e.to_string() })?;
+
+        let first_factor = !self.has_factors(user_id).await?;
+        let stored = self
+            .repository
+            .create_webauthn_credential(
+                user_id,
+                &key.raw_id,
+                &key.public_key,
+                i64::from(key.sign_count),
+                name,
+                &key.transports,
+            )
+            .await?;
+
+        info!(user_id = %user_id, credential_id = %stored.credential_id, "WebAuthn credential registered");
+
+        let codes = self.initial_recovery_codes(user_id, first_factor).await?;
+        Ok((stored, codes))
+    }
+
+    /// Removes a WebAuthn credential.
+    pub async fn delete_webauthn(&self, user_id: Uuid, role: &str, credential_id: Uuid) -> Result<(), ApiError> {
+        let credentials = self.repository.list_webauthn_credentials(user_id).await?;
+        if !credentials.iter().any(|c| c.credential_id == credential_id) {
+            return Err(ApiError::NotFound {
+                resource: format!("webauthn_credential:{}", credential_id),
+            });
+        }
+
+        let totp_enabled = self.confirmed_totp(user_id).await?.is_some();
+        self.ensure_not_last_factor(role, credentials.len() == 1 && !totp_enabled)?;
+
+        self.repository.delete_webauthn_credential(user_id, credential_id).await?;
+
+        info!(user_id = %user_id, credential_id = %credential_id, "WebAuthn credential removed");
+
+        self.cleanup_if_no_factors(user_id).await
+    }
+
+    /// Replaces the user's recovery codes.
+    pub async fn regenerate_recovery_codes(&self, user_id: Uuid) -> Result<Vec<String>, ApiError> {
+        if !self.has_factors(user_id).await? {
+            return Err(ApiError::BadRequest {
+                message: "Enable TOTP or WebAuthn first".to_string(),
+            });
+        }
+
+        self.issue_recovery_codes(user_id).await
+    }
+
+    // =========================================================================
+    // HELPERS
+    // =========================================================================
+
+    async fn enabled_methods(&self, user_id: Uuid) -> Result<Vec<MfaMethod>, ApiError> {
+        let mut methods = Vec::new();
+
+        if self.confirmed_totp(user_id).await?.is_some() {
+            methods.push(MfaMethod::Totp);
+        }
+        if !self.repository.list_webauthn_credentials(user_id).await?.is_empty() {
+            methods.push(MfaMethod::Webauthn);
+        }
+        if !methods.is_empty() && self.repository.count_unused_recovery_codes(user_id).await? > 0 {
+            methods.push(MfaMethod::RecoveryCode);
+        }
+
+        Ok(methods)
+    }
+
+    async fn confirmed_totp(&self, user_id: Uuid) -> Result<Option<MfaTotp>, ApiError> {
+        Ok(self
+            .repository
+            .find_totp(user_id)
+            .await?
+            .filter(|row| row.confirmed_at.is_some()))
+    }
+
+    async fn verify_totp(&self, user_id: Uuid, code: &str) -> Result<bool, ApiError> {
+        let Some(totp_row) = self.confirmed_totp(user_id).await? else {
+            return Ok(false);
+        };
+
+        let secret = self.cipher.decrypt(&totp_row.secret_encrypted)?;
+        let last_used = totp_row.last_used_step.map(|step| step as u64);
+
+        match totp::verify(&secret, code, now(), last_used) {
+            Some(step) => self.repository.consume_totp_step(user_id, step as i64).await,
+            None => Ok(false),
+        }
+    }
+
+    fn ensure_not_last_factor(&self, role: &str, is_last: bool) -> Result<(), ApiError> {
+        if is_last && self.role_requires_mfa(role) {
+            return Err(ApiError::BadRequest {
+                message: "Multi-factor authentication is required for your role; add another factor first"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Recovery codes are useless without a factor to recover.
+    async fn cleanup_if_no_factors(&self, user_id: Uuid) -> Result<(), ApiError> {
+        if !self.has_factors(user_id).await? {
+            self.repository.delete_recovery_codes(user_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn initial_recovery_codes(&self, user_id: Uuid, first_factor: bool) -> Result<Option<Vec<String>>, ApiError> {
+        if first_factor {
+            Ok(Some(self.issue_recovery_codes(user_id).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn issue_recovery_codes(&self, user_id: Uuid) -> Result<Vec<String>, ApiError> {
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes
+            .iter()
+            .filter_map(|code| normalize_recovery_code(code))
+            .map(|code| hash(&code))
+            .collect();
+
+        self.repository.replace_recovery_codes(user_id, &hashes).await?;
+
+        info!(user_id = %user_id, "Recovery codes issued");
+
+        Ok(codes)
+    }
+
+    async fn store_challenge(&self, key: &str) -> Result<Vec<u8>, ApiError> {
+        let mut challenge = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut challenge);
+
+        self.redis_client
+            .set(key, &webauthn::b64(&challenge), Some(Duration::from_secs(MFA_PENDING_TTL_SECS)))
+            .await?;
+
+        Ok(challenge)
+    }
+
+    /// Challenges are single use.
+    async fn take_challenge(&self, key: &str) -> Result<Option<Vec<u8>>, ApiError> {
+        let Some(challenge) = self.redis_client.get::<String>(key).await? else {
+            return Ok(None);
+        };
+        if !self.redis_client.delete(key).await? {
+            return Ok(None);
+        }
+
+        Ok(webauthn::decode(&challenge).ok())
+    }
+}
+
+// =============================================================================
+// SECRET ENCRYPTION
+// =============================================================================
+
+/// AES-256-GCM encryption of TOTP secrets; output is `nonce (12) ‖ ciphertext`.
+#[derive(Clone)]
+struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(key.into()),
+        }
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let mut out = nonce.to_vec();
+        out.extend(
+            self.cipher
+                .encrypt(Nonce::from_slice(&nonce), plaintext)
+                .expect("AES-GCM encryption of a short secret cannot fail"),
+        );
+        out
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, ApiError> {
+        if data.len() < 12 {
+            return Err(ApiError::InternalError {
+                message: "Encrypted TOTP secret is truncated".to_string(),
+            });
+        }
+
+        let (nonce, ciphertext) = data.split_at(12);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| ApiError::InternalError {
+                message: "Cannot decrypt TOTP secret (wrong MFA_ENCRYPTION_KEY?)".to_string(),
+            })
+    }
+}
+
+// =============================================================================
+// FUNCTIONS
+// =============================================================================
+
+fn pending_key(mfa_token: &str) -> String {
+    format!("mfa:pending:{}", hash(mfa_token))
+}
+
+fn failures_key(user_id: Uuid) -> String {
+    format!("mfa:failures:{}", user_id)
+}
+
+/// When the user may try again, if the failures (oldest first) fill the
+/// window: once the oldest failure that keeps it full ages out.
+fn locked_until(failures: &[DateTime<Utc>], window: Duration) -> Option<DateTime<Utc>> {
+    let max = MAX_ATTEMPTS as usize;
+    (failures.len() >= max)
+        .then(|| failures[failures.len() - max] + chrono::Duration::from_std(window).unwrap_or_default())
+}
+
+fn login_challenge_key(mfa_token: &str) -> String {
+    format!("mfa:webauthn:login:{}", hash(mfa_token))
+}
+
+fn register_challenge_key(user_id: Uuid) -> String {
+    format!("mfa:webauthn:register:{}", user_id)
+}
+
+/// SHA-256 hex; Redis keys and stored codes never contain the secret itself.
+fn hash(value: &str) -> String {
+    hex::encode(Sha256::digest(value.as_bytes()))
+}
+
+fn random_token() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+fn now() -> u64 {
+    Utc::now().timestamp().max(0) as u64
+}
+
+/// Codes formatted `XXXXX-XXXXX`.
+fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let chars: String = (0..RECOVERY_CODE_LENGTH)
+                .map(|_| RECOVERY_ALPHABET[rng.gen_range(0..RECOVERY_ALPHABET.len())] as char)
+                .collect();
+            format!("{}-{}", &chars[..RECOVERY_CODE_LENGTH / 2], &chars[RECOVERY_CODE_LENGTH / 2..])
+        })
+        .collect()
+}
+
+/// Uppercases and strips separators so `abcde fghij` matches `ABCDE-FGHIJ`.
+fn normalize_recovery_code(code: &str) -> Option<String> {
+    let normalized: String = code
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    (normalized.len() == RECOVERY_CODE_LENGTH && normalized.bytes().all(|b| RECOVERY_ALPHABET.contains(&b)))
+        .then_some(normalized)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_cipher_roundtrip() {
+        let cipher = SecretCipher::new(&[7u8; 32]);
+        let secret = totp::generate_secret();
+
+        let encrypted = cipher.encrypt(&secret);
+        assert_ne!(&encrypted[12..], secret.as_slice());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), secret);
+
+        // Random nonce: same secret encrypts differently
+        assert_ne!(cipher.encrypt(&secret), encrypted);
+        // Another key cannot decrypt
+        assert!(SecretCipher::new(&[8u8; 32]).decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_recovery_codes_format_and_normalization() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        for code in &codes {
+            assert_eq!(code.len(), RECOVERY_CODE_LENGTH + 1);
+            assert_eq!(&code[5..6], "-");
+            assert!(normalize_recovery_code(code).is_some());
+        }
+
+        let code = &codes[0];
+        let typed = code.replace('-', " ").to_lowercase();
+        assert_eq!(normalize_recovery_code(&typed), normalize_recovery_code(code));
+
+        assert_eq!(normalize_recovery_code("12345"), None);
+        assert_eq!(normalize_recovery_code("OOOOO-IIIII"), None);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list(" admin, instructor ,,"), vec!["admin", "instructor"]);
+        assert!(parse_list("").is_empty());
+    }
+
+    #[test]
+    fn test_locked_until_oldest_failure_leaves_window() {
+        let window = Duration::from_secs(FAILURE_WINDOW_SECS);
+        let start = Utc::now();
+        let failures: Vec<_> = (0..MAX_ATTEMPTS + 1)
+            .map(|i| start + chrono::Duration::minutes(i))
+            .collect();
+
+        assert_eq!(locked_until(&failures[..MAX_ATTEMPTS as usize - 1], window), None);
+        assert_eq!(
+            locked_until(&failures[..MAX_ATTEMPTS as usize], window),
+            Some(start + chrono::Duration::seconds(FAILURE_WINDOW_SECS as i64))
+        );
+        // With one failure too many, unlocking waits for the second oldest
+        assert_eq!(
+            locked_until(&failures, window),
+            Some(failures[1] + chrono::Duration::seconds(FAILURE_WINDOW_SECS as i64))
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis at REDIS_URL"]
+    async fn test_new_mfa_token_does_not_reset_failures() {
+        let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        let redis_client = RedisClient::new(&shared::config::RedisConfig { url: redis_url, pool_size: 1 })
+            .await
+            .unwrap();
+        // Never queried: the lockout is decided before any database access
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let config = MfaConfig {
+            encryption_key: [7u8; 32],
+            required_roles: Vec::new(),
+            webauthn: WebAuthnConfig {
+                rp_id: "localhost".to_string(),
+                rp_name: "ACC LMS".to_string(),
+                origins: vec!["http://localhost:5173".to_string()],
+            },
+        };
+        let service = MfaService::new(MfaRepository::new(pool), redis_client, config);
+        let user_id = Uuid::new_v4();
+
+        // Each failure on a fresh login, as if the password were re-entered
+        for _ in 0..MAX_ATTEMPTS {
+            service.count_failure(&random_token(), user_id).await.unwrap();
+        }
+
+        let result = service.verify_code(user_id, "123456").await;
+        assert!(matches!(result, Err(ApiError::AccountLocked { .. })));
+    }
+}
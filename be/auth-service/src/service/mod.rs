@@ -44,12 +44,20 @@
 //! |---------------------|-------------------------------|-----------------------------|
 //! | `register`          | Email uniqueness, password    | Create user, send email     |
 //! | `login`             | Credentials verification      | Update last_login, session  |
-//! | `refresh_token`     | Token validity                | Rotate tokens               |
+//! | `refresh_token`     | Token validity, reuse         | Rotate tokens, revoke family|
 //! | `logout`            | Token ownership               | Revoke token, blacklist     |
 //! | `logout_all`        | User authentication           | Revoke all, blacklist all   |
 //! | `verify_email`      | Token validity                | Update email_verified       |
 //! | `forgot_password`   | Email existence               | Generate reset token        |
 //! | `reset_password`    | Token validity, password      | Update password, clear token|
+//! | `verify_mfa_*`      | Pending login, second factor  | Issue tokens, `LoginFailed` |
+//! | `login_federated`   | User resolved by SSO          | Same as `login`             |
+//! | `revoke_session`    | Session ownership             | Revoke session's tokens     |
+//!
+//! Second factors live in [`mfa_service`], with the protocol details in
+//! [`totp`] (RFC 6238) and [`webauthn`] (passkeys and security keys).
+//! Single sign-on lives in [`sso_service`], on top of the OpenID Connect
+//! client in [`oidc`].
 //!
 //! ## Related Documentation
 //!
@@ -58,5 +66,12 @@
 //! - Repository: [`crate::repository::UserRepository`]
 
 pub mod auth_service;
+pub mod mfa_service;
+pub mod oidc;
+pub mod sso_service;
+pub mod totp;
+pub mod webauthn;
 
-pub use auth_service::AuthService;
+pub use auth_service::{AuthService, LoginOutcome};
+pub use mfa_service::{MfaConfig, MfaService};
+pub use sso_service::{SsoConfig, SsoService};
@@ -0,0 +1,497 @@
+//! # OpenID Connect Client
+//!
+//! Relying-party side of the OIDC authorization-code flow with PKCE
+//! (RFC 7636), as used by Google, Microsoft Entra ID and institutional
+//! identity providers (Keycloak, Shibboleth OIDC, ADFS, ...).
+//!
+//! ```text
+//! authorization_request ──> IdP login page ──> redirect_uri?code&state
+//!   · discovery document                          │
+//!   · state, nonce, PKCE verifier                 ▼
+//!                                          exchange_code
+//!                                            · POST token_endpoint (code + verifier)
+//!                                            · id_token signature (JWKS, by kid)
+//!                                            · iss, aud, exp, nonce
+//! ```
+//!
+//! ## Caching
+//!
+//! Discovery documents and JWKS are cached per issuer for an hour. An ID
+//! token signed with an unknown `kid` triggers one JWKS refetch, so IdP key
+//! rotation is picked up without waiting for the cache to expire.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use rand::RngCore;
+use reqwest::Url;
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+/// How long discovery documents and JWKS are reused.
+const METADATA_TTL: Duration = Duration::from_secs(3600);
+
+/// Clock skew tolerated on `exp`/`iat`.
+const CLOCK_LEEWAY_SECS: u64 = 60;
+
+/// Signature algorithms accepted for ID tokens (never HMAC).
+const ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// OIDC errors.
+#[derive(Debug, Error)]
+pub enum OidcError {
+    #[error("Discovery failed: {0}")]
+    Discovery(String),
+
+    #[error("Token exchange failed: {0}")]
+    TokenExchange(String),
+
+    #[error("Invalid ID token: {0}")]
+    InvalidIdToken(String),
+}
+
+/// Client registration at one identity provider.
+#[derive(Debug, Clone)]
+pub struct OidcClientSettings {
+    /// Issuer identifier, e.g. `https://accounts.google.com`
+    pub issuer: String,
+    pub client_id: String,
+    /// `None` for public clients relying on PKCE only
+    pub client_secret: Option<String>,
+    /// Where the IdP sends the user back (registered at the IdP)
+    pub redirect_uri: String,
+    /// Space-separated scopes; `openid` is always included
+    pub scopes: String,
+}
+
+/// Authorization URL plus the values to keep until the callback.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: String,
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+/// Subset of the discovery document we use.
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+/// Identity asserted by a validated ID token.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdTokenClaims {
+    /// Stable user identifier at the IdP
+    pub sub: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Some IdPs send `"true"` as a string
+    #[serde(default, deserialize_with = "bool_or_string")]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub given_name: Option<String>,
+    #[serde(default)]
+    pub family_name: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+struct CachedIssuer {
+    metadata: ProviderMetadata,
+    jwks: JwkSet,
+    fetched_at: Instant,
+}
+
+/// OIDC relying party shared by all configured providers.
+#[derive(Clone)]
+pub struct OidcClient {
+    http: reqwest::Client,
+    cache: Arc<RwLock<HashMap<String, Arc<CachedIssuer>>>>,
+}
+
+impl OidcClient {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("Failed to build HTTP client"),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Builds the URL the browser is sent to.
+    pub async fn authorization_request(&self, settings: &OidcClientSettings) -> Result<AuthorizationRequest, OidcError> {
+        let issuer = self.issuer(&settings.issuer, false).await?;
+
+        let state = random_string();
+        let nonce = random_string();
+        let code_verifier = random_string();
+
+        let scopes = if settings.scopes.split_whitespace().any(|s| s == "openid") {
+            settings.scopes.clone()
+        } else {
+            format!("openid {}", settings.scopes)
+        };
+
+        let url = Url::parse_with_params(
+            &issuer.metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", settings.client_id.as_str()),
+                ("redirect_uri", settings.redirect_uri.as_str()),
+                ("scope", scopes.trim()),
+                ("state", state.as_str()),
+                ("nonce", nonce.as_str()),
+                ("code_challenge", pkce_challenge(&code_verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| OidcError::Discovery(format!("invalid authorization_endpoint: {}", e)))?;
+
+        Ok(AuthorizationRequest {
+            url: url.into(),
+            state,
+            nonce,
+            code_verifier,
+        })
+    }
+
+    /// Exchanges the authorization code and validates the ID token.
+    pub async fn exchange_code(
+        &self,
+        settings: &OidcClientSettings,
+        code: &str,
+        code_verifier: &str,
+        expected_nonce: &str,
+    ) -> Result<IdTokenClaims, OidcError> {
+        let issuer = self.issuer(&settings.issuer, false).await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", settings.redirect_uri.as_str()),
+            ("client_id", settings.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        if let Some(secret) = &settings.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        let response = self
+            .http
+            .post(&issuer.metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| OidcError::TokenExchange(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(OidcError::TokenExchange(format!("{}: {}", status, body)));
+        }
+
+        let id_token = response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| OidcError::TokenExchange(e.to_string()))?
+            .id_token
+            .ok_or_else(|| OidcError::TokenExchange("response has no id_token".to_string()))?;
+
+        self.validate_id_token(settings, &id_token, expected_nonce).await
+    }
+
+    async fn validate_id_token(
+        &self,
+        settings: &OidcClientSettings,
+        id_token: &str,
+        expected_nonce: &str,
+    ) -> Result<IdTokenClaims, OidcError> {
+        let header = jsonwebtoken::decode_header(id_token).map_err(|e| OidcError::InvalidIdToken(e.to_string()))?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(OidcError::InvalidIdToken(format!("algorithm {:?} not allowed", header.alg)));
+        }
+
+        let mut issuer = self.issuer(&settings.issuer, false).await?;
+        let mut jwk = find_key(&issuer.jwks, header.kid.as_deref());
+        if jwk.is_none() {
+            // Key rotated at the IdP since we cached the JWKS
+            issuer = self.issuer(&settings.issuer, true).await?;
+            jwk = find_key(&issuer.jwks, header.kid.as_deref());
+        }
+        let jwk = jwk.ok_or_else(|| OidcError::InvalidIdToken("signing key not in JWKS".to_string()))?;
+        let key = DecodingKey::from_jwk(jwk).map_err(|e| OidcError::InvalidIdToken(e.to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&settings.client_id]);
+        validation.set_issuer(&[&issuer.metadata.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = CLOCK_LEEWAY_SECS;
+
+        let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| OidcError::InvalidIdToken(e.to_string()))?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(expected_nonce) {
+            return Err(OidcError::InvalidIdToken("nonce mismatch".to_string()));
+        }
+
+        Ok(claims)
+    }
+
+    /// Discovery document and JWKS of an issuer, from cache when fresh.
+    async fn issuer(&self, issuer: &str, force_refresh: bool) -> Result<Arc<CachedIssuer>, OidcError> {
+        if !force_refresh {
+            if let Some(cached) = self.cache.read().await.get(issuer) {
+                if cached.fetched_at.elapsed() < METADATA_TTL {
+                    return Ok(cached.clone());
+                }
+            }
+        }
+
+        let discovery_url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+        let metadata: ProviderMetadata = self.fetch_json(&discovery_url).await?;
+
+        // The document must describe the issuer we were configured with
+        if metadata.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+            return Err(OidcError::Discovery(format!(
+                "issuer mismatch: configured {}, discovered {}",
+                issuer, metadata.issuer
+            )));
+        }
+
+        let jwks: JwkSet = self.fetch_json(&metadata.jwks_uri).await?;
+
+        let cached = Arc::new(CachedIssuer {
+            metadata,
+            jwks,
+            fetched_at: Instant::now(),
+        });
+        self.cache.write().await.insert(issuer.to_string(), cached.clone());
+
+        Ok(cached)
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, OidcError> {
+        self.http
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| OidcError::Discovery(format!("{}: {}", url, e)))?
+            .json()
+            .await
+            .map_err(|e| OidcError::Discovery(format!("{}: {}", url, e)))
+    }
+}
+
+fn find_key<'a>(jwks: &'a JwkSet, kid: Option<&str>) -> Option<&'a jsonwebtoken::jwk::Jwk> {
+    match kid {
+        Some(kid) => jwks.find(kid),
+        // Without kid only an unambiguous single-key set can be used
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+}
+
+/// 32 random bytes, base64url (also a valid 43-character PKCE verifier).
+fn random_string() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// `S256` code challenge of a PKCE verifier.
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+
+    Ok(match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(value) => value,
+        BoolOrString::String(value) => value.eq_ignore_ascii_case("true"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+    use p256::ecdsa::SigningKey;
+    use p256::pkcs8::{EncodePrivateKey, LineEnding};
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Local OIDC provider: discovery, JWKS and token endpoint.
+    struct MockProvider {
+        server: MockServer,
+        key: SigningKey,
+    }
+
+    impl MockProvider {
+        async fn start() -> Self {
+            let server = MockServer::start().await;
+            let key = SigningKey::random(&mut rand::rngs::OsRng);
+            let point = key.verifying_key().to_encoded_point(false);
+
+            Mock::given(method("GET"))
+                .and(path("/.well-known/openid-configuration"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "issuer": server.uri(),
+                    "authorization_endpoint": format!("{}/authorize", server.uri()),
+                    "token_endpoint": format!("{}/token", server.uri()),
+                    "jwks_uri": format!("{}/jwks", server.uri()),
+                })))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/jwks"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "keys": [{
+                        "kty": "EC",
+                        "crv": "P-256",
+                        "kid": "key-1",
+                        "alg": "ES256",
+                        "use": "sig",
+                        "x": URL_SAFE_NO_PAD.encode(point.x().unwrap()),
+                        "y": URL_SAFE_NO_PAD.encode(point.y().unwrap()),
+                    }]
+                })))
+                .mount(&server)
+                .await;
+
+            Self { server, key }
+        }
+
+        fn settings(&self) -> OidcClientSettings {
+            OidcClientSettings {
+                issuer: self.server.uri(),
+                client_id: "acc-lms".to_string(),
+                client_secret: Some("secret".to_string()),
+                redirect_uri: "http://localhost:5173/auth/sso/callback".to_string(),
+                scopes: "email profile".to_string(),
+            }
+        }
+
+        fn id_token(&self, claims: serde_json::Value) -> String {
+            let pem = self.key.to_pkcs8_pem(LineEnding::LF).unwrap();
+            let mut header = Header::new(Algorithm::ES256);
+            header.kid = Some("key-1".to_string());
+
+            jsonwebtoken::encode(&header, &claims, &EncodingKey::from_ec_pem(pem.as_bytes()).unwrap()).unwrap()
+        }
+
+        async fn issue(&self, claims: serde_json::Value) {
+            Mock::given(method("POST"))
+                .and(path("/token"))
+                .and(body_string_contains("code_verifier=verifier"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "access_token": "at",
+                    "token_type": "Bearer",
+                    "id_token": self.id_token(claims),
+                })))
+                .mount(&self.server)
+                .await;
+        }
+
+        fn claims(&self, audience: &str, nonce: &str) -> serde_json::Value {
+            let now = chrono::Utc::now().timestamp();
+            json!({
+                "iss": self.server.uri(),
+                "aud": audience,
+                "sub": "idp-user-1",
+                "email": "ana@uni.edu",
+                "email_verified": "true",
+                "given_name": "Ana",
+                "iat": now,
+                "exp": now + 300,
+                "nonce": nonce,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorization_request_uses_pkce() {
+        let provider = MockProvider::start().await;
+        let request = OidcClient::new(Duration::from_secs(5))
+            .authorization_request(&provider.settings())
+            .await
+            .unwrap();
+
+        let url = Url::parse(&request.url).unwrap();
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(url.path(), "/authorize");
+        assert_eq!(params["scope"], "openid email profile");
+        assert_eq!(params["state"], request.state);
+        assert_eq!(params["code_challenge"], pkce_challenge(&request.code_verifier));
+        assert_eq!(params["code_challenge_method"], "S256");
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_validates_id_token() {
+        let provider = MockProvider::start().await;
+        provider.issue(provider.claims("acc-lms", "n-1")).await;
+
+        let claims = OidcClient::new(Duration::from_secs(5))
+            .exchange_code(&provider.settings(), "code", "verifier", "n-1")
+            .await
+            .unwrap();
+
+        assert_eq!(claims.sub, "idp-user-1");
+        assert_eq!(claims.email.as_deref(), Some("ana@uni.edu"));
+        assert!(claims.email_verified);
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_rejects_wrong_nonce_and_audience() {
+        let client = OidcClient::new(Duration::from_secs(5));
+
+        let provider = MockProvider::start().await;
+        provider.issue(provider.claims("acc-lms", "n-1")).await;
+        let result = client.exchange_code(&provider.settings(), "code", "verifier", "other").await;
+        assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+
+        let provider = MockProvider::start().await;
+        provider.issue(provider.claims("someone-else", "n-1")).await;
+        let result = client.exchange_code(&provider.settings(), "code", "verifier", "n-1").await;
+        assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+    }
+}
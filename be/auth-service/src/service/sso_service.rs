@@ -0,0 +1,434 @@
+//! # Single Sign-On
+//!
+//! Sign-in through OpenID Connect providers ("Sign in with Google /
+//! Microsoft", or a university's own IdP), configured per tenant in
+//! `auth.identity_providers`.
+//!
+//! ## Flow (SPA)
+//!
+//! ```text
+//! POST /sso/{slug}/authorize ──> authorizationUrl (state, nonce, PKCE in Redis, 10 min)
+//!        browser ──> IdP ──> SSO_REDIRECT_URI?code&state
+//! POST /sso/{slug}/callback {code, state}
+//!        └─> code exchange + ID token validation ──> user ──> tokens or MFA challenge
+//! ```
+//!
+//! ## Resolving the User
+//!
+//! 1. Identity (`provider`, `sub`) already linked: that user
+//! 2. Authorize called with an access token: link to the signed-in user
+//! 3. Verified email of an existing account: link to it, only if the
+//!    provider's `allowed_domains` vouch for that domain; otherwise the user
+//!    must sign in and link explicitly (2)
+//! 4. `jit_provisioning`: create the user with `default_role`
+//!
+//! Anything else is rejected. When `allowed_domains` is set, the ID token
+//! must carry a verified email in one of them.
+//!
+//! Federated sign-ins still go through MFA like password logins.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use shared::{auth::password::PasswordHasher, errors::ApiError, redis_client::RedisClient};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::oidc::{IdTokenClaims, OidcClient, OidcClientSettings, OidcError};
+use crate::domain::{IdentityProvider, NewUser, User};
+use crate::repository::{SsoRepository, UserRepository};
+
+/// Time the user has to complete the sign-in at the IdP.
+const STATE_TTL_SECS: u64 = 600;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// SSO settings, read from the environment.
+#[derive(Debug, Clone)]
+pub struct SsoConfig {
+    /// Frontend callback page registered at every IdP
+    pub redirect_uri: String,
+    /// Timeout of discovery, JWKS and token requests
+    pub http_timeout: Duration,
+}
+
+impl SsoConfig {
+    /// Reads `SSO_REDIRECT_URI` and `SSO_HTTP_TIMEOUT_SECS`.
+    pub fn from_env() -> Self {
+        Self {
+            redirect_uri: std::env::var("SSO_REDIRECT_URI")
+                .unwrap_or_else(|_| "http://localhost:5173/auth/sso/callback".to_string()),
+            http_timeout: Duration::from_secs(
+                std::env::var("SSO_HTTP_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+            ),
+        }
+    }
+}
+
+/// Sign-in waiting for the IdP callback (stored in Redis).
+#[derive(Debug, Serialize, Deserialize)]
+struct SsoState {
+    slug: String,
+    code_verifier: String,
+    nonce: String,
+    /// Signed-in user linking this provider to their account
+    link_user_id: Option<Uuid>,
+}
+
+// =============================================================================
+// SERVICE
+// =============================================================================
+
+/// Federated sign-in and account linking.
+#[derive(Clone)]
+pub struct SsoService {
+    repository: SsoRepository,
+    users: UserRepository,
+    oidc: OidcClient,
+    redis_client: RedisClient,
+    password_hasher: Arc<PasswordHasher>,
+    config: SsoConfig,
+}
+
+impl SsoService {
+    pub fn new(
+        repository: SsoRepository,
+        users: UserRepository,
+        redis_client: RedisClient,
+        password_hasher: Arc<PasswordHasher>,
+        config: SsoConfig,
+    ) -> Self {
+        Self {
+            repository,
+            users,
+            oidc: OidcClient::new(config.http_timeout),
+            redis_client,
+            password_hasher,
+            config,
+        }
+    }
+
+    /// Providers offered on the login page of a tenant.
+    pub async fn providers(&self, tenant_id: Option<Uuid>) -> Result<Vec<IdentityProvider>, ApiError> {
+        self.repository.list_providers(tenant_id).await
+    }
+
+    /// Starts a sign-in and returns the IdP authorization URL.
+    ///
+    /// With `link_user_id`, the callback links the IdP identity to that
+    /// user instead of resolving the account by email.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::NotFound` if the provider does not exist or is disabled
+    /// - `ApiError::ServiceUnavailable` if its discovery document is unreachable
+    pub async fn authorize(&self, slug: &str, link_user_id: Option<Uuid>) -> Result<String, ApiError> {
+        let provider = self.provider(slug).await?;
+        let settings = self.settings(&provider)?;
+
+        let request = self
+            .oidc
+            .authorization_request(&settings)
+            .await
+            .map_err(|e| oidc_error(&provider, e))?;
+
+        let state = SsoState {
+            slug: provider.slug,
+            code_verifier: request.code_verifier,
+            nonce: request.nonce,
+            link_user_id,
+        };
+        self.redis_client
+            .set(&state_key(&request.state), &state, Some(Duration::from_secs(STATE_TTL_SECS)))
+            .await?;
+
+        Ok(request.url)
+    }
+
+    /// Completes a sign-in with the code returned by the IdP.
+    ///
+    /// # Errors
+    ///
+    /// - `ApiError::InvalidToken` if `state` is unknown, expired, already
+    ///   used or belongs to another provider
+    /// - `ApiError::InvalidCredentials` if the code or ID token is rejected
+    /// - `ApiError::InsufficientPermissions` if the email is not allowed or
+    ///   no account can be linked or provisioned
+    /// - `ApiError::Conflict` if the identity is linked to another account,
+    ///   or the email belongs to an account that has to link it explicitly
+    pub async fn callback(&self, slug: &str, code: &str, state: &str) -> Result<User, ApiError> {
+        let key = state_key(state);
+        let pending: SsoState = self.redis_client.get(&key).await?.ok_or(ApiError::InvalidToken)?;
+
+        // Single use: only the request that deletes the state may continue
+        if !self.redis_client.delete(&key).await? || pending.slug != slug {
+            return Err(ApiError::InvalidToken);
+        }
+
+        let provider = self.provider(slug).await?;
+        let settings = self.settings(&provider)?;
+
+        let claims = self
+            .oidc
+            .exchange_code(&settings, code, &pending.code_verifier, &pending.nonce)
+            .await
+            .map_err(|e| oidc_error(&provider, e))?;
+
+        let user = self.resolve_user(&provider, &claims, pending.link_user_id).await?;
+
+        info!(
+            user_id = %user.user_id,
+            provider = %provider.slug,
+            "Federated sign-in"
+        );
+
+        Ok(user)
+    }
+
+    /// Finds, links or provisions the user behind an ID token.
+    async fn resolve_user(
+        &self,
+        provider: &IdentityProvider,
+        claims: &IdTokenClaims,
+        link_user_id: Option<Uuid>,
+    ) -> Result<User, ApiError> {
+        let email = verified_email(claims);
+
+        if !provider.allowed_domains.is_empty()
+            && !email.is_some_and(|email| email_domain_allowed(&provider.allowed_domains, email))
+        {
+            warn!(provider = %provider.slug, "SSO email not in allowed domains");
+            return Err(ApiError::InsufficientPermissions);
+        }
+
+        // 1. Known identity
+        if let Some(user) = self
+            .repository
+            .find_user_by_identity(provider.provider_id, &claims.sub)
+            .await?
+        {
+            if link_user_id.is_some_and(|id| id != user.user_id) {
+                return Err(ApiError::Conflict {
+                    resource: "Identity already linked to another account".to_string(),
+                });
+            }
+            self.repository
+                .record_identity_login(provider.provider_id, &claims.sub, email)
+                .await?;
+            return Ok(user);
+        }
+
+        // 2. Explicit link by a signed-in user
+        if let Some(user_id) = link_user_id {
+            let user = self.users.find_by_id(user_id).await?.ok_or(ApiError::InvalidToken)?;
+            self.link(provider, claims, &user).await?;
+            return Ok(user);
+        }
+
+        let email = email.ok_or_else(|| {
+            warn!(provider = %provider.slug, "SSO sign-in without verified email");
+            ApiError::InsufficientPermissions
+        })?;
+
+        // 3. Existing account with the same verified email
+        if let Some(user) = self.users.find_by_email(email).await? {
+            if !auto_link_allowed(&provider.allowed_domains, email) {
+                warn!(provider = %provider.slug, "SSO email matches an unlinked account");
+                return Err(ApiError::Conflict {
+                    resource: "Account exists; sign in and link this provider".to_string(),
+                });
+            }
+            self.link(provider, claims, &user).await?;
+            return Ok(user);
+        }
+
+        // 4. Just-in-time provisioning
+        if !provider.jit_provisioning {
+            warn!(provider = %provider.slug, "SSO sign-in for unknown user, provisioning disabled");
+            return Err(ApiError::InsufficientPermissions);
+        }
+
+        let (first_name, last_name) = display_names(claims, email);
+        let new_user = NewUser {
+            email: email.to_string(),
+            // Unusable until the user sets one through password reset
+            hashed_password: self.password_hasher.hash(&random_password())?,
+            first_name,
+            last_name,
+            role: provider.default_role.clone(),
+        };
+
+        let user = self
+            .repository
+            .provision_user(new_user, provider.provider_id, &claims.sub)
+            .await?;
+
+        info!(
+            user_id = %user.user_id,
+            provider = %provider.slug,
+            role = %user.role,
+            "User provisioned through SSO"
+        );
+
+        Ok(user)
+    }
+
+    async fn link(&self, provider: &IdentityProvider, claims: &IdTokenClaims, user: &User) -> Result<(), ApiError> {
+        self.repository
+            .link_identity(user.user_id, provider.provider_id, &claims.sub, verified_email(claims))
+            .await?;
+
+        info!(
+            user_id = %user.user_id,
+            provider = %provider.slug,
+            "SSO identity linked"
+        );
+
+        Ok(())
+    }
+
+    async fn provider(&self, slug: &str) -> Result<IdentityProvider, ApiError> {
+        self.repository
+            .find_provider(slug)
+            .await?
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("identity provider:{}", slug),
+            })
+    }
+
+    /// OIDC client settings, with the secret read from the environment.
+    fn settings(&self, provider: &IdentityProvider) -> Result<OidcClientSettings, ApiError> {
+        let client_secret = match &provider.client_secret_env {
+            Some(name) => Some(std::env::var(name).map_err(|_| ApiError::InternalError {
+                message: format!("{} is not set for identity provider {}", name, provider.slug),
+            })?),
+            None => None,
+        };
+
+        Ok(OidcClientSettings {
+            issuer: provider.issuer.clone(),
+            client_id: provider.client_id.clone(),
+            client_secret,
+            redirect_uri: self.config.redirect_uri.clone(),
+            scopes: provider.scopes.clone(),
+        })
+    }
+}
+
+// =============================================================================
+// HELPER FUNCTIONS
+// =============================================================================
+
+fn state_key(state: &str) -> String {
+    format!("sso:state:{}", state)
+}
+
+fn oidc_error(provider: &IdentityProvider, error: OidcError) -> ApiError {
+    warn!(provider = %provider.slug, error = %error, "OIDC error");
+
+    match error {
+        OidcError::Discovery(_) => ApiError::ServiceUnavailable {
+            service: provider.display_name.clone(),
+        },
+        OidcError::TokenExchange(_) | OidcError::InvalidIdToken(_) => ApiError::InvalidCredentials,
+    }
+}
+
+/// Email of the ID token, only if the IdP verified it.
+fn verified_email(claims: &IdTokenClaims) -> Option<&str> {
+    claims
+        .email
+        .as_deref()
+        .filter(|email| claims.email_verified && !email.is_empty())
+}
+
+fn email_domain_allowed(allowed_domains: &[String], email: &str) -> bool {
+    email
+        .rsplit_once('@')
+        .is_some_and(|(_, domain)| allowed_domains.iter().any(|d| d.eq_ignore_ascii_case(domain)))
+}
+
+/// Whether an IdP may take over an existing account by email alone: only
+/// for domains the provider is trusted to own, never for public providers.
+fn auto_link_allowed(allowed_domains: &[String], email: &str) -> bool {
+    !allowed_domains.is_empty() && email_domain_allowed(allowed_domains, email)
+}
+
+/// First and last name for a provisioned user.
+fn display_names(claims: &IdTokenClaims, email: &str) -> (String, String) {
+    if let Some(given_name) = &claims.given_name {
+        return (given_name.clone(), claims.family_name.clone().unwrap_or_default());
+    }
+
+    match claims.name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => match name.split_once(' ') {
+            Some((first, last)) => (first.to_string(), last.trim().to_string()),
+            None => (name.to_string(), String::new()),
+        },
+        None => (email.split('@').next().unwrap_or(email).to_string(), String::new()),
+    }
+}
+
+fn random_password() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(value: serde_json::Value) -> IdTokenClaims {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_verified_email_requires_email_verified() {
+        let verified = claims(serde_json::json!({"sub": "1", "email": "a@uni.edu", "email_verified": true}));
+        let unverified = claims(serde_json::json!({"sub": "1", "email": "a@uni.edu"}));
+
+        assert_eq!(verified_email(&verified), Some("a@uni.edu"));
+        assert_eq!(verified_email(&unverified), None);
+    }
+
+    #[test]
+    fn test_email_domain_allowed() {
+        let allowed = vec!["uni.edu".to_string()];
+
+        assert!(email_domain_allowed(&allowed, "ana@UNI.edu"));
+        assert!(!email_domain_allowed(&allowed, "ana@alumni.uni.edu"));
+        assert!(!email_domain_allowed(&allowed, "ana@uni.edu.evil.com"));
+        assert!(!email_domain_allowed(&allowed, "uni.edu"));
+    }
+
+    #[test]
+    fn test_auto_link_requires_allowed_domain() {
+        let allowed = vec!["uni.edu".to_string()];
+
+        assert!(auto_link_allowed(&allowed, "ana@uni.edu"));
+        assert!(!auto_link_allowed(&allowed, "ana@gmail.com"));
+        // Providers without allowed domains (Google, Microsoft) never auto-link
+        assert!(!auto_link_allowed(&[], "ana@uni.edu"));
+    }
+
+    #[test]
+    fn test_display_names_fallbacks() {
+        let given = claims(serde_json::json!({"sub": "1", "given_name": "Ana", "family_name": "Pérez"}));
+        let full = claims(serde_json::json!({"sub": "1", "name": "Ana María Pérez"}));
+        let none = claims(serde_json::json!({"sub": "1"}));
+
+        assert_eq!(display_names(&given, "x@uni.edu"), ("Ana".into(), "Pérez".into()));
+        assert_eq!(display_names(&full, "x@uni.edu"), ("Ana".into(), "María Pérez".into()));
+        assert_eq!(display_names(&none, "ana.perez@uni.edu"), ("ana.perez".into(), String::new()));
+    }
+}
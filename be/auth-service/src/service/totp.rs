@@ -0,0 +1,156 @@
+//! # TOTP (RFC 6238)
+//!
+//! Time-based one-time passwords as generated by authenticator apps
+//! (Google Authenticator, Authy, 1Password, ...).
+//!
+//! | Parameter | Value |
+//! |-----------|-------|
+//! | Algorithm | HMAC-SHA1 (the only one all apps support) |
+//! | Digits | 6 |
+//! | Period | 30 seconds |
+//! | Secret | 20 random bytes, base32 in the `otpauth://` URI |
+//! | Skew | ±1 period accepted |
+//!
+//! A code is accepted once: callers pass the last used time step and store
+//! the returned one, so a code observed by an attacker cannot be replayed
+//! within its validity window.
+
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+/// Code length.
+pub const TOTP_DIGITS: u32 = 6;
+
+/// Seconds per time step.
+pub const TOTP_PERIOD: u64 = 30;
+
+/// Secret length in bytes (160 bits, as recommended by RFC 4226).
+const SECRET_LENGTH: usize = 20;
+
+/// Time steps accepted before and after the current one (clock skew).
+const ALLOWED_SKEW: u64 = 1;
+
+/// Generates a random secret.
+pub fn generate_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_LENGTH];
+    rand::thread_rng().fill_bytes(&mut secret);
+    secret
+}
+
+/// Base32 form of a secret, for manual entry in authenticator apps.
+pub fn encode_secret(secret: &[u8]) -> String {
+    BASE32_NOPAD.encode(secret)
+}
+
+/// Builds the `otpauth://` URI shown as a QR code.
+pub fn otpauth_uri(secret: &[u8], issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        encode_secret(secret),
+        percent_encode(issuer),
+        TOTP_DIGITS,
+        TOTP_PERIOD
+    )
+}
+
+/// Time step of a Unix timestamp.
+pub fn time_step(unix_seconds: u64) -> u64 {
+    unix_seconds / TOTP_PERIOD
+}
+
+/// Code for a time step (HOTP of RFC 4226 with the step as counter).
+pub fn code_at(secret: &[u8], step: u64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Dynamic truncation
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    format!("{:0width$}", binary % 10u32.pow(TOTP_DIGITS), width = TOTP_DIGITS as usize)
+}
+
+/// Checks a code against the steps around `unix_seconds`.
+///
+/// Returns the matching step, or `None` if the code is wrong or belongs to
+/// a step not after `last_used_step` (replay).
+pub fn verify(secret: &[u8], code: &str, unix_seconds: u64, last_used_step: Option<u64>) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let current = time_step(unix_seconds);
+    let first = current.saturating_sub(ALLOWED_SKEW);
+
+    (first..=current + ALLOWED_SKEW)
+        .filter(|step| last_used_step.is_none_or(|last| *step > last))
+        .find(|step| constant_time_eq(code_at(secret, *step).as_bytes(), code.as_bytes()))
+}
+
+/// Compares without short-circuiting on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Percent-encodes a URI label component.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 appendix B secret for HMAC-SHA1
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // Last 6 digits of the RFC's 8-digit values
+        assert_eq!(code_at(RFC_SECRET, time_step(59)), "287082");
+        assert_eq!(code_at(RFC_SECRET, time_step(1_111_111_109)), "081804");
+        assert_eq!(code_at(RFC_SECRET, time_step(1_234_567_890)), "005924");
+        assert_eq!(code_at(RFC_SECRET, time_step(2_000_000_000)), "279037");
+    }
+
+    #[test]
+    fn test_verify_skew_and_replay() {
+        let now = 1_111_111_109;
+        let previous = code_at(RFC_SECRET, time_step(now) - 1);
+
+        let step = verify(RFC_SECRET, &previous, now, None).unwrap();
+        assert_eq!(step, time_step(now) - 1);
+
+        // Same code again is a replay
+        assert_eq!(verify(RFC_SECRET, &previous, now, Some(step)), None);
+        // Two periods away is outside the skew window
+        let stale = code_at(RFC_SECRET, time_step(now) - 2);
+        assert_eq!(verify(RFC_SECRET, &stale, now, None), None);
+        assert_eq!(verify(RFC_SECRET, "12345", now, None), None);
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let uri = otpauth_uri(RFC_SECRET, "ACC LMS", "ana@example.com");
+
+        assert!(uri.starts_with("otpauth://totp/ACC%20LMS:ana%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"));
+        assert!(uri.contains("&issuer=ACC%20LMS"));
+    }
+}
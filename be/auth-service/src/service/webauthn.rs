@@ -0,0 +1,564 @@
+//! # WebAuthn (Passkeys and Security Keys)
+//!
+//! Relying-party side of the W3C Web Authentication API: builds the options
+//! passed to `navigator.credentials.create()` / `.get()` and verifies the
+//! credentials the browser returns (serialized with `PublicKeyCredential.toJSON()`,
+//! binary fields as base64url).
+//!
+//! ## Ceremonies
+//!
+//! ```text
+//! Registration                              Authentication
+//! ────────────                              ──────────────
+//! creation_options(challenge) ──> browser   request_options(challenge) ──> browser
+//!                                   │                                        │
+//! verify_registration <── attestationObject verify_assertion <── authenticatorData
+//!   · clientData type/challenge/origin        · clientData type/challenge/origin
+//!   · rpIdHash, user present                  · rpIdHash, user present
+//!   · credential ID + COSE public key         · signature over authData ‖ SHA-256(clientData)
+//!                                             · signature counter increased
+//! ```
+//!
+//! ## Supported Keys
+//!
+//! | COSE alg | Algorithm | Typical authenticators |
+//! |----------|-----------|------------------------|
+//! | -7 | ES256 (ECDSA P-256, SHA-256) | Passkeys, security keys, Touch ID, Android |
+//! | -257 | RS256 (RSASSA-PKCS1-v1_5, SHA-256) | Windows Hello |
+//!
+//! Attestation is requested as `none`: the authenticator's make and model
+//! are not verified, only possession of the registered key.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ciborium::Value as CborValue;
+use p256::ecdsa::signature::Verifier;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// COSE algorithm identifier of ES256.
+pub const COSE_ALG_ES256: i64 = -7;
+
+/// COSE algorithm identifier of RS256.
+pub const COSE_ALG_RS256: i64 = -257;
+
+/// Milliseconds the browser waits for the user.
+const CEREMONY_TIMEOUT_MS: u64 = 300_000;
+
+/// Authenticator data flag: user present.
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Authenticator data flag: attested credential data included.
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// WebAuthn verification errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebAuthnError {
+    #[error("Malformed credential: {0}")]
+    Malformed(String),
+
+    #[error("Unexpected ceremony type: {0}")]
+    WrongType(String),
+
+    #[error("Challenge mismatch")]
+    ChallengeMismatch,
+
+    #[error("Origin not allowed: {0}")]
+    OriginNotAllowed(String),
+
+    #[error("Relying party ID mismatch")]
+    RpIdMismatch,
+
+    #[error("User presence not confirmed")]
+    UserNotPresent,
+
+    #[error("Unsupported public key algorithm: {0}")]
+    UnsupportedAlgorithm(i64),
+
+    #[error("Invalid signature")]
+    InvalidSignature,
+
+    #[error("Signature counter did not increase (possible cloned authenticator)")]
+    CounterRegression,
+}
+
+/// Relying party settings.
+#[derive(Debug, Clone)]
+pub struct WebAuthnConfig {
+    /// Domain the credentials are scoped to, e.g. `lms.example.com`
+    pub rp_id: String,
+    /// Name shown by the browser
+    pub rp_name: String,
+    /// Origins allowed to run ceremonies, e.g. `https://lms.example.com`
+    pub origins: Vec<String>,
+}
+
+// =============================================================================
+// CLIENT CREDENTIALS (PublicKeyCredential.toJSON())
+// =============================================================================
+
+/// Credential returned by `navigator.credentials.create()`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationCredential {
+    pub id: String,
+    pub response: AttestationResponse,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// Credential returned by `navigator.credentials.get()`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationCredential {
+    pub id: String,
+    pub response: AssertionResponse,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ceremony: String,
+    challenge: String,
+    origin: String,
+}
+
+/// Key produced by a successful registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredKey {
+    /// Credential ID chosen by the authenticator
+    pub raw_id: Vec<u8>,
+    /// COSE_Key encoded public key
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+    pub transports: Vec<String>,
+}
+
+// =============================================================================
+// OPTIONS
+// =============================================================================
+
+/// Options for `navigator.credentials.create({ publicKey })`.
+///
+/// `exclude` lists credentials already registered by the user so the same
+/// authenticator is not registered twice.
+pub fn creation_options(
+    config: &WebAuthnConfig,
+    user_handle: &[u8],
+    user_name: &str,
+    display_name: &str,
+    challenge: &[u8],
+    exclude: &[Vec<u8>],
+) -> serde_json::Value {
+    json!({
+        "rp": { "id": config.rp_id, "name": config.rp_name },
+        "user": {
+            "id": b64(user_handle),
+            "name": user_name,
+            "displayName": display_name,
+        },
+        "challenge": b64(challenge),
+        "pubKeyCredParams": [
+            { "type": "public-key", "alg": COSE_ALG_ES256 },
+            { "type": "public-key", "alg": COSE_ALG_RS256 },
+        ],
+        "timeout": CEREMONY_TIMEOUT_MS,
+        "attestation": "none",
+        "authenticatorSelection": {
+            "residentKey": "preferred",
+            "userVerification": "preferred",
+        },
+        "excludeCredentials": exclude
+            .iter()
+            .map(|id| json!({ "type": "public-key", "id": b64(id) }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Options for `navigator.credentials.get({ publicKey })` restricted to
+/// the user's credentials.
+pub fn request_options(config: &WebAuthnConfig, challenge: &[u8], allow: &[Vec<u8>]) -> serde_json::Value {
+    json!({
+        "rpId": config.rp_id,
+        "challenge": b64(challenge),
+        "timeout": CEREMONY_TIMEOUT_MS,
+        "userVerification": "preferred",
+        "allowCredentials": allow
+            .iter()
+            .map(|id| json!({ "type": "public-key", "id": b64(id) }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+// =============================================================================
+// VERIFICATION
+// =============================================================================
+
+/// Verifies a registration and extracts the new credential.
+pub fn verify_registration(
+    config: &WebAuthnConfig,
+    credential: &RegistrationCredential,
+    expected_challenge: &[u8],
+) -> Result<RegisteredKey, WebAuthnError> {
+    let client_data_raw = decode(&credential.response.client_data_json)?;
+    check_client_data(config, &client_data_raw, "webauthn.create", expected_challenge)?;
+
+    let attestation: CborValue = ciborium::from_reader(decode(&credential.response.attestation_object)?.as_slice())
+        .map_err(|e| WebAuthnError::Malformed(format!("attestation object: {}", e)))?;
+    let auth_data = cbor_map_get(&attestation, &CborValue::Text("authData".to_string()))
+        .and_then(CborValue::as_bytes)
+        .ok_or_else(|| WebAuthnError::Malformed("attestation object has no authData".to_string()))?;
+
+    let parsed = parse_authenticator_data(config, auth_data)?;
+    if parsed.flags & FLAG_ATTESTED_CREDENTIAL == 0 {
+        return Err(WebAuthnError::Malformed("no attested credential data".to_string()));
+    }
+
+    // aaguid (16) | credentialIdLength (2) | credentialId | credentialPublicKey
+    let rest = &auth_data[37..];
+    if rest.len() < 18 {
+        return Err(WebAuthnError::Malformed("attested credential data too short".to_string()));
+    }
+    let id_len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+    let raw_id = rest
+        .get(18..18 + id_len)
+        .ok_or_else(|| WebAuthnError::Malformed("credential ID truncated".to_string()))?
+        .to_vec();
+
+    // The key is a CBOR item followed by optional extensions; keep its bytes only
+    let key_bytes = &rest[18 + id_len..];
+    let mut reader = key_bytes;
+    let key: CborValue = ciborium::from_reader(&mut reader)
+        .map_err(|e| WebAuthnError::Malformed(format!("credential public key: {}", e)))?;
+    let public_key = key_bytes[..key_bytes.len() - reader.len()].to_vec();
+
+    // Rejects unsupported algorithms now rather than at first login
+    CosePublicKey::from_cbor(&key)?;
+
+    if decode(&credential.id)? != raw_id {
+        return Err(WebAuthnError::Malformed("credential ID does not match authenticator data".to_string()));
+    }
+
+    Ok(RegisteredKey {
+        raw_id,
+        public_key,
+        sign_count: parsed.sign_count,
+        transports: credential.response.transports.clone(),
+    })
+}
+
+/// Verifies an authentication assertion made with a registered key.
+///
+/// Returns the new signature counter, to be stored.
+pub fn verify_assertion(
+    config: &WebAuthnConfig,
+    credential: &AuthenticationCredential,
+    expected_challenge: &[u8],
+    public_key: &[u8],
+    stored_sign_count: u32,
+) -> Result<u32, WebAuthnError> {
+    let client_data_raw = decode(&credential.response.client_data_json)?;
+    check_client_data(config, &client_data_raw, "webauthn.get", expected_challenge)?;
+
+    let auth_data = decode(&credential.response.authenticator_data)?;
+    let parsed = parse_authenticator_data(config, &auth_data)?;
+
+    let key: CborValue = ciborium::from_reader(public_key)
+        .map_err(|e| WebAuthnError::Malformed(format!("stored public key: {}", e)))?;
+
+    let mut signed = auth_data.clone();
+    signed.extend_from_slice(&Sha256::digest(&client_data_raw));
+
+    CosePublicKey::from_cbor(&key)?.verify(&signed, &decode(&credential.response.signature)?)?;
+
+    // Authenticators without a counter always report 0
+    if (parsed.sign_count != 0 || stored_sign_count != 0) && parsed.sign_count <= stored_sign_count {
+        return Err(WebAuthnError::CounterRegression);
+    }
+
+    Ok(parsed.sign_count)
+}
+
+fn check_client_data(
+    config: &WebAuthnConfig,
+    raw: &[u8],
+    expected_type: &str,
+    expected_challenge: &[u8],
+) -> Result<(), WebAuthnError> {
+    let client_data: ClientData = serde_json::from_slice(raw)
+        .map_err(|e| WebAuthnError::Malformed(format!("clientDataJSON: {}", e)))?;
+
+    if client_data.ceremony != expected_type {
+        return Err(WebAuthnError::WrongType(client_data.ceremony));
+    }
+    if client_data.challenge != b64(expected_challenge) {
+        return Err(WebAuthnError::ChallengeMismatch);
+    }
+    if !config.origins.contains(&client_data.origin) {
+        return Err(WebAuthnError::OriginNotAllowed(client_data.origin));
+    }
+
+    Ok(())
+}
+
+struct AuthenticatorData {
+    flags: u8,
+    sign_count: u32,
+}
+
+/// Checks the fixed 37-byte prefix: rpIdHash (32) | flags (1) | signCount (4).
+fn parse_authenticator_data(config: &WebAuthnConfig, data: &[u8]) -> Result<AuthenticatorData, WebAuthnError> {
+    if data.len() < 37 {
+        return Err(WebAuthnError::Malformed("authenticator data too short".to_string()));
+    }
+
+    if data[..32] != Sha256::digest(config.rp_id.as_bytes())[..] {
+        return Err(WebAuthnError::RpIdMismatch);
+    }
+
+    let flags = data[32];
+    if flags & FLAG_USER_PRESENT == 0 {
+        return Err(WebAuthnError::UserNotPresent);
+    }
+
+    Ok(AuthenticatorData {
+        flags,
+        sign_count: u32::from_be_bytes([data[33], data[34], data[35], data[36]]),
+    })
+}
+
+// =============================================================================
+// COSE KEYS
+// =============================================================================
+
+enum CosePublicKey {
+    Es256(p256::ecdsa::VerifyingKey),
+    Rs256(rsa::RsaPublicKey),
+}
+
+impl CosePublicKey {
+    /// Parses a COSE_Key (RFC 9053): kty 2 (EC2) with crv P-256, or kty 3 (RSA).
+    fn from_cbor(key: &CborValue) -> Result<Self, WebAuthnError> {
+        let int = |label: i64| cbor_map_get(key, &CborValue::Integer(label.into()));
+        let bytes = |label: i64, name: &str| {
+            int(label)
+                .and_then(CborValue::as_bytes)
+                .ok_or_else(|| WebAuthnError::Malformed(format!("COSE key has no {}", name)))
+        };
+
+        let alg = int(3)
+            .and_then(CborValue::as_integer)
+            .and_then(|alg| i64::try_from(alg).ok())
+            .ok_or_else(|| WebAuthnError::Malformed("COSE key has no alg".to_string()))?;
+
+        match alg {
+            COSE_ALG_ES256 => {
+                let mut point = vec![0x04];
+                point.extend_from_slice(bytes(-2, "x")?);
+                point.extend_from_slice(bytes(-3, "y")?);
+
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(&point)
+                    .map(Self::Es256)
+                    .map_err(|_| WebAuthnError::Malformed("invalid P-256 point".to_string()))
+            }
+            COSE_ALG_RS256 => {
+                let n = rsa::BigUint::from_bytes_be(bytes(-1, "n")?);
+                let e = rsa::BigUint::from_bytes_be(bytes(-2, "e")?);
+
+                rsa::RsaPublicKey::new(n, e)
+                    .map(Self::Rs256)
+                    .map_err(|e| WebAuthnError::Malformed(format!("invalid RSA key: {}", e)))
+            }
+            other => Err(WebAuthnError::UnsupportedAlgorithm(other)),
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), WebAuthnError> {
+        match self {
+            Self::Es256(key) => {
+                // WebAuthn ECDSA signatures are ASN.1 DER encoded
+                let signature = p256::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| WebAuthnError::InvalidSignature)?;
+                key.verify(message, &signature).map_err(|_| WebAuthnError::InvalidSignature)
+            }
+            Self::Rs256(key) => {
+                let verifying_key = rsa::pkcs1v15::VerifyingKey::<Sha256>::new(key.clone());
+                let signature = rsa::pkcs1v15::Signature::try_from(signature)
+                    .map_err(|_| WebAuthnError::InvalidSignature)?;
+                verifying_key.verify(message, &signature).map_err(|_| WebAuthnError::InvalidSignature)
+            }
+        }
+    }
+}
+
+fn cbor_map_get<'a>(map: &'a CborValue, key: &CborValue) -> Option<&'a CborValue> {
+    map.as_map()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// base64url without padding, as used by WebAuthn JSON.
+pub fn b64(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Decodes base64url, tolerating padding.
+pub fn decode(value: &str) -> Result<Vec<u8>, WebAuthnError> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|e| WebAuthnError::Malformed(format!("base64url: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::{signature::Signer, SigningKey};
+
+    const ORIGIN: &str = "https://lms.example.com";
+
+    fn config() -> WebAuthnConfig {
+        WebAuthnConfig {
+            rp_id: "lms.example.com".to_string(),
+            rp_name: "ACC LMS".to_string(),
+            origins: vec![ORIGIN.to_string()],
+        }
+    }
+
+    fn cbor(value: &CborValue) -> Vec<u8> {
+        let mut out = Vec::new();
+        ciborium::into_writer(value, &mut out).unwrap();
+        out
+    }
+
+    fn client_data(ceremony: &str, challenge: &[u8], origin: &str) -> String {
+        b64(json!({ "type": ceremony, "challenge": b64(challenge), "origin": origin }).to_string().as_bytes())
+    }
+
+    fn auth_data(flags: u8, counter: u32) -> Vec<u8> {
+        let mut data = Sha256::digest(b"lms.example.com").to_vec();
+        data.push(flags);
+        data.extend_from_slice(&counter.to_be_bytes());
+        data
+    }
+
+    /// Simulates an authenticator registering an ES256 key.
+    fn register(key: &SigningKey, challenge: &[u8]) -> RegistrationCredential {
+        let point = key.verifying_key().to_encoded_point(false);
+        let cose_key = CborValue::Map(vec![
+            (CborValue::Integer(1.into()), CborValue::Integer(2.into())),
+            (CborValue::Integer(3.into()), CborValue::Integer((-7).into())),
+            (CborValue::Integer((-1).into()), CborValue::Integer(1.into())),
+            (CborValue::Integer((-2).into()), CborValue::Bytes(point.x().unwrap().to_vec())),
+            (CborValue::Integer((-3).into()), CborValue::Bytes(point.y().unwrap().to_vec())),
+        ]);
+
+        let raw_id = vec![7u8; 16];
+        let mut data = auth_data(FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL, 0);
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&(raw_id.len() as u16).to_be_bytes());
+        data.extend_from_slice(&raw_id);
+        data.extend_from_slice(&cbor(&cose_key));
+
+        let attestation = CborValue::Map(vec![
+            (CborValue::Text("fmt".to_string()), CborValue::Text("none".to_string())),
+            (CborValue::Text("attStmt".to_string()), CborValue::Map(vec![])),
+            (CborValue::Text("authData".to_string()), CborValue::Bytes(data)),
+        ]);
+
+        RegistrationCredential {
+            id: b64(&raw_id),
+            response: AttestationResponse {
+                client_data_json: client_data("webauthn.create", challenge, ORIGIN),
+                attestation_object: b64(&cbor(&attestation)),
+                transports: vec!["internal".to_string()],
+            },
+        }
+    }
+
+    /// Simulates an authenticator signing an assertion.
+    fn assert(key: &SigningKey, challenge: &[u8], counter: u32, origin: &str) -> AuthenticationCredential {
+        let data = auth_data(FLAG_USER_PRESENT, counter);
+        let client_data_json = client_data("webauthn.get", challenge, origin);
+
+        let mut signed = data.clone();
+        signed.extend_from_slice(&Sha256::digest(decode(&client_data_json).unwrap()));
+        let signature: p256::ecdsa::Signature = key.sign(&signed);
+
+        AuthenticationCredential {
+            id: b64(&[7u8; 16]),
+            response: AssertionResponse {
+                client_data_json,
+                authenticator_data: b64(&data),
+                signature: b64(signature.to_der().as_bytes()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_register_and_authenticate_es256() {
+        let key = SigningKey::random(&mut rand::rngs::OsRng);
+
+        let registered = verify_registration(&config(), &register(&key, b"register-challenge"), b"register-challenge").unwrap();
+        assert_eq!(registered.raw_id, vec![7u8; 16]);
+        assert_eq!(registered.transports, vec!["internal"]);
+
+        let counter = verify_assertion(
+            &config(),
+            &assert(&key, b"login-challenge", 5, ORIGIN),
+            b"login-challenge",
+            &registered.public_key,
+            registered.sign_count,
+        )
+        .unwrap();
+        assert_eq!(counter, 5);
+    }
+
+    #[test]
+    fn test_rejects_wrong_challenge_origin_and_key() {
+        let key = SigningKey::random(&mut rand::rngs::OsRng);
+        let registered = verify_registration(&config(), &register(&key, b"c1"), b"c1").unwrap();
+
+        assert_eq!(
+            verify_registration(&config(), &register(&key, b"c1"), b"c2"),
+            Err(WebAuthnError::ChallengeMismatch)
+        );
+        assert!(matches!(
+            verify_assertion(&config(), &assert(&key, b"c3", 1, "https://evil.example"), b"c3", &registered.public_key, 0),
+            Err(WebAuthnError::OriginNotAllowed(_))
+        ));
+
+        let other = SigningKey::random(&mut rand::rngs::OsRng);
+        assert_eq!(
+            verify_assertion(&config(), &assert(&other, b"c3", 1, ORIGIN), b"c3", &registered.public_key, 0),
+            Err(WebAuthnError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_rejects_counter_regression() {
+        let key = SigningKey::random(&mut rand::rngs::OsRng);
+        let registered = verify_registration(&config(), &register(&key, b"c"), b"c").unwrap();
+
+        assert_eq!(
+            verify_assertion(&config(), &assert(&key, b"c", 3, ORIGIN), b"c", &registered.public_key, 3),
+            Err(WebAuthnError::CounterRegression)
+        );
+    }
+}
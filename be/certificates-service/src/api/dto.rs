@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::domain::entities::TemplateLayout;
+
 // =============================================================================
 // Request DTOs
 // =============================================================================
@@ -43,6 +45,8 @@ pub struct CreateTemplateRequest {
     pub secondary_color: String,
     #[serde(default = "default_font_family")]
     pub font_family: String,
+    /// Defaults to the built-in A4 landscape layout
+    pub layout: Option<TemplateLayout>,
     #[serde(default)]
     pub is_default: bool,
 }
@@ -58,6 +62,7 @@ pub struct UpdateTemplateRequest {
     pub primary_color: Option<String>,
     pub secondary_color: Option<String>,
     pub font_family: Option<String>,
+    pub layout: Option<TemplateLayout>,
     pub is_default: Option<bool>,
     pub is_active: Option<bool>,
 }
@@ -98,6 +103,8 @@ pub struct CertificateResponse {
     pub issued_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
     pub pdf_url: Option<String>,
+    /// Signed Open Badges 3.0 credential
+    pub credential_url: String,
     pub status: String,
 }
 
@@ -113,6 +120,18 @@ pub struct VerificationResponse {
     pub completion_date: Option<DateTime<Utc>>,
     pub issued_at: Option<DateTime<Utc>>,
     pub status: Option<String>,
+    /// Whether the stored credential's signature checks out
+    pub signature_valid: Option<bool>,
+    pub message: String,
+}
+
+/// Response for verifying a presented credential.
+#[derive(Debug, Serialize)]
+pub struct CredentialVerificationResponse {
+    pub is_valid: bool,
+    pub signature_valid: bool,
+    pub certificate_id: Option<Uuid>,
+    pub status: Option<String>,
     pub message: String,
 }
 
@@ -128,6 +147,7 @@ pub struct TemplateResponse {
     pub primary_color: String,
     pub secondary_color: String,
     pub font_family: String,
+    pub layout: TemplateLayout,
     pub is_default: bool,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
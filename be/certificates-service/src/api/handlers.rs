@@ -2,7 +2,8 @@
 //!
 //! Request handlers for certificates endpoints.
 
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
+use shared::auth::ServiceTokens;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -13,17 +14,26 @@ use crate::domain::errors::CertificatesError;
 #[derive(Clone)]
 pub struct AppState {
     pub certificates_service: std::sync::Arc<crate::services::certificates::CertificatesService>,
+    /// Verifies service tokens on internal endpoints
+    pub service_tokens: std::sync::Arc<ServiceTokens>,
 }
 
 // =============================================================================
 // Certificate Handlers
 // =============================================================================
 
-/// Generate a new certificate.
+/// Generate a new certificate. Only callable by other services.
+///
+/// POST /internal/v1/certificates
 pub async fn generate_certificate(
+    req: HttpRequest,
     state: web::Data<AppState>,
     body: web::Json<GenerateCertificateRequest>,
 ) -> Result<HttpResponse, CertificatesError> {
+    state.service_tokens.verify_request(&req).map_err(|e| {
+        tracing::warn!(error = %e, "Rejected internal request");
+        CertificatesError::Unauthorized("A valid service token is required".to_string())
+    })?;
     body.validate().map_err(|e| CertificatesError::Validation(e.to_string()))?;
 
     let result = state.certificates_service.generate_certificate(
@@ -103,6 +113,58 @@ pub async fn verify_certificate(
     Ok(HttpResponse::Ok().json(result))
 }
 
+/// Get the signed Open Badges credential of a certificate.
+pub async fn get_certificate_credential(
+    state: web::Data<AppState>,
+    certificate_id: web::Path<Uuid>,
+) -> Result<HttpResponse, CertificatesError> {
+    let credential = state.certificates_service.get_certificate_credential(*certificate_id).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/ld+json")
+        .json(credential))
+}
+
+/// Verify a credential presented by its holder (public endpoint).
+pub async fn verify_credential(
+    state: web::Data<AppState>,
+    body: web::Json<serde_json::Value>,
+) -> Result<HttpResponse, CertificatesError> {
+    let result = state.certificates_service.verify_credential(&body).await;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// Issuer profile (public endpoint).
+pub async fn get_issuer_profile(
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, CertificatesError> {
+    Ok(HttpResponse::Ok()
+        .content_type("application/ld+json")
+        .json(state.certificates_service.get_issuer_profile()))
+}
+
+/// Issuer public keys as a JWK set (public endpoint).
+pub async fn get_public_keys(
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, CertificatesError> {
+    Ok(HttpResponse::Ok()
+        .insert_header(("Cache-Control", "public, max-age=3600"))
+        .json(state.certificates_service.get_public_keys()))
+}
+
+/// Revocation status list (public endpoint).
+pub async fn get_status_list(
+    state: web::Data<AppState>,
+    list_id: web::Path<i64>,
+) -> Result<HttpResponse, CertificatesError> {
+    let status_list = state.certificates_service.get_status_list(*list_id).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/ld+json")
+        .insert_header(("Cache-Control", "public, max-age=300"))
+        .json(status_list))
+}
+
 /// Get certificate for a specific user and course.
 pub async fn get_user_course_certificate(
     state: web::Data<AppState>,
@@ -151,6 +213,7 @@ pub async fn create_template(
         &body.primary_color,
         &body.secondary_color,
         &body.font_family,
+        body.layout.as_ref(),
         body.is_default,
     ).await?;
 
@@ -183,6 +246,7 @@ pub async fn update_template(
         body.primary_color.as_deref(),
         body.secondary_color.as_deref(),
         body.font_family.as_deref(),
+        body.layout.as_ref(),
         body.is_default,
         body.is_active,
     ).await?;
@@ -11,14 +11,18 @@ use actix_web::web;
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/v1")
-            // Certificate generation and management
-            .route("/certificates", web::post().to(handlers::generate_certificate))
+            // Certificate management
             .route("/users/{user_id}/certificates", web::get().to(handlers::list_user_certificates))
             .route("/certificates/{certificate_id}", web::get().to(handlers::get_certificate))
             .route("/certificates/{certificate_id}/pdf", web::get().to(handlers::download_certificate_pdf))
             .route("/certificates/{certificate_id}/revoke", web::post().to(handlers::revoke_certificate))
+            .route("/certificates/{certificate_id}/credential", web::get().to(handlers::get_certificate_credential))
             // Public verification (no auth required)
             .route("/verify/{verification_code}", web::get().to(handlers::verify_certificate))
+            .route("/credentials/verify", web::post().to(handlers::verify_credential))
+            .route("/issuer", web::get().to(handlers::get_issuer_profile))
+            .route("/issuer/keys", web::get().to(handlers::get_public_keys))
+            .route("/status-lists/{list_id}", web::get().to(handlers::get_status_list))
             // Templates (admin)
             .route("/templates", web::get().to(handlers::list_templates))
             .route("/templates", web::post().to(handlers::create_template))
@@ -28,4 +32,10 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             // Check if certificate exists for user/course
             .route("/users/{user_id}/courses/{course_id}/certificate", web::get().to(handlers::get_user_course_certificate)),
     );
+
+    // Service-to-service (X-Service-Token): issuance on course completion
+    cfg.service(
+        web::scope("/internal/v1")
+            .route("/certificates", web::post().to(handlers::generate_certificate)),
+    );
 }
@@ -4,6 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -37,6 +38,10 @@ pub struct Certificate {
     pub status: String,
     /// Template used for this certificate
     pub template_id: Option<Uuid>,
+    /// Position in the revocation status lists
+    pub status_list_index: i64,
+    /// Signed Open Badges 3.0 credential
+    pub credential: Option<Json<serde_json::Value>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -70,6 +75,8 @@ pub struct CertificateTemplate {
     pub secondary_color: String,
     /// Font family name
     pub font_family: String,
+    /// Page size and position of every element
+    pub layout: Json<TemplateLayout>,
     /// Whether this is the default template
     pub is_default: bool,
     pub is_active: bool,
@@ -77,6 +84,140 @@ pub struct CertificateTemplate {
     pub updated_at: DateTime<Utc>,
 }
 
+// =============================================================================
+// Template Layout
+// =============================================================================
+
+/// Layout of a certificate page. Coordinates are in millimetres from the
+/// bottom-left corner of the page.
+///
+/// Text blocks may use the placeholders `{student_name}`, `{course_title}`,
+/// `{instructor_name}`, `{completion_date}`, `{verification_code}` and
+/// `{verification_url}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TemplateLayout {
+    pub page_width_mm: f32,
+    pub page_height_mm: f32,
+    /// chrono format for `{completion_date}`
+    pub date_format: String,
+    pub texts: Vec<TextBlock>,
+    /// Where the template logo goes (ignored when the template has none)
+    pub logo: Option<ImageBlock>,
+    /// QR code pointing at the verification URL
+    pub qr_code: Option<ImageBlock>,
+    pub signatures: Vec<SignatureBlock>,
+}
+
+impl Default for TemplateLayout {
+    /// A4 landscape, centered texts, logo top-left, QR code bottom-right.
+    fn default() -> Self {
+        let centered = |text: &str, y_mm: f32, size_pt: f32, bold: bool| TextBlock {
+            text: text.to_string(),
+            x_mm: 148.5,
+            y_mm,
+            size_pt,
+            bold,
+            align: TextAlign::Center,
+            color: None,
+        };
+
+        Self {
+            page_width_mm: 297.0,
+            page_height_mm: 210.0,
+            date_format: "%B %d, %Y".to_string(),
+            texts: vec![
+                centered("Certificate of Completion", 170.0, 36.0, true),
+                centered("This certifies that", 148.0, 14.0, false),
+                centered("{student_name}", 130.0, 28.0, true),
+                centered("has successfully completed", 114.0, 14.0, false),
+                centered("{course_title}", 98.0, 22.0, true),
+                centered("Completed on {completion_date}", 82.0, 12.0, false),
+                TextBlock {
+                    text: "Verification code: {verification_code}".to_string(),
+                    x_mm: 20.0,
+                    y_mm: 20.0,
+                    size_pt: 9.0,
+                    bold: false,
+                    align: TextAlign::Left,
+                    color: None,
+                },
+                TextBlock {
+                    text: "{verification_url}".to_string(),
+                    x_mm: 20.0,
+                    y_mm: 14.0,
+                    size_pt: 8.0,
+                    bold: false,
+                    align: TextAlign::Left,
+                    color: None,
+                },
+            ],
+            logo: Some(ImageBlock { x_mm: 20.0, y_mm: 172.0, width_mm: 35.0, height_mm: None }),
+            qr_code: Some(ImageBlock { x_mm: 252.0, y_mm: 10.0, width_mm: 30.0, height_mm: None }),
+            signatures: vec![SignatureBlock {
+                image_url: None,
+                name: "{instructor_name}".to_string(),
+                title: Some("Instructor".to_string()),
+                x_mm: 148.5,
+                y_mm: 45.0,
+                width_mm: 60.0,
+            }],
+        }
+    }
+}
+
+/// A line of text.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TextBlock {
+    pub text: String,
+    pub x_mm: f32,
+    pub y_mm: f32,
+    pub size_pt: f32,
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub align: TextAlign,
+    /// Hex color; defaults to the template primary color for bold text
+    /// and the secondary color otherwise
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Horizontal alignment of a text block relative to `x_mm`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TextAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+/// Placement of an image (lower-left corner and size). Without
+/// `height_mm` the image keeps its aspect ratio.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ImageBlock {
+    pub x_mm: f32,
+    pub y_mm: f32,
+    pub width_mm: f32,
+    #[serde(default)]
+    pub height_mm: Option<f32>,
+}
+
+/// A signature: optional image above a line, with name and title below.
+/// `x_mm` is the center of the line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignatureBlock {
+    #[serde(default)]
+    pub image_url: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub x_mm: f32,
+    pub y_mm: f32,
+    pub width_mm: f32,
+}
+
 // =============================================================================
 // Certificate Verification
 // =============================================================================
@@ -33,6 +33,8 @@ pub enum CertificatesError {
     Validation(String),
     /// Not found
     NotFound(String),
+    /// Missing or invalid credentials
+    Unauthorized(String),
     /// Forbidden
     Forbidden(String),
     /// Database error
@@ -79,6 +81,7 @@ impl fmt::Display for CertificatesError {
             }
             CertificatesError::Validation(msg) => write!(f, "Validation error: {}", msg),
             CertificatesError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            CertificatesError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             CertificatesError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             CertificatesError::Database(msg) => write!(f, "Database error: {}", msg),
             CertificatesError::Internal(msg) => write!(f, "Internal error: {}", msg),
@@ -107,6 +110,7 @@ impl actix_web::ResponseError for CertificatesError {
             CertificatesError::InvalidVerificationCode(_) => StatusCode::BAD_REQUEST,
             CertificatesError::Validation(_) => StatusCode::BAD_REQUEST,
             CertificatesError::NotFound(_) => StatusCode::NOT_FOUND,
+            CertificatesError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             CertificatesError::Forbidden(_) => StatusCode::FORBIDDEN,
             CertificatesError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             CertificatesError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
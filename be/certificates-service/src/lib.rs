@@ -6,5 +6,6 @@ pub mod api;
 pub mod domain;
 pub mod repository;
 pub mod services;
+pub mod storage;
 
 pub use api::handlers::AppState;
@@ -9,6 +9,7 @@
 //! - Certificate templates per course
 
 use actix_web::{web, App, HttpServer, middleware};
+use shared::auth::ServiceTokens;
 use sqlx::postgres::PgPoolOptions;
 use tracing_actix_web::TracingLogger;
 
@@ -16,10 +17,14 @@ mod api;
 mod domain;
 mod repository;
 mod services;
+mod storage;
 
 use api::handlers::AppState;
 use repository::CertificatesRepository;
+use services::credentials::{CredentialIssuer, SigningKeys};
+use services::renderer::FontLibrary;
 use services::CertificatesService;
+use storage::LocalCertificateStorage;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -58,14 +63,61 @@ async fn main() -> std::io::Result<()> {
     let base_url = std::env::var("BASE_URL")
         .unwrap_or_else(|_| "https://acc-lms.com".to_string());
 
+    // Rendered PDFs (a volume served by the gateway)
+    let storage_dir = std::env::var("CERTIFICATES_STORAGE_DIR")
+        .unwrap_or_else(|_| "./storage/certificates".to_string());
+    let public_url = std::env::var("CERTIFICATES_PUBLIC_URL")
+        .unwrap_or_else(|_| format!("{}/files/certificates", base_url));
+    let storage = std::sync::Arc::new(LocalCertificateStorage::new(storage_dir, &public_url));
+
+    // Optional TrueType fonts for templates ({family}.ttf, {family}-Bold.ttf)
+    let fonts = match std::env::var("CERTIFICATES_FONTS_DIR") {
+        Ok(dir) => FontLibrary::load_dir(std::path::Path::new(&dir)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load certificate fonts from {}: {}", dir, e);
+            FontLibrary::default()
+        }),
+        Err(_) => FontLibrary::default(),
+    };
+
+    // Ed25519 key signing the certificate credentials (base64 seed), plus
+    // retired public keys that must keep verifying
+    let signing_keys = match std::env::var("CERTIFICATES_SIGNING_KEY").ok().filter(|k| !k.trim().is_empty()) {
+        Some(seed) => {
+            let retired = std::env::var("CERTIFICATES_RETIRED_PUBLIC_KEYS").unwrap_or_default();
+            let retired: Vec<&str> = retired.split(',').filter(|k| !k.trim().is_empty()).collect();
+            SigningKeys::from_base64(&seed, &retired).expect("Invalid certificate signing keys")
+        }
+        // An ephemeral key would issue credentials that stop verifying on
+        // restart, so only development may run without one
+        None if std::env::var("APP_ENV").as_deref() == Ok("development") => {
+            tracing::warn!("CERTIFICATES_SIGNING_KEY not set; using an ephemeral signing key");
+            SigningKeys::generate()
+        }
+        None => panic!("CERTIFICATES_SIGNING_KEY must be set outside development"),
+    };
+    let api_url = std::env::var("CERTIFICATES_API_URL")
+        .unwrap_or_else(|_| format!("{}/api/v1", base_url));
+    let issuer_name = std::env::var("CERTIFICATES_ISSUER_NAME")
+        .unwrap_or_else(|_| "ACC LMS".to_string());
+    let credentials = std::sync::Arc::new(CredentialIssuer::new(signing_keys, &api_url, &issuer_name));
+
     // Initialize repository and service
     let repository = CertificatesRepository::new(pool);
     let certificates_service = std::sync::Arc::new(
-        CertificatesService::new(repository, base_url)
+        CertificatesService::new(repository, base_url, storage, fonts, credentials)
+    );
+
+    // Authenticates other services on /internal endpoints (issuance)
+    let internal_secret = std::env::var("INTERNAL_SERVICE_SECRET")
+        .expect("INTERNAL_SERVICE_SECRET must be set");
+    let service_tokens = std::sync::Arc::new(
+        ServiceTokens::new("certificates-service", &internal_secret)
+            .expect("Invalid INTERNAL_SERVICE_SECRET"),
     );
 
     let app_state = web::Data::new(AppState {
         certificates_service,
+        service_tokens,
     });
 
     let bind_address = std::env::var("BIND_ADDRESS")
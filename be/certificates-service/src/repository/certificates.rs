@@ -3,6 +3,7 @@
 //! Database operations for certificates and templates.
 
 use chrono::{DateTime, Utc};
+use sqlx::types::Json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -150,6 +151,9 @@ impl CertificatesRepository {
     }
 
     /// Create a new certificate.
+    ///
+    /// Returns `None` if the user already has an active certificate for the
+    /// course (e.g. a concurrent issuance won).
     pub async fn create_certificate(
         &self,
         user_id: Uuid,
@@ -160,7 +164,7 @@ impl CertificatesRepository {
         instructor_name: &str,
         completion_date: DateTime<Utc>,
         template_id: Option<Uuid>,
-    ) -> Result<Certificate, CertificatesError> {
+    ) -> Result<Option<Certificate>, CertificatesError> {
         let certificate = sqlx::query_as::<_, Certificate>(
             r#"
             INSERT INTO certificates (
@@ -169,6 +173,7 @@ impl CertificatesRepository {
                 issued_at, status, template_id
             )
             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW(), 'active', $8)
+            ON CONFLICT (user_id, course_id) WHERE status = 'active' DO NOTHING
             RETURNING *
             "#
         )
@@ -180,7 +185,7 @@ impl CertificatesRepository {
             .bind(instructor_name)
             .bind(completion_date)
             .bind(template_id)
-            .fetch_one(&self.pool)
+            .fetch_optional(&self.pool)
             .await?;
 
         Ok(certificate)
@@ -207,6 +212,47 @@ impl CertificatesRepository {
         Ok(())
     }
 
+    /// Store the signed credential of a certificate.
+    pub async fn update_certificate_credential(
+        &self,
+        certificate_id: Uuid,
+        credential: &serde_json::Value,
+    ) -> Result<(), CertificatesError> {
+        sqlx::query(
+            r#"
+            UPDATE certificates
+            SET credential = $2, updated_at = NOW()
+            WHERE certificate_id = $1
+            "#
+        )
+            .bind(certificate_id)
+            .bind(Json(credential))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Status list indexes of revoked certificates in `[from, to)`.
+    pub async fn find_revoked_status_indexes(
+        &self,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<i64>, CertificatesError> {
+        let indexes: Vec<(i64,)> = sqlx::query_as(
+            r#"
+            SELECT status_list_index FROM certificates
+            WHERE status = 'revoked' AND status_list_index >= $1 AND status_list_index < $2
+            "#
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(indexes.into_iter().map(|(index,)| index).collect())
+    }
+
     /// Revoke a certificate.
     pub async fn revoke_certificate(
         &self,
@@ -297,6 +343,7 @@ impl CertificatesRepository {
         primary_color: &str,
         secondary_color: &str,
         font_family: &str,
+        layout: &TemplateLayout,
         is_default: bool,
     ) -> Result<CertificateTemplate, CertificatesError> {
         // If this is set as default, unset other defaults first
@@ -310,9 +357,9 @@ impl CertificatesRepository {
             r#"
             INSERT INTO certificate_templates (
                 name, description, course_id, background_url, logo_url,
-                primary_color, secondary_color, font_family, is_default, is_active
+                primary_color, secondary_color, font_family, layout, is_default, is_active
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, true)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, true)
             RETURNING *
             "#
         )
@@ -324,6 +371,7 @@ impl CertificatesRepository {
             .bind(primary_color)
             .bind(secondary_color)
             .bind(font_family)
+            .bind(Json(layout))
             .bind(is_default)
             .fetch_one(&self.pool)
             .await?;
@@ -342,6 +390,7 @@ impl CertificatesRepository {
         primary_color: Option<&str>,
         secondary_color: Option<&str>,
         font_family: Option<&str>,
+        layout: Option<&TemplateLayout>,
         is_default: Option<bool>,
         is_active: Option<bool>,
     ) -> Result<CertificateTemplate, CertificatesError> {
@@ -362,8 +411,9 @@ impl CertificatesRepository {
                 primary_color = COALESCE($6, primary_color),
                 secondary_color = COALESCE($7, secondary_color),
                 font_family = COALESCE($8, font_family),
-                is_default = COALESCE($9, is_default),
-                is_active = COALESCE($10, is_active),
+                layout = COALESCE($9, layout),
+                is_default = COALESCE($10, is_default),
+                is_active = COALESCE($11, is_active),
                 updated_at = NOW()
             WHERE template_id = $1
             RETURNING *
@@ -377,6 +427,7 @@ impl CertificatesRepository {
             .bind(primary_color)
             .bind(secondary_color)
             .bind(font_family)
+            .bind(layout.map(Json))
             .bind(is_default)
             .bind(is_active)
             .fetch_one(&self.pool)
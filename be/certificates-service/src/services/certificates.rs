@@ -2,25 +2,50 @@
 //!
 //! Business logic for certificate generation and verification.
 
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
+use sqlx::types::Json;
 use uuid::Uuid;
 
 use crate::api::dto::*;
 use crate::domain::entities::*;
 use crate::domain::errors::CertificatesError;
 use crate::repository::CertificatesRepository;
+use crate::services::credentials::{status_list_position, CredentialIssuer, STATUS_LIST_SIZE};
+use crate::services::renderer::{
+    render_certificate, AssetLoader, CertificateContent, FontLibrary, RenderTemplate,
+};
+use crate::storage::CertificateStorage;
 
 /// Service for certificates business logic.
 #[derive(Clone)]
 pub struct CertificatesService {
     repository: CertificatesRepository,
     base_url: String,
+    storage: Arc<dyn CertificateStorage>,
+    assets: AssetLoader,
+    fonts: FontLibrary,
+    credentials: Arc<CredentialIssuer>,
 }
 
 impl CertificatesService {
     /// Create a new service instance.
-    pub fn new(repository: CertificatesRepository, base_url: String) -> Self {
-        Self { repository, base_url }
+    pub fn new(
+        repository: CertificatesRepository,
+        base_url: String,
+        storage: Arc<dyn CertificateStorage>,
+        fonts: FontLibrary,
+        credentials: Arc<CredentialIssuer>,
+    ) -> Self {
+        Self {
+            repository,
+            base_url,
+            storage,
+            assets: AssetLoader::new(),
+            fonts,
+            credentials,
+        }
     }
 
     /// Generate a unique verification code.
@@ -40,6 +65,9 @@ impl CertificatesService {
     // =========================================================================
 
     /// Generate a new certificate.
+    ///
+    /// Idempotent per user and course: if an active certificate exists it
+    /// is returned unchanged.
     pub async fn generate_certificate(
         &self,
         user_id: Uuid,
@@ -79,8 +107,9 @@ impl CertificatesService {
             None
         };
 
-        // Create certificate record
-        let certificate = self.repository.create_certificate(
+        // Create certificate record; a concurrent request for the same
+        // user and course may have created it in the meantime
+        let created = self.repository.create_certificate(
             user_id,
             course_id,
             &verification_code,
@@ -90,11 +119,29 @@ impl CertificatesService {
             completion_date,
             effective_template_id,
         ).await?;
+        let mut certificate = match created {
+            Some(certificate) => certificate,
+            None => {
+                let existing = self.repository
+                    .find_certificate_by_user_and_course(user_id, course_id)
+                    .await?
+                    .ok_or_else(|| CertificatesError::Internal("Certificate conflict without active certificate".into()))?;
+                return Ok(self.certificate_to_response(&existing));
+            }
+        };
 
-        // Generate PDF in background (for now, we'll skip actual PDF generation)
-        // In production, this would trigger an async job or generate the PDF here
-        // let pdf_url = self.generate_pdf(&certificate, effective_template_id).await?;
-        // self.repository.update_certificate_pdf_url(certificate.certificate_id, &pdf_url).await?;
+        // Sign the credential alongside the PDF
+        let credential = self.issue_credential(&certificate).await?;
+        certificate.credential = Some(Json(credential));
+
+        // The certificate exists even if rendering fails; the PDF is then
+        // rendered on the first download
+        match self.render_and_store(&certificate).await {
+            Ok((_, pdf_url)) => certificate.pdf_url = Some(pdf_url),
+            Err(e) => tracing::warn!(
+                "Failed to render certificate {}: {}", certificate.certificate_id, e
+            ),
+        }
 
         Ok(self.certificate_to_response(&certificate))
     }
@@ -174,8 +221,15 @@ impl CertificatesService {
             return Err(CertificatesError::CertificateRevoked(certificate_id));
         }
 
-        // Generate PDF on-the-fly
-        self.generate_simple_pdf(&certificate).await
+        // Serve the stored PDF; render it only when missing
+        if certificate.pdf_url.is_some() {
+            if let Some(pdf) = self.storage.get(&Self::pdf_key(certificate.certificate_id)).await? {
+                return Ok(pdf);
+            }
+        }
+
+        let (pdf, _) = self.render_and_store(&certificate).await?;
+        Ok(pdf)
     }
 
     /// Revoke a certificate.
@@ -213,14 +267,20 @@ impl CertificatesService {
                 completion_date: None,
                 issued_at: None,
                 status: None,
+                signature_valid: None,
                 message: "Invalid verification code format".to_string(),
             };
         }
 
         match self.repository.find_certificate_by_code(verification_code).await {
             Ok(Some(cert)) => {
-                let is_valid = cert.status == certificate_status::ACTIVE;
+                // Certificates issued before signing have no credential yet
+                let signature_valid = cert.credential
+                    .as_ref()
+                    .map(|c| self.credentials.verify(&c.0).is_ok());
+                let is_valid = cert.status == certificate_status::ACTIVE && signature_valid != Some(false);
                 let message = match cert.status.as_str() {
+                    _ if signature_valid == Some(false) => "Certificate signature is invalid",
                     "active" => "Certificate is valid and active",
                     "revoked" => "Certificate has been revoked",
                     "expired" => "Certificate has expired",
@@ -237,6 +297,7 @@ impl CertificatesService {
                     completion_date: Some(cert.completion_date),
                     issued_at: Some(cert.issued_at),
                     status: Some(cert.status),
+                    signature_valid,
                     message: message.to_string(),
                 }
             }
@@ -250,6 +311,7 @@ impl CertificatesService {
                 completion_date: None,
                 issued_at: None,
                 status: None,
+                signature_valid: None,
                 message: "Certificate not found".to_string(),
             },
             Err(_) => VerificationResponse {
@@ -262,11 +324,121 @@ impl CertificatesService {
                 completion_date: None,
                 issued_at: None,
                 status: None,
+                signature_valid: None,
                 message: "Error verifying certificate".to_string(),
             },
         }
     }
 
+    // =========================================================================
+    // Verifiable Credentials
+    // =========================================================================
+
+    /// Get the signed credential of a certificate, issuing it for
+    /// certificates created before signing was enabled.
+    pub async fn get_certificate_credential(
+        &self,
+        certificate_id: Uuid,
+    ) -> Result<serde_json::Value, CertificatesError> {
+        let certificate = self.repository.find_certificate_by_id(certificate_id).await?
+            .ok_or(CertificatesError::CertificateNotFound(certificate_id))?;
+
+        match certificate.credential {
+            Some(credential) => Ok(credential.0),
+            None => self.issue_credential(&certificate).await,
+        }
+    }
+
+    /// Verify a credential presented by its holder: signature and
+    /// revocation status.
+    pub async fn verify_credential(
+        &self,
+        credential: &serde_json::Value,
+    ) -> CredentialVerificationResponse {
+        if let Err(reason) = self.credentials.verify(credential) {
+            return CredentialVerificationResponse {
+                is_valid: false,
+                signature_valid: false,
+                certificate_id: None,
+                status: None,
+                message: reason,
+            };
+        }
+
+        let certificate_id = credential["id"]
+            .as_str()
+            .and_then(|id| id.strip_prefix("urn:uuid:"))
+            .and_then(|id| Uuid::parse_str(id).ok());
+
+        let certificate = match certificate_id {
+            Some(id) => self.repository.find_certificate_by_id(id).await,
+            None => Ok(None),
+        };
+
+        let (status, message) = match &certificate {
+            Ok(Some(cert)) => (Some(cert.status.clone()), match cert.status.as_str() {
+                "active" => "Credential is valid",
+                "revoked" => "Credential has been revoked",
+                "expired" => "Credential has expired",
+                _ => "Unknown certificate status",
+            }),
+            Ok(None) => (None, "Certificate not found"),
+            Err(_) => (None, "Error verifying credential"),
+        };
+
+        CredentialVerificationResponse {
+            is_valid: status.as_deref() == Some(certificate_status::ACTIVE),
+            signature_valid: true,
+            certificate_id,
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    /// Signed revocation status list.
+    pub async fn get_status_list(
+        &self,
+        list_id: i64,
+    ) -> Result<serde_json::Value, CertificatesError> {
+        if list_id < 0 {
+            return Err(CertificatesError::NotFound(format!("Status list {}", list_id)));
+        }
+
+        let from = list_id * STATUS_LIST_SIZE;
+        let revoked: Vec<usize> = self.repository
+            .find_revoked_status_indexes(from, from + STATUS_LIST_SIZE)
+            .await?
+            .into_iter()
+            .map(|index| status_list_position(index).1)
+            .collect();
+
+        self.credentials.status_list(list_id, &revoked)
+    }
+
+    /// Issuer profile with its verification methods.
+    pub fn get_issuer_profile(&self) -> serde_json::Value {
+        self.credentials.profile()
+    }
+
+    /// Issuer public keys (JWK set).
+    pub fn get_public_keys(&self) -> serde_json::Value {
+        self.credentials.jwks()
+    }
+
+    /// Sign and store the credential of a certificate.
+    async fn issue_credential(
+        &self,
+        certificate: &Certificate,
+    ) -> Result<serde_json::Value, CertificatesError> {
+        let credential = self.credentials.issue(
+            certificate,
+            &self.build_verification_url(&certificate.verification_code),
+        );
+        self.repository.update_certificate_credential(certificate.certificate_id, &credential).await?;
+
+        Ok(credential)
+    }
+
     // =========================================================================
     // Template Operations
     // =========================================================================
@@ -294,11 +466,16 @@ impl CertificatesService {
         primary_color: &str,
         secondary_color: &str,
         font_family: &str,
+        layout: Option<&TemplateLayout>,
         is_default: bool,
     ) -> Result<TemplateResponse, CertificatesError> {
+        let default_layout = TemplateLayout::default();
+        let layout = layout.unwrap_or(&default_layout);
+        Self::validate_layout(layout)?;
+
         let template = self.repository.create_template(
             name, description, course_id, background_url, logo_url,
-            primary_color, secondary_color, font_family, is_default
+            primary_color, secondary_color, font_family, layout, is_default
         ).await?;
 
         Ok(self.template_to_response(&template))
@@ -326,6 +503,7 @@ impl CertificatesService {
         primary_color: Option<&str>,
         secondary_color: Option<&str>,
         font_family: Option<&str>,
+        layout: Option<&TemplateLayout>,
         is_default: Option<bool>,
         is_active: Option<bool>,
     ) -> Result<TemplateResponse, CertificatesError> {
@@ -333,9 +511,13 @@ impl CertificatesService {
         self.repository.find_template_by_id(template_id).await?
             .ok_or(CertificatesError::TemplateNotFound(template_id))?;
 
+        if let Some(layout) = layout {
+            Self::validate_layout(layout)?;
+        }
+
         let template = self.repository.update_template(
             template_id, name, description, background_url, logo_url,
-            primary_color, secondary_color, font_family, is_default, is_active
+            primary_color, secondary_color, font_family, layout, is_default, is_active
         ).await?;
 
         Ok(self.template_to_response(&template))
@@ -374,6 +556,7 @@ impl CertificatesService {
             issued_at: cert.issued_at,
             expires_at: cert.expires_at,
             pdf_url: cert.pdf_url.clone(),
+            credential_url: self.credentials.credential_url(cert.certificate_id),
             status: cert.status.clone(),
         }
     }
@@ -390,6 +573,7 @@ impl CertificatesService {
             primary_color: template.primary_color.clone(),
             secondary_color: template.secondary_color.clone(),
             font_family: template.font_family.clone(),
+            layout: template.layout.0.clone(),
             is_default: template.is_default,
             is_active: template.is_active,
             created_at: template.created_at,
@@ -397,119 +581,57 @@ impl CertificatesService {
         }
     }
 
-    /// Generate a simple PDF certificate.
-    async fn generate_simple_pdf(
-        &self,
-        certificate: &Certificate,
-    ) -> Result<Vec<u8>, CertificatesError> {
-        use printpdf::*;
-
-        // Create a new PDF document
-        let (doc, page1, layer1) = PdfDocument::new(
-            "Certificate of Completion",
-            Mm(297.0), // A4 landscape width
-            Mm(210.0), // A4 landscape height
-            "Layer 1",
-        );
-
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-
-        // Use built-in font
-        let font = doc.add_builtin_font(BuiltinFont::TimesRoman)
-            .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))?;
-
-        let bold_font = doc.add_builtin_font(BuiltinFont::TimesBold)
-            .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))?;
-
-        // Title
-        current_layer.use_text(
-            "Certificate of Completion",
-            36.0,
-            Mm(148.5),
-            Mm(180.0),
-            &bold_font,
-        );
-
-        // This certifies that
-        current_layer.use_text(
-            "This certifies that",
-            14.0,
-            Mm(148.5),
-            Mm(150.0),
-            &font,
-        );
-
-        // Student name
-        current_layer.use_text(
-            &certificate.student_name,
-            28.0,
-            Mm(148.5),
-            Mm(130.0),
-            &bold_font,
-        );
-
-        // Has successfully completed
-        current_layer.use_text(
-            "has successfully completed",
-            14.0,
-            Mm(148.5),
-            Mm(110.0),
-            &font,
-        );
+    // =========================================================================
+    // PDF Rendering
+    // =========================================================================
 
-        // Course title
-        current_layer.use_text(
-            &certificate.course_title,
-            22.0,
-            Mm(148.5),
-            Mm(90.0),
-            &bold_font,
-        );
+    /// Storage key of a certificate PDF.
+    fn pdf_key(certificate_id: Uuid) -> String {
+        format!("certificates/{}.pdf", certificate_id)
+    }
 
-        // Instructor
-        let instructor_text = format!("Instructor: {}", certificate.instructor_name);
-        current_layer.use_text(
-            &instructor_text,
-            12.0,
-            Mm(148.5),
-            Mm(60.0),
-            &font,
-        );
+    /// Reject layouts that cannot produce a page.
+    fn validate_layout(layout: &TemplateLayout) -> Result<(), CertificatesError> {
+        if layout.page_width_mm <= 0.0 || layout.page_height_mm <= 0.0 {
+            return Err(CertificatesError::Validation("Page size must be positive".into()));
+        }
+        if layout.texts.iter().any(|t| t.size_pt <= 0.0) {
+            return Err(CertificatesError::Validation("Font sizes must be positive".into()));
+        }
+        Ok(())
+    }
 
-        // Completion date
-        let date_text = format!("Completed on: {}", certificate.completion_date.format("%B %d, %Y"));
-        current_layer.use_text(
-            &date_text,
-            12.0,
-            Mm(148.5),
-            Mm(45.0),
-            &font,
-        );
+    /// Render a certificate with its template, store the PDF and record
+    /// its URL. Returns the PDF and the URL.
+    async fn render_and_store(
+        &self,
+        certificate: &Certificate,
+    ) -> Result<(Vec<u8>, String), CertificatesError> {
+        let template = match certificate.template_id {
+            Some(template_id) => self.repository.find_template_by_id(template_id).await?,
+            None => None,
+        };
+        let template = RenderTemplate::from_template(template.as_ref());
 
-        // Verification code
-        let code_text = format!("Verification Code: {}", certificate.verification_code);
-        current_layer.use_text(
-            &code_text,
-            10.0,
-            Mm(148.5),
-            Mm(25.0),
-            &font,
+        let content = CertificateContent::new(
+            certificate,
+            self.build_verification_url(&certificate.verification_code),
+            &template.layout.date_format,
         );
+        let assets = self.assets.load(&template).await;
+        let fonts = self.fonts.clone();
 
-        // Verification URL
-        let url_text = format!("Verify at: {}", self.build_verification_url(&certificate.verification_code));
-        current_layer.use_text(
-            &url_text,
-            8.0,
-            Mm(148.5),
-            Mm(15.0),
-            &font,
-        );
+        let pdf = tokio::task::spawn_blocking(move || {
+            render_certificate(&content, &template, &assets, &fonts)
+        })
+        .await
+        .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))??;
 
-        // Save PDF to bytes
-        let pdf_bytes = doc.save_to_bytes()
-            .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))?;
+        let pdf_url = self.storage
+            .put(&Self::pdf_key(certificate.certificate_id), pdf.clone(), "application/pdf")
+            .await?;
+        self.repository.update_certificate_pdf_url(certificate.certificate_id, &pdf_url).await?;
 
-        Ok(pdf_bytes)
+        Ok((pdf, pdf_url))
     }
 }
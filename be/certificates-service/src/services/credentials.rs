@@ -0,0 +1,500 @@
+//! # Verifiable Credentials
+//!
+//! Issues certificates as Open Badges 3.0 credentials (W3C Verifiable
+//! Credentials 2.0) secured with an Ed25519 Data Integrity proof
+//! (`eddsa-jcs-2022`), and publishes revocations as a Bitstring Status List.
+//!
+//! A credential can be verified offline with the issuer's public keys
+//! (`GET /api/v1/issuer/keys`); only the revocation check needs the status
+//! list (`GET /api/v1/status-lists/{list_id}`).
+
+use std::io::Write;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::entities::Certificate;
+use crate::domain::errors::CertificatesError;
+
+/// Entries per status list: 16 KB, the minimum the spec recommends so a
+/// list does not reveal which credential is being checked.
+pub const STATUS_LIST_SIZE: i64 = 131_072;
+
+const CREDENTIALS_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+const OPEN_BADGES_CONTEXT: &str = "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json";
+const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+
+/// Multicodec prefix of an Ed25519 public key (`ed25519-pub`).
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+// =============================================================================
+// Keys
+// =============================================================================
+
+/// Issuer keys: the active signing key plus retired public keys that are
+/// still published so older credentials keep verifying.
+pub struct SigningKeys {
+    active_id: String,
+    active: SigningKey,
+    published: Vec<(String, VerifyingKey)>,
+}
+
+impl SigningKeys {
+    pub fn new(active: SigningKey, retired: Vec<VerifyingKey>) -> Self {
+        let active_public = active.verifying_key();
+        let mut published = vec![(Self::key_id(&active_public), active_public)];
+        published.extend(retired.into_iter().map(|k| (Self::key_id(&k), k)));
+
+        Self {
+            active_id: Self::key_id(&active_public),
+            active,
+            published,
+        }
+    }
+
+    /// Loads the active key from a base64 Ed25519 seed (32 bytes) and the
+    /// retired public keys from base64.
+    pub fn from_base64(seed: &str, retired: &[&str]) -> Result<Self, CertificatesError> {
+        let seed: [u8; 32] = decode_key(seed)?;
+        let retired = retired
+            .iter()
+            .map(|key| {
+                let bytes: [u8; 32] = decode_key(key)?;
+                VerifyingKey::from_bytes(&bytes)
+                    .map_err(|e| CertificatesError::Internal(format!("Invalid public key: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(SigningKey::from_bytes(&seed), retired))
+    }
+
+    /// Generates a random key (development only: credentials stop
+    /// verifying once the process restarts).
+    pub fn generate() -> Self {
+        Self::new(SigningKey::generate(&mut rand::rngs::OsRng), Vec::new())
+    }
+
+    /// Key ID: first 8 bytes of the SHA-256 of the public key, in hex.
+    pub fn key_id(key: &VerifyingKey) -> String {
+        Sha256::digest(key.as_bytes())[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn find(&self, key_id: &str) -> Option<&VerifyingKey> {
+        self.published.iter().find(|(id, _)| id == key_id).map(|(_, key)| key)
+    }
+}
+
+fn decode_key(value: &str) -> Result<[u8; 32], CertificatesError> {
+    STANDARD
+        .decode(value.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CertificatesError::Internal("Keys must be 32 bytes in base64".into()))
+}
+
+/// Public key in multibase (`z` + base58btc of the multicodec key).
+fn public_key_multibase(key: &VerifyingKey) -> String {
+    let mut bytes = ED25519_MULTICODEC.to_vec();
+    bytes.extend_from_slice(key.as_bytes());
+    format!("z{}", bs58::encode(bytes).into_string())
+}
+
+// =============================================================================
+// Issuer
+// =============================================================================
+
+/// Builds, signs and verifies certificate credentials.
+pub struct CredentialIssuer {
+    keys: SigningKeys,
+    /// Public base URL of this API (e.g. `https://acc-lms.com/api/v1`)
+    api_url: String,
+    name: String,
+}
+
+impl CredentialIssuer {
+    pub fn new(keys: SigningKeys, api_url: &str, name: &str) -> Self {
+        Self {
+            keys,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    /// Issuer ID; also the URL of its profile.
+    pub fn issuer_id(&self) -> String {
+        format!("{}/issuer", self.api_url)
+    }
+
+    pub fn credential_url(&self, certificate_id: Uuid) -> String {
+        format!("{}/certificates/{}/credential", self.api_url, certificate_id)
+    }
+
+    pub fn status_list_url(&self, list_id: i64) -> String {
+        format!("{}/status-lists/{}", self.api_url, list_id)
+    }
+
+    fn verification_method(&self, key_id: &str) -> String {
+        format!("{}#{}", self.issuer_id(), key_id)
+    }
+
+    /// Issuer profile with its verification methods.
+    pub fn profile(&self) -> Value {
+        let methods: Vec<Value> = self.keys.published.iter().map(|(id, key)| json!({
+            "id": self.verification_method(id),
+            "type": "Multikey",
+            "controller": self.issuer_id(),
+            "publicKeyMultibase": public_key_multibase(key),
+        })).collect();
+        let method_ids: Vec<Value> = methods.iter().map(|m| m["id"].clone()).collect();
+
+        json!({
+            "@context": [CREDENTIALS_CONTEXT, OPEN_BADGES_CONTEXT],
+            "id": self.issuer_id(),
+            "type": ["Profile"],
+            "name": self.name,
+            "verificationMethod": methods,
+            "assertionMethod": method_ids,
+        })
+    }
+
+    /// Public keys as a JWK set (OKP / Ed25519).
+    pub fn jwks(&self) -> Value {
+        let keys: Vec<Value> = self.keys.published.iter().map(|(id, key)| json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": URL_SAFE_NO_PAD.encode(key.as_bytes()),
+            "kid": id,
+            "alg": "EdDSA",
+            "use": "sig",
+        })).collect();
+
+        json!({ "keys": keys })
+    }
+
+    /// Signed Open Badges credential for a certificate.
+    pub fn issue(&self, certificate: &Certificate, verification_url: &str) -> Value {
+        let (list_id, _) = status_list_position(certificate.status_list_index);
+        let status_list_url = self.status_list_url(list_id);
+
+        let mut credential = json!({
+            "@context": [CREDENTIALS_CONTEXT, OPEN_BADGES_CONTEXT],
+            "id": format!("urn:uuid:{}", certificate.certificate_id),
+            "type": ["VerifiableCredential", "OpenBadgeCredential"],
+            "name": format!("Certificate of Completion: {}", certificate.course_title),
+            "issuer": {
+                "id": self.issuer_id(),
+                "type": ["Profile"],
+                "name": self.name,
+            },
+            "validFrom": certificate.issued_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "credentialSubject": {
+                "id": format!("urn:uuid:{}", certificate.user_id),
+                "type": ["AchievementSubject"],
+                "identifier": [{
+                    "type": "IdentityObject",
+                    "identityType": "name",
+                    "hashed": false,
+                    "identityHash": certificate.student_name,
+                }],
+                "achievement": {
+                    "id": format!("urn:uuid:{}", certificate.course_id),
+                    "type": ["Achievement"],
+                    "achievementType": "Course",
+                    "name": certificate.course_title,
+                    "description": format!("Completion of the course {}", certificate.course_title),
+                    "criteria": {
+                        "narrative": format!(
+                            "Completed every required activity of the course, taught by {}.",
+                            certificate.instructor_name
+                        ),
+                    },
+                },
+                "activityEndDate": certificate.completion_date.to_rfc3339_opts(SecondsFormat::Secs, true),
+            },
+            "evidence": [{
+                "id": verification_url,
+                "type": ["Evidence"],
+                "name": format!("Verification code {}", certificate.verification_code),
+            }],
+            "credentialStatus": {
+                "id": format!("{}#{}", status_list_url, certificate.status_list_index),
+                "type": "BitstringStatusListEntry",
+                "statusPurpose": "revocation",
+                "statusListIndex": (certificate.status_list_index % STATUS_LIST_SIZE).to_string(),
+                "statusListCredential": status_list_url,
+            },
+        });
+
+        if let Some(expires_at) = certificate.expires_at {
+            credential["validUntil"] = json!(expires_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+
+        self.sign(credential)
+    }
+
+    /// Signed status list credential. `revoked` holds positions within the
+    /// list.
+    pub fn status_list(&self, list_id: i64, revoked: &[usize]) -> Result<Value, CertificatesError> {
+        let url = self.status_list_url(list_id);
+
+        let credential = json!({
+            "@context": [CREDENTIALS_CONTEXT],
+            "id": url,
+            "type": ["VerifiableCredential", "BitstringStatusListCredential"],
+            "issuer": self.issuer_id(),
+            "validFrom": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "credentialSubject": {
+                "id": format!("{}#list", url),
+                "type": "BitstringStatusList",
+                "statusPurpose": "revocation",
+                "encodedList": encode_status_list(revoked)?,
+            },
+        });
+
+        Ok(self.sign(credential))
+    }
+
+    /// Adds an `eddsa-jcs-2022` proof made with the active key.
+    fn sign(&self, mut document: Value) -> Value {
+        let mut proof = json!({
+            "type": "DataIntegrityProof",
+            "cryptosuite": CRYPTOSUITE,
+            "created": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "verificationMethod": self.verification_method(&self.keys.active_id),
+            "proofPurpose": "assertionMethod",
+        });
+
+        let signature = self.keys.active.sign(&proof_hash(&document, &proof));
+        proof["proofValue"] = json!(format!("z{}", bs58::encode(signature.to_bytes()).into_string()));
+        document["proof"] = proof;
+
+        document
+    }
+
+    /// Checks the proof of a credential issued here. Returns why it is not
+    /// valid otherwise.
+    pub fn verify(&self, credential: &Value) -> Result<(), String> {
+        let mut document = credential.clone();
+        let mut proof = document
+            .as_object_mut()
+            .and_then(|d| d.remove("proof"))
+            .ok_or("Credential has no proof")?;
+
+        if proof["type"] != "DataIntegrityProof" || proof["cryptosuite"] != CRYPTOSUITE {
+            return Err("Unsupported proof type".into());
+        }
+
+        let method = proof["verificationMethod"].as_str().unwrap_or_default();
+        let key_id = method
+            .strip_prefix(&format!("{}#", self.issuer_id()))
+            .ok_or("Credential was not issued by this issuer")?;
+        let key = self.keys.find(key_id).ok_or("Unknown signing key")?;
+
+        let proof_value = proof
+            .as_object_mut()
+            .and_then(|p| p.remove("proofValue"))
+            .ok_or("Proof has no value")?;
+        let signature = proof_value
+            .as_str()
+            .and_then(|v| v.strip_prefix('z'))
+            .and_then(|v| bs58::decode(v).into_vec().ok())
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or("Malformed proof value")?;
+
+        key.verify_strict(&proof_hash(&document, &proof), &signature)
+            .map_err(|_| "Invalid signature".to_string())
+    }
+}
+
+/// `eddsa-jcs-2022` hash: SHA-256 of the canonical proof options (with the
+/// document context) followed by SHA-256 of the canonical document.
+fn proof_hash(document: &Value, proof: &Value) -> Vec<u8> {
+    let mut options = proof.clone();
+    options["@context"] = document["@context"].clone();
+
+    let mut hash = Sha256::digest(canonicalize(&options)).to_vec();
+    hash.extend_from_slice(&Sha256::digest(canonicalize(document)));
+    hash
+}
+
+/// JSON Canonicalization Scheme (RFC 8785): keys sorted by UTF-16 code
+/// units, no whitespace.
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+// =============================================================================
+// Status Lists
+// =============================================================================
+
+/// List and position of a certificate's status index.
+pub fn status_list_position(index: i64) -> (i64, usize) {
+    (index / STATUS_LIST_SIZE, (index % STATUS_LIST_SIZE) as usize)
+}
+
+/// Encodes a bitstring with the given positions set: GZIP, then multibase
+/// base64url (`u` prefix). Bit 0 is the most significant bit of byte 0.
+pub fn encode_status_list(positions: &[usize]) -> Result<String, CertificatesError> {
+    let mut bits = vec![0u8; (STATUS_LIST_SIZE / 8) as usize];
+    for &position in positions.iter().filter(|&&p| p < STATUS_LIST_SIZE as usize) {
+        bits[position / 8] |= 0x80 >> (position % 8);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&bits)
+        .map_err(|e| CertificatesError::Internal(format!("Status list encoding: {}", e)))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| CertificatesError::Internal(format!("Status list encoding: {}", e)))?;
+
+    Ok(format!("u{}", URL_SAFE_NO_PAD.encode(compressed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn issuer() -> CredentialIssuer {
+        CredentialIssuer::new(SigningKeys::generate(), "https://acc-lms.com/api/v1/", "ACC LMS")
+    }
+
+    fn certificate() -> Certificate {
+        let date = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        Certificate {
+            certificate_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            course_id: Uuid::new_v4(),
+            verification_code: "ACC-2026-AB12-CD34".to_string(),
+            student_name: "Ana Pérez".to_string(),
+            course_title: "Rust for Backend Developers".to_string(),
+            instructor_name: "Luis Gómez".to_string(),
+            completion_date: date,
+            issued_at: date,
+            expires_at: None,
+            pdf_url: None,
+            status: "active".to_string(),
+            template_id: None,
+            status_list_index: STATUS_LIST_SIZE + 7,
+            credential: None,
+            created_at: date,
+            updated_at: date,
+        }
+    }
+
+    #[test]
+    fn test_issued_credential_verifies() {
+        let issuer = issuer();
+        let credential = issuer.issue(&certificate(), "https://acc-lms.com/verify/ACC-2026-AB12-CD34");
+
+        assert_eq!(credential["type"][1], "OpenBadgeCredential");
+        assert_eq!(credential["credentialStatus"]["statusListIndex"], "7");
+        assert_eq!(
+            credential["credentialStatus"]["statusListCredential"],
+            "https://acc-lms.com/api/v1/status-lists/1"
+        );
+        assert!(issuer.verify(&credential).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_credential_fails() {
+        let issuer = issuer();
+        let mut credential = issuer.issue(&certificate(), "https://acc-lms.com/verify/x");
+
+        credential["credentialSubject"]["achievement"]["name"] = json!("Another course");
+
+        assert_eq!(issuer.verify(&credential).unwrap_err(), "Invalid signature");
+    }
+
+    #[test]
+    fn test_retired_keys_still_verify() {
+        let old_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let old = CredentialIssuer::new(SigningKeys::new(old_key.clone(), Vec::new()), "https://acc-lms.com/api/v1", "ACC LMS");
+        let credential = old.issue(&certificate(), "https://acc-lms.com/verify/x");
+
+        let rotated = CredentialIssuer::new(
+            SigningKeys::new(SigningKey::generate(&mut rand::rngs::OsRng), vec![old_key.verifying_key()]),
+            "https://acc-lms.com/api/v1",
+            "ACC LMS",
+        );
+
+        assert!(rotated.verify(&credential).is_ok());
+        assert_eq!(rotated.jwks()["keys"].as_array().unwrap().len(), 2);
+        assert_eq!(issuer().verify(&credential).unwrap_err(), "Unknown signing key");
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_keys() {
+        let value = json!({"b": [1, {"z": null, "a": true}], "a": "é\n"});
+        assert_eq!(canonicalize(&value), r#"{"a":"é\n","b":[1,{"a":true,"z":null}]}"#);
+    }
+
+    #[test]
+    fn test_status_list_encoding() {
+        let encoded = encode_status_list(&[0, 9, STATUS_LIST_SIZE as usize - 1]).unwrap();
+
+        let compressed = URL_SAFE_NO_PAD.decode(encoded.strip_prefix('u').unwrap()).unwrap();
+        let mut bits = Vec::new();
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut bits).unwrap();
+
+        assert_eq!(bits.len(), 16_384);
+        assert_eq!(bits[0], 0b1000_0000);
+        assert_eq!(bits[1], 0b0100_0000);
+        assert_eq!(bits[16_383], 0b0000_0001);
+        assert_eq!(status_list_position(STATUS_LIST_SIZE + 7), (1, 7));
+    }
+
+    #[test]
+    fn test_public_key_multibase() {
+        let keys = SigningKeys::generate();
+        let multibase = public_key_multibase(&keys.active.verifying_key());
+        // Ed25519 multikeys always start with z6Mk
+        assert!(multibase.starts_with("z6Mk"));
+    }
+}
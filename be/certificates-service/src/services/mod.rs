@@ -3,5 +3,7 @@
 //! Business logic layer for the certificates service.
 
 pub mod certificates;
+pub mod credentials;
+pub mod renderer;
 
 pub use certificates::CertificatesService;
@@ -0,0 +1,628 @@
+//! # Certificate Renderer
+//!
+//! Lays out certificate PDFs from their template: background image, logo,
+//! text blocks, signatures and a QR code pointing at the verification page.
+//!
+//! Rendering is CPU-bound and synchronous; callers run it on a blocking
+//! thread. Images are fetched beforehand by [`AssetLoader`].
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use printpdf::image_crate::{self, DynamicImage, GenericImageView};
+use printpdf::{
+    BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument,
+    PdfLayerReference, Point, Rect, Rgb,
+};
+use printpdf::path::PaintMode;
+use qrcode::QrCode;
+use rusttype::Font;
+
+use crate::domain::entities::{
+    Certificate, CertificateTemplate, ImageBlock, SignatureBlock, TemplateLayout, TextAlign,
+    TextBlock,
+};
+use crate::domain::errors::CertificatesError;
+
+/// Colors used when there is no template.
+const DEFAULT_PRIMARY_COLOR: &str = "#1a365d";
+const DEFAULT_SECONDARY_COLOR: &str = "#c69c6d";
+const DEFAULT_FONT_FAMILY: &str = "serif";
+
+/// Images are embedded at this resolution unless scaled.
+const IMAGE_DPI: f32 = 300.0;
+
+/// Modules of white space around the QR code.
+const QR_QUIET_ZONE: usize = 2;
+
+// =============================================================================
+// Inputs
+// =============================================================================
+
+/// Template settings used for rendering.
+#[derive(Debug, Clone)]
+pub struct RenderTemplate {
+    pub layout: TemplateLayout,
+    pub primary_color: String,
+    pub secondary_color: String,
+    pub font_family: String,
+    pub background_url: Option<String>,
+    pub logo_url: Option<String>,
+}
+
+impl RenderTemplate {
+    /// Settings of a template, or the default layout when there is none.
+    pub fn from_template(template: Option<&CertificateTemplate>) -> Self {
+        match template {
+            Some(t) => Self {
+                layout: t.layout.0.clone(),
+                primary_color: t.primary_color.clone(),
+                secondary_color: t.secondary_color.clone(),
+                font_family: t.font_family.clone(),
+                background_url: t.background_url.clone(),
+                logo_url: t.logo_url.clone(),
+            },
+            None => Self {
+                layout: TemplateLayout::default(),
+                primary_color: DEFAULT_PRIMARY_COLOR.to_string(),
+                secondary_color: DEFAULT_SECONDARY_COLOR.to_string(),
+                font_family: DEFAULT_FONT_FAMILY.to_string(),
+                background_url: None,
+                logo_url: None,
+            },
+        }
+    }
+}
+
+/// Certificate data substituted into the template.
+#[derive(Debug, Clone)]
+pub struct CertificateContent {
+    pub student_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub completion_date: String,
+    pub verification_code: String,
+    pub verification_url: String,
+}
+
+impl CertificateContent {
+    pub fn new(certificate: &Certificate, verification_url: String, date_format: &str) -> Self {
+        Self {
+            student_name: certificate.student_name.clone(),
+            course_title: certificate.course_title.clone(),
+            instructor_name: certificate.instructor_name.clone(),
+            completion_date: certificate.completion_date.format(date_format).to_string(),
+            verification_code: certificate.verification_code.clone(),
+            verification_url,
+        }
+    }
+
+    /// Replaces the placeholders of a template text.
+    pub fn fill(&self, text: &str) -> String {
+        text.replace("{student_name}", &self.student_name)
+            .replace("{course_title}", &self.course_title)
+            .replace("{instructor_name}", &self.instructor_name)
+            .replace("{completion_date}", &self.completion_date)
+            .replace("{verification_code}", &self.verification_code)
+            .replace("{verification_url}", &self.verification_url)
+    }
+}
+
+/// Decoded template images. `signatures` follows the order of the layout's
+/// signature blocks.
+#[derive(Default)]
+pub struct RenderAssets {
+    pub background: Option<DynamicImage>,
+    pub logo: Option<DynamicImage>,
+    pub signatures: Vec<Option<DynamicImage>>,
+}
+
+// =============================================================================
+// Fonts
+// =============================================================================
+
+/// A TrueType font file and its metrics.
+struct FontFace {
+    data: Vec<u8>,
+    metrics: Font<'static>,
+}
+
+impl FontFace {
+    fn load(path: &Path) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        let metrics = Font::try_from_vec(data.clone())?;
+        Some(Self { data, metrics })
+    }
+
+    /// Width of a text in millimetres.
+    fn text_width_mm(&self, text: &str, size_pt: f32) -> f32 {
+        let units_per_em = f32::from(self.metrics.units_per_em());
+        let v_metrics = self.metrics.v_metrics_unscaled();
+        // rusttype scales by line height, not by em
+        let scale = rusttype::Scale::uniform(
+            size_pt * (v_metrics.ascent - v_metrics.descent) / units_per_em,
+        );
+
+        let width_pt: f32 = self.metrics
+            .glyphs_for(text.chars())
+            .map(|g| g.scaled(scale).h_metrics().advance_width)
+            .sum();
+
+        pt_to_mm(width_pt)
+    }
+}
+
+#[derive(Default)]
+struct FontFamily {
+    regular: Option<FontFace>,
+    bold: Option<FontFace>,
+}
+
+/// TrueType fonts available to templates, by family name.
+///
+/// A directory holds `{family}.ttf` and `{family}-Bold.ttf` files. Families
+/// not found here fall back to the closest PDF builtin font.
+#[derive(Clone, Default)]
+pub struct FontLibrary {
+    families: Arc<HashMap<String, FontFamily>>,
+}
+
+impl FontLibrary {
+    /// Loads every `.ttf` / `.otf` file of a directory.
+    pub fn load_dir(dir: &Path) -> std::io::Result<Self> {
+        let mut families: HashMap<String, FontFamily> = HashMap::new();
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_font = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("ttf") || e.eq_ignore_ascii_case("otf"));
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            if !is_font {
+                continue;
+            }
+
+            let Some(face) = FontFace::load(&path) else {
+                tracing::warn!("Skipping unreadable font {}", path.display());
+                continue;
+            };
+
+            let (family, bold) = match stem.strip_suffix("-Bold") {
+                Some(family) => (family, true),
+                None => (stem, false),
+            };
+            let entry = families.entry(family.to_lowercase()).or_default();
+            if bold {
+                entry.bold = Some(face);
+            } else {
+                entry.regular = Some(face);
+            }
+        }
+
+        Ok(Self { families: Arc::new(families) })
+    }
+
+    fn face(&self, family: &str, bold: bool) -> Option<&FontFace> {
+        let family = self.families.get(&family.to_lowercase())?;
+        if bold {
+            family.bold.as_ref().or(family.regular.as_ref())
+        } else {
+            family.regular.as_ref()
+        }
+    }
+}
+
+/// Builtin fonts closest to a family name (regular, bold).
+fn builtin_fonts(family: &str) -> (BuiltinFont, BuiltinFont) {
+    let family = family.to_lowercase();
+    if family.contains("mono") || family.contains("courier") {
+        (BuiltinFont::Courier, BuiltinFont::CourierBold)
+    } else if family.contains("times") || (family.contains("serif") && !family.contains("sans")) {
+        (BuiltinFont::TimesRoman, BuiltinFont::TimesBold)
+    } else {
+        (BuiltinFont::Helvetica, BuiltinFont::HelveticaBold)
+    }
+}
+
+/// Average glyph width of a builtin font, in ems.
+fn builtin_em_width(font: BuiltinFont) -> f32 {
+    match font {
+        BuiltinFont::Courier | BuiltinFont::CourierBold => 0.6,
+        BuiltinFont::TimesRoman => 0.45,
+        BuiltinFont::TimesBold => 0.5,
+        BuiltinFont::HelveticaBold => 0.56,
+        _ => 0.52,
+    }
+}
+
+/// A font registered in the document, with a way to measure text.
+struct DocFont<'a> {
+    reference: IndirectFontRef,
+    face: Option<&'a FontFace>,
+    builtin: BuiltinFont,
+}
+
+impl DocFont<'_> {
+    fn text_width_mm(&self, text: &str, size_pt: f32) -> f32 {
+        match self.face {
+            Some(face) => face.text_width_mm(text, size_pt),
+            None => pt_to_mm(text.chars().count() as f32 * size_pt * builtin_em_width(self.builtin)),
+        }
+    }
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/// Renders a certificate to PDF bytes.
+pub fn render_certificate(
+    content: &CertificateContent,
+    template: &RenderTemplate,
+    assets: &RenderAssets,
+    fonts: &FontLibrary,
+) -> Result<Vec<u8>, CertificatesError> {
+    let layout = &template.layout;
+    let (doc, page, layer) = PdfDocument::new(
+        "Certificate of Completion",
+        Mm(layout.page_width_mm),
+        Mm(layout.page_height_mm),
+        "Certificate",
+    );
+    let layer = doc.get_page(page).get_layer(layer);
+
+    let (builtin_regular, builtin_bold) = builtin_fonts(&template.font_family);
+    let add_font = |bold: bool| -> Result<DocFont, CertificatesError> {
+        let builtin = if bold { builtin_bold } else { builtin_regular };
+        let face = fonts.face(&template.font_family, bold);
+        let reference = match face {
+            Some(face) => doc.add_external_font(face.data.as_slice()),
+            None => doc.add_builtin_font(builtin),
+        }
+        .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))?;
+
+        Ok(DocFont { reference, face, builtin })
+    };
+    let regular = add_font(false)?;
+    let bold = add_font(true)?;
+
+    let primary = parse_hex_color(&template.primary_color).unwrap_or(Rgb::new(0.0, 0.0, 0.0, None));
+    let secondary = parse_hex_color(&template.secondary_color).unwrap_or(Rgb::new(0.0, 0.0, 0.0, None));
+
+    // Background covers the whole page
+    if let Some(background) = &assets.background {
+        let block = ImageBlock {
+            x_mm: 0.0,
+            y_mm: 0.0,
+            width_mm: layout.page_width_mm,
+            height_mm: Some(layout.page_height_mm),
+        };
+        draw_image(&layer, background, &block);
+    }
+
+    if let (Some(logo), Some(block)) = (&assets.logo, &layout.logo) {
+        draw_image(&layer, logo, block);
+    }
+
+    for text in &layout.texts {
+        let font = if text.bold { &bold } else { &regular };
+        let default_color = if text.bold { &primary } else { &secondary };
+        draw_text(&layer, text, &content.fill(&text.text), font, default_color);
+    }
+
+    for (i, signature) in layout.signatures.iter().enumerate() {
+        let image = assets.signatures.get(i).and_then(Option::as_ref);
+        draw_signature(&layer, signature, image, content, &regular, &bold, &primary, &secondary);
+    }
+
+    if let Some(block) = &layout.qr_code {
+        draw_qr_code(&layer, &content.verification_url, block)?;
+    }
+
+    doc.save_to_bytes()
+        .map_err(|e| CertificatesError::PdfGenerationFailed(e.to_string()))
+}
+
+fn draw_image(layer: &PdfLayerReference, image: &DynamicImage, block: &ImageBlock) {
+    let (width_px, height_px) = image.dimensions();
+    if width_px == 0 || height_px == 0 {
+        return;
+    }
+
+    // Natural size at the embedding resolution
+    let natural_width = px_to_mm(width_px);
+    let natural_height = px_to_mm(height_px);
+    let scale_x = block.width_mm / natural_width;
+    let scale_y = block.height_mm.map(|h| h / natural_height).unwrap_or(scale_x);
+
+    Image::from_dynamic_image(image).add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(block.x_mm)),
+            translate_y: Some(Mm(block.y_mm)),
+            scale_x: Some(scale_x),
+            scale_y: Some(scale_y),
+            dpi: Some(IMAGE_DPI),
+            ..Default::default()
+        },
+    );
+}
+
+fn draw_text(layer: &PdfLayerReference, block: &TextBlock, text: &str, font: &DocFont, default_color: &Rgb) {
+    if text.is_empty() {
+        return;
+    }
+
+    let color = block.color.as_deref().and_then(parse_hex_color).unwrap_or_else(|| default_color.clone());
+    let x = aligned_x(block.x_mm, font.text_width_mm(text, block.size_pt), block.align);
+
+    layer.set_fill_color(Color::Rgb(color));
+    layer.use_text(text, block.size_pt, Mm(x), Mm(block.y_mm), &font.reference);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_signature(
+    layer: &PdfLayerReference,
+    block: &SignatureBlock,
+    image: Option<&DynamicImage>,
+    content: &CertificateContent,
+    regular: &DocFont,
+    bold: &DocFont,
+    primary: &Rgb,
+    secondary: &Rgb,
+) {
+    let left = block.x_mm - block.width_mm / 2.0;
+    let right = block.x_mm + block.width_mm / 2.0;
+
+    // Signature image sits on the line, centered
+    if let Some(image) = image {
+        let width_mm = block.width_mm * 0.8;
+        draw_image(
+            layer,
+            image,
+            &ImageBlock { x_mm: block.x_mm - width_mm / 2.0, y_mm: block.y_mm + 1.0, width_mm, height_mm: None },
+        );
+    }
+
+    layer.set_outline_color(Color::Rgb(secondary.clone()));
+    layer.set_outline_thickness(0.75);
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(left), Mm(block.y_mm)), false),
+            (Point::new(Mm(right), Mm(block.y_mm)), false),
+        ],
+        is_closed: false,
+    });
+
+    let name = TextBlock {
+        text: block.name.clone(),
+        x_mm: block.x_mm,
+        y_mm: block.y_mm - 6.0,
+        size_pt: 12.0,
+        bold: true,
+        align: TextAlign::Center,
+        color: None,
+    };
+    draw_text(layer, &name, &content.fill(&name.text), bold, primary);
+
+    if let Some(title) = &block.title {
+        let title = TextBlock {
+            text: title.clone(),
+            y_mm: block.y_mm - 11.0,
+            size_pt: 10.0,
+            bold: false,
+            ..name
+        };
+        draw_text(layer, &title, &content.fill(&title.text), regular, secondary);
+    }
+}
+
+/// Draws the QR code as vector squares so it stays sharp when printed.
+fn draw_qr_code(layer: &PdfLayerReference, url: &str, block: &ImageBlock) -> Result<(), CertificatesError> {
+    let code = QrCode::new(url.as_bytes())
+        .map_err(|e| CertificatesError::PdfGenerationFailed(format!("QR code: {}", e)))?;
+    let modules = code.width();
+    let colors = code.to_colors();
+
+    let side = block.width_mm;
+    let module = side / (modules + 2 * QR_QUIET_ZONE) as f32;
+    let top = block.y_mm + side;
+    let origin_x = block.x_mm + module * QR_QUIET_ZONE as f32;
+    let origin_y = top - module * QR_QUIET_ZONE as f32;
+
+    layer.set_fill_color(Color::Rgb(Rgb::new(1.0, 1.0, 1.0, None)));
+    layer.add_rect(
+        Rect::new(Mm(block.x_mm), Mm(block.y_mm), Mm(block.x_mm + side), Mm(top)).with_mode(PaintMode::Fill),
+    );
+
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+    for (i, color) in colors.iter().enumerate() {
+        if *color != qrcode::Color::Dark {
+            continue;
+        }
+        let (row, col) = (i / modules, i % modules);
+        let x = origin_x + col as f32 * module;
+        let y = origin_y - (row + 1) as f32 * module;
+        layer.add_rect(Rect::new(Mm(x), Mm(y), Mm(x + module), Mm(y + module)).with_mode(PaintMode::Fill));
+    }
+
+    Ok(())
+}
+
+/// Left edge of a text of `width` aligned at `x`.
+fn aligned_x(x: f32, width: f32, align: TextAlign) -> f32 {
+    match align {
+        TextAlign::Left => x,
+        TextAlign::Center => x - width / 2.0,
+        TextAlign::Right => x - width,
+    }
+}
+
+/// Parses `#rrggbb` or `#rgb`.
+fn parse_hex_color(hex: &str) -> Option<Rgb> {
+    let hex = hex.trim().trim_start_matches('#');
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+
+    let channel = |i: usize| {
+        u8::from_str_radix(expanded.get(i..i + 2)?, 16)
+            .ok()
+            .map(|v| f32::from(v) / 255.0)
+    };
+
+    Some(Rgb::new(channel(0)?, channel(2)?, channel(4)?, None))
+}
+
+fn pt_to_mm(pt: f32) -> f32 {
+    pt * 25.4 / 72.0
+}
+
+fn px_to_mm(px: u32) -> f32 {
+    px as f32 / IMAGE_DPI * 25.4
+}
+
+// =============================================================================
+// Assets
+// =============================================================================
+
+/// Fetches template images from http(s) URLs or local paths.
+#[derive(Clone)]
+pub struct AssetLoader {
+    client: reqwest::Client,
+}
+
+impl Default for AssetLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssetLoader {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self { client }
+    }
+
+    /// Loads the images a template needs. Missing or broken images are
+    /// logged and left out so the certificate still renders.
+    pub async fn load(&self, template: &RenderTemplate) -> RenderAssets {
+        let mut signatures = Vec::with_capacity(template.layout.signatures.len());
+        for signature in &template.layout.signatures {
+            signatures.push(self.image(signature.image_url.as_deref()).await);
+        }
+
+        RenderAssets {
+            background: self.image(template.background_url.as_deref()).await,
+            logo: self.image(template.logo_url.as_deref()).await,
+            signatures,
+        }
+    }
+
+    async fn image(&self, source: Option<&str>) -> Option<DynamicImage> {
+        let source = source.filter(|s| !s.trim().is_empty())?;
+
+        let bytes = match self.fetch(source).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Failed to load certificate image {}: {}", source, e);
+                return None;
+            }
+        };
+
+        match image_crate::load_from_memory(&bytes) {
+            Ok(image) => Some(image),
+            Err(e) => {
+                tracing::warn!("Failed to decode certificate image {}: {}", source, e);
+                None
+            }
+        }
+    }
+
+    async fn fetch(&self, source: &str) -> Result<Vec<u8>, String> {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            let response = self.client
+                .get(source)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| e.to_string())?;
+            let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+            return Ok(bytes.to_vec());
+        }
+
+        let path = source.strip_prefix("file://").unwrap_or(source);
+        tokio::fs::read(path).await.map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use printpdf::image_crate::{Rgba, RgbaImage};
+
+    fn content() -> CertificateContent {
+        CertificateContent {
+            student_name: "Ana Pérez".to_string(),
+            course_title: "Rust for Backend Developers".to_string(),
+            instructor_name: "Luis Gómez".to_string(),
+            completion_date: "October 16, 2026".to_string(),
+            verification_code: "ACC-2026-AB12-CD34".to_string(),
+            verification_url: "https://acc-lms.com/verify/ACC-2026-AB12-CD34".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let text = content().fill("{student_name} completed {course_title} on {completion_date}");
+        assert_eq!(text, "Ana Pérez completed Rust for Backend Developers on October 16, 2026");
+    }
+
+    #[test]
+    fn test_alignment() {
+        assert_eq!(aligned_x(100.0, 40.0, TextAlign::Left), 100.0);
+        assert_eq!(aligned_x(100.0, 40.0, TextAlign::Center), 80.0);
+        assert_eq!(aligned_x(100.0, 40.0, TextAlign::Right), 60.0);
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        let color = parse_hex_color("#ff8000").unwrap();
+        assert_eq!((color.r, color.g, color.b), (1.0, 128.0 / 255.0, 0.0));
+        assert_eq!(parse_hex_color("#fff").unwrap().g, 1.0);
+        assert!(parse_hex_color("blue").is_none());
+        assert!(parse_hex_color("#12345").is_none());
+    }
+
+    #[test]
+    fn test_builtin_font_fallback() {
+        assert_eq!(builtin_fonts("serif").0, BuiltinFont::TimesRoman);
+        assert_eq!(builtin_fonts("Open Sans").0, BuiltinFont::Helvetica);
+        assert_eq!(builtin_fonts("JetBrains Mono").1, BuiltinFont::CourierBold);
+    }
+
+    #[test]
+    fn test_render_certificate() {
+        let mut template = RenderTemplate::from_template(None);
+        template.layout.signatures[0].image_url = Some("signature.png".to_string());
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 20, Rgba([20, 40, 80, 255])));
+        let assets = RenderAssets {
+            background: Some(image.clone()),
+            logo: Some(image.clone()),
+            signatures: vec![Some(image)],
+        };
+
+        let pdf = render_certificate(&content(), &template, &assets, &FontLibrary::default()).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
@@ -0,0 +1,105 @@
+//! # Certificate Storage
+//!
+//! Storage backends for rendered certificate PDFs. Certificates are
+//! rendered once and served from storage afterwards.
+
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::domain::errors::CertificatesError;
+
+/// Backend where rendered PDFs are kept.
+#[async_trait]
+pub trait CertificateStorage: Send + Sync {
+    /// Stores a file and returns its public URL.
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<String, CertificatesError>;
+
+    /// Reads a file; `None` when it does not exist.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CertificatesError>;
+}
+
+/// Stores files on the local filesystem (a shared volume served by the
+/// gateway at `public_url`).
+pub struct LocalCertificateStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalCertificateStorage {
+    pub fn new(root: impl Into<PathBuf>, public_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Resolves a key inside the root, rejecting absolute paths and `..`.
+    fn path(&self, key: &str) -> Result<PathBuf, CertificatesError> {
+        let relative = Path::new(key);
+        let safe = relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+
+        if key.is_empty() || !safe {
+            return Err(CertificatesError::Validation(format!("Invalid storage key: {}", key)));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl CertificateStorage for LocalCertificateStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> Result<String, CertificatesError> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| CertificatesError::Internal(format!("Storage error: {}", e)))?;
+        }
+
+        // Write to a temporary file first so readers never see partial PDFs
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, &data)
+            .await
+            .map_err(|e| CertificatesError::Internal(format!("Storage error: {}", e)))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| CertificatesError::Internal(format!("Storage error: {}", e)))?;
+
+        Ok(format!("{}/{}", self.public_url, key))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CertificatesError> {
+        let path = self.path(key)?;
+
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CertificatesError::Internal(format!("Storage error: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_storage_roundtrip() {
+        let root = std::env::temp_dir().join(format!("certificates-{}", uuid::Uuid::new_v4()));
+        let storage = LocalCertificateStorage::new(&root, "https://cdn.example.com/certificates/");
+
+        let url = storage.put("2026/abc.pdf", b"%PDF-1.3".to_vec(), "application/pdf").await.unwrap();
+
+        assert_eq!(url, "https://cdn.example.com/certificates/2026/abc.pdf");
+        assert_eq!(storage.get("2026/abc.pdf").await.unwrap().unwrap(), b"%PDF-1.3");
+        assert!(storage.get("2026/missing.pdf").await.unwrap().is_none());
+        assert!(storage.get("../etc/passwd").await.is_err());
+        assert!(storage.get("/etc/passwd").await.is_err());
+
+        tokio::fs::remove_dir_all(root).await.ok();
+    }
+}
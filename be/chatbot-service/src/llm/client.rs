@@ -0,0 +1,250 @@
+//! # Model Client
+//!
+//! HTTP client for OpenAI-compatible chat completion and embedding endpoints.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of characters of an error body included in error messages.
+const ERROR_BODY_LIMIT: usize = 500;
+
+/// Client for an OpenAI-compatible endpoint.
+#[derive(Clone)]
+pub struct ModelClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    chat_model: String,
+    embedding_model: String,
+}
+
+impl ModelClient {
+    /// `base_url` is the API root, e.g. `https://api.openai.com/v1` or
+    /// `http://localhost:11434/v1` (Ollama).
+    pub fn new(base_url: &str, chat_model: &str, embedding_model: &str, timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            chat_model: chat_model.to_string(),
+            embedding_model: embedding_model.to_string(),
+        }
+    }
+
+    /// Sends `Authorization: Bearer <key>`.
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Name of the embedding model. Stored with each vector so that vectors
+    /// of different models are never compared.
+    pub fn embedding_model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    /// Runs a chat completion that must answer with a JSON object and
+    /// returns the raw content.
+    pub async fn chat_json(
+        &self,
+        messages: Vec<ChatMessage>,
+        max_tokens: u32,
+        temperature: f32,
+    ) -> Result<String, String> {
+        let request = ChatCompletionRequest {
+            model: &self.chat_model,
+            messages,
+            max_tokens,
+            temperature,
+            response_format: ResponseFormat { kind: "json_object" },
+            stream: false,
+        };
+
+        let response: ChatCompletionResponse = self.post("/chat/completions", &request).await?;
+
+        response.choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .filter(|content| !content.trim().is_empty())
+            .ok_or_else(|| "Model returned an empty answer".to_string())
+    }
+
+    /// Generates the embedding of a text.
+    pub async fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        self.embed_batch(&[text.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| "No embedding returned".to_string())
+    }
+
+    /// Generates embeddings for several texts, in input order.
+    pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let request = EmbeddingRequest {
+            model: &self.embedding_model,
+            input: texts.iter().map(String::as_str).collect(),
+        };
+
+        let response: EmbeddingResponse = self.post("/embeddings", &request).await?;
+
+        let mut data = response.data;
+        if data.len() != texts.len() {
+            return Err(format!("Expected {} embeddings, got {}", texts.len(), data.len()));
+        }
+        data.sort_by_key(|d| d.index);
+
+        Ok(data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    async fn post<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<R, String> {
+        let mut request = self.http.post(format!("{}{}", self.base_url, path)).json(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let body: String = body.chars().take(ERROR_BODY_LIMIT).collect();
+            return Err(format!("Model endpoint returned {}: {}", status, body));
+        }
+
+        response.json::<R>().await.map_err(|e| e.to_string())
+    }
+}
+
+// Wire types (subset of the OpenAI API)
+
+/// Chat message sent to the model.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: String) -> Self {
+        Self { role: "system", content }
+    }
+
+    pub fn user(content: String) -> Self {
+        Self { role: "user", content }
+    }
+
+    pub fn assistant(content: String) -> Self {
+        Self { role: "assistant", content }
+    }
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    max_tokens: u32,
+    temperature: f32,
+    response_format: ResponseFormat,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: Vec<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_chat_json_requests_json_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer local-key"))
+            .and(body_partial_json(json!({
+                "model": "llama3.1",
+                "response_format": {"type": "json_object"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "{\"ok\": true}"}}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ModelClient::new(&format!("{}/v1/", server.uri()), "llama3.1", "nomic-embed-text", Duration::from_secs(5))
+            .with_api_key("local-key");
+
+        let content = client.chat_json(vec![ChatMessage::user("hola".to_string())], 100, 0.0).await.unwrap();
+        assert_eq!(content, "{\"ok\": true}");
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch_keeps_input_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [
+                    {"index": 1, "embedding": [0.0, 1.0]},
+                    {"index": 0, "embedding": [1.0, 0.0]}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ModelClient::new(&server.uri(), "chat", "embed", Duration::from_secs(5));
+        let embeddings = client.embed_batch(&["a".to_string(), "b".to_string()]).await.unwrap();
+
+        assert_eq!(embeddings, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+}
@@ -0,0 +1,69 @@
+//! # Language Model Access
+//!
+//! Chat completions and embeddings through any endpoint implementing the
+//! OpenAI `/chat/completions` and `/embeddings` API: OpenAI itself, vLLM,
+//! Ollama, LocalAI, or a mock server in tests.
+//!
+//! | Variable | Default |
+//! |----------|---------|
+//! | `CHATBOT_LLM_BASE_URL` | `https://api.openai.com/v1` |
+//! | `CHATBOT_LLM_API_KEY` | `OPENAI_API_KEY`, none for local servers |
+//! | `CHATBOT_CHAT_MODEL` | `gpt-4o-mini` |
+//! | `CHATBOT_EMBEDDING_MODEL` | `text-embedding-3-small` |
+//! | `CHATBOT_LLM_TIMEOUT_SECS` | `30` |
+
+pub mod client;
+pub mod prompts;
+
+pub use client::{ChatMessage, ModelClient};
+
+use std::time::Duration;
+
+/// Model endpoint configuration.
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub chat_model: String,
+    pub embedding_model: String,
+    pub timeout: Duration,
+}
+
+impl LlmConfig {
+    /// Loads the configuration from environment variables.
+    pub fn from_env() -> Self {
+        Self {
+            base_url: std::env::var("CHATBOT_LLM_BASE_URL")
+                .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
+            api_key: std::env::var("CHATBOT_LLM_API_KEY")
+                .or_else(|_| std::env::var("OPENAI_API_KEY"))
+                .ok()
+                .filter(|key| !key.is_empty()),
+            chat_model: std::env::var("CHATBOT_CHAT_MODEL")
+                .unwrap_or_else(|_| "gpt-4o-mini".to_string()),
+            embedding_model: std::env::var("CHATBOT_EMBEDDING_MODEL")
+                .unwrap_or_else(|_| "text-embedding-3-small".to_string()),
+            timeout: Duration::from_secs(
+                std::env::var("CHATBOT_LLM_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(30),
+            ),
+        }
+    }
+
+    /// Builds the client for this endpoint.
+    pub fn build_client(&self) -> ModelClient {
+        let client = ModelClient::new(
+            &self.base_url,
+            &self.chat_model,
+            &self.embedding_model,
+            self.timeout,
+        );
+
+        match &self.api_key {
+            Some(api_key) => client.with_api_key(api_key),
+            None => client,
+        }
+    }
+}
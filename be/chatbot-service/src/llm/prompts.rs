@@ -0,0 +1,213 @@
+//! # Prompts
+//!
+//! Prompt templates for intent classification and grounded answers, and
+//! parsing of the JSON the model answers with.
+
+use serde::Deserialize;
+
+use crate::domain::{IntentCategory, KBSearchResult, UserRole};
+
+/// Maximum characters of an article included as a source.
+const SOURCE_CONTENT_LIMIT: usize = 1500;
+
+/// Intent categories offered to the model, with a short description.
+const INTENT_CATEGORIES: &[(&str, &str)] = &[
+    ("greeting", "saludo"),
+    ("farewell", "despedida"),
+    ("thanks", "agradecimiento"),
+    ("help", "pide ayuda general o no sabe cómo usar la plataforma"),
+    ("course_progress", "progreso o avance en sus cursos"),
+    ("course_content", "contenido, lecciones o materiales de un curso"),
+    ("certificate", "certificados de finalización"),
+    ("payment", "pagos, precios, reembolsos o facturas"),
+    ("technical_issue", "errores o fallas técnicas"),
+    ("course_creation", "crear o publicar cursos (instructores)"),
+    ("analytics", "estadísticas de sus cursos (instructores)"),
+    ("earnings", "ingresos y pagos a instructores"),
+    ("student_management", "gestionar estudiantes de sus cursos"),
+    ("user_management", "gestionar usuarios de la plataforma (administradores)"),
+    ("system_health", "estado del sistema (administradores)"),
+    ("reports", "reportes de la plataforma (administradores)"),
+    ("configuration", "configuración de la plataforma (administradores)"),
+    ("unknown", "ninguna de las anteriores"),
+];
+
+/// Maps a language code to the name used inside prompts.
+pub fn language_name(language: &str) -> &'static str {
+    match language {
+        "es" => "español",
+        "en" => "English",
+        "pt" => "português",
+        _ => "español",
+    }
+}
+
+fn role_name(role: UserRole) -> &'static str {
+    match role {
+        UserRole::Anonymous => "visitante sin sesión",
+        UserRole::Student => "estudiante",
+        UserRole::Instructor => "instructor",
+        UserRole::Admin => "administrador",
+    }
+}
+
+/// Builds the system prompt for intent classification.
+pub fn intent_system_prompt(role: UserRole) -> String {
+    let categories: String = INTENT_CATEGORIES
+        .iter()
+        .map(|(name, description)| format!("- {}: {}\n", name, description))
+        .collect();
+
+    format!(
+        r#"Clasificas mensajes enviados al asistente de soporte de ACC LMS, una plataforma de cursos en línea. El usuario es un {}.
+
+CATEGORÍAS:
+{}
+Responde SOLO con un objeto JSON con el siguiente formato:
+{{"name": "check_progress", "category": "course_progress", "confidence": 0.9, "entities": [{{"type": "course", "value": "texto exacto del mensaje", "confidence": 0.8}}]}}
+
+- "name" es un identificador corto en snake_case de la intención concreta.
+- "category" debe ser una de las categorías listadas.
+- "confidence" va de 0 a 1.
+- "entities" contiene cursos, lecciones, fechas, montos u otros datos mencionados, copiados tal cual aparecen en el mensaje."#,
+        role_name(role),
+        categories
+    )
+}
+
+/// Builds the system prompt for an answer grounded in `sources`.
+///
+/// Sources are numbered from 1; the model cites them by number.
+pub fn answer_system_prompt(role: UserRole, language: &str, sources: &[KBSearchResult]) -> String {
+    let sources_text = if sources.is_empty() {
+        "(no se encontraron artículos relevantes)\n".to_string()
+    } else {
+        sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                let content: String = source.article.content.chars().take(SOURCE_CONTENT_LIMIT).collect();
+                format!("[{}] {}\n{}\n\n", i + 1, source.article.title, content)
+            })
+            .collect()
+    };
+
+    format!(
+        r#"Eres el asistente de soporte de ACC LMS, una plataforma de cursos en línea. Hablas con un {}. Responde en {}.
+
+REGLAS:
+1. Responde únicamente con información de las FUENTES. No inventes pasos, precios, plazos ni políticas.
+2. Cita las fuentes que usaste por su número.
+3. Si las fuentes no responden la pregunta, dilo y marca "answerable" como false.
+4. Si el usuario pide hablar con una persona, o el problema requiere revisar su cuenta o un pago concreto, usa "kind": "handoff".
+5. Para saludos, agradecimientos y despedidas usa "kind": "smalltalk" sin citas.
+6. Sé breve y amable.
+
+FUENTES:
+{}Responde SOLO con un objeto JSON con el siguiente formato:
+{{"kind": "answer", "answer": "texto de la respuesta", "citations": [1], "answerable": true, "confidence": 0.8}}
+
+"kind" es "answer", "smalltalk" o "handoff"; "confidence" (0 a 1) indica qué tan seguro estás de que la respuesta es correcta y completa."#,
+        role_name(role),
+        language_name(language),
+        sources_text
+    )
+}
+
+/// Strips the markdown code fences some models wrap JSON answers in.
+pub fn extract_json(content: &str) -> &str {
+    let trimmed = content.trim();
+
+    match trimmed.strip_prefix("```") {
+        Some(rest) => {
+            let body = rest.split_once('\n').map(|(_, body)| body).unwrap_or_default();
+            body.trim_end().trim_end_matches("```").trim()
+        }
+        None => trimmed,
+    }
+}
+
+/// Intent classification answer.
+#[derive(Debug, Deserialize)]
+pub struct RawIntent {
+    pub name: String,
+    pub category: String,
+    #[serde(default)]
+    pub confidence: f64,
+    #[serde(default)]
+    pub entities: Vec<RawEntity>,
+}
+
+/// Entity as returned by the model, without offsets.
+#[derive(Debug, Deserialize)]
+pub struct RawEntity {
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    pub value: String,
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+/// Kind of a grounded answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerKind {
+    #[default]
+    Answer,
+    Smalltalk,
+    Handoff,
+}
+
+/// Grounded answer.
+#[derive(Debug, Deserialize)]
+pub struct RawAnswer {
+    #[serde(default)]
+    pub kind: AnswerKind,
+    pub answer: String,
+    /// 1-based source numbers
+    #[serde(default)]
+    pub citations: Vec<usize>,
+    #[serde(default)]
+    pub answerable: bool,
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+/// Parses the intent classification answer.
+pub fn parse_intent(content: &str) -> Result<RawIntent, String> {
+    serde_json::from_str(extract_json(content))
+        .map_err(|e| format!("Failed to parse intent response: {}", e))
+}
+
+/// Parses the grounded answer.
+pub fn parse_answer(content: &str) -> Result<RawAnswer, String> {
+    serde_json::from_str(extract_json(content))
+        .map_err(|e| format!("Failed to parse answer response: {}", e))
+}
+
+/// Maps a category name to [`IntentCategory`], `Unknown` if not recognized.
+pub fn parse_category(name: &str) -> IntentCategory {
+    serde_json::from_value(serde_json::Value::String(name.trim().to_lowercase()))
+        .unwrap_or(IntentCategory::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer_with_fences_and_defaults() {
+        let answer = parse_answer("```json\n{\"answer\": \"Hola\", \"citations\": [2]}\n```").unwrap();
+
+        assert_eq!(answer.kind, AnswerKind::Answer);
+        assert_eq!(answer.citations, vec![2]);
+        assert!(!answer.answerable);
+    }
+
+    #[test]
+    fn test_parse_category() {
+        assert_eq!(parse_category("course_progress"), IntentCategory::CourseProgress);
+        assert_eq!(parse_category(" Certificate "), IntentCategory::Certificate);
+        assert_eq!(parse_category("weather"), IntentCategory::Unknown);
+    }
+}
@@ -7,7 +7,8 @@
 //! - Multi-role support (Anonymous, Student, Instructor, Admin)
 //! - Contextual conversations with session management
 //! - Knowledge base integration with semantic search
-//! - Intelligent escalation to human agents
+//! - Answers from a language model grounded in KB articles, with citations
+//! - Intelligent escalation to human agents on low calibrated confidence
 //! - Feedback collection and analytics
 //! - Rich content support (cards, carousels, code snippets)
 //!
@@ -34,21 +35,31 @@
 //! ### Health
 //! - GET    /health - Health check
 //! - GET    /ready  - Readiness check
+//!
+//! ## Language Model
+//!
+//! Any OpenAI-compatible endpoint (see [`llm`]). Published KB articles are
+//! embedded in the background every `CHATBOT_KB_INDEX_INTERVAL_SECS`
+//! (default 60) and retrieved by similarity for each message; responses
+//! below `CHATBOT_ESCALATION_THRESHOLD` (default 0.6) are escalated.
 
 use actix_cors::Cors;
 use actix_web::{middleware, web, App, HttpServer};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod api;
 mod domain;
+mod llm;
 mod repository;
 mod service;
 
 use api::{configure_routes, AppState};
 use repository::ChatbotRepository;
-use service::{ChatbotService, AIClient, AIResponse};
+use llm::LlmConfig;
+use service::{AIClient, AssistantConfig, ChatbotService, KbIndexer, RetrievalAIClient};
 
 /// Server configuration.
 #[derive(Debug, Clone)]
@@ -57,7 +68,8 @@ struct Config {
     port: u16,
     database_url: String,
     max_connections: u32,
-    openai_api_key: Option<String>,
+    escalation_threshold: f64,
+    kb_index_interval: Duration,
 }
 
 impl Config {
@@ -76,113 +88,20 @@ impl Config {
                 .unwrap_or_else(|_| "10".to_string())
                 .parse()
                 .expect("DATABASE_MAX_CONNECTIONS must be a number"),
-            openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
+            escalation_threshold: std::env::var("CHATBOT_ESCALATION_THRESHOLD")
+                .unwrap_or_else(|_| "0.6".to_string())
+                .parse()
+                .expect("CHATBOT_ESCALATION_THRESHOLD must be a number"),
+            kb_index_interval: Duration::from_secs(
+                std::env::var("CHATBOT_KB_INDEX_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .expect("CHATBOT_KB_INDEX_INTERVAL_SECS must be a number"),
+            ),
         }
     }
 }
 
-// =============================================================================
-// SIMPLE AI CLIENT (for initial implementation)
-// =============================================================================
-
-/// Simple rule-based AI client (can be replaced with OpenAI integration).
-struct SimpleAIClient;
-
-#[async_trait::async_trait]
-impl AIClient for SimpleAIClient {
-    async fn generate_response(
-        &self,
-        message: &str,
-        context: &domain::ConversationContext,
-        role: domain::UserRole,
-        _history: &[domain::Message],
-    ) -> std::result::Result<AIResponse, String> {
-        let message_lower = message.to_lowercase();
-
-        // Simple keyword-based responses (can be enhanced with actual AI)
-        let (text, confidence) = if message_lower.contains("hola") || message_lower.contains("buenos") {
-            ("¡Hola! ¿En qué puedo ayudarte?".to_string(), 0.95)
-        } else if message_lower.contains("gracias") {
-            ("¡De nada! ¿Hay algo más en lo que pueda ayudarte?".to_string(), 0.95)
-        } else if message_lower.contains("progreso") || message_lower.contains("avance") {
-            match role {
-                domain::UserRole::Student => {
-                    ("Puedes ver tu progreso en la sección 'Mi Progreso' del dashboard. Ahí verás el porcentaje completado de cada curso y tus logros.".to_string(), 0.85)
-                }
-                _ => ("Para ver el progreso necesitas estar registrado como estudiante.".to_string(), 0.7)
-            }
-        } else if message_lower.contains("certificado") {
-            ("Los certificados se generan automáticamente cuando completas un curso al 100%. Los encontrarás en la sección 'Mis Certificados'.".to_string(), 0.85)
-        } else if message_lower.contains("pago") || message_lower.contains("precio") {
-            ("Para temas de pagos, te recomiendo revisar nuestra sección de FAQ o contactar a soporte para ayuda personalizada.".to_string(), 0.75)
-        } else if message_lower.contains("curso") && message_lower.contains("crear") {
-            match role {
-                domain::UserRole::Instructor | domain::UserRole::Admin => {
-                    ("Para crear un curso, ve a tu dashboard de instructor y haz clic en 'Nuevo Curso'. Te guiaré paso a paso en el proceso.".to_string(), 0.85)
-                }
-                _ => ("Solo los instructores pueden crear cursos. Si deseas convertirte en instructor, revisa nuestra sección de registro de instructores.".to_string(), 0.8)
-            }
-        } else if message_lower.contains("problema") || message_lower.contains("error") || message_lower.contains("no funciona") {
-            ("Lamento que estés teniendo problemas. ¿Podrías darme más detalles sobre lo que está pasando? Si prefieres, puedo escalarlo a un agente humano.".to_string(), 0.6)
-        } else if message_lower.contains("humano") || message_lower.contains("agente") || message_lower.contains("persona") {
-            return Ok(AIResponse {
-                text: "Entendido, te voy a conectar con un agente humano que podrá ayudarte mejor.".to_string(),
-                confidence: 1.0,
-                intent: None,
-                suggested_articles: vec![],
-                should_escalate: true,
-                rich_content: None,
-            });
-        } else {
-            ("No estoy seguro de entender tu consulta. ¿Podrías reformularla o ser más específico?".to_string(), 0.4)
-        };
-
-        Ok(AIResponse {
-            text,
-            confidence,
-            intent: None,
-            suggested_articles: vec![],
-            should_escalate: false,
-            rich_content: None,
-        })
-    }
-
-    async fn detect_intent(
-        &self,
-        message: &str,
-        _role: domain::UserRole,
-    ) -> std::result::Result<domain::DetectedIntent, String> {
-        let message_lower = message.to_lowercase();
-
-        let (name, category, confidence) = if message_lower.contains("hola") || message_lower.contains("buenos") {
-            ("greeting", domain::IntentCategory::Greeting, 0.95)
-        } else if message_lower.contains("gracias") {
-            ("thanks", domain::IntentCategory::Thanks, 0.95)
-        } else if message_lower.contains("adiós") || message_lower.contains("chao") {
-            ("farewell", domain::IntentCategory::Farewell, 0.95)
-        } else if message_lower.contains("progreso") {
-            ("check_progress", domain::IntentCategory::CourseProgress, 0.85)
-        } else if message_lower.contains("certificado") {
-            ("get_certificate", domain::IntentCategory::Certificate, 0.85)
-        } else if message_lower.contains("pago") {
-            ("payment_help", domain::IntentCategory::Payment, 0.8)
-        } else if message_lower.contains("crear") && message_lower.contains("curso") {
-            ("create_course", domain::IntentCategory::CourseCreation, 0.85)
-        } else if message_lower.contains("problema") || message_lower.contains("error") {
-            ("technical_issue", domain::IntentCategory::TechnicalIssue, 0.7)
-        } else {
-            ("unknown", domain::IntentCategory::Unknown, 0.3)
-        };
-
-        Ok(domain::DetectedIntent {
-            name: name.to_string(),
-            category,
-            confidence,
-            entities: vec![],
-        })
-    }
-}
-
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     // Initialize tracing
@@ -210,13 +129,28 @@ async fn main() -> std::io::Result<()> {
     // Create repository
     let repository = ChatbotRepository::new(pool);
 
-    // Create AI client (can be replaced with OpenAI integration)
-    let ai_client: Arc<dyn AIClient> = Arc::new(SimpleAIClient);
+    // Create AI client
+    let llm_config = LlmConfig::from_env();
+    let model = llm_config.build_client();
+    info!(
+        "Language model: {} (chat: {}, embeddings: {})",
+        llm_config.base_url, llm_config.chat_model, llm_config.embedding_model
+    );
+
+    let ai_client: Arc<dyn AIClient> = Arc::new(RetrievalAIClient::new(
+        model.clone(),
+        repository.clone(),
+        AssistantConfig::from_env(),
+    ));
+
+    // Keep KB embeddings up to date
+    let indexer = KbIndexer::new(repository.clone(), model, config.kb_index_interval);
+    tokio::spawn(indexer.run());
 
     // Create service
     let service = Arc::new(
         ChatbotService::new(repository, ai_client)
-            .with_escalation_threshold(0.6)
+            .with_escalation_threshold(config.escalation_threshold)
     );
 
     // Create app state
@@ -287,6 +287,32 @@ impl ChatbotRepository {
         Ok(rows.iter().map(row_to_message).collect())
     }
 
+    /// Gets the last `limit` messages of a conversation, oldest first.
+    pub async fn get_recent_messages(
+        &self,
+        conversation_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Message>> {
+        let rows: Vec<PgRow> = sqlx::query(r#"
+            SELECT * FROM (
+                SELECT
+                    message_id, conversation_id, sender, content, timestamp,
+                    intent, confidence, feedback, metadata
+                FROM chatbot.messages
+                WHERE conversation_id = $1
+                ORDER BY timestamp DESC
+                LIMIT $2
+            ) recent
+            ORDER BY timestamp ASC
+        "#)
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_message).collect())
+    }
+
     /// Adds feedback to a message.
     pub async fn add_message_feedback(
         &self,
@@ -451,16 +477,7 @@ impl ChatbotRepository {
             let article = row_to_article(row);
             let relevance: f64 = row.get("relevance");
 
-            // Create snippet from content
-            let snippet = article.summary.clone()
-                .unwrap_or_else(|| {
-                    let content = &article.content;
-                    if content.len() > 200 {
-                        format!("{}...", &content[..200])
-                    } else {
-                        content.clone()
-                    }
-                });
+            let snippet = article_snippet(&article);
 
             KBSearchResult {
                 article,
@@ -471,6 +488,118 @@ impl ChatbotRepository {
         }).collect())
     }
 
+    /// Searches KB articles by cosine similarity to a query embedding.
+    ///
+    /// Only articles indexed with `model` are compared, and only those aimed
+    /// at `role` or at everyone (`anonymous`).
+    pub async fn search_articles_by_embedding(
+        &self,
+        embedding: &[f32],
+        model: &str,
+        role: UserRole,
+        language: &str,
+        min_similarity: f64,
+        limit: i64,
+    ) -> Result<Vec<KBSearchResult>> {
+        let rows: Vec<PgRow> = sqlx::query(r#"
+            SELECT
+                a.article_id, a.slug, a.title, a.content, a.summary, a.category, a.subcategory,
+                a.tags, a.keywords, a.intent_triggers, a.target_roles, a.language,
+                a.status, a.view_count, a.helpful_count, a.not_helpful_count,
+                a.created_at, a.updated_at, a.author_id,
+                1 - (e.embedding <=> $1::vector) as similarity
+            FROM chatbot.kb_article_embeddings e
+            JOIN chatbot.kb_articles a ON a.article_id = e.article_id
+            WHERE e.model = $2
+              AND a.status = 'published'
+              AND a.language = $3
+              AND ($4 = ANY(a.target_roles) OR 'anonymous' = ANY(a.target_roles))
+              AND 1 - (e.embedding <=> $1::vector) >= $5
+            ORDER BY e.embedding <=> $1::vector
+            LIMIT $6
+        "#)
+        .bind(embedding)
+        .bind(model)
+        .bind(language)
+        .bind(role.to_string())
+        .bind(min_similarity)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| {
+            let article = row_to_article(row);
+            let snippet = article_snippet(&article);
+
+            KBSearchResult {
+                article,
+                relevance_score: row.get("similarity"),
+                matched_keywords: vec![],
+                snippet,
+            }
+        }).collect())
+    }
+
+    /// Lists published articles without an up-to-date embedding for `model`,
+    /// with the hash of their current content.
+    pub async fn list_articles_to_index(
+        &self,
+        model: &str,
+        limit: i64,
+    ) -> Result<Vec<(KBArticle, String)>> {
+        let rows: Vec<PgRow> = sqlx::query(r#"
+            SELECT
+                a.article_id, a.slug, a.title, a.content, a.summary, a.category, a.subcategory,
+                a.tags, a.keywords, a.intent_triggers, a.target_roles, a.language,
+                a.status, a.view_count, a.helpful_count, a.not_helpful_count,
+                a.created_at, a.updated_at, a.author_id,
+                md5(a.title || coalesce(a.summary, '') || a.content) as content_hash
+            FROM chatbot.kb_articles a
+            LEFT JOIN chatbot.kb_article_embeddings e ON e.article_id = a.article_id
+            WHERE a.status = 'published'
+              AND (
+                  e.article_id IS NULL OR
+                  e.model <> $1 OR
+                  e.content_hash <> md5(a.title || coalesce(a.summary, '') || a.content)
+              )
+            ORDER BY a.updated_at
+            LIMIT $2
+        "#)
+        .bind(model)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| (row_to_article(row), row.get("content_hash"))).collect())
+    }
+
+    /// Stores the embedding of an article, replacing the previous one.
+    pub async fn save_article_embedding(
+        &self,
+        article_id: Uuid,
+        model: &str,
+        content_hash: &str,
+        embedding: &[f32],
+    ) -> Result<()> {
+        sqlx::query(r#"
+            INSERT INTO chatbot.kb_article_embeddings (article_id, model, content_hash, embedding)
+            VALUES ($1, $2, $3, $4::vector)
+            ON CONFLICT (article_id) DO UPDATE SET
+                model = EXCLUDED.model,
+                content_hash = EXCLUDED.content_hash,
+                embedding = EXCLUDED.embedding,
+                indexed_at = NOW()
+        "#)
+        .bind(article_id)
+        .bind(model)
+        .bind(content_hash)
+        .bind(embedding)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Gets articles by category.
     pub async fn get_articles_by_category(
         &self,
@@ -756,6 +885,19 @@ fn row_to_article(row: &PgRow) -> KBArticle {
     }
 }
 
+/// Summary of an article, or the start of its content.
+fn article_snippet(article: &KBArticle) -> String {
+    if let Some(summary) = &article.summary {
+        return summary.clone();
+    }
+
+    let content = &article.content;
+    match content.char_indices().nth(200) {
+        Some((end, _)) => format!("{}...", &content[..end]),
+        None => content.clone(),
+    }
+}
+
 fn row_to_suggestion(row: &PgRow) -> ContextualSuggestion {
     let target_roles: Vec<String> = row.get("target_roles");
     let conditions: serde_json::Value = row.get("context_conditions");
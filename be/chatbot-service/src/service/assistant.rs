@@ -0,0 +1,563 @@
+//! # Retrieval Assistant
+//!
+//! [`AIClient`] backed by a language model and the knowledge base.
+//!
+//! ## Answer Flow
+//!
+//! ```text
+//! message ──embed──> query vector ──KB similarity──> top-k articles
+//!                                                       │
+//!          history + numbered sources + message ────────┘
+//!                               │
+//!                             model ──> {kind, answer, citations, answerable, confidence}
+//!                               │
+//!          citations ──> RichContent::Links + suggested_articles
+//!          signals   ──> calibrated confidence ──> escalation_threshold
+//! ```
+//!
+//! The model is asked to answer only from the numbered sources. Its own
+//! confidence is not trusted alone: [`ConfidenceCalibrator`] combines it with
+//! how similar the retrieved articles are and whether the answer cites them,
+//! so an ungrounded answer falls below the escalation threshold even when the
+//! model is sure of itself.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::chatbot_service::{AIClient, AIResponse};
+use crate::domain::{
+    ConversationContext, DetectedIntent, ExtractedEntity, KBSearchResult, LinkItem, Message,
+    MessageSender, RichContent, UserRole,
+};
+use crate::llm::prompts::{self, AnswerKind};
+use crate::llm::{ChatMessage, ModelClient};
+use crate::repository::ChatbotRepository;
+
+// =============================================================================
+// KNOWLEDGE RETRIEVAL
+// =============================================================================
+
+/// Finds the knowledge base articles closest to a query embedding.
+#[async_trait]
+pub trait KnowledgeRetriever: Send + Sync {
+    /// Returns up to `limit` published articles for `role` and `language`
+    /// with similarity of at least `min_similarity`, most similar first.
+    /// `relevance_score` holds the cosine similarity.
+    async fn retrieve(
+        &self,
+        embedding: &[f32],
+        model: &str,
+        role: UserRole,
+        language: &str,
+        min_similarity: f64,
+        limit: i64,
+    ) -> Result<Vec<KBSearchResult>, String>;
+}
+
+#[async_trait]
+impl KnowledgeRetriever for ChatbotRepository {
+    async fn retrieve(
+        &self,
+        embedding: &[f32],
+        model: &str,
+        role: UserRole,
+        language: &str,
+        min_similarity: f64,
+        limit: i64,
+    ) -> Result<Vec<KBSearchResult>, String> {
+        self.search_articles_by_embedding(embedding, model, role, language, min_similarity, limit)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+// =============================================================================
+// CONFIDENCE CALIBRATION
+// =============================================================================
+
+/// Signals available after a grounded answer.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfidenceSignals {
+    /// Confidence reported by the model (0-1)
+    pub model_confidence: f64,
+    /// Highest similarity among cited sources, or among all retrieved
+    /// sources if none was cited (0 when nothing was retrieved)
+    pub top_similarity: f64,
+    /// Whether the answer cites at least one source
+    pub cited: bool,
+    /// Whether the model considers the sources sufficient
+    pub answerable: bool,
+}
+
+/// Logistic combination of [`ConfidenceSignals`].
+///
+/// With the defaults, a confident (≥ 0.7) answer citing a clearly related
+/// article (similarity ≥ 0.5) scores above 0.8, while an answer marked
+/// unanswerable, or given without any retrieved article, stays below the
+/// default escalation threshold of 0.6.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfidenceCalibrator {
+    pub bias: f64,
+    pub model_weight: f64,
+    pub similarity_weight: f64,
+    pub citation_weight: f64,
+    pub unanswerable_penalty: f64,
+}
+
+impl Default for ConfidenceCalibrator {
+    fn default() -> Self {
+        Self {
+            bias: -4.0,
+            model_weight: 3.0,
+            similarity_weight: 4.0,
+            citation_weight: 1.5,
+            unanswerable_penalty: 5.0,
+        }
+    }
+}
+
+impl ConfidenceCalibrator {
+    /// Returns the calibrated confidence (0-1).
+    pub fn calibrate(&self, signals: ConfidenceSignals) -> f64 {
+        let mut z = self.bias
+            + self.model_weight * signals.model_confidence.clamp(0.0, 1.0)
+            + self.similarity_weight * signals.top_similarity.clamp(0.0, 1.0);
+
+        if signals.cited {
+            z += self.citation_weight;
+        }
+        if !signals.answerable {
+            z -= self.unanswerable_penalty;
+        }
+
+        1.0 / (1.0 + (-z).exp())
+    }
+}
+
+// =============================================================================
+// RETRIEVAL AI CLIENT
+// =============================================================================
+
+/// Retrieval settings.
+#[derive(Debug, Clone)]
+pub struct AssistantConfig {
+    /// Articles given to the model as sources
+    pub top_k: i64,
+    /// Articles less similar than this are not used as sources
+    pub min_similarity: f64,
+    /// Previous messages sent to the model
+    pub max_history: usize,
+    pub max_tokens: u32,
+    pub temperature: f32,
+}
+
+impl Default for AssistantConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 4,
+            min_similarity: 0.3,
+            max_history: 6,
+            max_tokens: 700,
+            temperature: 0.2,
+        }
+    }
+}
+
+impl AssistantConfig {
+    /// Loads `CHATBOT_KB_TOP_K` and `CHATBOT_KB_MIN_SIMILARITY`, keeping the
+    /// defaults for unset values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            top_k: std::env::var("CHATBOT_KB_TOP_K")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.top_k),
+            min_similarity: std::env::var("CHATBOT_KB_MIN_SIMILARITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_similarity),
+            ..defaults
+        }
+    }
+}
+
+/// Answers with a language model grounded in the knowledge base.
+pub struct RetrievalAIClient<R: KnowledgeRetriever> {
+    model: ModelClient,
+    retriever: R,
+    calibrator: ConfidenceCalibrator,
+    config: AssistantConfig,
+}
+
+impl<R: KnowledgeRetriever> RetrievalAIClient<R> {
+    pub fn new(model: ModelClient, retriever: R, config: AssistantConfig) -> Self {
+        Self {
+            model,
+            retriever,
+            calibrator: ConfidenceCalibrator::default(),
+            config,
+        }
+    }
+
+    /// Converts the last messages of the conversation to chat messages.
+    fn history_messages(&self, history: &[Message]) -> Vec<ChatMessage> {
+        let skip = history.len().saturating_sub(self.config.max_history);
+
+        history[skip..]
+            .iter()
+            .filter(|m| !m.content.text.is_empty())
+            .filter_map(|m| match m.sender {
+                MessageSender::User => Some(ChatMessage::user(m.content.text.clone())),
+                MessageSender::Bot | MessageSender::Agent => {
+                    Some(ChatMessage::assistant(m.content.text.clone()))
+                }
+                MessageSender::System => None,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<R: KnowledgeRetriever> AIClient for RetrievalAIClient<R> {
+    async fn generate_response(
+        &self,
+        message: &str,
+        context: &ConversationContext,
+        role: UserRole,
+        history: &[Message],
+    ) -> Result<AIResponse, String> {
+        let language = if context.language.is_empty() { "es" } else { context.language.as_str() };
+
+        let embedding = self.model.embed(message).await?;
+        let sources = self
+            .retriever
+            .retrieve(
+                &embedding,
+                self.model.embedding_model(),
+                role,
+                language,
+                self.config.min_similarity,
+                self.config.top_k,
+            )
+            .await?;
+
+        let mut messages = vec![ChatMessage::system(prompts::answer_system_prompt(role, language, &sources))];
+        messages.extend(self.history_messages(history));
+        messages.push(ChatMessage::user(message.to_string()));
+
+        let content = self
+            .model
+            .chat_json(messages, self.config.max_tokens, self.config.temperature)
+            .await?;
+        let answer = prompts::parse_answer(&content)?;
+
+        // Keep valid citations in order, without duplicates
+        let mut cited: Vec<&KBSearchResult> = Vec::new();
+        for number in &answer.citations {
+            if let Some(source) = number.checked_sub(1).and_then(|i| sources.get(i)) {
+                if !cited.iter().any(|c| c.article.article_id == source.article.article_id) {
+                    cited.push(source);
+                }
+            }
+        }
+
+        if answer.kind != AnswerKind::Answer {
+            return Ok(AIResponse {
+                text: answer.answer,
+                confidence: answer.confidence.clamp(0.0, 1.0),
+                intent: None,
+                suggested_articles: vec![],
+                should_escalate: answer.kind == AnswerKind::Handoff,
+                rich_content: None,
+            });
+        }
+
+        let grounding = if cited.is_empty() { sources.iter().collect() } else { cited.clone() };
+        let top_similarity = grounding
+            .iter()
+            .map(|s| s.relevance_score)
+            .fold(0.0, f64::max);
+
+        let confidence = self.calibrator.calibrate(ConfidenceSignals {
+            model_confidence: answer.confidence,
+            top_similarity,
+            cited: !cited.is_empty(),
+            answerable: answer.answerable,
+        });
+
+        let suggested_articles: Vec<Uuid> = cited.iter().map(|s| s.article.article_id).collect();
+        let rich_content = (!cited.is_empty()).then(|| RichContent::Links {
+            links: cited
+                .iter()
+                .map(|s| LinkItem {
+                    title: s.article.title.clone(),
+                    url: format!("/ayuda/{}", s.article.slug),
+                    description: Some(s.snippet.clone()),
+                })
+                .collect(),
+        });
+
+        Ok(AIResponse {
+            text: answer.answer,
+            confidence,
+            intent: None,
+            suggested_articles,
+            should_escalate: false,
+            rich_content,
+        })
+    }
+
+    async fn detect_intent(&self, message: &str, role: UserRole) -> Result<DetectedIntent, String> {
+        let messages = vec![
+            ChatMessage::system(prompts::intent_system_prompt(role)),
+            ChatMessage::user(message.to_string()),
+        ];
+
+        let content = self.model.chat_json(messages, 300, 0.0).await?;
+        let raw = prompts::parse_intent(&content)?;
+
+        // Offsets are in characters; entities not found verbatim are dropped
+        let entities = raw
+            .entities
+            .into_iter()
+            .filter_map(|entity| {
+                let byte_start = message.find(&entity.value)?;
+                let start = message[..byte_start].chars().count();
+                let end = start + entity.value.chars().count();
+
+                Some(ExtractedEntity {
+                    entity_type: entity.entity_type,
+                    value: entity.value,
+                    start,
+                    end,
+                    confidence: entity.confidence.clamp(0.0, 1.0),
+                })
+            })
+            .collect();
+
+        Ok(DetectedIntent {
+            name: raw.name,
+            category: prompts::parse_category(&raw.category),
+            confidence: raw.confidence.clamp(0.0, 1.0),
+            entities,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::domain::{ArticleStatus, IntentCategory, KBArticle};
+
+    /// Returns fixed results, filtered like the repository.
+    struct StaticRetriever(Vec<KBSearchResult>);
+
+    #[async_trait]
+    impl KnowledgeRetriever for StaticRetriever {
+        async fn retrieve(
+            &self,
+            _embedding: &[f32],
+            _model: &str,
+            _role: UserRole,
+            _language: &str,
+            min_similarity: f64,
+            limit: i64,
+        ) -> Result<Vec<KBSearchResult>, String> {
+            Ok(self.0.iter()
+                .filter(|r| r.relevance_score >= min_similarity)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn source(slug: &str, title: &str, similarity: f64) -> KBSearchResult {
+        KBSearchResult {
+            article: KBArticle {
+                article_id: Uuid::new_v4(),
+                slug: slug.to_string(),
+                title: title.to_string(),
+                content: format!("Contenido de {}", title),
+                summary: None,
+                category: "general".to_string(),
+                subcategory: None,
+                tags: vec![],
+                keywords: vec![],
+                intent_triggers: vec![],
+                target_roles: vec![UserRole::Student],
+                language: "es".to_string(),
+                status: ArticleStatus::Published,
+                view_count: 0,
+                helpful_count: 0,
+                not_helpful_count: 0,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                author_id: Uuid::new_v4(),
+            },
+            relevance_score: similarity,
+            matched_keywords: vec![],
+            snippet: format!("Resumen de {}", title),
+        }
+    }
+
+    async fn mock_embeddings(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"index": 0, "embedding": [0.1, 0.2, 0.3]}]
+            })))
+            .mount(server)
+            .await;
+    }
+
+    async fn mock_model(server: &MockServer, answer: serde_json::Value) {
+        mock_embeddings(server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{"message": {"content": answer.to_string()}}]
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer, sources: Vec<KBSearchResult>) -> RetrievalAIClient<StaticRetriever> {
+        let model = ModelClient::new(&server.uri(), "chat", "embed", Duration::from_secs(5));
+        RetrievalAIClient::new(model, StaticRetriever(sources), AssistantConfig::default())
+    }
+
+    fn context() -> ConversationContext {
+        ConversationContext::new("es".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_grounded_answer_cites_sources() {
+        let server = MockServer::start().await;
+        let sources = vec![
+            source("certificados", "Certificados", 0.82),
+            source("pagos", "Pagos", 0.41),
+        ];
+        let certificate_id = sources[0].article.article_id;
+
+        // The sources must reach the model
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_string_contains("[1] Certificados"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{"message": {"content": json!({
+                    "kind": "answer",
+                    "answer": "Se generan al completar el curso.",
+                    "citations": [1, 1, 7],
+                    "answerable": true,
+                    "confidence": 0.9
+                }).to_string()}}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        mock_embeddings(&server).await;
+
+        let response = client(&server, sources)
+            .generate_response("¿Cómo obtengo mi certificado?", &context(), UserRole::Student, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(response.suggested_articles, vec![certificate_id]);
+        assert!(response.confidence > 0.9);
+        assert!(!response.should_escalate);
+        match response.rich_content {
+            Some(RichContent::Links { links }) => {
+                assert_eq!(links.len(), 1);
+                assert_eq!(links[0].url, "/ayuda/certificados");
+            }
+            other => panic!("Expected links, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ungrounded_answer_falls_below_threshold() {
+        let server = MockServer::start().await;
+        mock_model(&server, json!({
+            "kind": "answer",
+            "answer": "Creo que puedes pedir un reembolso en 30 días.",
+            "citations": [],
+            "answerable": false,
+            "confidence": 0.95
+        })).await;
+
+        let response = client(&server, vec![source("pagos", "Pagos", 0.2)])
+            .generate_response("¿Puedo pedir un reembolso?", &context(), UserRole::Student, &[])
+            .await
+            .unwrap();
+
+        assert!(response.confidence < 0.6);
+        assert!(response.rich_content.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handoff_escalates() {
+        let server = MockServer::start().await;
+        mock_model(&server, json!({
+            "kind": "handoff",
+            "answer": "Te comunico con una persona del equipo.",
+            "confidence": 0.9
+        })).await;
+
+        let response = client(&server, vec![])
+            .generate_response("Quiero hablar con un humano", &context(), UserRole::Student, &[])
+            .await
+            .unwrap();
+
+        assert!(response.should_escalate);
+    }
+
+    #[tokio::test]
+    async fn test_detect_intent_locates_entities() {
+        let server = MockServer::start().await;
+        mock_model(&server, json!({
+            "name": "check_progress",
+            "category": "course_progress",
+            "confidence": 0.88,
+            "entities": [
+                {"type": "course", "value": "Álgebra básica", "confidence": 0.9},
+                {"type": "course", "value": "Física", "confidence": 0.4}
+            ]
+        })).await;
+
+        let intent = client(&server, vec![])
+            .detect_intent("¿Cuánto llevo de Álgebra básica?", UserRole::Student)
+            .await
+            .unwrap();
+
+        assert_eq!(intent.category, IntentCategory::CourseProgress);
+        assert_eq!(intent.entities.len(), 1);
+        assert_eq!((intent.entities[0].start, intent.entities[0].end), (17, 31));
+    }
+
+    #[test]
+    fn test_calibration_rewards_grounding() {
+        let calibrator = ConfidenceCalibrator::default();
+        let grounded = ConfidenceSignals {
+            model_confidence: 0.7,
+            top_similarity: 0.6,
+            cited: true,
+            answerable: true,
+        };
+
+        let base = calibrator.calibrate(grounded);
+        assert!(base > 0.8);
+        assert!(calibrator.calibrate(ConfidenceSignals { cited: false, ..grounded }) < base);
+        assert!(calibrator.calibrate(ConfidenceSignals { answerable: false, ..grounded }) < 0.6);
+        assert!(calibrator.calibrate(ConfidenceSignals { top_similarity: 0.0, cited: false, ..grounded }) < 0.6);
+    }
+}
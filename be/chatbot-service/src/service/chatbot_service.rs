@@ -42,6 +42,9 @@ pub type Result<T> = std::result::Result<T, ChatbotError>;
 #[async_trait::async_trait]
 pub trait AIClient: Send + Sync {
     /// Generates a response for the given message and context.
+    ///
+    /// `history` holds the previous messages of the conversation, oldest
+    /// first, without `message` itself.
     async fn generate_response(
         &self,
         message: &str,
@@ -131,13 +134,13 @@ impl ChatbotService {
             ).await?;
         }
 
+        // Get recent conversation history for context
+        let history = self.repository.get_recent_messages(request.conversation_id, 10).await?;
+
         // Create user message
         let user_message = Message::user_message(request.conversation_id, request.content.clone());
         self.repository.save_message(&user_message).await?;
 
-        // Get conversation history for context
-        let history = self.repository.get_messages(request.conversation_id, 10, 0).await?;
-
         // Detect intent
         let intent = self.ai_client.detect_intent(&request.content, conversation.user_role)
             .await
@@ -174,8 +177,8 @@ impl ChatbotService {
             ).await?;
         }
 
-        // Build response with KB articles if available
-        let response_content = if !kb_results.is_empty() {
+        // Prefer the AI's own content (e.g. cited sources), then KB articles
+        let response_content = if ai_response.rich_content.is_none() && !kb_results.is_empty() {
             self.build_response_with_articles(&ai_response.text, &kb_results)
         } else {
             MessageContent {
@@ -192,10 +195,18 @@ impl ChatbotService {
             Some(ai_response.confidence),
         );
         bot_message.content = response_content;
-
-        if let Some(intent) = intent {
+        if let Some(intent) = ai_response.intent.or(intent) {
             bot_message = bot_message.with_intent(intent);
         }
+        // with_intent copies the intent's confidence; keep the response's
+        bot_message.confidence = Some(ai_response.confidence);
+
+        if !ai_response.suggested_articles.is_empty() {
+            bot_message.metadata.insert(
+                "suggested_articles".to_string(),
+                serde_json::json!(ai_response.suggested_articles),
+            );
+        }
 
         self.repository.save_message(&bot_message).await?;
 
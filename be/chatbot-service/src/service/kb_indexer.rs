@@ -0,0 +1,101 @@
+//! # Knowledge Base Indexer
+//!
+//! Background task that embeds published articles whose content or
+//! embedding model changed since they were last indexed.
+//!
+//! Staleness is detected in SQL by comparing the md5 of title, summary and
+//! content with the stored `content_hash`, so edits made through any path
+//! (API, migrations, manual SQL) are picked up on the next poll.
+
+use std::time::Duration;
+
+use tracing::{error, info};
+
+use crate::domain::KBArticle;
+use crate::llm::ModelClient;
+use crate::repository::ChatbotRepository;
+
+/// Articles embedded per request.
+const BATCH_SIZE: i64 = 16;
+
+/// Maximum characters of an article sent to the embedding model.
+const EMBEDDING_TEXT_LIMIT: usize = 8000;
+
+/// Keeps `chatbot.kb_article_embeddings` up to date.
+pub struct KbIndexer {
+    repository: ChatbotRepository,
+    model: ModelClient,
+    interval: Duration,
+}
+
+impl KbIndexer {
+    pub fn new(repository: ChatbotRepository, model: ModelClient, interval: Duration) -> Self {
+        Self { repository, model, interval }
+    }
+
+    /// Runs forever; spawn it on the runtime.
+    pub async fn run(self) {
+        info!(model = %self.model.embedding_model(), "KB indexer started");
+
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            // Drain full batches before waiting for the next tick
+            loop {
+                match self.index_batch().await {
+                    Ok(0) => break,
+                    Ok(count) => {
+                        info!(count, "KB articles indexed");
+                        if (count as i64) < BATCH_SIZE {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error!(error = %e, "KB indexing failed");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Embeds one batch of stale articles. Returns how many were indexed.
+    pub async fn index_batch(&self) -> Result<usize, String> {
+        let model = self.model.embedding_model();
+
+        let articles = self
+            .repository
+            .list_articles_to_index(model, BATCH_SIZE)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if articles.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<String> = articles.iter().map(|(article, _)| embedding_text(article)).collect();
+        let embeddings = self.model.embed_batch(&texts).await?;
+
+        for ((article, content_hash), embedding) in articles.iter().zip(embeddings) {
+            self.repository
+                .save_article_embedding(article.article_id, model, content_hash, &embedding)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(articles.len())
+    }
+}
+
+/// Text embedded for an article.
+fn embedding_text(article: &KBArticle) -> String {
+    let text = match &article.summary {
+        Some(summary) => format!("{}\n\n{}\n\n{}", article.title, summary, article.content),
+        None => format!("{}\n\n{}", article.title, article.content),
+    };
+
+    text.chars().take(EMBEDDING_TEXT_LIMIT).collect()
+}
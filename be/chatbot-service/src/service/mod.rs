@@ -1,5 +1,9 @@
 //! # Service Module
 
+pub mod assistant;
 pub mod chatbot_service;
+pub mod kb_indexer;
 
-pub use chatbot_service::{ChatbotService, ChatbotError, AIClient, AIResponse, Result};
+pub use assistant::{AssistantConfig, RetrievalAIClient};
+pub use chatbot_service::{ChatbotService, AIClient};
+pub use kb_indexer::KbIndexer;
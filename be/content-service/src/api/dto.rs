@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::domain::{ContentMetadata, ContentType, ProcessingStatus};
+use crate::domain::{ContentMetadata, ContentType, ProcessingJob, ProcessingOperation, ProcessingStatus};
 
 // =============================================================================
 // Request DTOs
@@ -39,6 +39,41 @@ pub struct ConfirmUploadDto {
     pub metadata: Option<MetadataDto>,
 }
 
+/// Request para obtener la URL de una parte (multipart)
+#[derive(Debug, Deserialize)]
+pub struct UploadPartUrlDto {
+    pub upload_id: String,
+    pub part_number: u32,
+}
+
+/// Parte subida (número + ETag devuelto por el storage)
+#[derive(Debug, Deserialize)]
+pub struct CompletedPartDto {
+    pub part_number: u32,
+    pub etag: String,
+}
+
+/// Request para completar un upload multipart
+#[derive(Debug, Deserialize)]
+pub struct CompleteMultipartDto {
+    pub upload_id: String,
+    pub parts: Vec<CompletedPartDto>,
+    pub checksum: Option<String>,
+}
+
+/// Request para abortar un upload multipart
+#[derive(Debug, Deserialize)]
+pub struct AbortMultipartDto {
+    pub upload_id: String,
+}
+
+/// Request para (re)procesar un asset
+#[derive(Debug, Default, Deserialize)]
+pub struct ProcessAssetDto {
+    #[serde(default)]
+    pub operations: Vec<ProcessingOperation>,
+}
+
 /// Request para actualizar metadata
 #[derive(Debug, Deserialize)]
 pub struct UpdateMetadataDto {
@@ -66,6 +101,15 @@ pub struct ListAssetsQuery {
 // Response DTOs
 // =============================================================================
 
+/// Upload reanudable creado (los chunks van por PATCH a `upload_url`)
+#[derive(Debug, Serialize)]
+pub struct ResumableUploadResponse {
+    pub asset_id: Uuid,
+    pub upload_url: String,
+    pub offset: i64,
+    pub length: i64,
+}
+
 /// Respuesta de URL de upload
 #[derive(Debug, Serialize)]
 pub struct UploadUrlResponse {
@@ -74,9 +118,24 @@ pub struct UploadUrlResponse {
     pub expires_at: DateTime<Utc>,
     pub max_size_bytes: u64,
     pub allowed_content_types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_size: Option<u64>,
     pub instructions: UploadInstructions,
 }
 
+/// Respuesta de URL para una parte de upload multipart
+#[derive(Debug, Serialize)]
+pub struct UploadPartUrlResponse {
+    pub asset_id: Uuid,
+    pub upload_id: String,
+    pub part_number: u32,
+    pub upload_url: String,
+    pub method: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct UploadInstructions {
     pub method: String,
@@ -102,6 +161,44 @@ pub struct DownloadUrlResponse {
     pub size_bytes: i64,
 }
 
+/// Sesión de streaming HLS
+#[derive(Debug, Serialize)]
+pub struct StreamResponse {
+    pub asset_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_playlist_url: Option<String>,
+    pub variants: Vec<StreamVariantResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_url: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub content_type: String,
+}
+
+/// Variante disponible en el master playlist
+#[derive(Debug, Serialize)]
+pub struct StreamVariantResponse {
+    pub quality: String,
+    pub width: i32,
+    pub height: i32,
+    pub bandwidth: i64,
+    pub playlist_url: String,
+}
+
+/// Token de sesión en la query de los playlists HLS
+#[derive(Debug, Deserialize)]
+pub struct StreamTokenQuery {
+    pub user_id: Uuid,
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Firma de una URL de archivo local (`/uploads/{key}`)
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
 /// Asset completo
 #[derive(Debug, Serialize)]
 pub struct AssetResponse {
@@ -206,6 +303,38 @@ impl From<MetadataDto> for ContentMetadata {
     }
 }
 
+/// Job de procesamiento
+#[derive(Debug, Serialize)]
+pub struct ProcessingJobResponse {
+    pub job_id: Uuid,
+    pub asset_id: Uuid,
+    pub operations: Vec<String>,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub run_after: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ProcessingJob> for ProcessingJobResponse {
+    fn from(job: ProcessingJob) -> Self {
+        Self {
+            job_id: job.job_id,
+            asset_id: job.asset_id,
+            operations: job.operations.iter().map(|o| o.name().to_string()).collect(),
+            status: job.status.to_string(),
+            attempts: job.attempts,
+            max_attempts: job.max_attempts,
+            last_error: job.last_error,
+            run_after: job.run_after,
+            created_at: job.created_at,
+            completed_at: job.completed_at,
+        }
+    }
+}
+
 /// Lista paginada de assets
 #[derive(Debug, Serialize)]
 pub struct AssetListDto {
@@ -4,21 +4,35 @@
 // Handlers para endpoints de la API de contenido
 // =============================================================================
 
+use std::future::Future;
+
 use actix_multipart::Multipart;
+use actix_web::http::Method;
 use actix_web::{web, HttpRequest, HttpResponse};
-use bytes::BytesMut;
-use futures_util::StreamExt;
-use sha2::{Sha256, Digest};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::mpsc;
 use tracing::{info, warn, error};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::api::dto::*;
-use crate::domain::{CreateUploadRequest, AssetFilters, ContentType, ProcessingStatus};
+use crate::domain::{CreateUploadRequest, AssetFilters, ContentType, ProcessingStatus, StreamToken};
 use crate::service::{ContentService, ContentError};
+use crate::storage::{ByteRange, ByteStream, CompletedPart, StorageError, StorageResult};
 
 type ServiceData = web::Data<std::sync::Arc<ContentService>>;
 
+/// Headers del protocolo tus (https://tus.io/protocols/resumable-upload)
+const TUS_RESUMABLE: &str = "Tus-Resumable";
+const TUS_VERSION: &str = "1.0.0";
+const UPLOAD_OFFSET: &str = "Upload-Offset";
+const UPLOAD_LENGTH: &str = "Upload-Length";
+const OFFSET_OCTET_STREAM: &str = "application/offset+octet-stream";
+
+/// Chunks en vuelo entre el body HTTP y el storage
+const BODY_CHANNEL_CAPACITY: usize = 8;
+
 // =============================================================================
 // Upload Handlers
 // =============================================================================
@@ -65,14 +79,23 @@ pub async fn create_upload_url(
 
     match service.create_upload_url(user_id, request, tenant_id).await {
         Ok(presigned) => {
+            // Multipart: se confirma completando el upload con los ETags de las partes
+            let confirm_endpoint = if presigned.upload_id.is_some() {
+                format!("/api/v1/content/assets/{}/multipart/complete", presigned.asset_id)
+            } else {
+                format!("/api/v1/content/assets/{}/confirm", presigned.asset_id)
+            };
+
             HttpResponse::Created().json(UploadUrlResponse {
                 asset_id: presigned.asset_id,
                 upload_url: presigned.upload_url.clone(),
                 expires_at: presigned.expires_at,
                 max_size_bytes: presigned.max_size_bytes,
                 allowed_content_types: presigned.allowed_content_types,
+                upload_id: presigned.upload_id,
+                part_size: presigned.part_size,
                 instructions: UploadInstructions {
-                    method: "POST".to_string(),
+                    method: presigned.upload_method,
                     headers: vec![
                         HeaderInfo {
                             name: "Content-Type".to_string(),
@@ -85,7 +108,7 @@ pub async fn create_upload_url(
                             required: true,
                         },
                     ],
-                    confirm_endpoint: format!("/api/v1/content/assets/{}/confirm", presigned.asset_id),
+                    confirm_endpoint,
                 },
             })
         }
@@ -128,69 +151,198 @@ pub async fn direct_upload(
         }
     };
 
-    // Leer el archivo del multipart
-    let mut file_data = BytesMut::new();
-    let mut hasher = Sha256::new();
-
-    while let Some(item) = payload.next().await {
-        match item {
-            Ok(mut field) => {
-                while let Some(chunk) = field.next().await {
-                    match chunk {
-                        Ok(data) => {
-                            hasher.update(&data);
-                            file_data.extend_from_slice(&data);
-                        }
-                        Err(e) => {
-                            error!("Error reading upload chunk: {}", e);
-                            return HttpResponse::InternalServerError().json(ErrorResponse {
-                                code: "UPLOAD_ERROR".to_string(),
-                                message: "Failed to read upload data".to_string(),
-                                details: None,
-                            });
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Error processing multipart: {}", e);
-                return HttpResponse::BadRequest().json(ErrorResponse {
-                    code: "MULTIPART_ERROR".to_string(),
-                    message: format!("Failed to process upload: {}", e),
-                    details: None,
-                });
-            }
+    // Solo se admite un archivo: el primer campo del multipart
+    let mut field = match payload.next().await {
+        Some(Ok(field)) => field,
+        Some(Err(e)) => {
+            error!("Error processing multipart: {}", e);
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                code: "MULTIPART_ERROR".to_string(),
+                message: format!("Failed to process upload: {}", e),
+                details: None,
+            });
+        }
+        None => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                code: "EMPTY_UPLOAD".to_string(),
+                message: "No file data received".to_string(),
+                details: None,
+            });
         }
+    };
+
+    // El archivo se escribe en el storage mientras se recibe (sin buffer en memoria)
+    let result = pipe_body(&mut field, |data| service.direct_upload(asset_id, user_id, data)).await;
+
+    match result {
+        Ok(asset) => HttpResponse::Ok().json(AssetResponse::from(asset)),
+        Err(e) => error_response(e),
     }
+}
 
-    if file_data.is_empty() {
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            code: "EMPTY_UPLOAD".to_string(),
-            message: "No file data received".to_string(),
+// =============================================================================
+// Resumable Upload Handlers (protocolo tus 1.0 core)
+// =============================================================================
+
+/// POST /api/v1/content/assets/{asset_id}/resumable
+/// Inicia (o reanuda) un upload reanudable. Header `Upload-Length` requerido
+pub async fn create_resumable_upload(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let upload_length = match header_i64(&req, UPLOAD_LENGTH) {
+        Some(length) => length,
+        None => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                code: "MISSING_UPLOAD_LENGTH".to_string(),
+                message: "Upload-Length header required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    match service.create_resumable_upload(asset_id, user_id, upload_length).await {
+        Ok(upload) => {
+            let location = format!("/api/v1/content/assets/{}/resumable", asset_id);
+            HttpResponse::Created()
+                .insert_header((TUS_RESUMABLE, TUS_VERSION))
+                .insert_header(("Location", location.clone()))
+                .insert_header((UPLOAD_OFFSET, upload.offset.to_string()))
+                .json(ResumableUploadResponse {
+                    asset_id,
+                    upload_url: location,
+                    offset: upload.offset,
+                    length: upload.length,
+                })
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+/// HEAD /api/v1/content/assets/{asset_id}/resumable
+/// Offset actual del upload, para reanudar tras un corte
+pub async fn get_resumable_upload(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+
+    match service.get_resumable_upload(asset_id, user_id).await {
+        Ok(upload) => HttpResponse::Ok()
+            .insert_header((TUS_RESUMABLE, TUS_VERSION))
+            .insert_header((UPLOAD_OFFSET, upload.offset.to_string()))
+            .insert_header((UPLOAD_LENGTH, upload.length.to_string()))
+            .insert_header(("Cache-Control", "no-store"))
+            .finish(),
+        // HEAD no lleva body
+        Err(e) => HttpResponse::build(error_response(e).status()).finish(),
+    }
+}
+
+/// PATCH /api/v1/content/assets/{asset_id}/resumable
+/// Añade un chunk en `Upload-Offset` (body `application/offset+octet-stream`)
+pub async fn append_resumable_chunk(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    mut body: web::Payload,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let content_type = req.headers().get("Content-Type").and_then(|v| v.to_str().ok());
+    if content_type != Some(OFFSET_OCTET_STREAM) {
+        return HttpResponse::UnsupportedMediaType().json(ErrorResponse {
+            code: "INVALID_CONTENT_TYPE".to_string(),
+            message: format!("Content-Type must be {}", OFFSET_OCTET_STREAM),
             details: None,
         });
     }
 
-    let checksum = hex::encode(hasher.finalize());
+    let offset = match header_i64(&req, UPLOAD_OFFSET) {
+        Some(offset) => offset,
+        None => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                code: "MISSING_UPLOAD_OFFSET".to_string(),
+                message: "Upload-Offset header required".to_string(),
+                details: None,
+            });
+        }
+    };
 
-    info!(
-        asset_id = %asset_id,
-        size = file_data.len(),
-        checksum = %checksum,
-        "Direct upload received"
-    );
+    let result = pipe_body(&mut body, |data| {
+        service.append_resumable_chunk(asset_id, user_id, offset, data)
+    })
+    .await;
 
-    // Obtener asset para guardar en storage
-    let asset = match service.get_asset(asset_id).await {
-        Ok(a) => a,
-        Err(e) => return error_response(e),
+    match result {
+        Ok(upload) => {
+            if upload.completed {
+                info!(asset_id = %asset_id, "Resumable upload completed");
+            }
+            HttpResponse::NoContent()
+                .insert_header((TUS_RESUMABLE, TUS_VERSION))
+                .insert_header((UPLOAD_OFFSET, upload.offset.to_string()))
+                .finish()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+/// DELETE /api/v1/content/assets/{asset_id}/resumable
+/// Descarta el upload reanudable en curso
+pub async fn abort_resumable_upload(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
     };
 
-    // Guardar en storage
-    // Nota: En producción esto debería usar el storage directamente
-    // Por ahora, confirmamos el upload
-    match service.confirm_upload(asset_id, user_id, Some(checksum)).await {
-        Ok(asset) => HttpResponse::Ok().json(AssetResponse::from(asset)),
+    match service.abort_resumable_upload(asset_id, user_id).await {
+        Ok(()) => HttpResponse::NoContent()
+            .insert_header((TUS_RESUMABLE, TUS_VERSION))
+            .finish(),
         Err(e) => error_response(e),
     }
 }
@@ -222,6 +374,100 @@ pub async fn confirm_upload(
     }
 }
 
+/// POST /api/v1/content/assets/{asset_id}/multipart/parts
+/// Genera URL presignada para subir una parte (upload multipart)
+pub async fn create_upload_part_url(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<UploadPartUrlDto>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    match service.create_upload_part_url(asset_id, user_id, &body.upload_id, body.part_number).await {
+        Ok(part) => HttpResponse::Ok().json(UploadPartUrlResponse {
+            asset_id: part.asset_id,
+            upload_id: part.upload_id,
+            part_number: part.part_number,
+            upload_url: part.upload_url,
+            method: "PUT".to_string(),
+            expires_at: part.expires_at,
+        }),
+        Err(e) => error_response(e),
+    }
+}
+
+/// POST /api/v1/content/assets/{asset_id}/multipart/complete
+/// Completa un upload multipart y confirma el asset
+pub async fn complete_multipart_upload(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<CompleteMultipartDto>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let body = body.into_inner();
+    let parts = body.parts
+        .into_iter()
+        .map(|p| CompletedPart { part_number: p.part_number, etag: p.etag })
+        .collect();
+
+    match service.complete_multipart_upload(asset_id, user_id, &body.upload_id, parts, body.checksum).await {
+        Ok(asset) => HttpResponse::Ok().json(AssetResponse::from(asset)),
+        Err(e) => error_response(e),
+    }
+}
+
+/// POST /api/v1/content/assets/{asset_id}/multipart/abort
+/// Cancela un upload multipart en curso
+pub async fn abort_multipart_upload(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<AbortMultipartDto>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    match service.abort_multipart_upload(asset_id, user_id, &body.upload_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => error_response(e),
+    }
+}
+
 // =============================================================================
 // Download Handlers
 // =============================================================================
@@ -290,18 +536,171 @@ pub async fn get_stream_url(
     let user_role = extract_user_role(&req).unwrap_or("student".to_string());
 
     match service.get_stream_url(asset_id, user_id, &user_role).await {
-        Ok(download) => HttpResponse::Ok().json(DownloadUrlResponse {
-            asset_id: download.asset_id,
-            download_url: download.download_url,
-            expires_at: download.expires_at,
-            filename: download.filename,
-            content_type: download.content_type,
-            size_bytes: download.size_bytes,
+        Ok(manifest) => HttpResponse::Ok().json(StreamResponse {
+            asset_id: manifest.asset_id,
+            master_playlist_url: manifest.master_playlist_url,
+            variants: manifest
+                .variants
+                .into_iter()
+                .map(|v| StreamVariantResponse {
+                    quality: v.quality,
+                    width: v.width,
+                    height: v.height,
+                    bandwidth: v.bandwidth,
+                    playlist_url: v.playlist_url,
+                })
+                .collect(),
+            fallback_url: manifest.fallback_url,
+            expires_at: manifest.expires_at,
+            content_type: manifest.content_type,
         }),
         Err(e) => error_response(e),
     }
 }
 
+/// GET /api/v1/content/assets/{asset_id}/hls/{playlist}.m3u8
+/// Sirve el master playlist (`master.m3u8`) o el de una variante (`720p.m3u8`).
+/// Autenticado por el token firmado de la query, no por headers.
+pub async fn get_hls_playlist(
+    service: ServiceData,
+    path: web::Path<(Uuid, String)>,
+    query: web::Query<StreamTokenQuery>,
+) -> HttpResponse {
+    let (asset_id, playlist) = path.into_inner();
+    let query = query.into_inner();
+    let token = StreamToken {
+        user_id: query.user_id,
+        expires: query.expires,
+        signature: query.signature,
+    };
+
+    let Some(name) = playlist.strip_suffix(".m3u8") else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            code: "NOT_FOUND".to_string(),
+            message: format!("Playlist not found: {}", playlist),
+            details: None,
+        });
+    };
+
+    let result = if name == "master" {
+        service.get_master_playlist(asset_id, &token).await
+    } else {
+        service.get_variant_playlist(asset_id, name, &token).await
+    };
+
+    match result {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("application/vnd.apple.mpegurl")
+            // Contiene URLs firmadas por usuario: no cachear en proxies
+            .insert_header(("Cache-Control", "private, no-store"))
+            .body(body),
+        Err(e) => error_response(e),
+    }
+}
+
+// =============================================================================
+// Processing Handlers
+// =============================================================================
+
+/// GET /api/v1/content/assets/{asset_id}/transcriptions/{language}
+/// Devuelve el texto de la transcripción (VTT, SRT o texto plano)
+pub async fn get_transcription(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<(Uuid, String)>,
+) -> HttpResponse {
+    let (asset_id, language) = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let user_role = extract_user_role(&req).unwrap_or("student".to_string());
+
+    match service.get_transcription(asset_id, &language, user_id, &user_role).await {
+        Ok((transcription, text)) => {
+            let content_type = match transcription.format.as_str() {
+                "vtt" => "text/vtt; charset=utf-8",
+                "srt" => "application/x-subrip; charset=utf-8",
+                _ => "text/plain; charset=utf-8",
+            };
+            HttpResponse::Ok()
+                .insert_header(("Content-Type", content_type))
+                .insert_header(("X-Transcription-Format", transcription.format))
+                .body(text)
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+/// POST /api/v1/content/assets/{asset_id}/process
+/// Encola operaciones de procesamiento (transcode, thumbnails, etc.)
+pub async fn process_asset(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: Option<web::Json<ProcessAssetDto>>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let user_role = extract_user_role(&req).unwrap_or("student".to_string());
+    let operations = body.map(|b| b.into_inner().operations).unwrap_or_default();
+
+    match service.request_processing(asset_id, user_id, &user_role, operations).await {
+        Ok(job) => HttpResponse::Accepted().json(ProcessingJobResponse::from(job)),
+        Err(e) => error_response(e),
+    }
+}
+
+/// GET /api/v1/content/assets/{asset_id}/jobs
+/// Lista los jobs de procesamiento de un asset
+pub async fn list_processing_jobs(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    let asset_id = path.into_inner();
+
+    let user_id = match extract_user_id(&req) {
+        Some(id) => id,
+        None => {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                code: "UNAUTHORIZED".to_string(),
+                message: "Authentication required".to_string(),
+                details: None,
+            });
+        }
+    };
+
+    let user_role = extract_user_role(&req).unwrap_or("student".to_string());
+
+    match service.list_processing_jobs(asset_id, user_id, &user_role).await {
+        Ok(jobs) => {
+            let response: Vec<ProcessingJobResponse> = jobs.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => error_response(e),
+    }
+}
+
 // =============================================================================
 // Asset Management Handlers
 // =============================================================================
@@ -501,6 +900,66 @@ pub async fn get_lesson_assets(
     }
 }
 
+// =============================================================================
+// Local Files
+// =============================================================================
+
+/// GET|HEAD /uploads/{key}
+/// Sirve archivos de LocalStorage con soporte de `Range` (200/206/416).
+/// La firma de la URL la valida el middleware `require_signed_url`.
+pub async fn serve_file(
+    service: ServiceData,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let key = path.into_inner();
+
+    // Un Range inválido se ignora y se sirve el archivo completo (RFC 9110)
+    let range = req
+        .headers()
+        .get("Range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(ByteRange::parse);
+
+    let file = match service.open_file(&key, range).await {
+        Ok(file) => file,
+        Err(e) => {
+            let response = error_response(e);
+            return if req.method() == Method::HEAD {
+                HttpResponse::build(response.status()).finish()
+            } else {
+                response
+            };
+        }
+    };
+
+    let mut response = match file.range {
+        Some((start, end)) => {
+            let mut builder = HttpResponse::PartialContent();
+            builder.insert_header((
+                "Content-Range",
+                format!("bytes {}-{}/{}", start, end, file.total_size),
+            ));
+            builder
+        }
+        None => HttpResponse::Ok(),
+    };
+
+    response
+        .insert_header(("Accept-Ranges", "bytes"))
+        .insert_header(("Content-Type", file.content_type.clone()))
+        .insert_header(("Cache-Control", "private, max-age=300"))
+        // Evita que el middleware Compress altere los offsets del rango
+        .insert_header(("Content-Encoding", "identity"))
+        .no_chunking(file.content_length());
+
+    if req.method() == Method::HEAD {
+        return response.finish();
+    }
+
+    response.streaming(file.stream)
+}
+
 // =============================================================================
 // Health Check
 // =============================================================================
@@ -539,6 +998,42 @@ fn extract_user_role(req: &HttpRequest) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+fn header_i64(req: &HttpRequest, name: &str) -> Option<i64> {
+    req.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.trim().parse().ok())
+}
+
+/// Adapta un body de actix (no `Send`) a un `ByteStream` del storage:
+/// los chunks se reenvían por un canal acotado mientras `consume` escribe
+async fn pipe_body<S, E, F, Fut, T>(body: &mut S, consume: F) -> T
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: std::fmt::Display,
+    F: FnOnce(ByteStream) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let (tx, rx) = mpsc::channel::<StorageResult<Bytes>>(BODY_CHANNEL_CAPACITY);
+    let data: ByteStream = Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }));
+
+    let forward = async move {
+        while let Some(chunk) = body.next().await {
+            let item = chunk.map_err(|e| StorageError::IoError(format!("Upload interrupted: {}", e)));
+            let failed = item.is_err();
+            // Si el consumidor terminó (error), dejamos de leer
+            if tx.send(item).await.is_err() || failed {
+                break;
+            }
+        }
+    };
+
+    let ((), result) = futures_util::join!(forward, consume(data));
+    result
+}
+
 fn error_response(err: ContentError) -> HttpResponse {
     match &err {
         ContentError::NotFound(_) => HttpResponse::NotFound().json(ErrorResponse {
@@ -558,6 +1053,34 @@ fn error_response(err: ContentError) -> HttpResponse {
                 details: None,
             })
         }
+        ContentError::FileNotFound(_) => HttpResponse::NotFound().json(ErrorResponse {
+            code: "FILE_NOT_FOUND".to_string(),
+            message: err.to_string(),
+            details: None,
+        }),
+        ContentError::RangeNotSatisfiable { size } => HttpResponse::RangeNotSatisfiable()
+            .insert_header(("Content-Range", format!("bytes */{}", size)))
+            .json(ErrorResponse {
+                code: "RANGE_NOT_SATISFIABLE".to_string(),
+                message: err.to_string(),
+                details: None,
+            }),
+        ContentError::UploadOffsetMismatch { expected, .. } => HttpResponse::Conflict()
+            .insert_header((TUS_RESUMABLE, TUS_VERSION))
+            .insert_header((UPLOAD_OFFSET, expected.to_string()))
+            .json(ErrorResponse {
+                code: "UPLOAD_OFFSET_MISMATCH".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "offset": expected })),
+            }),
+        ContentError::ServiceUnavailable(_) => {
+            error!("Dependency unavailable: {}", err);
+            HttpResponse::ServiceUnavailable().json(ErrorResponse {
+                code: "SERVICE_UNAVAILABLE".to_string(),
+                message: "Access could not be verified, try again later".to_string(),
+                details: None,
+            })
+        }
         ContentError::FileTooLarge { size, max } => HttpResponse::PayloadTooLarge().json(ErrorResponse {
             code: "FILE_TOO_LARGE".to_string(),
             message: err.to_string(),
@@ -5,6 +5,7 @@
 pub mod dto;
 pub mod handlers;
 pub mod routes;
+pub mod signed_files;
 
 pub use dto::*;
 pub use routes::configure;
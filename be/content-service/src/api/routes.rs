@@ -27,6 +27,23 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                 .route("/assets/{asset_id}/metadata", web::patch().to(handlers::update_metadata))
                 .route("/assets/{asset_id}", web::delete().to(handlers::delete_asset))
                 .route("/assets/{asset_id}/stream", web::get().to(handlers::get_stream_url))
+                .route("/assets/{asset_id}/hls/{playlist}", web::get().to(handlers::get_hls_playlist))
+                .route("/assets/{asset_id}/transcriptions/{language}", web::get().to(handlers::get_transcription))
+
+                // Processing
+                .route("/assets/{asset_id}/process", web::post().to(handlers::process_asset))
+                .route("/assets/{asset_id}/jobs", web::get().to(handlers::list_processing_jobs))
+
+                // Multipart upload (backends S3-compatibles)
+                .route("/assets/{asset_id}/multipart/parts", web::post().to(handlers::create_upload_part_url))
+                .route("/assets/{asset_id}/multipart/complete", web::post().to(handlers::complete_multipart_upload))
+                .route("/assets/{asset_id}/multipart/abort", web::post().to(handlers::abort_multipart_upload))
+
+                // Resumable upload (tus, LocalStorage)
+                .route("/assets/{asset_id}/resumable", web::post().to(handlers::create_resumable_upload))
+                .route("/assets/{asset_id}/resumable", web::head().to(handlers::get_resumable_upload))
+                .route("/assets/{asset_id}/resumable", web::patch().to(handlers::append_resumable_chunk))
+                .route("/assets/{asset_id}/resumable", web::delete().to(handlers::abort_resumable_upload))
 
                 // Download with key (human-readable)
                 .route("/download/{key:.*}", web::get().to(handlers::create_download_url))
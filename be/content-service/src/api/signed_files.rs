@@ -0,0 +1,47 @@
+// =============================================================================
+// ACC LMS - Signed Local Files
+// =============================================================================
+// Middleware para `/uploads` (LocalStorage): solo sirve archivos cuya URL
+// lleve una firma HMAC válida y vigente (ver LocalStorage::sign_download_url)
+// =============================================================================
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, HttpResponse};
+
+use crate::api::dto::{ErrorResponse, SignedUrlQuery};
+use crate::storage::UrlSigner;
+
+/// Ruta bajo la que se montan los archivos locales
+pub const UPLOADS_PATH: &str = "/uploads";
+
+pub async fn require_signed_url(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let key = req
+        .path()
+        .strip_prefix(UPLOADS_PATH)
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    let query = web::Query::<SignedUrlQuery>::from_query(req.query_string()).ok();
+
+    let valid = match (req.app_data::<web::Data<UrlSigner>>(), query) {
+        (Some(signer), Some(query)) => {
+            !key.is_empty() && !key.contains("..") && signer.verify(key, query.expires, &query.signature)
+        }
+        _ => false,
+    };
+
+    if !valid {
+        let response = HttpResponse::Forbidden().json(ErrorResponse {
+            code: "INVALID_SIGNATURE".to_string(),
+            message: "Missing, invalid or expired URL signature".to_string(),
+            details: None,
+        });
+        return Ok(req.into_response(response));
+    }
+
+    Ok(next.call(req).await?.map_into_boxed_body())
+}
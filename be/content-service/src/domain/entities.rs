@@ -138,6 +138,37 @@ impl VideoQuality {
             VideoQuality::Original => "",
         }
     }
+
+    /// Bitrate objetivo de video para transcodificación (kbps)
+    pub fn bitrate_kbps(&self) -> i32 {
+        match self {
+            VideoQuality::Low => 800,
+            VideoQuality::Medium => 2500,
+            VideoQuality::High => 5000,
+            VideoQuality::Original => 0,
+        }
+    }
+
+    /// Etiqueta persistida en `video_variants.quality` (360p, 720p, 1080p, original)
+    pub fn label(&self) -> &'static str {
+        match self {
+            VideoQuality::Low => "360p",
+            VideoQuality::Medium => "720p",
+            VideoQuality::High => "1080p",
+            VideoQuality::Original => "original",
+        }
+    }
+
+    /// Acepta tanto el nombre (`low`) como la resolución (`360p`)
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.to_lowercase().as_str() {
+            "low" | "360p" => Some(VideoQuality::Low),
+            "medium" | "720p" => Some(VideoQuality::Medium),
+            "high" | "1080p" => Some(VideoQuality::High),
+            "original" => Some(VideoQuality::Original),
+            _ => None,
+        }
+    }
 }
 
 // =============================================================================
@@ -163,6 +194,7 @@ pub struct ContentAsset {
     pub bucket: String,
     pub key: String,
     pub storage_class: String,
+    pub storage_backend: String, // local, s3, minio
 
     // Estado
     pub status: ProcessingStatus,
@@ -186,6 +218,23 @@ pub struct ContentAsset {
     pub processed_at: Option<DateTime<Utc>>,
 }
 
+/// Datos para registrar un asset antes del upload
+#[derive(Debug, Clone)]
+pub struct NewContentAsset {
+    pub tenant_id: Option<Uuid>,
+    pub owner_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub content_type: ContentType,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub storage_backend: String,
+    pub bucket: String,
+    pub key: String,
+    pub course_id: Option<Uuid>,
+    pub lesson_id: Option<Uuid>,
+}
+
 /// Metadata específica según tipo de contenido
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContentMetadata {
@@ -235,6 +284,7 @@ pub struct ContentMetadata {
 pub struct PresignedUpload {
     pub asset_id: Uuid,
     pub upload_url: String,
+    pub upload_method: String,
     pub expires_at: DateTime<Utc>,
     pub max_size_bytes: u64,
     pub allowed_content_types: Vec<String>,
@@ -244,6 +294,28 @@ pub struct PresignedUpload {
     pub part_size: Option<u64>,
 }
 
+/// Estado de un upload reanudable (offsets estilo tus)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumableUpload {
+    pub asset_id: Uuid,
+    /// Bytes recibidos y persistidos
+    pub offset: i64,
+    /// Tamaño total declarado (`Upload-Length`)
+    pub length: i64,
+    /// Si el último chunk completó el archivo
+    pub completed: bool,
+}
+
+/// Presigned URL para una parte de un upload multipart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedUploadPart {
+    pub asset_id: Uuid,
+    pub upload_id: String,
+    pub part_number: u32,
+    pub upload_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Presigned URL para descarga
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PresignedDownload {
@@ -255,6 +327,39 @@ pub struct PresignedDownload {
     pub size_bytes: i64,
 }
 
+/// Sesión de streaming adaptativo (HLS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamManifest {
+    pub asset_id: Uuid,
+    /// Master playlist firmado; None si el asset aún no tiene variantes HLS
+    pub master_playlist_url: Option<String>,
+    pub variants: Vec<StreamVariant>,
+    /// Descarga progresiva del original (fallback sin HLS)
+    pub fallback_url: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub content_type: String,
+}
+
+/// Variante listada en el master playlist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamVariant {
+    pub quality: String,
+    pub width: i32,
+    pub height: i32,
+    /// Ancho de banda declarado en `#EXT-X-STREAM-INF` (bits/s)
+    pub bandwidth: i64,
+    pub playlist_url: String,
+}
+
+/// Credencial de una sesión de streaming, firmada con HMAC.
+/// Viaja en la query de los playlists HLS (los reproductores no envían headers).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamToken {
+    pub user_id: Uuid,
+    pub expires: i64,
+    pub signature: String,
+}
+
 /// Variante de video (diferentes calidades)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoVariant {
@@ -270,6 +375,22 @@ pub struct VideoVariant {
     pub created_at: DateTime<Utc>,
 }
 
+impl VideoVariant {
+    pub fn hls_playlist_key(&self) -> String {
+        hls_playlist_key(&self.key)
+    }
+}
+
+/// Key del media playlist HLS de una variante:
+/// `a/b/id_720p.mp4` -> `a/b/id_720p/index.m3u8` (segmentos en el mismo prefijo)
+pub fn hls_playlist_key(variant_key: &str) -> String {
+    let base = match variant_key.rfind('.') {
+        Some(dot) if !variant_key[dot..].contains('/') => &variant_key[..dot],
+        _ => variant_key,
+    };
+    format!("{}/index.m3u8", base)
+}
+
 /// Thumbnail generado
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Thumbnail {
@@ -336,7 +457,7 @@ pub struct ProcessContentRequest {
     pub operations: Vec<ProcessingOperation>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ProcessingOperation {
     Transcode { qualities: Vec<String> },
@@ -345,6 +466,85 @@ pub enum ProcessingOperation {
     ExtractMetadata,
 }
 
+impl ProcessingOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessingOperation::Transcode { .. } => "transcode",
+            ProcessingOperation::GenerateThumbnail { .. } => "generate_thumbnail",
+            ProcessingOperation::Transcribe { .. } => "transcribe",
+            ProcessingOperation::ExtractMetadata => "extract_metadata",
+        }
+    }
+
+    /// Operaciones que se encolan automáticamente al confirmar un upload
+    pub fn defaults_for(content_type: ContentType) -> Vec<Self> {
+        match content_type {
+            ContentType::Video => vec![
+                ProcessingOperation::ExtractMetadata,
+                ProcessingOperation::Transcode {
+                    qualities: vec!["360p".to_string(), "720p".to_string(), "1080p".to_string()],
+                },
+                ProcessingOperation::GenerateThumbnail { timestamps: None },
+            ],
+            ContentType::Audio | ContentType::Image => vec![ProcessingOperation::ExtractMetadata],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Estado de un job de procesamiento en la cola
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobStatus::Queued => write!(f, "queued"),
+            JobStatus::Running => write!(f, "running"),
+            JobStatus::Completed => write!(f, "completed"),
+            JobStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(JobStatus::Queued),
+            "running" => Ok(JobStatus::Running),
+            "completed" => Ok(JobStatus::Completed),
+            "failed" => Ok(JobStatus::Failed),
+            other => Err(format!("Unknown job status: {}", other)),
+        }
+    }
+}
+
+/// Job durable de procesamiento (una fila en `content.processing_jobs`)
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessingJob {
+    pub job_id: Uuid,
+    pub asset_id: Uuid,
+    pub operations: Vec<ProcessingOperation>,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub run_after: DateTime<Utc>,
+    pub locked_by: Option<String>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 /// Filtros para listar assets
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct AssetFilters {
@@ -2,10 +2,14 @@
 // ACC LMS - Content Service Entry Point
 // =============================================================================
 // Servicio de gestión de contenido multimedia con almacenamiento híbrido
-// Soporta: LocalStorage (desarrollo/low-budget) + trait para S3/MinIO futuro
+// Soporta: LocalStorage (desarrollo/low-budget) + S3Storage (S3/MinIO)
 // =============================================================================
 
 use actix_web::{web, App, HttpServer, middleware};
+use shared::auth::ServiceTokens;
+use shared::config::RedisConfig;
+use shared::redis_client::RedisClient;
+use shared::service_client::{ServiceClient, ServiceClientConfig};
 use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
 use tracing::{info, error};
@@ -13,13 +17,15 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod api;
 mod domain;
+mod processing;
 mod repository;
 mod service;
 mod storage;
 
-use repository::ContentRepository;
+use processing::{FfmpegConfig, FfmpegExecutor, MediaProcessor, ProcessingWorker, WorkerConfig};
+use repository::{ContentRepository, ProcessingRepository};
 use service::ContentService;
-use storage::{LocalStorage, LocalStorageConfig, StorageBackend};
+use storage::{LocalStorage, LocalStorageConfig, S3Storage, S3StorageConfig, StorageBackend, UrlSigner};
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
@@ -35,13 +41,40 @@ pub struct AppConfig {
     pub local_storage_path: String,
     pub local_storage_base_url: String,
     pub max_file_size: u64,
+    pub url_signing_secret: String,
+
+    // S3 / MinIO
+    pub s3_endpoint: String,
+    pub s3_public_endpoint: Option<String>,
+    pub s3_region: String,
+    pub s3_bucket: String,
+    pub s3_access_key_id: String,
+    pub s3_secret_access_key: String,
+    pub s3_force_path_style: bool,
+    pub s3_multipart_part_size: u64,
+
+    // Processing
+    pub processing_enabled: bool,
+    pub processing_workers: usize,
+    pub processing_poll_interval_secs: u64,
+    pub processing_work_dir: String,
+    pub ffmpeg_path: String,
+    pub ffprobe_path: String,
 
     // JWT
     pub jwt_secret: String,
+
+    // Llamadas internas a enrollments/courses-service
+    pub internal_service_secret: String,
+    /// Caché de las consultas de acceso (opcional)
+    pub redis_url: Option<String>,
 }
 
 impl AppConfig {
     pub fn from_env() -> Self {
+        let jwt_secret = std::env::var("JWT_SECRET")
+            .unwrap_or_else(|_| "development-secret-change-in-production".to_string());
+
         Self {
             host: std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
             port: std::env::var("PORT")
@@ -60,8 +93,44 @@ impl AppConfig {
                 .unwrap_or_else(|_| "2147483648".to_string()) // 2GB default
                 .parse()
                 .expect("MAX_FILE_SIZE must be a number"),
-            jwt_secret: std::env::var("JWT_SECRET")
-                .unwrap_or_else(|_| "development-secret-change-in-production".to_string()),
+            url_signing_secret: std::env::var("URL_SIGNING_SECRET")
+                .unwrap_or_else(|_| jwt_secret.clone()),
+            s3_endpoint: std::env::var("S3_ENDPOINT")
+                .unwrap_or_else(|_| "http://localhost:9000".to_string()),
+            s3_public_endpoint: std::env::var("S3_PUBLIC_ENDPOINT").ok(),
+            s3_region: std::env::var("S3_REGION")
+                .unwrap_or_else(|_| "us-east-1".to_string()),
+            s3_bucket: std::env::var("S3_BUCKET")
+                .unwrap_or_else(|_| "acc-lms-content".to_string()),
+            s3_access_key_id: std::env::var("S3_ACCESS_KEY_ID").unwrap_or_default(),
+            s3_secret_access_key: std::env::var("S3_SECRET_ACCESS_KEY").unwrap_or_default(),
+            s3_force_path_style: std::env::var("S3_FORCE_PATH_STYLE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(true),
+            s3_multipart_part_size: std::env::var("S3_MULTIPART_PART_SIZE")
+                .unwrap_or_else(|_| "67108864".to_string()) // 64MB default
+                .parse()
+                .expect("S3_MULTIPART_PART_SIZE must be a number"),
+            processing_enabled: std::env::var("PROCESSING_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(true),
+            processing_workers: std::env::var("PROCESSING_WORKERS")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .expect("PROCESSING_WORKERS must be a number"),
+            processing_poll_interval_secs: std::env::var("PROCESSING_POLL_INTERVAL_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("PROCESSING_POLL_INTERVAL_SECS must be a number"),
+            processing_work_dir: std::env::var("PROCESSING_WORK_DIR")
+                .unwrap_or_else(|_| std::env::temp_dir().join("acc-content-processing").to_string_lossy().to_string()),
+            ffmpeg_path: std::env::var("FFMPEG_PATH")
+                .unwrap_or_else(|_| "ffmpeg".to_string()),
+            ffprobe_path: std::env::var("FFPROBE_PATH")
+                .unwrap_or_else(|_| "ffprobe".to_string()),
+            jwt_secret,
+            internal_service_secret: std::env::var("INTERNAL_SERVICE_SECRET").unwrap_or_default(),
+            redis_url: std::env::var("REDIS_URL").ok(),
         }
     }
 }
@@ -74,12 +143,29 @@ fn create_storage(config: &AppConfig) -> Result<Arc<dyn StorageBackend>, Box<dyn
                 base_url: config.local_storage_base_url.clone(),
                 upload_endpoint: format!("http://{}:{}/api/v1/content/upload", config.host, config.port),
                 max_file_size: config.max_file_size,
+                signing_secret: config.url_signing_secret.clone(),
             };
 
             let storage = LocalStorage::new(storage_config)?;
             Ok(Arc::new(storage))
         }
-        // Futuro: "s3" | "minio" => { ... }
+        "s3" | "minio" => {
+            let storage_config = S3StorageConfig {
+                endpoint: config.s3_endpoint.clone(),
+                public_endpoint: config.s3_public_endpoint.clone(),
+                region: config.s3_region.clone(),
+                bucket: config.s3_bucket.clone(),
+                access_key_id: config.s3_access_key_id.clone(),
+                secret_access_key: config.s3_secret_access_key.clone(),
+                force_path_style: config.s3_force_path_style,
+                max_file_size: config.max_file_size,
+                multipart_part_size: config.s3_multipart_part_size,
+                backend_type: config.storage_type.clone(),
+            };
+
+            let storage = S3Storage::new(storage_config)?;
+            Ok(Arc::new(storage))
+        }
         other => {
             error!("Unknown storage type: {}", other);
             Err(format!("Unknown storage type: {}", other).into())
@@ -121,14 +207,66 @@ async fn main() -> std::io::Result<()> {
 
     info!("Storage initialized: {}", config.storage_type);
 
+    // Firma de URLs locales y de sesiones de streaming HLS
+    let url_signer = UrlSigner::new(&config.url_signing_secret);
+
+    // Verificación de matrícula en enrollments/courses-service
+    let service_tokens = Arc::new(
+        ServiceTokens::new("content-service", &config.internal_service_secret)
+            .expect("INTERNAL_SERVICE_SECRET must be set (at least 32 characters)"),
+    );
+    let access_cache = match &config.redis_url {
+        Some(url) => Some(
+            RedisClient::new(&RedisConfig { url: url.clone(), pool_size: 5 })
+                .await
+                .expect("Failed to connect to Redis"),
+        ),
+        None => None,
+    };
+    let service_client = ServiceClient::new(ServiceClientConfig::from_env(), service_tokens, access_cache);
+
     // Create repository and service
     let repository = Arc::new(ContentRepository::new(pool.clone()));
-    let content_service = Arc::new(ContentService::new(repository.clone(), storage.clone()));
+    let processing_repository = Arc::new(ProcessingRepository::new(pool.clone()));
+    let content_service = Arc::new(ContentService::new(
+        repository.clone(),
+        processing_repository.clone(),
+        storage.clone(),
+        url_signer.clone(),
+        service_client,
+    ));
 
-    let bind_addr = format!("{}:{}", config.host, config.port);
+    // Start background processing workers
+    if config.processing_enabled {
+        let executor = Arc::new(FfmpegExecutor::new(FfmpegConfig {
+            ffmpeg_path: config.ffmpeg_path.clone().into(),
+            ffprobe_path: config.ffprobe_path.clone().into(),
+            ..Default::default()
+        }));
 
-    // Configure static file serving for uploads (LocalStorage)
-    let uploads_path = config.local_storage_path.clone();
+        for index in 0..config.processing_workers {
+            let processor = MediaProcessor::new(
+                storage.clone(),
+                executor.clone(),
+                std::path::PathBuf::from(&config.processing_work_dir),
+            );
+            let worker = Arc::new(ProcessingWorker::new(
+                repository.clone(),
+                processing_repository.clone(),
+                processor,
+                WorkerConfig {
+                    worker_id: format!("content-worker-{}-{}", std::process::id(), index),
+                    poll_interval: std::time::Duration::from_secs(config.processing_poll_interval_secs),
+                    ..Default::default()
+                },
+            ));
+            tokio::spawn(worker.run());
+        }
+
+        info!("Started {} processing worker(s)", config.processing_workers);
+    }
+
+    let bind_addr = format!("{}:{}", config.host, config.port);
 
     // Start HTTP server
     HttpServer::new(move || {
@@ -143,20 +281,24 @@ async fn main() -> std::io::Result<()> {
                     .allow_any_origin()
                     .allow_any_method()
                     .allow_any_header()
+                    // Content-Range, Upload-Offset, Location (tus)...
+                    .expose_any_header()
                     .max_age(3600),
             )
 
             // Inject service
             .app_data(web::Data::new(content_service.clone()))
+            .app_data(web::Data::new(url_signer.clone()))
 
             // Payload config (for large uploads)
             .app_data(web::PayloadConfig::new(2 * 1024 * 1024 * 1024)) // 2GB
 
-            // Static files for uploads
+            // Files for uploads (LocalStorage, solo con URL firmada, soporta Range)
             .service(
-                actix_files::Files::new("/uploads", &uploads_path)
-                    .show_files_listing()
-                    .use_etag(true)
+                web::scope(api::signed_files::UPLOADS_PATH)
+                    .wrap(middleware::from_fn(api::signed_files::require_signed_url))
+                    .route("/{key:.*}", web::get().to(api::handlers::serve_file))
+                    .route("/{key:.*}", web::head().to(api::handlers::serve_file))
             )
 
             // API routes
@@ -0,0 +1,106 @@
+// =============================================================================
+// ACC LMS - Media Executor Trait
+// =============================================================================
+// Abstracción sobre la herramienta que ejecuta las operaciones multimedia
+// (ffmpeg/ffprobe en producción, implementaciones fake en tests)
+// =============================================================================
+
+use async_trait::async_trait;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::domain::VideoQuality;
+use crate::repository::RepositoryError;
+use crate::storage::StorageError;
+
+#[derive(Debug, Error)]
+pub enum ProcessingError {
+    #[error("Executor error: {0}")]
+    Executor(String),
+
+    #[error("Operation not supported: {0}")]
+    NotSupported(String),
+
+    #[error("Invalid operation: {0}")]
+    InvalidOperation(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type ProcessingResult<T> = Result<T, ProcessingError>;
+
+/// Información técnica extraída de un archivo multimedia
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaProbe {
+    pub duration_seconds: Option<f64>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub bitrate: Option<i32>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub frame_rate: Option<f32>,
+}
+
+/// Resultado de una transcodificación
+#[derive(Debug, Clone)]
+pub struct TranscodeOutput {
+    pub codec: String,
+    pub width: i32,
+    pub height: i32,
+    pub bitrate_kbps: i32,
+}
+
+/// Ejecuta operaciones multimedia sobre archivos locales
+#[async_trait]
+pub trait MediaExecutor: Send + Sync {
+    /// Nombre del executor (para logging)
+    fn name(&self) -> &str;
+
+    /// Extrae metadata técnica (duración, resolución, codecs)
+    async fn probe(&self, input: &Path) -> ProcessingResult<MediaProbe>;
+
+    /// Transcodifica a la calidad indicada (salida MP4/H.264)
+    async fn transcode(
+        &self,
+        input: &Path,
+        output: &Path,
+        quality: VideoQuality,
+    ) -> ProcessingResult<TranscodeOutput>;
+
+    /// Extrae un frame como imagen JPEG del ancho indicado
+    async fn thumbnail(
+        &self,
+        input: &Path,
+        output: &Path,
+        timestamp_seconds: i32,
+        width: i32,
+    ) -> ProcessingResult<()>;
+
+    /// Segmenta una variante MP4 para HLS: escribe `index.m3u8` y los
+    /// segmentos `.ts` en `output_dir` (sin re-codificar)
+    async fn segment_hls(
+        &self,
+        _input: &Path,
+        _output_dir: &Path,
+        _segment_seconds: u32,
+    ) -> ProcessingResult<()> {
+        Err(ProcessingError::NotSupported(format!("HLS segmenting on {}", self.name())))
+    }
+
+    /// Genera subtítulos WebVTT a partir del audio
+    async fn transcribe(
+        &self,
+        _input: &Path,
+        _output: &Path,
+        _language: &str,
+    ) -> ProcessingResult<()> {
+        Err(ProcessingError::NotSupported(format!("transcription on {}", self.name())))
+    }
+}
@@ -0,0 +1,260 @@
+// =============================================================================
+// ACC LMS - FFmpeg Media Executor
+// =============================================================================
+// Implementación de MediaExecutor invocando los binarios ffmpeg/ffprobe
+// =============================================================================
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::debug;
+
+use super::executor::{MediaExecutor, MediaProbe, ProcessingError, ProcessingResult, TranscodeOutput};
+use crate::domain::VideoQuality;
+
+/// Intervalo de keyframes forzados (divide la duración de segmento HLS)
+const HLS_KEYFRAME_INTERVAL_SECS: u32 = 2;
+
+/// Configuración del executor ffmpeg
+#[derive(Debug, Clone)]
+pub struct FfmpegConfig {
+    pub ffmpeg_path: PathBuf,
+    pub ffprobe_path: PathBuf,
+    /// Tiempo máximo por invocación
+    pub timeout: Duration,
+}
+
+impl Default for FfmpegConfig {
+    fn default() -> Self {
+        Self {
+            ffmpeg_path: PathBuf::from("ffmpeg"),
+            ffprobe_path: PathBuf::from("ffprobe"),
+            timeout: Duration::from_secs(2 * 60 * 60), // 2 horas
+        }
+    }
+}
+
+pub struct FfmpegExecutor {
+    config: FfmpegConfig,
+}
+
+impl FfmpegExecutor {
+    pub fn new(config: FfmpegConfig) -> Self {
+        Self { config }
+    }
+
+    /// Ejecuta un comando con timeout y devuelve stdout
+    async fn run(&self, program: &Path, args: &[String]) -> ProcessingResult<Vec<u8>> {
+        debug!("Running {:?} {}", program, args.join(" "));
+
+        let child = Command::new(program)
+            .args(args)
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(self.config.timeout, child)
+            .await
+            .map_err(|_| ProcessingError::Executor(format!(
+                "{:?} timed out after {}s",
+                program,
+                self.config.timeout.as_secs()
+            )))??;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Las últimas líneas de stderr contienen la causa real
+            let tail: Vec<&str> = stderr.lines().rev().take(5).collect();
+            return Err(ProcessingError::Executor(format!(
+                "{:?} exited with {}: {}",
+                program,
+                output.status,
+                tail.into_iter().rev().collect::<Vec<_>>().join(" | ")
+            )));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl MediaExecutor for FfmpegExecutor {
+    fn name(&self) -> &str {
+        "ffmpeg"
+    }
+
+    async fn probe(&self, input: &Path) -> ProcessingResult<MediaProbe> {
+        let args = vec![
+            "-v".to_string(),
+            "error".to_string(),
+            "-print_format".to_string(),
+            "json".to_string(),
+            "-show_format".to_string(),
+            "-show_streams".to_string(),
+            input.to_string_lossy().to_string(),
+        ];
+
+        let stdout = self.run(&self.config.ffprobe_path, &args).await?;
+        parse_ffprobe_output(&stdout)
+    }
+
+    async fn transcode(
+        &self,
+        input: &Path,
+        output: &Path,
+        quality: VideoQuality,
+    ) -> ProcessingResult<TranscodeOutput> {
+        let (width, height) = quality.resolution();
+        if height == 0 {
+            return Err(ProcessingError::InvalidOperation(
+                "Original quality is not transcoded".to_string(),
+            ));
+        }
+        let bitrate = quality.bitrate_kbps();
+
+        let args: Vec<String> = [
+            "-y", "-i", &input.to_string_lossy(),
+            "-vf", &format!("scale=-2:{}", height),
+            "-c:v", "libx264", "-preset", "veryfast", "-profile:v", "main",
+            "-b:v", &format!("{}k", bitrate),
+            "-maxrate", &format!("{}k", bitrate * 107 / 100),
+            "-bufsize", &format!("{}k", bitrate * 2),
+            // Keyframes alineados entre calidades para poder cortar segmentos HLS
+            "-force_key_frames", &format!("expr:gte(t,n_forced*{})", HLS_KEYFRAME_INTERVAL_SECS),
+            "-c:a", "aac", "-b:a", "128k",
+            "-movflags", "+faststart",
+            &output.to_string_lossy(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        self.run(&self.config.ffmpeg_path, &args).await?;
+
+        Ok(TranscodeOutput {
+            codec: "h264".to_string(),
+            width: width as i32,
+            height: height as i32,
+            bitrate_kbps: bitrate,
+        })
+    }
+
+    async fn segment_hls(
+        &self,
+        input: &Path,
+        output_dir: &Path,
+        segment_seconds: u32,
+    ) -> ProcessingResult<()> {
+        let args: Vec<String> = [
+            "-y", "-i", &input.to_string_lossy(),
+            "-c", "copy",
+            "-f", "hls",
+            "-hls_time", &segment_seconds.to_string(),
+            "-hls_playlist_type", "vod",
+            "-hls_segment_filename", &output_dir.join("segment_%05d.ts").to_string_lossy(),
+            &output_dir.join("index.m3u8").to_string_lossy(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        self.run(&self.config.ffmpeg_path, &args).await?;
+        Ok(())
+    }
+
+    async fn thumbnail(
+        &self,
+        input: &Path,
+        output: &Path,
+        timestamp_seconds: i32,
+        width: i32,
+    ) -> ProcessingResult<()> {
+        let args: Vec<String> = [
+            "-y",
+            "-ss", &timestamp_seconds.to_string(),
+            "-i", &input.to_string_lossy(),
+            "-frames:v", "1",
+            "-vf", &format!("scale={}:-2", width),
+            "-q:v", "3",
+            &output.to_string_lossy(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        self.run(&self.config.ffmpeg_path, &args).await?;
+        Ok(())
+    }
+}
+
+/// Interpreta el JSON de `ffprobe -show_format -show_streams`
+pub fn parse_ffprobe_output(stdout: &[u8]) -> ProcessingResult<MediaProbe> {
+    let value: serde_json::Value = serde_json::from_slice(stdout)
+        .map_err(|e| ProcessingError::Executor(format!("Invalid ffprobe output: {}", e)))?;
+
+    let streams = value["streams"].as_array().cloned().unwrap_or_default();
+    let video = streams.iter().find(|s| s["codec_type"] == "video");
+    let audio = streams.iter().find(|s| s["codec_type"] == "audio");
+
+    let as_f64 = |v: &serde_json::Value| -> Option<f64> {
+        v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+    };
+
+    let frame_rate = video
+        .and_then(|v| v["avg_frame_rate"].as_str())
+        .and_then(|rate| {
+            let (num, den) = rate.split_once('/')?;
+            let (num, den): (f32, f32) = (num.parse().ok()?, den.parse().ok()?);
+            (den > 0.0).then(|| num / den)
+        });
+
+    Ok(MediaProbe {
+        duration_seconds: as_f64(&value["format"]["duration"]),
+        width: video.and_then(|v| v["width"].as_i64()).map(|w| w as i32),
+        height: video.and_then(|v| v["height"].as_i64()).map(|h| h as i32),
+        bitrate: as_f64(&value["format"]["bit_rate"]).map(|b| b as i32),
+        video_codec: video.and_then(|v| v["codec_name"].as_str()).map(String::from),
+        audio_codec: audio.and_then(|a| a["codec_name"].as_str()).map(String::from),
+        frame_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffprobe_output() {
+        let output = br#"{
+            "streams": [
+                {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080, "avg_frame_rate": "30000/1001"},
+                {"codec_type": "audio", "codec_name": "aac"}
+            ],
+            "format": {"duration": "125.480000", "bit_rate": "4500000"}
+        }"#;
+
+        let probe = parse_ffprobe_output(output).unwrap();
+        assert_eq!(probe.width, Some(1920));
+        assert_eq!(probe.height, Some(1080));
+        assert_eq!(probe.video_codec.as_deref(), Some("h264"));
+        assert_eq!(probe.audio_codec.as_deref(), Some("aac"));
+        assert_eq!(probe.bitrate, Some(4_500_000));
+        assert!((probe.duration_seconds.unwrap() - 125.48).abs() < 0.001);
+        assert!((probe.frame_rate.unwrap() - 29.97).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_parse_ffprobe_audio_only() {
+        let output = br#"{"streams": [{"codec_type": "audio", "codec_name": "mp3"}], "format": {"duration": "60.0"}}"#;
+
+        let probe = parse_ffprobe_output(output).unwrap();
+        assert_eq!(probe.width, None);
+        assert_eq!(probe.audio_codec.as_deref(), Some("mp3"));
+        assert_eq!(probe.duration_seconds, Some(60.0));
+    }
+
+    #[test]
+    fn test_parse_ffprobe_invalid() {
+        assert!(parse_ffprobe_output(b"not json").is_err());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod executor;
+pub mod ffmpeg;
+pub mod processor;
+pub mod worker;
+
+pub use ffmpeg::*;
+pub use processor::*;
+pub use worker::*;
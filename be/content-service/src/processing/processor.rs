@@ -0,0 +1,551 @@
+// =============================================================================
+// ACC LMS - Media Processor
+// =============================================================================
+// Ejecuta las ProcessingOperation de un asset: descarga el original desde el
+// StorageBackend, invoca el MediaExecutor y sube variantes/thumbnails/subtítulos
+// de vuelta al storage. No toca la base de datos (eso lo hace el worker).
+// =============================================================================
+
+use futures_util::StreamExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+use tracing::{info, warn};
+
+use super::executor::{MediaExecutor, MediaProbe, ProcessingError, ProcessingResult};
+use crate::domain::{hls_playlist_key, ContentAsset, ContentMetadata, ProcessingOperation, VideoQuality};
+use crate::repository::{NewThumbnail, NewTranscription, NewVideoVariant};
+use crate::storage::{ByteStream, StorageBackend};
+
+/// Ancho de los thumbnails generados
+const THUMBNAIL_WIDTH: i32 = 640;
+
+/// Duración objetivo de los segmentos HLS
+const HLS_SEGMENT_SECONDS: u32 = 6;
+
+/// Idioma por defecto para transcripción
+const DEFAULT_TRANSCRIPTION_LANGUAGE: &str = "es";
+
+/// Resultado de procesar un asset
+#[derive(Debug, Default)]
+pub struct ProcessingOutput {
+    pub metadata: ContentMetadata,
+    pub variants: Vec<NewVideoVariant>,
+    pub thumbnails: Vec<NewThumbnail>,
+    pub transcriptions: Vec<NewTranscription>,
+}
+
+pub struct MediaProcessor {
+    storage: Arc<dyn StorageBackend>,
+    executor: Arc<dyn MediaExecutor>,
+    work_dir: PathBuf,
+}
+
+impl MediaProcessor {
+    pub fn new(
+        storage: Arc<dyn StorageBackend>,
+        executor: Arc<dyn MediaExecutor>,
+        work_dir: PathBuf,
+    ) -> Self {
+        Self {
+            storage,
+            executor,
+            work_dir,
+        }
+    }
+
+    /// Procesa un asset ejecutando las operaciones en orden
+    pub async fn process(
+        &self,
+        asset: &ContentAsset,
+        operations: &[ProcessingOperation],
+    ) -> ProcessingResult<ProcessingOutput> {
+        let mut output = ProcessingOutput {
+            metadata: asset.metadata.clone(),
+            ..Default::default()
+        };
+
+        if operations.is_empty() {
+            return Ok(output);
+        }
+
+        // Directorio temporal por job (se elimina al salir)
+        fs::create_dir_all(&self.work_dir).await?;
+        let scratch = tempfile::Builder::new()
+            .prefix("acc-media-")
+            .tempdir_in(&self.work_dir)?;
+
+        let source = scratch.path().join(format!("source.{}", extension(&asset.key)));
+        self.download_file(&asset.key, &source).await?;
+
+        let probe = self.executor.probe(&source).await?;
+
+        for operation in operations {
+            info!(
+                asset_id = %asset.asset_id,
+                operation = operation.name(),
+                executor = self.executor.name(),
+                "Running processing operation"
+            );
+
+            match operation {
+                ProcessingOperation::ExtractMetadata => {
+                    apply_probe(&mut output.metadata, &probe);
+                }
+                ProcessingOperation::Transcode { qualities } => {
+                    self.transcode(asset, &source, scratch.path(), &probe, qualities, &mut output)
+                        .await?;
+                }
+                ProcessingOperation::GenerateThumbnail { timestamps } => {
+                    self.thumbnails(asset, &source, scratch.path(), &probe, timestamps.as_deref(), &mut output)
+                        .await?;
+                }
+                ProcessingOperation::Transcribe { language } => {
+                    let language = language.as_deref().unwrap_or(DEFAULT_TRANSCRIPTION_LANGUAGE);
+                    self.transcribe(asset, &source, scratch.path(), language, &mut output)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    async fn transcode(
+        &self,
+        asset: &ContentAsset,
+        source: &Path,
+        scratch: &Path,
+        probe: &MediaProbe,
+        labels: &[String],
+        output: &mut ProcessingOutput,
+    ) -> ProcessingResult<()> {
+        let qualities = select_qualities(labels, probe.height)?;
+        let mut available = Vec::new();
+
+        for quality in qualities {
+            let file = scratch.join(format!("variant{}.mp4", quality.suffix()));
+            let result = self.executor.transcode(source, &file, quality).await?;
+
+            let key = derived_key(&asset.key, quality.suffix(), "mp4");
+            let info = self.upload_file(&key, &file, "video/mp4").await?;
+
+            let hls_dir = scratch.join(format!("hls{}", quality.suffix()));
+            fs::create_dir_all(&hls_dir).await?;
+            match self.executor.segment_hls(&file, &hls_dir, HLS_SEGMENT_SECONDS).await {
+                Ok(()) => self.upload_hls(&hls_playlist_key(&key), &hls_dir).await?,
+                // Sin HLS la variante sigue disponible como MP4 progresivo
+                Err(ProcessingError::NotSupported(reason)) => {
+                    warn!(asset_id = %asset.asset_id, "HLS segmenting skipped: {}", reason);
+                }
+                Err(e) => return Err(e),
+            }
+
+            output.variants.push(NewVideoVariant {
+                quality: quality.label().to_string(),
+                codec: result.codec,
+                bitrate_kbps: result.bitrate_kbps,
+                width: result.width,
+                height: result.height,
+                key,
+                size_bytes: info as i64,
+            });
+            available.push(quality.label().to_string());
+        }
+
+        output.metadata.available_qualities = Some(available);
+        Ok(())
+    }
+
+    async fn thumbnails(
+        &self,
+        asset: &ContentAsset,
+        source: &Path,
+        scratch: &Path,
+        probe: &MediaProbe,
+        timestamps: Option<&[i32]>,
+        output: &mut ProcessingOutput,
+    ) -> ProcessingResult<()> {
+        let timestamps = thumbnail_timestamps(timestamps, probe.duration_seconds);
+        let height = match (probe.width, probe.height) {
+            (Some(w), Some(h)) if w > 0 => (THUMBNAIL_WIDTH * h / w) & !1,
+            _ => THUMBNAIL_WIDTH * 9 / 16,
+        };
+
+        for (index, timestamp) in timestamps.into_iter().enumerate() {
+            let file = scratch.join(format!("thumb_{}.jpg", timestamp));
+            self.executor.thumbnail(source, &file, timestamp, THUMBNAIL_WIDTH).await?;
+
+            let key = derived_key(&asset.key, &format!("_thumb_{}", timestamp), "jpg");
+            let size = self.upload_file(&key, &file, "image/jpeg").await?;
+
+            // El primero es el poster por defecto
+            let thumbnail_type = if index == 0 {
+                output.metadata.thumbnail_url = Some(format!("{}/{}", self.storage.base_url(), key));
+                "poster".to_string()
+            } else {
+                format!("frame_{}", timestamp)
+            };
+
+            output.thumbnails.push(NewThumbnail {
+                thumbnail_type,
+                timestamp_seconds: timestamp,
+                width: THUMBNAIL_WIDTH,
+                height,
+                key,
+                size_bytes: size as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn transcribe(
+        &self,
+        asset: &ContentAsset,
+        source: &Path,
+        scratch: &Path,
+        language: &str,
+        output: &mut ProcessingOutput,
+    ) -> ProcessingResult<()> {
+        let file = scratch.join(format!("transcript_{}.vtt", language));
+
+        match self.executor.transcribe(source, &file, language).await {
+            Ok(()) => {
+                let key = derived_key(&asset.key, &format!("_{}", language), "vtt");
+                let size = self.upload_file(&key, &file, "text/vtt").await?;
+
+                output.transcriptions.push(NewTranscription {
+                    language: language.to_string(),
+                    format: "vtt".to_string(),
+                    key,
+                    size_bytes: size as i64,
+                });
+                output.metadata.transcription_status = Some("completed".to_string());
+                output.metadata.transcription_language = Some(language.to_string());
+            }
+            // Sin motor de transcripción no es un fallo del asset
+            Err(ProcessingError::NotSupported(reason)) => {
+                warn!(asset_id = %asset.asset_id, "Transcription skipped: {}", reason);
+                output.metadata.transcription_status = Some("unavailable".to_string());
+            }
+            Err(e) => return Err(e),
+        }
+
+        Ok(())
+    }
+
+    /// Sube segmentos y playlist HLS. El playlist va al final: su existencia
+    /// indica que la variante está completa para streaming.
+    async fn upload_hls(&self, playlist_key: &str, dir: &Path) -> ProcessingResult<()> {
+        let prefix = playlist_key.trim_end_matches("index.m3u8");
+
+        let mut segments = Vec::new();
+        let mut entries = fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".ts") {
+                segments.push(name);
+            }
+        }
+        segments.sort();
+
+        for name in &segments {
+            self.upload_file(&format!("{}{}", prefix, name), &dir.join(name), "video/mp2t")
+                .await?;
+        }
+        self.upload_file(playlist_key, &dir.join("index.m3u8"), "application/vnd.apple.mpegurl")
+            .await?;
+
+        Ok(())
+    }
+
+    /// Descarga el original a disco en streaming (videos de varios GB)
+    async fn download_file(&self, key: &str, path: &Path) -> ProcessingResult<()> {
+        let mut object = self.storage.download_stream(key, None).await?;
+        let mut file = fs::File::create(path).await?;
+
+        while let Some(chunk) = object.stream.next().await {
+            file.write_all(&chunk?).await?;
+        }
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Sube un archivo generado al storage y devuelve su tamaño
+    async fn upload_file(&self, key: &str, path: &Path, content_type: &str) -> ProcessingResult<u64> {
+        let file = fs::File::open(path).await?;
+        let data: ByteStream = Box::pin(ReaderStream::new(file).map(|chunk| chunk.map_err(Into::into)));
+        let info = self.storage.upload_stream(key, data, content_type).await?;
+        Ok(info.size_bytes)
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Copia los datos de ffprobe a la metadata del asset
+fn apply_probe(metadata: &mut ContentMetadata, probe: &MediaProbe) {
+    metadata.duration_seconds = probe.duration_seconds.map(|d| d.round() as i32);
+    metadata.width = probe.width;
+    metadata.height = probe.height;
+    metadata.bitrate = probe.bitrate;
+    metadata.codec = probe.video_codec.clone().or_else(|| probe.audio_codec.clone());
+    metadata.frame_rate = probe.frame_rate;
+    metadata.aspect_ratio = match (probe.width, probe.height) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => {
+            let divisor = gcd(w, h);
+            Some(format!("{}:{}", w / divisor, h / divisor))
+        }
+        _ => None,
+    };
+}
+
+/// Calidades a generar: sin escalar hacia arriba, al menos la menor pedida
+fn select_qualities(labels: &[String], source_height: Option<i32>) -> ProcessingResult<Vec<VideoQuality>> {
+    let mut requested = Vec::new();
+    for label in labels {
+        let quality = VideoQuality::from_label(label)
+            .ok_or_else(|| ProcessingError::InvalidOperation(format!("Unknown quality: {}", label)))?;
+        if quality != VideoQuality::Original && !requested.contains(&quality) {
+            requested.push(quality);
+        }
+    }
+    requested.sort_by_key(|q| q.resolution().1);
+
+    let Some(source_height) = source_height else {
+        return Ok(requested);
+    };
+
+    let fitting: Vec<VideoQuality> = requested
+        .iter()
+        .copied()
+        .filter(|q| q.resolution().1 as i32 <= source_height)
+        .collect();
+
+    if fitting.is_empty() {
+        Ok(requested.into_iter().take(1).collect())
+    } else {
+        Ok(fitting)
+    }
+}
+
+/// Timestamps para thumbnails: los pedidos (acotados a la duración) o el 10%
+fn thumbnail_timestamps(requested: Option<&[i32]>, duration: Option<f64>) -> Vec<i32> {
+    let max = duration.map(|d| (d.floor() as i32 - 1).max(0));
+
+    match requested {
+        Some(list) if !list.is_empty() => {
+            let mut timestamps: Vec<i32> = list
+                .iter()
+                .map(|t| match max {
+                    Some(max) => (*t).clamp(0, max),
+                    None => (*t).max(0),
+                })
+                .collect();
+            timestamps.dedup();
+            timestamps
+        }
+        _ => vec![duration.map(|d| ((d * 0.1) as i32).min(10)).unwrap_or(0)],
+    }
+}
+
+/// Key derivado del original: `a/b/id.mp4` + `_720p` + `mp4` -> `a/b/id_720p.mp4`
+fn derived_key(key: &str, suffix: &str, extension: &str) -> String {
+    let base = match key.rfind('.') {
+        Some(dot) if !key[dot..].contains('/') => &key[..dot],
+        _ => key,
+    };
+    format!("{}{}.{}", base, suffix, extension)
+}
+
+fn extension(key: &str) -> &str {
+    Path::new(key)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("bin")
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ContentType, ProcessingStatus};
+    use crate::processing::executor::TranscodeOutput;
+    use crate::storage::{LocalStorage, LocalStorageConfig};
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    /// Executor fake: escribe archivos pequeños en vez de invocar ffmpeg
+    struct FakeExecutor;
+
+    #[async_trait]
+    impl MediaExecutor for FakeExecutor {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        async fn probe(&self, _input: &Path) -> ProcessingResult<MediaProbe> {
+            Ok(MediaProbe {
+                duration_seconds: Some(120.0),
+                width: Some(1280),
+                height: Some(720),
+                bitrate: Some(2_000_000),
+                video_codec: Some("h264".to_string()),
+                audio_codec: Some("aac".to_string()),
+                frame_rate: Some(30.0),
+            })
+        }
+
+        async fn transcode(&self, _input: &Path, output: &Path, quality: VideoQuality) -> ProcessingResult<TranscodeOutput> {
+            fs::write(output, quality.label()).await?;
+            let (width, height) = quality.resolution();
+            Ok(TranscodeOutput {
+                codec: "h264".to_string(),
+                width: width as i32,
+                height: height as i32,
+                bitrate_kbps: quality.bitrate_kbps(),
+            })
+        }
+
+        async fn segment_hls(&self, _input: &Path, output_dir: &Path, segment_seconds: u32) -> ProcessingResult<()> {
+            let playlist = format!(
+                "#EXTM3U\n#EXT-X-TARGETDURATION:{0}\n#EXTINF:{0}.0,\nsegment_00000.ts\n#EXTINF:{0}.0,\nsegment_00001.ts\n#EXT-X-ENDLIST\n",
+                segment_seconds
+            );
+            fs::write(output_dir.join("segment_00000.ts"), b"ts0").await?;
+            fs::write(output_dir.join("segment_00001.ts"), b"ts1").await?;
+            fs::write(output_dir.join("index.m3u8"), playlist).await?;
+            Ok(())
+        }
+
+        async fn thumbnail(&self, _input: &Path, output: &Path, _ts: i32, _width: i32) -> ProcessingResult<()> {
+            fs::write(output, b"jpeg").await?;
+            Ok(())
+        }
+    }
+
+    fn asset(key: &str) -> ContentAsset {
+        ContentAsset {
+            asset_id: Uuid::new_v4(),
+            tenant_id: None,
+            owner_id: Uuid::new_v4(),
+            filename: "lesson.mp4".to_string(),
+            original_filename: "lesson.mp4".to_string(),
+            content_type: ContentType::Video,
+            mime_type: "video/mp4".to_string(),
+            size_bytes: 5,
+            checksum: None,
+            bucket: "default".to_string(),
+            key: key.to_string(),
+            storage_class: "STANDARD".to_string(),
+            storage_backend: "local".to_string(),
+            status: ProcessingStatus::Processing,
+            error_message: None,
+            metadata: ContentMetadata::default(),
+            course_id: None,
+            lesson_id: None,
+            view_count: 0,
+            download_count: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            uploaded_at: None,
+            processed_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_video_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage: Arc<dyn StorageBackend> = Arc::new(
+            LocalStorage::new(LocalStorageConfig {
+                base_path: dir.path().join("files"),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        storage
+            .upload("owner/video/abc.mp4", Bytes::from_static(b"video"), "video/mp4")
+            .await
+            .unwrap();
+
+        let processor = MediaProcessor::new(storage.clone(), Arc::new(FakeExecutor), dir.path().join("work"));
+        let output = processor
+            .process(&asset("owner/video/abc.mp4"), &ProcessingOperation::defaults_for(ContentType::Video))
+            .await
+            .unwrap();
+
+        // 720p de origen: no se genera 1080p
+        let qualities: Vec<&str> = output.variants.iter().map(|v| v.quality.as_str()).collect();
+        assert_eq!(qualities, vec!["360p", "720p"]);
+        assert!(storage.exists("owner/video/abc_720p.mp4").await.unwrap());
+        assert!(storage.exists("owner/video/abc_720p/index.m3u8").await.unwrap());
+        assert!(storage.exists("owner/video/abc_720p/segment_00001.ts").await.unwrap());
+
+        assert_eq!(output.thumbnails.len(), 1);
+        assert_eq!(output.thumbnails[0].thumbnail_type, "poster");
+        assert_eq!(output.thumbnails[0].timestamp_seconds, 10);
+        assert!(storage.exists("owner/video/abc_thumb_10.jpg").await.unwrap());
+
+        assert_eq!(output.metadata.duration_seconds, Some(120));
+        assert_eq!(output.metadata.aspect_ratio.as_deref(), Some("16:9"));
+        assert!(output.metadata.thumbnail_url.unwrap().ends_with("abc_thumb_10.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_unsupported_is_not_fatal() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage: Arc<dyn StorageBackend> = Arc::new(
+            LocalStorage::new(LocalStorageConfig {
+                base_path: dir.path().join("files"),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        storage.upload("a.mp4", Bytes::from_static(b"video"), "video/mp4").await.unwrap();
+
+        let processor = MediaProcessor::new(storage, Arc::new(FakeExecutor), dir.path().join("work"));
+        let output = processor
+            .process(&asset("a.mp4"), &[ProcessingOperation::Transcribe { language: None }])
+            .await
+            .unwrap();
+
+        assert!(output.transcriptions.is_empty());
+        assert_eq!(output.metadata.transcription_status.as_deref(), Some("unavailable"));
+    }
+
+    #[test]
+    fn test_select_qualities() {
+        let labels: Vec<String> = ["1080p", "low", "720p"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(
+            select_qualities(&labels, Some(1080)).unwrap(),
+            vec![VideoQuality::Low, VideoQuality::Medium, VideoQuality::High]
+        );
+        assert_eq!(select_qualities(&labels, Some(240)).unwrap(), vec![VideoQuality::Low]);
+        assert!(select_qualities(&["4k".to_string()], None).is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_timestamps() {
+        assert_eq!(thumbnail_timestamps(None, Some(30.0)), vec![3]);
+        assert_eq!(thumbnail_timestamps(None, Some(3600.0)), vec![10]);
+        assert_eq!(thumbnail_timestamps(Some(&[5, 500]), Some(60.0)), vec![5, 59]);
+        assert_eq!(thumbnail_timestamps(None, None), vec![0]);
+    }
+
+    #[test]
+    fn test_derived_key() {
+        assert_eq!(derived_key("u/video/id.mp4", "_720p", "mp4"), "u/video/id_720p.mp4");
+        assert_eq!(derived_key("u/video.v2/id", "_thumb_3", "jpg"), "u/video.v2/id_thumb_3.jpg");
+    }
+}
@@ -0,0 +1,259 @@
+// =============================================================================
+// ACC LMS - Content Processing Worker
+// =============================================================================
+// Consume la cola `content.processing_jobs`: toma jobs con SKIP LOCKED,
+// ejecuta el MediaProcessor, persiste resultados y avanza ProcessingStatus.
+// Los fallos transitorios se reintentan con backoff exponencial.
+// =============================================================================
+
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use super::executor::{ProcessingError, ProcessingResult};
+use super::processor::{MediaProcessor, ProcessingOutput};
+use crate::domain::{ProcessingJob, ProcessingStatus};
+use crate::repository::{ContentRepository, ProcessingRepository, RepositoryError};
+
+/// Configuración del worker
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// Identificador del worker (para el lease del job)
+    pub worker_id: String,
+    /// Espera entre sondeos cuando la cola está vacía
+    pub poll_interval: Duration,
+    /// Tras este tiempo, un job `running` se considera abandonado
+    pub lease_timeout_secs: i64,
+    /// Retraso base del backoff exponencial
+    pub retry_base_delay_secs: i64,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            worker_id: format!("content-worker-{}", uuid::Uuid::new_v4()),
+            poll_interval: Duration::from_secs(5),
+            lease_timeout_secs: 3 * 60 * 60, // 3 horas (> timeout de ffmpeg)
+            retry_base_delay_secs: 30,
+        }
+    }
+}
+
+pub struct ProcessingWorker {
+    assets: Arc<ContentRepository>,
+    jobs: Arc<ProcessingRepository>,
+    processor: MediaProcessor,
+    config: WorkerConfig,
+}
+
+impl ProcessingWorker {
+    pub fn new(
+        assets: Arc<ContentRepository>,
+        jobs: Arc<ProcessingRepository>,
+        processor: MediaProcessor,
+        config: WorkerConfig,
+    ) -> Self {
+        Self {
+            assets,
+            jobs,
+            processor,
+            config,
+        }
+    }
+
+    /// Loop principal del worker (no retorna)
+    pub async fn run(self: Arc<Self>) {
+        info!(worker_id = %self.config.worker_id, "Processing worker started");
+
+        loop {
+            match self.run_once().await {
+                Ok(true) => continue,
+                Ok(false) => tokio::time::sleep(self.config.poll_interval).await,
+                Err(e) => {
+                    error!(worker_id = %self.config.worker_id, error = %e, "Processing worker error");
+                    tokio::time::sleep(self.config.poll_interval).await;
+                }
+            }
+        }
+    }
+
+    /// Procesa un job si hay alguno disponible. Devuelve si se procesó algo.
+    pub async fn run_once(&self) -> ProcessingResult<bool> {
+        self.fail_abandoned_jobs().await?;
+
+        let Some(job) = self
+            .jobs
+            .claim_next_job(&self.config.worker_id, self.config.lease_timeout_secs)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        self.handle_job(job).await?;
+        Ok(true)
+    }
+
+    /// Los jobs abandonados sin intentos restantes no se vuelven a tomar:
+    /// se marcan como fallidos junto con su asset.
+    async fn fail_abandoned_jobs(&self) -> ProcessingResult<()> {
+        let jobs = self
+            .jobs
+            .fail_abandoned_jobs(self.config.lease_timeout_secs)
+            .await?;
+
+        for job in jobs {
+            let message = job
+                .last_error
+                .clone()
+                .unwrap_or_else(|| "Processing lease expired".to_string());
+            self.assets
+                .update_status(job.asset_id, ProcessingStatus::Failed, Some(&message))
+                .await?;
+
+            error!(
+                job_id = %job.job_id,
+                asset_id = %job.asset_id,
+                attempts = job.attempts,
+                error = %message,
+                "Processing abandoned after max attempts"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn handle_job(&self, job: ProcessingJob) -> ProcessingResult<()> {
+        let started = std::time::Instant::now();
+        let operations: Vec<&str> = job.operations.iter().map(|o| o.name()).collect();
+
+        info!(
+            job_id = %job.job_id,
+            asset_id = %job.asset_id,
+            attempt = job.attempts,
+            operations = ?operations,
+            "Processing started"
+        );
+
+        let asset = match self.assets.get_asset(job.asset_id).await {
+            Ok(asset) => asset,
+            Err(RepositoryError::NotFound(_)) => {
+                // Asset eliminado mientras el job esperaba en la cola
+                self.jobs.fail_job(job.job_id, "Asset no longer exists").await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.jobs.mark_asset_processing(asset.asset_id).await?;
+
+        let result = match self.processor.process(&asset, &job.operations).await {
+            Ok(output) => self.persist_output(&job, output).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => {
+                self.jobs.complete_job(job.job_id).await?;
+                self.jobs.mark_asset_ready(asset.asset_id).await?;
+
+                info!(
+                    job_id = %job.job_id,
+                    asset_id = %job.asset_id,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Processing completed"
+                );
+            }
+            Err(e) => self.handle_failure(&job, e).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn persist_output(&self, job: &ProcessingJob, output: ProcessingOutput) -> ProcessingResult<()> {
+        for variant in &output.variants {
+            self.jobs.upsert_video_variant(job.asset_id, variant).await?;
+        }
+        for thumbnail in &output.thumbnails {
+            self.jobs.upsert_thumbnail(job.asset_id, thumbnail).await?;
+        }
+        for transcription in &output.transcriptions {
+            self.jobs.upsert_transcription(job.asset_id, transcription).await?;
+        }
+        self.assets.update_metadata(job.asset_id, &output.metadata).await?;
+        Ok(())
+    }
+
+    async fn handle_failure(&self, job: &ProcessingJob, err: ProcessingError) -> ProcessingResult<()> {
+        let message = err.to_string();
+
+        if err.is_retryable() && job.attempts < job.max_attempts {
+            let run_after = Utc::now() + retry_delay(job.attempts, self.config.retry_base_delay_secs);
+            self.jobs.retry_job(job.job_id, &message, run_after).await?;
+            self.assets
+                .update_status(job.asset_id, ProcessingStatus::Processing, Some(&message))
+                .await?;
+
+            warn!(
+                job_id = %job.job_id,
+                asset_id = %job.asset_id,
+                attempt = job.attempts,
+                max_attempts = job.max_attempts,
+                retry_at = %run_after,
+                error = %message,
+                "Processing failed, will retry"
+            );
+        } else {
+            self.jobs.fail_job(job.job_id, &message).await?;
+            self.assets
+                .update_status(job.asset_id, ProcessingStatus::Failed, Some(&message))
+                .await?;
+
+            error!(
+                job_id = %job.job_id,
+                asset_id = %job.asset_id,
+                attempts = job.attempts,
+                error = %message,
+                "Processing failed permanently"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl ProcessingError {
+    /// Errores de configuración/operación inválida no mejoran reintentando
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            ProcessingError::InvalidOperation(_) | ProcessingError::NotSupported(_)
+        )
+    }
+}
+
+/// Backoff exponencial: base * 2^(intento-1), máximo 1 hora
+pub fn retry_delay(attempt: i32, base_secs: i64) -> chrono::Duration {
+    let exponent = (attempt.max(1) - 1).min(16) as u32;
+    let secs = base_secs.saturating_mul(2i64.pow(exponent)).min(3600);
+    chrono::Duration::seconds(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        assert_eq!(retry_delay(1, 30).num_seconds(), 30);
+        assert_eq!(retry_delay(2, 30).num_seconds(), 60);
+        assert_eq!(retry_delay(3, 30).num_seconds(), 120);
+        assert_eq!(retry_delay(20, 30).num_seconds(), 3600);
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        assert!(ProcessingError::Executor("ffmpeg crashed".to_string()).is_retryable());
+        assert!(!ProcessingError::InvalidOperation("bad quality".to_string()).is_retryable());
+    }
+}
@@ -11,7 +11,7 @@ use thiserror::Error;
 use uuid::Uuid;
 
 use crate::domain::{
-    ContentAsset, ContentMetadata, ContentType, ProcessingStatus,
+    ContentAsset, ContentMetadata, ContentType, NewContentAsset, ProcessingStatus,
     AssetFilters, AssetListResponse, StorageStats, TypeStats, StatusStats,
 };
 
@@ -43,20 +43,7 @@ impl ContentRepository {
     // =========================================================================
 
     /// Crea un nuevo asset (antes del upload)
-    pub async fn create_asset(
-        &self,
-        owner_id: Uuid,
-        filename: &str,
-        original_filename: &str,
-        content_type: ContentType,
-        mime_type: &str,
-        size_bytes: i64,
-        bucket: &str,
-        key: &str,
-        course_id: Option<Uuid>,
-        lesson_id: Option<Uuid>,
-        tenant_id: Option<Uuid>,
-    ) -> Result<ContentAsset> {
+    pub async fn create_asset(&self, data: NewContentAsset) -> Result<ContentAsset> {
         let asset_id = Uuid::new_v4();
         let now = Utc::now();
         let metadata = ContentMetadata::default();
@@ -72,45 +59,47 @@ impl ContentRepository {
                 created_at, updated_at
             ) VALUES (
                 $1, $2, $3, $4, $5, $6::content_type, $7, $8, $9, $10,
-                'local', 'pending'::processing_status, $11, $12, $13, $14, $14
+                $15, 'pending'::processing_status, $11, $12, $13, $14, $14
             )
             "#,
         )
         .bind(asset_id)
-        .bind(tenant_id)
-        .bind(owner_id)
-        .bind(filename)
-        .bind(original_filename)
-        .bind(content_type.to_string())
-        .bind(mime_type)
-        .bind(size_bytes)
-        .bind(bucket)
-        .bind(key)
+        .bind(data.tenant_id)
+        .bind(data.owner_id)
+        .bind(&data.filename)
+        .bind(&data.original_filename)
+        .bind(data.content_type.to_string())
+        .bind(&data.mime_type)
+        .bind(data.size_bytes)
+        .bind(&data.bucket)
+        .bind(&data.key)
         .bind(&metadata_json)
-        .bind(course_id)
-        .bind(lesson_id)
+        .bind(data.course_id)
+        .bind(data.lesson_id)
         .bind(now)
+        .bind(&data.storage_backend)
         .execute(&self.pool)
         .await?;
 
         Ok(ContentAsset {
             asset_id,
-            tenant_id,
-            owner_id,
-            filename: filename.to_string(),
-            original_filename: original_filename.to_string(),
-            content_type,
-            mime_type: mime_type.to_string(),
-            size_bytes,
+            tenant_id: data.tenant_id,
+            owner_id: data.owner_id,
+            filename: data.filename,
+            original_filename: data.original_filename,
+            content_type: data.content_type,
+            mime_type: data.mime_type,
+            size_bytes: data.size_bytes,
             checksum: None,
-            bucket: bucket.to_string(),
-            key: key.to_string(),
+            bucket: data.bucket,
+            key: data.key,
             storage_class: "STANDARD".to_string(),
+            storage_backend: data.storage_backend,
             status: ProcessingStatus::Pending,
             error_message: None,
             metadata,
-            course_id,
-            lesson_id,
+            course_id: data.course_id,
+            lesson_id: data.lesson_id,
             view_count: 0,
             download_count: 0,
             created_at: now,
@@ -205,7 +194,8 @@ impl ContentRepository {
             checksum: row.get("checksum"),
             bucket: row.get("storage_bucket"),
             key: row.get("storage_key"),
-            storage_class: row.get("storage_backend"),
+            storage_class: "STANDARD".to_string(),
+            storage_backend: row.get("storage_backend"),
             status,
             error_message: row.get("processing_error"),
             metadata,
@@ -496,6 +486,40 @@ impl ContentRepository {
         Ok(result.assets)
     }
 
+    /// Offset persistido de un upload reanudable
+    pub async fn get_upload_offset(&self, asset_id: Uuid) -> Result<i64> {
+        let row = sqlx::query(
+            r#"
+            SELECT upload_offset
+            FROM content_assets
+            WHERE asset_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(RepositoryError::NotFound(asset_id))?;
+
+        Ok(row.get("upload_offset"))
+    }
+
+    /// Registra los bytes recibidos de un upload reanudable
+    pub async fn set_upload_offset(&self, asset_id: Uuid, offset: i64) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content_assets
+            SET upload_offset = $2, updated_at = NOW()
+            WHERE asset_id = $1
+            "#,
+        )
+        .bind(asset_id)
+        .bind(offset)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Incrementa contador de vistas
     pub async fn increment_view_count(&self, asset_id: Uuid) -> Result<()> {
         // Por ahora no hay columna view_count, ignoramos
@@ -1,3 +1,5 @@
 pub mod content_repository;
+pub mod processing_repository;
 
 pub use content_repository::{ContentRepository, RepositoryError, Result};
+pub use processing_repository::{NewThumbnail, NewTranscription, NewVideoVariant, ProcessingRepository};
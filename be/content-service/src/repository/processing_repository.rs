@@ -0,0 +1,434 @@
+// =============================================================================
+// ACC LMS - Content Processing Repository
+// =============================================================================
+// Persistencia de la cola de procesamiento (jobs) y de sus resultados
+// (variantes de video, thumbnails, transcripciones)
+// =============================================================================
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use super::content_repository::{RepositoryError, Result};
+use crate::domain::{
+    JobStatus, ProcessingJob, ProcessingOperation, ProcessingStatus, Transcription, VideoVariant,
+};
+
+/// Variante generada lista para persistir
+#[derive(Debug, Clone)]
+pub struct NewVideoVariant {
+    pub quality: String,
+    pub codec: String,
+    pub bitrate_kbps: i32,
+    pub width: i32,
+    pub height: i32,
+    pub key: String,
+    pub size_bytes: i64,
+}
+
+/// Thumbnail generado listo para persistir
+#[derive(Debug, Clone)]
+pub struct NewThumbnail {
+    pub thumbnail_type: String,
+    pub timestamp_seconds: i32,
+    pub width: i32,
+    pub height: i32,
+    pub key: String,
+    pub size_bytes: i64,
+}
+
+/// Transcripción generada lista para persistir
+#[derive(Debug, Clone)]
+pub struct NewTranscription {
+    pub language: String,
+    pub format: String,
+    pub key: String,
+    pub size_bytes: i64,
+}
+
+const JOB_COLUMNS: &str = r#"
+    job_id, asset_id, operations, status, attempts, max_attempts, last_error,
+    run_after, locked_by, locked_at, created_at, updated_at, completed_at
+"#;
+
+pub struct ProcessingRepository {
+    pool: PgPool,
+}
+
+impl ProcessingRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // =========================================================================
+    // Cola de jobs
+    // =========================================================================
+
+    /// Encola un job para un asset. Devuelve None si ya hay uno activo.
+    pub async fn enqueue_job(
+        &self,
+        asset_id: Uuid,
+        operations: &[ProcessingOperation],
+        max_attempts: i32,
+    ) -> Result<Option<ProcessingJob>> {
+        let operations_json = serde_json::to_value(operations)
+            .map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO content.processing_jobs (asset_id, operations, max_attempts)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (asset_id) WHERE status IN ('queued', 'running') DO NOTHING
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(asset_id)
+        .bind(&operations_json)
+        .bind(max_attempts)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Self::row_to_job).transpose()
+    }
+
+    /// Toma el siguiente job ejecutable (o con lease vencido) usando SKIP LOCKED
+    pub async fn claim_next_job(
+        &self,
+        worker_id: &str,
+        lease_timeout_secs: i64,
+    ) -> Result<Option<ProcessingJob>> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE content.processing_jobs
+            SET status = 'running', attempts = attempts + 1,
+                locked_by = $1, locked_at = NOW(), updated_at = NOW()
+            WHERE job_id = (
+                SELECT job_id FROM content.processing_jobs
+                WHERE (status = 'queued' AND run_after <= NOW())
+                   OR (status = 'running' AND locked_at < NOW() - make_interval(secs => $2)
+                       AND attempts < max_attempts)
+                ORDER BY run_after
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(worker_id)
+        .bind(lease_timeout_secs as f64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Self::row_to_job).transpose()
+    }
+
+    /// Marca como fallidos los jobs con lease vencido que ya agotaron sus intentos
+    /// (p.ej. el worker murió procesándolos). Devuelve los jobs afectados.
+    pub async fn fail_abandoned_jobs(&self, lease_timeout_secs: i64) -> Result<Vec<ProcessingJob>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE content.processing_jobs
+            SET status = 'failed',
+                last_error = COALESCE(last_error, 'Processing lease expired'),
+                locked_by = NULL, locked_at = NULL,
+                completed_at = NOW(), updated_at = NOW()
+            WHERE status = 'running'
+              AND locked_at < NOW() - make_interval(secs => $1)
+              AND attempts >= max_attempts
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(lease_timeout_secs as f64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_job).collect()
+    }
+
+    /// Marca un job como completado
+    pub async fn complete_job(&self, job_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content.processing_jobs
+            SET status = 'completed', last_error = NULL, locked_by = NULL, locked_at = NULL,
+                completed_at = NOW(), updated_at = NOW()
+            WHERE job_id = $1
+            "#,
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reprograma un job fallido para un nuevo intento
+    pub async fn retry_job(&self, job_id: Uuid, error: &str, run_after: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content.processing_jobs
+            SET status = 'queued', last_error = $2, run_after = $3,
+                locked_by = NULL, locked_at = NULL, updated_at = NOW()
+            WHERE job_id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .bind(run_after)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marca un job como fallido definitivamente
+    pub async fn fail_job(&self, job_id: Uuid, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content.processing_jobs
+            SET status = 'failed', last_error = $2, locked_by = NULL, locked_at = NULL,
+                completed_at = NOW(), updated_at = NOW()
+            WHERE job_id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Lista los jobs de un asset (más recientes primero)
+    pub async fn list_asset_jobs(&self, asset_id: Uuid) -> Result<Vec<ProcessingJob>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM content.processing_jobs
+            WHERE asset_id = $1
+            ORDER BY created_at DESC
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_job).collect()
+    }
+
+    fn row_to_job(row: sqlx::postgres::PgRow) -> Result<ProcessingJob> {
+        let operations_value: serde_json::Value = row.get("operations");
+        let operations: Vec<ProcessingOperation> = serde_json::from_value(operations_value)
+            .map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+
+        let status_str: String = row.get("status");
+        let status: JobStatus = status_str
+            .parse()
+            .map_err(RepositoryError::Serialization)?;
+
+        Ok(ProcessingJob {
+            job_id: row.get("job_id"),
+            asset_id: row.get("asset_id"),
+            operations,
+            status,
+            attempts: row.get("attempts"),
+            max_attempts: row.get("max_attempts"),
+            last_error: row.get("last_error"),
+            run_after: row.get("run_after"),
+            locked_by: row.get("locked_by"),
+            locked_at: row.get("locked_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            completed_at: row.get("completed_at"),
+        })
+    }
+
+    // =========================================================================
+    // Resultados del procesamiento
+    // =========================================================================
+
+    /// Inserta o reemplaza una variante de video
+    pub async fn upsert_video_variant(&self, asset_id: Uuid, variant: &NewVideoVariant) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO content.video_variants (
+                asset_id, quality, codec, bitrate_kbps, width, height,
+                storage_key, size_bytes, is_ready
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, TRUE)
+            ON CONFLICT (asset_id, quality, codec) DO UPDATE SET
+                bitrate_kbps = EXCLUDED.bitrate_kbps,
+                width = EXCLUDED.width,
+                height = EXCLUDED.height,
+                storage_key = EXCLUDED.storage_key,
+                size_bytes = EXCLUDED.size_bytes,
+                is_ready = TRUE
+            "#,
+        )
+        .bind(asset_id)
+        .bind(&variant.quality)
+        .bind(&variant.codec)
+        .bind(variant.bitrate_kbps)
+        .bind(variant.width)
+        .bind(variant.height)
+        .bind(&variant.key)
+        .bind(variant.size_bytes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Variantes listas de un asset, de menor a mayor resolución
+    pub async fn list_ready_variants(&self, asset_id: Uuid) -> Result<Vec<VideoVariant>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT variant_id, asset_id, quality, bitrate_kbps, width, height,
+                   storage_key, size_bytes, created_at
+            FROM content.video_variants
+            WHERE asset_id = $1 AND is_ready = TRUE
+            ORDER BY height, bitrate_kbps
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| VideoVariant {
+                variant_id: row.get("variant_id"),
+                asset_id: row.get("asset_id"),
+                quality: row.get("quality"),
+                width: row.get("width"),
+                height: row.get("height"),
+                bitrate: row.get("bitrate_kbps"),
+                key: row.get("storage_key"),
+                size_bytes: row.get("size_bytes"),
+                status: ProcessingStatus::Ready,
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Inserta o reemplaza un thumbnail
+    pub async fn upsert_thumbnail(&self, asset_id: Uuid, thumbnail: &NewThumbnail) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO content.thumbnails (
+                asset_id, thumbnail_type, width, height, timestamp_seconds,
+                storage_key, size_bytes
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (asset_id, thumbnail_type) DO UPDATE SET
+                width = EXCLUDED.width,
+                height = EXCLUDED.height,
+                timestamp_seconds = EXCLUDED.timestamp_seconds,
+                storage_key = EXCLUDED.storage_key,
+                size_bytes = EXCLUDED.size_bytes
+            "#,
+        )
+        .bind(asset_id)
+        .bind(&thumbnail.thumbnail_type)
+        .bind(thumbnail.width)
+        .bind(thumbnail.height)
+        .bind(thumbnail.timestamp_seconds)
+        .bind(&thumbnail.key)
+        .bind(thumbnail.size_bytes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Inserta o reemplaza una transcripción
+    pub async fn upsert_transcription(
+        &self,
+        asset_id: Uuid,
+        transcription: &NewTranscription,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO content.transcriptions (
+                asset_id, language, format, is_auto_generated, storage_key, size_bytes
+            ) VALUES ($1, $2, $3, TRUE, $4, $5)
+            ON CONFLICT (asset_id, language, format) DO UPDATE SET
+                storage_key = EXCLUDED.storage_key,
+                size_bytes = EXCLUDED.size_bytes
+            "#,
+        )
+        .bind(asset_id)
+        .bind(&transcription.language)
+        .bind(&transcription.format)
+        .bind(&transcription.key)
+        .bind(transcription.size_bytes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Transcripción de un asset en un idioma (prefiere txt > vtt > srt)
+    pub async fn get_transcription(&self, asset_id: Uuid, language: &str) -> Result<Option<Transcription>> {
+        let row = sqlx::query(
+            r#"
+            SELECT transcription_id, asset_id, language, format, storage_key, created_at
+            FROM content.transcriptions
+            WHERE asset_id = $1 AND language = $2
+            ORDER BY CASE format WHEN 'txt' THEN 0 WHEN 'vtt' THEN 1 ELSE 2 END
+            LIMIT 1
+            "#,
+        )
+        .bind(asset_id)
+        .bind(language)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| Transcription {
+            transcription_id: row.get("transcription_id"),
+            asset_id: row.get("asset_id"),
+            language: row.get("language"),
+            format: row.get("format"),
+            key: row.get("storage_key"),
+            status: ProcessingStatus::Ready,
+            word_count: None,
+            created_at: row.get("created_at"),
+        }))
+    }
+
+    /// Marca el inicio del procesamiento de un asset
+    pub async fn mark_asset_processing(&self, asset_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content_assets
+            SET status = 'processing'::processing_status, processing_error = NULL,
+                processing_started_at = COALESCE(processing_started_at, NOW()), updated_at = NOW()
+            WHERE asset_id = $1
+            "#,
+        )
+        .bind(asset_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marca el asset como listo tras procesar
+    pub async fn mark_asset_ready(&self, asset_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE content_assets
+            SET status = 'ready'::processing_status, processing_error = NULL,
+                processing_completed_at = NOW(), updated_at = NOW()
+            WHERE asset_id = $1
+            "#,
+        )
+        .bind(asset_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
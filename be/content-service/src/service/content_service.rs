@@ -4,19 +4,40 @@
 // Lógica de negocio para gestión de contenido multimedia
 // =============================================================================
 
+use chrono::{DateTime, Utc};
+use shared::service_client::ServiceClient;
 use std::sync::Arc;
 use thiserror::Error;
 use tracing::{info, warn, error};
 use uuid::Uuid;
 
 use crate::domain::{
-    ContentAsset, ContentMetadata, ContentType, ProcessingStatus,
-    CreateUploadRequest, PresignedUpload, PresignedDownload,
+    ContentAsset, ContentMetadata, ContentType, NewContentAsset, ProcessingStatus,
+    CreateUploadRequest, PresignedUpload, PresignedUploadPart, PresignedDownload,
     AssetFilters, AssetListResponse, StorageStats,
     AccessCheckRequest, AccessCheckResponse, AccessAction,
+    ProcessingJob, ProcessingOperation,
+    StreamManifest, StreamToken, StreamVariant, VideoQuality, VideoVariant, ResumableUpload,
+    Transcription,
 };
-use crate::repository::{ContentRepository, RepositoryError};
-use crate::storage::{StorageBackend, StorageError, UploadOptions};
+use crate::repository::{ContentRepository, ProcessingRepository, RepositoryError};
+use crate::service::hls;
+use crate::storage::{
+    ByteRange, ByteStream, CompletedPart, ObjectStream, StorageBackend, StorageError,
+    StoredFileInfo, UploadOptions, UrlSigner,
+};
+
+/// Tamaño a partir del cual se usa upload multipart (si el backend lo soporta)
+const MULTIPART_THRESHOLD_BYTES: u64 = 100 * 1024 * 1024; // 100MB
+
+/// Intentos máximos por job de procesamiento
+const PROCESSING_MAX_ATTEMPTS: i32 = 3;
+
+/// Validez de una sesión de streaming (master y media playlists)
+const STREAM_SESSION_SECS: i64 = 4 * 60 * 60; // 4 horas
+
+/// Prefijo de las rutas de la API (los playlists se sirven bajo él)
+const API_PREFIX: &str = "/api/v1/content";
 
 #[derive(Debug, Error)]
 pub enum ContentError {
@@ -40,11 +61,32 @@ pub enum ContentError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("File not found: {0}")]
+    FileNotFound(String),
+
+    #[error("Requested range not satisfiable (size: {size} bytes)")]
+    RangeNotSatisfiable { size: u64 },
+
+    #[error("Upload offset mismatch: expected {expected}, got {actual}")]
+    UploadOffsetMismatch { expected: u64, actual: u64 },
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
 }
 
 impl From<StorageError> for ContentError {
     fn from(err: StorageError) -> Self {
-        ContentError::Storage(err.to_string())
+        match err {
+            StorageError::NotSupported(_) => ContentError::Validation(err.to_string()),
+            StorageError::NotFound(key) => ContentError::FileNotFound(key),
+            StorageError::FileTooLarge { size, max } => ContentError::FileTooLarge { size, max },
+            StorageError::RangeNotSatisfiable { size } => ContentError::RangeNotSatisfiable { size },
+            StorageError::OffsetMismatch { expected, actual } => {
+                ContentError::UploadOffsetMismatch { expected, actual }
+            }
+            _ => ContentError::Storage(err.to_string()),
+        }
     }
 }
 
@@ -53,17 +95,27 @@ pub type Result<T> = std::result::Result<T, ContentError>;
 /// Servicio de gestión de contenido
 pub struct ContentService {
     repository: Arc<ContentRepository>,
+    processing: Arc<ProcessingRepository>,
     storage: Arc<dyn StorageBackend>,
+    signer: UrlSigner,
+    /// Consultas de matrícula e instructor en enrollments/courses-service
+    services: ServiceClient,
 }
 
 impl ContentService {
     pub fn new(
         repository: Arc<ContentRepository>,
+        processing: Arc<ProcessingRepository>,
         storage: Arc<dyn StorageBackend>,
+        signer: UrlSigner,
+        services: ServiceClient,
     ) -> Self {
         Self {
             repository,
+            processing,
             storage,
+            signer,
+            services,
         }
     }
 
@@ -98,30 +150,43 @@ impl ContentService {
         let key = self.storage.generate_key(&prefix, &request.filename);
 
         // Crear registro en DB (status = pending)
-        let asset = self.repository.create_asset(
+        let asset = self.repository.create_asset(NewContentAsset {
+            tenant_id,
             owner_id,
-            &key.split('/').last().unwrap_or(&request.filename),
-            &request.filename,
+            filename: key.split('/').last().unwrap_or(&request.filename).to_string(),
+            original_filename: request.filename.clone(),
             content_type,
-            &request.content_type,
-            request.size_bytes,
-            "default",  // bucket name
-            &key,
-            request.course_id,
-            request.lesson_id,
-            tenant_id,
-        ).await?;
-
-        // Generar URL de upload
-        let upload_url = self.storage.create_upload_url(
-            &key,
-            UploadOptions {
-                content_type: Some(request.content_type.clone()),
-                max_size: Some(max_size),
-                metadata: None,
-            },
-            3600, // 1 hora
-        ).await?;
+            mime_type: request.content_type.clone(),
+            size_bytes: request.size_bytes,
+            storage_backend: self.storage.backend_type().to_string(),
+            bucket: self.storage.bucket().to_string(),
+            key: key.clone(),
+            course_id: request.course_id,
+            lesson_id: request.lesson_id,
+        }).await?;
+
+        let options = UploadOptions {
+            content_type: Some(request.content_type.clone()),
+            max_size: Some(max_size),
+            metadata: None,
+        };
+
+        // Archivos grandes: upload multipart, devolvemos la URL de la parte 1
+        let (upload_url, upload_id, part_size) = if self.storage.supports_multipart()
+            && request.size_bytes as u64 > MULTIPART_THRESHOLD_BYTES
+        {
+            let multipart = self.storage.create_multipart_upload(&key, options).await?;
+            let url = self.storage.create_upload_part_url(
+                &key,
+                &multipart.upload_id,
+                1,
+                3600, // 1 hora
+            ).await?;
+            (url, Some(multipart.upload_id), Some(multipart.part_size))
+        } else {
+            let url = self.storage.create_upload_url(&key, options, 3600).await?; // 1 hora
+            (url, None, None)
+        };
 
         info!(
             asset_id = %asset.asset_id,
@@ -145,14 +210,102 @@ impl ContentService {
         Ok(PresignedUpload {
             asset_id: asset.asset_id,
             upload_url: upload_url.url,
+            upload_method: upload_url.method,
             expires_at: upload_url.expires_at,
             max_size_bytes: max_size,
             allowed_content_types: allowed_types,
-            upload_id: None,
-            part_size: None,
+            upload_id,
+            part_size,
         })
     }
 
+    /// Genera la URL presignada para una parte de un upload multipart
+    pub async fn create_upload_part_url(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        upload_id: &str,
+        part_number: u32,
+    ) -> Result<PresignedUploadPart> {
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        let upload_url = self.storage.create_upload_part_url(
+            &asset.key,
+            upload_id,
+            part_number,
+            3600, // 1 hora
+        ).await?;
+
+        Ok(PresignedUploadPart {
+            asset_id,
+            upload_id: upload_id.to_string(),
+            part_number,
+            upload_url: upload_url.url,
+            expires_at: upload_url.expires_at,
+        })
+    }
+
+    /// Ensambla las partes de un upload multipart y confirma el asset
+    pub async fn complete_multipart_upload(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        upload_id: &str,
+        parts: Vec<CompletedPart>,
+        checksum: Option<String>,
+    ) -> Result<ContentAsset> {
+        if parts.is_empty() {
+            return Err(ContentError::Validation("At least one part is required".to_string()));
+        }
+
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        self.storage.complete_multipart_upload(&asset.key, upload_id, &parts).await?;
+
+        info!(
+            asset_id = %asset_id,
+            parts = parts.len(),
+            "Multipart upload completed"
+        );
+
+        self.confirm_upload(asset_id, user_id, checksum).await
+    }
+
+    /// Cancela un upload multipart en curso
+    pub async fn abort_multipart_upload(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        upload_id: &str,
+    ) -> Result<()> {
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        self.storage.abort_multipart_upload(&asset.key, upload_id).await?;
+
+        info!(asset_id = %asset_id, "Multipart upload aborted");
+        Ok(())
+    }
+
+    /// Obtiene un asset pendiente de upload verificando ownership
+    async fn get_owned_pending_asset(&self, asset_id: Uuid, user_id: Uuid) -> Result<ContentAsset> {
+        let asset = self.repository.get_asset(asset_id).await?;
+
+        if asset.owner_id != user_id {
+            return Err(ContentError::AccessDenied(
+                "Not the owner of this asset".to_string()
+            ));
+        }
+
+        if !matches!(asset.status, ProcessingStatus::Pending | ProcessingStatus::Uploading) {
+            return Err(ContentError::Validation(format!(
+                "Asset is not awaiting upload (status: {})",
+                asset.status
+            )));
+        }
+
+        Ok(asset)
+    }
+
     /// Confirma que un upload se completó
     pub async fn confirm_upload(
         &self,
@@ -180,6 +333,18 @@ impl ContentService {
         // Obtener info del archivo
         let file_info = self.storage.get_info(&asset.key).await?;
 
+        self.finish_upload(&asset, file_info, checksum).await
+    }
+
+    /// Marca el asset como subido y encola su procesamiento por defecto
+    async fn finish_upload(
+        &self,
+        asset: &ContentAsset,
+        file_info: StoredFileInfo,
+        checksum: Option<String>,
+    ) -> Result<ContentAsset> {
+        let asset_id = asset.asset_id;
+
         // Actualizar DB
         let final_checksum = checksum.unwrap_or(file_info.checksum);
         self.repository.confirm_upload(
@@ -194,10 +359,189 @@ impl ContentService {
             "Upload confirmed"
         );
 
+        // Encolar procesamiento por defecto (video/audio/imagen)
+        let operations = ProcessingOperation::defaults_for(asset.content_type);
+        if !operations.is_empty() {
+            self.enqueue_processing(asset_id, operations).await?;
+        }
+
         // Retornar asset actualizado
         self.repository.get_asset(asset_id).await.map_err(Into::into)
     }
 
+    /// Upload directo al servicio: el body se escribe en el storage en streaming
+    pub async fn direct_upload(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        data: ByteStream,
+    ) -> Result<ContentAsset> {
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        let file_info = self.storage.upload_stream(&asset.key, data, &asset.mime_type).await?;
+
+        let max_size = asset.content_type.max_size_bytes();
+        if file_info.size_bytes == 0 || file_info.size_bytes > max_size {
+            let _ = self.storage.delete(&asset.key).await;
+            return Err(if file_info.size_bytes == 0 {
+                ContentError::Validation("No file data received".to_string())
+            } else {
+                ContentError::FileTooLarge { size: file_info.size_bytes, max: max_size }
+            });
+        }
+
+        info!(
+            asset_id = %asset_id,
+            size = file_info.size_bytes,
+            checksum = %file_info.checksum,
+            "Direct upload received"
+        );
+
+        self.finish_upload(&asset, file_info, None).await
+    }
+
+    // =========================================================================
+    // Resumable Upload (offsets estilo tus)
+    // =========================================================================
+
+    /// Inicia un upload reanudable. `upload_length` debe coincidir con el
+    /// tamaño declarado al crear el asset. Si ya estaba iniciado, devuelve
+    /// el offset actual para reanudar.
+    pub async fn create_resumable_upload(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        upload_length: i64,
+    ) -> Result<ResumableUpload> {
+        if !self.storage.supports_resumable() {
+            return Err(ContentError::Validation(format!(
+                "Resumable uploads are not supported by {} storage, use multipart upload",
+                self.storage.backend_type()
+            )));
+        }
+
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        if upload_length != asset.size_bytes {
+            return Err(ContentError::Validation(format!(
+                "Upload-Length ({}) does not match declared size ({})",
+                upload_length, asset.size_bytes
+            )));
+        }
+
+        if asset.status == ProcessingStatus::Uploading {
+            return self.resumable_state(&asset).await;
+        }
+
+        self.storage.create_resumable_upload(&asset.key).await?;
+        self.repository.set_upload_offset(asset_id, 0).await?;
+        self.repository.update_status(asset_id, ProcessingStatus::Uploading, None).await?;
+
+        info!(asset_id = %asset_id, length = upload_length, "Resumable upload started");
+
+        Ok(ResumableUpload {
+            asset_id,
+            offset: 0,
+            length: asset.size_bytes,
+            completed: false,
+        })
+    }
+
+    /// Estado (offset) de un upload reanudable en curso
+    pub async fn get_resumable_upload(&self, asset_id: Uuid, user_id: Uuid) -> Result<ResumableUpload> {
+        let asset = self.get_owned_resumable_asset(asset_id, user_id).await?;
+        self.resumable_state(&asset).await
+    }
+
+    /// Añade un chunk en `offset`. Al recibir el último byte mueve el archivo
+    /// a su key definitivo y confirma el asset.
+    pub async fn append_resumable_chunk(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        offset: i64,
+        data: ByteStream,
+    ) -> Result<ResumableUpload> {
+        let asset = self.get_owned_resumable_asset(asset_id, user_id).await?;
+
+        if offset < 0 {
+            return Err(ContentError::Validation("Upload-Offset must be positive".to_string()));
+        }
+
+        let result = self
+            .storage
+            .append_resumable_chunk(&asset.key, offset as u64, data, asset.size_bytes as u64)
+            .await;
+
+        let new_offset = match result {
+            Ok(new_offset) => new_offset as i64,
+            Err(e) => {
+                // Un chunk cortado conserva lo escrito: persistir el offset real
+                if let Ok(current) = self.storage.resumable_offset(&asset.key).await {
+                    let _ = self.repository.set_upload_offset(asset_id, current as i64).await;
+                }
+                return Err(e.into());
+            }
+        };
+        self.repository.set_upload_offset(asset_id, new_offset).await?;
+
+        let completed = new_offset == asset.size_bytes;
+        if completed {
+            let file_info = self
+                .storage
+                .complete_resumable_upload(&asset.key, &asset.mime_type)
+                .await?;
+            self.finish_upload(&asset, file_info, None).await?;
+        }
+
+        Ok(ResumableUpload {
+            asset_id,
+            offset: new_offset,
+            length: asset.size_bytes,
+            completed,
+        })
+    }
+
+    /// Descarta un upload reanudable; el asset vuelve a `pending`
+    pub async fn abort_resumable_upload(&self, asset_id: Uuid, user_id: Uuid) -> Result<()> {
+        let asset = self.get_owned_resumable_asset(asset_id, user_id).await?;
+
+        self.storage.abort_resumable_upload(&asset.key).await?;
+        self.repository.set_upload_offset(asset_id, 0).await?;
+        self.repository.update_status(asset_id, ProcessingStatus::Pending, None).await?;
+
+        info!(asset_id = %asset_id, "Resumable upload aborted");
+        Ok(())
+    }
+
+    async fn get_owned_resumable_asset(&self, asset_id: Uuid, user_id: Uuid) -> Result<ContentAsset> {
+        let asset = self.get_owned_pending_asset(asset_id, user_id).await?;
+
+        if asset.status != ProcessingStatus::Uploading {
+            return Err(ContentError::Validation(
+                "No resumable upload in progress for this asset".to_string()
+            ));
+        }
+
+        Ok(asset)
+    }
+
+    /// El storage es la fuente de verdad del offset; la DB se sincroniza
+    async fn resumable_state(&self, asset: &ContentAsset) -> Result<ResumableUpload> {
+        let offset = self.storage.resumable_offset(&asset.key).await? as i64;
+
+        if self.repository.get_upload_offset(asset.asset_id).await? != offset {
+            self.repository.set_upload_offset(asset.asset_id, offset).await?;
+        }
+
+        Ok(ResumableUpload {
+            asset_id: asset.asset_id,
+            offset,
+            length: asset.size_bytes,
+            completed: false,
+        })
+    }
+
     // =========================================================================
     // Download Flow
     // =========================================================================
@@ -255,13 +599,14 @@ impl ContentService {
         })
     }
 
-    /// Obtiene URL directa para streaming (video)
+    /// Abre una sesión de streaming (video): master playlist HLS firmado con
+    /// todas las variantes disponibles, o descarga progresiva si aún no hay HLS
     pub async fn get_stream_url(
         &self,
         asset_id: Uuid,
         user_id: Uuid,
         user_role: &str,
-    ) -> Result<PresignedDownload> {
+    ) -> Result<StreamManifest> {
         let asset = self.repository.get_asset(asset_id).await?;
 
         // Solo videos
@@ -287,19 +632,181 @@ impl ContentService {
         // Incrementar vistas
         let _ = self.repository.increment_view_count(asset_id).await;
 
-        // URL de streaming (más larga)
-        let download_url = self.storage.create_download_url(&asset.key, 14400).await?; // 4 horas
+        let expires_at = Utc::now() + chrono::Duration::seconds(STREAM_SESSION_SECS);
+        let token = self.stream_token_query(asset_id, user_id, expires_at);
+        let variants = self.stream_variants(asset_id, &token).await?;
+
+        if variants.is_empty() {
+            // Sin variantes HLS (procesamiento pendiente o deshabilitado)
+            let download_url = self
+                .storage
+                .create_download_url(&asset.key, STREAM_SESSION_SECS as u64)
+                .await?;
+
+            return Ok(StreamManifest {
+                asset_id: asset.asset_id,
+                master_playlist_url: None,
+                variants,
+                fallback_url: Some(download_url.url),
+                expires_at: download_url.expires_at,
+                content_type: asset.mime_type,
+            });
+        }
 
-        Ok(PresignedDownload {
+        info!(
+            asset_id = %asset_id,
+            user_id = %user_id,
+            variants = variants.len(),
+            "Stream session created"
+        );
+
+        Ok(StreamManifest {
             asset_id: asset.asset_id,
-            download_url: download_url.url,
-            expires_at: download_url.expires_at,
-            filename: asset.original_filename,
-            content_type: asset.mime_type,
-            size_bytes: asset.size_bytes,
+            master_playlist_url: Some(format!(
+                "{}/assets/{}/hls/master.m3u8?{}",
+                API_PREFIX, asset_id, token
+            )),
+            variants,
+            fallback_url: None,
+            expires_at,
+            content_type: "application/vnd.apple.mpegurl".to_string(),
         })
     }
 
+    /// Master playlist HLS de una sesión de streaming
+    pub async fn get_master_playlist(&self, asset_id: Uuid, token: &StreamToken) -> Result<String> {
+        self.verify_stream_token(asset_id, token)?;
+
+        let expires_at = DateTime::from_timestamp(token.expires, 0)
+            .ok_or_else(|| ContentError::AccessDenied("Invalid stream token".to_string()))?;
+        let query = self.stream_token_query(asset_id, token.user_id, expires_at);
+        let variants = self.stream_variants(asset_id, &query).await?;
+
+        if variants.is_empty() {
+            return Err(ContentError::Validation(
+                "Asset has no HLS variants".to_string()
+            ));
+        }
+
+        Ok(hls::render_master_playlist(&variants))
+    }
+
+    /// Media playlist de una variante con URLs de segmento firmadas.
+    /// Los segmentos caducan con la sesión de streaming: los reproductores VOD
+    /// piden el playlist una sola vez, así que una pausa o un seek hacia atrás
+    /// no deben invalidarlos.
+    pub async fn get_variant_playlist(
+        &self,
+        asset_id: Uuid,
+        quality: &str,
+        token: &StreamToken,
+    ) -> Result<String> {
+        self.verify_stream_token(asset_id, token)?;
+
+        let quality = VideoQuality::from_label(quality)
+            .ok_or_else(|| ContentError::Validation(format!("Unknown quality: {}", quality)))?;
+
+        let variant = self
+            .processing
+            .list_ready_variants(asset_id)
+            .await?
+            .into_iter()
+            .find(|v| v.quality == quality.label())
+            .ok_or(ContentError::NotFound(asset_id))?;
+
+        let playlist_key = variant.hls_playlist_key();
+        let stored = match self.storage.download(&playlist_key).await {
+            Ok(data) => data,
+            Err(StorageError::NotFound(_)) => return Err(ContentError::NotFound(asset_id)),
+            Err(e) => return Err(e.into()),
+        };
+        let stored = String::from_utf8_lossy(&stored);
+        let prefix = playlist_key.trim_end_matches("index.m3u8");
+
+        let expires_at = DateTime::from_timestamp(token.expires, 0)
+            .ok_or_else(|| ContentError::AccessDenied("Invalid stream token".to_string()))?;
+
+        sign_media_playlist(self.storage.as_ref(), prefix, &stored, expires_at)
+    }
+
+    /// Variantes con playlist HLS disponible, con URLs de playlist firmadas
+    async fn stream_variants(&self, asset_id: Uuid, token_query: &str) -> Result<Vec<StreamVariant>> {
+        let variants: Vec<VideoVariant> = self.processing.list_ready_variants(asset_id).await?;
+        let mut streamable = Vec::with_capacity(variants.len());
+
+        for variant in variants {
+            // Variantes anteriores a HLS solo tienen MP4
+            if !self.storage.exists(&variant.hls_playlist_key()).await? {
+                continue;
+            }
+
+            streamable.push(StreamVariant {
+                playlist_url: format!(
+                    "{}/assets/{}/hls/{}.m3u8?{}",
+                    API_PREFIX, asset_id, variant.quality, token_query
+                ),
+                bandwidth: hls::variant_bandwidth(variant.bitrate),
+                quality: variant.quality,
+                width: variant.width,
+                height: variant.height,
+            });
+        }
+
+        Ok(streamable)
+    }
+
+    fn stream_token_query(&self, asset_id: Uuid, user_id: Uuid, expires_at: DateTime<Utc>) -> String {
+        format!(
+            "user_id={}&{}",
+            user_id,
+            self.signer.query(&stream_token_payload(asset_id, user_id), expires_at)
+        )
+    }
+
+    fn verify_stream_token(&self, asset_id: Uuid, token: &StreamToken) -> Result<()> {
+        let payload = stream_token_payload(asset_id, token.user_id);
+        if self.signer.verify(&payload, token.expires, &token.signature) {
+            Ok(())
+        } else {
+            Err(ContentError::AccessDenied("Invalid or expired stream token".to_string()))
+        }
+    }
+
+    /// Texto de la transcripción de un asset (subtítulos o texto plano)
+    pub async fn get_transcription(
+        &self,
+        asset_id: Uuid,
+        language: &str,
+        user_id: Uuid,
+        user_role: &str,
+    ) -> Result<(Transcription, String)> {
+        let access = self.check_access(AccessCheckRequest {
+            user_id,
+            asset_id,
+            action: AccessAction::View,
+        }, user_role).await?;
+
+        if !access.allowed {
+            return Err(ContentError::AccessDenied(
+                access.reason.unwrap_or_else(|| "Access denied".to_string())
+            ));
+        }
+
+        let transcription = self.processing.get_transcription(asset_id, language).await?
+            .ok_or_else(|| ContentError::FileNotFound(format!("transcription {}/{}", asset_id, language)))?;
+
+        let data = self.storage.download(&transcription.key).await?;
+        let text = String::from_utf8_lossy(&data).into_owned();
+
+        Ok((transcription, text))
+    }
+
+    /// Abre un archivo del storage (completo o un rango) para servirlo.
+    /// La URL ya fue validada por firma (ver api::signed_files).
+    pub async fn open_file(&self, key: &str, range: Option<ByteRange>) -> Result<ObjectStream> {
+        self.storage.download_stream(key, range).await.map_err(Into::into)
+    }
+
     // =========================================================================
     // Asset Management
     // =========================================================================
@@ -390,6 +897,94 @@ impl ContentService {
         self.repository.get_lesson_assets(lesson_id).await.map_err(Into::into)
     }
 
+    // =========================================================================
+    // Processing
+    // =========================================================================
+
+    /// Solicita (re)procesar un asset; sin operaciones usa las de su tipo
+    pub async fn request_processing(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        user_role: &str,
+        operations: Vec<ProcessingOperation>,
+    ) -> Result<ProcessingJob> {
+        let asset = self.repository.get_asset(asset_id).await?;
+
+        if asset.owner_id != user_id && user_role != "admin" {
+            return Err(ContentError::AccessDenied(
+                "Not authorized to process this asset".to_string()
+            ));
+        }
+
+        if !matches!(asset.status, ProcessingStatus::Ready | ProcessingStatus::Failed) {
+            return Err(ContentError::Validation(format!(
+                "Asset cannot be processed in status {}",
+                asset.status
+            )));
+        }
+
+        let operations = if operations.is_empty() {
+            ProcessingOperation::defaults_for(asset.content_type)
+        } else {
+            operations
+        };
+
+        if operations.is_empty() {
+            return Err(ContentError::Validation(format!(
+                "No processing operations available for {}",
+                asset.content_type
+            )));
+        }
+
+        self.enqueue_processing(asset_id, operations).await?
+            .ok_or_else(|| ContentError::Validation(
+                "Asset already has a processing job in progress".to_string()
+            ))
+    }
+
+    /// Lista los jobs de procesamiento de un asset
+    pub async fn list_processing_jobs(
+        &self,
+        asset_id: Uuid,
+        user_id: Uuid,
+        user_role: &str,
+    ) -> Result<Vec<ProcessingJob>> {
+        let asset = self.repository.get_asset(asset_id).await?;
+
+        if asset.owner_id != user_id && user_role != "admin" {
+            return Err(ContentError::AccessDenied(
+                "Not the owner of this asset".to_string()
+            ));
+        }
+
+        self.processing.list_asset_jobs(asset_id).await.map_err(Into::into)
+    }
+
+    /// Encola un job y pasa el asset a `processing`
+    async fn enqueue_processing(
+        &self,
+        asset_id: Uuid,
+        operations: Vec<ProcessingOperation>,
+    ) -> Result<Option<ProcessingJob>> {
+        let job = self.processing
+            .enqueue_job(asset_id, &operations, PROCESSING_MAX_ATTEMPTS)
+            .await?;
+
+        if let Some(job) = &job {
+            self.processing.mark_asset_processing(asset_id).await?;
+
+            info!(
+                asset_id = %asset_id,
+                job_id = %job.job_id,
+                operations = ?operations.iter().map(|o| o.name()).collect::<Vec<_>>(),
+                "Processing job enqueued"
+            );
+        }
+
+        Ok(job)
+    }
+
     // =========================================================================
     // Access Control
     // =========================================================================
@@ -418,25 +1013,28 @@ impl ContentService {
             });
         }
 
-        // Para View/Download, verificar enrollment (simplificado)
-        // TODO: Integrar con enrollments-service para verificar matrícula
         match request.action {
             AccessAction::View | AccessAction::Download => {
-                // Por ahora, permitir si tiene course_id asociado
-                // En producción: llamar a enrollments-service
-                if asset.course_id.is_some() {
-                    // Asumimos que hay enrollment válido si llegó aquí
-                    // El middleware de autenticación ya validó el token
-                    Ok(AccessCheckResponse {
-                        allowed: true,
-                        reason: Some("Enrollment verified".to_string()),
-                    })
-                } else {
-                    Ok(AccessCheckResponse {
+                // Un asset fuera de un curso solo lo ve su propietario
+                let Some(course_id) = asset.course_id else {
+                    return Ok(AccessCheckResponse {
                         allowed: false,
-                        reason: Some("No enrollment found".to_string()),
-                    })
-                }
+                        reason: Some("Asset is not part of a course".to_string()),
+                    });
+                };
+
+                // Matrícula vigente o instructor del curso. Si la consulta
+                // falla se responde 503, nunca se concede el acceso
+                let allowed = self.services
+                    .can_access_course(request.user_id, course_id)
+                    .await
+                    .map_err(|e| ContentError::ServiceUnavailable(e.to_string()))?;
+
+                let reason = if allowed { "Enrollment verified" } else { "No enrollment found" };
+                Ok(AccessCheckResponse {
+                    allowed,
+                    reason: Some(reason.to_string()),
+                })
             }
             AccessAction::Edit | AccessAction::Delete => {
                 Ok(AccessCheckResponse {
@@ -488,3 +1086,70 @@ impl ContentService {
         Ok(())
     }
 }
+
+/// Payload firmado en los tokens de streaming
+fn stream_token_payload(asset_id: Uuid, user_id: Uuid) -> String {
+    format!("hls:{}:{}", asset_id, user_id)
+}
+
+/// Reescribe un media playlist almacenado firmando cada segmento hasta `expires_at`
+fn sign_media_playlist(
+    storage: &dyn StorageBackend,
+    prefix: &str,
+    stored: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<String> {
+    let segments = hls::parse_media_playlist(stored);
+    let playlist = hls::render_media_playlist(&segments, |segment| {
+        storage.sign_download_url(&format!("{}{}", prefix, segment.uri), expires_at)
+    })?;
+
+    Ok(playlist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{LocalStorage, LocalStorageConfig};
+
+    const STORED_PLAYLIST: &str = "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:6\n\
+        #EXTINF:6.006000,\nsegment_00000.ts\n#EXTINF:6.006000,\nsegment_00001.ts\n#EXT-X-ENDLIST\n";
+
+    #[test]
+    fn test_segment_urls_survive_pause_within_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LocalStorageConfig {
+            base_path: dir.path().join("files"),
+            ..Default::default()
+        };
+        let signer = UrlSigner::new(&config.signing_secret);
+        let storage = LocalStorage::new(config).unwrap();
+
+        let session_start = Utc::now();
+        let session_expires = session_start + chrono::Duration::seconds(STREAM_SESSION_SECS);
+        let prefix = "videos/abc/hls/720p/";
+        let playlist =
+            sign_media_playlist(&storage, prefix, STORED_PLAYLIST, session_expires).unwrap();
+
+        // El reproductor pausa 30 minutos y luego pide el primer segmento (seek atrás)
+        let resumed_at = session_start + chrono::Duration::minutes(30);
+        let urls: Vec<&str> = playlist.lines().filter(|l| l.starts_with("http")).collect();
+        assert_eq!(urls.len(), 2);
+
+        for (url, uri) in urls.iter().zip(["segment_00000.ts", "segment_00001.ts"]) {
+            let query = url.split_once('?').unwrap().1;
+            let param = |name: &str| {
+                query
+                    .split('&')
+                    .find_map(|p| p.strip_prefix(name))
+                    .unwrap()
+                    .to_string()
+            };
+            let expires: i64 = param("expires=").parse().unwrap();
+
+            assert_eq!(expires, session_expires.timestamp());
+            assert!(expires > resumed_at.timestamp());
+            assert!(signer.verify(&format!("{}{}", prefix, uri), expires, &param("signature=")));
+        }
+    }
+}
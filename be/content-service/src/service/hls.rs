@@ -0,0 +1,157 @@
+// =============================================================================
+// ACC LMS - HLS Playlists
+// =============================================================================
+// Generación del master playlist (una entrada por VideoVariant) y reescritura
+// de los media playlists almacenados con URLs firmadas por segmento
+// =============================================================================
+
+use std::fmt::Write;
+
+use crate::domain::StreamVariant;
+
+/// Bitrate de audio AAC usado al transcodificar (kbps)
+const AUDIO_BITRATE_KBPS: i64 = 128;
+
+/// H.264 Main + AAC-LC (ver FfmpegExecutor::transcode)
+const VARIANT_CODECS: &str = "avc1.4d401f,mp4a.40.2";
+
+/// Segmento de un media playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct HlsSegment {
+    pub duration: f64,
+    pub uri: String,
+}
+
+/// Ancho de banda pico declarado para una variante (bits/s):
+/// maxrate de video (bitrate * 1.07) + audio
+pub fn variant_bandwidth(video_bitrate_kbps: i32) -> i64 {
+    (video_bitrate_kbps as i64 * 107 / 100 + AUDIO_BITRATE_KBPS) * 1000
+}
+
+/// Master playlist con una entrada `#EXT-X-STREAM-INF` por variante
+pub fn render_master_playlist(variants: &[StreamVariant]) -> String {
+    let mut out = String::from("#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-INDEPENDENT-SEGMENTS\n");
+
+    for variant in variants {
+        let _ = writeln!(
+            out,
+            "#EXT-X-STREAM-INF:BANDWIDTH={},RESOLUTION={}x{},CODECS=\"{}\",NAME=\"{}\"",
+            variant.bandwidth, variant.width, variant.height, VARIANT_CODECS, variant.quality
+        );
+        let _ = writeln!(out, "{}", variant.playlist_url);
+    }
+
+    out
+}
+
+/// Extrae los segmentos (duración + URI relativa) de un media playlist
+pub fn parse_media_playlist(playlist: &str) -> Vec<HlsSegment> {
+    let mut segments = Vec::new();
+    let mut duration = None;
+
+    for line in playlist.lines().map(str::trim) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            let value = info.split(',').next().unwrap_or_default();
+            duration = value.trim().parse::<f64>().ok();
+        } else if !line.is_empty() && !line.starts_with('#') {
+            segments.push(HlsSegment {
+                duration: duration.take().unwrap_or(0.0),
+                uri: line.to_string(),
+            });
+        }
+    }
+
+    segments
+}
+
+/// Media playlist VOD; `sign` recibe cada segmento y devuelve la URL firmada
+/// a emitir
+pub fn render_media_playlist<E>(
+    segments: &[HlsSegment],
+    mut sign: impl FnMut(&HlsSegment) -> Result<String, E>,
+) -> Result<String, E> {
+    let target_duration = segments
+        .iter()
+        .map(|s| s.duration.ceil() as u64)
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::from("#EXTM3U\n#EXT-X-VERSION:3\n");
+    let _ = writeln!(out, "#EXT-X-TARGETDURATION:{}", target_duration);
+    out.push_str("#EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n");
+
+    for segment in segments {
+        let _ = writeln!(out, "#EXTINF:{:.3},", segment.duration);
+        let _ = writeln!(out, "{}", sign(segment)?);
+    }
+
+    out.push_str("#EXT-X-ENDLIST\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FFMPEG_PLAYLIST: &str = "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:6\n\
+        #EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n\
+        #EXTINF:6.006000,\nsegment_00000.ts\n#EXTINF:6.006000,\nsegment_00001.ts\n\
+        #EXTINF:2.502500,\nsegment_00002.ts\n#EXT-X-ENDLIST\n";
+
+    #[test]
+    fn test_parse_media_playlist() {
+        let segments = parse_media_playlist(FFMPEG_PLAYLIST);
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].uri, "segment_00000.ts");
+        assert!((segments[2].duration - 2.5025).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_render_media_playlist_signs_segments() {
+        let segments = parse_media_playlist(FFMPEG_PLAYLIST);
+        let mut signed = Vec::new();
+
+        let playlist = render_media_playlist(&segments, |segment| {
+            signed.push(segment.uri.clone());
+            Ok::<_, ()>(format!("https://cdn/{}?sig=x", segment.uri))
+        })
+        .unwrap();
+
+        assert_eq!(signed, ["segment_00000.ts", "segment_00001.ts", "segment_00002.ts"]);
+        assert!(playlist.contains("#EXT-X-TARGETDURATION:7\n"));
+        assert!(playlist.contains("#EXTINF:6.006,\nhttps://cdn/segment_00000.ts?sig=x\n"));
+        assert!(playlist.ends_with("#EXT-X-ENDLIST\n"));
+    }
+
+    #[test]
+    fn test_render_master_playlist() {
+        let variants = vec![
+            StreamVariant {
+                quality: "360p".to_string(),
+                width: 640,
+                height: 360,
+                bandwidth: variant_bandwidth(800),
+                playlist_url: "360p.m3u8?sig=a".to_string(),
+            },
+            StreamVariant {
+                quality: "720p".to_string(),
+                width: 1280,
+                height: 720,
+                bandwidth: variant_bandwidth(2500),
+                playlist_url: "720p.m3u8?sig=b".to_string(),
+            },
+        ];
+
+        let playlist = render_master_playlist(&variants);
+        let lines: Vec<&str> = playlist.lines().collect();
+
+        assert_eq!(lines[0], "#EXTM3U");
+        assert_eq!(
+            lines[3],
+            "#EXT-X-STREAM-INF:BANDWIDTH=984000,RESOLUTION=640x360,CODECS=\"avc1.4d401f,mp4a.40.2\",NAME=\"360p\""
+        );
+        assert_eq!(lines[4], "360p.m3u8?sig=a");
+        assert_eq!(lines[6], "720p.m3u8?sig=b");
+    }
+}
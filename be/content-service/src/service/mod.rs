@@ -1,3 +1,4 @@
 pub mod content_service;
+pub mod hls;
 
 pub use content_service::{ContentService, ContentError, Result};
@@ -5,9 +5,11 @@
 // =============================================================================
 
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
 use std::path::PathBuf;
+use std::pin::Pin;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -33,6 +35,18 @@ pub enum StorageError {
 
     #[error("Configuration error: {0}")]
     ConfigError(String),
+
+    #[error("Operation not supported by backend: {0}")]
+    NotSupported(String),
+
+    #[error("Remote storage error: {0}")]
+    Remote(String),
+
+    #[error("Requested range not satisfiable (size: {size} bytes)")]
+    RangeNotSatisfiable { size: u64 },
+
+    #[error("Upload offset mismatch: expected {expected}, got {actual}")]
+    OffsetMismatch { expected: u64, actual: u64 },
 }
 
 impl From<std::io::Error> for StorageError {
@@ -79,12 +93,117 @@ pub struct UploadOptions {
     pub metadata: Option<Vec<(String, String)>>,
 }
 
+/// Upload multipart iniciado en el backend
+#[derive(Debug, Clone)]
+pub struct MultipartUpload {
+    pub upload_id: String,
+    pub part_size: u64,
+}
+
+/// Flujo de bytes de un objeto (uploads/descargas sin cargar todo en memoria)
+pub type ByteStream = Pin<Box<dyn Stream<Item = StorageResult<Bytes>> + Send>>;
+
+/// Rango de bytes pedido con `Range: bytes=...` (un solo rango)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `bytes=start-`
+    From(u64),
+    /// `bytes=start-end` (inclusive)
+    Inclusive(u64, u64),
+    /// `bytes=-n` (últimos n bytes)
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Interpreta un header Range. Rangos múltiples o inválidos devuelven
+    /// None (se sirve el archivo completo, como permite RFC 9110)
+    pub fn parse(header: &str) -> Option<Self> {
+        let spec = header.trim().strip_prefix("bytes=")?;
+        if spec.contains(',') {
+            return None;
+        }
+
+        let (start, end) = spec.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+
+        match (start.is_empty(), end.is_empty()) {
+            (false, true) => Some(ByteRange::From(start.parse().ok()?)),
+            (false, false) => {
+                let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+                (start <= end).then_some(ByteRange::Inclusive(start, end))
+            }
+            (true, false) => Some(ByteRange::Suffix(end.parse().ok()?)),
+            (true, true) => None,
+        }
+    }
+
+    /// Resuelve el rango contra el tamaño total: `(inicio, fin)` inclusivos,
+    /// o None si no es satisfacible (416)
+    pub fn resolve(&self, total: u64) -> Option<(u64, u64)> {
+        if total == 0 {
+            return None;
+        }
+
+        match *self {
+            ByteRange::From(start) if start < total => Some((start, total - 1)),
+            ByteRange::Inclusive(start, end) if start < total => Some((start, end.min(total - 1))),
+            ByteRange::Suffix(len) if len > 0 => Some((total.saturating_sub(len), total - 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Contenido (completo o parcial) de un objeto en streaming
+pub struct ObjectStream {
+    pub stream: ByteStream,
+    /// Tamaño total del objeto
+    pub total_size: u64,
+    /// Rango servido (inclusivo); None si es el objeto completo
+    pub range: Option<(u64, u64)>,
+    pub content_type: String,
+}
+
+impl ObjectStream {
+    /// Bytes que contiene el stream
+    pub fn content_length(&self) -> u64 {
+        match self.range {
+            Some((start, end)) => end - start + 1,
+            None => self.total_size,
+        }
+    }
+}
+
+impl std::fmt::Debug for ObjectStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectStream")
+            .field("total_size", &self.total_size)
+            .field("range", &self.range)
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parte completada de un upload multipart (número + ETag devuelto)
+#[derive(Debug, Clone)]
+pub struct CompletedPart {
+    pub part_number: u32,
+    pub etag: String,
+}
+
 /// Trait para backends de almacenamiento
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
     /// Nombre del backend (para logging)
     fn name(&self) -> &str;
 
+    /// Tipo de backend persistido en `storage_backend` (local, s3, minio)
+    fn backend_type(&self) -> &str;
+
+    /// Bucket/contenedor donde se guardan los objetos
+    fn bucket(&self) -> &str {
+        "default"
+    }
+
     /// Genera una URL para upload directo (presigned URL o endpoint local)
     async fn create_upload_url(
         &self,
@@ -100,6 +219,10 @@ pub trait StorageBackend: Send + Sync {
         expires_in_secs: u64,
     ) -> StorageResult<DownloadUrl>;
 
+    /// Firma una URL de descarga sin verificar que el objeto exista (sin I/O).
+    /// Pensado para firmar muchos objetos a la vez, p.ej. segmentos HLS.
+    fn sign_download_url(&self, key: &str, expires_at: DateTime<Utc>) -> StorageResult<String>;
+
     /// Sube un archivo directamente (para archivos pequeños)
     async fn upload(
         &self,
@@ -108,9 +231,56 @@ pub trait StorageBackend: Send + Sync {
         content_type: &str,
     ) -> StorageResult<StoredFileInfo>;
 
+    /// Sube un archivo desde un stream sin acumularlo en memoria.
+    /// La implementación por defecto lo acumula y delega en `upload`.
+    async fn upload_stream(
+        &self,
+        key: &str,
+        mut data: ByteStream,
+        content_type: &str,
+    ) -> StorageResult<StoredFileInfo> {
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = data.next().await {
+            buffer.extend_from_slice(&chunk?);
+        }
+        self.upload(key, buffer.freeze(), content_type).await
+    }
+
     /// Descarga un archivo
     async fn download(&self, key: &str) -> StorageResult<Bytes>;
 
+    /// Descarga en streaming, completo o un rango de bytes (206).
+    /// La implementación por defecto descarga todo y recorta.
+    async fn download_stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> StorageResult<ObjectStream> {
+        let info = self.get_info(key).await?;
+        let data = self.download(key).await?;
+        let total_size = data.len() as u64;
+
+        let range = match range {
+            Some(range) => Some(
+                range
+                    .resolve(total_size)
+                    .ok_or(StorageError::RangeNotSatisfiable { size: total_size })?,
+            ),
+            None => None,
+        };
+        let data = match range {
+            Some((start, end)) => data.slice(start as usize..=end as usize),
+            None => data,
+        };
+
+        Ok(ObjectStream {
+            stream: Box::pin(futures_util::stream::once(async move { Ok(data) })),
+            total_size,
+            range,
+            content_type: info.content_type,
+        })
+    }
+
     /// Elimina un archivo
     async fn delete(&self, key: &str) -> StorageResult<()>;
 
@@ -136,6 +306,95 @@ pub trait StorageBackend: Send + Sync {
     /// Obtiene el path/URL base del storage
     fn base_url(&self) -> &str;
 
+    // =========================================================================
+    // Multipart upload (opcional, solo backends S3-compatibles)
+    // =========================================================================
+
+    /// Indica si el backend soporta uploads multipart
+    fn supports_multipart(&self) -> bool {
+        false
+    }
+
+    /// Inicia un upload multipart
+    async fn create_multipart_upload(
+        &self,
+        key: &str,
+        _options: UploadOptions,
+    ) -> StorageResult<MultipartUpload> {
+        Err(StorageError::NotSupported(format!("multipart upload ({}) on {}", key, self.name())))
+    }
+
+    /// Genera una URL presignada para subir una parte
+    async fn create_upload_part_url(
+        &self,
+        key: &str,
+        _upload_id: &str,
+        _part_number: u32,
+        _expires_in_secs: u64,
+    ) -> StorageResult<UploadUrl> {
+        Err(StorageError::NotSupported(format!("multipart upload ({}) on {}", key, self.name())))
+    }
+
+    /// Completa un upload multipart ensamblando las partes
+    async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        _upload_id: &str,
+        _parts: &[CompletedPart],
+    ) -> StorageResult<StoredFileInfo> {
+        Err(StorageError::NotSupported(format!("multipart upload ({}) on {}", key, self.name())))
+    }
+
+    /// Aborta un upload multipart y libera las partes subidas
+    async fn abort_multipart_upload(&self, key: &str, _upload_id: &str) -> StorageResult<()> {
+        Err(StorageError::NotSupported(format!("multipart upload ({}) on {}", key, self.name())))
+    }
+
+    // =========================================================================
+    // Upload reanudable por offsets (opcional, estilo tus)
+    // =========================================================================
+
+    /// Indica si el backend soporta uploads reanudables por offset
+    fn supports_resumable(&self) -> bool {
+        false
+    }
+
+    /// Inicia (o reinicia) el área de staging de un upload reanudable
+    async fn create_resumable_upload(&self, key: &str) -> StorageResult<()> {
+        Err(StorageError::NotSupported(format!("resumable upload ({}) on {}", key, self.name())))
+    }
+
+    /// Bytes persistidos hasta ahora en un upload reanudable
+    async fn resumable_offset(&self, key: &str) -> StorageResult<u64> {
+        Err(StorageError::NotSupported(format!("resumable upload ({}) on {}", key, self.name())))
+    }
+
+    /// Añade un chunk en `offset` (debe coincidir con lo persistido) y
+    /// devuelve el nuevo offset. Si el stream se corta, conserva lo escrito.
+    async fn append_resumable_chunk(
+        &self,
+        key: &str,
+        _offset: u64,
+        _data: ByteStream,
+        _max_size: u64,
+    ) -> StorageResult<u64> {
+        Err(StorageError::NotSupported(format!("resumable upload ({}) on {}", key, self.name())))
+    }
+
+    /// Mueve el staging completo al key definitivo
+    async fn complete_resumable_upload(
+        &self,
+        key: &str,
+        _content_type: &str,
+    ) -> StorageResult<StoredFileInfo> {
+        Err(StorageError::NotSupported(format!("resumable upload ({}) on {}", key, self.name())))
+    }
+
+    /// Descarta un upload reanudable en curso
+    async fn abort_resumable_upload(&self, key: &str) -> StorageResult<()> {
+        Err(StorageError::NotSupported(format!("resumable upload ({}) on {}", key, self.name())))
+    }
+
     /// Genera un key único para un archivo
     fn generate_key(&self, prefix: &str, filename: &str) -> String {
         let id = Uuid::new_v4();
@@ -152,3 +411,28 @@ pub trait StorageBackend: Send + Sync {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(ByteRange::parse("bytes=0-499"), Some(ByteRange::Inclusive(0, 499)));
+        assert_eq!(ByteRange::parse("bytes=500-"), Some(ByteRange::From(500)));
+        assert_eq!(ByteRange::parse("bytes=-200"), Some(ByteRange::Suffix(200)));
+        assert_eq!(ByteRange::parse("bytes=0-1,5-6"), None);
+        assert_eq!(ByteRange::parse("bytes=9-3"), None);
+        assert_eq!(ByteRange::parse("items=0-1"), None);
+    }
+
+    #[test]
+    fn test_resolve_byte_range() {
+        assert_eq!(ByteRange::Inclusive(0, 499).resolve(1000), Some((0, 499)));
+        assert_eq!(ByteRange::Inclusive(900, 5000).resolve(1000), Some((900, 999)));
+        assert_eq!(ByteRange::From(10).resolve(1000), Some((10, 999)));
+        assert_eq!(ByteRange::Suffix(5000).resolve(1000), Some((0, 999)));
+        assert_eq!(ByteRange::From(1000).resolve(1000), None);
+        assert_eq!(ByteRange::Suffix(0).resolve(1000), None);
+    }
+}
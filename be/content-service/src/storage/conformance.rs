@@ -0,0 +1,485 @@
+// =============================================================================
+// ACC LMS - Storage Backend Conformance Tests
+// =============================================================================
+// Suite de comportamiento común que todo StorageBackend debe cumplir.
+// Se ejecuta contra LocalStorage y contra S3Storage apuntando a un
+// stand-in de MinIO en memoria (wiremock).
+// =============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+use super::*;
+
+// =============================================================================
+// Suite
+// =============================================================================
+
+async fn run_suite(backend: &dyn StorageBackend) {
+    let data = Bytes::from_static(b"hello conformance");
+    let checksum = hex::encode(Sha256::digest(&data));
+
+    // upload + get_info
+    let info = backend.upload("suite/docs/a.txt", data.clone(), "text/plain").await.unwrap();
+    assert_eq!(info.size_bytes, data.len() as u64);
+    assert_eq!(info.checksum, checksum);
+
+    let info = backend.get_info("suite/docs/a.txt").await.unwrap();
+    assert_eq!(info.key, "suite/docs/a.txt");
+    assert_eq!(info.size_bytes, data.len() as u64);
+    assert_eq!(info.checksum, checksum);
+
+    // exists + download
+    assert!(backend.exists("suite/docs/a.txt").await.unwrap());
+    assert_eq!(backend.download("suite/docs/a.txt").await.unwrap(), data);
+
+    // list por prefijo (varias páginas en el stand-in)
+    backend.upload("suite/docs/b.txt", Bytes::from_static(b"b"), "text/plain").await.unwrap();
+    backend.upload("suite/docs/c.txt", Bytes::from_static(b"c"), "text/plain").await.unwrap();
+    backend.upload("other/d.txt", Bytes::from_static(b"d"), "text/plain").await.unwrap();
+
+    let mut keys: Vec<String> = backend
+        .list("suite/docs")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|f| f.key)
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["suite/docs/a.txt", "suite/docs/b.txt", "suite/docs/c.txt"]);
+
+    // copy + rename
+    let copied = backend.copy("suite/docs/a.txt", "suite/copy/a.txt").await.unwrap();
+    assert_eq!(copied.size_bytes, data.len() as u64);
+    assert_eq!(backend.download("suite/copy/a.txt").await.unwrap(), data);
+
+    backend.rename("suite/copy/a.txt", "suite/moved/a.txt").await.unwrap();
+    assert!(!backend.exists("suite/copy/a.txt").await.unwrap());
+    assert!(backend.exists("suite/moved/a.txt").await.unwrap());
+
+    // URLs
+    let upload_url = backend
+        .create_upload_url("suite/new.txt", UploadOptions::default(), 60)
+        .await
+        .unwrap();
+    assert!(upload_url.expires_at > chrono::Utc::now());
+
+    let download_url = backend.create_download_url("suite/docs/a.txt", 60).await.unwrap();
+    assert!(download_url.url.contains("suite/docs/a.txt"));
+
+    // Firma sin I/O: no exige que el objeto exista
+    let signed = backend
+        .sign_download_url("suite/hls/segment_00000.ts", chrono::Utc::now() + chrono::Duration::seconds(60))
+        .unwrap();
+    assert!(signed.contains("suite/hls/segment_00000.ts"));
+
+    // upload/descarga en streaming + rangos
+    let chunks = vec![Ok(Bytes::from_static(b"0123")), Ok(Bytes::from_static(b"456789"))];
+    let info = backend
+        .upload_stream("suite/stream.bin", Box::pin(futures_util::stream::iter(chunks)), "application/octet-stream")
+        .await
+        .unwrap();
+    assert_eq!(info.size_bytes, 10);
+    assert_eq!(info.checksum, hex::encode(Sha256::digest(b"0123456789")));
+    let info = backend.get_info("suite/stream.bin").await.unwrap();
+    assert_eq!(info.checksum, hex::encode(Sha256::digest(b"0123456789")));
+
+    let full = backend.download_stream("suite/stream.bin", None).await.unwrap();
+    assert_eq!((full.total_size, full.range, full.content_length()), (10, None, 10));
+    assert_eq!(collect(full.stream).await, b"0123456789");
+
+    let partial = backend
+        .download_stream("suite/stream.bin", Some(ByteRange::Inclusive(2, 5)))
+        .await
+        .unwrap();
+    assert_eq!((partial.total_size, partial.range), (10, Some((2, 5))));
+    assert_eq!(collect(partial.stream).await, b"2345");
+
+    let suffix = backend
+        .download_stream("suite/stream.bin", Some(ByteRange::Suffix(3)))
+        .await
+        .unwrap();
+    assert_eq!(collect(suffix.stream).await, b"789");
+
+    assert!(matches!(
+        backend.download_stream("suite/stream.bin", Some(ByteRange::From(10))).await,
+        Err(StorageError::RangeNotSatisfiable { size: 10 })
+    ));
+    assert!(matches!(
+        backend.download_stream("missing.bin", None).await,
+        Err(StorageError::NotFound(_))
+    ));
+
+    // delete (idempotente) + errores NotFound
+    backend.delete("suite/docs/a.txt").await.unwrap();
+    backend.delete("suite/docs/a.txt").await.unwrap();
+    assert!(!backend.exists("suite/docs/a.txt").await.unwrap());
+
+    assert!(matches!(backend.download("missing.txt").await, Err(StorageError::NotFound(_))));
+    assert!(matches!(backend.get_info("missing.txt").await, Err(StorageError::NotFound(_))));
+    assert!(matches!(
+        backend.create_download_url("missing.txt", 60).await,
+        Err(StorageError::NotFound(_))
+    ));
+    assert!(matches!(
+        backend.copy("missing.txt", "dest.txt").await,
+        Err(StorageError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn local_storage_conformance() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = LocalStorage::new(LocalStorageConfig {
+        base_path: dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert!(!storage.supports_multipart());
+    run_suite(&storage).await;
+}
+
+async fn collect(mut stream: ByteStream) -> Vec<u8> {
+    let mut out = Vec::new();
+    while let Some(chunk) = futures_util::StreamExt::next(&mut stream).await {
+        out.extend_from_slice(&chunk.unwrap());
+    }
+    out
+}
+
+#[tokio::test]
+async fn local_storage_resumable_upload() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = LocalStorage::new(LocalStorageConfig {
+        base_path: dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+    let chunk = |data: &'static [u8]| -> ByteStream {
+        Box::pin(futures_util::stream::iter(vec![Ok(Bytes::from_static(data))]))
+    };
+
+    assert!(storage.supports_resumable());
+    storage.create_resumable_upload("u/video/a.mp4").await.unwrap();
+    assert_eq!(storage.resumable_offset("u/video/a.mp4").await.unwrap(), 0);
+
+    let offset = storage.append_resumable_chunk("u/video/a.mp4", 0, chunk(b"hello "), 11).await.unwrap();
+    assert_eq!(offset, 6);
+
+    // Offset desfasado (p.ej. reintento de un chunk ya escrito)
+    assert!(matches!(
+        storage.append_resumable_chunk("u/video/a.mp4", 0, chunk(b"hello "), 11).await,
+        Err(StorageError::OffsetMismatch { expected: 6, actual: 0 })
+    ));
+
+    // Excede el tamaño declarado: se descarta el chunk entero
+    assert!(matches!(
+        storage.append_resumable_chunk("u/video/a.mp4", 6, chunk(b"world!!"), 11).await,
+        Err(StorageError::FileTooLarge { .. })
+    ));
+    assert_eq!(storage.resumable_offset("u/video/a.mp4").await.unwrap(), 6);
+
+    storage.append_resumable_chunk("u/video/a.mp4", 6, chunk(b"world"), 11).await.unwrap();
+
+    // El staging no es visible hasta completar
+    assert!(!storage.exists("u/video/a.mp4").await.unwrap());
+    assert!(storage.list("").await.unwrap().is_empty());
+
+    let info = storage.complete_resumable_upload("u/video/a.mp4", "video/mp4").await.unwrap();
+    assert_eq!(info.size_bytes, 11);
+    assert_eq!(info.checksum, hex::encode(Sha256::digest(b"hello world")));
+    assert_eq!(storage.download("u/video/a.mp4").await.unwrap(), Bytes::from_static(b"hello world"));
+    assert!(matches!(storage.resumable_offset("u/video/a.mp4").await, Err(StorageError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn s3_storage_conformance() {
+    let server = MockServer::start().await;
+    Mock::given(any()).respond_with(FakeS3::default()).mount(&server).await;
+
+    let storage = S3Storage::new(s3_config(&server)).unwrap();
+    run_suite(&storage).await;
+}
+
+#[tokio::test]
+async fn s3_storage_multipart_upload() {
+    let server = MockServer::start().await;
+    Mock::given(any()).respond_with(FakeS3::default()).mount(&server).await;
+
+    let storage = S3Storage::new(s3_config(&server)).unwrap();
+
+    // 2 partes: 5MB + 1 byte
+    let data = Bytes::from(vec![7u8; 5 * 1024 * 1024 + 1]);
+    let info = storage.upload("big/video.mp4", data.clone(), "video/mp4").await.unwrap();
+    assert_eq!(info.size_bytes, data.len() as u64);
+    assert_eq!(info.checksum, hex::encode(Sha256::digest(&data)));
+    assert_eq!(storage.download("big/video.mp4").await.unwrap(), data);
+
+    // Upload en streaming: se parte en multipart sin acumular el archivo
+    let chunks: Vec<StorageResult<Bytes>> = data.chunks(1024 * 1024).map(|c| Ok(data.slice_ref(c))).collect();
+    let info = storage
+        .upload_stream("big/streamed.mp4", Box::pin(futures_util::stream::iter(chunks)), "video/mp4")
+        .await
+        .unwrap();
+    assert_eq!(info.size_bytes, data.len() as u64);
+    assert_eq!(info.checksum, hex::encode(Sha256::digest(&data)));
+    assert_eq!(storage.download("big/streamed.mp4").await.unwrap(), data);
+
+    // Ambos caminos dejan el checksum como metadata del objeto
+    let uploaded = storage.get_info("big/video.mp4").await.unwrap();
+    let streamed = storage.get_info("big/streamed.mp4").await.unwrap();
+    assert_eq!(uploaded.checksum, hex::encode(Sha256::digest(&data)));
+    assert_eq!(streamed.checksum, uploaded.checksum);
+    assert_eq!(streamed.content_type, "video/mp4");
+
+    // Flujo presignado: iniciar, pedir URL de parte, abortar
+    let upload = storage
+        .create_multipart_upload("big/other.mp4", UploadOptions::default())
+        .await
+        .unwrap();
+    let part_url = storage
+        .create_upload_part_url("big/other.mp4", &upload.upload_id, 1, 60)
+        .await
+        .unwrap();
+    assert!(part_url.url.contains("partNumber=1"));
+    assert!(part_url.url.contains("X-Amz-Signature="));
+    storage.abort_multipart_upload("big/other.mp4", &upload.upload_id).await.unwrap();
+}
+
+fn s3_config(server: &MockServer) -> S3StorageConfig {
+    S3StorageConfig {
+        endpoint: server.uri(),
+        bucket: "acc-test".to_string(),
+        access_key_id: "acc_minio".to_string(),
+        secret_access_key: "acc_minio_secret".to_string(),
+        force_path_style: true,
+        multipart_part_size: 5 * 1024 * 1024,
+        ..Default::default()
+    }
+}
+
+// =============================================================================
+// Stand-in de MinIO en memoria
+// =============================================================================
+
+#[derive(Clone)]
+struct StoredObject {
+    data: Vec<u8>,
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct FakeS3State {
+    objects: BTreeMap<String, StoredObject>,
+    uploads: HashMap<String, (Vec<(String, String)>, BTreeMap<u32, Vec<u8>>)>,
+    next_upload: u32,
+}
+
+/// Implementa el subconjunto del API S3 (path-style) que usa S3Storage.
+/// Pagina ListObjectsV2 de 2 en 2 para ejercitar continuation tokens.
+#[derive(Clone, Default)]
+struct FakeS3 {
+    state: Arc<Mutex<FakeS3State>>,
+}
+
+const PAGE_SIZE: usize = 2;
+
+impl FakeS3 {
+    fn error(status: u16, code: &str) -> ResponseTemplate {
+        ResponseTemplate::new(status)
+            .set_body_string(format!("<Error><Code>{}</Code><Message>{}</Message></Error>", code, code))
+    }
+
+    fn meta_headers(request: &Request) -> Vec<(String, String)> {
+        request
+            .headers
+            .iter()
+            .filter(|(k, _)| k.as_str().starts_with("x-amz-meta-") || k.as_str() == "content-type")
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
+            .collect()
+    }
+}
+
+impl Respond for FakeS3 {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let authorized = request
+            .headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.starts_with("AWS4-HMAC-SHA256 Credential=acc_minio/"))
+            .unwrap_or(false);
+        if !authorized {
+            return Self::error(403, "AccessDenied");
+        }
+
+        let query: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+        let path = percent_decode(request.url.path());
+        let key = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .map(|(_, key)| key.to_string())
+            .unwrap_or_default();
+
+        let mut state = self.state.lock().unwrap();
+
+        match (request.method.as_str(), key.is_empty()) {
+            ("GET", true) if query.contains_key("list-type") => {
+                let prefix = query.get("prefix").cloned().unwrap_or_default();
+                let start: usize = query
+                    .get("continuation-token")
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or(0);
+                let matching: Vec<(&String, &StoredObject)> = state
+                    .objects
+                    .iter()
+                    .filter(|(k, _)| k.starts_with(&prefix))
+                    .collect();
+                let page = matching.iter().skip(start).take(PAGE_SIZE);
+                let truncated = start + PAGE_SIZE < matching.len();
+
+                let mut body = format!("<ListBucketResult><IsTruncated>{}</IsTruncated>", truncated);
+                for (k, object) in page {
+                    body.push_str(&format!(
+                        "<Contents><Key>{}</Key><LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+                         <Size>{}</Size></Contents>",
+                        k,
+                        object.data.len()
+                    ));
+                }
+                if truncated {
+                    body.push_str(&format!(
+                        "<NextContinuationToken>{}</NextContinuationToken>",
+                        start + PAGE_SIZE
+                    ));
+                }
+                body.push_str("</ListBucketResult>");
+                ResponseTemplate::new(200).set_body_string(body)
+            }
+            ("PUT", false) if query.contains_key("uploadId") => {
+                let upload_id = &query["uploadId"];
+                let part: u32 = query.get("partNumber").and_then(|p| p.parse().ok()).unwrap_or(0);
+                match state.uploads.get_mut(upload_id) {
+                    Some((_, parts)) => {
+                        parts.insert(part, request.body.clone());
+                        ResponseTemplate::new(200).insert_header("ETag", format!("\"etag-{}\"", part))
+                    }
+                    None => Self::error(404, "NoSuchUpload"),
+                }
+            }
+            ("PUT", false) => {
+                if let Some(source) = request.headers.get("x-amz-copy-source") {
+                    let source = percent_decode(source.to_str().unwrap_or_default());
+                    let source_key = source
+                        .trim_start_matches('/')
+                        .split_once('/')
+                        .map(|(_, k)| k.to_string())
+                        .unwrap_or_default();
+                    return match state.objects.get(&source_key).cloned() {
+                        Some(mut object) => {
+                            if request.headers.get("x-amz-metadata-directive").is_some_and(|v| v == "REPLACE") {
+                                object.headers = Self::meta_headers(request);
+                            }
+                            state.objects.insert(key, object);
+                            ResponseTemplate::new(200)
+                                .set_body_string("<CopyObjectResult><ETag>\"copy\"</ETag></CopyObjectResult>")
+                        }
+                        None => Self::error(404, "NoSuchKey"),
+                    };
+                }
+
+                state.objects.insert(
+                    key,
+                    StoredObject {
+                        data: request.body.clone(),
+                        headers: Self::meta_headers(request),
+                    },
+                );
+                ResponseTemplate::new(200).insert_header("ETag", "\"put\"")
+            }
+            ("POST", false) if query.contains_key("uploads") => {
+                state.next_upload += 1;
+                let upload_id = format!("upload-{}", state.next_upload);
+                state
+                    .uploads
+                    .insert(upload_id.clone(), (Self::meta_headers(request), BTreeMap::new()));
+                ResponseTemplate::new(200).set_body_string(format!(
+                    "<InitiateMultipartUploadResult><UploadId>{}</UploadId></InitiateMultipartUploadResult>",
+                    upload_id
+                ))
+            }
+            ("POST", false) if query.contains_key("uploadId") => {
+                match state.uploads.remove(&query["uploadId"]) {
+                    Some((headers, parts)) => {
+                        let data = parts.into_values().flatten().collect();
+                        state.objects.insert(key, StoredObject { data, headers });
+                        ResponseTemplate::new(200).set_body_string(
+                            "<CompleteMultipartUploadResult><ETag>\"mp\"</ETag></CompleteMultipartUploadResult>",
+                        )
+                    }
+                    None => Self::error(404, "NoSuchUpload"),
+                }
+            }
+            ("DELETE", false) if query.contains_key("uploadId") => {
+                state.uploads.remove(&query["uploadId"]);
+                ResponseTemplate::new(204)
+            }
+            ("DELETE", false) => {
+                state.objects.remove(&key);
+                ResponseTemplate::new(204)
+            }
+            ("GET", false) | ("HEAD", false) => match state.objects.get(&key) {
+                Some(object) => {
+                    let is_get = request.method.as_str() == "GET";
+                    let range = request
+                        .headers
+                        .get("range")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.strip_prefix("bytes="))
+                        .and_then(|v| v.split_once('-'))
+                        .and_then(|(s, e)| Some((s.parse::<usize>().ok()?, e.parse::<usize>().ok()?)))
+                        .filter(|_| is_get);
+
+                    let mut response = ResponseTemplate::new(if range.is_some() { 206 } else { 200 })
+                        .insert_header("Last-Modified", "Wed, 01 Jan 2025 00:00:00 GMT")
+                        .insert_header("ETag", "\"etag\"");
+                    for (name, value) in &object.headers {
+                        response = response.insert_header(name.as_str(), value.as_str());
+                    }
+
+                    match range {
+                        Some((start, end)) => response.set_body_bytes(object.data[start..=end].to_vec()),
+                        None if is_get => response.set_body_bytes(object.data.clone()),
+                        None => response.insert_header("Content-Length", object.data.len().to_string().as_str()),
+                    }
+                }
+                None if request.method.as_str() == "HEAD" => ResponseTemplate::new(404),
+                None => Self::error(404, "NoSuchKey"),
+            },
+            _ => Self::error(400, "NotImplemented"),
+        }
+    }
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&input[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
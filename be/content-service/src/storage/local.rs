@@ -8,16 +8,23 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
 use sha2::{Sha256, Digest};
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
 use tracing::{debug, info, warn};
 
 use super::backend::{
-    StorageBackend, StorageError, StorageResult,
+    ByteRange, ByteStream, ObjectStream, StorageBackend, StorageError, StorageResult,
     StoredFileInfo, UploadUrl, DownloadUrl, UploadOptions,
 };
+use super::signing::UrlSigner;
+
+/// Directorio (bajo `base_path`) donde se acumulan los uploads reanudables
+const RESUMABLE_DIR: &str = ".resumable";
 
 /// Configuración para LocalStorage
 #[derive(Debug, Clone)]
@@ -30,6 +37,8 @@ pub struct LocalStorageConfig {
     pub upload_endpoint: String,
     /// Tamaño máximo de archivo por defecto (bytes)
     pub max_file_size: u64,
+    /// Secreto HMAC para firmar URLs de descarga
+    pub signing_secret: String,
 }
 
 impl Default for LocalStorageConfig {
@@ -39,6 +48,7 @@ impl Default for LocalStorageConfig {
             base_url: "http://localhost:8083/files".to_string(),
             upload_endpoint: "http://localhost:8083/api/v1/content/upload".to_string(),
             max_file_size: 2 * 1024 * 1024 * 1024, // 2GB
+            signing_secret: "development-secret-change-in-production".to_string(),
         }
     }
 }
@@ -46,6 +56,7 @@ impl Default for LocalStorageConfig {
 /// Backend de almacenamiento en sistema de archivos local
 pub struct LocalStorage {
     config: LocalStorageConfig,
+    signer: UrlSigner,
 }
 
 impl LocalStorage {
@@ -56,7 +67,8 @@ impl LocalStorage {
             info!("Created storage directory: {:?}", config.base_path);
         }
 
-        Ok(Self { config })
+        let signer = UrlSigner::new(&config.signing_secret);
+        Ok(Self { config, signer })
     }
 
     /// Construye el path completo para un key
@@ -64,6 +76,11 @@ impl LocalStorage {
         self.config.base_path.join(key)
     }
 
+    /// Path de staging de un upload reanudable
+    fn staging_path(&self, key: &str) -> PathBuf {
+        self.config.base_path.join(RESUMABLE_DIR).join(key)
+    }
+
     /// Calcula el checksum SHA-256 de datos
     fn calculate_checksum(data: &[u8]) -> String {
         let mut hasher = Sha256::new();
@@ -71,6 +88,49 @@ impl LocalStorage {
         hex::encode(hasher.finalize())
     }
 
+    /// Checksum SHA-256 de un archivo leyéndolo por bloques
+    async fn checksum_file(path: &Path) -> StorageResult<String> {
+        let mut file = fs::File::open(path).await?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+
+        loop {
+            let read = file.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Escribe un stream al final de `file`; falla si se supera `max_size`
+    /// (contando los `written` bytes previos). Devuelve el total escrito.
+    async fn write_stream(
+        file: &mut fs::File,
+        mut data: ByteStream,
+        mut written: u64,
+        max_size: u64,
+        mut hasher: Option<&mut Sha256>,
+    ) -> StorageResult<u64> {
+        while let Some(chunk) = data.next().await {
+            let chunk = chunk?;
+            written += chunk.len() as u64;
+            if written > max_size {
+                file.flush().await?;
+                return Err(StorageError::FileTooLarge { size: written, max: max_size });
+            }
+            if let Some(hasher) = hasher.as_deref_mut() {
+                hasher.update(&chunk);
+            }
+            file.write_all(&chunk).await?;
+        }
+
+        file.flush().await?;
+        Ok(written)
+    }
+
     /// Asegura que el directorio padre existe
     async fn ensure_parent_dir(&self, path: &Path) -> StorageResult<()> {
         if let Some(parent) = path.parent() {
@@ -96,6 +156,10 @@ impl StorageBackend for LocalStorage {
         "local-filesystem"
     }
 
+    fn backend_type(&self) -> &str {
+        "local"
+    }
+
     async fn create_upload_url(
         &self,
         key: &str,
@@ -148,20 +212,23 @@ impl StorageBackend for LocalStorage {
         }
 
         let expires_at = Utc::now() + chrono::Duration::seconds(expires_in_secs as i64);
-        let token = base64_url_encode(&format!("{}:{}", key, expires_at.timestamp()));
-
-        let url = format!(
-            "{}/{}?token={}",
-            self.config.base_url,
-            key,
-            token
-        );
+        let url = self.sign_download_url(key, expires_at)?;
 
         debug!("Created download URL for key: {}", key);
 
         Ok(DownloadUrl { url, expires_at })
     }
 
+    fn sign_download_url(&self, key: &str, expires_at: DateTime<Utc>) -> StorageResult<String> {
+        // La firma se valida al servir /uploads (ver api::signed_files)
+        Ok(format!(
+            "{}/{}?{}",
+            self.config.base_url,
+            key,
+            self.signer.query(key, expires_at)
+        ))
+    }
+
     async fn upload(
         &self,
         key: &str,
@@ -203,6 +270,92 @@ impl StorageBackend for LocalStorage {
         })
     }
 
+    async fn upload_stream(
+        &self,
+        key: &str,
+        data: ByteStream,
+        content_type: &str,
+    ) -> StorageResult<StoredFileInfo> {
+        let path = self.full_path(key);
+        self.ensure_parent_dir(&path).await?;
+
+        // Se escribe a un temporal y se renombra: nunca queda un archivo a medias
+        let temp = path.with_file_name(format!(
+            ".{}.upload-{}",
+            path.file_name().and_then(|n| n.to_str()).unwrap_or("file"),
+            uuid::Uuid::new_v4()
+        ));
+
+        let mut hasher = Sha256::new();
+        let mut file = fs::File::create(&temp).await?;
+        let result = Self::write_stream(&mut file, data, 0, self.config.max_file_size, Some(&mut hasher)).await;
+        drop(file);
+
+        let size = match result {
+            Ok(size) => size,
+            Err(e) => {
+                let _ = fs::remove_file(&temp).await;
+                return Err(e);
+            }
+        };
+        fs::rename(&temp, &path).await?;
+
+        let now = Utc::now();
+        info!("Uploaded file (stream): {} ({} bytes)", key, size);
+
+        Ok(StoredFileInfo {
+            key: key.to_string(),
+            size_bytes: size,
+            content_type: content_type.to_string(),
+            checksum: hex::encode(hasher.finalize()),
+            created_at: now,
+            modified_at: now,
+        })
+    }
+
+    async fn download_stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> StorageResult<ObjectStream> {
+        let path = self.full_path(key);
+
+        let mut file = match fs::File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(key.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let total_size = file.metadata().await?.len();
+
+        let range = match range {
+            Some(range) => Some(
+                range
+                    .resolve(total_size)
+                    .ok_or(StorageError::RangeNotSatisfiable { size: total_size })?,
+            ),
+            None => None,
+        };
+
+        let (start, length) = match range {
+            Some((start, end)) => (start, end - start + 1),
+            None => (0, total_size),
+        };
+        if start > 0 {
+            file.seek(SeekFrom::Start(start)).await?;
+        }
+
+        let stream = ReaderStream::new(file.take(length)).map(|chunk| chunk.map_err(StorageError::from));
+
+        Ok(ObjectStream {
+            stream: Box::pin(stream),
+            total_size,
+            range,
+            content_type: Self::guess_content_type(&path),
+        })
+    }
+
     async fn download(&self, key: &str) -> StorageResult<Bytes> {
         let path = self.full_path(key);
 
@@ -243,8 +396,7 @@ impl StorageBackend for LocalStorage {
         }
 
         let metadata = fs::metadata(&path).await?;
-        let data = fs::read(&path).await?;
-        let checksum = Self::calculate_checksum(&data);
+        let checksum = Self::checksum_file(&path).await?;
 
         let modified = metadata.modified()
             .map(DateTime::<Utc>::from)
@@ -287,7 +439,10 @@ impl StorageBackend for LocalStorage {
                 let metadata = entry.metadata().await?;
 
                 if metadata.is_dir() {
-                    stack.push(path);
+                    // El staging de uploads reanudables no son archivos publicados
+                    if path != self.config.base_path.join(RESUMABLE_DIR) {
+                        stack.push(path);
+                    }
                 } else {
                     let key = path
                         .strip_prefix(&self.config.base_path)
@@ -335,6 +490,98 @@ impl StorageBackend for LocalStorage {
     fn base_url(&self) -> &str {
         &self.config.base_url
     }
+
+    fn supports_resumable(&self) -> bool {
+        true
+    }
+
+    async fn create_resumable_upload(&self, key: &str) -> StorageResult<()> {
+        let path = self.staging_path(key);
+        self.ensure_parent_dir(&path).await?;
+        fs::File::create(&path).await?;
+
+        debug!("Created resumable upload staging for key: {}", key);
+        Ok(())
+    }
+
+    async fn resumable_offset(&self, key: &str) -> StorageResult<u64> {
+        match fs::metadata(self.staging_path(key)).await {
+            Ok(metadata) => Ok(metadata.len()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(key.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn append_resumable_chunk(
+        &self,
+        key: &str,
+        offset: u64,
+        data: ByteStream,
+        max_size: u64,
+    ) -> StorageResult<u64> {
+        let current = self.resumable_offset(key).await?;
+        if current != offset {
+            return Err(StorageError::OffsetMismatch { expected: current, actual: offset });
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(self.staging_path(key))
+            .await?;
+
+        let max_size = max_size.min(self.config.max_file_size);
+        let written = Self::write_stream(&mut file, data, offset, max_size, None).await;
+
+        // Un chunk que excede el tamaño declarado se descarta entero; uno
+        // interrumpido conserva lo escrito y el cliente reanuda desde ahí
+        if let Err(StorageError::FileTooLarge { .. }) = written {
+            file.set_len(offset).await?;
+        }
+
+        let offset = written?;
+        debug!("Appended chunk to {} (offset: {})", key, offset);
+        Ok(offset)
+    }
+
+    async fn complete_resumable_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> StorageResult<StoredFileInfo> {
+        let staging = self.staging_path(key);
+        if !staging.exists() {
+            return Err(StorageError::NotFound(key.to_string()));
+        }
+
+        let path = self.full_path(key);
+        self.ensure_parent_dir(&path).await?;
+        fs::rename(&staging, &path).await?;
+
+        if let Some(parent) = staging.parent() {
+            let _ = self.cleanup_empty_dirs(parent).await;
+        }
+
+        let mut info = self.get_info(key).await?;
+        info.content_type = content_type.to_string();
+
+        info!("Completed resumable upload: {} ({} bytes)", key, info.size_bytes);
+        Ok(info)
+    }
+
+    async fn abort_resumable_upload(&self, key: &str) -> StorageResult<()> {
+        let staging = self.staging_path(key);
+        if staging.exists() {
+            fs::remove_file(&staging).await?;
+            if let Some(parent) = staging.parent() {
+                let _ = self.cleanup_empty_dirs(parent).await;
+            }
+        }
+
+        info!("Aborted resumable upload: {}", key);
+        Ok(())
+    }
 }
 
 impl LocalStorage {
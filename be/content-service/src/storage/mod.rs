@@ -1,5 +1,12 @@
 pub mod backend;
 pub mod local;
+pub mod s3;
+pub mod signing;
+
+#[cfg(test)]
+mod conformance;
 
 pub use backend::*;
 pub use local::*;
+pub use s3::*;
+pub use signing::*;
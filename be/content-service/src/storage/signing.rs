@@ -0,0 +1,90 @@
+// =============================================================================
+// ACC LMS - URL Signing
+// =============================================================================
+// Firma HMAC-SHA256 de URLs con expiración (descargas locales, tokens HLS)
+// =============================================================================
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Firmador de URLs con secreto compartido
+#[derive(Clone)]
+pub struct UrlSigner {
+    secret: Vec<u8>,
+}
+
+impl UrlSigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    /// Firma `payload` con expiración (timestamp unix); devuelve hex
+    pub fn sign(&self, payload: &str, expires: i64) -> String {
+        let mut mac = self.mac(payload, expires);
+        hex::encode(mac.finalize_reset().into_bytes())
+    }
+
+    /// Verifica firma (tiempo constante) y que no haya expirado
+    pub fn verify(&self, payload: &str, expires: i64, signature: &str) -> bool {
+        if expires < Utc::now().timestamp() {
+            return false;
+        }
+
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        self.mac(payload, expires).verify_slice(&signature).is_ok()
+    }
+
+    /// Query string `expires=...&signature=...` para un payload
+    pub fn query(&self, payload: &str, expires_at: DateTime<Utc>) -> String {
+        let expires = expires_at.timestamp();
+        format!("expires={}&signature={}", expires, self.sign(payload, expires))
+    }
+
+    fn mac(&self, payload: &str, expires: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(payload.as_bytes());
+        mac.update(b":");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+}
+
+impl std::fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UrlSigner").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = UrlSigner::new("secret");
+        let expires = Utc::now().timestamp() + 60;
+        let signature = signer.sign("videos/a.ts", expires);
+
+        assert!(signer.verify("videos/a.ts", expires, &signature));
+        assert!(!signer.verify("videos/b.ts", expires, &signature));
+        assert!(!signer.verify("videos/a.ts", expires + 1, &signature));
+        assert!(!UrlSigner::new("other").verify("videos/a.ts", expires, &signature));
+    }
+
+    #[test]
+    fn test_expired_signature_rejected() {
+        let signer = UrlSigner::new("secret");
+        let expires = Utc::now().timestamp() - 1;
+        let signature = signer.sign("videos/a.ts", expires);
+
+        assert!(!signer.verify("videos/a.ts", expires, &signature));
+    }
+}
@@ -491,3 +491,30 @@ pub async fn list_instructor_courses(
 
     Ok(HttpResponse::Ok().json(dtos))
 }
+
+// =============================================================================
+// INTERNAL
+// =============================================================================
+
+/// Returns the authorization data of a course. Only callable by other
+/// services.
+///
+/// `GET /internal/v1/courses/:id`
+#[instrument(skip(state, req))]
+pub async fn internal_get_course(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    state.service_tokens.verify_request(&req)?;
+
+    let course = state
+        .course_service
+        .course_info(path.into_inner())
+        .await?
+        .ok_or(ApiError::NotFound {
+            resource: "Course".to_string(),
+        })?;
+
+    Ok(HttpResponse::Ok().json(course))
+}
@@ -53,5 +53,10 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     "/instructor/courses",
                     web::get().to(handlers::list_instructor_courses),
                 ),
+        )
+        // Service-to-service API (service token required)
+        .service(
+            web::scope("/internal/v1")
+                .route("/courses/{id}", web::get().to(handlers::internal_get_course)),
         );
 }
@@ -7,6 +7,7 @@
 //! - Section and lesson management
 //! - Course publishing workflow
 //! - Category management
+//! - Internal course lookups for other services
 //!
 //! ## Architecture
 //! This service follows Clean Architecture with:
@@ -19,6 +20,7 @@ use std::env;
 use std::sync::Arc;
 
 use actix_web::{middleware, web, App, HttpServer};
+use shared::auth::ServiceTokens;
 use sqlx::postgres::PgPoolOptions;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -34,6 +36,8 @@ use service::CourseService;
 /// Application state shared across handlers.
 pub struct AppState {
     pub course_service: Arc<CourseService>,
+    /// Verifies service tokens on internal endpoints
+    pub service_tokens: Arc<ServiceTokens>,
 }
 
 /// Service configuration loaded from environment.
@@ -44,6 +48,8 @@ pub struct Config {
     pub port: u16,
     pub max_db_connections: u32,
     pub rust_log: String,
+    /// Secret shared by all services to sign service tokens
+    pub internal_service_secret: String,
 }
 
 impl Config {
@@ -63,6 +69,7 @@ impl Config {
                 .expect("MAX_DB_CONNECTIONS must be a valid u32"),
             rust_log: env::var("RUST_LOG")
                 .unwrap_or_else(|_| "info,courses_service=debug,sqlx=warn".to_string()),
+            internal_service_secret: env::var("INTERNAL_SERVICE_SECRET").unwrap_or_default(),
         }
     }
 }
@@ -99,8 +106,17 @@ async fn main() -> std::io::Result<()> {
     // Initialize service
     let course_service = Arc::new(CourseService::new(repository));
 
+    // Authenticates other services on /internal endpoints
+    let service_tokens = Arc::new(
+        ServiceTokens::new("courses-service", &config.internal_service_secret)
+            .expect("INTERNAL_SERVICE_SECRET must be set (at least 32 characters)"),
+    );
+
     // Create application state
-    let app_state = web::Data::new(AppState { course_service });
+    let app_state = web::Data::new(AppState {
+        course_service,
+        service_tokens,
+    });
 
     // Start HTTP server
     let bind_address = format!("{}:{}", config.host, config.port);
@@ -18,6 +18,7 @@
 //! | Delete | ✗ | ✓ (own) | ✓ |
 
 use shared::errors::ApiError;
+use shared::service_client::CourseInfo;
 use tracing::{info, instrument, warn};
 use uuid::Uuid;
 
@@ -131,6 +132,18 @@ impl CourseService {
     // COURSE DETAIL
     // =========================================================================
 
+    /// Returns the authorization data of a course, published or not.
+    /// Used by other services through the internal API.
+    pub async fn course_info(&self, course_id: Uuid) -> Result<Option<CourseInfo>, ApiError> {
+        let course = self.repository.find_by_id(course_id).await?;
+
+        Ok(course.map(|course| CourseInfo {
+            course_id: course.course_id,
+            instructor_id: course.instructor_id,
+            is_published: course.is_published,
+        }))
+    }
+
     /// Gets course by ID with full content structure.
     ///
     /// - Published courses: visible to everyone
@@ -8,8 +8,8 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::domain::{
-    Enrollment, EnrollmentStatus, EnrollmentWithProgress, LessonProgress,
-    LessonProgressStatus,
+    CompletionRules, CompletionStatus, Enrollment, EnrollmentStatus, EnrollmentWithProgress,
+    LessonProgress, LessonProgressStatus, UpsertCompletionRules,
 };
 use crate::repository::{CourseEnrollmentStats, UserLearningStats};
 
@@ -196,9 +196,6 @@ pub struct UpdateProgressRequest {
 #[serde(rename_all = "camelCase")]
 pub struct CompleteLessonRequest {
     pub lesson_id: Uuid,
-
-    /// Total number of lessons in the course (for completion check)
-    pub total_lessons: i64,
 }
 
 /// Request to save playback position.
@@ -315,6 +312,124 @@ impl From<UserLearningStats> for UserStatsDto {
     }
 }
 
+// =============================================================================
+// COMPLETION DTOs
+// =============================================================================
+
+/// Request to set the completion rules of a course.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCompletionRulesRequest {
+    /// Every lesson must be completed
+    #[serde(default = "default_require_all_lessons")]
+    pub require_all_lessons: bool,
+
+    /// Minimum quiz average (0-100)
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub min_quiz_average: Option<f64>,
+
+    /// Quiz the student must pass
+    pub final_assessment_id: Option<Uuid>,
+}
+
+fn default_require_all_lessons() -> bool {
+    true
+}
+
+impl From<UpdateCompletionRulesRequest> for UpsertCompletionRules {
+    fn from(r: UpdateCompletionRulesRequest) -> Self {
+        Self {
+            require_all_lessons: r.require_all_lessons,
+            min_quiz_average: r.min_quiz_average,
+            final_assessment_id: r.final_assessment_id,
+        }
+    }
+}
+
+/// Completion rules of a course.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionRulesDto {
+    pub course_id: Uuid,
+    pub require_all_lessons: bool,
+    pub min_quiz_average: Option<f64>,
+    pub final_assessment_id: Option<Uuid>,
+}
+
+impl From<CompletionRules> for CompletionRulesDto {
+    fn from(r: CompletionRules) -> Self {
+        Self {
+            course_id: r.course_id,
+            require_all_lessons: r.require_all_lessons,
+            min_quiz_average: r.min_quiz_average,
+            final_assessment_id: r.final_assessment_id,
+        }
+    }
+}
+
+/// Completion state of an enrollment, rule by rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionStatusDto {
+    pub completed: bool,
+    pub rules: CompletionRulesDto,
+    pub lessons: LessonsRequirementDto,
+    /// Present when the course sets a minimum quiz average
+    pub quiz_average: Option<QuizAverageRequirementDto>,
+    /// Present when the course requires a final assessment
+    pub final_assessment: Option<FinalAssessmentRequirementDto>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LessonsRequirementDto {
+    pub met: bool,
+    pub completed: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizAverageRequirementDto {
+    pub met: bool,
+    pub required: f64,
+    /// `None` when the course has no published quizzes
+    pub current: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalAssessmentRequirementDto {
+    pub met: bool,
+    pub quiz_id: Uuid,
+}
+
+impl From<CompletionStatus> for CompletionStatusDto {
+    fn from(s: CompletionStatus) -> Self {
+        let quiz_average = s.rules.min_quiz_average.map(|required| QuizAverageRequirementDto {
+            met: s.evaluation.quiz_average_met,
+            required,
+            current: s.progress.quiz_average,
+        });
+        let final_assessment = s.rules.final_assessment_id.map(|quiz_id| FinalAssessmentRequirementDto {
+            met: s.evaluation.final_assessment_met,
+            quiz_id,
+        });
+
+        Self {
+            completed: s.completed,
+            lessons: LessonsRequirementDto {
+                met: s.evaluation.lessons_met,
+                completed: s.progress.completed_lessons,
+                total: s.progress.total_lessons,
+            },
+            quiz_average,
+            final_assessment,
+            rules: s.rules.into(),
+        }
+    }
+}
+
 // =============================================================================
 // ERROR RESPONSE
 // =============================================================================
@@ -2,7 +2,10 @@
 //!
 //! Actix-web handlers for enrollment endpoints.
 
-use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use shared::auth::ServiceTokens;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -12,6 +15,8 @@ use crate::service::{EnrollmentError, EnrollmentService};
 /// Application state shared across handlers.
 pub struct AppState {
     pub enrollment_service: EnrollmentService,
+    /// Verifies service tokens on internal endpoints
+    pub service_tokens: Arc<ServiceTokens>,
 }
 
 // =============================================================================
@@ -290,7 +295,6 @@ pub async fn complete_lesson(
             enrollment_id,
             body.lesson_id,
             user_id.into_inner(),
-            body.total_lessons,
         )
         .await;
 
@@ -432,6 +436,85 @@ pub async fn get_my_stats(
     }
 }
 
+// =============================================================================
+// COMPLETION HANDLERS
+// =============================================================================
+
+/// Gets the completion rules of a course.
+///
+/// GET /api/v1/courses/{course_id}/completion-rules
+pub async fn get_completion_rules(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    let course_id = path.into_inner();
+
+    let result = state.enrollment_service
+        .get_completion_rules(course_id)
+        .await;
+
+    match result {
+        Ok(rules) => HttpResponse::Ok().json(CompletionRulesDto::from(rules)),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Sets the completion rules of a course (admin or course instructor).
+///
+/// PUT /api/v1/courses/{course_id}/completion-rules
+pub async fn update_completion_rules(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateCompletionRulesRequest>,
+    user_id: web::ReqData<Uuid>,
+    is_admin: web::ReqData<bool>,
+) -> HttpResponse {
+    if let Err(errors) = body.validate() {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            "Invalid request data",
+        ).with_details(serde_json::to_value(errors).unwrap_or_default()));
+    }
+
+    let course_id = path.into_inner();
+
+    let result = state.enrollment_service
+        .update_completion_rules(
+            course_id,
+            body.into_inner().into(),
+            user_id.into_inner(),
+            is_admin.into_inner(),
+        )
+        .await;
+
+    match result {
+        Ok(rules) => HttpResponse::Ok().json(CompletionRulesDto::from(rules)),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Checks the enrollment against the course completion rules and
+/// completes it when they are met.
+///
+/// POST /api/v1/enrollments/{enrollment_id}/completion
+pub async fn evaluate_completion(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    user_id: web::ReqData<Uuid>,
+    is_admin: web::ReqData<bool>,
+) -> HttpResponse {
+    let enrollment_id = path.into_inner();
+
+    let result = state.enrollment_service
+        .evaluate_completion(enrollment_id, user_id.into_inner(), is_admin.into_inner())
+        .await;
+
+    match result {
+        Ok(status) => HttpResponse::Ok().json(CompletionStatusDto::from(status)),
+        Err(e) => error_response(e),
+    }
+}
+
 // =============================================================================
 // CERTIFICATE HANDLERS
 // =============================================================================
@@ -456,6 +539,35 @@ pub async fn issue_certificate(
     }
 }
 
+// =============================================================================
+// INTERNAL HANDLERS
+// =============================================================================
+
+/// Returns whether a user's enrollment grants access to a course.
+/// Only callable by other services.
+///
+/// GET /internal/v1/users/{user_id}/courses/{course_id}/access
+pub async fn internal_enrollment_access(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> HttpResponse {
+    if let Err(e) = state.service_tokens.verify_request(&req) {
+        tracing::warn!(error = %e, "Rejected internal request");
+        return HttpResponse::Unauthorized().json(ErrorResponse::new(
+            "unauthorized",
+            "A valid service token is required",
+        ));
+    }
+
+    let (user_id, course_id) = path.into_inner();
+
+    match state.enrollment_service.enrollment_access(user_id, course_id).await {
+        Ok(access) => HttpResponse::Ok().json(access),
+        Err(e) => error_response(e),
+    }
+}
+
 // =============================================================================
 // ERROR HANDLING
 // =============================================================================
@@ -510,6 +622,13 @@ fn error_response(error: EnrollmentError) -> HttpResponse {
                 msg,
             ))
         }
+        EnrollmentError::Certificates(e) => {
+            tracing::error!("Certificate service error: {}", e);
+            HttpResponse::BadGateway().json(ErrorResponse::new(
+                "certificate_service_error",
+                "Certificate could not be issued, try again later",
+            ))
+        }
         EnrollmentError::Database(e) => {
             tracing::error!("Database error: {:?}", e);
             HttpResponse::InternalServerError().json(ErrorResponse::new(
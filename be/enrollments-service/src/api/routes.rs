@@ -18,6 +18,7 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .route("/{enrollment_id}", web::get().to(handlers::get_enrollment))
                     .route("/{enrollment_id}/progress", web::get().to(handlers::get_enrollment_with_progress))
                     .route("/{enrollment_id}/status", web::patch().to(handlers::update_enrollment_status))
+                    .route("/{enrollment_id}/completion", web::post().to(handlers::evaluate_completion))
                     .route("/{enrollment_id}/certificate", web::post().to(handlers::issue_certificate))
                     // Lesson progress
                     .route("/{enrollment_id}/lessons", web::get().to(handlers::get_lesson_progress))
@@ -32,6 +33,8 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .route("/enrollments", web::get().to(handlers::list_course_enrollments))
                     .route("/enrollment/check", web::get().to(handlers::check_enrollment))
                     .route("/stats", web::get().to(handlers::get_course_stats))
+                    .route("/completion-rules", web::get().to(handlers::get_completion_rules))
+                    .route("/completion-rules", web::put().to(handlers::update_completion_rules))
             )
             // User stats
             .service(
@@ -44,4 +47,13 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .route("/stats", web::get().to(handlers::get_my_stats))
             )
     );
+
+    // Service-to-service endpoints (service token required)
+    cfg.service(
+        web::scope("/internal/v1")
+            .route(
+                "/users/{user_id}/courses/{course_id}/access",
+                web::get().to(handlers::internal_enrollment_access),
+            )
+    );
 }
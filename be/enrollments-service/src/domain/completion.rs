@@ -0,0 +1,191 @@
+//! # Course Completion Rules
+//!
+//! Requirements an enrollment must meet before it is marked completed and a
+//! certificate is issued. Each course may configure:
+//!
+//! | Rule | Met when |
+//! |------|----------|
+//! | `require_all_lessons` | Every lesson of the course is completed |
+//! | `min_quiz_average` | Average of the best graded attempt per published quiz (0 if never taken) reaches the minimum |
+//! | `final_assessment_id` | The student passed that quiz |
+//!
+//! Courses without configured rules only require all lessons, which was the
+//! behavior before rules existed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// =============================================================================
+// RULES
+// =============================================================================
+
+/// Completion rules of a course.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CompletionRules {
+    pub course_id: Uuid,
+    pub require_all_lessons: bool,
+    /// Minimum quiz average (0-100)
+    pub min_quiz_average: Option<f64>,
+    /// Quiz the student must pass
+    pub final_assessment_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CompletionRules {
+    /// Rules of a course that has not configured any: all lessons.
+    pub fn default_for(course_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            course_id,
+            require_all_lessons: true,
+            min_quiz_average: None,
+            final_assessment_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Data for creating or replacing the rules of a course.
+#[derive(Debug, Clone)]
+pub struct UpsertCompletionRules {
+    pub require_all_lessons: bool,
+    pub min_quiz_average: Option<f64>,
+    pub final_assessment_id: Option<Uuid>,
+}
+
+impl UpsertCompletionRules {
+    /// Returns true if at least one requirement is set. Rules without
+    /// requirements would complete an enrollment as soon as it is checked.
+    pub fn has_requirements(&self) -> bool {
+        self.require_all_lessons || self.min_quiz_average.is_some() || self.final_assessment_id.is_some()
+    }
+}
+
+// =============================================================================
+// EVALUATION
+// =============================================================================
+
+/// What a student has achieved in a course.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletionProgress {
+    pub completed_lessons: i64,
+    pub total_lessons: i64,
+    /// `None` when the course has no published quizzes
+    pub quiz_average: Option<f64>,
+    pub final_assessment_passed: bool,
+}
+
+/// Result of checking a student's progress against the course rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CompletionEvaluation {
+    pub lessons_met: bool,
+    pub quiz_average_met: bool,
+    pub final_assessment_met: bool,
+}
+
+impl CompletionEvaluation {
+    /// Checks each rule. Rules that are not configured are met.
+    pub fn evaluate(rules: &CompletionRules, progress: &CompletionProgress) -> Self {
+        let lessons_met = !rules.require_all_lessons
+            || (progress.total_lessons > 0 && progress.completed_lessons >= progress.total_lessons);
+
+        // A course without quizzes has nothing to average
+        let quiz_average_met = match (rules.min_quiz_average, progress.quiz_average) {
+            (Some(min), Some(average)) => average >= min,
+            _ => true,
+        };
+
+        let final_assessment_met = rules.final_assessment_id.is_none() || progress.final_assessment_passed;
+
+        Self {
+            lessons_met,
+            quiz_average_met,
+            final_assessment_met,
+        }
+    }
+
+    /// Returns true if every rule is met.
+    pub fn is_satisfied(&self) -> bool {
+        self.lessons_met && self.quiz_average_met && self.final_assessment_met
+    }
+}
+
+/// Completion state of an enrollment: its course rules, the student's
+/// progress and which rules are met.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletionStatus {
+    pub rules: CompletionRules,
+    pub progress: CompletionProgress,
+    pub evaluation: CompletionEvaluation,
+    /// The enrollment is completed (now or earlier)
+    pub completed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(completed_lessons: i64, quiz_average: Option<f64>, passed: bool) -> CompletionProgress {
+        CompletionProgress {
+            completed_lessons,
+            total_lessons: 10,
+            quiz_average,
+            final_assessment_passed: passed,
+        }
+    }
+
+    #[test]
+    fn test_default_rules_require_all_lessons() {
+        let rules = CompletionRules::default_for(Uuid::new_v4());
+
+        assert!(!CompletionEvaluation::evaluate(&rules, &progress(9, None, false)).is_satisfied());
+        assert!(CompletionEvaluation::evaluate(&rules, &progress(10, None, false)).is_satisfied());
+    }
+
+    #[test]
+    fn test_course_without_lessons_is_not_complete() {
+        let rules = CompletionRules::default_for(Uuid::new_v4());
+        let empty = CompletionProgress {
+            completed_lessons: 0,
+            total_lessons: 0,
+            quiz_average: None,
+            final_assessment_passed: false,
+        };
+
+        assert!(!CompletionEvaluation::evaluate(&rules, &empty).lessons_met);
+    }
+
+    #[test]
+    fn test_quiz_average_and_final_assessment() {
+        let rules = CompletionRules {
+            min_quiz_average: Some(70.0),
+            final_assessment_id: Some(Uuid::new_v4()),
+            ..CompletionRules::default_for(Uuid::new_v4())
+        };
+
+        let low_average = CompletionEvaluation::evaluate(&rules, &progress(10, Some(65.5), true));
+        assert!(low_average.lessons_met && !low_average.quiz_average_met);
+        assert!(!low_average.is_satisfied());
+
+        let not_passed = CompletionEvaluation::evaluate(&rules, &progress(10, Some(80.0), false));
+        assert!(!not_passed.final_assessment_met);
+
+        assert!(CompletionEvaluation::evaluate(&rules, &progress(10, Some(70.0), true)).is_satisfied());
+        // No published quizzes: the average rule does not block completion
+        assert!(CompletionEvaluation::evaluate(&rules, &progress(10, None, true)).is_satisfied());
+    }
+
+    #[test]
+    fn test_has_requirements() {
+        let rules = UpsertCompletionRules {
+            require_all_lessons: false,
+            min_quiz_average: None,
+            final_assessment_id: None,
+        };
+        assert!(!rules.has_requirements());
+        assert!(UpsertCompletionRules { min_quiz_average: Some(50.0), ..rules }.has_requirements());
+    }
+}
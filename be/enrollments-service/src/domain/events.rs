@@ -1,7 +1,9 @@
 //! # Enrollment Domain Events
 //!
 //! Events emitted by the enrollments service for cross-service communication.
-//! These events are published to a message broker (e.g., NATS, RabbitMQ) for:
+//! These events are written to the service outbox in the same transaction as
+//! the state change and relayed to Redis Streams (`events:enrollment`,
+//! `events:progress`) for:
 //!
 //! - Analytics tracking
 //! - Notifications triggering
@@ -10,6 +12,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::events::PublishableEvent;
 use uuid::Uuid;
 
 use super::entities::{EnrollmentStatus, LessonProgressStatus};
@@ -187,3 +190,41 @@ impl ProgressEvent {
         }
     }
 }
+
+// =============================================================================
+// OUTBOX
+// =============================================================================
+
+impl PublishableEvent for EnrollmentEvent {
+    fn aggregate_type(&self) -> &'static str {
+        "enrollment"
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.enrollment_id()
+    }
+
+    fn event_type(&self) -> &'static str {
+        EnrollmentEvent::event_type(self)
+    }
+}
+
+impl PublishableEvent for ProgressEvent {
+    fn aggregate_type(&self) -> &'static str {
+        "progress"
+    }
+
+    /// The progress record, or the user for streak updates.
+    fn aggregate_id(&self) -> Uuid {
+        match self {
+            ProgressEvent::Updated { progress_id, .. }
+            | ProgressEvent::LessonCompleted { progress_id, .. }
+            | ProgressEvent::PositionSaved { progress_id, .. } => *progress_id,
+            ProgressEvent::StreakUpdated { user_id, .. } => *user_id,
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        ProgressEvent::event_type(self)
+    }
+}
@@ -6,6 +6,7 @@
 //!
 //! - [`Enrollment`]: Main aggregate representing a student's course enrollment
 //! - [`LessonProgress`]: Progress tracking for individual lessons
+//! - [`CompletionRules`]: Per-course requirements for completion and certificates
 //!
 //! ## Events
 //!
@@ -13,10 +14,15 @@
 //! - `enrollment.created`, `enrollment.completed`
 //! - `progress.updated`, `lesson.completed`
 
+pub mod completion;
 pub mod entities;
 pub mod events;
 pub mod value_objects;
 
+pub use completion::{
+    CompletionEvaluation, CompletionProgress, CompletionRules, CompletionStatus,
+    UpsertCompletionRules,
+};
 pub use entities::{
     Enrollment, EnrollmentStatus, EnrollmentWithProgress, LessonProgress,
     LessonProgressStatus, NewEnrollment, NewLessonProgress, UpdateEnrollment,
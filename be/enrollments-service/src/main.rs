@@ -6,13 +6,21 @@
 //!
 //! - Enrollment CRUD operations
 //! - Lesson progress tracking
-//! - Course completion detection
-//! - Certificate issuance
+//! - Course completion against per-course rules
+//! - Automatic certificate issuance in certificates-service
 //! - Learning statistics
+//! - Domain events via the transactional outbox (`events:enrollment`,
+//!   `events:progress` streams)
+//! - Internal enrollment access lookups for other services
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix_web::{middleware, web, App, HttpServer};
+use shared::auth::ServiceTokens;
+use shared::events::{stream_name, ConsumerConfig, EventConsumer, OutboxRelay, RelayConfig};
+use shared::config::RedisConfig;
+use shared::redis_client::RedisClient;
 use sqlx::postgres::PgPoolOptions;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -23,6 +31,7 @@ mod service;
 
 use api::{configure_routes, AppState};
 use repository::EnrollmentRepository;
+use service::certificates::{CertificatesClient, CompletionCertificates, CERTIFICATES_CONSUMER_GROUP};
 use service::EnrollmentService;
 
 #[actix_web::main]
@@ -40,6 +49,12 @@ async fn main() -> std::io::Result<()> {
     // Load configuration from environment
     let database_url = std::env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set");
+    let redis_url = std::env::var("REDIS_URL")
+        .unwrap_or_else(|_| "redis://localhost:6379".into());
+    let certificates_url = std::env::var("CERTIFICATES_SERVICE_URL")
+        .unwrap_or_else(|_| "http://localhost:8100".into());
+    let internal_secret = std::env::var("INTERNAL_SERVICE_SECRET")
+        .expect("INTERNAL_SERVICE_SECRET must be set");
     let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".into());
     let port: u16 = std::env::var("PORT")
         .unwrap_or_else(|_| "8083".into())
@@ -61,12 +76,44 @@ async fn main() -> std::io::Result<()> {
     //     .await
     //     .expect("Failed to run migrations");
 
+    // Redis Streams carry the domain events
+    let redis = RedisClient::new(&RedisConfig { url: redis_url, pool_size: 10 })
+        .await
+        .expect("Failed to connect to Redis");
+
     // Build application state
-    let repository = Arc::new(EnrollmentRepository::new(pool));
-    let enrollment_service = EnrollmentService::new(repository);
+    let repository = Arc::new(EnrollmentRepository::new(pool.clone()));
+
+    // Publish outbox events in the background
+    let relay = OutboxRelay::new(pool, &redis, repository.outbox(), RelayConfig::default());
+    tokio::spawn(relay.run());
+
+    // Authenticates other services on /internal endpoints, and this service
+    // on certificates-service issuance
+    let service_tokens = Arc::new(
+        ServiceTokens::new("enrollments-service", &internal_secret)
+            .expect("Invalid INTERNAL_SERVICE_SECRET"),
+    );
+
+    let certificates = Arc::new(CertificatesClient::new(
+        &certificates_url,
+        Duration::from_secs(30),
+        service_tokens.clone(),
+    ));
+    let enrollment_service = EnrollmentService::new(repository, certificates);
+
+    // Issue certificates for completed enrollments; one consumer per replica
+    let consumer_name = std::env::var("HOSTNAME")
+        .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
+    let consumer = EventConsumer::new(
+        &redis,
+        ConsumerConfig::new(CERTIFICATES_CONSUMER_GROUP, consumer_name, vec![stream_name("enrollment")]),
+    );
+    tokio::spawn(consumer.run(CompletionCertificates::new(enrollment_service.clone())));
 
     let app_state = web::Data::new(AppState {
         enrollment_service,
+        service_tokens,
     });
 
     tracing::info!("Starting HTTP server on {}:{}", host, port);
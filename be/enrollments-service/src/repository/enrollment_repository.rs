@@ -7,29 +7,51 @@
 //! This repository operates on the `enrollments` schema:
 //! - `enrollments.enrollments`: Student course enrollments
 //! - `enrollments.lesson_progress`: Per-lesson progress tracking
+//! - `enrollments.course_completion_rules`: Per-course completion rules
+//! - `enrollments.outbox_events`: Domain events awaiting relay
+//!
+//! State changes that emit domain events write the event to the outbox in
+//! the same transaction.
 //!
 //! ## Cross-Schema Access
 //!
-//! Has SELECT permission on `courses` schema for validation.
+//! Has SELECT permission on `courses` schema for validation, and on
+//! `assessments` quizzes/submissions and `users.profiles` for completion
+//! rules and certificate names.
 
+use chrono::Utc;
+use shared::events::OutboxWriter;
 use sqlx::postgres::PgPool;
 use uuid::Uuid;
 
 use crate::domain::{
-    Enrollment, EnrollmentStatus, LessonProgress, LessonProgressStatus,
-    NewEnrollment, NewLessonProgress, UpdateEnrollment, UpdateLessonProgress,
+    CompletionRules, Enrollment, EnrollmentEvent, EnrollmentStatus, LessonProgress,
+    LessonProgressStatus, NewEnrollment, NewLessonProgress, ProgressEvent, UpdateEnrollment,
+    UpdateLessonProgress, UpsertCompletionRules,
 };
 
+/// Outbox table of this service.
+pub const OUTBOX_TABLE: &str = "enrollments.outbox_events";
+
 /// Repository for enrollment data access.
 #[derive(Debug, Clone)]
 pub struct EnrollmentRepository {
     pool: PgPool,
+    outbox: OutboxWriter,
 }
 
 impl EnrollmentRepository {
     /// Creates a new enrollment repository.
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            outbox: OutboxWriter::new(OUTBOX_TABLE).expect("valid outbox table name"),
+        }
+    }
+
+    /// Outbox the repository writes events to.
+    pub fn outbox(&self) -> &OutboxWriter {
+        &self.outbox
     }
 
     // =========================================================================
@@ -178,9 +200,11 @@ impl EnrollmentRepository {
         Ok(result.0)
     }
 
-    /// Creates a new enrollment.
+    /// Creates a new enrollment and emits `enrollment.created`.
     pub async fn create(&self, data: NewEnrollment) -> Result<Enrollment, sqlx::Error> {
-        sqlx::query_as::<_, Enrollment>(
+        let mut tx = self.pool.begin().await?;
+
+        let enrollment = sqlx::query_as::<_, Enrollment>(
             r#"
             INSERT INTO enrollments.enrollments (
                 user_id, course_id, status, progress_percentage,
@@ -198,8 +222,69 @@ impl EnrollmentRepository {
         .bind(data.course_id)
         .bind(&data.enrollment_source)
         .bind(data.expires_at)
-        .fetch_one(&self.pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let event = EnrollmentEvent::Created {
+            enrollment_id: enrollment.enrollment_id,
+            user_id: enrollment.user_id,
+            course_id: enrollment.course_id,
+            enrollment_source: enrollment.enrollment_source.clone().unwrap_or_else(|| "purchase".into()),
+            occurred_at: enrollment.created_at,
+        };
+        self.outbox.publish(&mut *tx, &event).await?;
+
+        tx.commit().await?;
+        Ok(enrollment)
+    }
+
+    /// Changes the status of an enrollment and emits
+    /// `enrollment.status_changed` if it actually changed.
+    pub async fn update_status(
+        &self,
+        enrollment_id: Uuid,
+        status: EnrollmentStatus,
+    ) -> Result<Enrollment, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let (old_status,): (EnrollmentStatus,) = sqlx::query_as(
+            "SELECT status FROM enrollments.enrollments WHERE enrollment_id = $1 FOR UPDATE",
+        )
+        .bind(enrollment_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let enrollment = sqlx::query_as::<_, Enrollment>(
+            r#"
+            UPDATE enrollments.enrollments
+            SET status = $2, updated_at = NOW()
+            WHERE enrollment_id = $1
+            RETURNING
+                enrollment_id, user_id, course_id, status,
+                progress_percentage, started_at, completed_at,
+                last_accessed_at, certificate_issued_at,
+                enrollment_source, expires_at, created_at, updated_at
+            "#,
+        )
+        .bind(enrollment_id)
+        .bind(status.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if old_status != status {
+            let event = EnrollmentEvent::StatusChanged {
+                enrollment_id,
+                user_id: enrollment.user_id,
+                course_id: enrollment.course_id,
+                old_status,
+                new_status: status,
+                occurred_at: enrollment.updated_at,
+            };
+            self.outbox.publish(&mut *tx, &event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(enrollment)
     }
 
     /// Updates an enrollment.
@@ -279,8 +364,13 @@ impl EnrollmentRepository {
     }
 
     /// Marks enrollment as completed.
+    ///
+    /// Emits `enrollment.completed` only on the first completion, so
+    /// completing more lessons afterwards does not repeat the event.
     pub async fn mark_completed(&self, enrollment_id: Uuid) -> Result<Enrollment, sqlx::Error> {
-        sqlx::query_as::<_, Enrollment>(
+        let mut tx = self.pool.begin().await?;
+
+        let completed = sqlx::query_as::<_, Enrollment>(
             r#"
             UPDATE enrollments.enrollments
             SET
@@ -288,7 +378,7 @@ impl EnrollmentRepository {
                 progress_percentage = 100.00,
                 completed_at = NOW(),
                 updated_at = NOW()
-            WHERE enrollment_id = $1
+            WHERE enrollment_id = $1 AND status <> 'completed'
             RETURNING
                 enrollment_id, user_id, course_id, status,
                 progress_percentage, started_at, completed_at,
@@ -297,8 +387,45 @@ impl EnrollmentRepository {
             "#,
         )
         .bind(enrollment_id)
-        .fetch_one(&self.pool)
-        .await
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let enrollment = match completed {
+            Some(enrollment) => {
+                let completed_at = enrollment.completed_at.unwrap_or_else(Utc::now);
+                let started_at = enrollment.started_at.unwrap_or(enrollment.created_at);
+                let event = EnrollmentEvent::Completed {
+                    enrollment_id,
+                    user_id: enrollment.user_id,
+                    course_id: enrollment.course_id,
+                    completion_time_days: (completed_at - started_at).num_days() as i32,
+                    final_progress: enrollment.progress_percentage,
+                    occurred_at: completed_at,
+                };
+                self.outbox.publish(&mut *tx, &event).await?;
+                enrollment
+            }
+            None => {
+                // Already completed
+                sqlx::query_as::<_, Enrollment>(
+                    r#"
+                    SELECT
+                        enrollment_id, user_id, course_id, status,
+                        progress_percentage, started_at, completed_at,
+                        last_accessed_at, certificate_issued_at,
+                        enrollment_source, expires_at, created_at, updated_at
+                    FROM enrollments.enrollments
+                    WHERE enrollment_id = $1
+                    "#,
+                )
+                .bind(enrollment_id)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
+        tx.commit().await?;
+        Ok(enrollment)
     }
 
     // =========================================================================
@@ -379,12 +506,29 @@ impl EnrollmentRepository {
     }
 
     /// Updates lesson progress.
+    ///
+    /// Emits `progress.updated` when the status or completion percentage
+    /// changed; position-only saves emit nothing.
     pub async fn update_progress(
         &self,
         progress_id: Uuid,
         data: UpdateLessonProgress,
     ) -> Result<LessonProgress, sqlx::Error> {
-        sqlx::query_as::<_, LessonProgress>(
+        let mut tx = self.pool.begin().await?;
+
+        let (old_status, old_percentage): (LessonProgressStatus, f64) = sqlx::query_as(
+            r#"
+            SELECT status, completion_percentage::FLOAT8
+            FROM enrollments.lesson_progress
+            WHERE progress_id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(progress_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let progress = sqlx::query_as::<_, LessonProgress>(
             r#"
             UPDATE enrollments.lesson_progress
             SET
@@ -409,16 +553,44 @@ impl EnrollmentRepository {
         .bind(data.last_position_seconds)
         .bind(data.completed_at.is_some())
         .bind(data.completed_at.flatten())
-        .fetch_one(&self.pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if progress.status != old_status || progress.completion_percentage != old_percentage {
+            let event = ProgressEvent::Updated {
+                progress_id,
+                enrollment_id: progress.enrollment_id,
+                lesson_id: progress.lesson_id,
+                user_id: progress.user_id,
+                old_status,
+                new_status: progress.status,
+                completion_percentage: progress.completion_percentage,
+                occurred_at: Utc::now(),
+            };
+            self.outbox.publish(&mut *tx, &event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(progress)
     }
 
-    /// Marks a lesson as completed.
+    /// Marks a lesson as completed and emits `progress.lesson_completed`
+    /// the first time.
     pub async fn mark_lesson_completed(
         &self,
         progress_id: Uuid,
+        course_id: Uuid,
     ) -> Result<LessonProgress, sqlx::Error> {
-        sqlx::query_as::<_, LessonProgress>(
+        let mut tx = self.pool.begin().await?;
+
+        let (old_status,): (LessonProgressStatus,) = sqlx::query_as(
+            "SELECT status FROM enrollments.lesson_progress WHERE progress_id = $1 FOR UPDATE",
+        )
+        .bind(progress_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let progress = sqlx::query_as::<_, LessonProgress>(
             r#"
             UPDATE enrollments.lesson_progress
             SET
@@ -435,8 +607,24 @@ impl EnrollmentRepository {
             "#,
         )
         .bind(progress_id)
-        .fetch_one(&self.pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if old_status != LessonProgressStatus::Completed {
+            let event = ProgressEvent::LessonCompleted {
+                progress_id,
+                enrollment_id: progress.enrollment_id,
+                lesson_id: progress.lesson_id,
+                user_id: progress.user_id,
+                course_id,
+                time_spent_seconds: progress.time_spent_seconds,
+                occurred_at: progress.completed_at.unwrap_or_else(Utc::now),
+            };
+            self.outbox.publish(&mut *tx, &event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(progress)
     }
 
     /// Counts completed lessons for an enrollment.
@@ -472,6 +660,238 @@ impl EnrollmentRepository {
         Ok((completed as f64 / total_lessons as f64) * 100.0)
     }
 
+    // =========================================================================
+    // COMPLETION RULES
+    // =========================================================================
+
+    /// Finds the completion rules configured for a course.
+    pub async fn find_completion_rules(
+        &self,
+        course_id: Uuid,
+    ) -> Result<Option<CompletionRules>, sqlx::Error> {
+        sqlx::query_as::<_, CompletionRules>(
+            r#"
+            SELECT
+                course_id, require_all_lessons,
+                min_quiz_average::FLOAT8 AS min_quiz_average,
+                final_assessment_id, created_at, updated_at
+            FROM enrollments.course_completion_rules
+            WHERE course_id = $1
+            "#,
+        )
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Creates or replaces the completion rules of a course.
+    pub async fn upsert_completion_rules(
+        &self,
+        course_id: Uuid,
+        data: &UpsertCompletionRules,
+    ) -> Result<CompletionRules, sqlx::Error> {
+        sqlx::query_as::<_, CompletionRules>(
+            r#"
+            INSERT INTO enrollments.course_completion_rules (
+                course_id, require_all_lessons, min_quiz_average, final_assessment_id
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (course_id) DO UPDATE SET
+                require_all_lessons = EXCLUDED.require_all_lessons,
+                min_quiz_average = EXCLUDED.min_quiz_average,
+                final_assessment_id = EXCLUDED.final_assessment_id,
+                updated_at = NOW()
+            RETURNING
+                course_id, require_all_lessons,
+                min_quiz_average::FLOAT8 AS min_quiz_average,
+                final_assessment_id, created_at, updated_at
+            "#,
+        )
+        .bind(course_id)
+        .bind(data.require_all_lessons)
+        .bind(data.min_quiz_average)
+        .bind(data.final_assessment_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Returns the instructor of a course, or `None` if it does not exist.
+    pub async fn find_course_instructor(&self, course_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let result: Option<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT instructor_id
+            FROM courses.courses
+            WHERE course_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|(instructor_id,)| instructor_id))
+    }
+
+    /// Counts the lessons of a course.
+    pub async fn count_course_lessons(&self, course_id: Uuid) -> Result<i64, sqlx::Error> {
+        let result: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM courses.lessons WHERE course_id = $1",
+        )
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    /// Checks that a quiz belongs to a course.
+    pub async fn quiz_belongs_to_course(&self, quiz_id: Uuid, course_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM assessments.quizzes WHERE quiz_id = $1 AND course_id = $2)",
+        )
+        .bind(quiz_id)
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    /// Average percentage over the published quizzes of a course, taking the
+    /// best graded attempt of each and 0 for quizzes never taken.
+    ///
+    /// Returns `None` if the course has no published quizzes.
+    pub async fn quiz_average(&self, enrollment_id: Uuid, course_id: Uuid) -> Result<Option<f64>, sqlx::Error> {
+        let result: (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT AVG(COALESCE(best.percentage, 0))::FLOAT8
+            FROM assessments.quizzes q
+            LEFT JOIN LATERAL (
+                SELECT MAX(s.score / NULLIF(s.max_score, 0) * 100) AS percentage
+                FROM assessments.quiz_submissions s
+                WHERE s.quiz_id = q.quiz_id
+                  AND s.enrollment_id = $1
+                  AND s.status = 'graded'
+            ) best ON TRUE
+            WHERE q.course_id = $2 AND q.is_published = TRUE
+            "#,
+        )
+        .bind(enrollment_id)
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    /// Checks if the student passed a quiz in any graded attempt.
+    pub async fn has_passed_quiz(&self, enrollment_id: Uuid, quiz_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result: (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM assessments.quiz_submissions
+                WHERE enrollment_id = $1 AND quiz_id = $2
+                  AND status = 'graded' AND passed = TRUE
+            )
+            "#,
+        )
+        .bind(enrollment_id)
+        .bind(quiz_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    // =========================================================================
+    // CERTIFICATES
+    // =========================================================================
+
+    /// Names printed on the certificate of a user for a course.
+    ///
+    /// Returns `None` if the course does not exist.
+    pub async fn find_certificate_names(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+    ) -> Result<Option<CertificateNames>, sqlx::Error> {
+        sqlx::query_as::<_, CertificateNames>(
+            r#"
+            SELECT
+                NULLIF(TRIM(sp.first_name || ' ' || sp.last_name), '') AS student_name,
+                c.title AS course_title,
+                NULLIF(TRIM(ip.first_name || ' ' || ip.last_name), '') AS instructor_name
+            FROM courses.courses c
+            LEFT JOIN users.profiles sp ON sp.user_id = $1
+            LEFT JOIN users.profiles ip ON ip.user_id = c.instructor_id
+            WHERE c.course_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Records the certificate issued for an enrollment and emits
+    /// `enrollment.certificate_issued` the first time.
+    pub async fn record_certificate(
+        &self,
+        enrollment_id: Uuid,
+        certificate_id: Uuid,
+    ) -> Result<Enrollment, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let recorded = sqlx::query_as::<_, Enrollment>(
+            r#"
+            UPDATE enrollments.enrollments
+            SET certificate_issued_at = NOW(), updated_at = NOW()
+            WHERE enrollment_id = $1 AND certificate_issued_at IS NULL
+            RETURNING
+                enrollment_id, user_id, course_id, status,
+                progress_percentage, started_at, completed_at,
+                last_accessed_at, certificate_issued_at,
+                enrollment_source, expires_at, created_at, updated_at
+            "#,
+        )
+        .bind(enrollment_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let enrollment = match recorded {
+            Some(enrollment) => {
+                let event = EnrollmentEvent::CertificateIssued {
+                    enrollment_id,
+                    user_id: enrollment.user_id,
+                    course_id: enrollment.course_id,
+                    certificate_id,
+                    occurred_at: enrollment.certificate_issued_at.unwrap_or_else(Utc::now),
+                };
+                self.outbox.publish(&mut *tx, &event).await?;
+                enrollment
+            }
+            None => {
+                // Already recorded
+                sqlx::query_as::<_, Enrollment>(
+                    r#"
+                    SELECT
+                        enrollment_id, user_id, course_id, status,
+                        progress_percentage, started_at, completed_at,
+                        last_accessed_at, certificate_issued_at,
+                        enrollment_source, expires_at, created_at, updated_at
+                    FROM enrollments.enrollments
+                    WHERE enrollment_id = $1
+                    "#,
+                )
+                .bind(enrollment_id)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
+        tx.commit().await?;
+        Ok(enrollment)
+    }
+
     // =========================================================================
     // STATISTICS QUERIES
     // =========================================================================
@@ -540,3 +960,11 @@ pub struct UserLearningStats {
     pub in_progress: i64,
     pub total_time_seconds: i64,
 }
+
+/// Names printed on a certificate. Missing profiles yield `None`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CertificateNames {
+    pub student_name: Option<String>,
+    pub course_title: String,
+    pub instructor_name: Option<String>,
+}
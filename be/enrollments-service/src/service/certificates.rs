@@ -0,0 +1,132 @@
+//! # Certificate Issuance
+//!
+//! Issues certificates in certificates-service for completed enrollments.
+//!
+//! Completing an enrollment emits `enrollment.completed` through the outbox;
+//! [`CompletionCertificates`] consumes it from `events:enrollment` and issues
+//! the certificate. If certificates-service is down the event stays pending
+//! and is retried, so completion never waits on (or fails because of)
+//! certificate rendering. Issuance is idempotent on both sides:
+//! certificates-service returns the existing certificate for a user and
+//! course, and the enrollment records it only once.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::auth::{ServiceTokens, SERVICE_TOKEN_HEADER};
+use shared::events::{EventError, EventHandler, StreamEvent};
+use uuid::Uuid;
+
+use crate::domain::EnrollmentEvent;
+use crate::service::{EnrollmentError, EnrollmentService};
+
+/// Consumer group reading `events:enrollment` for certificates.
+pub const CERTIFICATES_CONSUMER_GROUP: &str = "enrollments-certificates";
+
+/// Data printed on a certificate.
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateRequest {
+    pub user_id: Uuid,
+    pub course_id: Uuid,
+    pub student_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub completion_date: DateTime<Utc>,
+}
+
+/// Issues certificates.
+#[async_trait]
+pub trait CertificateIssuer: Send + Sync {
+    /// Issues (or returns the existing) certificate and returns its ID.
+    async fn issue(&self, request: &CertificateRequest) -> Result<Uuid, String>;
+}
+
+/// Issues certificates through the certificates-service internal API.
+pub struct CertificatesClient {
+    http: reqwest::Client,
+    base_url: String,
+    tokens: Arc<ServiceTokens>,
+}
+
+impl CertificatesClient {
+    /// `base_url` is the certificates-service root, e.g. `http://certificates-service:8100`.
+    pub fn new(base_url: &str, timeout: Duration, tokens: Arc<ServiceTokens>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            tokens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct IssuedCertificate {
+    certificate_id: Uuid,
+}
+
+#[async_trait]
+impl CertificateIssuer for CertificatesClient {
+    async fn issue(&self, request: &CertificateRequest) -> Result<Uuid, String> {
+        let url = format!("{}/internal/v1/certificates", self.base_url);
+        let token = self.tokens.token().map_err(|e| e.to_string())?;
+
+        let response = self.http
+            .post(&url)
+            .header(SERVICE_TOKEN_HEADER, token)
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("certificates-service returned {}: {}", status, body));
+        }
+
+        let issued: IssuedCertificate = response.json().await.map_err(|e| e.to_string())?;
+        Ok(issued.certificate_id)
+    }
+}
+
+/// Issues the certificate of every completed enrollment.
+pub struct CompletionCertificates {
+    service: EnrollmentService,
+}
+
+impl CompletionCertificates {
+    /// Creates the handler.
+    pub fn new(service: EnrollmentService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl EventHandler for CompletionCertificates {
+    async fn handle(&self, event: &StreamEvent) -> Result<(), EventError> {
+        let enrollment_id = match event.payload_as::<EnrollmentEvent>()? {
+            EnrollmentEvent::Completed { enrollment_id, .. } => enrollment_id,
+            _ => return Ok(()),
+        };
+
+        match self.service.issue_certificate_for(enrollment_id).await {
+            Ok(_) => Ok(()),
+            // Retrying cannot fix these (e.g. refunded meanwhile, missing profile)
+            Err(e @ (EnrollmentError::NotFound
+                | EnrollmentError::CourseNotFound
+                | EnrollmentError::Validation(_))) => {
+                tracing::warn!(%enrollment_id, error = %e, "Certificate not issued");
+                Ok(())
+            }
+            Err(e) => Err(EventError::Handler(e.to_string())),
+        }
+    }
+}
@@ -6,20 +6,23 @@
 //!
 //! - Enrollment creation and management
 //! - Progress tracking and calculation
-//! - Completion detection and certificate issuance
+//! - Completion detection against per-course rules and certificate issuance
 //! - Authorization enforcement
 
 use std::sync::Arc;
+use shared::service_client::EnrollmentAccess;
 use uuid::Uuid;
 
 use crate::domain::{
+    CompletionEvaluation, CompletionProgress, CompletionRules, CompletionStatus,
     Enrollment, EnrollmentStatus, EnrollmentWithProgress,
     LessonProgress, LessonProgressStatus, NewEnrollment, NewLessonProgress,
-    UpdateEnrollment, UpdateLessonProgress,
+    UpdateEnrollment, UpdateLessonProgress, UpsertCompletionRules,
 };
 use crate::repository::{
     EnrollmentRepository, CourseEnrollmentStats, UserLearningStats,
 };
+use crate::service::certificates::{CertificateIssuer, CertificateRequest};
 
 /// Enrollment service errors.
 #[derive(Debug, thiserror::Error)]
@@ -50,21 +53,25 @@ pub enum EnrollmentError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("Certificate service error: {0}")]
+    Certificates(String),
 }
 
 /// Result type for enrollment operations.
 pub type EnrollmentResult<T> = Result<T, EnrollmentError>;
 
 /// Service for enrollment business logic.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct EnrollmentService {
     repository: Arc<EnrollmentRepository>,
+    certificates: Arc<dyn CertificateIssuer>,
 }
 
 impl EnrollmentService {
     /// Creates a new enrollment service.
-    pub fn new(repository: Arc<EnrollmentRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<EnrollmentRepository>, certificates: Arc<dyn CertificateIssuer>) -> Self {
+        Self { repository, certificates }
     }
 
     // =========================================================================
@@ -155,6 +162,22 @@ impl EnrollmentService {
             .map_err(Into::into)
     }
 
+    /// Returns whether a user's enrollment grants access to a course. Used
+    /// by other services through the internal API.
+    pub async fn enrollment_access(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+    ) -> EnrollmentResult<EnrollmentAccess> {
+        let enrollment = self.repository.find_by_user_and_course(user_id, course_id).await?;
+
+        Ok(EnrollmentAccess {
+            has_access: enrollment.as_ref().is_some_and(Enrollment::has_access),
+            enrollment_id: enrollment.as_ref().map(|e| e.enrollment_id),
+            status: enrollment.map(|e| e.status.to_string()),
+        })
+    }
+
     /// Enrolls a user in a course.
     pub async fn enroll_user(
         &self,
@@ -178,10 +201,9 @@ impl EnrollmentService {
             expires_at,
         };
 
+        // Emits EnrollmentEvent::Created through the outbox
         let enrollment = self.repository.create(data).await?;
 
-        // TODO: Emit EnrollmentEvent::Created
-
         Ok(enrollment)
     }
 
@@ -200,14 +222,10 @@ impl EnrollmentService {
             return Err(EnrollmentError::Unauthorized);
         }
 
-        let update = UpdateEnrollment {
-            status: Some(status),
-            ..Default::default()
-        };
-
-        let updated = self.repository.update(enrollment.enrollment_id, update).await?;
-
-        // TODO: Emit EnrollmentEvent::StatusChanged
+        // Emits EnrollmentEvent::StatusChanged through the outbox
+        let updated = self.repository
+            .update_status(enrollment.enrollment_id, status)
+            .await?;
 
         Ok(updated)
     }
@@ -320,7 +338,12 @@ impl EnrollmentService {
         // Update last accessed
         self.repository.update_last_accessed(enrollment_id).await?;
 
-        // TODO: Emit ProgressEvent::Updated
+        // Reaching 100% completes the lesson, which may complete the course
+        if progress.status != LessonProgressStatus::Completed
+            && updated_progress.status == LessonProgressStatus::Completed
+        {
+            self.check_and_complete_course(&enrollment).await?;
+        }
 
         Ok(updated_progress)
     }
@@ -331,7 +354,6 @@ impl EnrollmentService {
         enrollment_id: Uuid,
         lesson_id: Uuid,
         user_id: Uuid,
-        total_lessons_in_course: i64,
     ) -> EnrollmentResult<(LessonProgress, bool)> {
         // Verify enrollment
         let enrollment = self.repository
@@ -351,19 +373,15 @@ impl EnrollmentService {
             }
         };
 
-        // Mark completed
+        // Mark completed (emits ProgressEvent::LessonCompleted)
         let updated = self.repository
-            .mark_lesson_completed(progress.progress_id)
+            .mark_lesson_completed(progress.progress_id, enrollment.course_id)
             .await?;
 
-        // TODO: Emit ProgressEvent::LessonCompleted
-
         // Check if course is now complete
-        let course_completed = self
-            .check_and_complete_course(enrollment_id, total_lessons_in_course)
-            .await?;
+        let status = self.check_and_complete_course(&enrollment).await?;
 
-        Ok((updated, course_completed))
+        Ok((updated, status.completed))
     }
 
     /// Saves playback position for a lesson.
@@ -423,42 +441,142 @@ impl EnrollmentService {
     // COMPLETION & CERTIFICATES
     // =========================================================================
 
-    /// Checks if course is complete and marks it if so.
-    async fn check_and_complete_course(
+    /// Gets the completion rules of a course (defaults if none are set).
+    pub async fn get_completion_rules(&self, course_id: Uuid) -> EnrollmentResult<CompletionRules> {
+        let rules = self.repository
+            .find_completion_rules(course_id)
+            .await?
+            .unwrap_or_else(|| CompletionRules::default_for(course_id));
+
+        Ok(rules)
+    }
+
+    /// Sets the completion rules of a course (admin or course instructor).
+    ///
+    /// Applies to enrollments checked from now on; completed enrollments
+    /// are not affected.
+    pub async fn update_completion_rules(
+        &self,
+        course_id: Uuid,
+        data: UpsertCompletionRules,
+        requesting_user_id: Uuid,
+        is_admin: bool,
+    ) -> EnrollmentResult<CompletionRules> {
+        let instructor_id = self.repository
+            .find_course_instructor(course_id)
+            .await?
+            .ok_or(EnrollmentError::CourseNotFound)?;
+
+        if instructor_id != requesting_user_id && !is_admin {
+            return Err(EnrollmentError::Unauthorized);
+        }
+
+        if !data.has_requirements() {
+            return Err(EnrollmentError::Validation(
+                "At least one completion requirement is needed".into()
+            ));
+        }
+
+        if let Some(quiz_id) = data.final_assessment_id {
+            if !self.repository.quiz_belongs_to_course(quiz_id, course_id).await? {
+                return Err(EnrollmentError::Validation(
+                    "Final assessment must be a quiz of this course".into()
+                ));
+            }
+        }
+
+        self.repository
+            .upsert_completion_rules(course_id, &data)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Checks an enrollment against its course rules and completes it if
+    /// they are met. Call it after grades change (e.g. the final quiz was
+    /// passed after the last lesson).
+    pub async fn evaluate_completion(
         &self,
         enrollment_id: Uuid,
-        total_lessons: i64,
-    ) -> EnrollmentResult<bool> {
-        let completed_count = self.repository
-            .count_completed_lessons(enrollment_id)
-            .await?;
+        requesting_user_id: Uuid,
+        is_admin: bool,
+    ) -> EnrollmentResult<CompletionStatus> {
+        let enrollment = self.get_enrollment(enrollment_id, requesting_user_id, is_admin).await?;
 
-        if completed_count >= total_lessons && total_lessons > 0 {
-            // Mark enrollment as completed
-            self.repository.mark_completed(enrollment_id).await?;
+        self.check_and_complete_course(&enrollment).await
+    }
 
-            // TODO: Emit EnrollmentEvent::Completed
-            // TODO: Trigger certificate generation
+    /// Evaluates the course rules and marks the enrollment completed when
+    /// they are met (emits EnrollmentEvent::Completed, which triggers the
+    /// certificate). Otherwise updates the progress percentage.
+    async fn check_and_complete_course(&self, enrollment: &Enrollment) -> EnrollmentResult<CompletionStatus> {
+        let rules = self.get_completion_rules(enrollment.course_id).await?;
+        let progress = self.completion_progress(enrollment, &rules).await?;
+        let evaluation = CompletionEvaluation::evaluate(&rules, &progress);
+
+        let completed = match enrollment.status {
+            EnrollmentStatus::Completed => true,
+            EnrollmentStatus::Active if evaluation.is_satisfied() => {
+                self.repository.mark_completed(enrollment.enrollment_id).await?;
+                true
+            }
+            _ => {
+                // Update progress percentage
+                let progress_pct = self.repository
+                    .calculate_progress(enrollment.enrollment_id, progress.total_lessons)
+                    .await?;
+
+                let update = UpdateEnrollment {
+                    progress_percentage: Some(progress_pct.min(100.0)),
+                    ..Default::default()
+                };
+
+                self.repository.update(enrollment.enrollment_id, update).await?;
+                false
+            }
+        };
 
-            Ok(true)
-        } else {
-            // Update progress percentage
-            let progress_pct = self.repository
-                .calculate_progress(enrollment_id, total_lessons)
-                .await?;
+        Ok(CompletionStatus {
+            rules,
+            progress,
+            evaluation,
+            completed,
+        })
+    }
 
-            let update = UpdateEnrollment {
-                progress_percentage: Some(progress_pct as f64),
-                ..Default::default()
-            };
+    /// Gathers what the student achieved for the rules that are set.
+    async fn completion_progress(
+        &self,
+        enrollment: &Enrollment,
+        rules: &CompletionRules,
+    ) -> EnrollmentResult<CompletionProgress> {
+        let total_lessons = self.repository.count_course_lessons(enrollment.course_id).await?;
+        let completed_lessons = self.repository
+            .count_completed_lessons(enrollment.enrollment_id)
+            .await?;
 
-            self.repository.update(enrollment_id, update).await?;
+        let quiz_average = match rules.min_quiz_average {
+            Some(_) => self.repository
+                .quiz_average(enrollment.enrollment_id, enrollment.course_id)
+                .await?,
+            None => None,
+        };
 
-            Ok(false)
-        }
+        let final_assessment_passed = match rules.final_assessment_id {
+            Some(quiz_id) => self.repository
+                .has_passed_quiz(enrollment.enrollment_id, quiz_id)
+                .await?,
+            None => false,
+        };
+
+        Ok(CompletionProgress {
+            completed_lessons,
+            total_lessons,
+            quiz_average,
+            final_assessment_passed,
+        })
     }
 
-    /// Issues a certificate for completed enrollment.
+    /// Issues a certificate for completed enrollment (admin only).
     pub async fn issue_certificate(
         &self,
         enrollment_id: Uuid,
@@ -468,27 +586,54 @@ impl EnrollmentService {
             return Err(EnrollmentError::Unauthorized);
         }
 
+        self.issue_certificate_for(enrollment_id).await
+    }
+
+    /// Issues the certificate of a completed enrollment in
+    /// certificates-service and records it (emits
+    /// EnrollmentEvent::CertificateIssued). Does nothing if it was already
+    /// issued.
+    pub async fn issue_certificate_for(&self, enrollment_id: Uuid) -> EnrollmentResult<Enrollment> {
         let enrollment = self.repository
             .find_by_id(enrollment_id)
             .await?
             .ok_or(EnrollmentError::NotFound)?;
 
+        if enrollment.certificate_issued_at.is_some() {
+            return Ok(enrollment);
+        }
+
         if enrollment.status != EnrollmentStatus::Completed {
             return Err(EnrollmentError::Validation(
                 "Cannot issue certificate for incomplete enrollment".into()
             ));
         }
 
-        let update = UpdateEnrollment {
-            certificate_issued_at: Some(Some(chrono::Utc::now())),
-            ..Default::default()
+        let names = self.repository
+            .find_certificate_names(enrollment.user_id, enrollment.course_id)
+            .await?
+            .ok_or(EnrollmentError::CourseNotFound)?;
+
+        let request = CertificateRequest {
+            user_id: enrollment.user_id,
+            course_id: enrollment.course_id,
+            student_name: names.student_name
+                .ok_or_else(|| EnrollmentError::Validation("Student profile not found".into()))?,
+            course_title: names.course_title,
+            instructor_name: names.instructor_name
+                .ok_or_else(|| EnrollmentError::Validation("Instructor profile not found".into()))?,
+            completion_date: enrollment.completed_at.unwrap_or_else(chrono::Utc::now),
         };
 
-        let updated = self.repository.update(enrollment_id, update).await?;
-
-        // TODO: Emit EnrollmentEvent::CertificateIssued
+        let certificate_id = self.certificates
+            .issue(&request)
+            .await
+            .map_err(EnrollmentError::Certificates)?;
 
-        Ok(updated)
+        self.repository
+            .record_certificate(enrollment_id, certificate_id)
+            .await
+            .map_err(Into::into)
     }
 
     // =========================================================================
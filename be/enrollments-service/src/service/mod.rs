@@ -2,6 +2,7 @@
 //!
 //! Business logic for enrollments and progress tracking.
 
+pub mod certificates;
 pub mod enrollment_service;
 
 pub use enrollment_service::{EnrollmentError, EnrollmentResult, EnrollmentService};
@@ -13,6 +13,7 @@ use crate::api::{
     QuizStatsResponse,
 };
 use crate::domain::{GradeError, GradeFilter, ExportFormat};
+use crate::service::export_service::Export;
 
 /// Extract user ID from request headers (JWT claim).
 fn extract_user_id(req: &HttpRequest) -> Result<Uuid, GradeError> {
@@ -46,6 +47,20 @@ fn error_response(e: GradeError) -> HttpResponse {
     e.error_response()
 }
 
+/// Return an export as a file download.
+fn export_response(export: Export) -> HttpResponse {
+    match export {
+        Export::Document(export) => HttpResponse::Ok()
+            .content_type(export.content_type.as_str())
+            .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", export.filename)))
+            .body(export.content.unwrap_or_default()),
+        Export::Stream(export) => HttpResponse::Ok()
+            .content_type(export.content_type.as_str())
+            .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", export.filename)))
+            .streaming(export.stream),
+    }
+}
+
 // =============================================================================
 // HEALTH CHECK
 // =============================================================================
@@ -178,13 +193,7 @@ pub async fn export_transcript(
     let format = query.format.unwrap_or(ExportFormat::Csv);
 
     match state.export_service.export_transcript(user_id, format).await {
-        Ok(export) => {
-            // Return file directly
-            HttpResponse::Ok()
-                .content_type(export.content_type.as_str())
-                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", export.filename)))
-                .body(export.content.unwrap_or_default())
-        }
+        Ok(export) => export_response(export),
         Err(e) => error_response(e),
     }
 }
@@ -260,7 +269,7 @@ pub async fn get_quiz_stats(
 }
 
 /// GET /api/v1/stats/courses/{course_id}/export
-/// Export all grades for a course as CSV (instructor only).
+/// Export all grades for a course as CSV, JSON, or PDF (instructor only).
 pub async fn export_course_grades(
     req: HttpRequest,
     state: web::Data<AppState>,
@@ -287,12 +296,7 @@ pub async fn export_course_grades(
     let format = query.format.unwrap_or(ExportFormat::Csv);
 
     match state.export_service.export_course_grades(course_id, format).await {
-        Ok(export) => {
-            HttpResponse::Ok()
-                .content_type(export.content_type.as_str())
-                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", export.filename)))
-                .body(export.content.unwrap_or_default())
-        }
+        Ok(export) => export_response(export),
         Err(e) => error_response(e),
     }
 }
@@ -353,12 +357,7 @@ pub async fn admin_export_course_grades(
     let format = query.format.unwrap_or(ExportFormat::Csv);
 
     match state.export_service.export_course_grades(course_id, format).await {
-        Ok(export) => {
-            HttpResponse::Ok()
-                .content_type(export.content_type.as_str())
-                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", export.filename)))
-                .body(export.content.unwrap_or_default())
-        }
+        Ok(export) => export_response(export),
         Err(e) => error_response(e),
     }
 }
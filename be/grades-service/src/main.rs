@@ -8,7 +8,7 @@
 //! - Student grade summaries by course
 //! - Course-wide grade statistics (for instructors)
 //! - Academic transcript generation
-//! - Grade export (CSV, JSON)
+//! - Grade export (CSV, JSON, streamed PDF)
 //! - Grade trend analytics
 //!
 //! ## Port: 8094
@@ -80,6 +80,7 @@ async fn main() -> std::io::Result<()> {
     let export_service = Arc::new(service::ExportService::new(
         grade_repo.clone(),
         transcript_repo.clone(),
+        transcript_service.clone(),
     ));
 
     // Create app state
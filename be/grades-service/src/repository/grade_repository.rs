@@ -289,6 +289,79 @@ impl GradeRepository {
 
         Ok(grades)
     }
+
+    /// Get the title of a course.
+    pub async fn get_course_title(&self, course_id: Uuid) -> Result<String, GradeError> {
+        let title: Option<(String,)> = sqlx::query_as(
+            "SELECT title FROM courses.courses WHERE course_id = $1"
+        )
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        title
+            .map(|(title,)| title)
+            .ok_or_else(|| GradeError::NotFound(format!("Course {} not found", course_id)))
+    }
+
+    /// Count the graded submissions of a course.
+    pub async fn count_course_grades(&self, course_id: Uuid) -> Result<i64, GradeError> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM assessments.quiz_submissions qs
+            JOIN assessments.quizzes q ON qs.quiz_id = q.quiz_id
+            JOIN auth.users u ON qs.user_id = u.user_id
+            WHERE q.course_id = $1
+            "#
+        )
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    /// Get one page of a course's grades, in gradebook order.
+    pub async fn get_course_grades_page(
+        &self,
+        course_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<StudentGradeRow>, GradeError> {
+        let grades = sqlx::query_as::<_, StudentGradeRow>(
+            r#"
+            SELECT
+                qs.user_id,
+                u.first_name || ' ' || u.last_name as student_name,
+                u.email as student_email,
+                qs.submission_id,
+                q.title as quiz_title,
+                qs.score,
+                qs.max_score,
+                CASE
+                    WHEN qs.max_score > 0 THEN (qs.score / qs.max_score * 100)::DECIMAL(5,2)
+                    ELSE 0
+                END as percentage,
+                qs.passed,
+                qs.status,
+                qs.submitted_at
+            FROM assessments.quiz_submissions qs
+            JOIN assessments.quizzes q ON qs.quiz_id = q.quiz_id
+            JOIN auth.users u ON qs.user_id = u.user_id
+            WHERE q.course_id = $1
+            ORDER BY u.last_name, u.first_name, q.title, qs.submission_id
+            LIMIT $2 OFFSET $3
+            "#
+        )
+        .bind(course_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(grades)
+    }
 }
 
 /// Course info for aggregation.
@@ -6,6 +6,7 @@ pub mod grade_service;
 pub mod transcript_service;
 pub mod stats_service;
 pub mod export_service;
+pub mod pdf_writer;
 
 pub use grade_service::GradeService;
 pub use transcript_service::TranscriptService;
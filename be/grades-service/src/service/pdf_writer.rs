@@ -0,0 +1,354 @@
+//! # PDF Writer
+//!
+//! Minimal streaming PDF writer for exports. Each page is serialized as
+//! soon as it is laid out, so large gradebooks never sit in memory as a
+//! whole: `begin` emits the header, `add_page` one chunk per page and
+//! `finish` the page tree, cross-reference table and trailer.
+//!
+//! Text uses the standard Helvetica fonts (WinAnsi encoding); coordinates
+//! are in points from the bottom-left corner.
+
+use std::fmt::Write as _;
+
+/// Object numbers reserved by `begin`.
+const CATALOG_ID: usize = 1;
+const PAGES_ID: usize = 2;
+const FONT_REGULAR_ID: usize = 3;
+const FONT_BOLD_ID: usize = 4;
+const INFO_ID: usize = 5;
+
+/// A4 portrait size in points.
+pub const A4_PORTRAIT: (f32, f32) = (595.0, 842.0);
+/// A4 landscape size in points.
+pub const A4_LANDSCAPE: (f32, f32) = (842.0, 595.0);
+
+/// Serializes a PDF document incrementally.
+pub struct PdfStreamWriter {
+    /// Bytes emitted so far
+    written: usize,
+    /// Byte offset of every object, by object number
+    offsets: Vec<Option<usize>>,
+    pages: Vec<usize>,
+}
+
+impl Default for PdfStreamWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfStreamWriter {
+    pub fn new() -> Self {
+        Self {
+            written: 0,
+            offsets: vec![None; INFO_ID + 1],
+            pages: Vec::new(),
+        }
+    }
+
+    /// Header, catalog, fonts and document info.
+    pub fn begin(&mut self, title: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.emit_raw(&mut out, b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+        self.emit_object(&mut out, CATALOG_ID, format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES_ID).as_bytes());
+        self.emit_object(
+            &mut out,
+            FONT_REGULAR_ID,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        );
+        self.emit_object(
+            &mut out,
+            FONT_BOLD_ID,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+        );
+
+        let mut info = b"<< /Title ".to_vec();
+        info.extend(pdf_string(title));
+        info.extend(b" /Producer (ACC LMS) >>");
+        self.emit_object(&mut out, INFO_ID, &info);
+
+        out
+    }
+
+    /// Serializes a page (content stream and page object).
+    pub fn add_page(&mut self, page: PdfPage) -> Vec<u8> {
+        let mut out = Vec::new();
+        let content_id = self.reserve();
+        let page_id = self.reserve();
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+        stream.extend(&page.content);
+        stream.extend(b"\nendstream");
+        self.emit_object(&mut out, content_id, &stream);
+
+        let page_object = format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Contents {} 0 R \
+             /Resources << /Font << /F1 {} 0 R /F2 {} 0 R >> >> >>",
+            PAGES_ID, page.width, page.height, content_id, FONT_REGULAR_ID, FONT_BOLD_ID
+        );
+        self.emit_object(&mut out, page_id, page_object.as_bytes());
+        self.pages.push(page_id);
+
+        out
+    }
+
+    /// Page tree, cross-reference table and trailer.
+    pub fn finish(mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        let kids: Vec<String> = self.pages.iter().map(|id| format!("{} 0 R", id)).collect();
+        let pages = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len());
+        self.emit_object(&mut out, PAGES_ID, pages.as_bytes());
+
+        let xref_offset = self.written;
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len());
+        for offset in self.offsets.iter().skip(1) {
+            let _ = writeln!(xref, "{:010} 00000 n ", offset.unwrap_or(0));
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len(),
+            CATALOG_ID,
+            INFO_ID,
+            xref_offset
+        );
+        self.emit_raw(&mut out, xref.as_bytes());
+
+        out
+    }
+
+    fn reserve(&mut self) -> usize {
+        self.offsets.push(None);
+        self.offsets.len() - 1
+    }
+
+    fn emit_object(&mut self, out: &mut Vec<u8>, id: usize, body: &[u8]) {
+        self.offsets[id] = Some(self.written);
+        self.emit_raw(out, format!("{} 0 obj\n", id).as_bytes());
+        self.emit_raw(out, body);
+        self.emit_raw(out, b"\nendobj\n");
+    }
+
+    fn emit_raw(&mut self, out: &mut Vec<u8>, bytes: &[u8]) {
+        self.written += bytes.len();
+        out.extend_from_slice(bytes);
+    }
+}
+
+// =============================================================================
+// PAGES
+// =============================================================================
+
+/// Horizontal text alignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Drawing operations of one page.
+pub struct PdfPage {
+    width: f32,
+    height: f32,
+    content: Vec<u8>,
+}
+
+impl PdfPage {
+    pub fn new((width, height): (f32, f32)) -> Self {
+        Self {
+            width,
+            height,
+            content: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Draws text with its baseline at `y`, aligned relative to `x`.
+    pub fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, align: Align, text: &str) {
+        let width = text_width(text, size, bold);
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x - width / 2.0,
+            Align::Right => x - width,
+        };
+        let font = if bold { "F2" } else { "F1" };
+
+        self.content.extend(format!("BT /{} {} Tf {:.2} {:.2} Td ", font, size, x, y).as_bytes());
+        self.content.extend(pdf_string(text));
+        self.content.extend(b" Tj ET\n");
+    }
+
+    /// Draws text cut with an ellipsis to fit `max_width`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_fit(&mut self, x: f32, y: f32, size: f32, bold: bool, align: Align, max_width: f32, text: &str) {
+        let fitted = fit_text(text, size, bold, max_width);
+        self.text(x, y, size, bold, align, &fitted);
+    }
+
+    /// Sets the fill color (also used by text).
+    pub fn fill_color(&mut self, (r, g, b): (f32, f32, f32)) {
+        self.content.extend(format!("{:.3} {:.3} {:.3} rg\n", r, g, b).as_bytes());
+    }
+
+    /// Sets the stroke color.
+    pub fn stroke_color(&mut self, (r, g, b): (f32, f32, f32)) {
+        self.content.extend(format!("{:.3} {:.3} {:.3} RG\n", r, g, b).as_bytes());
+    }
+
+    /// Fills a rectangle with the current fill color.
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.content.extend(format!("{:.2} {:.2} {:.2} {:.2} re f\n", x, y, width, height).as_bytes());
+    }
+
+    /// Strokes a line with the current stroke color.
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32) {
+        self.content.extend(
+            format!("{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n", thickness, x1, y1, x2, y2).as_bytes(),
+        );
+    }
+}
+
+// =============================================================================
+// TEXT
+// =============================================================================
+
+/// Helvetica advance widths (1/1000 em) for ASCII 32..=126.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold advance widths (1/1000 em) for ASCII 32..=126.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Width of a text in points.
+pub fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let widths = if bold { &HELVETICA_BOLD_WIDTHS } else { &HELVETICA_WIDTHS };
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => u32::from(widths[(code - 32) as usize]),
+            // Accented Latin letters are close to the average lowercase width
+            _ => 556,
+        })
+        .sum();
+
+    units as f32 * size / 1000.0
+}
+
+/// Cuts a text with an ellipsis so it fits `max_width`.
+pub fn fit_text(text: &str, size: f32, bold: bool, max_width: f32) -> String {
+    if text_width(text, size, bold) <= max_width {
+        return text.to_string();
+    }
+
+    let mut fitted: String = text.to_string();
+    while !fitted.is_empty() && text_width(&format!("{}...", fitted), size, bold) > max_width {
+        fitted.pop();
+    }
+    format!("{}...", fitted.trim_end())
+}
+
+/// Encodes a PDF literal string in WinAnsi, escaping delimiters.
+/// Characters outside Latin-1 become `?`.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for c in text.chars() {
+        let byte = match c as u32 {
+            code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+            _ => b'?',
+        };
+        if matches!(byte, b'(' | b')' | b'\\') {
+            out.push(b'\\');
+        }
+        out.push(byte);
+    }
+    out.push(b')');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(pages: usize) -> Vec<u8> {
+        let mut writer = PdfStreamWriter::new();
+        let mut pdf = writer.begin("Transcript (test)");
+        for i in 0..pages {
+            let mut page = PdfPage::new(A4_PORTRAIT);
+            page.text(40.0, 800.0, 12.0, i % 2 == 0, Align::Left, "Hello");
+            pdf.extend(writer.add_page(page));
+        }
+        pdf.extend(writer.finish());
+        pdf
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let pdf = render(3);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("/Title (Transcript \\(test\\))"));
+    }
+
+    #[test]
+    fn test_xref_offsets_point_at_objects() {
+        let pdf = render(2);
+        // Everything after the binary comment in the header is ASCII
+        let tail = pdf.iter().position(|&b| b == b'\n').unwrap() + 1;
+        let binary_line = pdf[tail..].iter().position(|&b| b == b'\n').unwrap() + 1;
+        let body_start = tail + binary_line;
+        let text = std::str::from_utf8(&pdf[body_start..]).unwrap();
+
+        let start = text.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let xref_offset: usize = text[start..].lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref_offset..].starts_with(b"xref"));
+
+        let xref = std::str::from_utf8(&pdf[xref_offset..]).unwrap();
+        let entries = xref.lines().skip(3).take_while(|l| !l.starts_with("trailer"));
+        for (id, entry) in (1..).zip(entries) {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", id).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_fit_text() {
+        assert_eq!(fit_text("Short", 10.0, false, 100.0), "Short");
+
+        let fitted = fit_text("A very long course title that cannot fit", 10.0, false, 80.0);
+        assert!(fitted.ends_with("..."));
+        assert!(text_width(&fitted, 10.0, false) <= 80.0);
+    }
+
+    #[test]
+    fn test_pdf_string_escapes_and_encodes() {
+        assert_eq!(pdf_string("a(b)\\"), b"(a\\(b\\)\\\\)".to_vec());
+        assert_eq!(pdf_string("Pérez"), b"(P\xE9rez)".to_vec());
+        assert_eq!(pdf_string("日本"), b"(??)".to_vec());
+    }
+}
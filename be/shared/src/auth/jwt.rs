@@ -53,11 +53,24 @@
 //!
 //! ## Security Notes
 //!
-//! - We use **HS256** (HMAC-SHA256) for signing
-//! - Secret key must be at least 32 characters
+//! - auth-service signs with **RS256** or **EdDSA** keys named by `kid`;
+//!   other services only hold the public keys (see [`crate::auth::jwks`])
+//! - Without signing keys we fall back to **HS256** with `JWT_SECRET`
+//!   (at least 32 characters)
+//! - The validation algorithm comes from our key, never from the token header
 //! - Tokens are validated for: signature, expiration, issuer, audience
 //! - The `jti` claim enables token blacklisting (for logout)
 //!
+//! ## Modes
+//!
+//! | Constructor | Signs | Validates |
+//! |-------------|-------|-----------|
+//! | [`JwtService::new`] | HS256 secret | HS256 secret |
+//! | [`JwtService::with_signing_keys`] | Active key | Any published key (+ HS256 until `legacy_hs256_until`) |
+//! | [`JwtService::verifier`] | - | Keys fetched from the JWKS URL |
+//!
+//! [`JwtService::from_config`] picks the mode from [`JwtConfig`].
+//!
 //! ## Usage Example
 //!
 //! ```rust,ignore
@@ -66,7 +79,7 @@
 //!
 //! // Create service with configuration
 //! let config = JwtConfig { /* ... */ };
-//! let jwt_service = JwtService::new(config);
+//! let jwt_service = JwtService::from_config(config).await?;
 //!
 //! // Generate tokens for a user
 //! let tokens = jwt_service.generate_tokens(
@@ -85,14 +98,22 @@
 //! ## Related Documentation
 //!
 //! - [`crate::config::JwtConfig`] - Configuration options
+//! - [`crate::auth::jwks`] - Signing keys, rotation and the JWKS endpoint
 //! - [`crate::auth::middleware`] - Request authentication
 //! - [`_docs/business/functional-requirements.md`] - RF-AUTH-001
 
+use crate::auth::jwks::{KeyError, KeyStore, SigningKeys};
 use crate::config::JwtConfig;
 use crate::errors::ApiError;
-use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, TokenData,
+    Validation,
+};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // =============================================================================
@@ -222,7 +243,8 @@ pub struct TokenPair {
 /// ## Thread Safety
 ///
 /// This type is `Clone` and can be wrapped in `Arc` for sharing across
-/// async tasks. The underlying keys are immutable after construction.
+/// async tasks. Signing keys are immutable after construction; in verifier
+/// mode the public keys are refreshed in the background.
 ///
 /// ## Example
 ///
@@ -235,10 +257,16 @@ pub struct TokenPair {
 /// ```
 #[derive(Clone)]
 pub struct JwtService {
-    /// Key for signing tokens (kept secret)
-    encoding_key: EncodingKey,
-    /// Key for verifying signatures
-    decoding_key: DecodingKey,
+    /// Header and key for signing tokens (`None` in verifier mode)
+    signer: Option<(Header, EncodingKey)>,
+    /// HS256 key for tokens without `kid`
+    secret_key: Option<DecodingKey>,
+    /// When HS256 tokens stop being accepted (`None`: no end date)
+    secret_until: Option<DateTime<Utc>>,
+    /// Public keys for tokens with `kid`
+    keys: Option<Arc<KeyStore>>,
+    /// Key set published on `/.well-known/jwks.json`
+    jwks: JwkSet,
     /// Configuration with TTLs, issuer, audience
     config: JwtConfig,
 }
@@ -246,6 +274,8 @@ pub struct JwtService {
 impl JwtService {
     /// Creates a new JWT service with the given configuration.
     ///
+    /// Signs and validates with the HS256 shared secret.
+    ///
     /// ## Parameters
     ///
     /// - `config`: JWT configuration including secret key and TTLs
@@ -261,12 +291,95 @@ impl JwtService {
         let decoding_key = DecodingKey::from_secret(config.secret.as_bytes());
 
         Self {
-            encoding_key,
-            decoding_key,
+            signer: Some((Header::default(), encoding_key)),
+            secret_key: Some(decoding_key),
+            secret_until: None,
+            keys: None,
+            jwks: JwkSet { keys: Vec::new() },
+            config,
+        }
+    }
+
+    /// Creates the JWT service from configuration, picking the mode:
+    ///
+    /// 1. `signing_keys_dir` set: [`Self::with_signing_keys`] (auth-service)
+    /// 2. `jwks_url` set: [`Self::verifier`]
+    /// 3. Otherwise: [`Self::new`] (HS256)
+    ///
+    /// ## Errors
+    ///
+    /// Returns `KeyError` if the keys cannot be loaded or the first JWKS
+    /// fetch fails.
+    pub async fn from_config(config: JwtConfig) -> Result<Self, KeyError> {
+        // Empty values count as unset, so compose files can pass them through
+        let set = |value: &Option<String>| value.clone().filter(|v| !v.is_empty());
+
+        if let Some(dir) = set(&config.signing_keys_dir) {
+            let active_kid = set(&config.active_kid);
+            let keys = SigningKeys::load_dir(Path::new(&dir), active_kid.as_deref())?;
+            return Ok(Self::with_signing_keys(config, keys));
+        }
+        if let Some(url) = set(&config.jwks_url) {
+            return Self::verifier(config, &url).await;
+        }
+        Ok(Self::new(config))
+    }
+
+    /// Creates a service that signs with the active asymmetric key.
+    ///
+    /// Tokens signed by any of the keys validate, so tokens issued before a
+    /// rotation stay valid. HS256 tokens without `kid`, from sessions issued
+    /// before the switch, are accepted only if `config.secret` is set and
+    /// `config.legacy_hs256_until` has not passed.
+    pub fn with_signing_keys(config: JwtConfig, keys: SigningKeys) -> Self {
+        let active = keys.active();
+        let mut header = Header::new(active.algorithm());
+        header.kid = Some(active.kid().to_string());
+
+        let secret_until = config.legacy_hs256_until;
+        let secret_key = (!config.secret.is_empty() && secret_until.is_some())
+            .then(|| DecodingKey::from_secret(config.secret.as_bytes()));
+
+        Self {
+            signer: Some((header, active.encoding_key().clone())),
+            secret_key,
+            secret_until,
+            keys: Some(Arc::new(KeyStore::local(&keys))),
+            jwks: keys.jwks(),
             config,
         }
     }
 
+    /// Creates a validate-only service that trusts the keys published on
+    /// `jwks_url`, refreshed every `config.jwks_refresh_seconds`.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `KeyError::Fetch` if the first fetch fails.
+    pub async fn verifier(config: JwtConfig, jwks_url: &str) -> Result<Self, KeyError> {
+        let keys = KeyStore::remote(jwks_url).await?;
+        keys.spawn_refresh(std::time::Duration::from_secs(config.jwks_refresh_seconds));
+
+        Ok(Self {
+            signer: None,
+            secret_key: None,
+            secret_until: None,
+            keys: Some(keys),
+            jwks: JwkSet { keys: Vec::new() },
+            config,
+        })
+    }
+
+    /// Public keys to serve on `/.well-known/jwks.json`.
+    ///
+    /// Empty unless the service signs with asymmetric keys; the HS256
+    /// secret is never published.
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+
     /// Generates a token pair (access + refresh) for a user.
     ///
     /// This is called after successful authentication (login).
@@ -284,7 +397,8 @@ impl JwtService {
     /// ## Errors
     ///
     /// Returns `ApiError::InternalError` if token encoding fails
-    /// (which shouldn't happen with valid configuration).
+    /// (which shouldn't happen with valid configuration) or the service is
+    /// a verifier.
     pub fn generate_tokens(
         &self,
         user_id: Uuid,
@@ -333,8 +447,15 @@ impl JwtService {
             token_type,
         };
 
-        // Encode with HS256 (default header)
-        encode(&Header::default(), &claims, &self.encoding_key)
+        let (header, key) = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| ApiError::InternalError {
+                message: "JWT verifier cannot sign tokens".to_string(),
+            })?;
+
+        // Header carries the algorithm and, for asymmetric keys, the kid
+        encode(header, &claims, key)
             .map_err(|e| ApiError::InternalError {
                 message: format!("Failed to generate token: {}", e),
             })
@@ -390,13 +511,35 @@ impl JwtService {
     ///
     /// Internal method that handles common validation.
     fn decode_token(&self, token: &str) -> Result<Claims, ApiError> {
+        // Pick the key by kid; the algorithm is the key's, not the header's
+        let header = decode_header(token).map_err(|_| ApiError::InvalidToken)?;
+        let (algorithm, decoding_key) = match header.kid {
+            Some(kid) => {
+                let key = self
+                    .keys
+                    .as_ref()
+                    .and_then(|keys| keys.get(&kid))
+                    .ok_or(ApiError::InvalidToken)?;
+                (key.algorithm, key.key)
+            }
+            None => {
+                if self.secret_until.is_some_and(|until| Utc::now() >= until) {
+                    return Err(ApiError::InvalidToken);
+                }
+                (
+                    Algorithm::HS256,
+                    self.secret_key.clone().ok_or(ApiError::InvalidToken)?,
+                )
+            }
+        };
+
         // Configure validation rules
-        let mut validation = Validation::default();
+        let mut validation = Validation::new(algorithm);
         validation.set_issuer(&[&self.config.issuer]);
         validation.set_audience(&[&self.config.audience]);
 
         // Decode and validate
-        let token_data: TokenData<Claims> = decode(token, &self.decoding_key, &validation)
+        let token_data: TokenData<Claims> = decode(token, &decoding_key, &validation)
             .map_err(|e| match e.kind() {
                 // Map specific JWT errors to our error types
                 jsonwebtoken::errors::ErrorKind::ExpiredSignature => ApiError::TokenExpired,
@@ -432,6 +575,7 @@ impl std::fmt::Debug for JwtService {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("JwtService")
             .field("config", &self.config)
+            .field("signs", &self.signer.is_some())
             .finish_non_exhaustive() // Indicates hidden fields
     }
 }
@@ -443,6 +587,10 @@ impl std::fmt::Debug for JwtService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::jwks::tests::{ed25519_key, RSA_PEM};
+    use crate::auth::jwks::{SigningKey, JWKS_PATH};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     /// Creates a test configuration with valid parameters.
     fn test_config() -> JwtConfig {
@@ -452,9 +600,19 @@ mod tests {
             refresh_token_ttl_seconds: 604800,
             issuer: "test-issuer".to_string(),
             audience: "test-audience".to_string(),
+            signing_keys_dir: None,
+            active_kid: None,
+            jwks_url: None,
+            jwks_refresh_seconds: 300,
+            legacy_hs256_until: None,
         }
     }
 
+    fn signing_keys(kids: &[&str], active_kid: Option<&str>) -> SigningKeys {
+        let keys = kids.iter().map(|kid| ed25519_key(kid)).collect();
+        SigningKeys::new(keys, active_kid).unwrap()
+    }
+
     #[test]
     fn test_generate_and_validate_access_token() {
         let service = JwtService::new(test_config());
@@ -549,5 +707,137 @@ mod tests {
         // Each token should have a unique JTI
         assert_ne!(claims1.jti, claims2.jti);
     }
-}
 
+    #[test]
+    fn test_asymmetric_tokens_carry_kid() {
+        for key in [
+            ed25519_key("ed-1"),
+            SigningKey::from_pkcs8_pem("rsa-1", RSA_PEM).unwrap(),
+        ] {
+            let algorithm = key.algorithm();
+            let service = JwtService::with_signing_keys(
+                test_config(),
+                SigningKeys::new(vec![key], None).unwrap(),
+            );
+
+            let tokens = service
+                .generate_tokens(Uuid::new_v4(), "test@example.com", "student")
+                .unwrap();
+
+            let header = decode_header(&tokens.access_token).unwrap();
+            assert_eq!(header.alg, algorithm);
+            assert!(header.kid.is_some());
+            assert!(service.validate_access_token(&tokens.access_token).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rotation_keeps_old_tokens_valid() {
+        let old = ed25519_key("2026-04");
+        let new = ed25519_key("2026-10");
+
+        let before = JwtService::with_signing_keys(
+            test_config(),
+            SigningKeys::new(vec![old.clone()], None).unwrap(),
+        );
+        let old_tokens = before
+            .generate_tokens(Uuid::new_v4(), "test@example.com", "student")
+            .unwrap();
+
+        // Overlap: both keys published, the new one signs
+        let during = JwtService::with_signing_keys(
+            test_config(),
+            SigningKeys::new(vec![old, new.clone()], Some("2026-10")).unwrap(),
+        );
+        assert_eq!(during.jwks().keys.len(), 2);
+        assert!(during.validate_access_token(&old_tokens.access_token).is_ok());
+
+        // Old key retired
+        let after = JwtService::with_signing_keys(
+            test_config(),
+            SigningKeys::new(vec![new], None).unwrap(),
+        );
+        let result = after.validate_access_token(&old_tokens.access_token);
+        assert!(matches!(result, Err(ApiError::InvalidToken)));
+    }
+
+    #[test]
+    fn test_legacy_hs256_tokens_need_secret() {
+        let legacy = JwtService::new(test_config())
+            .generate_tokens(Uuid::new_v4(), "test@example.com", "student")
+            .unwrap();
+
+        let config = JwtConfig {
+            legacy_hs256_until: Some(Utc::now() + Duration::days(1)),
+            ..test_config()
+        };
+        let with_secret = JwtService::with_signing_keys(config, signing_keys(&["k1"], None));
+        assert!(with_secret.validate_access_token(&legacy.access_token).is_ok());
+
+        let config = JwtConfig {
+            secret: String::new(),
+            legacy_hs256_until: Some(Utc::now() + Duration::days(1)),
+            ..test_config()
+        };
+        let without_secret = JwtService::with_signing_keys(config, signing_keys(&["k1"], None));
+        let result = without_secret.validate_access_token(&legacy.access_token);
+        assert!(matches!(result, Err(ApiError::InvalidToken)));
+    }
+
+    #[test]
+    fn test_legacy_hs256_tokens_need_opt_in() {
+        let legacy = JwtService::new(test_config())
+            .generate_tokens(Uuid::new_v4(), "test@example.com", "student")
+            .unwrap();
+
+        // Secret set, but no end date: HS256 is off once keys are configured
+        let without_opt_in = JwtService::with_signing_keys(test_config(), signing_keys(&["k1"], None));
+        let result = without_opt_in.validate_access_token(&legacy.access_token);
+        assert!(matches!(result, Err(ApiError::InvalidToken)));
+
+        let config = JwtConfig {
+            legacy_hs256_until: Some(Utc::now() - Duration::seconds(1)),
+            ..test_config()
+        };
+        let expired = JwtService::with_signing_keys(config, signing_keys(&["k1"], None));
+        let result = expired.validate_access_token(&legacy.access_token);
+        assert!(matches!(result, Err(ApiError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_verifier_validates_with_fetched_keys() {
+        let keys = signing_keys(&["k1"], None);
+        let issuer = JwtService::with_signing_keys(test_config(), keys.clone());
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(JWKS_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issuer.jwks()))
+            .mount(&server)
+            .await;
+
+        let config = JwtConfig {
+            secret: String::new(),
+            jwks_url: Some(format!("{}{}", server.uri(), JWKS_PATH)),
+            ..test_config()
+        };
+        let verifier = JwtService::from_config(config).await.unwrap();
+
+        let user_id = Uuid::new_v4();
+        let tokens = issuer
+            .generate_tokens(user_id, "test@example.com", "student")
+            .unwrap();
+        let claims = verifier.validate_access_token(&tokens.access_token).unwrap();
+        assert_eq!(claims.sub, user_id);
+
+        // Cannot sign, and does not trust HS256 tokens
+        assert!(verifier
+            .generate_tokens(user_id, "test@example.com", "student")
+            .is_err());
+        let hs256 = JwtService::new(test_config())
+            .generate_tokens(user_id, "test@example.com", "student")
+            .unwrap();
+        assert!(verifier.validate_access_token(&hs256.access_token).is_err());
+        assert!(verifier.jwks().keys.is_empty());
+    }
+}
@@ -8,8 +8,10 @@
 //! ```text
 //! auth/
 //! ├── jwt.rs        - JWT token generation and validation
+//! ├── jwks.rs       - Asymmetric signing keys and JSON Web Key Sets
 //! ├── password.rs   - Secure password hashing with Argon2id
-//! └── middleware.rs - Actix-web authentication middleware
+//! ├── middleware.rs - Actix-web authentication middleware
+//! └── service_token.rs - Service-to-service tokens for internal endpoints
 //! ```
 //!
 //! ## Overview
@@ -17,9 +19,11 @@
 //! | Component | Purpose | See Also |
 //! |-----------|---------|----------|
 //! | [`JwtService`] | Generate and validate JWT tokens | [RFC 7519](https://tools.ietf.org/html/rfc7519) |
+//! | [`SigningKeys`] | RS256/EdDSA keys with `kid` rotation | [RFC 7517](https://tools.ietf.org/html/rfc7517) |
 //! | [`PasswordHasher`] | Argon2id password hashing | [OWASP Password Storage](https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html) |
 //! | [`AuthMiddleware`] | Request authentication | Actix-web middleware docs |
 //! | [`AuthenticatedUser`] | Extractor for authenticated user | Actix-web extractors |
+//! | [`ServiceTokens`] | Authenticate services on internal endpoints | [`crate::service_client`] |
 //!
 //! ## Security Implementation (RF-AUTH-001)
 //!
@@ -102,11 +106,15 @@
 //! - [`_docs/business/functional-requirements.md`] - RF-AUTH-001 to RF-AUTH-004
 //! - [`_docs/development/development-standards.md`] - Security practices
 
+pub mod jwks;
 pub mod jwt;
 pub mod middleware;
 pub mod password;
+pub mod service_token;
 
 // Re-export main types for convenient access
+pub use jwks::{KeyError, SigningKey, SigningKeys, JWKS_PATH};
 pub use jwt::{Claims, JwtService, TokenPair};
 pub use middleware::{AuthMiddleware, AuthenticatedUser};
 pub use password::PasswordHasher;
+pub use service_token::{ServiceClaims, ServiceTokens, SERVICE_TOKEN_HEADER};
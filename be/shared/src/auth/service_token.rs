@@ -0,0 +1,231 @@
+//! # Service-to-Service Tokens
+//!
+//! Short-lived JWTs that authenticate one microservice to another on
+//! internal endpoints (`/internal/v1/...`).
+//!
+//! ## Why Separate Tokens?
+//!
+//! User access tokens say *who the user is*; internal endpoints answer
+//! questions about **any** user (e.g. "is user X enrolled in course Y?"), so
+//! they must only be reachable by other services. Service tokens:
+//!
+//! | Property | Value |
+//! |----------|-------|
+//! | Header | `X-Service-Token` |
+//! | Algorithm | HS256 with `INTERNAL_SERVICE_SECRET` (shared by all services) |
+//! | `sub` | Calling service name, e.g. `assessments-service` |
+//! | `iss` / `aud` | `acc-lms` / `acc-lms-internal` |
+//! | TTL | 5 minutes, reused until 30 seconds before expiry |
+//!
+//! The audience differs from user tokens (`acc-lms-api`), so a user token is
+//! never accepted as a service token, even if both secrets were equal.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use shared::auth::ServiceTokens;
+//!
+//! let tokens = ServiceTokens::new("assessments-service", &secret)?;
+//!
+//! // Caller
+//! request.header(SERVICE_TOKEN_HEADER, tokens.token()?);
+//!
+//! // Internal endpoint
+//! let caller = tokens.verify_request(&req)?;
+//! tracing::debug!(service = %caller.sub, "Internal call");
+//! ```
+
+use std::sync::Mutex;
+
+use actix_web::HttpRequest;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::ApiError;
+
+/// Header carrying the service token.
+pub const SERVICE_TOKEN_HEADER: &str = "X-Service-Token";
+
+/// Issuer of service tokens.
+pub const SERVICE_TOKEN_ISSUER: &str = "acc-lms";
+
+/// Audience of service tokens.
+pub const SERVICE_TOKEN_AUDIENCE: &str = "acc-lms-internal";
+
+/// Lifetime of a service token.
+const TOKEN_TTL_SECONDS: i64 = 300;
+
+/// A cached token is replaced this long before it expires.
+const REFRESH_MARGIN_SECONDS: i64 = 30;
+
+/// Minimum length of the shared secret.
+const MIN_SECRET_LENGTH: usize = 32;
+
+/// Service token payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceClaims {
+    /// Name of the calling service
+    pub sub: String,
+    pub iss: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: Uuid,
+}
+
+/// Issues and verifies service tokens.
+///
+/// Create once per service and share it (it is cheap to call [`token`](Self::token)
+/// on every request: the token is reused until shortly before it expires).
+pub struct ServiceTokens {
+    service_name: String,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    /// Current token and its expiration
+    cached: Mutex<Option<(String, i64)>>,
+}
+
+impl ServiceTokens {
+    /// Creates the token service of `service_name`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `ApiError::InternalError` if the secret is shorter than 32
+    /// characters.
+    pub fn new(service_name: &str, secret: &str) -> Result<Self, ApiError> {
+        if secret.len() < MIN_SECRET_LENGTH {
+            return Err(ApiError::InternalError {
+                message: format!(
+                    "Internal service secret must be at least {} characters",
+                    MIN_SECRET_LENGTH
+                ),
+            });
+        }
+
+        Ok(Self {
+            service_name: service_name.to_string(),
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            cached: Mutex::new(None),
+        })
+    }
+
+    /// Name of this service (the `sub` of issued tokens).
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Returns a valid token for calling other services.
+    pub fn token(&self) -> Result<String, ApiError> {
+        let now = Utc::now().timestamp();
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((token, exp)) = cached.as_ref() {
+            if exp - now > REFRESH_MARGIN_SECONDS {
+                return Ok(token.clone());
+            }
+        }
+
+        let (token, exp) = self.issue()?;
+        *cached = Some((token.clone(), exp));
+        Ok(token)
+    }
+
+    /// Signs a new token.
+    fn issue(&self) -> Result<(String, i64), ApiError> {
+        let now = Utc::now();
+        let exp = (now + Duration::seconds(TOKEN_TTL_SECONDS)).timestamp();
+
+        let claims = ServiceClaims {
+            sub: self.service_name.clone(),
+            iss: SERVICE_TOKEN_ISSUER.to_string(),
+            aud: SERVICE_TOKEN_AUDIENCE.to_string(),
+            exp,
+            iat: now.timestamp(),
+            jti: Uuid::new_v4(),
+        };
+
+        let token = encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| ApiError::InternalError {
+                message: format!("Failed to generate service token: {}", e),
+            })?;
+
+        Ok((token, exp))
+    }
+
+    /// Validates a service token.
+    ///
+    /// ## Errors
+    ///
+    /// - `ApiError::TokenExpired` - Token has expired
+    /// - `ApiError::InvalidToken` - Invalid signature, issuer or audience
+    pub fn verify(&self, token: &str) -> Result<ServiceClaims, ApiError> {
+        let mut validation = Validation::default();
+        validation.set_issuer(&[SERVICE_TOKEN_ISSUER]);
+        validation.set_audience(&[SERVICE_TOKEN_AUDIENCE]);
+
+        decode::<ServiceClaims>(token, &self.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                jsonwebtoken::errors::ErrorKind::ExpiredSignature => ApiError::TokenExpired,
+                _ => ApiError::InvalidToken,
+            })
+    }
+
+    /// Validates the service token of an internal request.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `ApiError::MissingAuth` if the header is absent, otherwise
+    /// the errors of [`verify`](Self::verify).
+    pub fn verify_request(&self, req: &HttpRequest) -> Result<ServiceClaims, ApiError> {
+        let token = req
+            .headers()
+            .get(SERVICE_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(ApiError::MissingAuth)?;
+
+        self.verify(token)
+    }
+}
+
+// Implement Debug manually to avoid exposing keys
+impl std::fmt::Debug for ServiceTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceTokens")
+            .field("service_name", &self.service_name)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "internal_secret_minimum_32_chars_long";
+
+    #[test]
+    fn test_issue_and_verify() {
+        let tokens = ServiceTokens::new("assessments-service", SECRET).unwrap();
+
+        let token = tokens.token().unwrap();
+        let claims = tokens.verify(&token).unwrap();
+
+        assert_eq!(claims.sub, "assessments-service");
+        assert_eq!(claims.aud, SERVICE_TOKEN_AUDIENCE);
+        // Reused while valid
+        assert_eq!(tokens.token().unwrap(), token);
+    }
+
+    #[test]
+    fn test_rejects_other_secret_and_short_secret() {
+        let tokens = ServiceTokens::new("ai-service", SECRET).unwrap();
+        let other = ServiceTokens::new("ai-service", "another_secret_minimum_32_chars_long").unwrap();
+
+        let token = other.token().unwrap();
+        assert!(matches!(tokens.verify(&token), Err(ApiError::InvalidToken)));
+        assert!(ServiceTokens::new("ai-service", "short").is_err());
+    }
+}
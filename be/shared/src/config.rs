@@ -26,6 +26,8 @@
 //!
 //! # JWT settings
 //! JWT_SECRET=your_secret_key_minimum_32_characters
+//! # or, auth-service: JWT_SIGNING_KEYS_DIR=/run/secrets/jwt-keys
+//! # or, other services: JWT_JWKS_URL=http://auth-service:8080/.well-known/jwks.json
 //! ```
 //!
 //! ## Configuration Sections
@@ -169,12 +171,17 @@ pub struct RedisConfig {
 /// - Access tokens: Short-lived (15 min default)
 /// - Refresh tokens: Longer-lived (7 days default) with rotation
 /// - Secret must be at least 32 characters
+///
+/// The signing mode follows from the key settings; see
+/// [`auth::jwks`](crate::auth::jwks).
 #[derive(Debug, Clone, Deserialize)]
 pub struct JwtConfig {
     /// Secret key for HS256 signing.
     /// 
     /// **Security**: Must be at least 32 characters.
     /// In production, use a cryptographically random string.
+    /// Not needed by services validating through `jwks_url`.
+    #[serde(default)]
     pub secret: String,
     
     /// Access token time-to-live in seconds.
@@ -196,6 +203,32 @@ pub struct JwtConfig {
     /// Default: `acc-lms-api`
     #[serde(default = "default_audience")]
     pub audience: String,
+
+    /// Directory of PKCS#8 private keys (`<kid>.pem`) to sign with.
+    /// Set only on auth-service.
+    #[serde(default)]
+    pub signing_keys_dir: Option<String>,
+
+    /// `kid` of the key that signs new tokens.
+    /// Default: the greatest `kid` in `signing_keys_dir`
+    #[serde(default)]
+    pub active_kid: Option<String>,
+
+    /// URL of auth-service's key set, for validate-only services.
+    /// Example: `http://auth-service:8080/.well-known/jwks.json`
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+
+    /// Seconds between key set refreshes.
+    /// Default: `300` (5 minutes)
+    #[serde(default = "default_jwks_refresh")]
+    pub jwks_refresh_seconds: u64,
+
+    /// With `signing_keys_dir` set, keep accepting HS256 tokens (no `kid`)
+    /// signed with `secret` until this instant, then reject them.
+    /// Example: `2026-11-30T00:00:00Z`. Default: HS256 is rejected
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    pub legacy_hs256_until: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Application runtime environment.
@@ -270,6 +303,26 @@ impl AppConfig {
                 "jwt.secret",
                 std::env::var("JWT_SECRET").ok(),
             )?
+            .set_override_option(
+                "jwt.signing_keys_dir",
+                std::env::var("JWT_SIGNING_KEYS_DIR").ok(),
+            )?
+            .set_override_option(
+                "jwt.active_kid",
+                std::env::var("JWT_ACTIVE_KID").ok(),
+            )?
+            .set_override_option(
+                "jwt.jwks_url",
+                std::env::var("JWT_JWKS_URL").ok(),
+            )?
+            .set_override_option(
+                "jwt.jwks_refresh_seconds",
+                std::env::var("JWT_JWKS_REFRESH_SECONDS").ok(),
+            )?
+            .set_override_option(
+                "jwt.legacy_hs256_until",
+                std::env::var("JWT_LEGACY_HS256_UNTIL").ok(),
+            )?
             .set_override_option(
                 "service_name",
                 std::env::var("SERVICE_NAME").ok(),
@@ -352,6 +405,25 @@ fn default_audience() -> String {
     "acc-lms-api".to_string()
 }
 
+fn default_jwks_refresh() -> u64 {
+    300
+}
+
+/// RFC 3339 timestamp; empty values count as unset, so compose files can
+/// pass them through.
+fn deserialize_optional_datetime<'de, D>(
+    deserializer: D,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    value
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| v.trim().parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -75,7 +75,10 @@
 //! - See [`_docs/business/functional-requirements.md`] for error code specifications
 //! - See [`auth/jwt`](crate::auth::jwt) for token-related error handling
 
-use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use actix_web::{
+    http::{header::RETRY_AFTER, StatusCode},
+    HttpResponse, ResponseError,
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
@@ -479,7 +482,14 @@ impl ResponseError for ApiError {
 
     fn error_response(&self) -> HttpResponse {
         let response = ErrorResponse::new(self);
-        HttpResponse::build(self.status_code()).json(response)
+        let mut builder = HttpResponse::build(self.status_code());
+
+        // Standard hint for clients and proxies on when to retry
+        if let Self::TooManyRequests { retry_after_seconds } = self {
+            builder.insert_header((RETRY_AFTER, retry_after_seconds.to_string()));
+        }
+
+        builder.json(response)
     }
 }
 
@@ -516,7 +526,16 @@ impl axum::response::IntoResponse for ApiError {
         };
 
         let response = ErrorResponse::new(&self);
-        (status, axum::Json(response)).into_response()
+        let mut response = (status, axum::Json(response)).into_response();
+
+        if let Self::TooManyRequests { retry_after_seconds } = self {
+            response.headers_mut().insert(
+                axum::http::header::RETRY_AFTER,
+                axum::http::HeaderValue::from(retry_after_seconds),
+            );
+        }
+
+        response
     }
 }
 
@@ -561,6 +580,13 @@ mod tests {
         assert_eq!(ApiError::TooManyRequests { retry_after_seconds: 60 }.error_code(), "TOO_MANY_REQUESTS");
     }
 
+    #[test]
+    fn test_too_many_requests_sets_retry_after() {
+        let response = ApiError::TooManyRequests { retry_after_seconds: 3 }.error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "3");
+    }
+
     #[test]
     fn test_server_errors_are_flagged() {
         assert!(ApiError::InternalError { message: "test".to_string() }.is_server_error());
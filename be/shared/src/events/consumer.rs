@@ -0,0 +1,409 @@
+//! # Event Consumer
+//!
+//! Reads domain events from Redis Streams with a consumer group, so each
+//! event is handled by one replica of the consuming service.
+//!
+//! ## Retries and Dead Letters
+//!
+//! ```text
+//!  XREADGROUP ──▶ handle ──ok──▶ XACK
+//!                   │
+//!                  err (stays pending)
+//!                   │
+//!   after retry_after idle: XCLAIM ──▶ handle again
+//!                   │
+//!   delivered max_deliveries times ──▶ XADD {stream}:dead + XACK
+//! ```
+//!
+//! Entries left pending by a crashed replica are claimed the same way.
+//! Handlers may see an event more than once and must be idempotent on
+//! [`StreamEvent::event_id`].
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::aio::ConnectionManager;
+use redis::streams::{StreamClaimReply, StreamId, StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use serde::de::DeserializeOwned;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::{dead_letter_stream, EventError};
+use crate::redis_client::RedisClient;
+
+/// An event read from a stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    /// Stream the entry was read from
+    pub stream: String,
+    /// Redis entry ID (`<ms>-<seq>`)
+    pub entry_id: String,
+    /// Outbox event ID; use it to deduplicate
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub payload: serde_json::Value,
+    /// Times this entry has been delivered, including this one
+    pub deliveries: u64,
+}
+
+impl StreamEvent {
+    /// Deserializes the payload into the producer's event type.
+    pub fn payload_as<T: DeserializeOwned>(&self) -> Result<T, EventError> {
+        serde_json::from_value(self.payload.clone()).map_err(Into::into)
+    }
+
+    /// Parses a stream entry written by the outbox relay.
+    pub(crate) fn from_entry(stream: &str, entry: &StreamId, deliveries: u64) -> Result<Self, EventError> {
+        let malformed = |reason: String| EventError::MalformedEntry { id: entry.id.clone(), reason };
+        let field = |name: &str| -> Result<String, EventError> {
+            let value = entry
+                .map
+                .get(name)
+                .ok_or_else(|| malformed(format!("missing field '{}'", name)))?;
+            redis::from_redis_value::<String>(value).map_err(|e| malformed(format!("field '{}': {}", name, e)))
+        };
+
+        Ok(Self {
+            stream: stream.to_string(),
+            entry_id: entry.id.clone(),
+            event_id: field("event_id")?.parse().map_err(|e| malformed(format!("event_id: {}", e)))?,
+            event_type: field("event_type")?,
+            aggregate_type: field("aggregate_type")?,
+            aggregate_id: field("aggregate_id")?.parse().map_err(|e| malformed(format!("aggregate_id: {}", e)))?,
+            occurred_at: DateTime::parse_from_rfc3339(&field("occurred_at")?)
+                .map_err(|e| malformed(format!("occurred_at: {}", e)))?
+                .with_timezone(&Utc),
+            payload: serde_json::from_str(&field("payload")?).map_err(|e| malformed(format!("payload: {}", e)))?,
+            deliveries,
+        })
+    }
+}
+
+/// Handles events for a consumer.
+///
+/// Returning an error leaves the event pending so it is retried.
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    async fn handle(&self, event: &StreamEvent) -> Result<(), EventError>;
+}
+
+/// Consumer settings.
+#[derive(Debug, Clone)]
+pub struct ConsumerConfig {
+    /// Consumer group, usually the consuming service name
+    pub group: String,
+    /// Consumer name, unique per replica (e.g. hostname)
+    pub consumer: String,
+    /// Streams to read, e.g. `events:enrollment`
+    pub streams: Vec<String>,
+    /// Entries read per call
+    pub batch_size: usize,
+    /// How long a read waits for new entries
+    pub block: Duration,
+    /// How long a failed or orphaned entry stays pending before a retry
+    pub retry_after: Duration,
+    /// Deliveries before an entry is moved to the dead-letter stream
+    pub max_deliveries: u64,
+}
+
+impl ConsumerConfig {
+    /// Creates a configuration with default batching and retry settings.
+    pub fn new(group: impl Into<String>, consumer: impl Into<String>, streams: Vec<String>) -> Self {
+        Self {
+            group: group.into(),
+            consumer: consumer.into(),
+            streams,
+            batch_size: 32,
+            block: Duration::from_secs(5),
+            retry_after: Duration::from_secs(30),
+            max_deliveries: 5,
+        }
+    }
+}
+
+/// Consumer-group reader with retries and dead-lettering.
+pub struct EventConsumer {
+    redis: ConnectionManager,
+    config: ConsumerConfig,
+}
+
+impl EventConsumer {
+    /// Creates a consumer.
+    pub fn new(redis: &RedisClient, config: ConsumerConfig) -> Self {
+        Self { redis: redis.connection(), config }
+    }
+
+    /// Runs forever; spawn it on the runtime.
+    pub async fn run<H: EventHandler>(self, handler: H) {
+        info!(group = %self.config.group, streams = ?self.config.streams, "Event consumer started");
+
+        while let Err(e) = self.ensure_groups().await {
+            error!(error = %e, "Failed to create consumer groups, retrying");
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+
+        let mut last_retry = tokio::time::Instant::now();
+
+        loop {
+            if last_retry.elapsed() >= self.config.retry_after {
+                last_retry = tokio::time::Instant::now();
+                if let Err(e) = self.retry_pending(&handler).await {
+                    error!(group = %self.config.group, error = %e, "Retrying pending events failed");
+                }
+            }
+
+            if let Err(e) = self.read_new(&handler).await {
+                error!(group = %self.config.group, error = %e, "Reading events failed");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+
+    /// Creates the consumer group on every stream (and the stream itself).
+    ///
+    /// New groups start at the end of the stream.
+    pub async fn ensure_groups(&self) -> Result<(), EventError> {
+        let mut conn = self.redis.clone();
+        for stream in &self.config.streams {
+            let created: redis::RedisResult<()> = conn
+                .xgroup_create_mkstream(stream, &self.config.group, "$")
+                .await;
+            match created {
+                Ok(()) => {}
+                Err(e) if e.code() == Some("BUSYGROUP") => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads and handles entries never delivered to this group.
+    async fn read_new<H: EventHandler>(&self, handler: &H) -> Result<usize, EventError> {
+        let options = StreamReadOptions::default()
+            .group(&self.config.group, &self.config.consumer)
+            .count(self.config.batch_size)
+            .block(self.config.block.as_millis() as usize);
+        let ids = vec![">"; self.config.streams.len()];
+
+        let mut conn = self.redis.clone();
+        let reply: Option<StreamReadReply> = conn.xread_options(&self.config.streams, &ids, &options).await?;
+
+        let mut handled = 0;
+        for key in reply.map(|r| r.keys).unwrap_or_default() {
+            for entry in &key.ids {
+                self.process(handler, &key.key, entry, 1).await?;
+                handled += 1;
+            }
+        }
+
+        Ok(handled)
+    }
+
+    /// Claims entries pending longer than `retry_after` and handles them
+    /// again, dead-lettering those out of deliveries.
+    async fn retry_pending<H: EventHandler>(&self, handler: &H) -> Result<(), EventError> {
+        let mut conn = self.redis.clone();
+        let min_idle = self.config.retry_after.as_millis() as u64;
+
+        for stream in &self.config.streams {
+            // (entry ID, consumer, idle ms, deliveries)
+            let pending: Vec<(String, String, u64, u64)> = redis::cmd("XPENDING")
+                .arg(stream)
+                .arg(&self.config.group)
+                .arg("IDLE")
+                .arg(min_idle)
+                .arg("-")
+                .arg("+")
+                .arg(self.config.batch_size)
+                .query_async(&mut conn)
+                .await?;
+
+            for (entry_id, _, _, deliveries) in pending {
+                let claimed: StreamClaimReply = conn
+                    .xclaim(stream, &self.config.group, &self.config.consumer, min_idle, &[&entry_id])
+                    .await?;
+                // Claimed by another replica in the meantime, or trimmed
+                let Some(entry) = claimed.ids.first() else {
+                    continue;
+                };
+
+                // XCLAIM counts as a delivery
+                self.process(handler, stream, entry, deliveries + 1).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles one entry and acknowledges or dead-letters it.
+    async fn process<H: EventHandler>(
+        &self,
+        handler: &H,
+        stream: &str,
+        entry: &StreamId,
+        deliveries: u64,
+    ) -> Result<(), EventError> {
+        let event = match StreamEvent::from_entry(stream, entry, deliveries) {
+            Ok(event) => event,
+            Err(e) => {
+                // Retrying cannot fix a malformed entry
+                warn!(stream, entry_id = %entry.id, error = %e, "Malformed event");
+                return self.dead_letter(stream, entry, deliveries, &e.to_string()).await;
+            }
+        };
+
+        if deliveries > self.config.max_deliveries {
+            return self.dead_letter(stream, entry, deliveries, "maximum deliveries exceeded").await;
+        }
+
+        match handler.handle(&event).await {
+            Ok(()) => {
+                let mut conn = self.redis.clone();
+                conn.xack::<_, _, _, ()>(stream, &self.config.group, &[&entry.id]).await?;
+                Ok(())
+            }
+            Err(e) if is_exhausted(deliveries, self.config.max_deliveries) => {
+                error!(
+                    stream,
+                    event_id = %event.event_id,
+                    event_type = %event.event_type,
+                    deliveries,
+                    error = %e,
+                    "Event handling failed for the last time"
+                );
+                self.dead_letter(stream, entry, deliveries, &e.to_string()).await
+            }
+            Err(e) => {
+                warn!(
+                    stream,
+                    event_id = %event.event_id,
+                    event_type = %event.event_type,
+                    deliveries,
+                    error = %e,
+                    "Event handling failed, will retry"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Copies an entry to the dead-letter stream and acknowledges it.
+    async fn dead_letter(&self, stream: &str, entry: &StreamId, deliveries: u64, reason: &str) -> Result<(), EventError> {
+        let mut cmd = redis::cmd("XADD");
+        cmd.arg(dead_letter_stream(stream)).arg("*");
+        for (field, value) in dead_letter_fields(stream, entry, &self.config.group, deliveries, reason) {
+            cmd.arg(field).arg(value);
+        }
+
+        let mut conn = self.redis.clone();
+        cmd.query_async::<String>(&mut conn).await?;
+        conn.xack::<_, _, _, ()>(stream, &self.config.group, &[&entry.id]).await?;
+
+        Ok(())
+    }
+}
+
+/// True once an entry has used up its deliveries.
+fn is_exhausted(deliveries: u64, max_deliveries: u64) -> bool {
+    deliveries >= max_deliveries
+}
+
+/// Fields of a dead-letter entry: the original fields plus failure details.
+fn dead_letter_fields(
+    stream: &str,
+    entry: &StreamId,
+    group: &str,
+    deliveries: u64,
+    reason: &str,
+) -> Vec<(String, Vec<u8>)> {
+    let mut fields: HashMap<String, Vec<u8>> = entry
+        .map
+        .iter()
+        .filter_map(|(k, v)| redis::from_redis_value::<Vec<u8>>(v).ok().map(|v| (k.clone(), v)))
+        .collect();
+
+    let details = [
+        ("dead_letter_stream", stream.to_string()),
+        ("dead_letter_entry_id", entry.id.clone()),
+        ("dead_letter_group", group.to_string()),
+        ("dead_letter_deliveries", deliveries.to_string()),
+        ("dead_letter_reason", reason.to_string()),
+        ("dead_letter_at", Utc::now().to_rfc3339()),
+    ];
+    for (field, value) in details {
+        fields.insert(field.to_string(), value.into_bytes());
+    }
+
+    let mut fields: Vec<(String, Vec<u8>)> = fields.into_iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::relay::stream_fields;
+    use crate::events::OutboxEvent;
+
+    fn entry(event: &OutboxEvent) -> StreamId {
+        StreamId {
+            id: "1700000000000-0".into(),
+            map: stream_fields(event)
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), redis::Value::BulkString(v.into_bytes())))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_stream_fields() {
+        let aggregate_id = Uuid::new_v4();
+        let event = OutboxEvent::new(
+            "enrollment",
+            aggregate_id,
+            "enrollment.completed",
+            &serde_json::json!({"user_id": aggregate_id, "final_progress": 100.0}),
+        )
+        .unwrap();
+
+        let parsed = StreamEvent::from_entry("events:enrollment", &entry(&event), 1).unwrap();
+        assert_eq!(parsed.event_id, event.event_id);
+        assert_eq!(parsed.event_type, "enrollment.completed");
+        assert_eq!(parsed.aggregate_id, aggregate_id);
+        assert_eq!(parsed.payload, event.payload);
+        assert_eq!(parsed.occurred_at, event.occurred_at);
+
+        let payload: HashMap<String, serde_json::Value> = parsed.payload_as().unwrap();
+        assert_eq!(payload["final_progress"], serde_json::json!(100.0));
+    }
+
+    #[test]
+    fn test_malformed_entry_is_rejected() {
+        let event = OutboxEvent::new("course", Uuid::new_v4(), "course.published", &serde_json::json!({})).unwrap();
+        let mut broken = entry(&event);
+        broken.map.remove("event_id");
+        assert!(matches!(
+            StreamEvent::from_entry("events:course", &broken, 1),
+            Err(EventError::MalformedEntry { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dead_letter_keeps_original_fields() {
+        let event = OutboxEvent::new("course", Uuid::new_v4(), "course.published", &serde_json::json!({})).unwrap();
+        let fields = dead_letter_fields("events:course", &entry(&event), "notifications", 5, "boom");
+
+        let names: Vec<&str> = fields.iter().map(|(k, _)| k.as_str()).collect();
+        assert!(names.contains(&"event_id"));
+        assert!(names.contains(&"payload"));
+        assert!(names.contains(&"dead_letter_reason"));
+        assert!(is_exhausted(5, 5));
+        assert!(!is_exhausted(4, 5));
+    }
+}
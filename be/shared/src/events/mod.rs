@@ -0,0 +1,132 @@
+//! # Domain Event Bus
+//!
+//! Reliable publication of domain events between services using the
+//! **transactional outbox** pattern and Redis Streams.
+//!
+//! ## Why an Outbox?
+//!
+//! Publishing straight to a broker after a commit loses the event if the
+//! process dies in between; publishing before the commit announces changes
+//! that may roll back. Instead, the event is written to an `outbox_events`
+//! table **in the same transaction** as the state change, and a relay
+//! publishes committed rows afterwards.
+//!
+//! ```text
+//! ┌──────────────────────────── one transaction ───────────────────────────┐
+//! │  UPDATE enrollments ...        INSERT INTO enrollments.outbox_events   │
+//! └────────────────────────────────────────────────────────────────────────┘
+//!                                         │
+//!                               ┌─────────▼─────────┐
+//!                               │    OutboxRelay    │  polls, XADD, marks
+//!                               └─────────┬─────────┘  published_at
+//!                                         │
+//!                          ┌──────────────▼──────────────┐
+//!                          │ Redis Stream events:{type}  │
+//!                          └──────┬───────────────┬──────┘
+//!                                 │               │   consumer groups
+//!                        ┌────────▼──────┐ ┌──────▼────────┐
+//!                        │ notifications │ │ certificates  │ ...
+//!                        └───────────────┘ └───────────────┘
+//! ```
+//!
+//! ## Delivery Guarantees
+//!
+//! | Property | Guarantee |
+//! |----------|-----------|
+//! | Atomicity | Event exists if and only if the state change committed |
+//! | Delivery | At least once; handlers must be idempotent on `event_id` |
+//! | Ordering | Per stream, in outbox insertion order per relay batch |
+//! | Failures | Retried up to `max_deliveries`, then moved to `{stream}:dead` |
+//!
+//! ## Components
+//!
+//! | Type | Role |
+//! |------|------|
+//! | [`OutboxEvent`] | Event row: aggregate, type and JSON payload |
+//! | [`PublishableEvent`] | Implemented by service event enums |
+//! | [`OutboxWriter`] | Inserts events with any executor (pool or transaction) |
+//! | [`OutboxRelay`] | Background task publishing pending rows to Redis Streams |
+//! | [`EventConsumer`] | Consumer-group reader with retries and dead-lettering |
+//! | [`EventHandler`] | Trait implemented by consuming services |
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use shared::events::OutboxWriter;
+//!
+//! let outbox = OutboxWriter::new("enrollments.outbox_events")?;
+//!
+//! let mut tx = pool.begin().await?;
+//! let enrollment = insert_enrollment(&mut *tx, data).await?;
+//! let event = EnrollmentEvent::Created { enrollment_id: enrollment.enrollment_id, /* ... */ };
+//! outbox.publish(&mut *tx, &event).await?;
+//! tx.commit().await?;
+//! ```
+//!
+//! ## Related Documentation
+//!
+//! - [`crate::redis_client`] - Redis connection
+//! - `db/migrations/postgresql/022_event_outbox.sql` - Outbox tables
+
+pub mod consumer;
+pub mod outbox;
+pub mod relay;
+
+pub use consumer::{ConsumerConfig, EventConsumer, EventHandler, StreamEvent};
+pub use outbox::{OutboxEvent, OutboxWriter, PublishableEvent};
+pub use relay::{OutboxRelay, RelayConfig};
+
+use thiserror::Error;
+
+/// Prefix of the Redis streams events are published to.
+pub const STREAM_PREFIX: &str = "events";
+
+/// Suffix of the dead-letter stream of a stream.
+pub const DEAD_LETTER_SUFFIX: &str = "dead";
+
+/// Returns the stream an aggregate type's events are published to.
+///
+/// ```rust,ignore
+/// assert_eq!(stream_name("enrollment"), "events:enrollment");
+/// ```
+pub fn stream_name(aggregate_type: &str) -> String {
+    format!("{}:{}", STREAM_PREFIX, aggregate_type)
+}
+
+/// Returns the dead-letter stream of a stream.
+pub fn dead_letter_stream(stream: &str) -> String {
+    format!("{}:{}", stream, DEAD_LETTER_SUFFIX)
+}
+
+/// Event bus errors.
+#[derive(Debug, Error)]
+pub enum EventError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Invalid outbox table name: {0}")]
+    InvalidTable(String),
+
+    #[error("Malformed stream entry {id}: {reason}")]
+    MalformedEntry { id: String, reason: String },
+
+    #[error("Handler failed: {0}")]
+    Handler(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_names() {
+        assert_eq!(stream_name("enrollment"), "events:enrollment");
+        assert_eq!(dead_letter_stream("events:enrollment"), "events:enrollment:dead");
+    }
+}
@@ -0,0 +1,183 @@
+//! # Outbox Writer
+//!
+//! Stores domain events in the service's `outbox_events` table. Pass the
+//! same transaction used for the state change so both commit or roll back
+//! together.
+//!
+//! Each service owns its table in its own schema (schema-per-service), so
+//! the writer is built with the qualified table name, e.g.
+//! `enrollments.outbox_events`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use super::EventError;
+
+/// A domain event that can be stored in the outbox.
+///
+/// Implemented by each service's event enums; the event is serialized as
+/// the payload.
+pub trait PublishableEvent: Serialize {
+    /// Aggregate kind, e.g. `enrollment`; selects the stream
+    fn aggregate_type(&self) -> &'static str;
+
+    /// ID of the entity the event is about
+    fn aggregate_id(&self) -> Uuid;
+
+    /// Dotted event name, e.g. `enrollment.completed`
+    fn event_type(&self) -> &'static str;
+}
+
+/// A domain event waiting in (or read from) the outbox.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct OutboxEvent {
+    /// Unique event ID (UUID v7, so IDs sort by creation time)
+    pub event_id: Uuid,
+    /// Aggregate kind; selects the stream (`enrollment` → `events:enrollment`)
+    pub aggregate_type: String,
+    /// ID of the entity the event is about
+    pub aggregate_id: Uuid,
+    /// Dotted event name, e.g. `enrollment.completed`
+    pub event_type: String,
+    /// Serialized event
+    pub payload: serde_json::Value,
+    /// When the change happened
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl OutboxEvent {
+    /// Creates an event by serializing `payload`.
+    pub fn new<T: Serialize>(
+        aggregate_type: impl Into<String>,
+        aggregate_id: Uuid,
+        event_type: impl Into<String>,
+        payload: &T,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            event_id: Uuid::now_v7(),
+            aggregate_type: aggregate_type.into(),
+            aggregate_id,
+            event_type: event_type.into(),
+            payload: serde_json::to_value(payload)?,
+            occurred_at: Utc::now(),
+        })
+    }
+
+    /// Creates the outbox row of a domain event.
+    pub fn from_event<T: PublishableEvent>(event: &T) -> Result<Self, serde_json::Error> {
+        Self::new(event.aggregate_type(), event.aggregate_id(), event.event_type(), event)
+    }
+
+    /// Overrides the occurrence time (defaults to now).
+    pub fn occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.occurred_at = occurred_at;
+        self
+    }
+}
+
+/// Inserts events into a service's outbox table.
+#[derive(Debug, Clone)]
+pub struct OutboxWriter {
+    table: String,
+}
+
+impl OutboxWriter {
+    /// Creates a writer for a qualified table name such as
+    /// `enrollments.outbox_events`.
+    ///
+    /// The name is interpolated into SQL, so only lowercase identifiers
+    /// are accepted.
+    pub fn new(table: impl Into<String>) -> Result<Self, EventError> {
+        let table = table.into();
+        if !is_valid_table_name(&table) {
+            return Err(EventError::InvalidTable(table));
+        }
+        Ok(Self { table })
+    }
+
+    /// Qualified outbox table name.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Stores an event.
+    ///
+    /// Use the transaction of the state change as the executor
+    /// (`&mut *tx`); with a pool the event is stored on its own.
+    pub async fn enqueue<'e, E: PgExecutor<'e>>(&self, executor: E, event: &OutboxEvent) -> Result<(), sqlx::Error> {
+        let query = format!(
+            r#"
+            INSERT INTO {} (event_id, aggregate_type, aggregate_id, event_type, payload, occurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            self.table
+        );
+
+        sqlx::query(&query)
+            .bind(event.event_id)
+            .bind(&event.aggregate_type)
+            .bind(event.aggregate_id)
+            .bind(&event.event_type)
+            .bind(&event.payload)
+            .bind(event.occurred_at)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Serializes and stores a domain event.
+    ///
+    /// Serialization failures are reported as [`sqlx::Error::Encode`] so
+    /// repositories can keep returning `sqlx::Error`.
+    pub async fn publish<'e, E, T>(&self, executor: E, event: &T) -> Result<(), sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+        T: PublishableEvent,
+    {
+        let event = OutboxEvent::from_event(event).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        self.enqueue(executor, &event).await
+    }
+}
+
+/// Accepts `schema.table` or `table` made of `[a-z_][a-z0-9_]*` parts.
+fn is_valid_table_name(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() <= 2
+        && parts.iter().all(|part| {
+            let mut chars = part.chars();
+            matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+                && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_name_validation() {
+        assert!(OutboxWriter::new("enrollments.outbox_events").is_ok());
+        assert!(OutboxWriter::new("outbox_events").is_ok());
+        assert!(OutboxWriter::new("a.b.c").is_err());
+        assert!(OutboxWriter::new("users.outbox; DROP TABLE x").is_err());
+        assert!(OutboxWriter::new("").is_err());
+    }
+
+    #[test]
+    fn test_new_event_serializes_payload() {
+        #[derive(Serialize)]
+        struct Created {
+            course_id: Uuid,
+        }
+
+        let course_id = Uuid::new_v4();
+        let event = OutboxEvent::new("course", course_id, "course.created", &Created { course_id }).unwrap();
+
+        assert_eq!(event.aggregate_type, "course");
+        assert_eq!(event.payload["course_id"], serde_json::json!(course_id));
+        assert_eq!(event.event_id.get_version_num(), 7);
+    }
+}
@@ -0,0 +1,195 @@
+//! # Outbox Relay
+//!
+//! Background task that publishes committed outbox rows to Redis Streams
+//! and marks them as published.
+//!
+//! Rows are claimed with `FOR UPDATE SKIP LOCKED`, so every replica of a
+//! service can run a relay without publishing the same row twice. A row is
+//! only marked published after `XADD` succeeded; if the process dies in
+//! between, the row is published again (consumers deduplicate on
+//! `event_id`).
+
+use std::time::Duration;
+
+use redis::aio::ConnectionManager;
+use sqlx::PgPool;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::{stream_name, EventError, OutboxEvent, OutboxWriter};
+use crate::redis_client::RedisClient;
+
+/// Relay settings.
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// Time between polls when the outbox is drained
+    pub interval: Duration,
+    /// Rows published per transaction
+    pub batch_size: i64,
+    /// Approximate length each stream is trimmed to (`MAXLEN ~`)
+    pub max_stream_len: usize,
+    /// Published rows older than this are deleted
+    pub retention: Duration,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            batch_size: 100,
+            max_stream_len: 100_000,
+            retention: Duration::from_secs(7 * 24 * 3600),
+        }
+    }
+}
+
+/// Publishes a service's outbox to Redis Streams.
+pub struct OutboxRelay {
+    pool: PgPool,
+    redis: ConnectionManager,
+    table: String,
+    config: RelayConfig,
+}
+
+impl OutboxRelay {
+    /// Creates a relay for the table written by `outbox`.
+    pub fn new(pool: PgPool, redis: &RedisClient, outbox: &OutboxWriter, config: RelayConfig) -> Self {
+        Self {
+            pool,
+            redis: redis.connection(),
+            table: outbox.table().to_string(),
+            config,
+        }
+    }
+
+    /// Runs forever; spawn it on the runtime.
+    pub async fn run(self) {
+        info!(table = %self.table, "Outbox relay started");
+
+        let mut ticker = tokio::time::interval(self.config.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_cleanup = tokio::time::Instant::now();
+
+        loop {
+            ticker.tick().await;
+
+            // Drain full batches before waiting for the next tick
+            loop {
+                match self.publish_batch().await {
+                    Ok(count) if count as i64 >= self.config.batch_size => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!(table = %self.table, error = %e, "Outbox relay failed");
+                        break;
+                    }
+                }
+            }
+
+            if last_cleanup.elapsed() >= Duration::from_secs(3600) {
+                last_cleanup = tokio::time::Instant::now();
+                if let Err(e) = self.delete_published().await {
+                    warn!(table = %self.table, error = %e, "Outbox cleanup failed");
+                }
+            }
+        }
+    }
+
+    /// Publishes one batch of pending rows. Returns how many were published.
+    pub async fn publish_batch(&self) -> Result<usize, EventError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = format!(
+            r#"
+            SELECT event_id, aggregate_type, aggregate_id, event_type, payload, occurred_at
+            FROM {}
+            WHERE published_at IS NULL
+            ORDER BY created_at ASC, event_id ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            self.table
+        );
+        let pending = sqlx::query_as::<_, OutboxEvent>(&query)
+            .bind(self.config.batch_size)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        if pending.is_empty() {
+            tx.commit().await?;
+            return Ok(0);
+        }
+
+        let mut conn = self.redis.clone();
+        let mut published: Vec<Uuid> = Vec::with_capacity(pending.len());
+        let mut failure = None;
+
+        for event in &pending {
+            let mut cmd = redis::cmd("XADD");
+            cmd.arg(stream_name(&event.aggregate_type))
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(self.config.max_stream_len)
+                .arg("*");
+            for (field, value) in stream_fields(event) {
+                cmd.arg(field).arg(value);
+            }
+
+            match cmd.query_async::<String>(&mut conn).await {
+                Ok(_) => published.push(event.event_id),
+                Err(e) => {
+                    // Stop here so later events are not published before this one
+                    failure = Some((event.event_id, e));
+                    break;
+                }
+            }
+        }
+
+        if !published.is_empty() {
+            let query = format!("UPDATE {} SET published_at = NOW() WHERE event_id = ANY($1)", self.table);
+            sqlx::query(&query).bind(&published).execute(&mut *tx).await?;
+        }
+
+        if let Some((event_id, e)) = &failure {
+            let query = format!(
+                "UPDATE {} SET attempts = attempts + 1, last_error = $2 WHERE event_id = $1",
+                self.table
+            );
+            sqlx::query(&query).bind(event_id).bind(e.to_string()).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+
+        debug!(table = %self.table, published = published.len(), "Outbox batch published");
+
+        match failure {
+            Some((_, e)) => Err(e.into()),
+            None => Ok(published.len()),
+        }
+    }
+
+    /// Deletes published rows past the retention period.
+    async fn delete_published(&self) -> Result<u64, EventError> {
+        let query = format!(
+            "DELETE FROM {} WHERE published_at < NOW() - make_interval(secs => $1)",
+            self.table
+        );
+        let result = sqlx::query(&query)
+            .bind(self.config.retention.as_secs_f64())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Fields of the stream entry for an event.
+pub(crate) fn stream_fields(event: &OutboxEvent) -> Vec<(&'static str, String)> {
+    vec![
+        ("event_id", event.event_id.to_string()),
+        ("event_type", event.event_type.clone()),
+        ("aggregate_type", event.aggregate_type.clone()),
+        ("aggregate_id", event.aggregate_id.to_string()),
+        ("occurred_at", event.occurred_at.to_rfc3339()),
+        ("payload", event.payload.to_string()),
+    ]
+}
@@ -31,7 +31,10 @@
 //! | [`errors`] | Standardized error handling | [`ApiError`], [`ApiResult`] |
 //! | [`auth`] | JWT tokens, password hashing, middleware | [`JwtService`], [`PasswordHasher`] |
 //! | [`database`] | PostgreSQL connection pool | [`create_pool`](database::create_pool) |
+//! | [`events`] | Transactional outbox and Redis Streams event bus | [`OutboxWriter`](events::OutboxWriter), [`EventConsumer`](events::EventConsumer) |
+//! | [`rate_limit`] | Redis-backed per-role rate limiting middleware | [`RateLimiter`](rate_limit::RateLimiter) |
 //! | [`redis_client`] | Redis for cache & sessions | [`RedisClient`] |
+//! | [`service_client`] | Internal enrollments/courses lookups between services | [`ServiceClient`](service_client::ServiceClient) |
 //! | [`tracing_config`] | Structured logging setup | [`init_tracing`](tracing_config::init_tracing) |
 //! | [`validation`] | Request validation helpers | Custom validators |
 //!
@@ -70,7 +73,10 @@ pub mod auth;
 pub mod config;
 pub mod database;
 pub mod errors;
+pub mod events;
+pub mod rate_limit;
 pub mod redis_client;
+pub mod service_client;
 pub mod tracing_config;
 pub mod validation;
 
@@ -0,0 +1,717 @@
+//! # Rate Limiting
+//!
+//! Per-actor rate limiting (RF-GLOBAL-004) as an Actix-web middleware.
+//! Counters live in Redis, so all replicas of a service share one budget.
+//!
+//! ## Limits by Role
+//!
+//! | Actor | Rate | Burst | Keyed by |
+//! |-------|------|-------|----------|
+//! | Anonymous | 10 req/s | 20 | Client IP |
+//! | Student | 100 req/s | 200 | User ID |
+//! | Instructor | 200 req/s | 400 | User ID |
+//! | Admin | 500 req/s | 1000 | User ID |
+//!
+//! Routes can override the limit for everyone (e.g. stricter login); an
+//! override has its own bucket and replaces the role limit on that route.
+//!
+//! ## Token Bucket
+//!
+//! Each actor has a bucket of up to `burst` tokens, refilled at `rate`
+//! tokens per second. A request takes one token; an empty bucket answers
+//! `429 Too Many Requests`. Refill and take run in one Lua script using the
+//! Redis clock, so concurrent replicas never double-spend.
+//!
+//! ```text
+//! ratelimit:{scope}:{actor}  →  HASH { tokens, ts }   (expires once full)
+//!
+//! scope: "global" or the overriding route prefix
+//! actor: "user:{user_id}" or "ip:{client_ip}"
+//! ```
+//!
+//! ## Response Headers
+//!
+//! | Header | Value |
+//! |--------|-------|
+//! | `RateLimit-Limit` | Bucket size (burst) |
+//! | `RateLimit-Remaining` | Tokens left |
+//! | `RateLimit-Reset` | Seconds until the bucket is full again |
+//! | `Retry-After` | Seconds until a token is available (429 only) |
+//!
+//! ## Actor Identification
+//!
+//! 1. [`AuthenticatedUser`] in the request extensions
+//! 2. Bearer token validated with the [`JwtService`] given to
+//!    [`RateLimiter::with_jwt`]; invalid tokens count as anonymous
+//! 3. Client IP: the TCP peer address. When the peer is one of the
+//!    [`RateLimitConfig::trusted_proxies`] (nginx, Traefik), the
+//!    `Forwarded` / `X-Forwarded-For` chain is walked from the right and
+//!    the first address outside those proxies is used instead
+//!
+//! Forwarded headers from any other peer are ignored: a client could
+//! otherwise pick a fresh bucket per request by rotating them.
+//!
+//! ## Failure Mode
+//!
+//! If Redis is unreachable the request goes through and a warning is
+//! logged: the limiter must not take the API down with it.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use shared::rate_limit::{RateLimit, RateLimitConfig, RateLimiter, RouteLimit};
+//!
+//! let limiter = RateLimiter::new(redis_client.clone(), RateLimitConfig::default())
+//!     .with_jwt(jwt_service.clone())
+//!     .with_route(RouteLimit::new("/api/v1/auth/login", RateLimit::new(1, 5)).method(Method::POST));
+//!
+//! App::new().wrap(limiter)
+//! ```
+
+use std::future::{ready, Future, Ready};
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{
+    HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, FORWARDED, X_FORWARDED_FOR,
+};
+use actix_web::http::Method;
+use actix_web::{Error, HttpMessage, ResponseError};
+use ipnet::IpNet;
+use uuid::Uuid;
+
+use crate::auth::middleware::{AuthenticatedUser, UserRole};
+use crate::auth::JwtService;
+use crate::errors::ApiError;
+use crate::redis_client::RedisClient;
+
+/// `RateLimit-Limit` header.
+pub const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+
+/// `RateLimit-Remaining` header.
+pub const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+
+/// `RateLimit-Reset` header.
+pub const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Bucket scope of requests without a route override.
+const GLOBAL_SCOPE: &str = "global";
+
+/// Refills the bucket, takes a token if there is one and returns
+/// `{allowed, remaining, ms_until_full, ms_until_next_token}`.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local rate = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = time[1] * 1000 + math.floor(time[2] / 1000)
+
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1]) or burst
+local ts = tonumber(state[2]) or now
+tokens = math.min(burst, tokens + math.max(0, now - ts) * rate / 1000)
+
+local allowed = 0
+local retry_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_ms = math.ceil((1 - tokens) * 1000 / rate)
+end
+
+local full_ms = math.ceil((burst - tokens) * 1000 / rate)
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], math.max(full_ms, 1000))
+
+return { allowed, math.floor(tokens), full_ms, retry_ms }
+"#;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// A token bucket: `per_second` refill rate, `burst` capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub per_second: u32,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub const fn new(per_second: u32, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+}
+
+/// Limits by role (RF-GLOBAL-004).
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub anonymous: RateLimit,
+    pub student: RateLimit,
+    pub instructor: RateLimit,
+    pub admin: RateLimit,
+    /// Prefix of the Redis keys
+    pub key_prefix: String,
+    /// Reverse proxies whose forwarded headers are trusted
+    pub trusted_proxies: Vec<IpNet>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            anonymous: RateLimit::new(10, 20),
+            student: RateLimit::new(100, 200),
+            instructor: RateLimit::new(200, 400),
+            admin: RateLimit::new(500, 1000),
+            key_prefix: "ratelimit".to_string(),
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Limit of a role; `None` is an anonymous client.
+    pub fn for_role(&self, role: Option<UserRole>) -> RateLimit {
+        match role {
+            None => self.anonymous,
+            Some(UserRole::Student) => self.student,
+            Some(UserRole::Instructor) => self.instructor,
+            Some(UserRole::Admin) => self.admin,
+        }
+    }
+
+    /// Sets the trusted proxies from a comma-separated list of CIDRs or
+    /// addresses (e.g. `RATE_LIMIT_TRUSTED_PROXIES=10.0.0.0/8,172.16.0.0/12`).
+    pub fn with_trusted_proxies(mut self, list: &str) -> Result<Self, String> {
+        self.trusted_proxies = list
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                item.parse::<IpNet>()
+                    .or_else(|_| item.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| format!("Invalid trusted proxy: {}", item))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    fn is_trusted_proxy(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(ip))
+    }
+
+    /// Client address of a request that arrived from `peer`; see
+    /// [Actor Identification](self#actor-identification).
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.is_trusted_proxy(&peer) {
+            return peer;
+        }
+
+        let mut client = peer;
+        for hop in forwarded_chain(headers).iter().rev() {
+            let Some(ip) = parse_forwarded_node(hop) else {
+                break;
+            };
+            client = ip;
+            if !self.is_trusted_proxy(&ip) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+/// Forwarded-for addresses, client first: `Forwarded` when present (RFC
+/// 7239), else `X-Forwarded-For`.
+fn forwarded_chain(headers: &HeaderMap) -> Vec<String> {
+    let values = |name| {
+        headers
+            .get_all(name)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect::<Vec<_>>()
+    };
+
+    let forwarded: Vec<String> = values(FORWARDED)
+        .into_iter()
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                name.eq_ignore_ascii_case("for").then(|| value.trim().to_string())
+            })
+        })
+        .collect();
+
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+    values(X_FORWARDED_FOR).into_iter().map(String::from).collect()
+}
+
+/// Address of a forwarded node: `ip`, `ip:port`, `[ipv6]` or `[ipv6]:port`,
+/// optionally quoted; obfuscated identifiers and `unknown` give `None`.
+fn parse_forwarded_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim_matches('"');
+
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+    node.parse::<IpAddr>()
+        .ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// Limit for every actor on the routes under a path prefix.
+#[derive(Debug, Clone)]
+pub struct RouteLimit {
+    path_prefix: String,
+    method: Option<Method>,
+    limit: RateLimit,
+}
+
+impl RouteLimit {
+    /// Applies to `path_prefix` and its sub-paths, any method.
+    pub fn new(path_prefix: impl Into<String>, limit: RateLimit) -> Self {
+        Self {
+            path_prefix: path_prefix.into().trim_end_matches('/').to_string(),
+            method: None,
+            limit,
+        }
+    }
+
+    /// Restricts the override to one method.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        let under_prefix = path
+            .strip_prefix(self.path_prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+        under_prefix && self.method.as_ref().is_none_or(|m| m == method)
+    }
+}
+
+// =============================================================================
+// POLICY
+// =============================================================================
+
+/// Who a request is counted against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Actor {
+    User { user_id: Uuid, role: UserRole },
+    Anonymous { ip: String },
+}
+
+impl Actor {
+    fn key(&self) -> String {
+        match self {
+            Self::User { user_id, .. } => format!("user:{}", user_id),
+            Self::Anonymous { ip } => format!("ip:{}", ip),
+        }
+    }
+
+    fn role(&self) -> Option<UserRole> {
+        match self {
+            Self::User { role, .. } => Some(*role),
+            Self::Anonymous { .. } => None,
+        }
+    }
+}
+
+impl From<&AuthenticatedUser> for Actor {
+    fn from(user: &AuthenticatedUser) -> Self {
+        Self::User {
+            user_id: user.user_id,
+            role: user.role,
+        }
+    }
+}
+
+/// Role limits and route overrides; picks the bucket of a request.
+#[derive(Debug, Clone, Default)]
+struct Policy {
+    config: RateLimitConfig,
+    routes: Vec<RouteLimit>,
+}
+
+impl Policy {
+    /// Redis key and limit of the bucket a request draws from.
+    fn bucket(&self, method: &Method, path: &str, actor: &Actor) -> (String, RateLimit) {
+        let (scope, limit) = match self.routes.iter().find(|r| r.matches(method, path)) {
+            Some(route) => (route.path_prefix.as_str(), route.limit),
+            None => (GLOBAL_SCOPE, self.config.for_role(actor.role())),
+        };
+
+        let key = format!("{}:{}:{}", self.config.key_prefix, scope, actor.key());
+        (key, limit)
+    }
+}
+
+/// Outcome of taking a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Decision {
+    allowed: bool,
+    limit: u32,
+    remaining: u32,
+    reset_seconds: u64,
+    retry_after_seconds: u64,
+}
+
+impl Decision {
+    /// Reads the script's `{allowed, remaining, ms_until_full, ms_until_next_token}`.
+    fn from_script(limit: RateLimit, reply: &[i64]) -> Option<Self> {
+        let [allowed, remaining, full_ms, retry_ms] = *reply else {
+            return None;
+        };
+        let seconds = |ms: i64| (ms.max(0) as u64).div_ceil(1000);
+
+        Some(Self {
+            allowed: allowed == 1,
+            limit: limit.burst,
+            remaining: remaining.clamp(0, i64::from(limit.burst)) as u32,
+            reset_seconds: seconds(full_ms),
+            retry_after_seconds: seconds(retry_ms).max(1),
+        })
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        for (name, value) in [
+            (RATE_LIMIT_LIMIT, u64::from(self.limit)),
+            (RATE_LIMIT_REMAINING, u64::from(self.remaining)),
+            (RATE_LIMIT_RESET, self.reset_seconds),
+        ] {
+            headers.insert(name, HeaderValue::from(value));
+        }
+    }
+}
+
+// =============================================================================
+// MIDDLEWARE
+// =============================================================================
+
+/// Rate limiting middleware; see the [module docs](self).
+#[derive(Clone)]
+pub struct RateLimiter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    redis: RedisClient,
+    policy: Policy,
+    jwt: Option<Arc<JwtService>>,
+    script: redis::Script,
+}
+
+impl RateLimiter {
+    /// Creates a limiter with the given role limits and no overrides.
+    pub fn new(redis: RedisClient, config: RateLimitConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                redis,
+                policy: Policy {
+                    config,
+                    routes: Vec::new(),
+                },
+                jwt: None,
+                script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+            }),
+        }
+    }
+
+    /// Identifies users from their Bearer token when no auth middleware
+    /// runs before the limiter.
+    pub fn with_jwt(mut self, jwt: Arc<JwtService>) -> Self {
+        self.inner_mut().jwt = Some(jwt);
+        self
+    }
+
+    /// Adds a route override; the first matching override wins.
+    pub fn with_route(mut self, route: RouteLimit) -> Self {
+        self.inner_mut().policy.routes.push(route);
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner {
+        Arc::get_mut(&mut self.inner).expect("RateLimiter is configured before it is cloned")
+    }
+}
+
+impl Inner {
+    fn actor(&self, req: &ServiceRequest) -> Actor {
+        if let Some(user) = req.extensions().get::<AuthenticatedUser>() {
+            return Actor::from(user);
+        }
+
+        let bearer_user = self.jwt.as_ref().and_then(|jwt| {
+            let header = req.headers().get(AUTHORIZATION)?.to_str().ok()?;
+            let token = JwtService::extract_from_header(header).ok()?;
+            jwt.validate_access_token(token).ok()
+        });
+        if let Some(claims) = bearer_user {
+            return Actor::from(&AuthenticatedUser::from(claims));
+        }
+
+        let ip = req.peer_addr().map(|peer| {
+            self.policy
+                .config
+                .client_ip(peer.ip(), req.headers())
+                .to_string()
+        });
+        Actor::Anonymous {
+            ip: ip.unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+
+    /// Takes a token; `None` when Redis could not be asked.
+    async fn check(&self, req: &ServiceRequest) -> Option<Decision> {
+        let actor = self.actor(req);
+        let (key, limit) = self.policy.bucket(req.method(), req.path(), &actor);
+
+        let reply: Result<Vec<i64>, _> = self
+            .script
+            .key(&key)
+            .arg(limit.per_second.max(1))
+            .arg(limit.burst.max(1))
+            .invoke_async(&mut self.redis.connection())
+            .await;
+
+        match reply {
+            Ok(reply) => Decision::from_script(limit, &reply),
+            Err(e) => {
+                tracing::warn!(error = %e, key = %key, "Rate limiter unavailable, allowing request");
+                None
+            }
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            inner: Arc::clone(&self.inner),
+        }))
+    }
+}
+
+/// Per-worker service created by [`RateLimiter`].
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    inner: Arc<Inner>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let inner = Arc::clone(&self.inner);
+
+        Box::pin(async move {
+            let decision = inner.check(&req).await;
+
+            if let Some(decision) = decision.filter(|d| !d.allowed) {
+                let error = ApiError::TooManyRequests {
+                    retry_after_seconds: decision.retry_after_seconds,
+                };
+                let mut response = error.error_response();
+                decision.apply(response.headers_mut());
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+
+            let mut response = service.call(req).await?;
+            if let Some(decision) = decision {
+                decision.apply(response.headers_mut());
+            }
+            Ok(response.map_into_left_body())
+        })
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(role: UserRole) -> Actor {
+        Actor::User {
+            user_id: Uuid::nil(),
+            role,
+        }
+    }
+
+    #[test]
+    fn test_bucket_uses_role_limits() {
+        let policy = Policy::default();
+        let anonymous = Actor::Anonymous {
+            ip: "203.0.113.7".to_string(),
+        };
+
+        let (key, limit) = policy.bucket(&Method::GET, "/api/v1/courses", &anonymous);
+        assert_eq!(key, "ratelimit:global:ip:203.0.113.7");
+        assert_eq!(limit, RateLimit::new(10, 20));
+
+        let (key, limit) = policy.bucket(&Method::GET, "/api/v1/courses", &user(UserRole::Admin));
+        assert_eq!(key, format!("ratelimit:global:user:{}", Uuid::nil()));
+        assert_eq!(limit, RateLimit::new(500, 1000));
+        assert_eq!(
+            policy.config.for_role(Some(UserRole::Instructor)),
+            RateLimit::new(200, 400)
+        );
+    }
+
+    #[test]
+    fn test_route_override() {
+        let policy = Policy {
+            routes: vec![
+                RouteLimit::new("/api/v1/auth/login/", RateLimit::new(1, 5)).method(Method::POST)
+            ],
+            ..Policy::default()
+        };
+        let actor = user(UserRole::Student);
+
+        let (key, limit) = policy.bucket(&Method::POST, "/api/v1/auth/login", &actor);
+        assert_eq!(
+            key,
+            format!("ratelimit:/api/v1/auth/login:user:{}", Uuid::nil())
+        );
+        assert_eq!(limit, RateLimit::new(1, 5));
+
+        // Other methods and look-alike paths keep the role limit
+        let (_, limit) = policy.bucket(&Method::GET, "/api/v1/auth/login", &actor);
+        assert_eq!(limit, RateLimit::new(100, 200));
+        let (_, limit) = policy.bucket(&Method::POST, "/api/v1/auth/login-sso", &actor);
+        assert_eq!(limit, RateLimit::new(100, 200));
+    }
+
+    fn forwarded(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn test_forwarded_headers_ignored_from_untrusted_peer() {
+        let config = RateLimitConfig::default()
+            .with_trusted_proxies("10.0.0.0/8")
+            .unwrap();
+        let peer: IpAddr = "203.0.113.7".parse().unwrap();
+
+        // A client rotating X-Forwarded-For still lands in its own bucket
+        for spoofed in ["198.51.100.1", "198.51.100.2"] {
+            let headers = forwarded(&[("x-forwarded-for", spoofed)]);
+            assert_eq!(config.client_ip(peer, &headers), peer);
+        }
+
+        // No trusted proxies configured: headers are never used
+        let headers = forwarded(&[("x-forwarded-for", "198.51.100.1")]);
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        assert_eq!(RateLimitConfig::default().client_ip(proxy, &headers), proxy);
+    }
+
+    #[test]
+    fn test_forwarded_headers_from_trusted_proxy() {
+        let config = RateLimitConfig::default()
+            .with_trusted_proxies("10.0.0.0/8, 172.16.0.1")
+            .unwrap();
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+
+        // Rightmost untrusted hop wins; a spoofed leftmost entry is skipped
+        let headers = forwarded(&[(
+            "x-forwarded-for",
+            "198.51.100.1, 203.0.113.7, 172.16.0.1",
+        )]);
+        assert_eq!(
+            config.client_ip(proxy, &headers),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+
+        let headers = forwarded(&[
+            ("forwarded", "for=\"[2001:db8::7]:4711\";proto=https"),
+            ("forwarded", "for=10.0.0.9"),
+        ]);
+        assert_eq!(
+            config.client_ip(proxy, &headers),
+            "2001:db8::7".parse::<IpAddr>().unwrap()
+        );
+
+        // Unparseable hop: stop at the last proxy reached
+        let headers = forwarded(&[("x-forwarded-for", "unknown, 10.0.0.9")]);
+        assert_eq!(
+            config.client_ip(proxy, &headers),
+            "10.0.0.9".parse::<IpAddr>().unwrap()
+        );
+
+        assert!(RateLimitConfig::default()
+            .with_trusted_proxies("10.0.0.0/33")
+            .is_err());
+    }
+
+    #[test]
+    fn test_decision_from_script() {
+        let limit = RateLimit::new(10, 20);
+
+        let allowed = Decision::from_script(limit, &[1, 19, 100, 0]).unwrap();
+        assert!(allowed.allowed);
+        assert_eq!(allowed.remaining, 19);
+        assert_eq!(allowed.reset_seconds, 1);
+
+        let denied = Decision::from_script(limit, &[0, 0, 2000, 40]).unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_seconds, 1);
+        assert_eq!(denied.reset_seconds, 2);
+
+        assert!(Decision::from_script(limit, &[1, 2]).is_none());
+    }
+
+    #[test]
+    fn test_decision_headers() {
+        let decision = Decision {
+            allowed: true,
+            limit: 200,
+            remaining: 150,
+            reset_seconds: 1,
+            retry_after_seconds: 1,
+        };
+        let mut headers = HeaderMap::new();
+        decision.apply(&mut headers);
+
+        assert_eq!(headers.get(RATE_LIMIT_LIMIT).unwrap(), "200");
+        assert_eq!(headers.get(RATE_LIMIT_REMAINING).unwrap(), "150");
+        assert_eq!(headers.get(RATE_LIMIT_RESET).unwrap(), "1");
+    }
+}
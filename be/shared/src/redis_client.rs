@@ -139,6 +139,14 @@ impl RedisClient {
         Ok(Self { conn })
     }
 
+    /// Returns a handle to the underlying connection manager.
+    ///
+    /// Used by components that issue commands not wrapped here, such as
+    /// the stream commands of [`crate::events`]. Cloning is cheap.
+    pub fn connection(&self) -> ConnectionManager {
+        self.conn.clone()
+    }
+
     // =========================================================================
     // Basic Operations
     // =========================================================================
@@ -254,6 +262,16 @@ impl RedisClient {
         conn.incr(key, 1).await.map_err(ApiError::RedisError)
     }
 
+    /// Adds `delta` (may be negative) to a counter (atomic operation).
+    ///
+    /// ## Returns
+    ///
+    /// The new value after the change.
+    pub async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, ApiError> {
+        let mut conn = self.conn.clone();
+        conn.incr(key, delta).await.map_err(ApiError::RedisError)
+    }
+
     /// Sets TTL on an existing key.
     ///
     /// ## Returns
@@ -277,6 +295,58 @@ impl RedisClient {
         Ok(())
     }
 
+    // =========================================================================
+    // Sliding Windows
+    // =========================================================================
+
+    /// Records an event in a sliding window and returns how many events the
+    /// last `window` now holds.
+    ///
+    /// Events are members of a sorted set scored by their time (ms), so
+    /// old events leave the window one by one instead of the whole count
+    /// resetting when a TTL runs out. The key expires once the window is
+    /// empty.
+    pub async fn record_in_window(&self, key: &str, window: Duration) -> Result<i64, ApiError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let window_ms = window.as_millis() as i64;
+        let mut conn = self.conn.clone();
+
+        let (count,): (i64,) = redis::pipe()
+            .atomic()
+            .zrembyscore(key, "-inf", now - window_ms)
+            .ignore()
+            .zadd(key, uuid::Uuid::new_v4().to_string(), now)
+            .ignore()
+            .zcard(key)
+            .pexpire(key, window_ms)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(ApiError::RedisError)?;
+
+        Ok(count)
+    }
+
+    /// Times of the events recorded in the last `window`, oldest first.
+    pub async fn events_in_window(
+        &self,
+        key: &str,
+        window: Duration,
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, ApiError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut conn = self.conn.clone();
+
+        let events: Vec<(String, f64)> = conn
+            .zrangebyscore_withscores(key, format!("({}", now - window.as_millis() as i64), "+inf")
+            .await
+            .map_err(ApiError::RedisError)?;
+
+        Ok(events
+            .into_iter()
+            .filter_map(|(_, at)| chrono::DateTime::from_timestamp_millis(at as i64))
+            .collect())
+    }
+
     // =========================================================================
     // Token Management (Authentication)
     // =========================================================================
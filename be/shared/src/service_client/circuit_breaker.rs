@@ -0,0 +1,162 @@
+//! # Circuit Breaker
+//!
+//! Stops calling a service that keeps failing, so callers fail fast instead
+//! of waiting for a timeout on every request.
+//!
+//! ```text
+//!            failures >= threshold
+//!  ┌────────┐ ───────────────────> ┌────────┐
+//!  │ Closed │                      │  Open  │ calls rejected
+//!  └────────┘ <───┐                └───┬────┘
+//!      ▲          │ success            │ cooldown elapsed
+//!      │      ┌───┴──────┐             │
+//!      └──────┤ HalfOpen │ <───────────┘
+//!   success   └──────────┘ one trial call; failure reopens
+//! ```
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// State of a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through
+    Closed,
+    /// Calls are rejected until the cooldown elapses
+    Open,
+    /// One trial call is allowed
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Circuit breaker of one downstream service.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Opens after `failure_threshold` consecutive failures and allows a
+    /// trial call after `cooldown`.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Returns true if a call may be made now. When the cooldown of an open
+    /// circuit has elapsed, this moves it to half-open and allows one call.
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.lock();
+
+        match inner.state {
+            CircuitState::Closed => true,
+            // A trial call is already in flight
+            CircuitState::HalfOpen => false,
+            CircuitState::Open => {
+                let cooled_down = inner
+                    .opened_at
+                    .is_none_or(|opened_at| opened_at.elapsed() >= self.cooldown);
+
+                if cooled_down {
+                    inner.state = CircuitState::HalfOpen;
+                }
+                cooled_down
+            }
+        }
+    }
+
+    /// Records a successful call and closes the circuit.
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    /// Records a failed call. Opens the circuit when the threshold is
+    /// reached or the half-open trial call failed.
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures += 1;
+
+        if inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.failure_threshold
+        {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.allow_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Only one trial call
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!breaker.allow_request());
+
+        // Failed trial reopens; successful trial closes
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
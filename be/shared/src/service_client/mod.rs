@@ -0,0 +1,444 @@
+//! # Internal Service Client
+//!
+//! Typed client for the authorization lookups services make about each
+//! other: "is this user enrolled in this course?" and "who teaches this
+//! course?".
+//!
+//! ## Request Path
+//!
+//! ```text
+//!  ai / content / assessments
+//!            │
+//!            ▼
+//!  ┌──────────────────┐  hit   ┌─────────────────────────┐
+//!  │  Redis cache     │ ─────> │ cached answer (30s TTL) │
+//!  └────────┬─────────┘        └─────────────────────────┘
+//!           │ miss
+//!  ┌────────▼─────────┐  open  ┌─────────────────────────┐
+//!  │ Circuit breaker  │ ─────> │ CircuitOpen (fail fast) │
+//!  └────────┬─────────┘        └─────────────────────────┘
+//!           │ closed
+//!  ┌────────▼─────────────────────────────┐
+//!  │ GET /internal/v1/...                 │  X-Service-Token, 2s timeout
+//!  │ enrollments-service / courses-service │
+//!  └──────────────────────────────────────┘
+//! ```
+//!
+//! ## Internal Endpoints
+//!
+//! | Endpoint | Response |
+//! |----------|----------|
+//! | `GET {enrollments}/internal/v1/users/{user_id}/courses/{course_id}/access` | [`EnrollmentAccess`] |
+//! | `GET {courses}/internal/v1/courses/{course_id}` | [`CourseInfo`], 404 if missing |
+//!
+//! Both require a service token ([`crate::auth::ServiceTokens`]).
+//!
+//! ## Failure Handling
+//!
+//! Errors are returned, never turned into "allowed": callers deny access
+//! (or answer 503) when a lookup fails. The cache keeps TTLs short because
+//! a revoked enrollment stays readable from it until the entry expires;
+//! denied access is not cached, so a new enrollment works immediately.
+//! Redis errors only skip the cache.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use shared::service_client::{ServiceClient, ServiceClientConfig};
+//!
+//! let tokens = Arc::new(ServiceTokens::new("ai-service", &secret)?);
+//! let client = ServiceClient::new(ServiceClientConfig::from_env(), tokens, Some(redis));
+//!
+//! if client.can_access_course(user_id, course_id).await? {
+//!     // enrolled, or teaches the course
+//! }
+//! ```
+
+pub mod circuit_breaker;
+
+pub use circuit_breaker::{CircuitBreaker, CircuitState};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::auth::{ServiceTokens, SERVICE_TOKEN_HEADER};
+use crate::errors::ApiError;
+use crate::redis_client::RedisClient;
+
+/// Name of enrollments-service in errors and logs.
+pub const ENROLLMENTS_SERVICE: &str = "enrollments-service";
+
+/// Name of courses-service in errors and logs.
+pub const COURSES_SERVICE: &str = "courses-service";
+
+// =============================================================================
+// RESPONSES
+// =============================================================================
+
+/// Whether a user may access the content of a course through an enrollment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrollmentAccess {
+    /// An active or completed, unexpired enrollment exists
+    pub has_access: bool,
+    /// Most recent enrollment, if any
+    pub enrollment_id: Option<Uuid>,
+    /// Status of that enrollment
+    pub status: Option<String>,
+}
+
+/// Course data needed for authorization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CourseInfo {
+    pub course_id: Uuid,
+    pub instructor_id: Uuid,
+    pub is_published: bool,
+}
+
+// =============================================================================
+// ERRORS
+// =============================================================================
+
+/// Internal call errors.
+#[derive(Debug, Error)]
+pub enum ServiceClientError {
+    #[error("{service} circuit is open")]
+    CircuitOpen { service: &'static str },
+
+    #[error("{service} timed out")]
+    Timeout { service: &'static str },
+
+    #[error("Request to {service} failed: {message}")]
+    Request { service: &'static str, message: String },
+
+    #[error("{service} returned {status}")]
+    Status { service: &'static str, status: u16 },
+
+    #[error("Invalid response from {service}: {message}")]
+    Decode { service: &'static str, message: String },
+
+    #[error("Service token error: {0}")]
+    Token(String),
+}
+
+impl ServiceClientError {
+    /// Service the failed call was made to.
+    pub fn service(&self) -> &'static str {
+        match self {
+            Self::CircuitOpen { service }
+            | Self::Timeout { service }
+            | Self::Request { service, .. }
+            | Self::Status { service, .. }
+            | Self::Decode { service, .. } => service,
+            Self::Token(_) => "service-tokens",
+        }
+    }
+}
+
+impl From<ServiceClientError> for ApiError {
+    fn from(err: ServiceClientError) -> Self {
+        tracing::error!(error = %err, "Internal service call failed");
+        ApiError::ServiceUnavailable {
+            service: err.service().to_string(),
+        }
+    }
+}
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// Internal client configuration.
+#[derive(Debug, Clone)]
+pub struct ServiceClientConfig {
+    pub enrollments_url: String,
+    pub courses_url: String,
+    /// Timeout of each call
+    pub timeout: Duration,
+    /// How long answers are cached in Redis
+    pub cache_ttl: Duration,
+    /// Consecutive failures that open a circuit
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls
+    pub open_duration: Duration,
+}
+
+impl Default for ServiceClientConfig {
+    fn default() -> Self {
+        Self {
+            enrollments_url: "http://localhost:8083".to_string(),
+            courses_url: "http://localhost:8082".to_string(),
+            timeout: Duration::from_secs(2),
+            cache_ttl: Duration::from_secs(30),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ServiceClientConfig {
+    /// Reads `ENROLLMENTS_SERVICE_URL`, `COURSES_SERVICE_URL`,
+    /// `INTERNAL_CLIENT_TIMEOUT_MS` and `INTERNAL_CLIENT_CACHE_TTL_SECS`,
+    /// falling back to the defaults.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            enrollments_url: std::env::var("ENROLLMENTS_SERVICE_URL")
+                .unwrap_or(defaults.enrollments_url),
+            courses_url: std::env::var("COURSES_SERVICE_URL").unwrap_or(defaults.courses_url),
+            timeout: env_u64("INTERNAL_CLIENT_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+            cache_ttl: env_u64("INTERNAL_CLIENT_CACHE_TTL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.cache_ttl),
+            ..defaults
+        }
+    }
+}
+
+// =============================================================================
+// CLIENT
+// =============================================================================
+
+/// Client for internal enrollments and courses lookups.
+#[derive(Clone)]
+pub struct ServiceClient {
+    http: reqwest::Client,
+    tokens: Arc<ServiceTokens>,
+    cache: Option<RedisClient>,
+    enrollments_url: String,
+    courses_url: String,
+    cache_ttl: Duration,
+    enrollments_breaker: Arc<CircuitBreaker>,
+    courses_breaker: Arc<CircuitBreaker>,
+}
+
+impl ServiceClient {
+    /// Creates the client. Without `cache` every lookup is a request.
+    pub fn new(
+        config: ServiceClientConfig,
+        tokens: Arc<ServiceTokens>,
+        cache: Option<RedisClient>,
+    ) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            tokens,
+            cache,
+            enrollments_url: config.enrollments_url.trim_end_matches('/').to_string(),
+            courses_url: config.courses_url.trim_end_matches('/').to_string(),
+            cache_ttl: config.cache_ttl,
+            enrollments_breaker: Arc::new(CircuitBreaker::new(
+                config.failure_threshold,
+                config.open_duration,
+            )),
+            courses_breaker: Arc::new(CircuitBreaker::new(
+                config.failure_threshold,
+                config.open_duration,
+            )),
+        }
+    }
+
+    /// Returns the enrollment access of a user to a course.
+    pub async fn enrollment_access(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+    ) -> Result<EnrollmentAccess, ServiceClientError> {
+        let cache_key = format!("svc:enrollment_access:{}:{}", user_id, course_id);
+        if let Some(access) = self.cached(&cache_key).await {
+            return Ok(access);
+        }
+
+        let url = format!(
+            "{}/internal/v1/users/{}/courses/{}/access",
+            self.enrollments_url, user_id, course_id
+        );
+        let access: EnrollmentAccess = self
+            .get(ENROLLMENTS_SERVICE, &self.enrollments_breaker, &url)
+            .await?
+            .ok_or(ServiceClientError::Status {
+                service: ENROLLMENTS_SERVICE,
+                status: 404,
+            })?;
+
+        if access.has_access {
+            self.store(&cache_key, &access).await;
+        }
+        Ok(access)
+    }
+
+    /// Returns a course, or `None` if it does not exist.
+    pub async fn course(&self, course_id: Uuid) -> Result<Option<CourseInfo>, ServiceClientError> {
+        let cache_key = format!("svc:course:{}", course_id);
+        if let Some(course) = self.cached(&cache_key).await {
+            return Ok(course);
+        }
+
+        let url = format!("{}/internal/v1/courses/{}", self.courses_url, course_id);
+        let course: Option<CourseInfo> = self.get(COURSES_SERVICE, &self.courses_breaker, &url).await?;
+
+        self.store(&cache_key, &course).await;
+        Ok(course)
+    }
+
+    /// Returns true if the user teaches the course or has enrollment access.
+    pub async fn can_access_course(
+        &self,
+        user_id: Uuid,
+        course_id: Uuid,
+    ) -> Result<bool, ServiceClientError> {
+        let is_instructor = self
+            .course(course_id)
+            .await?
+            .is_some_and(|course| course.instructor_id == user_id);
+
+        if is_instructor {
+            return Ok(true);
+        }
+
+        Ok(self.enrollment_access(user_id, course_id).await?.has_access)
+    }
+
+    /// Makes an authenticated GET through the circuit breaker.
+    /// Returns `None` on 404.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        service: &'static str,
+        breaker: &CircuitBreaker,
+        url: &str,
+    ) -> Result<Option<T>, ServiceClientError> {
+        if !breaker.allow_request() {
+            return Err(ServiceClientError::CircuitOpen { service });
+        }
+
+        let token = self
+            .tokens
+            .token()
+            .map_err(|e| ServiceClientError::Token(e.to_string()))?;
+
+        let result = self.send::<T>(service, url, &token).await;
+
+        match &result {
+            // 4xx other than 404 are caller bugs, not an unhealthy service
+            Ok(_) | Err(ServiceClientError::Status { status: 400..=499, .. }) => {
+                breaker.record_success()
+            }
+            Err(_) => breaker.record_failure(),
+        }
+
+        result
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        service: &'static str,
+        url: &str,
+        token: &str,
+    ) -> Result<Option<T>, ServiceClientError> {
+        let response = self
+            .http
+            .get(url)
+            .header(SERVICE_TOKEN_HEADER, token)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    ServiceClientError::Timeout { service }
+                } else {
+                    ServiceClientError::Request {
+                        service,
+                        message: e.to_string(),
+                    }
+                }
+            })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(ServiceClientError::Status {
+                service,
+                status: status.as_u16(),
+            });
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map(Some)
+            .map_err(|e| ServiceClientError::Decode {
+                service,
+                message: e.to_string(),
+            })
+    }
+
+    async fn cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let cache = self.cache.as_ref()?;
+
+        match cache.get::<T>(key).await {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!(key, error = %e, "Internal client cache read failed");
+                None
+            }
+        }
+    }
+
+    async fn store<T: Serialize>(&self, key: &str, value: &T) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.set(key, value, Some(self.cache_ttl)).await {
+                tracing::warn!(key, error = %e, "Internal client cache write failed");
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ServiceClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceClient")
+            .field("enrollments_url", &self.enrollments_url)
+            .field("courses_url", &self.courses_url)
+            .field("cached", &self.cache.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_map_to_service_unavailable() {
+        let err: ApiError = ServiceClientError::CircuitOpen { service: ENROLLMENTS_SERVICE }.into();
+
+        match err {
+            ApiError::ServiceUnavailable { service } => assert_eq!(service, ENROLLMENTS_SERVICE),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_client_trims_base_urls() {
+        let tokens = Arc::new(ServiceTokens::new("test", "internal_secret_minimum_32_chars_long").unwrap());
+        let config = ServiceClientConfig {
+            enrollments_url: "http://enrollments:8083/".to_string(),
+            ..ServiceClientConfig::default()
+        };
+
+        let client = ServiceClient::new(config, tokens, None);
+        assert_eq!(client.enrollments_url, "http://enrollments:8083");
+    }
+}
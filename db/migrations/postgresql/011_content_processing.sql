@@ -0,0 +1,67 @@
+-- =============================================================================
+-- ACC LMS - Content Processing Queue Migration
+-- =============================================================================
+-- Cola durable de jobs de procesamiento multimedia (transcode, thumbnails,
+-- transcripción, metadata) consumida por el worker de content-service
+-- =============================================================================
+
+SET search_path TO content, public;
+
+-- =============================================================================
+-- Tabla de jobs de procesamiento
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS content.processing_jobs (
+    job_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    asset_id UUID NOT NULL REFERENCES content.assets(asset_id) ON DELETE CASCADE,
+
+    -- Operaciones solicitadas (ProcessingOperation serializado)
+    operations JSONB NOT NULL DEFAULT '[]',
+
+    -- Estado: queued, running, completed, failed
+    status VARCHAR(20) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+
+    -- Reintentos
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 3,
+    last_error TEXT,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Lease del worker (se recupera si el worker muere)
+    locked_by VARCHAR(100),
+    locked_at TIMESTAMPTZ,
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- =============================================================================
+-- Indexes
+-- =============================================================================
+
+-- Cola: jobs listos para ejecutar
+CREATE INDEX idx_processing_jobs_queue ON content.processing_jobs(run_after)
+    WHERE status = 'queued';
+
+-- Leases vencidos
+CREATE INDEX idx_processing_jobs_running ON content.processing_jobs(locked_at)
+    WHERE status = 'running';
+
+CREATE INDEX idx_processing_jobs_asset ON content.processing_jobs(asset_id);
+
+-- Un solo job activo por asset
+CREATE UNIQUE INDEX idx_processing_jobs_active_asset ON content.processing_jobs(asset_id)
+    WHERE status IN ('queued', 'running');
+
+-- =============================================================================
+-- Triggers
+-- =============================================================================
+
+CREATE TRIGGER update_processing_jobs_updated_at
+    BEFORE UPDATE ON content.processing_jobs
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE content.processing_jobs IS 'Cola durable de procesamiento multimedia con reintentos';
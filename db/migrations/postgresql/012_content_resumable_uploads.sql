@@ -0,0 +1,14 @@
+-- =============================================================================
+-- ACC LMS - Content Resumable Uploads Migration
+-- =============================================================================
+-- Offset de uploads reanudables (protocolo estilo tus) por asset
+-- =============================================================================
+
+SET search_path TO content, public;
+
+ALTER TABLE content.assets
+    ADD COLUMN IF NOT EXISTS upload_offset BIGINT NOT NULL DEFAULT 0
+        CHECK (upload_offset >= 0);
+
+COMMENT ON COLUMN content.assets.upload_offset IS
+    'Bytes recibidos de un upload reanudable en curso (Upload-Offset)';
@@ -0,0 +1,50 @@
+-- =============================================================================
+-- ACC LMS - AI Content Index Migration
+-- =============================================================================
+-- Embeddings por curso/lección/chunk (ingesta de ai-service) y hashes del
+-- contenido indexado por lección para re-indexación incremental
+-- =============================================================================
+
+SET search_path TO ai, public;
+
+-- =============================================================================
+-- Embeddings por chunk (columnas usadas por EmbeddingRepository)
+-- =============================================================================
+ALTER TABLE ai.content_embeddings
+    ADD COLUMN IF NOT EXISTS course_id UUID,
+    ADD COLUMN IF NOT EXISTS lesson_id UUID,
+    ADD COLUMN IF NOT EXISTS chunk_index INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS content_text TEXT,
+    ALTER COLUMN content_id DROP NOT NULL,
+    ALTER COLUMN text_content DROP NOT NULL;
+
+-- content_type ahora es lesson_text, transcription, description o resource
+ALTER TABLE ai.content_embeddings
+    DROP CONSTRAINT IF EXISTS content_embeddings_content_type_check;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ai_embeddings_chunk
+    ON ai.content_embeddings(course_id, lesson_id, chunk_index);
+
+CREATE INDEX IF NOT EXISTS idx_ai_embeddings_course_id
+    ON ai.content_embeddings(course_id);
+
+-- =============================================================================
+-- Estado de indexación por lección
+-- =============================================================================
+-- source_key: lesson_id, o 'course' para descripción del curso + artículos KB
+CREATE TABLE IF NOT EXISTS ai.content_index_state (
+    course_id UUID NOT NULL,
+    source_key TEXT NOT NULL,
+    lesson_id UUID,
+
+    -- SHA-256 del contenido indexado (texto de lección + transcripciones)
+    content_hash VARCHAR(64) NOT NULL,
+    chunk_count INTEGER NOT NULL DEFAULT 0,
+
+    indexed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (course_id, source_key)
+);
+
+COMMENT ON TABLE ai.content_index_state IS
+    'Hash del contenido indexado por lección; solo se re-embeben las lecciones cuyo hash cambia';
@@ -0,0 +1,36 @@
+-- =============================================================================
+-- ACC LMS - AI Usage Metering Migration
+-- =============================================================================
+-- Registro de tokens consumidos por llamada LLM y por funcionalidad.
+-- Los contadores de cuota (diarios/mensuales) viven en Redis; esta tabla
+-- es el histórico para facturación y analítica.
+--
+-- Las cuotas por plan se pueden sobrescribir en subscriptions.plans.limits:
+--   {"ai_quotas": {"tutor_chat": {"daily": 80, "monthly": 1200}}}
+-- =============================================================================
+
+SET search_path TO ai, public;
+
+CREATE TABLE IF NOT EXISTS ai.usage_records (
+    usage_id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+
+    -- tutor_chat, semantic_search, summary_generation, quiz_generation,
+    -- transcription_generation
+    feature VARCHAR(50) NOT NULL,
+
+    tokens_input INTEGER NOT NULL DEFAULT 0,
+    tokens_output INTEGER NOT NULL DEFAULT 0,
+    cost_cents INTEGER,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_usage_user_feature
+    ON ai.usage_records(user_id, feature, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ai_usage_created_at
+    ON ai.usage_records(created_at);
+
+COMMENT ON TABLE ai.usage_records IS
+    'Uso de IA por llamada LLM (tokens por funcionalidad) para facturación';
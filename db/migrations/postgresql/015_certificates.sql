@@ -0,0 +1,64 @@
+-- =============================================================================
+-- ACC LMS - Certificates Migration
+-- =============================================================================
+-- Certificados emitidos al completar un curso y plantillas de diseño.
+--
+-- layout describe la página (tamaño en mm), los bloques de texto con
+-- placeholders ({student_name}, {course_title}, {instructor_name},
+-- {completion_date}, {verification_code}, {verification_url}), la posición
+-- del logo, del código QR de verificación y de las firmas. Un layout vacío
+-- ('{}') usa el diseño por defecto (A4 horizontal).
+--
+-- pdf_url apunta al PDF renderizado en el almacenamiento; las descargas lo
+-- sirven sin volver a generarlo.
+-- =============================================================================
+
+SET search_path TO public;
+
+CREATE TABLE IF NOT EXISTS certificate_templates (
+    template_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    course_id UUID,
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    background_url TEXT,
+    logo_url TEXT,
+    primary_color VARCHAR(20) NOT NULL DEFAULT '#1a365d',
+    secondary_color VARCHAR(20) NOT NULL DEFAULT '#c69c6d',
+    font_family VARCHAR(100) NOT NULL DEFAULT 'serif',
+    layout JSONB NOT NULL DEFAULT '{}',
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Instalaciones con la tabla previa a los layouts
+ALTER TABLE certificate_templates
+    ADD COLUMN IF NOT EXISTS layout JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_certificate_templates_course
+    ON certificate_templates(course_id) WHERE is_active = true;
+
+CREATE TABLE IF NOT EXISTS certificates (
+    certificate_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    course_id UUID NOT NULL,
+    verification_code VARCHAR(50) NOT NULL UNIQUE,
+    student_name VARCHAR(200) NOT NULL,
+    course_title VARCHAR(300) NOT NULL,
+    instructor_name VARCHAR(200) NOT NULL,
+    completion_date TIMESTAMPTZ NOT NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    pdf_url TEXT,
+
+    -- active, revoked, expired
+    status VARCHAR(20) NOT NULL DEFAULT 'active',
+
+    template_id UUID REFERENCES certificate_templates(template_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_certificates_user_course
+    ON certificates(user_id, course_id);
@@ -0,0 +1,28 @@
+-- =============================================================================
+-- ACC LMS - Certificate Credentials Migration
+-- =============================================================================
+-- Cada certificado se emite también como credencial verificable Open Badges
+-- 3.0 (W3C VC 2.0) firmada con Ed25519 (prueba eddsa-jcs-2022), que se puede
+-- verificar sin conexión con las claves públicas del emisor.
+--
+-- La revocación se publica en listas de estado (Bitstring Status List) de
+-- 131072 entradas: la lista N contiene los índices
+-- [N * 131072, (N + 1) * 131072).
+-- =============================================================================
+
+SET search_path TO public;
+
+CREATE SEQUENCE IF NOT EXISTS certificate_status_list_index_seq START WITH 0 MINVALUE 0;
+
+ALTER TABLE certificates
+    ADD COLUMN IF NOT EXISTS status_list_index BIGINT NOT NULL
+        DEFAULT nextval('certificate_status_list_index_seq');
+
+ALTER TABLE certificates
+    ADD COLUMN IF NOT EXISTS credential JSONB;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_certificates_status_list_index
+    ON certificates(status_list_index);
+
+CREATE INDEX IF NOT EXISTS idx_certificates_revoked_status
+    ON certificates(status_list_index) WHERE status = 'revoked';
@@ -0,0 +1,18 @@
+-- =============================================================================
+-- ACC LMS - Code Question Grading Migration
+-- =============================================================================
+-- Las preguntas de tipo 'code' pueden llevar casos de prueba ocultos. Las
+-- respuestas se compilan y ejecutan en un sandbox local de assessments-service
+-- y el resultado de cada caso se guarda en la respuesta (crédito parcial
+-- ponderado por el peso de cada caso).
+-- =============================================================================
+
+SET search_path TO assessments, public;
+
+-- Casos de prueba: [{"name", "input", "expected_output", "weight"}, ...]
+ALTER TABLE assessments.quiz_questions
+    ADD COLUMN IF NOT EXISTS test_cases JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+-- Resultados por caso: [{"name", "status", "weight", "time_ms", "message"}, ...]
+ALTER TABLE assessments.quiz_responses
+    ADD COLUMN IF NOT EXISTS test_results JSONB;
@@ -0,0 +1,18 @@
+-- =============================================================================
+-- ACC LMS - Question Scoring Policies Migration
+-- =============================================================================
+-- Política de calificación por pregunta: crédito parcial en opción múltiple,
+-- penalización por respuestas incorrectas, respuestas numéricas con
+-- tolerancia/unidades y respuestas cortas por regex o coincidencia aproximada.
+-- Un objeto vacío equivale a todo-o-nada (comportamiento anterior).
+--
+-- Ejemplo:
+--   {"partial_credit": true, "wrong_penalty": 0.25,
+--    "text_match": {"mode": "fuzzy", "min_similarity": 0.9},
+--    "numeric": {"tolerance": 0.01, "relative": true, "units": ["m/s"]}}
+-- =============================================================================
+
+SET search_path TO assessments, public;
+
+ALTER TABLE assessments.quiz_questions
+    ADD COLUMN IF NOT EXISTS scoring_policy JSONB NOT NULL DEFAULT '{}'::jsonb;
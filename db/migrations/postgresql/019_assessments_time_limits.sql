@@ -0,0 +1,46 @@
+-- =============================================================================
+-- ACC LMS - Quiz Time Limits & Accommodations Migration
+-- =============================================================================
+-- El servidor calcula la fecha límite de cada intento al iniciarlo
+-- (started_at + time_limit_minutes, ajustado por adaptaciones del estudiante),
+-- rechaza respuestas tardías y un proceso en segundo plano envía
+-- automáticamente los intentos vencidos.
+--
+-- Las adaptaciones (tiempo extra, intentos extra) se definen por estudiante
+-- para todo un curso (quiz_id NULL) o para un quiz concreto, que prevalece.
+-- =============================================================================
+
+SET search_path TO assessments, public;
+
+-- Fecha límite del intento (NULL = sin límite de tiempo)
+ALTER TABLE assessments.quiz_submissions
+    ADD COLUMN IF NOT EXISTS deadline_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS auto_submitted BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Búsqueda de intentos vencidos por el proceso de barrido
+CREATE INDEX IF NOT EXISTS idx_assessments_submissions_deadline
+    ON assessments.quiz_submissions(deadline_at)
+    WHERE status = 'in_progress' AND deadline_at IS NOT NULL;
+
+-- Adaptaciones por estudiante
+CREATE TABLE IF NOT EXISTS assessments.quiz_accommodations (
+    accommodation_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    course_id UUID NOT NULL, -- References courses.courses(course_id)
+    quiz_id UUID REFERENCES assessments.quizzes(quiz_id) ON DELETE CASCADE,
+    user_id UUID NOT NULL, -- References auth.users(user_id)
+    time_multiplier DECIMAL(4,2) NOT NULL DEFAULT 1.00 CHECK (time_multiplier >= 1.00),
+    extra_time_minutes INTEGER NOT NULL DEFAULT 0 CHECK (extra_time_minutes >= 0),
+    extra_attempts INTEGER NOT NULL DEFAULT 0 CHECK (extra_attempts >= 0),
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Una adaptación por estudiante y alcance (curso completo o quiz)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_assessments_accommodations_scope
+    ON assessments.quiz_accommodations(
+        user_id, course_id, COALESCE(quiz_id, '00000000-0000-0000-0000-000000000000'::uuid)
+    );
+
+CREATE INDEX IF NOT EXISTS idx_assessments_accommodations_course
+    ON assessments.quiz_accommodations(course_id);
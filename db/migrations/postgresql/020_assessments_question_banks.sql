@@ -0,0 +1,107 @@
+-- =============================================================================
+-- ACC LMS - Question Banks & Randomized Quizzes Migration
+-- =============================================================================
+-- Bancos de preguntas reutilizables por curso, etiquetados por tema y
+-- dificultad. Cada edición del contenido de una pregunta crea una versión
+-- nueva e inmutable.
+--
+-- Un quiz puede combinar preguntas fijas con reglas de sorteo ("5 preguntas
+-- aleatorias de dificultad media del banco X"). Al iniciar un intento se
+-- ensambla la lista de preguntas y se guarda en submission_questions, con la
+-- versión exacta recibida y el orden de opciones mostrado, para que la
+-- calificación y las estadísticas no dependan de ediciones posteriores.
+-- =============================================================================
+
+SET search_path TO assessments, public;
+
+-- Bancos de preguntas
+CREATE TABLE IF NOT EXISTS assessments.question_banks (
+    bank_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    course_id UUID NOT NULL, -- References courses.courses(course_id)
+    title TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assessments_question_banks_course
+    ON assessments.question_banks(course_id);
+
+-- Preguntas del banco (metadatos; el contenido vive en las versiones)
+CREATE TABLE IF NOT EXISTS assessments.bank_questions (
+    bank_question_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    bank_id UUID NOT NULL REFERENCES assessments.question_banks(bank_id) ON DELETE CASCADE,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    difficulty TEXT NOT NULL DEFAULT 'medium' CHECK (difficulty IN ('easy', 'medium', 'hard')),
+    current_version INTEGER NOT NULL DEFAULT 1,
+    is_archived BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assessments_bank_questions_bank
+    ON assessments.bank_questions(bank_id, difficulty) WHERE NOT is_archived;
+CREATE INDEX IF NOT EXISTS idx_assessments_bank_questions_tags
+    ON assessments.bank_questions USING GIN (tags);
+
+-- Versiones inmutables del contenido
+CREATE TABLE IF NOT EXISTS assessments.bank_question_versions (
+    version_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    bank_question_id UUID NOT NULL REFERENCES assessments.bank_questions(bank_question_id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    question_type TEXT NOT NULL CHECK (question_type IN ('single_choice', 'multiple_choice', 'true_false', 'short_answer', 'essay', 'code')),
+    question_text TEXT NOT NULL,
+    options JSONB NOT NULL DEFAULT '[]'::jsonb,
+    correct_answers JSONB NOT NULL DEFAULT '[]'::jsonb,
+    points INTEGER NOT NULL DEFAULT 1,
+    explanation TEXT,
+    code_language TEXT,
+    test_cases JSONB NOT NULL DEFAULT '[]'::jsonb,
+    scoring_policy JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (bank_question_id, version)
+);
+
+-- Reglas de sorteo de un quiz
+CREATE TABLE IF NOT EXISTS assessments.quiz_draw_rules (
+    rule_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    quiz_id UUID NOT NULL REFERENCES assessments.quizzes(quiz_id) ON DELETE CASCADE,
+    bank_id UUID NOT NULL REFERENCES assessments.question_banks(bank_id),
+    question_count INTEGER NOT NULL CHECK (question_count > 0),
+    difficulty TEXT CHECK (difficulty IN ('easy', 'medium', 'hard')),
+    tags TEXT[] NOT NULL DEFAULT '{}', -- Cualquiera de las etiquetas
+    points_per_question INTEGER NOT NULL CHECK (points_per_question > 0),
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assessments_draw_rules_quiz
+    ON assessments.quiz_draw_rules(quiz_id);
+
+-- Orden aleatorio de opciones por intento
+ALTER TABLE assessments.quizzes
+    ADD COLUMN IF NOT EXISTS shuffle_options BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Preguntas recibidas en cada intento
+CREATE TABLE IF NOT EXISTS assessments.submission_questions (
+    submission_id UUID NOT NULL REFERENCES assessments.quiz_submissions(submission_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    -- Identificador usado en quiz_responses.question_id
+    question_id UUID NOT NULL,
+    quiz_question_id UUID REFERENCES assessments.quiz_questions(question_id),
+    version_id UUID REFERENCES assessments.bank_question_versions(version_id),
+    rule_id UUID REFERENCES assessments.quiz_draw_rules(rule_id) ON DELETE SET NULL,
+    points INTEGER NOT NULL,
+    option_order JSONB, -- Índices de `options` en el orden mostrado
+    PRIMARY KEY (submission_id, position),
+    UNIQUE (submission_id, question_id),
+    CHECK (num_nonnulls(quiz_question_id, version_id) = 1),
+    CHECK (question_id = COALESCE(quiz_question_id, version_id))
+);
+
+CREATE INDEX IF NOT EXISTS idx_assessments_submission_questions_question
+    ON assessments.submission_questions(question_id);
+
+-- Las respuestas pueden referirse a preguntas fijas o a versiones del banco
+ALTER TABLE assessments.quiz_responses
+    DROP CONSTRAINT IF EXISTS quiz_responses_question_id_fkey;
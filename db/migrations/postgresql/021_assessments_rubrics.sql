@@ -0,0 +1,27 @@
+-- =============================================================================
+-- ACC LMS - Essay Rubrics Migration
+-- =============================================================================
+-- Rúbricas para preguntas de ensayo: criterios con niveles de desempeño y
+-- puntos. El puntaje de la rúbrica se escala a los puntos de la pregunta.
+--
+-- Al calificar, el desglose elegido (criterio, nivel, puntos y comentario)
+-- se guarda en la respuesta, de modo que editar la rúbrica no altera las
+-- calificaciones ya entregadas.
+--
+-- Ejemplo de rúbrica:
+--   {"criteria": [{"id": "thesis", "title": "Tesis",
+--                  "levels": [{"id": "weak", "title": "Débil", "points": 1},
+--                             {"id": "strong", "title": "Sólida", "points": 4}]}]}
+-- =============================================================================
+
+SET search_path TO assessments, public;
+
+ALTER TABLE assessments.quiz_questions
+    ADD COLUMN IF NOT EXISTS rubric JSONB;
+
+ALTER TABLE assessments.bank_question_versions
+    ADD COLUMN IF NOT EXISTS rubric JSONB;
+
+-- Desglose por criterio de respuestas calificadas con rúbrica
+ALTER TABLE assessments.quiz_responses
+    ADD COLUMN IF NOT EXISTS rubric_scores JSONB;
@@ -0,0 +1,43 @@
+-- =============================================================================
+-- ACC LMS - Transactional Outbox Migration
+-- =============================================================================
+-- Tabla outbox por servicio. Los eventos de dominio se insertan en la misma
+-- transacción que el cambio de estado que los origina; un relay los publica
+-- después en Redis Streams (`events:{aggregate_type}`) y marca published_at.
+--
+-- Si la transacción hace rollback el evento no existe; si Redis no está
+-- disponible el evento queda pendiente y se reintenta. Los consumidores
+-- deben ser idempotentes usando event_id (entrega al-menos-una-vez).
+-- =============================================================================
+
+DO $$
+DECLARE
+    svc TEXT;
+BEGIN
+    FOREACH svc IN ARRAY ARRAY['auth', 'users', 'courses', 'enrollments', 'payments']
+    LOOP
+        EXECUTE format(
+            'CREATE TABLE IF NOT EXISTS %I.outbox_events (
+                event_id UUID PRIMARY KEY,
+                aggregate_type TEXT NOT NULL,
+                aggregate_id UUID NOT NULL,
+                event_type TEXT NOT NULL,
+                payload JSONB NOT NULL,
+                occurred_at TIMESTAMPTZ NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                published_at TIMESTAMPTZ,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT
+            )',
+            svc
+        );
+
+        -- Eventos pendientes en orden de creación
+        EXECUTE format(
+            'CREATE INDEX IF NOT EXISTS idx_%s_outbox_events_pending
+                ON %I.outbox_events(created_at)
+                WHERE published_at IS NULL',
+            svc, svc
+        );
+    END LOOP;
+END $$;
@@ -0,0 +1,47 @@
+-- =============================================================================
+-- ACC LMS - Course Completion Rules Migration
+-- =============================================================================
+-- Reglas de finalización por curso, evaluadas por enrollments-service:
+--   require_all_lessons  todas las lecciones del curso completadas
+--   min_quiz_average     promedio mínimo (0-100) de los cuestionarios
+--                        publicados del curso; cuenta el mejor intento
+--                        calificado y 0 en los no presentados
+--   final_assessment_id  cuestionario final que debe estar aprobado
+--
+-- Un curso sin fila usa la regla anterior (solo todas las lecciones).
+--
+-- Al cumplirse las reglas la inscripción pasa a 'completed' y se emite
+-- enrollment.completed; el consumidor de ese evento emite el certificado en
+-- certificates-service con los nombres del estudiante, curso e instructor.
+-- =============================================================================
+
+SET search_path TO enrollments, public;
+
+CREATE TABLE IF NOT EXISTS enrollments.course_completion_rules (
+    course_id UUID PRIMARY KEY, -- References courses.courses(course_id)
+    require_all_lessons BOOLEAN NOT NULL DEFAULT TRUE,
+    min_quiz_average DECIMAL(5,2) CHECK (min_quiz_average BETWEEN 0 AND 100),
+    final_assessment_id UUID, -- References assessments.quizzes(quiz_id)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+DROP TRIGGER IF EXISTS enrollments_completion_rules_updated_at
+    ON enrollments.course_completion_rules;
+CREATE TRIGGER enrollments_completion_rules_updated_at
+    BEFORE UPDATE ON enrollments.course_completion_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Lectura de calificaciones (promedio y cuestionario final) y de nombres
+-- para el certificado
+GRANT USAGE ON SCHEMA assessments TO enrollments_svc;
+GRANT SELECT ON assessments.quizzes, assessments.quiz_submissions TO enrollments_svc;
+
+GRANT USAGE ON SCHEMA users TO enrollments_svc;
+GRANT SELECT ON users.profiles TO enrollments_svc;
+
+-- Un solo certificado activo por estudiante y curso, para que reintentos
+-- concurrentes de la emisión no creen duplicados
+CREATE UNIQUE INDEX IF NOT EXISTS uq_certificates_user_course_active
+    ON public.certificates(user_id, course_id)
+    WHERE status = 'active';
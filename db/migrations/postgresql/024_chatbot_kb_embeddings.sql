@@ -0,0 +1,27 @@
+-- =============================================================================
+-- ACC LMS - Chatbot KB Embeddings Migration
+-- =============================================================================
+-- Embeddings de los artículos de la base de conocimiento, usados por el
+-- chatbot para recuperar artículos por similitud antes de responder.
+--
+-- chatbot-service indexa en segundo plano los artículos publicados cuyo
+-- contenido (content_hash = md5 de título, resumen y contenido) o modelo de
+-- embeddings cambió. Los vectores solo se comparan con consultas embebidas
+-- con el mismo modelo, por eso la dimensión no es fija y no hay índice HNSW.
+--
+-- Requiere la extensión vector (004_ai_service.sql).
+-- =============================================================================
+
+SET search_path TO chatbot, public;
+
+CREATE TABLE IF NOT EXISTS chatbot.kb_article_embeddings (
+    article_id UUID PRIMARY KEY
+        REFERENCES chatbot.kb_articles(article_id) ON DELETE CASCADE,
+    model VARCHAR(100) NOT NULL,
+    content_hash CHAR(32) NOT NULL,
+    embedding vector NOT NULL,
+    indexed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_kb_article_embeddings_model
+    ON chatbot.kb_article_embeddings(model);
@@ -0,0 +1,47 @@
+-- =============================================================================
+-- ACC LMS - Auth Multi-Factor Authentication Migration
+-- =============================================================================
+-- Segundo factor para el login de auth-service: TOTP (RFC 6238), llaves
+-- WebAuthn/passkeys y códigos de recuperación de un solo uso.
+--
+-- El secreto TOTP se guarda cifrado con AES-256-GCM (MFA_ENCRYPTION_KEY,
+-- nonce de 12 bytes al inicio). last_used_step impide reutilizar un código
+-- dentro de su ventana de validez. Las llaves WebAuthn guardan la clave
+-- pública COSE y el contador de firmas para detectar autenticadores
+-- clonados. Los códigos de recuperación se guardan como SHA-256.
+-- =============================================================================
+
+SET search_path TO auth, public;
+
+CREATE TABLE IF NOT EXISTS auth.mfa_totp (
+    user_id UUID PRIMARY KEY REFERENCES auth.users(user_id) ON DELETE CASCADE,
+    secret_encrypted BYTEA NOT NULL,
+    -- NULL hasta que el usuario confirma el primer código
+    confirmed_at TIMESTAMPTZ,
+    last_used_step BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS auth.webauthn_credentials (
+    credential_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES auth.users(user_id) ON DELETE CASCADE,
+    raw_id BYTEA NOT NULL UNIQUE,
+    public_key BYTEA NOT NULL,
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    name VARCHAR(100) NOT NULL,
+    transports TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_webauthn_credentials_user_id
+    ON auth.webauthn_credentials(user_id);
+
+CREATE TABLE IF NOT EXISTS auth.mfa_recovery_codes (
+    code_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES auth.users(user_id) ON DELETE CASCADE,
+    code_hash CHAR(64) NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, code_hash)
+);
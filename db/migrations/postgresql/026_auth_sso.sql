@@ -0,0 +1,69 @@
+-- =============================================================================
+-- ACC LMS - Auth Single Sign-On (OpenID Connect) Migration
+-- =============================================================================
+-- Inicio de sesión federado con proveedores OIDC (Google, Microsoft Entra ID
+-- o el IdP propio de cada universidad) mediante authorization code + PKCE.
+--
+-- identity_providers: configuración por tenant. El secreto del cliente no se
+-- guarda en la base de datos: client_secret_env indica la variable de entorno
+-- que lo contiene (NULL para clientes públicos que solo usan PKCE).
+-- allowed_domains restringe los correos aceptados (vacío = cualquiera);
+-- jit_provisioning permite crear la cuenta en el primer inicio de sesión con
+-- default_role.
+--
+-- user_identities: vínculo entre un usuario y su identidad (sub) en el IdP.
+--
+-- Ejemplos:
+--   INSERT INTO auth.identity_providers
+--       (slug, display_name, issuer, client_id, client_secret_env)
+--   VALUES ('google', 'Google', 'https://accounts.google.com',
+--           '<client-id>.apps.googleusercontent.com', 'SSO_GOOGLE_CLIENT_SECRET');
+--
+--   INSERT INTO auth.identity_providers
+--       (slug, tenant_id, display_name, issuer, client_id, client_secret_env,
+--        allowed_domains, default_role)
+--   VALUES ('uni-microsoft', '<tenant-uuid>', 'Microsoft',
+--           'https://login.microsoftonline.com/<directory-id>/v2.0',
+--           '<application-id>', 'SSO_UNI_MICROSOFT_CLIENT_SECRET',
+--           ARRAY['uni.edu'], 'student');
+-- =============================================================================
+
+SET search_path TO auth, public;
+
+CREATE TABLE IF NOT EXISTS auth.identity_providers (
+    provider_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug VARCHAR(50) NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9-]+$'),
+    -- NULL = disponible para todos los tenants
+    tenant_id UUID,
+    display_name VARCHAR(100) NOT NULL,
+    issuer TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    client_secret_env VARCHAR(100),
+    scopes TEXT NOT NULL DEFAULT 'openid email profile',
+    allowed_domains TEXT[] NOT NULL DEFAULT '{}',
+    jit_provisioning BOOLEAN NOT NULL DEFAULT TRUE,
+    default_role TEXT NOT NULL DEFAULT 'student' CHECK (default_role IN ('student', 'instructor')),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_identity_providers_tenant_id
+    ON auth.identity_providers(tenant_id) WHERE enabled;
+
+CREATE TABLE IF NOT EXISTS auth.user_identities (
+    identity_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES auth.users(user_id) ON DELETE CASCADE,
+    provider_id UUID NOT NULL REFERENCES auth.identity_providers(provider_id) ON DELETE CASCADE,
+    -- Claim "sub" del ID token
+    subject TEXT NOT NULL,
+    email TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ,
+    UNIQUE (provider_id, subject),
+    -- Una identidad por proveedor y usuario
+    UNIQUE (provider_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_user_identities_user_id
+    ON auth.user_identities(user_id);
@@ -0,0 +1,37 @@
+-- =============================================================================
+-- ACC LMS - Auth Sessions Migration
+-- =============================================================================
+-- Gestión de sesiones y dispositivos sobre auth.refresh_tokens.
+--
+-- Cada login abre una sesión (family_id); al rotar el refresh token la fila
+-- anterior se revoca con revoked_reason = 'rotated' y la nueva hereda el
+-- family_id. Si un token rotado vuelve a presentarse, alguien tiene una copia
+-- y se revoca toda la familia ('reuse_detected').
+--
+-- location es una ubicación aproximada ("Bogotá, CO") que aporta el proxy de
+-- borde. ip_address pasa a TEXT y las fechas a TIMESTAMPTZ, como los maneja la
+-- entidad RefreshToken.
+-- =============================================================================
+
+SET search_path TO auth, public;
+
+ALTER TABLE auth.refresh_tokens
+    ALTER COLUMN ip_address TYPE TEXT USING host(ip_address),
+    ALTER COLUMN expires_at TYPE TIMESTAMPTZ,
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ,
+    ALTER COLUMN revoked_at TYPE TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS family_id UUID,
+    ADD COLUMN IF NOT EXISTS location TEXT,
+    ADD COLUMN IF NOT EXISTS revoked_reason VARCHAR(20)
+        CHECK (revoked_reason IN ('rotated', 'logout', 'revoked', 'reuse_detected'));
+
+-- Los tokens existentes forman cada uno su propia sesión
+UPDATE auth.refresh_tokens SET family_id = token_id WHERE family_id IS NULL;
+
+ALTER TABLE auth.refresh_tokens ALTER COLUMN family_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_auth_refresh_tokens_family_id
+    ON auth.refresh_tokens(family_id);
+
+CREATE INDEX IF NOT EXISTS idx_auth_refresh_tokens_token_hash
+    ON auth.refresh_tokens(token_hash);